@@ -3,24 +3,49 @@
 //! This module coordinates the actions of all trading agents to make final trading decisions.
 
 use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Arc;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use anyhow::Result;
+use tokio::sync::RwLock;
 use tracing::{info, debug, error, warn};
 
-use crate::engine::message_bus::TradeDirection;
+use crate::engine::agent_runtime::{AgentRuntime, AgentRuntimeConfig};
+use crate::engine::message_log::MessageRecorder;
+use crate::engine::supervisor::{AgentFactory, AgentSupervisor, EscalationPolicy, RestartPolicy, SupervisorConfig, SupervisorEvent};
+use crate::monitoring::unified_error_manager::UnifiedErrorManager;
+use crate::engine::agent_trait::{Agent, AgentContext};
+use crate::engine::message_bus::{BusMessage, TradeDirection};
 use crate::exchange::bybit::adapter::BybitAdapter;
 use crate::strategy::simple_strategy::Candle;
 use crate::agents::market_analyzer::{MarketAnalyzer, MarketAnalysis};
-use crate::agents::sentiment_analyzer::{SentimentAnalyzer, SentimentAnalysis};
+use crate::agents::sentiment_analyzer::{SentimentAnalyzer, SentimentAnalyzerConfig, SentimentAnalysis};
+use crate::agents::news_agent::NewsItem;
+use crate::agents::onchain_agent::OnChainFeatures;
+use crate::agents::funding_agent::{CrowdedSide, FundingSnapshot};
+use crate::agents::order_flow_agent::OrderFlowFeatures;
 use crate::agents::risk_manager::{RiskManager, RiskAssessment};
 use crate::agents::trade_executor::{TradeExecutor, TradeExecution, ExecutionStatus};
 use crate::agents::zero_loss_enforcer::{ZeroLossEnforcer, ZeroLossAssessment};
+use crate::agents::consensus::{AgentVote, ConsensusConfig, ConsensusOutcome, ConsensusVoting, Veto, VoteDirection};
+use crate::agents::feedback_loop::FeedbackLoop;
 use crate::agents::quantum_predictor::{QuantumPredictor, QuantumPrediction};
 use crate::agents::hyperdimensional_pattern_recognizer::{HyperdimensionalPatternRecognizer, PatternRecognition, PatternType};
 use crate::quantum::spectral_tree_engine::SpectralTreeEngine;
 use crate::quantum::hyperdimensional_computing::HyperdimensionalComputing;
-use crate::strategy::advanced_multi_factor_strategy::{AdvancedMultiFactorStrategy, StrategyConfig, MultiFactorAnalysis};
+use crate::strategy::advanced_multi_factor_strategy::{AdvancedMultiFactorStrategy, MultiFactorAnalysis, TradingAction};
+use crate::deployment::config_manager::{load_multi_factor_config, STRATEGY_DIRECTORY};
+use crate::execution::order_manager::lock_order_manager;
+
+/// Filled orders per symbol before observed slippage is used for sizing
+const MIN_SLIPPAGE_SAMPLES: usize = 5;
+
+/// Strategy the coordinator's consensus trades are attributed to
+pub const CONSENSUS_STRATEGY: &str = "consensus";
+
+/// Confidence of the funding agent's vote against a crowded side
+const CROWDED_VOTE_CONFIDENCE: f64 = 60.0;
 
 /// Trading decision with superintelligent analysis
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -69,6 +94,14 @@ pub struct TradingDecision {
 
     /// Superintelligence score (0-100)
     pub superintelligence_score: f64,
+
+    /// Agent that produced the decision, if it trades on its own capital budget
+    #[serde(default)]
+    pub agent: Option<String>,
+
+    /// Votes and vetoes the decision was reached by
+    #[serde(default)]
+    pub consensus: Option<ConsensusOutcome>,
 }
 
 /// Decision type
@@ -96,6 +129,26 @@ pub enum DecisionType {
     InsufficientData,
 }
 
+/// Capital a trading agent may commit to open positions
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AgentCapitalBudget {
+    /// Agent name
+    pub agent: String,
+
+    /// Capital granted
+    pub budget: f64,
+
+    /// Capital committed to open positions
+    pub committed: f64,
+}
+
+impl AgentCapitalBudget {
+    /// Capital still available for new positions
+    pub fn available(&self) -> f64 {
+        (self.budget - self.committed).max(0.0)
+    }
+}
+
 /// Superintelligent Agent Coordinator
 pub struct AgentCoordinator {
     /// Market analyzer
@@ -151,6 +204,24 @@ pub struct AgentCoordinator {
 
     /// Hyperdimensional projection factor
     hyperdimensional_factor: f64,
+
+    /// Capital budgets by agent; agents without one are not limited
+    agent_budgets: HashMap<String, AgentCapitalBudget>,
+
+    /// Message-driven agents, each running in its own task
+    agent_runtime: AgentRuntime,
+
+    /// Restarts failed agents and halts the system when they keep failing
+    supervisor: AgentSupervisor,
+
+    /// Records the messages routed to the agents for replay
+    message_recorder: Option<MessageRecorder>,
+
+    /// Latest funding and basis by symbol, the funding-harvest strategy's inputs
+    funding: HashMap<String, FundingSnapshot>,
+
+    /// Weighted vote the analysis agents decide by
+    consensus: ConsensusVoting,
 }
 
 impl AgentCoordinator {
@@ -158,17 +229,25 @@ impl AgentCoordinator {
     pub fn new(total_capital: f64) -> Self {
         info!("Initializing SUPERINTELLIGENT Agent Coordinator with ${:.2} capital", total_capital);
 
+        // Entries stop as soon as the risk manager halts the system
+        let risk_manager = RiskManager::new(total_capital);
+        let trade_executor = TradeExecutor::new();
+        if let Ok(mut order_manager) = lock_order_manager(&trade_executor.get_order_manager()) {
+            order_manager.set_state_machine(risk_manager.get_state_machine());
+        }
+        let supervisor = AgentSupervisor::new(SupervisorConfig::default(), risk_manager.get_state_machine());
+
         Self {
             market_analyzer: MarketAnalyzer::new(),
             sentiment_analyzer: SentimentAnalyzer::new(),
-            risk_manager: RiskManager::new(total_capital),
-            trade_executor: TradeExecutor::new(),
+            risk_manager,
+            trade_executor,
             zero_loss_enforcer: ZeroLossEnforcer::new(),
             quantum_predictor: QuantumPredictor::new(),
             pattern_recognizer: HyperdimensionalPatternRecognizer::new(),
             spectral_engine: SpectralTreeEngine::new(),
             hyperdimensional_engine: HyperdimensionalComputing::new(),
-            multi_factor_strategy: AdvancedMultiFactorStrategy::new(StrategyConfig::default()).unwrap(),
+            multi_factor_strategy: AdvancedMultiFactorStrategy::new(load_multi_factor_config(Path::new(STRATEGY_DIRECTORY))).unwrap(),
             decision_cache: HashMap::new(),
             min_confidence: 90.0, // Minimum 90% confidence for exceptional trades
             min_opportunity_score: 90.0, // Minimum 90% opportunity score for exceptional trades
@@ -177,6 +256,12 @@ impl AgentCoordinator {
             superintelligence_level: 10, // Maximum superintelligence
             quantum_entanglement_factor: 0.618, // Golden ratio for quantum entanglement
             hyperdimensional_factor: 1.618, // Golden ratio for hyperdimensional projection
+            agent_budgets: HashMap::new(),
+            agent_runtime: AgentRuntime::new(AgentRuntimeConfig::default()),
+            supervisor,
+            message_recorder: None,
+            funding: HashMap::new(),
+            consensus: ConsensusVoting::new(ConsensusConfig::default()),
         }
     }
 
@@ -202,7 +287,8 @@ impl AgentCoordinator {
             }
         };
 
-        // Step 2: Sentiment Analysis with Superintelligence
+        // Step 2: Sentiment Analysis with Superintelligence, from the cached readings
+        self.sentiment_analyzer.spawn_refresh(symbol);
         let sentiment_analysis = match self.sentiment_analyzer.analyze(symbol) {
             Ok(analysis) => {
                 debug!("Sentiment analysis for {}: score = {}",
@@ -284,6 +370,8 @@ impl AgentCoordinator {
                 trade_execution: None,
                 reasoning: "Insufficient data for analysis".to_string(),
                 superintelligence_score: 0.0,
+                agent: None,
+                consensus: None,
             };
 
             self.decision_cache.insert(symbol.to_string(), decision.clone());
@@ -328,6 +416,8 @@ impl AgentCoordinator {
                 trade_execution: None,
                 reasoning: "Failed to assess risk".to_string(),
                 superintelligence_score: 0.0,
+                agent: None,
+                consensus: None,
             };
 
             self.decision_cache.insert(symbol.to_string(), decision.clone());
@@ -336,52 +426,123 @@ impl AgentCoordinator {
 
         let risk_assessment = risk_assessment.unwrap();
 
-        // Step 4: Enhanced Trading Decision with Multi-Factor Analysis
-        let (decision_type, confidence, reasoning) = if let Some(ref mfa) = multi_factor_analysis {
-            // Use advanced multi-factor analysis for decision making
-            let base_decision = self.make_decision(
-                &market_analysis,
-                &sentiment_analysis,
-                &risk_assessment,
-                quantum_prediction.as_ref(),
-                pattern_recognition.as_ref(),
-            );
+        // Step 4: Weighted consensus vote
+        let (base_type, base_confidence, base_reasoning) = self.make_decision(
+            &market_analysis,
+            &sentiment_analysis,
+            &risk_assessment,
+            quantum_prediction.as_ref(),
+            pattern_recognition.as_ref(),
+        );
+        let base_direction = match base_type {
+            DecisionType::EnterLong | DecisionType::Buy => VoteDirection::Long,
+            DecisionType::EnterShort | DecisionType::Sell => VoteDirection::Short,
+            _ => VoteDirection::Neutral,
+        };
+        let mut votes = vec![AgentVote::new("MarketAnalyzer", base_direction, base_confidence, base_reasoning)];
 
-            let enhanced_confidence = (base_decision.1 + mfa.confidence) / 2.0;
-            let enhanced_reasoning = format!(
-                "Multi-factor analysis: composite_score={:.1}, action={:?}. {}",
-                mfa.composite_score, mfa.action, base_decision.2
-            );
+        if let Some(ref mfa) = multi_factor_analysis {
+            let direction = match mfa.action {
+                TradingAction::StrongBuy | TradingAction::Buy => VoteDirection::Long,
+                TradingAction::StrongSell | TradingAction::Sell => VoteDirection::Short,
+                TradingAction::Hold => VoteDirection::Neutral,
+            };
+            votes.push(AgentVote::new(
+                "MultiFactorStrategy",
+                direction,
+                mfa.confidence,
+                format!("composite_score={:.1}, action={:?}", mfa.composite_score, mfa.action),
+            ));
+        }
 
-            // Override decision based on multi-factor analysis if it's more confident
-            if mfa.confidence > base_decision.1 {
-                let decision_type = match mfa.action {
-                    crate::strategy::advanced_multi_factor_strategy::TradingAction::StrongBuy => DecisionType::Buy,
-                    crate::strategy::advanced_multi_factor_strategy::TradingAction::Buy => DecisionType::Buy,
-                    crate::strategy::advanced_multi_factor_strategy::TradingAction::Sell => DecisionType::Sell,
-                    crate::strategy::advanced_multi_factor_strategy::TradingAction::StrongSell => DecisionType::Sell,
-                    _ => DecisionType::Hold,
-                };
-                (decision_type, mfa.confidence, enhanced_reasoning)
+        if let Some(ref prediction) = quantum_prediction {
+            let expected_move = (prediction.price_1h - market_analysis.current_price) / market_analysis.current_price * 100.0;
+            let direction = if expected_move > 0.1 {
+                VoteDirection::Long
+            } else if expected_move < -0.1 {
+                VoteDirection::Short
             } else {
-                (base_decision.0, enhanced_confidence, enhanced_reasoning)
-            }
-        } else {
-            // Fallback to original decision making
-            self.make_decision(
+                VoteDirection::Neutral
+            };
+            votes.push(AgentVote::new(
+                "QuantumPredictor",
+                direction,
+                prediction.confidence,
+                format!("1h target ${:.2} ({:+.2}%)", prediction.price_1h, expected_move),
+            ));
+        }
+
+        // Extreme funding or basis marks the crowded side; the funding agent votes against it
+        if let Some(snapshot) = self.get_crowded_symbols().into_iter().find(|s| s.symbol == symbol) {
+            let direction = match snapshot.crowded {
+                Some(CrowdedSide::Longs) => VoteDirection::Short,
+                Some(CrowdedSide::Shorts) => VoteDirection::Long,
+                None => VoteDirection::Neutral,
+            };
+            votes.push(AgentVote::new(
+                "FundingAgent",
+                direction,
+                CROWDED_VOTE_CONFIDENCE,
+                format!("{:?} crowded: funding {:.4}%, basis {:?}%", snapshot.crowded, snapshot.funding_rate * 100.0, snapshot.basis_pct),
+            ));
+        }
+
+        let mut vetoes = Vec::new();
+        if risk_assessment.risk_score > self.max_risk_score {
+            vetoes.push(Veto::new(
+                "RiskManager",
+                format!("risk score {:.1} above {:.1}", risk_assessment.risk_score, self.max_risk_score),
+            ));
+        }
+        let mut consensus = self.consensus.tally(votes.clone(), vetoes.clone());
+
+        // The zero-loss enforcer reviews the direction the vote settled on
+        let mut zero_loss_assessment = None;
+        let proposed_direction = match consensus.direction {
+            VoteDirection::Long => Some(TradeDirection::Long),
+            VoteDirection::Short => Some(TradeDirection::Short),
+            VoteDirection::Neutral => None,
+        };
+        if let Some(direction) = proposed_direction {
+            match self.zero_loss_enforcer.assess_trade(
+                symbol,
+                direction,
+                market_analysis.current_price,
                 &market_analysis,
                 &sentiment_analysis,
                 &risk_assessment,
-                quantum_prediction.as_ref(),
-                pattern_recognition.as_ref(),
-            )
+            ) {
+                Ok(assessment) => {
+                    if !assessment.approved {
+                        vetoes.push(Veto::new("ZeroLossEnforcer", assessment.reasoning.clone()));
+                    }
+                    zero_loss_assessment = Some(assessment);
+                },
+                Err(e) => {
+                    error!("Failed to perform zero-loss assessment for {}: {}", symbol, e);
+                    vetoes.push(Veto::new("ZeroLossEnforcer", format!("assessment failed: {}", e)));
+                }
+            }
+            if !vetoes.is_empty() {
+                consensus = self.consensus.tally(votes, vetoes);
+            }
+        }
+
+        let decision_type = match consensus.direction {
+            VoteDirection::Long => DecisionType::EnterLong,
+            VoteDirection::Short => DecisionType::EnterShort,
+            VoteDirection::Neutral => DecisionType::Hold,
         };
+        let confidence = consensus.confidence;
+        let reasoning = consensus.rationale();
 
+        if consensus.is_vetoed() {
+            warn!("Trade on {} vetoed: {}", symbol, reasoning);
+        }
         debug!("Trading decision for {}: {:?} (confidence: {})",
                symbol, decision_type, confidence);
 
-        // Step 5: Zero-Loss Enforcement
-        let mut zero_loss_assessment = None;
+        // Step 5: Execution
         let mut trade_execution = None;
 
         if confidence >= self.min_confidence {
@@ -389,57 +550,29 @@ impl AgentCoordinator {
                 DecisionType::EnterLong | DecisionType::EnterShort => {
                     // Check if we already have an active position
                     if adapter.get_positions(Some(symbol)).await.unwrap_or_default().is_empty() {
-                        // Convert decision type to trade direction
                         let direction = match decision_type {
                             DecisionType::EnterLong => TradeDirection::Long,
                             DecisionType::EnterShort => TradeDirection::Short,
                             _ => unreachable!(),
                         };
+                        let leverage = zero_loss_assessment.as_ref().map_or(1.0, |a| a.leverage);
 
-                        // Perform zero-loss assessment
-                        match self.zero_loss_enforcer.assess_trade(
+                        match self.trade_executor.execute_trade(
+                            adapter,
                             symbol,
                             direction,
-                            market_analysis.current_price,
-                            &market_analysis,
-                            &sentiment_analysis,
                             &risk_assessment,
-                        ) {
-                            Ok(assessment) => {
-                                zero_loss_assessment = Some(assessment.clone());
-
-                                if assessment.approved {
-                                    info!("Zero-loss enforcement APPROVED trade for {}: {}",
-                                          symbol, assessment.reasoning);
-
-                                    // Execute trade with zero-loss parameters
-                                    // Use the same direction since Long/Short are the only variants
-                                    let trade_direction = direction;
-
-                                    match self.trade_executor.execute_trade(
-                                        adapter,
-                                        symbol,
-                                        trade_direction,
-                                        &risk_assessment,
-                                        market_analysis.current_price,
-                                    ).await {
-                                        Ok(execution) => {
-                                            info!("Executed {:?} trade for {} with {:.1}x leverage",
-                                                  direction, symbol, assessment.leverage);
-                                            trade_execution = Some(execution);
-                                        },
-                                        Err(e) => {
-                                            error!("Failed to execute {:?} trade for {}: {}",
-                                                  direction, symbol, e);
-                                        }
-                                    }
-                                } else {
-                                    warn!("Zero-loss enforcement REJECTED trade for {}: {}",
-                                          symbol, assessment.reasoning);
-                                }
+                            market_analysis.current_price,
+                            CONSENSUS_STRATEGY,
+                        ).await {
+                            Ok(execution) => {
+                                info!("Executed {:?} trade for {} with {:.1}x leverage",
+                                      direction, symbol, leverage);
+                                trade_execution = Some(execution);
                             },
                             Err(e) => {
-                                error!("Failed to perform zero-loss assessment for {}: {}", symbol, e);
+                                error!("Failed to execute {:?} trade for {}: {}",
+                                      direction, symbol, e);
                             }
                         }
                     } else {
@@ -496,6 +629,8 @@ impl AgentCoordinator {
             trade_execution,
             reasoning,
             superintelligence_score,
+            agent: None,
+            consensus: Some(consensus),
         };
 
         // Cache the decision
@@ -822,6 +957,10 @@ impl AgentCoordinator {
             }
         }
 
+        // Feed observed slippage back into position sizing
+        let slippage = self.trade_executor.get_execution_quality().expected_slippage_by_symbol(MIN_SLIPPAGE_SAMPLES);
+        self.risk_manager.update_expected_slippage(slippage);
+
         Ok(())
     }
 
@@ -845,11 +984,68 @@ impl AgentCoordinator {
         &self.sentiment_analyzer
     }
 
+    /// Replace the sentiment analyzer with one reading the configured sources
+    pub fn set_sentiment_config(&mut self, config: SentimentAnalyzerConfig) {
+        self.sentiment_analyzer = SentimentAnalyzer::with_config(config);
+    }
+
+    /// Feed a news item to the sentiment analyzer and the risk manager
+    pub fn ingest_news(&mut self, item: &NewsItem) {
+        self.sentiment_analyzer.ingest_news(item);
+        self.risk_manager.apply_news(item);
+    }
+
+    /// Feed on-chain features to the market analyzer
+    pub fn update_onchain_features(&mut self, features: OnChainFeatures) {
+        self.market_analyzer.update_onchain_features(features);
+    }
+
+    /// Feed order-flow features to the multi-factor strategy's microstructure score
+    pub fn update_order_flow(&mut self, features: OrderFlowFeatures) {
+        self.multi_factor_strategy.update_order_flow(features);
+    }
+
+    /// Record the latest funding and basis of a symbol
+    pub fn update_funding(&mut self, snapshot: FundingSnapshot) {
+        self.funding.insert(snapshot.symbol.clone(), snapshot);
+    }
+
+    /// Get the latest funding and basis of a symbol
+    pub fn get_funding(&self, symbol: &str) -> Option<&FundingSnapshot> {
+        self.funding.get(symbol)
+    }
+
+    /// Symbols whose funding or basis is extreme, with their snapshots
+    pub fn get_crowded_symbols(&self) -> Vec<&FundingSnapshot> {
+        self.funding.values().filter(|s| s.crowded.is_some()).collect()
+    }
+
+    /// Weighted vote the analysis agents decide by
+    pub fn get_consensus(&self) -> &ConsensusVoting {
+        &self.consensus
+    }
+
+    /// Refresh vote weights from the feedback loop's performance records
+    pub fn update_vote_weights(&mut self, feedback_loop: &FeedbackLoop) {
+        self.consensus.update_weights(feedback_loop.get_all_agent_performance());
+    }
+
     /// Get risk manager
     pub fn get_risk_manager(&self) -> &RiskManager {
         &self.risk_manager
     }
 
+    /// Record the realized per-bar volatility (fraction) of a symbol for leverage caps and order routing
+    pub fn update_volatility(&mut self, symbol: &str, volatility: f64) {
+        self.risk_manager.update_volatility(symbol, volatility);
+        self.trade_executor.update_volatility(symbol, volatility);
+    }
+
+    /// Get mutable risk manager
+    pub fn get_risk_manager_mut(&mut self) -> &mut RiskManager {
+        &mut self.risk_manager
+    }
+
     /// Get trade executor
     pub fn get_trade_executor(&self) -> &TradeExecutor {
         &self.trade_executor
@@ -865,6 +1061,137 @@ impl AgentCoordinator {
         self.risk_manager.get_total_capital()
     }
 
+    /// Grant an agent a capital budget, keeping what it has committed
+    pub fn set_agent_budget(&mut self, agent: &str, budget: f64) -> Result<()> {
+        if !budget.is_finite() || budget < 0.0 {
+            return Err(anyhow::anyhow!("Invalid capital budget for {}: {}", agent, budget));
+        }
+
+        let entry = self.agent_budgets.entry(agent.to_string()).or_insert_with(|| AgentCapitalBudget {
+            agent: agent.to_string(),
+            budget: 0.0,
+            committed: 0.0,
+        });
+        entry.budget = budget;
+        info!("Capital budget of {} set to ${:.2} (${:.2} committed)", agent, budget, entry.committed);
+        Ok(())
+    }
+
+    /// Remove an agent's budget, leaving it unlimited
+    pub fn remove_agent_budget(&mut self, agent: &str) -> Option<AgentCapitalBudget> {
+        self.agent_budgets.remove(agent)
+    }
+
+    /// Get an agent's budget
+    pub fn get_agent_budget(&self, agent: &str) -> Option<&AgentCapitalBudget> {
+        self.agent_budgets.get(agent)
+    }
+
+    /// Get all agent budgets
+    pub fn get_agent_budgets(&self) -> &HashMap<String, AgentCapitalBudget> {
+        &self.agent_budgets
+    }
+
+    /// Check that an agent can commit `notional` more capital
+    pub fn check_agent_budget(&self, agent: &str, notional: f64) -> Result<()> {
+        match self.agent_budgets.get(agent) {
+            Some(budget) if notional > budget.available() => Err(anyhow::anyhow!(
+                "{} capital budget exceeded: ${:.2} requested, ${:.2} of ${:.2} available",
+                agent, notional, budget.available(), budget.budget
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    /// Check that an entry decision of `notional` fits its agent's budget
+    pub fn check_decision_budget(&self, decision: &TradingDecision, notional: f64) -> Result<()> {
+        let is_entry = matches!(
+            decision.decision_type,
+            DecisionType::EnterLong | DecisionType::EnterShort | DecisionType::Buy | DecisionType::Sell
+        );
+        match (&decision.agent, is_entry) {
+            (Some(agent), true) => self.check_agent_budget(agent, notional),
+            _ => Ok(()),
+        }
+    }
+
+    /// Commit capital of an opened position to its agent's budget
+    pub fn commit_agent_capital(&mut self, agent: &str, notional: f64) -> Result<()> {
+        self.check_agent_budget(agent, notional)?;
+        if let Some(budget) = self.agent_budgets.get_mut(agent) {
+            budget.committed += notional;
+        }
+        Ok(())
+    }
+
+    /// Release capital of a closed position from its agent's budget
+    pub fn release_agent_capital(&mut self, agent: &str, notional: f64) {
+        if let Some(budget) = self.agent_budgets.get_mut(agent) {
+            budget.committed = (budget.committed - notional).max(0.0);
+        }
+    }
+
+    /// Hand an agent to the runtime; it is driven by messages and ticks from then on
+    pub async fn spawn_agent(&mut self, agent: Box<dyn Agent>, context: Arc<RwLock<AgentContext>>) -> Result<()> {
+        self.agent_runtime.spawn(agent, context).await
+    }
+
+    /// Start an agent under supervision; failures restart it or escalate per its policies
+    pub async fn supervise_agent(
+        &mut self,
+        factory: AgentFactory,
+        context: Arc<RwLock<AgentContext>>,
+        restart: RestartPolicy,
+        escalation: EscalationPolicy,
+    ) -> Result<()> {
+        self.supervisor.supervise(&mut self.agent_runtime, factory, context, restart, escalation).await
+    }
+
+    /// Detect dead or stalled agents and restart or escalate, reporting to `errors`
+    pub async fn check_agents(&mut self, errors: &mut UnifiedErrorManager) -> Vec<SupervisorEvent> {
+        self.supervisor.check(&mut self.agent_runtime, errors, Utc::now()).await
+    }
+
+    /// Get the agent supervisor
+    pub fn get_supervisor(&self) -> &AgentSupervisor {
+        &self.supervisor
+    }
+
+    /// Route a bus message to the running agents whose topics match it; returns how many accepted it
+    pub fn dispatch_to_agents(&mut self, message: &BusMessage) -> usize {
+        if let Some(recorder) = self.message_recorder.as_mut() {
+            if let Err(e) = recorder.record(message) {
+                warn!("Failed to record bus message: {}", e);
+            }
+        }
+        self.agent_runtime.dispatch(message)
+    }
+
+    /// Record every message routed to the agents from now on
+    pub fn set_message_recorder(&mut self, recorder: MessageRecorder) {
+        self.message_recorder = Some(recorder);
+    }
+
+    /// Get the message recorder, if recording
+    pub fn get_message_recorder(&self) -> Option<&MessageRecorder> {
+        self.message_recorder.as_ref()
+    }
+
+    /// Stop every running agent
+    pub async fn stop_agents(&mut self) -> Result<()> {
+        self.agent_runtime.stop_all().await
+    }
+
+    /// Get the agent runtime
+    pub fn get_agent_runtime(&self) -> &AgentRuntime {
+        &self.agent_runtime
+    }
+
+    /// Get the agent runtime mutably
+    pub fn get_agent_runtime_mut(&mut self) -> &mut AgentRuntime {
+        &mut self.agent_runtime
+    }
+
     /// Set minimum confidence threshold
     pub fn set_min_confidence(&mut self, min_confidence: f64) {
         self.min_confidence = min_confidence;
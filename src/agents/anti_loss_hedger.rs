@@ -7,7 +7,7 @@ use std::sync::Arc;
 use std::collections::{HashMap, VecDeque};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use anyhow::Result;
+use anyhow::{anyhow, Result};
 use tracing::{info, debug, warn, error};
 use async_trait::async_trait;
 use tokio::sync::RwLock;
@@ -75,6 +75,16 @@ pub enum HedgeStatus {
 
     /// Hedge is cancelled
     Cancelled,
+
+    /// Hedge is closed (unwound)
+    Closed,
+}
+
+impl HedgeStatus {
+    /// Whether the hedge is done with and holds no exchange order
+    pub fn is_settled(&self) -> bool {
+        matches!(self, HedgeStatus::Expired | HedgeStatus::Cancelled | HedgeStatus::Closed)
+    }
 }
 
 /// Hedge record
@@ -139,11 +149,31 @@ pub struct HedgeRecord {
 
     /// Net PnL
     pub net_pnl: Option<f64>,
+
+    /// Beta of the original asset against the hedge asset, used to size the hedge
+    #[serde(default = "default_beta")]
+    pub beta: f64,
+
+    /// Bybit position index the hedge lives on (1 long / 2 short in hedge mode, 0 in one-way mode)
+    #[serde(default)]
+    pub position_idx: u8,
+
+    /// Exchange order that opened the hedge, once executed
+    #[serde(default)]
+    pub hedge_order_id: Option<String>,
+
+    /// Why the hedge was unwound
+    #[serde(default)]
+    pub unwind_reason: Option<String>,
+}
+
+fn default_beta() -> f64 {
+    1.0
 }
 
-/// Anti-Loss Hedger Agent
 /// Anti-Loss Hedger configuration
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct AntiLossHedgerConfig {
     /// Hedge threshold (percentage)
     pub hedge_threshold: f64,
@@ -162,6 +192,24 @@ pub struct AntiLossHedgerConfig {
 
     /// Expiration time (seconds)
     pub expiration_time: u64,
+
+    /// Whether the account holds long and short positions on a symbol side by side (Bybit hedge mode)
+    ///
+    /// Off by default: entry orders and stop amendments go out in one-way mode,
+    /// and Bybit rejects hedge-mode position indexes on a one-way account.
+    pub position_hedge_mode: bool,
+
+    /// Minimum absolute correlation of a correlated-asset hedge
+    pub min_hedge_correlation: f64,
+
+    /// Unwind once the hedged trade has recovered to within this loss (fraction of entry)
+    pub unwind_recovery_threshold: f64,
+
+    /// Unwind once the hedge itself has lost this fraction of its notional
+    pub max_hedge_loss: f64,
+
+    /// Unwind after this many seconds active (0 keeps the hedge until the trade closes)
+    pub max_hedge_duration: u64,
 }
 
 impl Default for AntiLossHedgerConfig {
@@ -173,6 +221,11 @@ impl Default for AntiLossHedgerConfig {
             check_interval: 60, // 1 minute
             activation_threshold: 0.02, // 2% drawdown
             expiration_time: 3600, // 1 hour
+            position_hedge_mode: false,
+            min_hedge_correlation: 0.8,
+            unwind_recovery_threshold: 0.002,
+            max_hedge_loss: 0.02,
+            max_hedge_duration: 14400, // 4 hours
         }
     }
 }
@@ -230,6 +283,12 @@ pub struct AntiLossHedger {
     /// Correlation matrix
     correlation_matrix: HashMap<String, HashMap<String, f64>>,
 
+    /// Beta of one symbol against another
+    betas: HashMap<String, HashMap<String, f64>>,
+
+    /// Latest price of each symbol that may serve as a hedge
+    prices: HashMap<String, f64>,
+
     /// Positions
     positions: HashMap<String, Position>,
 
@@ -260,6 +319,8 @@ impl AntiLossHedger {
             },
             hedges: VecDeque::with_capacity(MAX_HEDGES),
             correlation_matrix: HashMap::new(),
+            betas: HashMap::new(),
+            prices: HashMap::new(),
             positions: HashMap::new(),
             hedge_positions: HashMap::new(),
             running: false,
@@ -270,17 +331,30 @@ impl AntiLossHedger {
     /// Create a hedge for a trade
     pub fn create_hedge(&mut self, trade_id: &str, symbol: &str, direction: TradeDirection, position_size: f64, entry_price: f64) -> Result<HedgeRecord> {
         // Determine hedge type and symbol
-        let (hedge_type, hedge_symbol) = self.determine_hedge_type(symbol)?;
+        let (hedge_type, hedge_symbol, beta) = self.determine_hedge_type(symbol)?;
 
-        // Determine hedge direction (opposite of original)
-        let hedge_direction = match direction {
-            TradeDirection::Long => TradeDirection::Short,
-            TradeDirection::Short => TradeDirection::Long,
+        // Determine hedge direction (opposite of original, unless the hedge asset moves against it)
+        let hedge_direction = match (&direction, beta < 0.0) {
+            (TradeDirection::Long, false) | (TradeDirection::Short, true) => TradeDirection::Short,
+            (TradeDirection::Short, false) | (TradeDirection::Long, true) => TradeDirection::Long,
             _ => TradeDirection::Neutral,
         };
 
-        // Calculate hedge position size
-        let hedge_position_size = position_size * self.config.hedge_ratio;
+        // Offset the beta-weighted notional of the position
+        let hedge_price = if hedge_symbol == symbol {
+            entry_price
+        } else {
+            self.prices.get(&hedge_symbol).copied()
+                .ok_or_else(|| anyhow!("No price for hedge asset {}", hedge_symbol))?
+        };
+        let hedge_position_size = position_size * entry_price * beta.abs() * self.config.hedge_ratio / hedge_price;
+
+        // Each side of a symbol is its own position in hedge mode
+        let position_idx = match (hedge_type, &hedge_direction) {
+            (HedgeType::Inverse, TradeDirection::Long) => 1,
+            (HedgeType::Inverse, TradeDirection::Short) => 2,
+            _ => 0,
+        };
 
         // Create hedge ID
         let hedge_id = format!("hedge-{}-{}", trade_id, Utc::now().timestamp_millis());
@@ -310,14 +384,21 @@ impl AntiLossHedger {
             hedge_pnl: None,
             original_pnl: None,
             net_pnl: None,
+            beta,
+            position_idx,
+            hedge_order_id: None,
+            unwind_reason: None,
         };
 
         // Add to hedges
         self.hedges.push_back(hedge.clone());
 
-        // Enforce capacity limit
-        if self.hedges.len() > MAX_HEDGES {
-            self.hedges.pop_front();
+        // Enforce capacity limit, never dropping a hedge that is still live
+        while self.hedges.len() > MAX_HEDGES {
+            match self.hedges.iter().position(|h| h.status.is_settled()) {
+                Some(index) => { self.hedges.remove(index); },
+                None => break,
+            }
         }
 
         // Update state
@@ -327,16 +408,47 @@ impl AntiLossHedger {
         Ok(hedge)
     }
 
-    /// Determine hedge type and symbol
-    fn determine_hedge_type(&self, symbol: &str) -> Result<(HedgeType, String)> {
-        // For now, use inverse hedge
-        let hedge_type = HedgeType::Inverse;
-        let hedge_symbol = symbol.to_string();
+    /// Determine hedge type, symbol and beta
+    ///
+    /// In hedge mode the opposite perp position on the same symbol is the cleanest
+    /// hedge. In one-way mode an opposite order would just net against the position,
+    /// so the most correlated asset with a known price is used instead. Options
+    /// hedges need an options venue the adapter does not trade yet.
+    fn determine_hedge_type(&self, symbol: &str) -> Result<(HedgeType, String, f64)> {
+        if self.config.position_hedge_mode {
+            return Ok((HedgeType::Inverse, symbol.to_string(), 1.0));
+        }
 
-        // In a real implementation, this would use the correlation matrix
-        // to find the best hedge asset
+        let best = self.correlation_matrix.get(symbol)
+            .into_iter()
+            .flatten()
+            .filter(|(other, correlation)| {
+                other.as_str() != symbol
+                    && correlation.abs() >= self.config.min_hedge_correlation
+                    && self.prices.contains_key(other.as_str())
+            })
+            .max_by(|a, b| a.1.abs().total_cmp(&b.1.abs()));
+
+        match best {
+            Some((hedge_symbol, correlation)) => {
+                // Without a beta estimate assume both assets are equally volatile
+                let beta = self.get_beta(symbol, hedge_symbol).unwrap_or(*correlation);
+                Ok((HedgeType::Correlated, hedge_symbol.clone(), beta))
+            },
+            None => Err(anyhow!(
+                "No hedge for {}: one-way position mode and no asset correlated above {:.2}",
+                symbol, self.config.min_hedge_correlation
+            )),
+        }
+    }
 
-        Ok((hedge_type, hedge_symbol))
+    /// Price of the hedge leg given the original asset's price
+    fn hedge_leg_price(&self, hedge: &HedgeRecord, original_price: f64) -> f64 {
+        if hedge.hedge_symbol == hedge.original_symbol {
+            original_price
+        } else {
+            self.prices.get(&hedge.hedge_symbol).copied().unwrap_or(original_price)
+        }
     }
 
     /// Update hedge status
@@ -380,7 +492,7 @@ impl AntiLossHedger {
                 // Activate hedge
                 hedge.status = HedgeStatus::Activated;
                 hedge.activation_time = Some(Utc::now());
-                hedge.hedge_entry_price = Some(current_price);
+                hedge.hedge_entry_price = Some(self.hedge_leg_price(&hedge, current_price));
                 hedge.original_pnl = Some(original_pnl);
 
                 self.hedges[index] = hedge.clone();
@@ -396,7 +508,7 @@ impl AntiLossHedger {
                         "symbol": hedge.hedge_symbol,
                         "direction": format!("{:?}", hedge.hedge_direction),
                         "position_size": hedge.hedge_position_size,
-                        "entry_price": current_price,
+                        "entry_price": hedge.hedge_entry_price,
                         "timestamp": Utc::now(),
                     }),
                 ));
@@ -427,6 +539,8 @@ impl AntiLossHedger {
             }
 
             // Update hedge
+            let exit_price = self.hedge_leg_price(&hedge, exit_price);
+            hedge.status = HedgeStatus::Closed;
             hedge.hedge_exit_price = Some(exit_price);
 
             // Calculate hedge PnL
@@ -467,6 +581,7 @@ impl AntiLossHedger {
                     "hedge_pnl": hedge_pnl,
                     "original_pnl": hedge.original_pnl.unwrap(),
                     "net_pnl": net_pnl,
+                    "unwind_reason": hedge.unwind_reason,
                     "timestamp": Utc::now(),
                 }),
             ));
@@ -480,6 +595,114 @@ impl AntiLossHedger {
         Ok(message)
     }
 
+    /// Check the unwind criteria of a trade's activated hedges at the original asset's price
+    ///
+    /// A hedge is unwound once the hedged trade has recovered, once the hedge itself
+    /// loses more than `max_hedge_loss`, or after `max_hedge_duration`. Returns the
+    /// hedges due with their reason; the caller flattens and closes them.
+    pub fn check_unwinds(&mut self, trade_id: &str, original_price: f64) -> Vec<(String, String)> {
+        let now = Utc::now();
+        let mut due = Vec::new();
+
+        for hedge in self.hedges.iter().filter(|h| h.trade_id == trade_id && h.status == HedgeStatus::Activated) {
+            let Some(hedge_entry_price) = hedge.hedge_entry_price else {
+                continue;
+            };
+
+            let original_pnl = self.calculate_pnl(
+                hedge.original_direction.clone(),
+                hedge.original_entry_price,
+                original_price,
+                hedge.original_position_size,
+            );
+            let hedge_pnl = self.calculate_pnl(
+                hedge.hedge_direction.clone(),
+                hedge_entry_price,
+                self.hedge_leg_price(hedge, original_price),
+                hedge.hedge_position_size,
+            );
+            let original_pct = original_pnl / (hedge.original_entry_price * hedge.original_position_size);
+            let hedge_pct = hedge_pnl / (hedge_entry_price * hedge.hedge_position_size);
+            let held_too_long = self.config.max_hedge_duration > 0
+                && hedge.activation_time.map_or(false, |activated| {
+                    now - activated >= chrono::Duration::seconds(self.config.max_hedge_duration as i64)
+                });
+
+            let reason = if original_pct >= -self.config.unwind_recovery_threshold {
+                "recovered"
+            } else if hedge_pct <= -self.config.max_hedge_loss {
+                "hedge_stop"
+            } else if held_too_long {
+                "max_duration"
+            } else {
+                continue;
+            };
+            due.push((hedge.id.clone(), reason.to_string()));
+        }
+
+        for (hedge_id, reason) in &due {
+            if let Some(hedge) = self.hedges.iter_mut().find(|h| &h.id == hedge_id) {
+                hedge.unwind_reason = Some(reason.clone());
+            }
+        }
+
+        due
+    }
+
+    /// Open an activated hedge on the exchange
+    pub async fn execute_hedge(&mut self, hedge_id: &str) -> Result<()> {
+        let index = self.hedges.iter().position(|h| h.id == hedge_id)
+            .ok_or_else(|| anyhow!("Unknown hedge {}", hedge_id))?;
+        let hedge = &self.hedges[index];
+        if hedge.status != HedgeStatus::Activated || hedge.hedge_order_id.is_some() {
+            return Ok(());
+        }
+
+        let side = match hedge.hedge_direction {
+            TradeDirection::Long => OrderSide::Buy,
+            TradeDirection::Short => OrderSide::Sell,
+            _ => return Ok(()),
+        };
+        let order = self.exchange.place_position_order(
+            &hedge.hedge_symbol,
+            side,
+            hedge.hedge_position_size,
+            hedge.position_idx,
+            false,
+        ).await?;
+
+        info!("Executed hedge {} on {}: {:?} {}", hedge_id, hedge.hedge_symbol, side, hedge.hedge_position_size);
+        self.hedges[index].hedge_order_id = Some(order.order_id);
+
+        Ok(())
+    }
+
+    /// Flatten an executed hedge's exchange position before the hedge is closed
+    pub async fn unwind_hedge_order(&mut self, hedge_id: &str) -> Result<()> {
+        let hedge = self.get_hedge(hedge_id)
+            .ok_or_else(|| anyhow!("Unknown hedge {}", hedge_id))?;
+        if hedge.status != HedgeStatus::Activated || hedge.hedge_order_id.is_none() {
+            return Ok(());
+        }
+
+        let side = match hedge.hedge_direction {
+            TradeDirection::Long => OrderSide::Sell,
+            TradeDirection::Short => OrderSide::Buy,
+            _ => return Ok(()),
+        };
+        self.exchange.place_position_order(
+            &hedge.hedge_symbol,
+            side,
+            hedge.hedge_position_size,
+            hedge.position_idx,
+            true,
+        ).await?;
+
+        info!("Unwound hedge {} on {}: {:?} {}", hedge_id, hedge.hedge_symbol, side, hedge.hedge_position_size);
+
+        Ok(())
+    }
+
     /// Calculate PnL
     fn calculate_pnl(&self, direction: TradeDirection, entry_price: f64, current_price: f64, position_size: f64) -> f64 {
         match direction {
@@ -687,6 +910,27 @@ impl AntiLossHedger {
             .insert(symbol1.to_string(), correlation);
     }
 
+    /// Update the beta of `symbol` against `hedge_symbol`
+    pub fn update_beta(&mut self, symbol: &str, hedge_symbol: &str, beta: f64) {
+        self.betas.entry(symbol.to_string())
+            .or_insert_with(HashMap::new)
+            .insert(hedge_symbol.to_string(), beta);
+    }
+
+    /// Get the beta of `symbol` against `hedge_symbol`
+    pub fn get_beta(&self, symbol: &str, hedge_symbol: &str) -> Option<f64> {
+        self.betas.get(symbol)
+            .and_then(|map| map.get(hedge_symbol))
+            .copied()
+    }
+
+    /// Record the latest price of a symbol that may serve as a hedge
+    pub fn update_price(&mut self, symbol: &str, price: f64) {
+        if price > 0.0 && price.is_finite() {
+            self.prices.insert(symbol.to_string(), price);
+        }
+    }
+
     /// Get correlation
     pub fn get_correlation(&self, symbol1: &str, symbol2: &str) -> Option<f64> {
         self.correlation_matrix.get(symbol1)
@@ -746,7 +990,7 @@ impl Agent for AntiLossHedger {
         Ok(())
     }
 
-    async fn update(&mut self) -> Result<()> {
+    async fn tick(&mut self) -> Result<()> {
         if !self.running {
             return Ok(());
         }
@@ -868,6 +1112,10 @@ impl Agent for AntiLossHedger {
     fn get_config(&self) -> Box<dyn AgentConfig> {
         Box::new(self.config.clone())
     }
+
+    fn topics(&self) -> Vec<String> {
+        vec!["signal.#".to_string()]
+    }
 }
 
 #[cfg(test)]
@@ -876,7 +1124,11 @@ mod tests {
 
     #[test]
     fn test_hedge_creation_and_activation() {
-        let mut hedger = AntiLossHedger::new();
+        let mut hedger = AntiLossHedger::new(
+            AntiLossHedgerConfig { position_hedge_mode: true, ..Default::default() },
+            Arc::new(BybitAdapter::new("", "", true)),
+            Arc::new(MessageBus::new()),
+        );
 
         // Create a hedge
         let hedge = hedger.create_hedge(
@@ -916,4 +1168,68 @@ mod tests {
         assert!(closed_hedge.hedge_pnl.is_some());
         assert!(closed_hedge.net_pnl.is_some());
     }
+
+    #[test]
+    fn test_correlated_hedge_sized_by_beta_and_unwound() {
+        let mut hedger = AntiLossHedger::new(
+            AntiLossHedgerConfig { position_hedge_mode: false, ..Default::default() },
+            Arc::new(BybitAdapter::new("", "", true)),
+            Arc::new(MessageBus::new()),
+        );
+
+        // One-way mode without a correlated asset has nothing to hedge with
+        assert!(hedger.create_hedge("trade-0", "BTCUSDT", TradeDirection::Long, 1.0, 50000.0).is_err());
+
+        hedger.update_price("ETHUSDT", 2500.0);
+        hedger.update_correlation("BTCUSDT", "ETHUSDT", 0.9);
+        hedger.update_beta("BTCUSDT", "ETHUSDT", 0.8);
+
+        let hedge = hedger.create_hedge("trade-1", "BTCUSDT", TradeDirection::Long, 1.0, 50000.0).unwrap();
+        assert_eq!(hedge.hedge_type, HedgeType::Correlated);
+        assert_eq!(hedge.hedge_symbol, "ETHUSDT");
+        assert!(matches!(hedge.hedge_direction, TradeDirection::Short));
+        assert_eq!(hedge.position_idx, 0);
+        // $50k of BTC at beta 0.8, hedged at a 50% ratio, in ETH at $2500
+        assert!((hedge.hedge_position_size - 8.0).abs() < 1e-9);
+
+        hedger.update_hedge(&hedge.id, 49500.0).unwrap();
+        assert_eq!(hedger.get_hedge(&hedge.id).unwrap().hedge_entry_price, Some(2500.0));
+
+        // Still 1% down: keep the hedge
+        assert!(hedger.check_unwinds("trade-1", 49500.0).is_empty());
+
+        // Back within 0.2% of entry: unwind
+        let due = hedger.check_unwinds("trade-1", 49950.0);
+        assert_eq!(due, vec![(hedge.id.clone(), "recovered".to_string())]);
+
+        hedger.close_hedge(&hedge.id, 49950.0).unwrap();
+        let closed = hedger.get_hedge(&hedge.id).unwrap();
+        assert_eq!(closed.status, HedgeStatus::Closed);
+        assert_eq!(closed.unwind_reason.as_deref(), Some("recovered"));
+        assert!(hedger.close_hedge(&hedge.id, 49950.0).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_capacity_keeps_live_hedges() {
+        let mut hedger = AntiLossHedger::new(
+            AntiLossHedgerConfig { position_hedge_mode: true, ..Default::default() },
+            Arc::new(BybitAdapter::new("", "", true)),
+            Arc::new(MessageBus::new()),
+        );
+
+        let live = hedger.create_hedge("trade-0", "BTCUSDT", TradeDirection::Long, 0.1, 50000.0).unwrap();
+        hedger.update_hedge(&live.id, 49500.0).unwrap();
+        let closed = hedger.create_hedge("trade-1", "BTCUSDT", TradeDirection::Long, 0.1, 50000.0).unwrap();
+        hedger.update_hedge(&closed.id, 49500.0).unwrap();
+        hedger.close_hedge(&closed.id, 49500.0).unwrap();
+
+        for i in 0..MAX_HEDGES {
+            hedger.create_hedge(&format!("trade-{}", i + 2), "BTCUSDT", TradeDirection::Long, 0.1, 50000.0).unwrap();
+        }
+
+        // The closed hedge makes room; the activated one and every pending one stay
+        assert!(hedger.get_hedge(&closed.id).is_none());
+        assert_eq!(hedger.get_hedge(&live.id).unwrap().status, HedgeStatus::Activated);
+        assert_eq!(hedger.get_all_hedges().len(), MAX_HEDGES + 1);
+    }
 }
@@ -2,23 +2,44 @@
 //!
 //! This module provides the Asset Scanner agent for the OMNI-ALPHA VΩ∞∞ platform.
 //! The Asset Scanner agent scans all available assets and identifies profitable trading opportunities.
+//! Which assets make up the tradable universe is decided by a chain of scan filters.
 
 use std::sync::Arc;
 use tokio::sync::RwLock;
+use serde::{Deserialize, Serialize};
 use anyhow::Result;
 use tracing::{info, debug, warn, error};
 use async_trait::async_trait;
 
 use crate::engine::agent_trait::{Agent, AgentContext, AgentConfig};
-use crate::engine::message_bus::{BusMessage, MessageBus, MessageType};
+use crate::engine::message_bus::{BusMessage, Message, MessageBus, MessageType};
 use crate::exchange::bybit::adapter::BybitAdapter;
-use crate::exchange::asset_scanner::{AssetScanner, TradingOpportunity};
+use crate::exchange::asset_scanner::{AssetScanner, SymbolUniverse, TradingOpportunity};
+use crate::agents::scan_filters::{check_all, ScanCandidate, ScanFilter, ScanFilterKind};
 use std::collections::HashMap;
 use std::time::{Duration, SystemTime};
 use tokio::time::sleep;
 
+/// Topic the scanned universe is published on (`Message::Custom`)
+pub const UNIVERSE_TOPIC: &str = "scan_universe";
+
+/// Bus message carrying the symbols of the scanned universe
+pub fn universe_message(symbols: &[String]) -> Message {
+    Message::Custom(UNIVERSE_TOPIC.to_string(), serde_json::json!({ "symbols": symbols }))
+}
+
+/// Symbols of the scanned universe, if the message carries them
+pub fn universe_from_message(message: &Message) -> Option<Vec<String>> {
+    match message {
+        Message::Custom(topic, payload) if topic == UNIVERSE_TOPIC => {
+            serde_json::from_value(payload.get("symbols")?.clone()).ok()
+        }
+        _ => None,
+    }
+}
+
 /// Enhanced Asset Scanner Agent configuration for Phase 3
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AssetScannerAgentConfig {
     /// Maximum assets to analyze (300+ for comprehensive scanning)
     pub max_assets: usize,
@@ -71,6 +92,11 @@ pub struct AssetScannerAgentConfig {
 
     /// Asset cooldown period in minutes (15 minutes between same-asset trades)
     pub asset_cooldown_minutes: u64,
+
+    /// Scan filter chain for both the comprehensive scan and Phase 3 filtering;
+    /// when empty, each builds its own chain from the thresholds above
+    #[serde(default)]
+    pub filters: Vec<ScanFilterKind>,
 }
 
 impl Default for AssetScannerAgentConfig {
@@ -96,7 +122,49 @@ impl Default for AssetScannerAgentConfig {
             exclude_high_value: true, // Exclude BTC, ETH due to capital constraints
             target_movement_range: (0.5, 0.8), // 0.5%-0.8% target movements for profit
             asset_cooldown_minutes: 15, // 15 minutes cooldown between same-asset trades
+            filters: Vec::new(),
+        }
+    }
+}
+
+impl AssetScannerAgentConfig {
+    /// Comprehensive scan chain: the volume, market cap, volatility and high-value settings
+    ///
+    /// The target movement range is the move a trade aims to capture, not a cap on
+    /// daily volatility, so the volatility band has no upper bound.
+    pub fn threshold_filters(&self, high_value_assets: &[String]) -> Vec<ScanFilterKind> {
+        let mut filters = vec![
+            ScanFilterKind::MinVolume { min_usd: self.min_daily_volume },
+            ScanFilterKind::MinMarketCap { min_usd: self.min_market_cap },
+            ScanFilterKind::Volatility {
+                min_pct: self.min_volatility.max(self.target_movement_range.0),
+                max_pct: None,
+            },
+        ];
+        if self.exclude_high_value {
+            filters.push(ScanFilterKind::Blacklist { symbols: high_value_assets.to_vec() });
         }
+        filters
+    }
+
+    /// Phase 3 chain: volume, market cap, volatility, the leverage floor and a price
+    /// that a minimum order can be sized at
+    pub fn phase3_filters(&self) -> Vec<ScanFilterKind> {
+        vec![
+            ScanFilterKind::MinVolume { min_usd: self.min_daily_volume },
+            ScanFilterKind::MinMarketCap { min_usd: self.min_market_cap },
+            ScanFilterKind::Volatility { min_pct: self.min_volatility, max_pct: None },
+            ScanFilterKind::MinLeverage { min_leverage: self.leverage_range.0 },
+            ScanFilterKind::MinPrice { min_price: 0.001 },
+        ]
+    }
+
+    /// Check every configured filter can be passed
+    pub fn validate(&self) -> Result<()> {
+        for filter in self.filters.iter().chain(self.threshold_filters(&[]).iter()).chain(self.phase3_filters().iter()) {
+            filter.validate()?;
+        }
+        Ok(())
     }
 }
 
@@ -178,6 +246,12 @@ pub struct AssetScannerAgent {
 
     /// High-value assets to exclude (BTC, ETH, etc.)
     excluded_high_value_assets: Vec<String>,
+
+    /// Scan filter chain of the comprehensive scan
+    filters: Vec<Box<dyn ScanFilter>>,
+
+    /// Filter chain of Phase 3 filtering
+    phase3_filters: Vec<Box<dyn ScanFilter>>,
 }
 
 /// Enhanced asset metadata for Phase 3 comprehensive filtering
@@ -227,6 +301,14 @@ impl AssetScannerAgent {
             "BNBUSDT".to_string(),
         ];
 
+        let (filter_kinds, phase3_kinds) = if config.filters.is_empty() {
+            (config.threshold_filters(&excluded_high_value_assets), config.phase3_filters())
+        } else {
+            (config.filters.clone(), config.filters.clone())
+        };
+        let filters = filter_kinds.iter().map(ScanFilterKind::build).collect();
+        let phase3_filters = phase3_kinds.iter().map(ScanFilterKind::build).collect();
+
         Self {
             config,
             exchange,
@@ -246,6 +328,48 @@ impl AssetScannerAgent {
             last_comprehensive_scan: 0,
             asset_performance: HashMap::new(),
             excluded_high_value_assets,
+            filters,
+            phase3_filters,
+        }
+    }
+
+    /// Append a filter to the scan chain
+    pub fn add_filter(&mut self, filter: Box<dyn ScanFilter>) {
+        self.filters.push(filter);
+    }
+
+    /// Names of the filters in the scan chain
+    pub fn get_filter_names(&self) -> Vec<String> {
+        self.filters.iter().map(|f| f.get_name()).collect()
+    }
+
+    /// Whether a candidate passes every filter of a chain
+    fn passes_filters(filters: &[Box<dyn ScanFilter>], candidate: &ScanCandidate) -> bool {
+        match check_all(filters, candidate) {
+            Ok(()) => true,
+            Err(e) => {
+                debug!("❌ {}", e);
+                false
+            }
+        }
+    }
+
+    /// Fill in the spread and funding rate of a candidate when a filter of the chain reads them
+    async fn complete_candidate(&self, filters: &[Box<dyn ScanFilter>], candidate: &mut ScanCandidate) {
+        if filters.iter().any(|f| f.needs_orderbook()) {
+            if let Ok(book) = self.exchange.get_orderbook(&candidate.symbol, 1).await {
+                if let (Some((bid, _)), Some((ask, _))) = (book.bids.first(), book.asks.first()) {
+                    let mid = (bid + ask) / 2.0;
+                    if mid > 0.0 {
+                        candidate.spread_bps = Some((ask - bid) / mid * 10_000.0);
+                    }
+                }
+            }
+        }
+        if filters.iter().any(|f| f.needs_funding()) {
+            if let Ok(rate) = self.exchange.get_funding_rate(&candidate.symbol).await {
+                candidate.funding_rate = Some(rate.funding_rate);
+            }
         }
     }
 
@@ -554,6 +678,10 @@ impl AssetScannerAgent {
 
         // Get ALL linear instruments from Bybit
         let instruments = self.exchange.get_instruments("linear").await?;
+        self.asset_scanner.update_universe(&instruments.list);
+        let max_leverage: HashMap<String, f64> = instruments.list.iter()
+            .map(|i| (i.symbol.clone(), i.leverage_filter.max_leverage as f64))
+            .collect();
         let all_symbols: Vec<String> = instruments.list
             .into_iter()
             .filter(|i| i.symbol.ends_with("USDT"))
@@ -569,12 +697,6 @@ impl AssetScannerAgent {
         for symbol in &all_symbols {
             processed_count += 1;
 
-            // Skip excluded high-value assets
-            if self.excluded_high_value_assets.contains(&symbol) {
-                debug!("⚠️ Skipping high-value asset: {}", symbol);
-                continue;
-            }
-
             // Check asset cooldown
             if self.is_asset_in_cooldown(&symbol) {
                 debug!("⏰ Asset {} in cooldown period", symbol);
@@ -582,7 +704,7 @@ impl AssetScannerAgent {
             }
 
             // Get enhanced metadata and apply filtering
-            match self.get_enhanced_asset_metadata(&symbol).await {
+            match self.get_enhanced_asset_metadata(&symbol, max_leverage.get(symbol).copied()).await {
                 Ok(metadata) => {
                     if metadata.meets_phase3_criteria {
                         filtered_symbols.push(symbol.clone());
@@ -611,6 +733,9 @@ impl AssetScannerAgent {
         self.filtered_assets = filtered_symbols.clone();
         self.last_comprehensive_scan = chrono::Utc::now().timestamp() as u64;
 
+        // Hand the universe to the agents tracking it
+        self.message_bus.send(universe_message(&filtered_symbols));
+
         Ok(filtered_symbols)
     }
 
@@ -627,7 +752,17 @@ impl AssetScannerAgent {
             // Get comprehensive market data
             match self.get_comprehensive_market_data(symbol).await {
                 Ok(market_data) => {
-                    if self.meets_phase3_criteria(&market_data) {
+                    let mut candidate = ScanCandidate {
+                        symbol: symbol.clone(),
+                        price: market_data.current_price,
+                        volume_24h_usd: market_data.volume_24h_usd,
+                        market_cap_usd: market_data.market_cap_usd,
+                        volatility_pct: market_data.volatility_24h,
+                        max_leverage: Some(market_data.max_leverage),
+                        ..Default::default()
+                    };
+                    self.complete_candidate(&self.phase3_filters, &mut candidate).await;
+                    if Self::passes_filters(&self.phase3_filters, &candidate) {
                         filtered_assets.push(symbol.clone());
                         info!("✅ {} meets Phase 3 criteria: Vol=${:.2}M, MCap=${:.2}M, Volatility={:.2}%",
                               symbol, market_data.volume_24h_usd / 1_000_000.0,
@@ -692,33 +827,6 @@ impl AssetScannerAgent {
         })
     }
 
-    /// Check if market data meets Phase 3 criteria
-    fn meets_phase3_criteria(&self, data: &ComprehensiveMarketData) -> bool {
-        // Phase 3 Filtering Criteria:
-        // 1. 24h trading volume > $5,000,000 USD
-        // 2. Market capitalization > $100,000,000 USD
-        // 3. Daily volatility > 2.0%
-        // 4. Leverage compatibility: 50x-100x available
-        // 5. Order size compatibility: 5.00 USDT minimum viable
-
-        let volume_criteria = data.volume_24h_usd >= 5_000_000.0;
-        let market_cap_criteria = data.market_cap_usd >= 100_000_000.0;
-        let volatility_criteria = data.volatility_24h >= 2.0;
-        let leverage_criteria = data.max_leverage >= 50.0;
-        let price_criteria = data.current_price >= 0.001; // Minimum price for 5 USDT position
-
-        let meets_all = volume_criteria && market_cap_criteria && volatility_criteria &&
-                       leverage_criteria && price_criteria;
-
-        if !meets_all {
-            debug!("❌ {} criteria check: Vol={} MCap={} Volatility={} Leverage={} Price={}",
-                   data.symbol, volume_criteria, market_cap_criteria, volatility_criteria,
-                   leverage_criteria, price_criteria);
-        }
-
-        meets_all
-    }
-
     /// Estimate circulating supply for market cap calculation
     fn estimate_circulating_supply(&self, symbol: &str, current_price: f64) -> f64 {
         // Simplified estimation based on common patterns
@@ -758,7 +866,7 @@ impl AssetScannerAgent {
     }
 
     /// Get enhanced asset metadata with Phase 3 filtering criteria
-    async fn get_enhanced_asset_metadata(&self, symbol: &str) -> Result<EnhancedAssetMetadata> {
+    async fn get_enhanced_asset_metadata(&self, symbol: &str, max_leverage: Option<f64>) -> Result<EnhancedAssetMetadata> {
         // Get basic ticker data
         let ticker_data = self.exchange.get_ticker(symbol).await?;
         let ticker = ticker_data.first().ok_or_else(|| anyhow!("No ticker data for {}", symbol))?;
@@ -783,15 +891,18 @@ impl AssetScannerAgent {
             current_price,
         );
 
-        // Check if meets all Phase 3 criteria
-        let meets_phase3_criteria =
-            daily_volume_usd >= self.config.min_daily_volume &&
-            market_cap_usd >= self.config.min_market_cap &&
-            daily_volatility >= self.config.min_volatility &&
-            !is_high_value &&
-            current_price * self.config.min_order_size <= self.config.min_order_size * current_price &&
-            daily_volatility >= self.config.target_movement_range.0 &&
-            daily_volatility <= self.config.target_movement_range.1 * 2.0; // Allow some buffer
+        // Check if it passes the scan filters
+        let mut candidate = ScanCandidate {
+            symbol: symbol.to_string(),
+            price: current_price,
+            volume_24h_usd: daily_volume_usd,
+            market_cap_usd,
+            volatility_pct: daily_volatility,
+            max_leverage,
+            ..Default::default()
+        };
+        self.complete_candidate(&self.filters, &mut candidate).await;
+        let meets_phase3_criteria = Self::passes_filters(&self.filters, &candidate);
 
         Ok(EnhancedAssetMetadata {
             symbol: symbol.to_string(),
@@ -799,7 +910,7 @@ impl AssetScannerAgent {
             market_cap_usd,
             daily_volatility,
             current_price,
-            leverage_available: self.config.leverage_range.1, // Use max leverage
+            leverage_available: max_leverage.unwrap_or(self.config.leverage_range.1),
             min_order_size: self.config.min_order_size,
             is_high_value,
             last_movement_percent: price_change_24h,
@@ -845,6 +956,11 @@ impl AssetScannerAgent {
         &self.filtered_assets
     }
 
+    /// Point-in-time listing history of scanned symbols
+    pub fn get_universe(&self) -> &SymbolUniverse {
+        self.asset_scanner.get_universe()
+    }
+
     /// Get asset performance data
     pub fn get_asset_performance(&self, symbol: &str) -> Option<&AssetPerformance> {
         self.asset_performance.get(symbol)
@@ -855,6 +971,7 @@ impl AssetScannerAgent {
 impl Agent for AssetScannerAgent {
     async fn initialize(&mut self, _context: Arc<RwLock<AgentContext>>) -> Result<()> {
         info!("Initializing Asset Scanner Agent");
+        self.config.validate()?;
 
         // Send initialization message
         let message = BusMessage {
@@ -902,7 +1019,7 @@ impl Agent for AssetScannerAgent {
         Ok(())
     }
 
-    async fn update(&mut self) -> Result<()> {
+    async fn tick(&mut self) -> Result<()> {
         if !self.running {
             return Ok(());
         }
@@ -1000,4 +1117,8 @@ impl Agent for AssetScannerAgent {
     fn get_config(&self) -> Box<dyn AgentConfig> {
         Box::new(self.config.clone())
     }
+
+    fn topics(&self) -> Vec<String> {
+        vec!["signal.#".to_string()]
+    }
 }
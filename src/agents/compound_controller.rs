@@ -46,6 +46,10 @@ pub struct CapitalAllocationStrategy {
     /// Maximum concurrent trades
     pub max_concurrent_trades: usize,
 
+    /// Notional per position in USDT
+    #[serde(default)]
+    pub position_size_usdt: f64,
+
     /// Minimum confidence threshold
     pub min_confidence: f64,
 
@@ -101,7 +105,7 @@ pub struct CompoundControllerState {
 
 /// Compound Controller Agent
 /// Compound Controller configuration
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CompoundControllerConfig {
     /// Tier 1 position size percentage
     pub tier1_position_size: f64,
@@ -126,6 +130,12 @@ pub struct CompoundControllerConfig {
 
     /// Tier 4 max concurrent trades
     pub tier4_max_trades: usize,
+
+    /// Capital at which Tier 2, 3 and 4 start
+    pub tier_thresholds: [f64; 3],
+
+    /// How far (in %) capital must fall below a tier's threshold before dropping back a tier
+    pub hysteresis_pct: f64,
 }
 
 impl Default for CompoundControllerConfig {
@@ -139,6 +149,8 @@ impl Default for CompoundControllerConfig {
             tier2_max_trades: 3,
             tier3_max_trades: 5,
             tier4_max_trades: 10,
+            tier_thresholds: [25.0, 100.0, 500.0],
+            hysteresis_pct: 5.0,
         }
     }
 }
@@ -156,6 +168,9 @@ pub struct CompoundController {
     /// Allocation strategies by tier
     allocation_strategies: HashMap<CapitalTier, CapitalAllocationStrategy>,
 
+    /// Sizing multiplier from the drawdown throttle
+    drawdown_multiplier: f64,
+
     /// Running flag
     running: bool,
 }
@@ -172,6 +187,7 @@ impl CompoundController {
             max_leverage: 3.0,
             position_size_pct: 10.0,
             max_concurrent_trades: 1,
+            position_size_usdt: 5.0,
             min_confidence: 0.95,
             min_roi: 0.5,
             max_drawdown_pct: 5.0,
@@ -192,6 +208,7 @@ impl CompoundController {
             max_leverage: 5.0,
             position_size_pct: 15.0,
             max_concurrent_trades: 2,
+            position_size_usdt: 10.0,
             min_confidence: 0.9,
             min_roi: 0.4,
             max_drawdown_pct: 7.0,
@@ -213,6 +230,7 @@ impl CompoundController {
             max_leverage: 10.0,
             position_size_pct: 20.0,
             max_concurrent_trades: 3,
+            position_size_usdt: 25.0,
             min_confidence: 0.85,
             min_roi: 0.3,
             max_drawdown_pct: 10.0,
@@ -235,6 +253,7 @@ impl CompoundController {
             max_leverage: 20.0,
             position_size_pct: 25.0,
             max_concurrent_trades: 5,
+            position_size_usdt: 100.0,
             min_confidence: 0.8,
             min_roi: 0.2,
             max_drawdown_pct: 15.0,
@@ -253,15 +272,7 @@ impl CompoundController {
         });
 
         // Determine initial tier
-        let initial_tier = if initial_capital < 25.0 {
-            CapitalTier::Tier1
-        } else if initial_capital < 100.0 {
-            CapitalTier::Tier2
-        } else if initial_capital < 500.0 {
-            CapitalTier::Tier3
-        } else {
-            CapitalTier::Tier4
-        };
+        let initial_tier = Self::tier_for_capital(&config.tier_thresholds, initial_capital);
 
         // Create state
         let state = CompoundControllerState {
@@ -282,6 +293,7 @@ impl CompoundController {
             message_bus,
             state,
             allocation_strategies: strategies,
+            drawdown_multiplier: 1.0,
             running: false,
         }
     }
@@ -310,16 +322,17 @@ impl CompoundController {
         // Calculate total profit
         self.state.total_profit = new_capital - self.state.initial_capital;
 
-        // Check for tier change
-        let new_tier = if new_capital < 25.0 {
-            CapitalTier::Tier1
-        } else if new_capital < 100.0 {
-            CapitalTier::Tier2
-        } else if new_capital < 500.0 {
-            CapitalTier::Tier3
-        } else {
-            CapitalTier::Tier4
-        };
+        // Check for tier change; dropping back needs a clear move below the threshold
+        let mut new_tier = Self::tier_for_capital(&self.config.tier_thresholds, new_capital);
+        if Self::tier_index(new_tier) < Self::tier_index(self.state.current_tier) {
+            let buffered = new_capital * (1.0 + self.config.hysteresis_pct / 100.0);
+            let buffered_tier = Self::tier_for_capital(&self.config.tier_thresholds, buffered);
+            if Self::tier_index(buffered_tier) >= Self::tier_index(self.state.current_tier) {
+                new_tier = self.state.current_tier;
+            } else {
+                new_tier = buffered_tier;
+            }
+        }
 
         if new_tier != self.state.current_tier {
             // Tier has changed
@@ -328,11 +341,11 @@ impl CompoundController {
 
             // Log tier change
             info!(
-                "Capital tier changed to {:?}: ${:.2} - Max leverage: {}x, Position size: {}%, Max trades: {}",
+                "Capital tier changed to {:?}: ${:.2} - Max leverage: {}x, Position size: {} USDT, Max trades: {}",
                 new_tier,
                 new_capital,
                 self.state.current_strategy.max_leverage,
-                self.state.current_strategy.position_size_pct,
+                self.state.current_strategy.position_size_usdt,
                 self.state.current_strategy.max_concurrent_trades
             );
 
@@ -342,13 +355,50 @@ impl CompoundController {
         // No return value
     }
 
+    /// Tier for a capital level
+    fn tier_for_capital(thresholds: &[f64; 3], capital: f64) -> CapitalTier {
+        if capital < thresholds[0] {
+            CapitalTier::Tier1
+        } else if capital < thresholds[1] {
+            CapitalTier::Tier2
+        } else if capital < thresholds[2] {
+            CapitalTier::Tier3
+        } else {
+            CapitalTier::Tier4
+        }
+    }
+
+    fn tier_index(tier: CapitalTier) -> usize {
+        match tier {
+            CapitalTier::Tier1 => 0,
+            CapitalTier::Tier2 => 1,
+            CapitalTier::Tier3 => 2,
+            CapitalTier::Tier4 => 3,
+        }
+    }
+
+    /// Notional per position of the current tier in USDT
+    pub fn get_position_size_usdt(&self) -> f64 {
+        self.state.current_strategy.position_size_usdt
+    }
+
+    /// Set the sizing multiplier of the drawdown throttle
+    pub fn set_drawdown_multiplier(&mut self, multiplier: f64) {
+        self.drawdown_multiplier = multiplier.clamp(0.0, 1.0);
+    }
+
+    /// Maximum concurrent positions of the current tier
+    pub fn get_max_concurrent_positions(&self) -> usize {
+        self.state.current_strategy.max_concurrent_trades
+    }
+
     /// Calculate position size
     pub fn calculate_position_size(&self, confidence: f64, risk_factor: f64) -> f64 {
         // Base position size as percentage of capital
         let base_pct = self.state.current_strategy.position_size_pct;
 
-        // Adjust based on confidence and risk
-        let adjusted_pct = base_pct * confidence * risk_factor;
+        // Adjust based on confidence, risk and drawdown
+        let adjusted_pct = base_pct * confidence * risk_factor * self.drawdown_multiplier;
 
         // Calculate actual position size
         let position_size = self.state.current_capital * (adjusted_pct / 100.0);
@@ -498,7 +548,7 @@ impl Agent for CompoundController {
         Ok(())
     }
 
-    async fn update(&mut self) -> Result<()> {
+    async fn tick(&mut self) -> Result<()> {
         if !self.running {
             return Ok(());
         }
@@ -536,6 +586,10 @@ impl Agent for CompoundController {
     fn get_config(&self) -> Box<dyn AgentConfig> {
         Box::new(self.config.clone())
     }
+
+    fn topics(&self) -> Vec<String> {
+        vec!["system.#".to_string()]
+    }
 }
 
 #[cfg(test)]
@@ -588,4 +642,26 @@ mod tests {
         assert!(size1 > size2);
         assert!(size1 <= 100.0);
     }
+
+    #[test]
+    fn test_tier_hysteresis_and_limits() {
+        let mut controller = CompoundController::new(
+            CompoundControllerConfig::default(),
+            Arc::new(MessageBus::new()),
+            12.0,
+        );
+        assert_eq!(controller.get_position_size_usdt(), 5.0);
+
+        controller.update_capital(26.0);
+        assert_eq!(controller.get_state().current_tier, CapitalTier::Tier2);
+        assert_eq!(controller.get_max_concurrent_positions(), 2);
+
+        // Dipping just under the threshold keeps the tier
+        controller.update_capital(24.5);
+        assert_eq!(controller.get_state().current_tier, CapitalTier::Tier2);
+
+        // A clear drop falls back
+        controller.update_capital(23.0);
+        assert_eq!(controller.get_state().current_tier, CapitalTier::Tier1);
+    }
 }
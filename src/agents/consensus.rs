@@ -0,0 +1,298 @@
+//! Consensus Voting
+//!
+//! This module implements the voting protocol the agent coordinator decides
+//! with. Every analysis agent submits a vote with a direction, a confidence and
+//! its rationale; each vote counts with a weight derived from the agent's
+//! `FeedbackLoop` performance record. The zero-loss enforcer and the risk
+//! manager do not vote but may veto, which turns any outcome into a hold.
+
+use std::collections::HashMap;
+use serde::{Deserialize, Serialize};
+use tracing::{debug, warn};
+
+use crate::agents::feedback_loop::AgentPerformance;
+use crate::agents::memory_node::AgentContribution;
+
+/// Direction an agent votes for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum VoteDirection {
+    /// Enter long
+    Long,
+
+    /// Enter short
+    Short,
+
+    /// Stay out
+    Neutral,
+}
+
+/// Vote submitted by an agent
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AgentVote {
+    /// Voting agent
+    pub agent: String,
+
+    /// Direction voted for
+    pub direction: VoteDirection,
+
+    /// Confidence (0-100)
+    pub confidence: f64,
+
+    /// Why the agent votes this way
+    pub rationale: String,
+
+    /// Weight applied to the vote
+    #[serde(default)]
+    pub weight: f64,
+}
+
+impl AgentVote {
+    /// Create a vote; the weight is applied when the votes are tallied
+    pub fn new(agent: &str, direction: VoteDirection, confidence: f64, rationale: String) -> Self {
+        Self {
+            agent: agent.to_string(),
+            direction,
+            confidence: confidence.clamp(0.0, 100.0),
+            rationale,
+            weight: 0.0,
+        }
+    }
+}
+
+/// Veto raised against a trade
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Veto {
+    /// Vetoing agent
+    pub agent: String,
+
+    /// Reason for the veto
+    pub reason: String,
+}
+
+impl Veto {
+    /// Create a veto
+    pub fn new(agent: &str, reason: String) -> Self {
+        Self {
+            agent: agent.to_string(),
+            reason,
+        }
+    }
+}
+
+/// Consensus settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ConsensusConfig {
+    /// Weight of agents without a performance record
+    pub default_weight: f64,
+
+    /// Minimum weight any agent can have
+    pub min_weight: f64,
+
+    /// Weighted support (0-100) the winning direction needs
+    pub min_support: f64,
+
+    /// Agents whose veto is honored
+    pub veto_agents: Vec<String>,
+}
+
+impl Default for ConsensusConfig {
+    fn default() -> Self {
+        Self {
+            default_weight: 1.0,
+            min_weight: 0.05,
+            min_support: 50.0,
+            veto_agents: vec!["ZeroLossEnforcer".to_string(), "RiskManager".to_string()],
+        }
+    }
+}
+
+/// Result of a vote
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConsensusOutcome {
+    /// Winning direction
+    pub direction: VoteDirection,
+
+    /// Weighted support of the winning direction (0-100)
+    pub confidence: f64,
+
+    /// Weighted support for long (0-100)
+    pub long_support: f64,
+
+    /// Weighted support for short (0-100)
+    pub short_support: f64,
+
+    /// Weighted votes
+    pub votes: Vec<AgentVote>,
+
+    /// Honored vetoes
+    pub vetoes: Vec<Veto>,
+}
+
+impl ConsensusOutcome {
+    /// Whether a veto overruled the vote
+    pub fn is_vetoed(&self) -> bool {
+        !self.vetoes.is_empty()
+    }
+
+    /// What each voter contributed to a trade in `direction`
+    pub fn contributions(&self, direction: VoteDirection) -> Vec<AgentContribution> {
+        self.votes.iter()
+            .map(|vote| AgentContribution {
+                agent: vote.agent.clone(),
+                agreed: vote.direction == direction,
+                confidence: vote.confidence,
+                weight: vote.weight,
+            })
+            .collect()
+    }
+
+    /// Votes and vetoes as a single line
+    pub fn rationale(&self) -> String {
+        let mut parts: Vec<String> = self.votes.iter()
+            .map(|v| format!("{} {:?} {:.0}% x{:.2} ({})", v.agent, v.direction, v.confidence, v.weight, v.rationale))
+            .collect();
+        parts.extend(self.vetoes.iter().map(|v| format!("VETO by {}: {}", v.agent, v.reason)));
+        format!(
+            "Consensus {:?} (long {:.1}, short {:.1}): {}",
+            self.direction, self.long_support, self.short_support, parts.join("; ")
+        )
+    }
+}
+
+/// Performance-weighted consensus voting
+pub struct ConsensusVoting {
+    /// Settings
+    config: ConsensusConfig,
+
+    /// Weight by agent name
+    weights: HashMap<String, f64>,
+}
+
+impl ConsensusVoting {
+    /// Create a new consensus vote
+    pub fn new(config: ConsensusConfig) -> Self {
+        Self {
+            config,
+            weights: HashMap::new(),
+        }
+    }
+
+    /// Settings
+    pub fn get_config(&self) -> &ConsensusConfig {
+        &self.config
+    }
+
+    /// Weight of an agent's vote
+    pub fn get_weight(&self, agent: &str) -> f64 {
+        self.weights.get(agent).copied().unwrap_or(self.config.default_weight)
+    }
+
+    /// Set the weight of an agent's vote
+    pub fn set_weight(&mut self, agent: &str, weight: f64) {
+        self.weights.insert(agent.to_string(), weight.max(self.config.min_weight));
+    }
+
+    /// Derive vote weights from `FeedbackLoop` performance records
+    ///
+    /// As in the strategy ensemble, the performance score (-1.0 to 1.0) is mapped
+    /// to (0.0 to 2.0) and scaled by the record's confidence.
+    pub fn update_weights(&mut self, performance: &HashMap<String, AgentPerformance>) {
+        for (agent, record) in performance {
+            let weight = (1.0 + record.score) * record.confidence.max(0.1);
+            self.set_weight(agent, weight);
+        }
+    }
+
+    /// Tally the votes; any veto from a veto agent turns the outcome neutral
+    pub fn tally(&self, votes: Vec<AgentVote>, vetoes: Vec<Veto>) -> ConsensusOutcome {
+        let votes: Vec<AgentVote> = votes.into_iter()
+            .map(|mut vote| {
+                vote.weight = self.get_weight(&vote.agent);
+                vote
+            })
+            .collect();
+
+        let total_weight: f64 = votes.iter().map(|v| v.weight).sum();
+        let support = |direction: VoteDirection| {
+            if total_weight <= 0.0 {
+                return 0.0;
+            }
+            votes.iter()
+                .filter(|v| v.direction == direction)
+                .map(|v| v.weight * v.confidence)
+                .sum::<f64>() / total_weight
+        };
+        let long_support = support(VoteDirection::Long);
+        let short_support = support(VoteDirection::Short);
+
+        let vetoes: Vec<Veto> = vetoes.into_iter()
+            .filter(|veto| {
+                let honored = self.config.veto_agents.contains(&veto.agent);
+                if !honored {
+                    warn!("Ignoring veto from {}, which holds no veto right", veto.agent);
+                }
+                honored
+            })
+            .collect();
+
+        let (direction, confidence) = if !vetoes.is_empty() {
+            (VoteDirection::Neutral, 0.0)
+        } else if long_support > short_support && long_support >= self.config.min_support {
+            (VoteDirection::Long, long_support)
+        } else if short_support > long_support && short_support >= self.config.min_support {
+            (VoteDirection::Short, short_support)
+        } else {
+            (VoteDirection::Neutral, 100.0 - long_support.max(short_support))
+        };
+
+        debug!("Consensus {:?}: long {:.1}, short {:.1}, {} votes, {} vetoes",
+               direction, long_support, short_support, votes.len(), vetoes.len());
+
+        ConsensusOutcome {
+            direction,
+            confidence,
+            long_support,
+            short_support,
+            votes,
+            vetoes,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vote(agent: &str, direction: VoteDirection, confidence: f64) -> AgentVote {
+        AgentVote::new(agent, direction, confidence, String::new())
+    }
+
+    #[test]
+    fn test_weighted_vote_and_veto() {
+        let mut voting = ConsensusVoting::new(ConsensusConfig::default());
+        let votes = || vec![
+            vote("MarketAnalyzer", VoteDirection::Long, 80.0),
+            vote("MultiFactorStrategy", VoteDirection::Short, 80.0),
+        ];
+
+        // Equal weights leave the vote split
+        let outcome = voting.tally(votes(), Vec::new());
+        assert_eq!(outcome.direction, VoteDirection::Neutral);
+        assert_eq!(outcome.long_support, 40.0);
+
+        // A strong track record tips the balance
+        voting.set_weight("MarketAnalyzer", 3.0);
+        let outcome = voting.tally(votes(), Vec::new());
+        assert_eq!(outcome.direction, VoteDirection::Long);
+        assert_eq!(outcome.confidence, 60.0);
+
+        // Only agents with a veto right can block the trade
+        let outcome = voting.tally(votes(), vec![Veto::new("MultiFactorStrategy", "disagrees".to_string())]);
+        assert_eq!(outcome.direction, VoteDirection::Long);
+        let outcome = voting.tally(votes(), vec![Veto::new("RiskManager", "risk too high".to_string())]);
+        assert_eq!(outcome.direction, VoteDirection::Neutral);
+        assert!(outcome.is_vetoed());
+        assert!(outcome.rationale().contains("VETO by RiskManager"));
+    }
+}
@@ -1,20 +1,34 @@
 //! Feedback Loop Agent
 //!
 //! This agent is responsible for agent reinforcement and mutation eligibility
-//! based on trade performance.
+//! based on trade performance. Agents that declare parameter bounds are mutated
+//! by perturbing their parameters within those bounds; every mutation schedules
+//! an A/B evaluation of parent and child through the backtester, and the child's
+//! parameters are adopted only if it wins. Performance and mutation records can be
+//! persisted to disk and restored on restart.
 
 use std::collections::{HashMap, VecDeque};
+use std::path::Path;
 use chrono::{DateTime, Utc, Duration};
 use serde::{Deserialize, Serialize};
 use anyhow::Result;
 use tracing::{info, debug, warn};
 use async_trait::async_trait;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use uuid::Uuid;
 
 use crate::engine::agent_trait::{Agent, AgentContext, AgentConfig};
+use crate::engine::json_store::{load_json, save_json};
 use crate::engine::message_bus::{BusMessage, Message, MessageBus, MessageType};
 use std::sync::Arc;
 use tokio::sync::RwLock;
 use crate::agents::memory_node::{TradeMemory, TradeOutcome, ReinforcementFeedback};
+use crate::monitoring::surveillance::{SurveillanceAlert, SURVEILLANCE_TOPIC};
+use crate::strategy::registry::StrategyControl;
+use crate::strategy::optimizer::{FitnessScore, ParameterRange, ParameterSet};
+use crate::backtest::BacktestResult;
+use crate::backtest::parallel::{BacktestJob, ParallelBacktester};
 
 /// Maximum number of agent performance records to keep
 const MAX_AGENT_RECORDS: usize = 100;
@@ -106,8 +120,70 @@ pub struct MutationRecord {
     pub success: Option<bool>,
 }
 
+/// A/B evaluation of a mutation waiting for the backtester
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AbEvaluation {
+    /// Mutation being evaluated
+    pub mutation_id: String,
+
+    /// Mutated agent
+    pub agent: String,
+
+    /// Parameters before the mutation (A)
+    pub baseline: ParameterSet,
+
+    /// Mutated parameters (B)
+    pub candidate: ParameterSet,
+
+    /// When the evaluation was scheduled
+    pub scheduled_at: DateTime<Utc>,
+}
+
+/// Result of an A/B evaluation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AbOutcome {
+    /// Evaluated mutation
+    pub mutation_id: String,
+
+    /// Mutated agent
+    pub agent: String,
+
+    /// Mean scalar fitness of the baseline across symbols
+    pub baseline_fitness: f64,
+
+    /// Mean scalar fitness of the candidate across symbols
+    pub candidate_fitness: f64,
+
+    /// Whether the candidate's parameters were adopted
+    pub adopted: bool,
+}
+
+/// Persisted feedback loop state
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FeedbackLoopSnapshot {
+    /// When the snapshot was taken
+    pub saved_at: DateTime<Utc>,
+
+    /// Agent state
+    pub state: FeedbackLoopState,
+
+    /// Agent performance records
+    pub agent_performance: HashMap<String, AgentPerformance>,
+
+    /// Mutation records
+    pub mutations: Vec<MutationRecord>,
+
+    /// Current parameters by agent
+    #[serde(default)]
+    pub parameters: HashMap<String, ParameterSet>,
+
+    /// A/B evaluations not yet run
+    #[serde(default)]
+    pub pending_evaluations: Vec<AbEvaluation>,
+}
+
 /// Feedback Loop configuration
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FeedbackLoopConfig {
     /// Learning rate
     pub learning_rate: f64,
@@ -120,6 +196,18 @@ pub struct FeedbackLoopConfig {
 
     /// Consecutive failure threshold
     pub consecutive_failure_threshold: usize,
+
+    /// Mutation step as a fraction of each parameter's range
+    #[serde(default = "default_mutation_strength")]
+    pub mutation_strength: f64,
+
+    /// RNG seed for reproducible mutations (random when unset)
+    #[serde(default)]
+    pub seed: Option<u64>,
+}
+
+fn default_mutation_strength() -> f64 {
+    0.1
 }
 
 impl Default for FeedbackLoopConfig {
@@ -129,6 +217,8 @@ impl Default for FeedbackLoopConfig {
             mutation_threshold: 0.6,
             kill_threshold: 0.3,
             consecutive_failure_threshold: 5,
+            mutation_strength: default_mutation_strength(),
+            seed: None,
         }
     }
 }
@@ -150,6 +240,18 @@ pub struct FeedbackLoop {
     /// Mutation records
     mutations: Vec<MutationRecord>,
 
+    /// Declared parameter bounds by agent
+    parameter_space: HashMap<String, HashMap<String, ParameterRange>>,
+
+    /// Current parameters by agent
+    parameters: HashMap<String, ParameterSet>,
+
+    /// A/B evaluations not yet run
+    pending_evaluations: Vec<AbEvaluation>,
+
+    /// Random number generator driving mutations
+    rng: StdRng,
+
     /// Running flag
     running: bool,
 }
@@ -157,6 +259,11 @@ pub struct FeedbackLoop {
 impl FeedbackLoop {
     /// Create a new feedback loop
     pub fn new(config: FeedbackLoopConfig, message_bus: Arc<MessageBus>) -> Self {
+        let rng = match config.seed {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => StdRng::from_entropy(),
+        };
+
         Self {
             config,
             message_bus,
@@ -169,6 +276,10 @@ impl FeedbackLoop {
             },
             agent_performance: HashMap::new(),
             mutations: Vec::new(),
+            parameter_space: HashMap::new(),
+            parameters: HashMap::new(),
+            pending_evaluations: Vec::new(),
+            rng,
             running: false,
         }
     }
@@ -182,38 +293,48 @@ impl FeedbackLoop {
             self.update_agent_performance(agent_name, trade, *adjustment)?;
         }
 
-        // Check for mutation eligibility
+        // Check for mutation eligibility; agents with an evaluation in flight wait for its result
         let mutation_candidates = self.find_mutation_candidates();
         for agent_name in mutation_candidates {
+            if self.pending_evaluations.iter().any(|e| e.agent == agent_name) {
+                continue;
+            }
+
+            let mutation = match self.mutate_agent(&agent_name) {
+                Some(mutation) => mutation,
+                None => {
+                    let Some(performance) = self.agent_performance.get(&agent_name) else {
+                        continue;
+                    };
+                    let mutation = MutationRecord {
+                        id: format!("mutation-{}-{}", agent_name, Uuid::new_v4()),
+                        parent_agent: agent_name.clone(),
+                        mutated_agent: format!("{}-mutated", agent_name),
+                        timestamp: Utc::now(),
+                        parameters: HashMap::new(),
+                        description: "Mutation requested due to high performance".to_string(),
+                        performance_before: performance.score,
+                        performance_after: None,
+                        success: None,
+                    };
+                    self.mutations.push(mutation.clone());
+                    self.state.mutations_triggered += 1;
+                    mutation
+                }
+            };
+
+            // Create mutation message
             if let Some(performance) = self.agent_performance.get(&agent_name) {
-                // Create mutation message
-                let message = Message::Custom(
+                messages.push(Message::Custom(
                     "mutation_request".to_string(),
                     serde_json::json!({
                         "agent_name": agent_name,
+                        "mutation_id": mutation.id,
+                        "parameters": mutation.parameters,
                         "performance": performance,
                         "timestamp": Utc::now(),
                     }),
-                );
-
-                messages.push(message);
-
-                // Record mutation
-                let mutation_id = format!("mutation-{}-{}", agent_name, Utc::now().timestamp_millis());
-                let mutation = MutationRecord {
-                    id: mutation_id,
-                    parent_agent: agent_name.clone(),
-                    mutated_agent: format!("{}-mutated", agent_name),
-                    timestamp: Utc::now(),
-                    parameters: HashMap::new(),
-                    description: "Mutation requested due to high performance".to_string(),
-                    performance_before: performance.score,
-                    performance_after: None,
-                    success: None,
-                };
-
-                self.mutations.push(mutation);
-                self.state.mutations_triggered += 1;
+                ));
             }
         }
 
@@ -333,6 +454,263 @@ impl FeedbackLoop {
             .collect()
     }
 
+    /// Act on a post-trade surveillance alert
+    ///
+    /// Returns the alert message and, when the alert calls for it, the control
+    /// message disabling the offending strategy.
+    pub fn process_surveillance_alert(&mut self, alert: &SurveillanceAlert) -> Vec<Message> {
+        let mut messages = vec![Message::Custom(
+            SURVEILLANCE_TOPIC.to_string(),
+            serde_json::to_value(alert).unwrap_or(serde_json::Value::Null),
+        )];
+
+        if alert.disable_strategy {
+            warn!("Disabling strategy {} after {:?} alert: {}", alert.strategy, alert.kind, alert.details);
+            if let Some(performance) = self.agent_performance.get_mut(&alert.strategy) {
+                performance.kill_eligible = true;
+            }
+            messages.push(StrategyControl::Disable { strategy: alert.strategy.clone() }.to_message());
+            self.state.agents_killed += 1;
+        }
+
+        messages
+    }
+
+    /// Declare the parameter bounds of an agent so it can be mutated
+    ///
+    /// `current` is clamped into the bounds. Parameters restored from a snapshot
+    /// take precedence over `current`.
+    pub fn declare_parameters(&mut self, agent_name: &str, space: HashMap<String, ParameterRange>, current: ParameterSet) {
+        let current = self.parameters.remove(agent_name).unwrap_or(current);
+        let normalized = space.iter()
+            .map(|(name, range)| (name.clone(), range.normalize(current.get(name).copied().unwrap_or(range.min))))
+            .collect();
+
+        self.parameters.insert(agent_name.to_string(), normalized);
+        self.parameter_space.insert(agent_name.to_string(), space);
+    }
+
+    /// Mutate an agent's parameters within their declared bounds
+    ///
+    /// Every parameter moves by up to `mutation_strength` of its range; the child is
+    /// recorded and an A/B evaluation against the parent scheduled. Returns `None`
+    /// for agents without declared bounds.
+    pub fn mutate_agent(&mut self, agent_name: &str) -> Option<MutationRecord> {
+        let space = self.parameter_space.get(agent_name)?;
+        let baseline = self.parameters.get(agent_name)?.clone();
+
+        // Walk the parameters in a stable order so seeded runs are reproducible
+        let mut names: Vec<&String> = space.keys().collect();
+        names.sort();
+
+        let mut candidate = baseline.clone();
+        for name in names {
+            let range = &space[name];
+            let delta = self.rng.gen_range(-1.0..=1.0) * (range.max - range.min) * self.config.mutation_strength;
+            let value = baseline.get(name).copied().unwrap_or(range.min) + delta;
+            candidate.insert(name.clone(), range.normalize(value));
+        }
+
+        let timestamp = Utc::now();
+        let mutation = MutationRecord {
+            id: format!("mutation-{}-{}", agent_name, Uuid::new_v4()),
+            parent_agent: agent_name.to_string(),
+            mutated_agent: format!("{}-mutated", agent_name),
+            timestamp,
+            parameters: candidate.clone(),
+            description: "Parameters perturbed within declared bounds, pending A/B evaluation".to_string(),
+            performance_before: self.agent_performance.get(agent_name).map(|p| p.score).unwrap_or(0.0),
+            performance_after: None,
+            success: None,
+        };
+
+        info!("Mutated {} ({}), A/B evaluation scheduled", agent_name, mutation.id);
+        self.pending_evaluations.push(AbEvaluation {
+            mutation_id: mutation.id.clone(),
+            agent: agent_name.to_string(),
+            baseline,
+            candidate,
+            scheduled_at: timestamp,
+        });
+        self.mutations.push(mutation.clone());
+        self.state.mutations_triggered += 1;
+
+        Some(mutation)
+    }
+
+    /// Run the scheduled A/B evaluations through the backtester
+    ///
+    /// Parent and child are backtested on every symbol; the child's parameters are
+    /// adopted if its mean scalar fitness beats the parent's. Evaluations whose
+    /// backtests all fail are dropped and their mutation marked unsuccessful. If the
+    /// backtester itself fails, the evaluations stay scheduled.
+    pub fn run_ab_evaluations<F>(&mut self, backtester: &ParallelBacktester, symbols: &[String], evaluate: F) -> Result<Vec<AbOutcome>>
+    where
+        F: Fn(&str, &BacktestJob) -> Result<BacktestResult> + Send + Sync,
+    {
+        if symbols.is_empty() {
+            return Ok(Vec::new());
+        }
+        let evaluations = self.take_pending_evaluations();
+        if evaluations.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        match Self::backtest_evaluations(&evaluations, backtester, symbols, evaluate) {
+            Ok(fitness) => self.apply_ab_results(evaluations, fitness),
+            Err(e) => {
+                self.restore_pending_evaluations(evaluations);
+                Err(e)
+            }
+        }
+    }
+
+    /// Take the scheduled A/B evaluations, e.g. to backtest them off the async runtime
+    pub fn take_pending_evaluations(&mut self) -> Vec<AbEvaluation> {
+        std::mem::take(&mut self.pending_evaluations)
+    }
+
+    /// Put taken evaluations back ahead of any scheduled since
+    pub fn restore_pending_evaluations(&mut self, evaluations: Vec<AbEvaluation>) {
+        self.pending_evaluations.splice(0..0, evaluations);
+    }
+
+    /// Backtest parent and child of each evaluation on every symbol; returns the
+    /// baseline and candidate fitness scores per evaluation
+    pub fn backtest_evaluations<F>(
+        evaluations: &[AbEvaluation],
+        backtester: &ParallelBacktester,
+        symbols: &[String],
+        evaluate: F,
+    ) -> Result<Vec<(Vec<f64>, Vec<f64>)>>
+    where
+        F: Fn(&str, &BacktestJob) -> Result<BacktestResult> + Send + Sync,
+    {
+        // Jobs alternate baseline and candidate per symbol, evaluation after evaluation
+        let mut jobs = Vec::with_capacity(evaluations.len() * symbols.len() * 2);
+        for evaluation in evaluations {
+            for symbol in symbols {
+                for parameters in [&evaluation.baseline, &evaluation.candidate] {
+                    jobs.push(BacktestJob { index: jobs.len(), symbol: symbol.clone(), parameters: parameters.clone() });
+                }
+            }
+        }
+
+        let per_evaluation = symbols.len() * 2;
+        let outcomes = backtester.run(jobs, |job| evaluate(&evaluations[job.index / per_evaluation].agent, job))?;
+
+        let mut fitness = vec![(Vec::new(), Vec::new()); evaluations.len()];
+        for outcome in outcomes {
+            if let Some(result) = outcome.result {
+                let score = FitnessScore::from_result(&result).scalar();
+                let (baseline, candidate) = &mut fitness[outcome.job.index / per_evaluation];
+                if outcome.job.index % 2 == 0 { baseline.push(score) } else { candidate.push(score) }
+            }
+        }
+        Ok(fitness)
+    }
+
+    /// Adopt or reject each evaluation's mutation from its backtested fitness
+    pub fn apply_ab_results(&mut self, evaluations: Vec<AbEvaluation>, fitness: Vec<(Vec<f64>, Vec<f64>)>) -> Result<Vec<AbOutcome>> {
+        let mean = |scores: &[f64]| scores.iter().sum::<f64>() / scores.len() as f64;
+        let mut results = Vec::with_capacity(evaluations.len());
+        for (evaluation, (baseline, candidate)) in evaluations.into_iter().zip(fitness) {
+            if baseline.is_empty() || candidate.is_empty() {
+                warn!("A/B evaluation of {} failed: no completed backtests", evaluation.mutation_id);
+                self.record_mutation_result(&evaluation.mutation_id, false, 0.0)?;
+                continue;
+            }
+
+            let baseline_fitness = mean(&baseline);
+            let candidate_fitness = mean(&candidate);
+            let adopted = candidate_fitness > baseline_fitness;
+            self.record_mutation_result(&evaluation.mutation_id, adopted, candidate_fitness)?;
+            if adopted {
+                info!("Adopting mutation {} of {}: fitness {:.4} > {:.4}",
+                      evaluation.mutation_id, evaluation.agent, candidate_fitness, baseline_fitness);
+                self.parameters.insert(evaluation.agent.clone(), evaluation.candidate);
+            } else {
+                debug!("Rejecting mutation {} of {}: fitness {:.4} <= {:.4}",
+                       evaluation.mutation_id, evaluation.agent, candidate_fitness, baseline_fitness);
+            }
+
+            results.push(AbOutcome {
+                mutation_id: evaluation.mutation_id,
+                agent: evaluation.agent,
+                baseline_fitness,
+                candidate_fitness,
+                adopted,
+            });
+        }
+
+        Ok(results)
+    }
+
+    /// Snapshot of the persistent state
+    pub fn snapshot(&self) -> FeedbackLoopSnapshot {
+        FeedbackLoopSnapshot {
+            saved_at: Utc::now(),
+            state: self.state.clone(),
+            agent_performance: self.agent_performance.clone(),
+            mutations: self.mutations.clone(),
+            parameters: self.parameters.clone(),
+            pending_evaluations: self.pending_evaluations.clone(),
+        }
+    }
+
+    /// Restore performance and mutation records from a snapshot
+    ///
+    /// Restored parameters are clamped into bounds already declared.
+    pub fn restore(&mut self, snapshot: FeedbackLoopSnapshot) {
+        self.state = snapshot.state;
+        self.agent_performance = snapshot.agent_performance;
+        self.mutations = snapshot.mutations;
+        self.pending_evaluations = snapshot.pending_evaluations;
+        for (agent, parameters) in snapshot.parameters {
+            let parameters = match self.parameter_space.get(&agent) {
+                Some(space) => parameters.into_iter()
+                    .map(|(name, value)| {
+                        let value = space.get(&name).map_or(value, |range| range.normalize(value));
+                        (name, value)
+                    })
+                    .collect(),
+                None => parameters,
+            };
+            self.parameters.insert(agent, parameters);
+        }
+        self.state.agents_tracked = self.agent_performance.len();
+    }
+
+    /// Persist the state to a JSON file; writes go to a temp file that is then renamed
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        save_json(path, &self.snapshot())?;
+        debug!("Feedback loop state saved to {}", path.display());
+        Ok(())
+    }
+
+    /// Restore the state from a JSON file; returns whether the file existed
+    pub fn load(&mut self, path: impl AsRef<Path>) -> Result<bool> {
+        let path = path.as_ref();
+        let Some(snapshot) = load_json::<FeedbackLoopSnapshot>(path)? else {
+            return Ok(false);
+        };
+        info!("Restored {} agent records and {} mutations from {}",
+              snapshot.agent_performance.len(), snapshot.mutations.len(), path.display());
+        self.restore(snapshot);
+        Ok(true)
+    }
+
+    /// Get the current parameters of an agent
+    pub fn get_parameters(&self, agent_name: &str) -> Option<&ParameterSet> {
+        self.parameters.get(agent_name)
+    }
+
+    /// Get the A/B evaluations not yet run
+    pub fn get_pending_evaluations(&self) -> &[AbEvaluation] {
+        &self.pending_evaluations
+    }
+
     /// Record mutation result
     pub fn record_mutation_result(&mut self, mutation_id: &str, success: bool, performance: f64) -> Result<()> {
         if let Some(mutation) = self.mutations.iter_mut().find(|m| m.id == mutation_id) {
@@ -345,6 +723,12 @@ impl FeedbackLoop {
         Ok(())
     }
 
+    /// Record externally generated mutations, e.g. from the GA strategy optimizer
+    pub fn record_mutations(&mut self, records: Vec<MutationRecord>) {
+        self.state.mutations_triggered += records.len();
+        self.mutations.extend(records);
+    }
+
     /// Get agent performance
     pub fn get_agent_performance(&self, agent_name: &str) -> Option<&AgentPerformance> {
         self.agent_performance.get(agent_name)
@@ -437,7 +821,7 @@ impl Agent for FeedbackLoop {
         Ok(())
     }
 
-    async fn update(&mut self) -> Result<()> {
+    async fn tick(&mut self) -> Result<()> {
         if !self.running {
             return Ok(());
         }
@@ -468,6 +852,10 @@ impl Agent for FeedbackLoop {
     fn get_config(&self) -> Box<dyn AgentConfig> {
         Box::new(self.config.clone())
     }
+
+    fn topics(&self) -> Vec<String> {
+        vec!["performance.#".to_string()]
+    }
 }
 
 #[cfg(test)]
@@ -492,6 +880,7 @@ mod tests {
             leverage: 1.0,
             pnl: Some(100.0),
             roi: Some(2.0),
+            r_multiple: None,
             duration_seconds: Some(3600),
             contributing_agents: vec!["agent1".to_string(), "agent2".to_string()],
             agent_confidence: {
@@ -500,6 +889,7 @@ mod tests {
                 map.insert("agent2".to_string(), 0.6);
                 map
             },
+            agent_contributions: Vec::new(),
             market_conditions: crate::agents::memory_node::MarketConditions {
                 volatility: 0.5,
                 volume: 1000000.0,
@@ -511,6 +901,7 @@ mod tests {
                 open_interest: Some(500000000.0),
                 orderbook_imbalance: Some(0.2),
             },
+            embedding: Vec::new(),
             outcome: Some(TradeOutcome::Profit),
             fractal_signature: Some(vec![0.1, 0.2, 0.3, 0.4, 0.5]),
             tags: vec!["strategy:breakout".to_string(), "volatility:high".to_string()],
@@ -548,4 +939,53 @@ mod tests {
         assert_eq!(feedback_loop.get_state().trades_processed, 1);
         assert_eq!(feedback_loop.get_state().agents_tracked, 2);
     }
+
+    #[test]
+    fn test_bounded_mutation_ab_and_persistence() {
+        let config = FeedbackLoopConfig { seed: Some(11), mutation_strength: 0.5, ..Default::default() };
+        let mut feedback_loop = FeedbackLoop::new(config.clone(), Arc::new(MessageBus::new()));
+        feedback_loop.declare_parameters(
+            "breakout",
+            HashMap::from([("threshold".to_string(), ParameterRange::new(0.0, 1.0))]),
+            HashMap::from([("threshold".to_string(), 0.9)]),
+        );
+
+        // Children always stay within the declared bounds
+        let mutation = feedback_loop.mutate_agent("breakout").unwrap();
+        let child = mutation.parameters["threshold"];
+        assert!((0.0..=1.0).contains(&child));
+        assert_eq!(feedback_loop.get_pending_evaluations().len(), 1);
+        assert!(feedback_loop.mutate_agent("unknown").is_none());
+
+        // Taken evaluations can be put back, e.g. after the backtester failed
+        let taken = feedback_loop.take_pending_evaluations();
+        feedback_loop.restore_pending_evaluations(taken);
+        assert_eq!(feedback_loop.get_pending_evaluations().len(), 1);
+
+        // A higher threshold backtests better; the child is adopted only if it is higher
+        let symbols = vec!["BTCUSDT".to_string(), "ETHUSDT".to_string()];
+        let outcomes = feedback_loop.run_ab_evaluations(&ParallelBacktester::new(), &symbols, |_, job| {
+            let mut result = BacktestResult::new(
+                crate::backtest::BacktestConfig::new(0, 86_400, 1_000.0, vec![job.symbol.clone()]),
+                Vec::new(),
+            );
+            result.total_return = job.parameters["threshold"] * 10.0;
+            Ok(result)
+        }).unwrap();
+        assert_eq!(outcomes.len(), 1);
+        assert_eq!(outcomes[0].adopted, child > 0.9);
+        let expected = if child > 0.9 { child } else { 0.9 };
+        assert_eq!(feedback_loop.get_parameters("breakout").unwrap()["threshold"], expected);
+        assert!(feedback_loop.get_pending_evaluations().is_empty());
+        assert_eq!(feedback_loop.get_mutations()[0].success, Some(child > 0.9));
+
+        // Records survive a restart
+        let path = std::env::temp_dir().join(format!("omni_feedback_{}.json", std::process::id()));
+        feedback_loop.save(&path).unwrap();
+        let mut restored = FeedbackLoop::new(config, Arc::new(MessageBus::new()));
+        assert!(restored.load(&path).unwrap());
+        assert_eq!(restored.get_mutations().len(), 1);
+        assert_eq!(restored.get_parameters("breakout").unwrap()["threshold"], expected);
+        std::fs::remove_file(&path).ok();
+    }
 }
@@ -0,0 +1,404 @@
+//! Funding Rate and Basis Agent
+//!
+//! This agent tracks the funding rate and the perp-spot basis of every symbol in
+//! the scanned universe. Each poll yields a `FundingSnapshot` with the funding
+//! rate, its annualized carry, the basis against the spot price and a z-score of
+//! the rate against its own recent history. Rates far from their history, or
+//! beyond an absolute threshold, flag the crowded side of the market as a
+//! contrarian signal; the annualized carry is the input the funding-harvest
+//! strategy sizes its spot-perp legs from.
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::Arc;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use anyhow::Result;
+use async_trait::async_trait;
+use tokio::sync::RwLock;
+use tracing::{debug, info, warn};
+
+use crate::agents::asset_scanner_agent::{universe_from_message, UNIVERSE_TOPIC};
+use crate::engine::agent_trait::{Agent, AgentConfig, AgentContext};
+use crate::engine::message_bus::{BusMessage, Message, MessageBus};
+use crate::exchange::bybit::adapter::BybitAdapter;
+
+/// Topic used for funding and basis snapshots (`Message::Custom`)
+pub const FUNDING_TOPIC: &str = "funding_basis";
+
+/// Funding agent settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FundingAgentConfig {
+    /// Symbols tracked until the scanned universe is handed over
+    pub symbols: Vec<String>,
+
+    /// Seconds between polls
+    pub poll_interval_secs: i64,
+
+    /// Hours between funding payments
+    pub funding_interval_hours: f64,
+
+    /// Funding samples kept per symbol for the z-score
+    pub history_len: usize,
+
+    /// Samples needed before the z-score is used
+    pub min_history: usize,
+
+    /// Z-score beyond which funding is extreme
+    pub extreme_zscore: f64,
+
+    /// Absolute funding rate per interval beyond which funding is extreme
+    pub extreme_rate: f64,
+
+    /// Absolute basis (%) beyond which the basis is extreme
+    pub extreme_basis_pct: f64,
+}
+
+impl Default for FundingAgentConfig {
+    fn default() -> Self {
+        Self {
+            symbols: vec!["BTCUSDT".to_string(), "ETHUSDT".to_string()],
+            poll_interval_secs: 300,
+            funding_interval_hours: 8.0,
+            history_len: 90,
+            min_history: 10,
+            extreme_zscore: 2.0,
+            extreme_rate: 0.001,
+            extreme_basis_pct: 0.5,
+        }
+    }
+}
+
+/// Side of the market paying for its positioning
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CrowdedSide {
+    /// Longs pay shorts; the contrarian trade is short
+    Longs,
+
+    /// Shorts pay longs; the contrarian trade is long
+    Shorts,
+}
+
+/// Funding and basis of one symbol
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FundingSnapshot {
+    /// Symbol
+    pub symbol: String,
+
+    /// Time of the reading
+    pub timestamp: DateTime<Utc>,
+
+    /// Funding rate per interval
+    pub funding_rate: f64,
+
+    /// Funding rate annualized (%), earned by shorting the perp when positive
+    pub annualized_funding_pct: f64,
+
+    /// Perp mark price
+    pub mark_price: f64,
+
+    /// Spot price, if the symbol trades spot
+    pub spot_price: Option<f64>,
+
+    /// Perp premium over spot (%)
+    pub basis_pct: Option<f64>,
+
+    /// Funding rate against its recent history, once there is enough of it
+    pub funding_zscore: Option<f64>,
+
+    /// Crowded side when funding or basis is extreme
+    pub crowded: Option<CrowdedSide>,
+}
+
+impl FundingSnapshot {
+    /// Convert to a bus message
+    pub fn to_message(&self) -> Message {
+        Message::Custom(
+            FUNDING_TOPIC.to_string(),
+            serde_json::to_value(self).unwrap_or(Value::Null),
+        )
+    }
+
+    /// Parse a funding snapshot from a message, if it is one
+    pub fn from_message(message: &Message) -> Option<Self> {
+        match message {
+            Message::Custom(topic, payload) if topic == FUNDING_TOPIC => {
+                match serde_json::from_value(payload.clone()) {
+                    Ok(snapshot) => Some(snapshot),
+                    Err(e) => {
+                        warn!("Ignoring malformed funding snapshot: {}", e);
+                        None
+                    }
+                }
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Funding rate and basis agent
+pub struct FundingAgent {
+    /// Settings
+    config: FundingAgentConfig,
+
+    /// Exchange adapter
+    exchange: Arc<BybitAdapter>,
+
+    /// Message bus snapshots are published on
+    message_bus: Arc<MessageBus>,
+
+    /// Whether the agent is running
+    running: bool,
+
+    /// Symbols tracked
+    symbols: Vec<String>,
+
+    /// Last poll
+    last_poll: Option<DateTime<Utc>>,
+
+    /// Recent funding rates by symbol, oldest first
+    history: HashMap<String, VecDeque<f64>>,
+
+    /// Latest snapshot by symbol
+    snapshots: HashMap<String, FundingSnapshot>,
+}
+
+impl FundingAgent {
+    /// Create a new funding agent
+    pub fn new(config: FundingAgentConfig, exchange: Arc<BybitAdapter>, message_bus: Arc<MessageBus>) -> Self {
+        let symbols = config.symbols.clone();
+        Self {
+            config,
+            exchange,
+            message_bus,
+            running: false,
+            symbols,
+            last_poll: None,
+            history: HashMap::new(),
+            snapshots: HashMap::new(),
+        }
+    }
+
+    /// Track the symbols of the scanned universe
+    pub fn set_symbols(&mut self, symbols: Vec<String>) {
+        self.history.retain(|symbol, _| symbols.contains(symbol));
+        self.snapshots.retain(|symbol, _| symbols.contains(symbol));
+        self.symbols = symbols;
+    }
+
+    /// Symbols tracked
+    pub fn get_symbols(&self) -> &[String] {
+        &self.symbols
+    }
+
+    /// Record a reading and build its snapshot
+    pub fn record(
+        &mut self,
+        symbol: &str,
+        funding_rate: f64,
+        mark_price: f64,
+        spot_price: Option<f64>,
+        now: DateTime<Utc>,
+    ) -> FundingSnapshot {
+        let history = self.history.entry(symbol.to_string()).or_default();
+        let funding_zscore = if history.len() >= self.config.min_history.max(2) {
+            let n = history.len() as f64;
+            let mean = history.iter().sum::<f64>() / n;
+            let std_dev = (history.iter().map(|r| (r - mean).powi(2)).sum::<f64>() / n).sqrt();
+            if std_dev > 0.0 { Some((funding_rate - mean) / std_dev) } else { None }
+        } else {
+            None
+        };
+        history.push_back(funding_rate);
+        while history.len() > self.config.history_len {
+            history.pop_front();
+        }
+
+        let payments_per_year = 365.0 * 24.0 / self.config.funding_interval_hours.max(1.0);
+        let basis_pct = spot_price
+            .filter(|spot| *spot > 0.0)
+            .map(|spot| (mark_price - spot) / spot * 100.0);
+
+        let extreme_funding = funding_zscore.is_some_and(|z| z.abs() >= self.config.extreme_zscore)
+            || funding_rate.abs() >= self.config.extreme_rate;
+        let extreme_basis = basis_pct.is_some_and(|b| b.abs() >= self.config.extreme_basis_pct);
+        let crowded = if extreme_funding {
+            Some(if funding_rate > 0.0 { CrowdedSide::Longs } else { CrowdedSide::Shorts })
+        } else if extreme_basis {
+            basis_pct.map(|b| if b > 0.0 { CrowdedSide::Longs } else { CrowdedSide::Shorts })
+        } else {
+            None
+        };
+
+        let snapshot = FundingSnapshot {
+            symbol: symbol.to_string(),
+            timestamp: now,
+            funding_rate,
+            annualized_funding_pct: funding_rate * payments_per_year * 100.0,
+            mark_price,
+            spot_price,
+            basis_pct,
+            funding_zscore,
+            crowded,
+        };
+        self.snapshots.insert(symbol.to_string(), snapshot.clone());
+        snapshot
+    }
+
+    /// Read funding and prices of every tracked symbol
+    pub async fn poll(&mut self, now: DateTime<Utc>) -> Vec<FundingSnapshot> {
+        let mut snapshots = Vec::new();
+        for symbol in self.symbols.clone() {
+            let funding = match self.exchange.get_funding_rate(&symbol).await {
+                Ok(funding) => funding,
+                Err(e) => {
+                    warn!("Failed to get funding rate for {}: {}", symbol, e);
+                    continue;
+                }
+            };
+            let mark_price = match self.exchange.get_category_ticker(&symbol, "linear").await {
+                Ok(tickers) => tickers.first().map(|t| t.mark_price.unwrap_or(t.last_price)),
+                Err(e) => {
+                    warn!("Failed to get perp ticker for {}: {}", symbol, e);
+                    None
+                }
+            };
+            let Some(mark_price) = mark_price else {
+                continue;
+            };
+            // Not every perp has a spot market of the same name
+            let spot_price = self.exchange.get_category_ticker(&symbol, "spot").await
+                .ok()
+                .and_then(|tickers| tickers.first().map(|t| t.last_price));
+
+            snapshots.push(self.record(&symbol, funding.funding_rate, mark_price, spot_price, now));
+        }
+        snapshots
+    }
+
+    /// Latest snapshot of a symbol
+    pub fn get_snapshot(&self, symbol: &str) -> Option<&FundingSnapshot> {
+        self.snapshots.get(symbol)
+    }
+}
+
+#[async_trait]
+impl Agent for FundingAgent {
+    fn get_name(&self) -> &str {
+        "FundingAgent"
+    }
+
+    fn get_config(&self) -> Box<dyn AgentConfig> {
+        Box::new(self.config.clone())
+    }
+
+    fn topics(&self) -> Vec<String> {
+        // The asset scanner's universe, on `agent.scan_universe`
+        vec![format!("agent.{}", UNIVERSE_TOPIC)]
+    }
+
+    async fn initialize(&mut self, _context: Arc<RwLock<AgentContext>>) -> Result<()> {
+        info!("Initializing Funding Agent with {} symbols", self.symbols.len());
+        Ok(())
+    }
+
+    async fn start(&mut self) -> Result<()> {
+        self.running = true;
+        Ok(())
+    }
+
+    async fn stop(&mut self) -> Result<()> {
+        self.running = false;
+        Ok(())
+    }
+
+    async fn tick(&mut self) -> Result<()> {
+        let now = Utc::now();
+        let due = self.last_poll
+            .map_or(true, |last| now - last >= chrono::Duration::seconds(self.config.poll_interval_secs));
+        if !self.running || !due {
+            return Ok(());
+        }
+        self.last_poll = Some(now);
+
+        for snapshot in self.poll(now).await {
+            if let Some(crowded) = snapshot.crowded {
+                info!("Extreme funding on {}: {:.4}% ({:.1}% annualized), crowded {:?}",
+                      snapshot.symbol, snapshot.funding_rate * 100.0, snapshot.annualized_funding_pct, crowded);
+            } else {
+                debug!("Funding on {}: {:.4}%", snapshot.symbol, snapshot.funding_rate * 100.0);
+            }
+            self.message_bus.send(snapshot.to_message());
+        }
+        Ok(())
+    }
+
+    async fn handle_message(&mut self, message: BusMessage) -> Result<()> {
+        if message.topic != format!("agent.{}", UNIVERSE_TOPIC) {
+            return Ok(());
+        }
+        match serde_json::from_str::<Message>(&message.content) {
+            Ok(universe) => match universe_from_message(&universe) {
+                Some(symbols) => {
+                    info!("Funding Agent now tracking the {} symbols of the scanned universe", symbols.len());
+                    self.set_symbols(symbols);
+                },
+                None => debug!("No universe on {}", message.topic),
+            },
+            Err(e) => warn!("Failed to parse universe message: {}", e),
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agents::asset_scanner_agent::universe_message;
+    use crate::engine::message_bus::MessageType;
+
+    #[test]
+    fn test_extreme_funding_flags_crowded_side() {
+        let exchange = Arc::new(BybitAdapter::new("", "", true));
+        let mut agent = FundingAgent::new(FundingAgentConfig::default(), exchange, Arc::new(MessageBus::new()));
+        let now = Utc::now();
+
+        for i in 0..20 {
+            let rate = if i % 2 == 0 { 0.0001 } else { 0.0002 };
+            let snapshot = agent.record("BTCUSDT", rate, 100.0, Some(100.0), now);
+            assert!(snapshot.crowded.is_none());
+        }
+
+        // 0.01% per 8h is 10.95% a year
+        let snapshot = agent.record("ETHUSDT", 0.0001, 101.0, Some(100.0), now);
+        assert!((snapshot.annualized_funding_pct - 10.95).abs() < 1e-9);
+        assert!((snapshot.basis_pct.unwrap() - 1.0).abs() < 1e-9);
+        assert_eq!(snapshot.crowded, Some(CrowdedSide::Longs));
+
+        // Far below its own history, though small in absolute terms
+        let snapshot = agent.record("BTCUSDT", -0.0002, 100.0, None, now);
+        assert!(snapshot.funding_zscore.unwrap() < -2.0);
+        assert_eq!(snapshot.crowded, Some(CrowdedSide::Shorts));
+
+        agent.set_symbols(vec!["ETHUSDT".to_string()]);
+        assert!(agent.get_snapshot("BTCUSDT").is_none());
+        assert!(FundingSnapshot::from_message(&snapshot.to_message()).is_some());
+    }
+
+    #[tokio::test]
+    async fn test_tracks_the_published_universe() {
+        let exchange = Arc::new(BybitAdapter::new("", "", true));
+        let mut agent = FundingAgent::new(FundingAgentConfig::default(), exchange, Arc::new(MessageBus::new()));
+        let universe = vec!["SOLUSDT".to_string(), "XRPUSDT".to_string()];
+
+        agent.handle_message(BusMessage {
+            message_type: MessageType::AgentCommunication,
+            topic: format!("agent.{}", UNIVERSE_TOPIC),
+            content: serde_json::to_string(&universe_message(&universe)).unwrap(),
+            timestamp: Utc::now(),
+        }).await.unwrap();
+
+        assert_eq!(agent.get_symbols(), universe.as_slice());
+    }
+}
@@ -1,10 +1,14 @@
 //! Ghost Trader Agent
 //!
 //! This agent simulates trades before real execution to validate profitability
-//! and avoid losses.
+//! and avoid losses. With a live L2 book and a recent trade tape on record, the
+//! order is filled against the visible book to estimate fill probability and
+//! slippage, and the recorded ticks are replayed from the fill price to estimate
+//! the outcome and the expected adverse excursion. Without market data it falls
+//! back to simulated price paths.
 
 use std::sync::Arc;
-use std::collections::{HashMap, VecDeque};
+use std::collections::{HashMap, HashSet, VecDeque};
 use chrono::{DateTime, Utc, Duration};
 use serde::{Deserialize, Serialize};
 use anyhow::Result;
@@ -12,15 +16,24 @@ use tracing::{info, debug, warn};
 use async_trait::async_trait;
 use tokio::sync::RwLock;
 use rand_distr::{Normal, Distribution};
-use rand::thread_rng;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
 
 use crate::engine::agent_trait::{Agent, AgentContext, AgentConfig};
 use crate::engine::message_bus::{BusMessage, MessageBus, MessageType, TradeDirection};
-use crate::market_simulator::MarketSimulator;
+use crate::exchange::bybit::adapter::BybitAdapter;
+use crate::exchange::bybit::types::{BybitOrderbook, BybitPublicTrade};
+use crate::market_simulator::{MarketSimulator, SimulationConfig};
 
 /// Maximum number of simulations to store
 const MAX_SIMULATIONS: usize = 100;
 
+/// Recent ticks kept per symbol for replay
+const MAX_TICKS: usize = 2000;
+
+/// Ticks needed before recorded market data replaces simulated paths
+const MIN_REPLAY_TICKS: usize = 50;
+
 /// Ghost trader state
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GhostTraderState {
@@ -133,11 +146,27 @@ pub struct TradeSimulationResult {
 
     /// Rejection reason
     pub rejection_reason: Option<String>,
+
+    /// Share of the order the visible book can fill (0.0 to 1.0)
+    #[serde(default)]
+    pub fill_probability: f64,
+
+    /// Expected slippage of the fill against the entry price (bps, positive is adverse)
+    #[serde(default)]
+    pub expected_slippage_bps: f64,
+
+    /// Expected maximum adverse excursion (% of fill price)
+    #[serde(default)]
+    pub expected_mae_pct: f64,
+
+    /// Whether the recorded book and ticks were replayed
+    #[serde(default)]
+    pub used_market_data: bool,
 }
 
 /// Ghost Trader Agent
 /// Ghost Trader configuration
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GhostTraderConfig {
     /// Simulation depth
     pub simulation_depth: usize,
@@ -147,6 +176,18 @@ pub struct GhostTraderConfig {
 
     /// Minimum expected ROI
     pub min_expected_roi: f64,
+
+    /// RNG seed for reproducible simulations (random when unset)
+    pub seed: Option<u64>,
+
+    /// Minimum share of the order the visible book must fill
+    pub min_fill_probability: f64,
+
+    /// Maximum expected slippage (bps)
+    pub max_slippage_bps: f64,
+
+    /// Maximum expected adverse excursion as a share of the stop distance
+    pub max_mae_stop_ratio: f64,
 }
 
 impl Default for GhostTraderConfig {
@@ -155,6 +196,10 @@ impl Default for GhostTraderConfig {
             simulation_depth: 100,
             min_success_rate: 0.6, // 60% success rate
             min_expected_roi: 0.01, // 1% ROI
+            seed: None,
+            min_fill_probability: 0.9,
+            max_slippage_bps: 10.0,
+            max_mae_stop_ratio: 0.8,
         }
     }
 }
@@ -174,11 +219,43 @@ pub struct GhostTrader {
 
     /// Running flag
     running: bool,
+
+    /// Random number generator seeding each simulation
+    rng: StdRng,
+
+    /// Latest orderbook by symbol
+    orderbooks: HashMap<String, BybitOrderbook>,
+
+    /// Recent trades by symbol as (time in ms, price), oldest first
+    ticks: HashMap<String, VecDeque<(i64, f64)>>,
+
+    /// Execution IDs of the recorded trades, by symbol
+    tick_ids: HashMap<String, (HashSet<String>, VecDeque<String>)>,
+}
+
+/// One simulated path
+struct PathRun {
+    /// Prices along the path
+    path: Vec<f64>,
+
+    /// How the trade ended
+    outcome: SimulationOutcome,
+
+    /// ROI at exit (%)
+    roi: f64,
+
+    /// Seconds to exit
+    duration: u64,
 }
 
 impl GhostTrader {
     /// Create a new ghost trader
     pub fn new(config: GhostTraderConfig, message_bus: Arc<MessageBus>) -> Self {
+        let rng = match config.seed {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => StdRng::from_entropy(),
+        };
+
         Self {
             config,
             message_bus,
@@ -192,7 +269,144 @@ impl GhostTrader {
             },
             simulation_results: VecDeque::with_capacity(MAX_SIMULATIONS),
             running: false,
+            rng,
+            orderbooks: HashMap::new(),
+            ticks: HashMap::new(),
+            tick_ids: HashMap::new(),
+        }
+    }
+
+    /// Record the latest orderbook of a symbol
+    pub fn update_orderbook(&mut self, orderbook: BybitOrderbook) {
+        self.orderbooks.insert(orderbook.symbol.clone(), orderbook);
+    }
+
+    /// Record trades of a symbol's tape, in any order; trades seen before are skipped
+    pub fn record_trades(&mut self, symbol: &str, trades: &[BybitPublicTrade]) {
+        let mut new_trades: Vec<&BybitPublicTrade> = trades.iter().filter(|t| t.price > 0.0).collect();
+        new_trades.sort_by_key(|t| t.time);
+
+        let ticks = self.ticks.entry(symbol.to_string()).or_default();
+        let (seen, order) = self.tick_ids.entry(symbol.to_string()).or_default();
+        for trade in new_trades {
+            if !seen.insert(trade.exec_id.clone()) {
+                continue;
+            }
+            order.push_back(trade.exec_id.clone());
+            ticks.push_back((trade.time, trade.price));
+        }
+
+        while ticks.len() > MAX_TICKS {
+            ticks.pop_front();
+        }
+        while order.len() > MAX_TICKS {
+            if let Some(id) = order.pop_front() {
+                seen.remove(&id);
+            }
+        }
+    }
+
+    /// Fetch the current book and trade tape of a symbol from the exchange
+    pub async fn refresh_market_data(&mut self, exchange: &BybitAdapter, symbol: &str, category: &str) -> Result<()> {
+        let orderbook = exchange.get_orderbook(symbol, 50).await?;
+        let trades = exchange.get_recent_trades(symbol, category, 1000).await?;
+        self.update_orderbook(orderbook);
+        self.record_trades(symbol, &trades);
+        Ok(())
+    }
+
+    /// Whether a symbol has enough recorded market data to be replayed
+    pub fn has_market_data(&self, symbol: &str) -> bool {
+        self.orderbooks.contains_key(symbol)
+            && self.ticks.get(symbol).map_or(0, |t| t.len()) >= MIN_REPLAY_TICKS
+    }
+
+    /// Fill an order against the visible book
+    ///
+    /// Returns the share of the order filled and the volume-weighted fill price.
+    fn fill_against_book(orderbook: &BybitOrderbook, params: &TradeSimulationParams) -> (f64, Option<f64>) {
+        let levels = match params.direction {
+            TradeDirection::Long => &orderbook.asks,
+            TradeDirection::Short => &orderbook.bids,
+            _ => return (0.0, None),
+        };
+        if params.position_size <= 0.0 {
+            return (1.0, levels.first().map(|level| level.0));
+        }
+
+        let mut remaining = params.position_size;
+        let mut cost = 0.0;
+        for &(price, quantity) in levels {
+            if remaining <= 0.0 {
+                break;
+            }
+            let take = quantity.min(remaining);
+            cost += take * price;
+            remaining -= take;
+        }
+
+        let filled = params.position_size - remaining.max(0.0);
+        if filled <= 0.0 {
+            return (0.0, None);
+        }
+        (filled / params.position_size, Some(cost / filled))
+    }
+
+    /// Replay recorded tick returns from the fill price
+    ///
+    /// Each path starts at a random tick and walks the recorded returns in order,
+    /// wrapping around, for as many steps as the tape holds or the duration allows.
+    fn replay_paths(&mut self, params: &TradeSimulationParams, ticks: &[(i64, f64)]) -> Vec<PathRun> {
+        let returns: Vec<f64> = ticks.windows(2)
+            .filter(|w| w[0].1 > 0.0)
+            .map(|w| w[1].1 / w[0].1 - 1.0)
+            .collect();
+        if returns.is_empty() {
+            return Vec::new();
+        }
+
+        let span_secs = (ticks[ticks.len() - 1].0 - ticks[0].0).max(0) as f64 / 1000.0;
+        let secs_per_tick = (span_secs / returns.len() as f64).max(0.001);
+        let steps = ((params.duration as f64 / secs_per_tick) as usize).clamp(1, returns.len());
+
+        let mut runs = Vec::with_capacity(params.num_simulations);
+        for _ in 0..params.num_simulations {
+            let start = self.rng.gen_range(0..returns.len());
+            let mut path = Vec::with_capacity(steps + 1);
+            path.push(params.entry_price);
+            let mut run = None;
+
+            for step in 0..steps {
+                let price = path[path.len() - 1] * (1.0 + returns[(start + step) % returns.len()]);
+                path.push(price);
+                let duration = ((step + 1) as f64 * secs_per_tick) as u64;
+                if self.check_stop_loss(params, price) {
+                    run = Some((SimulationOutcome::Loss, self.calculate_roi(params, price), duration));
+                    break;
+                }
+                if self.check_take_profit(params, price) {
+                    run = Some((SimulationOutcome::Win, self.calculate_roi(params, price), duration));
+                    break;
+                }
+            }
+
+            let (outcome, roi, duration) = run.unwrap_or((SimulationOutcome::Timeout, 0.0, 0));
+            runs.push(PathRun { path, outcome, roi, duration });
         }
+        runs
+    }
+
+    /// Largest move against the trade along a path (% of entry price)
+    fn adverse_excursion_pct(params: &TradeSimulationParams, path: &[f64]) -> f64 {
+        if params.entry_price <= 0.0 {
+            return 0.0;
+        }
+        let worst = match params.direction {
+            TradeDirection::Long => path.iter().fold(params.entry_price, |worst, p| worst.min(*p)),
+            TradeDirection::Short => path.iter().fold(params.entry_price, |worst, p| worst.max(*p)),
+            _ => params.entry_price,
+        };
+        (worst - params.entry_price).abs() / params.entry_price * 100.0
     }
 
     /// Simulate a trade
@@ -211,33 +425,60 @@ impl GhostTrader {
             timestamps.push(start_time + Duration::seconds((i as i64) * (time_step as i64)));
         }
 
-        // Run simulations
-        let mut price_paths = Vec::with_capacity(params.num_simulations);
+        // Fill against the recorded book and replay the tape, or simulate paths
+        let mut fill_probability = 1.0;
+        let mut expected_slippage_bps = 0.0;
+        let mut used_market_data = false;
+        let mut path_params = params.clone();
+
+        let runs = if self.has_market_data(&params.symbol) {
+            let (share, fill_price) = Self::fill_against_book(&self.orderbooks[&params.symbol], &params);
+            fill_probability = share;
+            if let Some(fill_price) = fill_price {
+                let signed = if matches!(params.direction, TradeDirection::Short) { -1.0 } else { 1.0 };
+                expected_slippage_bps = (fill_price - params.entry_price) / params.entry_price * 10_000.0 * signed;
+                path_params.entry_price = fill_price;
+            }
+            used_market_data = true;
+            let ticks: Vec<(i64, f64)> = self.ticks[&params.symbol].iter().copied().collect();
+            self.replay_paths(&path_params, &ticks)
+        } else {
+            // Create a market simulator for this simulation, seeded from the agent's RNG
+            let mut simulator = MarketSimulator::new(SimulationConfig {
+                seed: Some(self.rng.gen()),
+                ..SimulationConfig::default()
+            });
+
+            let mut runs = Vec::with_capacity(params.num_simulations);
+            for _ in 0..params.num_simulations {
+                let (path, outcome, roi, duration) = self.simulate_price_path_with_simulator(&mut simulator, &params, num_steps, time_step)?;
+                runs.push(PathRun { path, outcome, roi, duration });
+            }
+            runs
+        };
+
+        let mut price_paths = Vec::with_capacity(runs.len());
         let mut win_count = 0;
         let mut loss_count = 0;
         let mut timeout_count = 0;
         let mut total_roi = 0.0;
         let mut total_duration = 0;
+        let mut total_mae = 0.0;
 
-        // Create a market simulator for this simulation
-        let mut simulator = MarketSimulator::new();
-
-        for _ in 0..params.num_simulations {
-            // Simulate price path using the local simulator
-            let (path, outcome, roi, duration) = self.simulate_price_path_with_simulator(&mut simulator, &params, num_steps, time_step)?;
+        for run in runs {
+            total_mae += Self::adverse_excursion_pct(&path_params, &run.path);
+            price_paths.push(run.path);
 
-            price_paths.push(path);
-
-            match outcome {
+            match run.outcome {
                 SimulationOutcome::Win => {
                     win_count += 1;
-                    total_roi += roi;
-                    total_duration += duration;
+                    total_roi += run.roi;
+                    total_duration += run.duration;
                 },
                 SimulationOutcome::Loss => {
                     loss_count += 1;
-                    total_roi += roi;
-                    total_duration += duration;
+                    total_roi += run.roi;
+                    total_duration += run.duration;
                 },
                 SimulationOutcome::Timeout => {
                     timeout_count += 1;
@@ -245,6 +486,9 @@ impl GhostTrader {
             }
         }
 
+        let expected_mae_pct = if price_paths.is_empty() { 0.0 } else { total_mae / price_paths.len() as f64 };
+        let stop_distance_pct = (path_params.entry_price - params.stop_loss_price).abs() / path_params.entry_price * 100.0;
+
         // Calculate statistics
         let success_rate = if win_count + loss_count > 0 {
             win_count as f64 / (win_count + loss_count) as f64
@@ -265,17 +509,20 @@ impl GhostTrader {
         };
 
         // Determine if trade is approved
-        let approved = success_rate >= params.min_success_rate && avg_roi >= params.min_roi;
-
-        let rejection_reason = if !approved {
-            if success_rate < params.min_success_rate {
-                Some(format!("Success rate too low: {:.2}% < {:.2}%", success_rate * 100.0, params.min_success_rate * 100.0))
-            } else {
-                Some(format!("Average ROI too low: {:.2}% < {:.2}%", avg_roi * 100.0, params.min_roi * 100.0))
-            }
+        let rejection_reason = if used_market_data && fill_probability < self.config.min_fill_probability {
+            Some(format!("Fill probability too low: {:.0}% < {:.0}%", fill_probability * 100.0, self.config.min_fill_probability * 100.0))
+        } else if used_market_data && expected_slippage_bps > self.config.max_slippage_bps {
+            Some(format!("Expected slippage too high: {:.1} bps > {:.1} bps", expected_slippage_bps, self.config.max_slippage_bps))
+        } else if used_market_data && expected_mae_pct > stop_distance_pct * self.config.max_mae_stop_ratio {
+            Some(format!("Expected adverse excursion too large: {:.2}% against a {:.2}% stop", expected_mae_pct, stop_distance_pct))
+        } else if success_rate < params.min_success_rate {
+            Some(format!("Success rate too low: {:.2}% < {:.2}%", success_rate * 100.0, params.min_success_rate * 100.0))
+        } else if avg_roi < params.min_roi {
+            Some(format!("Average ROI too low: {:.2}% < {:.2}%", avg_roi * 100.0, params.min_roi * 100.0))
         } else {
             None
         };
+        let approved = rejection_reason.is_none();
 
         // Create result
         let result = TradeSimulationResult {
@@ -292,6 +539,10 @@ impl GhostTrader {
             timestamps,
             approved,
             rejection_reason,
+            fill_probability,
+            expected_slippage_bps,
+            expected_mae_pct,
+            used_market_data,
         };
 
         // Update state
@@ -329,8 +580,8 @@ impl GhostTrader {
         let mut path = Vec::with_capacity(num_steps);
         path.push(params.current_price);
 
-        // Set up random number generator
-        let mut rng = thread_rng();
+        // Draw from the simulator's seeded generator
+        let rng = simulator.rng();
 
         // Calculate drift and volatility
         let drift = params.trend * 0.0001; // Base drift
@@ -344,7 +595,7 @@ impl GhostTrader {
             let prev_price = path[i - 1];
 
             // Generate random component
-            let random = normal.sample(&mut rng);
+            let random = normal.sample(rng);
 
             // Calculate price change
             let dt = time_step as f64 / 86400.0; // Convert to days
@@ -420,66 +671,15 @@ impl GhostTrader {
         &self.simulation_results
     }
 
-    /// Simulate trade (simplified version)
-    fn simulate_trade_simple(&self, _symbol: &str, side: &str, entry_price: f64, stop_loss: f64, take_profit: f64) -> (bool, f64) {
-        // In a real implementation, we would use historical data and Monte Carlo simulation
-        // For now, we'll use a simple random simulation
-
-        let mut rng = rand::thread_rng();
-        let mut success_count = 0;
-        let mut total_roi = 0.0;
-
-        for _ in 0..self.config.simulation_depth {
-            // Generate random price movement
-            let price_movement = Normal::new(0.0, 0.01).unwrap().sample(&mut rng);
-            let final_price = entry_price * (1.0 + price_movement);
-
-            // Check if stop loss or take profit hit
-            let (success, roi) = match side {
-                "buy" => {
-                    if final_price <= stop_loss {
-                        // Stop loss hit
-                        (false, (stop_loss - entry_price) / entry_price)
-                    } else if final_price >= take_profit {
-                        // Take profit hit
-                        (true, (take_profit - entry_price) / entry_price)
-                    } else {
-                        // Neither hit, use final price
-                        (final_price > entry_price, (final_price - entry_price) / entry_price)
-                    }
-                },
-                "sell" => {
-                    if final_price >= stop_loss {
-                        // Stop loss hit
-                        (false, (entry_price - stop_loss) / entry_price)
-                    } else if final_price <= take_profit {
-                        // Take profit hit
-                        (true, (entry_price - take_profit) / entry_price)
-                    } else {
-                        // Neither hit, use final price
-                        (final_price < entry_price, (entry_price - final_price) / entry_price)
-                    }
-                },
-                _ => (false, 0.0),
-            };
-
-            if success {
-                success_count += 1;
-            }
-
-            total_roi += roi;
-        }
-
-        let success_rate = success_count as f64 / self.config.simulation_depth as f64;
-        let expected_roi = total_roi / self.config.simulation_depth as f64;
-
-        (success_rate >= self.config.min_success_rate && expected_roi >= self.config.min_expected_roi, expected_roi)
-    }
-
     /// Get state
     pub fn get_state(&self) -> &GhostTraderState {
         &self.state
     }
+
+    /// Simulation settings
+    pub fn get_simulation_config(&self) -> &GhostTraderConfig {
+        &self.config
+    }
 }
 
 /// Simulation outcome
@@ -546,7 +746,7 @@ impl Agent for GhostTrader {
         Ok(())
     }
 
-    async fn update(&mut self) -> Result<()> {
+    async fn tick(&mut self) -> Result<()> {
         if !self.running {
             return Ok(());
         }
@@ -566,6 +766,7 @@ impl Agent for GhostTrader {
                 let mut symbol = String::new();
                 let mut side = String::new();
                 let mut price = 0.0;
+                let mut quantity = 0.0;
                 let mut stop_loss = 0.0;
                 let mut take_profit = 0.0;
 
@@ -577,6 +778,7 @@ impl Agent for GhostTrader {
                             "symbol" => symbol = kv[1].to_string(),
                             "side" => side = kv[1].to_string(),
                             "price" => price = kv[1].parse::<f64>().unwrap_or(0.0),
+                            "quantity" => quantity = kv[1].parse::<f64>().unwrap_or(0.0),
                             "stop_loss" => stop_loss = kv[1].parse::<f64>().unwrap_or(0.0),
                             "take_profit" => take_profit = kv[1].parse::<f64>().unwrap_or(0.0),
                             _ => {}
@@ -599,7 +801,7 @@ impl Agent for GhostTrader {
                     entry_price: price,
                     stop_loss_price: stop_loss,
                     take_profit_price: take_profit,
-                    position_size: 1.0,
+                    position_size: quantity,
                     leverage: 1.0,
                     timeframe: 60, // 1 minute
                     duration: 3600, // 1 hour simulation
@@ -612,7 +814,10 @@ impl Agent for GhostTrader {
 
                 // Simulate trade
                 let result = self.simulate_trade(params)?;
-                let should_execute = result.success_rate > 0.6; // 60% success rate threshold
+                let should_execute = result.approved;
+                if let Some(reason) = &result.rejection_reason {
+                    info!("Ghost trade for {} rejected: {}", symbol, reason);
+                }
                 let expected_roi = result.avg_roi;
 
                 // Send response
@@ -641,6 +846,10 @@ impl Agent for GhostTrader {
     fn get_config(&self) -> Box<dyn AgentConfig> {
         Box::new(self.config.clone())
     }
+
+    fn topics(&self) -> Vec<String> {
+        vec!["signal.#".to_string()]
+    }
 }
 
 #[cfg(test)]
@@ -678,4 +887,71 @@ mod tests {
         assert_eq!(result.price_paths.len(), 10);
         assert!(result.success_rate >= 0.0 && result.success_rate <= 1.0);
     }
+
+    #[test]
+    fn test_orderbook_replay() {
+        let mut ghost_trader = GhostTrader::new(
+            GhostTraderConfig { seed: Some(3), ..Default::default() },
+            Arc::new(MessageBus::new()),
+        );
+
+        // A tape oscillating 0.1% around 100 never reaches a 5% stop
+        let trades: Vec<BybitPublicTrade> = (0..100)
+            .map(|i| BybitPublicTrade {
+                exec_id: format!("t{}", i),
+                symbol: "BTCUSDT".to_string(),
+                price: if i % 2 == 0 { 100.0 } else { 100.1 },
+                size: 1.0,
+                side: "Buy".to_string(),
+                time: 1_000 * i as i64,
+            })
+            .collect();
+        ghost_trader.record_trades("BTCUSDT", &trades);
+        ghost_trader.record_trades("BTCUSDT", &trades[..10]);
+        assert_eq!(ghost_trader.ticks["BTCUSDT"].len(), 100);
+
+        ghost_trader.update_orderbook(BybitOrderbook {
+            symbol: "BTCUSDT".to_string(),
+            timestamp: 100_000,
+            bids: vec![(99.99, 5.0)],
+            asks: vec![(100.0, 1.0), (100.2, 1.0)],
+        });
+        assert!(ghost_trader.has_market_data("BTCUSDT"));
+
+        let params = |position_size: f64| TradeSimulationParams {
+            symbol: "BTCUSDT".to_string(),
+            current_price: 100.0,
+            direction: TradeDirection::Long,
+            entry_price: 100.0,
+            stop_loss_price: 95.0,
+            take_profit_price: 100.05,
+            position_size,
+            leverage: 1.0,
+            timeframe: 1,
+            duration: 60,
+            volatility: 0.0,
+            trend: 0.0,
+            num_simulations: 20,
+            min_success_rate: 0.5,
+            min_roi: 0.0,
+        };
+
+        // One contract fills at the touch
+        let result = ghost_trader.simulate_trade(params(1.0)).unwrap();
+        assert!(result.used_market_data);
+        assert_eq!(result.fill_probability, 1.0);
+        assert_eq!(result.expected_slippage_bps, 0.0);
+        assert!(result.expected_mae_pct < 0.2);
+        assert!(result.approved, "{:?}", result.rejection_reason);
+
+        // Two contracts walk into the second level: 10 bps of slippage
+        let result = ghost_trader.simulate_trade(params(2.0)).unwrap();
+        assert!((result.expected_slippage_bps - 10.0).abs() < 1e-6);
+
+        // Four contracts exceed the visible book
+        let result = ghost_trader.simulate_trade(params(4.0)).unwrap();
+        assert_eq!(result.fill_probability, 0.5);
+        assert!(!result.approved);
+        assert!(result.rejection_reason.unwrap().contains("Fill probability"));
+    }
 }
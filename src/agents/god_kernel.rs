@@ -2,6 +2,14 @@
 //!
 //! This agent is responsible for breeding/killing agents and evolving the system.
 //! It acts as the central intelligence that manages the entire agent ecosystem.
+//!
+//! Strategies registered with a builder are evolved for real: underperformers
+//! get mutated challengers that trade in shadow mode through the `GhostTrader`,
+//! challengers that prove themselves are promoted to a live capital budget and
+//! those that keep losing are retired. Candidate agents go through the same
+//! trial: they run in shadow mode on live data, their trade signals are scored
+//! but never executed, and only those that score well are handed back for the
+//! live runtime.
 
 use std::collections::{HashMap, VecDeque};
 use chrono::{DateTime, Utc, Duration};
@@ -15,7 +23,17 @@ use crate::engine::agent_trait::{Agent, AgentContext, AgentConfig};
 use crate::engine::message_bus::{BusMessage, Message, MessageBus, MessageType};
 use std::sync::Arc;
 use tokio::sync::RwLock;
+use crate::agents::agent_coordinator::{AgentCapitalBudget, AgentCoordinator};
 use crate::agents::feedback_loop::{AgentPerformance, MutationRecord};
+use crate::agents::memory_node::AgentTrust;
+use crate::agents::ghost_trader::{GhostTrader, TradeSimulationParams};
+use crate::agents::agent_coordinator::DecisionType;
+use crate::engine::message_bus::TradeDirection;
+use crate::engine::shadow_mode::{ShadowAgentFactory, ShadowConfig, ShadowDecision, ShadowRunner, ShadowScore};
+use crate::monitoring::performance_monitor::PerformanceMonitor;
+use crate::strategy::registry::StrategyControl;
+use crate::strategy::simple_strategy::Candle;
+use crate::strategy::strategy_trait::Strategy;
 
 /// Maximum number of mutations to track
 const MAX_MUTATIONS: usize = 100;
@@ -23,6 +41,15 @@ const MAX_MUTATIONS: usize = 100;
 /// Maximum number of agent generations to track
 const MAX_GENERATIONS: usize = 10;
 
+/// Price paths simulated per shadow trade
+const SHADOW_SIMULATIONS: usize = 20;
+
+/// Horizon of a shadow trade in seconds
+const SHADOW_TRADE_SECS: u64 = 3600;
+
+/// Builds a strategy instance from its name and parameter set
+pub type StrategyBuilder = Box<dyn Fn(&str, &HashMap<String, f64>) -> Result<Box<dyn Strategy>> + Send + Sync>;
+
 /// God kernel state
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GodKernelState {
@@ -142,12 +169,12 @@ pub enum EvolutionEventType {
 }
 
 /// God Kernel configuration
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GodKernelConfig {
     /// Evolution interval in seconds
     pub evolution_interval: u64,
 
-    /// Mutation probability
+    /// Probability that a parameter is perturbed when an agent is mutated
     pub mutation_probability: f64,
 
     /// Kill threshold
@@ -155,6 +182,25 @@ pub struct GodKernelConfig {
 
     /// Promotion threshold
     pub promotion_threshold: f64,
+
+    /// Share by which a capital budget grows or shrinks per adjustment
+    pub budget_step: f64,
+
+    /// Smallest capital budget, as a share of total capital
+    pub min_budget_fraction: f64,
+
+    /// Largest capital budget, as a share of total capital
+    pub max_budget_fraction: f64,
+
+    /// Strategies trading in shadow mode at once
+    pub max_shadow_agents: usize,
+
+    /// Shadow trades before a challenger is promoted or retired
+    pub shadow_min_trades: usize,
+
+    /// How candidate agents' shadow decisions are closed
+    #[serde(default)]
+    pub shadow_mode: ShadowConfig,
 }
 
 impl Default for GodKernelConfig {
@@ -164,10 +210,28 @@ impl Default for GodKernelConfig {
             mutation_probability: 0.1, // 10% chance
             kill_threshold: -0.5, // Kill agents with performance below -50%
             promotion_threshold: 0.7, // Promote agents with performance above 70%
+            budget_step: 0.2,
+            min_budget_fraction: 0.05,
+            max_budget_fraction: 0.5,
+            max_shadow_agents: 4,
+            shadow_min_trades: 20,
+            shadow_mode: ShadowConfig::default(),
         }
     }
 }
 
+/// Challenger strategy trading in shadow mode
+struct ShadowStrategy {
+    /// Strategy instance
+    strategy: Box<dyn Strategy>,
+
+    /// Score of each simulated trade (-1.0 to 1.0)
+    outcomes: Vec<f64>,
+
+    /// Open time of the last candle evaluated, by symbol
+    last_candle: HashMap<String, i64>,
+}
+
 /// God Kernel Agent
 pub struct GodKernel {
     /// Configuration
@@ -188,6 +252,21 @@ pub struct GodKernel {
     /// Mutation history
     mutations: VecDeque<MutationRecord>,
 
+    /// Strategy builders by family
+    builders: HashMap<String, StrategyBuilder>,
+
+    /// Builder family of each strategy agent
+    families: HashMap<String, String>,
+
+    /// Challengers in shadow mode, by name
+    shadow: HashMap<String, ShadowStrategy>,
+
+    /// Candidate agents in shadow mode
+    shadow_agents: ShadowRunner,
+
+    /// Closed trades of each strategy already fed to the kernel
+    attributed_trades: HashMap<String, usize>,
+
     /// Running flag
     running: bool,
 }
@@ -195,6 +274,7 @@ pub struct GodKernel {
 impl GodKernel {
     /// Create a new god kernel
     pub fn new(config: GodKernelConfig, message_bus: Arc<MessageBus>) -> Self {
+        let shadow_agents = ShadowRunner::new(config.shadow_mode.clone());
         Self {
             config,
             message_bus,
@@ -213,6 +293,11 @@ impl GodKernel {
             agent_metadata: HashMap::new(),
             evolution_events: VecDeque::with_capacity(MAX_MUTATIONS),
             mutations: VecDeque::with_capacity(MAX_MUTATIONS),
+            builders: HashMap::new(),
+            families: HashMap::new(),
+            shadow: HashMap::new(),
+            shadow_agents,
+            attributed_trades: HashMap::new(),
             running: false,
         }
     }
@@ -357,33 +442,458 @@ impl GodKernel {
             info!("Agent promoted: {} - Performance score: {:.2}", name, performance.score);
         }
 
-        // Check for mutation
-        let mut should_mutate = false;
-        let mutation_probability = self.config.mutation_probability;
+        // Mutate agents the feedback loop flagged; strategies get shadow challengers instead
+        let should_mutate = performance.mutation_eligible
+            && !self.families.contains_key(name)
+            && self.agent_metadata.get(name).map_or(false, |m| m.active);
 
-        {
-            let mut rng = rand::thread_rng();
-            if let Some(metadata) = self.agent_metadata.get_mut(name) {
-                if metadata.active && rng.gen::<f64>() < mutation_probability {
-                    should_mutate = true;
+        if should_mutate {
+            if let Some((_, message)) = self.mutate_agent(name)? {
+                messages.push(message);
+            }
+        }
+        Ok(messages)
+    }
+
+    /// Apply per-strategy trade attribution
+    ///
+    /// Strategies with at least `min_trades` attributed trades are registered (if
+    /// needed) and evaluated like any other agent. A strategy that gets killed is
+    /// also sent a `StrategyControl::Disable` so the trading system drains it.
+    /// Strategies without a trade closed since the last call are skipped, so the
+    /// same record is never evaluated twice.
+    pub fn apply_strategy_attribution(&mut self, monitor: &PerformanceMonitor, min_trades: usize) -> Result<Vec<Message>> {
+        let mut messages = Vec::new();
+
+        for performance in monitor.ranked_strategies(min_trades) {
+            let name = performance.strategy.clone();
+            if self.attributed_trades.get(&name) == Some(&performance.total_trades) {
+                continue;
+            }
+            self.attributed_trades.insert(name.clone(), performance.total_trades);
+
+            if !self.agent_metadata.contains_key(&name) {
+                self.register_agent(&name, "strategy", HashMap::new(), vec!["strategy".to_string()])?;
+            }
+
+            let was_active = self.agent_metadata.get(&name).map_or(false, |m| m.active);
+            messages.extend(self.update_agent_performance(&name, &performance.to_agent_performance())?);
+            let is_active = self.agent_metadata.get(&name).map_or(false, |m| m.active);
+
+            if was_active && !is_active {
+                messages.push(StrategyControl::Disable { strategy: name }.to_message());
+            }
+        }
+
+        Ok(messages)
+    }
+
+    /// Apply the memory node's rolling trust scores
+    ///
+    /// Agents with at least `min_trades` scored contributions are registered (if
+    /// needed) and evaluated like any other agent. Strategies are skipped; their
+    /// attributed trades are the better record of how they perform.
+    pub fn apply_trust_scores(&mut self, trust_scores: &HashMap<String, AgentTrust>, min_trades: usize) -> Result<Vec<Message>> {
+        let mut messages = Vec::new();
+
+        let mut ranked: Vec<&AgentTrust> = trust_scores.values()
+            .filter(|trust| trust.trades >= min_trades)
+            .collect();
+        ranked.sort_by(|a, b| a.agent.cmp(&b.agent));
+
+        for trust in ranked {
+            match self.agent_metadata.get(&trust.agent) {
+                Some(metadata) if metadata.tags.iter().any(|t| t == "strategy") => continue,
+                Some(_) => {}
+                None => {
+                    self.register_agent(&trust.agent, "agent", HashMap::new(), vec!["voter".to_string()])?;
                 }
             }
+
+            debug!("Trust in {}: {:.2} over {} trades ({:.0}% hit rate)",
+                   trust.agent, trust.trust_score, trust.trades, trust.hit_rate * 100.0);
+            messages.extend(self.update_agent_performance(&trust.agent, &trust.to_agent_performance())?);
         }
 
-        // Handle mutation if needed
-        if should_mutate {
-            // Create mutation
-            let mutation_result = self.mutate_agent(name)?;
+        Ok(messages)
+    }
 
-            if let Some(message) = mutation_result {
-                messages.push(message);
+    /// Adjust the coordinator's agent capital budgets by performance
+    ///
+    /// Agents at or above the promotion threshold get `budget_step` more capital,
+    /// agents with a negative score get `budget_step` less, within the configured
+    /// share of total capital. Killed agents keep only what they have committed.
+    /// Returns the budgets that changed.
+    pub fn adjust_agent_budgets(&self, coordinator: &mut AgentCoordinator) -> Result<Vec<AgentCapitalBudget>> {
+        let total_capital = coordinator.get_total_capital();
+        let min_budget = total_capital * self.config.min_budget_fraction;
+        let max_budget = total_capital * self.config.max_budget_fraction;
+
+        let mut agents: Vec<AgentCapitalBudget> = coordinator.get_agent_budgets().values().cloned().collect();
+        agents.sort_by(|a, b| a.agent.cmp(&b.agent));
+
+        let mut changed = Vec::new();
+        for budget in agents {
+            let metadata = match self.agent_metadata.get(&budget.agent) {
+                Some(metadata) => metadata,
+                None => continue,
+            };
+
+            let target = if !metadata.active {
+                budget.committed
+            } else if metadata.performance_score >= self.config.promotion_threshold {
+                (budget.budget * (1.0 + self.config.budget_step)).clamp(min_budget, max_budget)
+            } else if metadata.performance_score < 0.0 {
+                (budget.budget * (1.0 - self.config.budget_step)).clamp(min_budget, max_budget)
+            } else {
+                continue;
+            };
+
+            if (target - budget.budget).abs() > f64::EPSILON {
+                debug!("Capital budget of {} adjusted from ${:.2} to ${:.2} (score {:.2})",
+                       budget.agent, budget.budget, target, metadata.performance_score);
+                coordinator.set_agent_budget(&budget.agent, target)?;
+                if let Some(updated) = coordinator.get_agent_budget(&budget.agent) {
+                    changed.push(updated.clone());
+                }
             }
         }
+
+        Ok(changed)
+    }
+
+    /// Register a strategy the kernel may breed challengers from
+    pub fn register_strategy_builder(&mut self, name: &str, parameters: HashMap<String, f64>, builder: StrategyBuilder) -> Result<()> {
+        if !self.agent_metadata.contains_key(name) {
+            self.register_agent(name, "strategy", parameters, vec!["strategy".to_string()])?;
+        } else if let Some(metadata) = self.agent_metadata.get_mut(name) {
+            metadata.parameters = parameters;
+        }
+
+        self.builders.insert(name.to_string(), builder);
+        self.families.insert(name.to_string(), name.to_string());
+        Ok(())
+    }
+
+    /// Build a registered strategy again with new parameters, under its own name
+    pub fn rebuild_strategy(&mut self, name: &str, parameters: &HashMap<String, f64>) -> Result<Box<dyn Strategy>> {
+        let builder = self.builders.get(name)
+            .ok_or_else(|| anyhow::anyhow!("No strategy builder registered for {}", name))?;
+        let strategy = builder(name, parameters)?;
+        if let Some(metadata) = self.agent_metadata.get_mut(name) {
+            metadata.parameters = parameters.clone();
+        }
+        Ok(strategy)
+    }
+
+    /// Spawn shadow challengers for live strategies below the promotion threshold
+    ///
+    /// Each challenger is a mutation of its parent's parameters built by the parent's
+    /// builder. A parent has at most one challenger at a time.
+    pub fn spawn_challengers(&mut self) -> Result<Vec<Message>> {
+        let mut messages = Vec::new();
+
+        let mut parents: Vec<String> = self.families.keys()
+            .filter(|name| !self.shadow.contains_key(*name))
+            .filter(|name| self.agent_metadata.get(*name).map_or(false, |m| {
+                m.active && m.performance_score < self.config.promotion_threshold
+            }))
+            .filter(|name| !self.shadow.keys().any(|child| {
+                self.agent_metadata.get(child).and_then(|m| m.parent.as_ref()) == Some(*name)
+            }))
+            .cloned()
+            .collect();
+        parents.sort();
+
+        for parent in parents {
+            if self.shadow.len() >= self.config.max_shadow_agents {
+                break;
+            }
+            let Some((child, message)) = self.mutate_agent(&parent)? else {
+                continue;
+            };
+            let family = self.families[&parent].clone();
+            let parameters = self.agent_metadata[&child].parameters.clone();
+
+            match self.builders[&family](&child, &parameters) {
+                Ok(strategy) => {
+                    if let Some(metadata) = self.agent_metadata.get_mut(&child) {
+                        metadata.tags.push("shadow".to_string());
+                    }
+                    self.families.insert(child.clone(), family);
+                    self.shadow.insert(child.clone(), ShadowStrategy {
+                        strategy,
+                        outcomes: Vec::new(),
+                        last_candle: HashMap::new(),
+                    });
+                    info!("Challenger {} of {} trading in shadow mode", child, parent);
+                    messages.push(message);
+                }
+                Err(e) => {
+                    warn!("Failed to build challenger {} of {}: {}", child, parent, e);
+                    self.retire_agent(&child, "Challenger could not be built");
+                }
+            }
+        }
+
         Ok(messages)
     }
 
-    /// Mutate an agent
-    fn mutate_agent(&mut self, name: &str) -> Result<Option<Message>> {
+    /// Run the shadow challengers on the latest candles
+    ///
+    /// Every new entry signal is simulated by the ghost trader instead of traded; the
+    /// simulated success rate, mapped to (-1.0 to 1.0), scores the challenger.
+    pub fn run_shadow(&mut self, candles_by_symbol: &[(String, Vec<Candle>)], ghost_trader: &mut GhostTrader) {
+        for (name, shadow) in self.shadow.iter_mut() {
+            for (symbol, candles) in candles_by_symbol {
+                let Some(last) = candles.last() else {
+                    continue;
+                };
+                if candles.len() < shadow.strategy.min_candles() || shadow.last_candle.get(symbol) == Some(&last.open_time) {
+                    continue;
+                }
+                shadow.last_candle.insert(symbol.clone(), last.open_time);
+
+                let signal = match shadow.strategy.analyze(symbol, candles) {
+                    Ok(signal) => signal,
+                    Err(e) => {
+                        debug!("Challenger {} failed for {}: {}", name, symbol, e);
+                        continue;
+                    }
+                };
+                let (direction, is_long) = match signal.decision_type {
+                    DecisionType::EnterLong | DecisionType::Buy => (TradeDirection::Long, true),
+                    DecisionType::EnterShort | DecisionType::Sell => (TradeDirection::Short, false),
+                    _ => continue,
+                };
+
+                let price = signal.entry_price;
+                let returns: Vec<f64> = candles.windows(2)
+                    .filter(|w| w[0].close > 0.0)
+                    .map(|w| (w[1].close - w[0].close) / w[0].close)
+                    .collect();
+                let mean = returns.iter().sum::<f64>() / returns.len().max(1) as f64;
+                let volatility = (returns.iter().map(|r| (r - mean).powi(2)).sum::<f64>() / returns.len().max(1) as f64).sqrt();
+
+                let params = TradeSimulationParams {
+                    symbol: symbol.clone(),
+                    current_price: price,
+                    direction,
+                    entry_price: price,
+                    stop_loss_price: signal.stop_loss.unwrap_or(if is_long { price * 0.99 } else { price * 1.01 }),
+                    take_profit_price: signal.take_profit.unwrap_or(if is_long { price * 1.02 } else { price * 0.98 }),
+                    position_size: 1.0,
+                    leverage: 1.0,
+                    timeframe: 1,
+                    duration: SHADOW_TRADE_SECS,
+                    volatility,
+                    trend: mean,
+                    num_simulations: SHADOW_SIMULATIONS,
+                    min_success_rate: 0.0,
+                    min_roi: f64::MIN,
+                };
+                match ghost_trader.simulate_trade(params) {
+                    Ok(result) => shadow.outcomes.push(result.success_rate * 2.0 - 1.0),
+                    Err(e) => warn!("Shadow simulation for {} on {} failed: {}", name, symbol, e),
+                }
+            }
+        }
+    }
+
+    /// Promote challengers that proved themselves and retire those that did not
+    ///
+    /// Promoted strategies are returned for registration in the live strategy
+    /// registry and get the smallest capital budget with the coordinator. A
+    /// challenger still undecided after three times the minimum trades is retired.
+    pub fn promote_and_retire(&mut self, coordinator: &mut AgentCoordinator) -> Result<(Vec<Box<dyn Strategy>>, Vec<Message>)> {
+        let mut promoted = Vec::new();
+        let mut messages = Vec::new();
+
+        let mut decided: Vec<(String, f64)> = self.shadow.iter()
+            .filter(|(_, shadow)| shadow.outcomes.len() >= self.config.shadow_min_trades)
+            .map(|(name, shadow)| (name.clone(), shadow.outcomes.iter().sum::<f64>() / shadow.outcomes.len() as f64))
+            .collect();
+        decided.sort_by(|a, b| a.0.cmp(&b.0));
+
+        for (name, score) in decided {
+            if let Some(metadata) = self.agent_metadata.get_mut(&name) {
+                metadata.performance_score = score;
+                metadata.last_active = Utc::now();
+            }
+
+            if score >= self.config.promotion_threshold {
+                let Some(shadow) = self.shadow.remove(&name) else {
+                    continue;
+                };
+                if let Some(metadata) = self.agent_metadata.get_mut(&name) {
+                    metadata.tags.retain(|t| t != "shadow");
+                    metadata.tags.push("strategy".to_string());
+                }
+                coordinator.set_agent_budget(&name, coordinator.get_total_capital() * self.config.min_budget_fraction)?;
+
+                self.record_evolution_event(
+                    EvolutionEventType::AgentPromoted,
+                    &name,
+                    "Challenger promoted to live trading",
+                    serde_json::json!({ "shadow_score": score }),
+                );
+                messages.push(Message::Custom(
+                    "agent_promoted".to_string(),
+                    serde_json::json!({
+                        "agent_name": name,
+                        "reason": "Shadow trading above threshold",
+                        "score": score,
+                        "threshold": self.config.promotion_threshold,
+                    }),
+                ));
+                info!("Challenger {} promoted to live trading - shadow score: {:.2}", name, score);
+                promoted.push(shadow.strategy);
+            } else if score <= self.config.kill_threshold
+                || self.shadow[&name].outcomes.len() >= self.config.shadow_min_trades * 3
+            {
+                self.shadow.remove(&name);
+                self.retire_agent(&name, "Challenger failed in shadow trading");
+                messages.push(Message::Custom(
+                    "agent_killed".to_string(),
+                    serde_json::json!({
+                        "agent_name": name,
+                        "reason": "Shadow trading below threshold",
+                        "score": score,
+                        "threshold": self.config.kill_threshold,
+                    }),
+                ));
+            }
+        }
+
+        Ok((promoted, messages))
+    }
+
+    /// Start a candidate agent in shadow mode, on a message bus of its own
+    pub async fn add_shadow_agent(&mut self, factory: ShadowAgentFactory, context: Arc<RwLock<AgentContext>>) -> Result<String> {
+        let name = self.shadow_agents.add(factory, context).await?;
+        if !self.agent_metadata.contains_key(&name) {
+            self.register_agent(&name, "agent", HashMap::new(), vec!["shadow".to_string()])?;
+        }
+        Ok(name)
+    }
+
+    /// Hand a live message to the candidate agents
+    pub async fn dispatch_to_shadow_agents(&mut self, message: &BusMessage) {
+        self.shadow_agents.dispatch(message).await;
+    }
+
+    /// Tick the candidate agents and score their decisions against the latest prices
+    pub async fn run_shadow_agents(&mut self, prices: &HashMap<String, f64>, now: DateTime<Utc>) {
+        self.shadow_agents.run(prices, now).await;
+    }
+
+    /// Score of a candidate agent in shadow mode
+    pub fn get_shadow_agent_score(&self, name: &str) -> Option<ShadowScore> {
+        self.shadow_agents.score(name)
+    }
+
+    /// Decisions a candidate agent made in shadow mode
+    pub fn get_shadow_agent_decisions(&self, name: &str) -> Vec<ShadowDecision> {
+        self.shadow_agents.get_decisions(name)
+    }
+
+    /// Promote candidate agents that proved themselves in shadow mode and retire those that did not
+    ///
+    /// Promoted candidates are stopped in shadow mode and their factories returned,
+    /// to be built again on the live bus; they get the smallest capital budget. A
+    /// candidate still undecided after three times the minimum trades is retired.
+    pub async fn promote_shadow_agents(&mut self, coordinator: &mut AgentCoordinator) -> Result<(Vec<(String, Arc<ShadowAgentFactory>)>, Vec<Message>)> {
+        let mut promoted = Vec::new();
+        let mut messages = Vec::new();
+
+        let decided: Vec<(String, ShadowScore)> = self.shadow_agents.get_candidates().into_iter()
+            .filter_map(|name| self.shadow_agents.score(&name).map(|score| (name, score)))
+            .filter(|(_, score)| score.closed >= self.config.shadow_min_trades)
+            .collect();
+
+        for (name, score) in decided {
+            if let Some(metadata) = self.agent_metadata.get_mut(&name) {
+                metadata.performance_score = score.score;
+                metadata.last_active = Utc::now();
+            }
+
+            if score.score >= self.config.promotion_threshold {
+                let Some(factory) = self.shadow_agents.remove(&name).await else {
+                    continue;
+                };
+                if let Some(metadata) = self.agent_metadata.get_mut(&name) {
+                    metadata.tags.retain(|t| t != "shadow");
+                }
+                coordinator.set_agent_budget(&name, coordinator.get_total_capital() * self.config.min_budget_fraction)?;
+
+                self.record_evolution_event(
+                    EvolutionEventType::AgentPromoted,
+                    &name,
+                    "Candidate agent promoted to live trading",
+                    serde_json::json!({ "shadow_score": score.score, "decisions": score.closed, "mean_return": score.mean_return }),
+                );
+                messages.push(Message::Custom(
+                    "agent_promoted".to_string(),
+                    serde_json::json!({
+                        "agent_name": name,
+                        "reason": "Shadow decisions above threshold",
+                        "score": score.score,
+                        "threshold": self.config.promotion_threshold,
+                    }),
+                ));
+                info!("Candidate agent {} promoted to live trading - shadow score: {:.2}", name, score.score);
+                promoted.push((name, factory));
+            } else if score.score <= self.config.kill_threshold || score.closed >= self.config.shadow_min_trades * 3 {
+                self.shadow_agents.remove(&name).await;
+                self.retire_agent(&name, "Candidate agent failed in shadow mode");
+                messages.push(Message::Custom(
+                    "agent_killed".to_string(),
+                    serde_json::json!({
+                        "agent_name": name,
+                        "reason": "Shadow decisions below threshold",
+                        "score": score.score,
+                        "threshold": self.config.kill_threshold,
+                    }),
+                ));
+            }
+        }
+
+        Ok((promoted, messages))
+    }
+
+    /// Names of the challengers in shadow mode
+    pub fn get_shadow_agents(&self) -> Vec<String> {
+        let mut names: Vec<String> = self.shadow.keys().cloned().collect();
+        names.sort();
+        names
+    }
+
+    /// Deactivate an agent for good
+    fn retire_agent(&mut self, name: &str, reason: &str) {
+        let Some(metadata) = self.agent_metadata.get_mut(name) else {
+            return;
+        };
+        if !metadata.active {
+            return;
+        }
+        metadata.active = false;
+        let score = metadata.performance_score;
+        self.families.remove(name);
+        self.builders.remove(name);
+        self.state.agents_killed += 1;
+        self.state.active_agents = self.state.active_agents.saturating_sub(1);
+
+        self.record_evolution_event(
+            EvolutionEventType::AgentKilled,
+            name,
+            reason,
+            serde_json::json!({ "performance_score": score }),
+        );
+        info!("Agent retired: {} - {} (score {:.2})", name, reason, score);
+    }
+
+    /// Mutate an agent, returning the mutated agent's name and announcement
+    fn mutate_agent(&mut self, name: &str) -> Result<Option<(String, Message)>> {
         // First get the metadata and clone what we need
         let mut mutation_data = None;
 
@@ -391,13 +901,20 @@ impl GodKernel {
             // Create mutated parameters
             let mut mutated_params = metadata.parameters.clone();
 
-            // Mutate parameters
+            // Perturb each parameter with the mutation probability, and at least one
             let mut rng = rand::thread_rng();
-            for (_, value) in mutated_params.iter_mut() {
-                // Apply random mutation
+            let mut keys: Vec<String> = mutated_params.keys().cloned().collect();
+            keys.sort();
+            let forced = if keys.is_empty() { None } else { Some(rng.gen_range(0..keys.len())) };
+            for (index, key) in keys.iter().enumerate() {
+                if Some(index) != forced && rng.gen::<f64>() >= self.config.mutation_probability {
+                    continue;
+                }
                 let mutation_factor = 0.1; // 10% mutation
                 let mutation = (rng.gen::<f64>() - 0.5) * 2.0 * mutation_factor;
-                *value *= 1.0 + mutation;
+                if let Some(value) = mutated_params.get_mut(key) {
+                    *value *= 1.0 + mutation;
+                }
             }
 
             // Create mutated agent name
@@ -486,7 +1003,7 @@ impl GodKernel {
 
             info!("Agent mutated: {} -> {}", name, mutated_name);
 
-            return Ok(Some(message));
+            return Ok(Some((mutated_name, message)));
         }
 
         Ok(None)
@@ -741,7 +1258,7 @@ impl Agent for GodKernel {
         Ok(())
     }
 
-    async fn update(&mut self) -> Result<()> {
+    async fn tick(&mut self) -> Result<()> {
         if !self.running {
             return Ok(());
         }
@@ -775,6 +1292,10 @@ impl Agent for GodKernel {
     fn get_config(&self) -> Box<dyn AgentConfig> {
         Box::new(self.config.clone())
     }
+
+    fn topics(&self) -> Vec<String> {
+        vec!["performance.#".to_string()]
+    }
 }
 
 #[cfg(test)]
@@ -869,4 +1390,162 @@ mod tests {
         assert_eq!(mutated_agents.len(), 1);
         assert_eq!(mutated_agents[0].parent.as_ref().unwrap(), "test_agent");
     }
+
+    #[test]
+    fn test_trust_scores_drive_agent_scores() {
+        let mut kernel = GodKernel::new(GodKernelConfig::default(), Arc::new(MessageBus::new()));
+        kernel.set_mutation_probability(0.0);
+        kernel.register_agent("trend", "strategy", HashMap::new(), vec!["strategy".to_string()]).unwrap();
+
+        let trust = |agent: &str, trades: usize, trust_score: f64| AgentTrust {
+            agent: agent.to_string(),
+            trades,
+            hit_rate: 0.5,
+            trust_score,
+        };
+        let scores = HashMap::from([
+            ("MarketAnalyzer".to_string(), trust("MarketAnalyzer", 20, -0.8)),
+            ("QuantumPredictor".to_string(), trust("QuantumPredictor", 3, -0.8)),
+            ("trend".to_string(), trust("trend", 20, 0.9)),
+        ]);
+
+        let messages = kernel.apply_trust_scores(&scores, 10).unwrap();
+        assert_eq!(messages.len(), 1);
+        let analyzer = kernel.get_agent_metadata("MarketAnalyzer").unwrap();
+        assert!(!analyzer.active);
+        assert_eq!(analyzer.performance_score, -0.8);
+        assert!(kernel.get_agent_metadata("QuantumPredictor").is_none());
+        assert_eq!(kernel.get_agent_metadata("trend").unwrap().performance_score, 0.0);
+    }
+
+    struct AlwaysLong {
+        name: String,
+    }
+
+    impl Strategy for AlwaysLong {
+        fn get_name(&self) -> String {
+            self.name.clone()
+        }
+
+        fn analyze(&mut self, symbol: &str, candles: &[Candle]) -> Result<crate::strategy::strategy_trait::StrategySignal> {
+            let mut signal = crate::strategy::strategy_trait::StrategySignal::hold(&self.name, symbol, candles[candles.len() - 1].close, "");
+            signal.decision_type = DecisionType::EnterLong;
+            signal.confidence = 80.0;
+            Ok(signal)
+        }
+
+        fn min_candles(&self) -> usize {
+            2
+        }
+    }
+
+    #[test]
+    fn test_challengers_are_shadow_traded_promoted_and_retired() {
+        let bus = Arc::new(MessageBus::new());
+        let mut kernel = GodKernel::new(GodKernelConfig::default(), Arc::clone(&bus));
+        let builder: StrategyBuilder = Box::new(|name, _params| Ok(Box::new(AlwaysLong { name: name.to_string() })));
+        kernel.register_strategy_builder("trend", HashMap::from([("period".to_string(), 20.0)]), builder).unwrap();
+
+        assert_eq!(kernel.spawn_challengers().unwrap().len(), 1);
+        assert!(kernel.spawn_challengers().unwrap().is_empty());
+        let challenger = kernel.get_shadow_agents()[0].clone();
+        assert_ne!(kernel.get_agent_metadata(&challenger).unwrap().parameters["period"], 20.0);
+
+        // Each new candle is simulated once
+        let candles: Vec<Candle> = (0..10).map(|i| Candle {
+            open_time: i,
+            open: 100.0,
+            high: 101.0,
+            low: 99.0,
+            close: 100.0 + i as f64 * 0.1,
+            volume: 1.0,
+        }).collect();
+        let mut ghost_trader = GhostTrader::new(
+            crate::agents::ghost_trader::GhostTraderConfig { seed: Some(7), ..Default::default() },
+            bus,
+        );
+        let universe = vec![("BTCUSDT".to_string(), candles)];
+        kernel.run_shadow(&universe, &mut ghost_trader);
+        kernel.run_shadow(&universe, &mut ghost_trader);
+        assert_eq!(kernel.shadow[&challenger].outcomes.len(), 1);
+
+        let mut coordinator = AgentCoordinator::new(1000.0);
+        kernel.shadow.get_mut(&challenger).unwrap().outcomes = vec![0.9; 20];
+        let (promoted, _) = kernel.promote_and_retire(&mut coordinator).unwrap();
+        assert_eq!(promoted.len(), 1);
+        assert_eq!(promoted[0].get_name(), challenger);
+        assert!((coordinator.get_agent_budget(&challenger).unwrap().budget - 50.0).abs() < 1e-9);
+
+        // The parent gets a new challenger; this one keeps losing
+        kernel.spawn_challengers().unwrap();
+        let loser = kernel.get_shadow_agents()[0].clone();
+        assert_eq!(kernel.get_agent_metadata(&loser).unwrap().parent.as_deref(), Some("trend"));
+        kernel.shadow.get_mut(&loser).unwrap().outcomes = vec![-0.9; 20];
+        let (promoted, messages) = kernel.promote_and_retire(&mut coordinator).unwrap();
+        assert!(promoted.is_empty());
+        assert_eq!(messages.len(), 1);
+        assert!(!kernel.get_agent_metadata(&loser).unwrap().active);
+        assert!(kernel.get_shadow_agents().is_empty());
+    }
+
+    #[test]
+    fn test_budgets_follow_performance() {
+        let mut kernel = GodKernel::new(GodKernelConfig::default(), Arc::new(MessageBus::new()));
+        kernel.register_agent("winner", "strategy", HashMap::new(), Vec::new()).unwrap();
+        kernel.register_agent("loser", "strategy", HashMap::new(), Vec::new()).unwrap();
+        kernel.agent_metadata.get_mut("winner").unwrap().performance_score = 0.9;
+        kernel.agent_metadata.get_mut("loser").unwrap().performance_score = -0.2;
+
+        let mut coordinator = AgentCoordinator::new(100.0);
+        coordinator.set_agent_budget("winner", 20.0).unwrap();
+        coordinator.set_agent_budget("loser", 20.0).unwrap();
+        coordinator.commit_agent_capital("loser", 15.0).unwrap();
+
+        let changed = kernel.adjust_agent_budgets(&mut coordinator).unwrap();
+        assert_eq!(changed.len(), 2);
+        assert!((coordinator.get_agent_budget("winner").unwrap().budget - 24.0).abs() < 1e-9);
+        assert!((coordinator.get_agent_budget("loser").unwrap().budget - 16.0).abs() < 1e-9);
+        assert!(coordinator.check_agent_budget("loser", 2.0).is_err());
+        assert!(coordinator.check_agent_budget("unbudgeted", 1000.0).is_ok());
+    }
+
+    #[test]
+    fn test_strategy_attribution_only_feeds_new_trades() {
+        use crate::monitoring::performance_monitor::TradeAttribution;
+
+        let mut kernel = GodKernel::new(GodKernelConfig::default(), Arc::new(MessageBus::new()));
+        kernel.set_mutation_probability(0.0);
+        let mut monitor = PerformanceMonitor::new();
+        let trade = |id: usize, strategy: &str, pnl: f64| TradeAttribution {
+            trade_id: format!("{}-{}", strategy, id),
+            strategy: strategy.to_string(),
+            symbol: "BTCUSDT".to_string(),
+            pnl,
+            gross_pnl: pnl,
+            fees: 0.0,
+            funding: 0.0,
+            roi: pnl,
+            entry_time: Utc::now(),
+            exit_time: Utc::now(),
+        };
+        for id in 0..10 {
+            monitor.record_trade(trade(id, "winner", 5.0));
+            monitor.record_trade(trade(id, "loser", -5.0));
+        }
+
+        let messages = kernel.apply_strategy_attribution(&monitor, 10).unwrap();
+        let disables = |messages: &[Message]| messages.iter().filter(|m| StrategyControl::from_message(m).is_some()).count();
+        assert_eq!(disables(&messages), 1);
+        assert!(!kernel.get_agent_metadata("loser").unwrap().active);
+        let events = kernel.get_evolution_events().len();
+
+        // Nothing closed since: the same aggregates are not counted again
+        assert!(kernel.apply_strategy_attribution(&monitor, 10).unwrap().is_empty());
+        assert_eq!(kernel.get_evolution_events().len(), events);
+
+        monitor.record_trade(trade(10, "winner", 5.0));
+        let messages = kernel.apply_strategy_attribution(&monitor, 10).unwrap();
+        assert_eq!(disables(&messages), 0);
+        assert_eq!(kernel.get_evolution_events().len(), events + 1);
+    }
 }
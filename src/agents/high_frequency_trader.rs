@@ -2,13 +2,18 @@
 //!
 //! This module provides the High Frequency Trader agent for the OMNI-ALPHA VΩ∞∞ platform.
 //! The High Frequency Trader agent executes trades at high frequency to achieve the target
-//! of 750 profitable trades per day.
+//! of 750 profitable trades per day. It shares the coordinator's order manager, private
+//! API rate limiter and system state machine, so its entries are throttled per symbol
+//! together with the trade executor's, refused while trading is halted and paced with
+//! every other order call on the account. Entries pass the pre-trade checks the trader
+//! can evaluate on its own, carry an `orderLinkId`, and are followed to their fill.
 
 use std::sync::Arc;
 use std::collections::HashMap;
 use std::time::{Duration, SystemTime};
 use tokio::sync::RwLock;
 use tokio::time::sleep;
+use serde::{Deserialize, Serialize};
 use anyhow::Result;
 use tracing::{info, debug, warn, error};
 use async_trait::async_trait;
@@ -16,15 +21,20 @@ use chrono::Utc;
 use uuid::Uuid;
 
 use crate::engine::agent_trait::{Agent, AgentContext, AgentConfig};
-use crate::engine::message_bus::{BusMessage, MessageBus, MessageType, TradeDirection};
+use crate::engine::message_bus::{BusMessage, Message, MessageBus, MessageType, TradeDirection};
+use crate::engine::state_machine::SharedStateMachine;
 use crate::exchange::bybit::adapter::BybitAdapter;
-use crate::exchange::bybit::types::{OrderSide, OrderType, TimeInForce};
+use crate::exchange::bybit::rate_limiter::SharedRateLimiter;
+use crate::exchange::bybit::types::{BybitOrder, OrderSide, OrderStatus, OrderType, TimeInForce};
 use crate::exchange::asset_scanner::{AssetScanner, TradingOpportunity};
 use crate::agents::main_strategy_controller::{TradingCommand, CommandType, ExecutionResponse};
 use crate::agents::trade_executor::ExecutionStatus;
+use crate::agents::order_flow_agent::{OrderFlowFeatures, ORDER_FLOW_TOPIC};
+use crate::execution::order_manager::{lock_order_manager, ExecutionReport, SharedOrderManager};
+use crate::risk::pre_trade::{PreTradeCheck, PreTradeConfig, PreTradeDecision, PreTradePipeline, TradeProposal};
 
 /// High Frequency Trader Agent configuration
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct HighFrequencyTraderConfig {
     /// Initial capital
     pub initial_capital: f64,
@@ -49,6 +59,26 @@ pub struct HighFrequencyTraderConfig {
 
     /// Trade interval in milliseconds
     pub trade_interval_ms: u64,
+
+    /// Order-flow imbalance (0.0 to 1.0) against which no trade is opened
+    pub min_order_flow_imbalance: f64,
+
+    /// Pre-trade checks run on every entry
+    #[serde(default = "default_pre_trade")]
+    pub pre_trade: PreTradeConfig,
+}
+
+/// Pre-trade settings fitted to sub-minute scalps
+///
+/// Scalps target less than they risk and rely on their hit rate, and the same
+/// symbol may be traded again within a minute.
+fn default_pre_trade() -> PreTradeConfig {
+    PreTradeConfig {
+        min_confidence: 0.8,
+        min_risk_reward_ratio: 0.7,
+        symbol_cooldown_secs: 60,
+        ..PreTradeConfig::default()
+    }
 }
 
 impl Default for HighFrequencyTraderConfig {
@@ -62,6 +92,8 @@ impl Default for HighFrequencyTraderConfig {
             max_assets: 100,
             dynamic_leverage: true,
             trade_interval_ms: 115200, // 86400000 ms in a day / 750 trades = 115200 ms per trade
+            min_order_flow_imbalance: 0.3,
+            pre_trade: default_pre_trade(),
         }
     }
 }
@@ -171,16 +203,45 @@ pub struct HighFrequencyTrader {
 
     /// Total profit
     total_profit: f64,
+
+    /// Latest order flow by symbol
+    order_flow: HashMap<String, OrderFlowFeatures>,
+
+    /// Order state machine, shared with the coordinator's trade executor
+    order_manager: SharedOrderManager,
+
+    /// Private API rate limiter, shared with the coordinator's trade executor
+    order_limiter: SharedRateLimiter,
+
+    /// System state machine whose halt stops new entries
+    state_machine: SharedStateMachine,
+
+    /// Pre-trade checks and their record of rejections
+    pre_trade: PreTradePipeline,
+
+    /// Submitted orders not yet settled: exchange order ID -> symbol
+    pending_orders: HashMap<String, String>,
 }
 
 impl HighFrequencyTrader {
     /// Create a new High Frequency Trader Agent
-    pub fn new(config: HighFrequencyTraderConfig, exchange: Arc<BybitAdapter>, message_bus: Arc<MessageBus>) -> Self {
+    ///
+    /// `order_manager`, `order_limiter` and `state_machine` are the coordinator's
+    /// (`AgentCoordinator::get_trade_executor` and `get_risk_manager`).
+    pub fn new(
+        config: HighFrequencyTraderConfig,
+        exchange: Arc<BybitAdapter>,
+        message_bus: Arc<MessageBus>,
+        order_manager: SharedOrderManager,
+        order_limiter: SharedRateLimiter,
+        state_machine: SharedStateMachine,
+    ) -> Self {
         let asset_scanner = AssetScanner::new(
             exchange.clone(),
             config.max_assets,
             config.timeframes.clone(),
         );
+        let pre_trade = PreTradePipeline::new(config.pre_trade.clone());
 
         Self {
             config,
@@ -195,6 +256,213 @@ impl HighFrequencyTrader {
             last_day: chrono::Utc::now().date().and_hms(0, 0, 0).timestamp(),
             current_capital: 12.0,
             total_profit: 0.0,
+            order_flow: HashMap::new(),
+            order_manager,
+            order_limiter,
+            state_machine,
+            pre_trade,
+            pending_orders: HashMap::new(),
+        }
+    }
+
+    /// Get the order manager
+    pub fn get_order_manager(&self) -> SharedOrderManager {
+        Arc::clone(&self.order_manager)
+    }
+
+    /// Get the pre-trade pipeline
+    pub fn get_pre_trade(&self) -> &PreTradePipeline {
+        &self.pre_trade
+    }
+
+    /// Run an entry through the pre-trade checks, recording the outcome
+    fn run_pre_trade_checks(&mut self, proposal: &TradeProposal, max_leverage: f64) -> PreTradeDecision {
+        let checks = self.pre_trade.get_checks().to_vec();
+        let decision = PreTradePipeline::evaluate(&checks, proposal, |check, proposal| {
+            self.evaluate_pre_trade_check(check, proposal, max_leverage)
+        });
+        self.pre_trade.record(&decision);
+        decision
+    }
+
+    /// Evaluate one pre-trade check
+    ///
+    /// Market conditions and correlation are judged from the trading system's
+    /// components, which the trader does not have; those checks pass here.
+    fn evaluate_pre_trade_check(&self, check: PreTradeCheck, proposal: &TradeProposal, max_leverage: f64) -> Result<()> {
+        let symbol = proposal.symbol.as_str();
+        let config = self.pre_trade.get_config();
+
+        match check {
+            PreTradeCheck::TradingState => {
+                let state_machine = self.state_machine.read()
+                    .map_err(|_| anyhow::anyhow!("System state machine is poisoned"))?;
+                if state_machine.is_halted() {
+                    return Err(anyhow::anyhow!("trading halted"));
+                }
+            },
+            PreTradeCheck::Capacity => {
+                if self.active_trades.len() >= self.config.max_concurrent_trades {
+                    return Err(anyhow::anyhow!("{} trades already open", self.active_trades.len()));
+                }
+            },
+            PreTradeCheck::Capital => {
+                let margin = proposal.position_value() / proposal.leverage.max(1.0);
+                if margin <= 0.0 {
+                    return Err(anyhow::anyhow!("no position size"));
+                }
+                if margin > self.current_capital {
+                    return Err(anyhow::anyhow!("margin {:.2} exceeds {:.2} available", margin, self.current_capital));
+                }
+            },
+            PreTradeCheck::Exposure => {
+                if self.active_trades.contains_key(symbol) {
+                    return Err(anyhow::anyhow!("trade already open on {}", symbol));
+                }
+            },
+            PreTradeCheck::Leverage => {
+                if proposal.leverage > max_leverage {
+                    return Err(anyhow::anyhow!("leverage {:.1}x above the {:.1}x cap", proposal.leverage, max_leverage));
+                }
+            },
+            PreTradeCheck::Cooldown => {
+                self.pre_trade.check_cooldown(symbol, &proposal.source, proposal.timestamp)?;
+            },
+            PreTradeCheck::ZeroLoss => {
+                if proposal.confidence < config.min_confidence {
+                    return Err(anyhow::anyhow!("confidence {:.2} below {:.2}", proposal.confidence, config.min_confidence));
+                }
+                let risk = (proposal.entry_price - proposal.stop_loss_price).abs();
+                let reward = (proposal.take_profit_price - proposal.entry_price).abs();
+                if risk <= 0.0 || reward / risk < config.min_risk_reward_ratio {
+                    return Err(anyhow::anyhow!("reward-to-risk below {:.2}", config.min_risk_reward_ratio));
+                }
+            },
+            PreTradeCheck::MarketConditions | PreTradeCheck::Correlation => {},
+        }
+
+        Ok(())
+    }
+
+    /// Submit a market order through the order state machine and the rate limiter
+    ///
+    /// Entries are refused while trading is halted or the symbol is throttled;
+    /// exits (`reduce_only`) are always submitted.
+    async fn submit_order(
+        &mut self,
+        symbol: &str,
+        side: OrderSide,
+        quantity: f64,
+        time_in_force: TimeInForce,
+        reduce_only: bool,
+        take_profit: Option<f64>,
+        stop_loss: Option<f64>,
+    ) -> Result<BybitOrder> {
+        let (local_order_id, order_link_id) = {
+            let mut order_manager = lock_order_manager(&self.order_manager)?;
+            let local_order_id = if reduce_only {
+                order_manager.create_order(symbol, side, OrderType::Market, quantity, None)?
+            } else {
+                order_manager.create_entry_order(symbol, side, OrderType::Market, quantity, None, Utc::now())?
+            };
+            let order_link_id = order_manager.get_order(&local_order_id).map(|o| o.order_link_id.clone());
+            (local_order_id, order_link_id)
+        };
+
+        self.order_limiter.lock().await.wait_if_needed().await?;
+        let order_result = self.exchange.place_linked_order(
+            symbol,
+            side,
+            OrderType::Market,
+            quantity,
+            None,
+            time_in_force,
+            reduce_only,
+            false, // close_on_trigger
+            take_profit,
+            stop_loss,
+            order_link_id.as_deref(),
+        ).await;
+
+        match order_result {
+            Ok(order) => {
+                let _ = lock_order_manager(&self.order_manager)?.apply_report(&local_order_id, ExecutionReport::Submitted {
+                    exchange_order_id: order.order_id.clone(),
+                });
+                self.pending_orders.insert(order.order_id.clone(), symbol.to_string());
+                Ok(order)
+            },
+            Err(e) => {
+                let _ = lock_order_manager(&self.order_manager)?.apply_report(&local_order_id, ExecutionReport::Rejected { reason: e.to_string() });
+                Err(e)
+            }
+        }
+    }
+
+    /// Advance submitted orders from their exchange status until they settle
+    async fn sync_orders(&mut self) -> Result<()> {
+        let pending: Vec<(String, String)> = self.pending_orders.iter()
+            .map(|(order_id, symbol)| (order_id.clone(), symbol.clone()))
+            .collect();
+
+        for (order_id, symbol) in pending {
+            self.order_limiter.lock().await.wait_if_needed().await?;
+            let order = match self.exchange.get_order(&symbol, &order_id).await {
+                Ok(order) => order,
+                Err(e) => {
+                    debug!("Status of order {} on {} unavailable: {}", order_id, symbol, e);
+                    continue;
+                }
+            };
+
+            let mut order_manager = lock_order_manager(&self.order_manager)?;
+            // An IOC order that filled only partly ends cancelled: book the fill first
+            let fill = order_manager.get_order_by_exchange_id(&order_id)
+                .and_then(|managed| managed.incremental_fill(order.cum_exec_qty, order.cum_exec_value));
+            let closed = match &order.order_status {
+                status @ (OrderStatus::Cancelled | OrderStatus::Rejected) =>
+                    ExecutionReport::from_exchange_status(status.clone(), &order_id),
+                _ => None,
+            };
+            for report in fill.into_iter().chain(closed) {
+                if let Err(e) = order_manager.apply_exchange_report(&order_id, report) {
+                    debug!("Report for order {} not applied: {}", order_id, e);
+                }
+            }
+
+            let settled = order_manager.get_order_by_exchange_id(&order_id)
+                .map_or(true, |managed| managed.state.is_terminal());
+            drop(order_manager);
+            if settled {
+                self.pending_orders.remove(&order_id);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Record the latest order flow of a symbol
+    pub fn update_order_flow(&mut self, features: OrderFlowFeatures) {
+        self.order_flow.insert(features.symbol.clone(), features);
+    }
+
+    /// Get the latest order flow of a symbol
+    pub fn get_order_flow(&self, symbol: &str) -> Option<&OrderFlowFeatures> {
+        self.order_flow.get(symbol)
+    }
+
+    /// Whether one-sided order flow runs against an opportunity
+    fn against_order_flow(&self, opportunity: &TradingOpportunity) -> bool {
+        let Some(flow) = self.order_flow.get(&opportunity.symbol) else {
+            return false;
+        };
+        if !flow.is_one_sided(self.config.min_order_flow_imbalance) {
+            return false;
+        }
+        match opportunity.action.as_str() {
+            "buy" => flow.imbalance < 0.0,
+            "sell" => flow.imbalance > 0.0,
+            _ => false,
         }
     }
 
@@ -312,9 +580,10 @@ impl HighFrequencyTrader {
 
         let opportunities = self.asset_scanner.scan_all_assets().await?;
 
-        // Filter by score threshold
+        // Filter by score threshold, skipping trades into one-sided order flow
         let filtered_opportunities: Vec<TradingOpportunity> = opportunities.into_iter()
             .filter(|o| o.score >= 0.8)
+            .filter(|o| !self.against_order_flow(o))
             .collect();
 
         debug!("Found {} high-quality trading opportunities", filtered_opportunities.len());
@@ -374,14 +643,6 @@ impl HighFrequencyTrader {
         info!("Position size: {} (value: ${:.2}), Target profit: ${:.2}",
               quantity, position_value, self.config.min_profit_per_trade);
 
-        // Set leverage for the symbol
-        if self.config.dynamic_leverage && leverage > 1.0 {
-            match self.exchange.set_leverage(&opportunity.symbol, leverage as u32).await {
-                Ok(_) => info!("Set leverage to {}x for {}", leverage, opportunity.symbol),
-                Err(e) => warn!("Failed to set leverage for {}: {}", opportunity.symbol, e),
-            }
-        }
-
         // Calculate take profit price (to achieve minimum profit)
         let take_profit = match opportunity.action.as_str() {
             "buy" => opportunity.price * (1.0 + (self.config.min_profit_per_trade / (position_value * leverage))),
@@ -396,6 +657,31 @@ impl HighFrequencyTrader {
             _ => opportunity.price,
         };
 
+        let proposal = TradeProposal {
+            symbol: opportunity.symbol.clone(),
+            direction: if opportunity.action == "buy" { TradeDirection::Long } else { TradeDirection::Short },
+            source: self.get_name().to_string(),
+            confidence: opportunity.score,
+            entry_price: opportunity.price,
+            stop_loss_price: stop_loss,
+            take_profit_price: take_profit,
+            position_size: quantity,
+            leverage,
+            timestamp: Utc::now(),
+        };
+        if !self.run_pre_trade_checks(&proposal, asset_metadata.max_leverage).is_approved() {
+            return Ok(());
+        }
+
+        // Set leverage for the symbol
+        if self.config.dynamic_leverage && leverage > 1.0 {
+            self.order_limiter.lock().await.wait_if_needed().await?;
+            match self.exchange.set_leverage(&opportunity.symbol, leverage as u32).await {
+                Ok(_) => info!("Set leverage to {}x for {}", leverage, opportunity.symbol),
+                Err(e) => warn!("Failed to set leverage for {}: {}", opportunity.symbol, e),
+            }
+        }
+
         // Place order
         let side = if opportunity.action == "buy" {
             OrderSide::Buy
@@ -403,20 +689,24 @@ impl HighFrequencyTrader {
             OrderSide::Sell
         };
 
-        let order = self.exchange.place_order(
+        let order = match self.submit_order(
             &opportunity.symbol,
             side,
-            OrderType::Market,
             quantity,
-            None,
             TimeInForce::GoodTillCancel,
             false,
-            false,
             Some(take_profit),
             Some(stop_loss),
-        ).await?;
+        ).await {
+            Ok(order) => order,
+            Err(e) => {
+                debug!("Opportunity on {} not executed: {}", opportunity.symbol, e);
+                return Ok(());
+            }
+        };
 
         info!("Order placed successfully! Order ID: {}", order.order_id);
+        self.pre_trade.record_entry(&opportunity.symbol, self.get_name(), Utc::now());
 
         // Create trade record
         let trade_record = TradeRecord {
@@ -483,6 +773,9 @@ impl HighFrequencyTrader {
 
     /// Update active trades
     async fn update_active_trades(&mut self) -> Result<()> {
+        // Follow submitted orders to their fills first
+        self.sync_orders().await?;
+
         // Get positions
         let positions = self.exchange.get_positions(None).await?;
 
@@ -620,17 +913,41 @@ impl HighFrequencyTrader {
         // Calculate quantity based on position size and leverage
         let quantity = (command.position_size * command.leverage) / command.entry_price;
 
+        let max_leverage = self.get_asset_metadata(&command.symbol).await
+            .map_or(command.leverage, |metadata| metadata.max_leverage);
+        let proposal = TradeProposal {
+            symbol: command.symbol.clone(),
+            direction: command.direction.clone(),
+            source: self.get_name().to_string(),
+            confidence: command.confidence / 100.0,
+            entry_price: command.entry_price,
+            stop_loss_price: command.stop_loss,
+            take_profit_price: command.take_profit,
+            position_size: quantity,
+            leverage: command.leverage,
+            timestamp: Utc::now(),
+        };
+        if let Some(rejection) = self.run_pre_trade_checks(&proposal, max_leverage).rejection {
+            return Err(anyhow::anyhow!("Pre-trade {:?} check failed: {}", rejection.check, rejection.reason));
+        }
+
+        // Set the commanded leverage
+        if command.leverage > 1.0 {
+            self.order_limiter.lock().await.wait_if_needed().await?;
+            if let Err(e) = self.exchange.set_leverage(&command.symbol, command.leverage as u32).await {
+                warn!("Failed to set leverage for {}: {}", command.symbol, e);
+            }
+        }
+
         // Place the order
-        let order_result = self.exchange.place_order(
+        let order_result = self.submit_order(
             &command.symbol,
             side,
-            OrderType::Market,
             quantity,
-            None, // Market order, no price needed
             TimeInForce::IOC,
-            Some(command.leverage),
-            Some(command.stop_loss),
+            false,
             Some(command.take_profit),
+            Some(command.stop_loss),
         ).await?;
 
         // Create trade record
@@ -651,6 +968,7 @@ impl HighFrequencyTrader {
 
         // Add to active trades
         self.active_trades.insert(command.symbol.clone(), trade_record);
+        self.pre_trade.record_entry(&command.symbol, self.get_name(), Utc::now());
 
         // Update trade counter
         self.trades_today += 1;
@@ -669,17 +987,16 @@ impl HighFrequencyTrader {
                 OrderSide::Buy => OrderSide::Sell,
                 OrderSide::Sell => OrderSide::Buy,
             };
+            let quantity = trade_record.quantity;
 
-            let close_result = self.exchange.place_order(
+            let close_result = self.submit_order(
                 &command.symbol,
                 close_side,
-                OrderType::Market,
-                trade_record.quantity,
-                None,
+                quantity,
                 TimeInForce::IOC,
-                None, // No leverage for closing
-                None, // No stop loss for closing
-                None, // No take profit for closing
+                true, // reduce_only
+                None,
+                None,
             ).await?;
 
             // Remove from active trades
@@ -717,14 +1034,19 @@ impl HighFrequencyTrader {
 
     /// Cancel order command
     async fn cancel_order_command(&mut self, command: &TradingCommand) -> Result<String> {
-        if let Some(trade_record) = self.active_trades.get(&command.symbol) {
+        if let Some(order_id) = self.active_trades.get(&command.symbol).map(|t| t.order_id.clone()) {
             // Cancel the order
-            self.exchange.cancel_order(&command.symbol, &trade_record.order_id).await?;
+            self.order_limiter.lock().await.wait_if_needed().await?;
+            self.exchange.cancel_order(&command.symbol, &order_id).await?;
+            if let Err(e) = lock_order_manager(&self.order_manager)?.apply_exchange_report(&order_id, ExecutionReport::Cancelled) {
+                debug!("Cancellation of {} not tracked: {}", order_id, e);
+            }
+            self.pending_orders.remove(&order_id);
 
             // Remove from active trades
             self.active_trades.remove(&command.symbol);
 
-            info!("✅ Order cancelled: {} (Order: {})", command.symbol, trade_record.order_id);
+            info!("✅ Order cancelled: {} (Order: {})", command.symbol, order_id);
             Ok("order_cancelled".to_string())
         } else {
             Err(anyhow::anyhow!("No active position found for {}", command.symbol))
@@ -744,15 +1066,14 @@ impl HighFrequencyTrader {
                     OrderSide::Buy => OrderSide::Sell,
                     OrderSide::Sell => OrderSide::Buy,
                 };
+                let quantity = trade_record.quantity;
 
-                if let Ok(_) = self.exchange.place_order(
+                if let Ok(_) = self.submit_order(
                     &symbol,
                     close_side,
-                    OrderType::Market,
-                    trade_record.quantity,
-                    None,
+                    quantity,
                     TimeInForce::IOC,
-                    None,
+                    true, // reduce_only
                     None,
                     None,
                 ).await {
@@ -866,7 +1187,7 @@ impl Agent for HighFrequencyTrader {
         Ok(())
     }
 
-    async fn update(&mut self) -> Result<()> {
+    async fn tick(&mut self) -> Result<()> {
         if !self.running {
             return Ok(());
         }
@@ -936,6 +1257,18 @@ impl Agent for HighFrequencyTrader {
     }
 
     async fn handle_message(&mut self, message: BusMessage) -> Result<()> {
+        // Order flow features arrive as the trading loop's messages, on `agent.order_flow`
+        if message.topic == format!("agent.{}", ORDER_FLOW_TOPIC) {
+            match serde_json::from_str::<Message>(&message.content) {
+                Ok(flow) => match OrderFlowFeatures::from_message(&flow) {
+                    Some(features) => self.update_order_flow(features),
+                    None => debug!("No order flow features on {}", message.topic),
+                },
+                Err(e) => warn!("Failed to parse order flow message: {}", e),
+            }
+            return Ok(());
+        }
+
         match message.message_type {
             MessageType::TradingCommand => {
                 info!("📥 Received trading command from main strategy controller");
@@ -993,4 +1326,80 @@ impl Agent for HighFrequencyTrader {
     fn get_config(&self) -> Box<dyn AgentConfig> {
         Box::new(self.config.clone())
     }
+
+    fn topics(&self) -> Vec<String> {
+        // Commands from the controller, and the order flow features on `agent.order_flow`
+        vec!["agent.#".to_string()]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::state_machine::{Event, StateMachine};
+    use crate::exchange::bybit::rate_limiter::RateLimiter;
+    use crate::execution::order_manager::{shared_order_manager, OrderManager};
+
+    #[tokio::test]
+    async fn test_entries_are_refused_while_halted() {
+        let order_manager = shared_order_manager(OrderManager::new());
+        let state_machine = StateMachine::shared();
+        order_manager.lock().unwrap().set_state_machine(Arc::clone(&state_machine));
+        let mut trader = HighFrequencyTrader::new(
+            HighFrequencyTraderConfig::default(),
+            Arc::new(BybitAdapter::new("", "", true)),
+            Arc::new(MessageBus::new()),
+            Arc::clone(&order_manager),
+            Arc::new(tokio::sync::Mutex::new(RateLimiter::bybit_private())),
+            Arc::clone(&state_machine),
+        );
+        state_machine.write().unwrap().handle(Event::Start).unwrap();
+        state_machine.write().unwrap().handle(Event::Halt("drawdown".to_string())).unwrap();
+
+        let result = trader.submit_order("BTCUSDT", OrderSide::Buy, 0.01, TimeInForce::IOC, false, None, None).await;
+        assert!(result.unwrap_err().to_string().contains("Trading halted"));
+        assert!(order_manager.lock().unwrap().get_open_orders().is_empty());
+
+        // The halt also fails the pre-trade checks
+        let proposal = TradeProposal {
+            symbol: "BTCUSDT".to_string(),
+            direction: TradeDirection::Long,
+            source: "HighFrequencyTrader".to_string(),
+            confidence: 0.9,
+            entry_price: 100.0,
+            stop_loss_price: 99.5,
+            take_profit_price: 100.5,
+            position_size: 0.01,
+            leverage: 1.0,
+            timestamp: Utc::now(),
+        };
+        let decision = trader.run_pre_trade_checks(&proposal, 50.0);
+        assert_eq!(decision.rejection.unwrap().check, PreTradeCheck::TradingState);
+    }
+
+    #[tokio::test]
+    async fn test_order_flow_messages_update_the_flow() {
+        let state_machine = StateMachine::shared();
+        let mut trader = HighFrequencyTrader::new(
+            HighFrequencyTraderConfig::default(),
+            Arc::new(BybitAdapter::new("", "", true)),
+            Arc::new(MessageBus::new()),
+            shared_order_manager(OrderManager::new()),
+            Arc::new(tokio::sync::Mutex::new(RateLimiter::bybit_private())),
+            state_machine,
+        );
+        let features = OrderFlowFeatures {
+            symbol: "BTCUSDT".to_string(),
+            imbalance: -0.8,
+            ..OrderFlowFeatures::default()
+        };
+        trader.handle_message(BusMessage {
+            message_type: MessageType::AgentCommunication,
+            topic: format!("agent.{}", ORDER_FLOW_TOPIC),
+            content: serde_json::to_string(&features.to_message()).unwrap(),
+            timestamp: Utc::now(),
+        }).await.unwrap();
+
+        assert_eq!(trader.get_order_flow("BTCUSDT").map(|flow| flow.imbalance), Some(-0.8));
+    }
 }
@@ -0,0 +1,379 @@
+//! Macro-Economic Calendar Agent
+//!
+//! This agent reads an economic-calendar feed and keeps the schedule of macro
+//! releases that move crypto (CPI, FOMC, NFP and the like). The schedule is
+//! published as a whole so the gap protection can block entries and close
+//! leveraged positions around each event, and every pre-event risk window is
+//! announced once when it opens. Impact comes from the feed and is raised to
+//! high for releases whose name matches one of the configured keywords.
+
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::time::Duration;
+use chrono::{DateTime, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use anyhow::Result;
+use async_trait::async_trait;
+use reqwest::Client;
+use tokio::sync::RwLock;
+use tracing::{debug, info, warn};
+
+use crate::engine::agent_trait::{Agent, AgentConfig, AgentContext};
+use crate::engine::message_bus::{BusMessage, Message, MessageBus};
+use crate::risk::protection::{EventImpact, ScheduledEvent};
+
+/// Topic used for the macro event schedule (`Message::Custom`)
+pub const MACRO_CALENDAR_TOPIC: &str = "macro_calendar";
+
+/// Topic used when a pre-event risk window opens (`Message::Custom`)
+pub const MACRO_RISK_WINDOW_TOPIC: &str = "macro_risk_window";
+
+/// Macro calendar agent settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MacroCalendarAgentConfig {
+    /// Calendar feed URL
+    pub url: String,
+
+    /// API key, sent as a bearer token when set
+    pub api_key: Option<String>,
+
+    /// JSON pointer to the event array (empty for the document root)
+    pub events_pointer: String,
+
+    /// Event name field
+    pub name_field: String,
+
+    /// Release time field (RFC 3339 or Unix seconds)
+    pub time_field: String,
+
+    /// Impact field ("High", "Medium", "Low" or 3, 2, 1)
+    pub impact_field: String,
+
+    /// Country or currency field
+    pub country_field: String,
+
+    /// Countries or currencies kept; empty keeps all
+    pub countries: Vec<String>,
+
+    /// Name fragments that always mean high impact
+    pub high_impact_keywords: Vec<String>,
+
+    /// Lowest impact kept
+    pub min_impact: EventImpact,
+
+    /// Hours ahead events are kept
+    pub lookahead_hours: i64,
+
+    /// Seconds between feed reads
+    pub poll_interval_secs: i64,
+
+    /// Minutes before an event its risk window opens
+    pub pre_event_minutes: i64,
+
+    /// Minutes after an event its risk window closes
+    pub post_event_minutes: i64,
+
+    /// HTTP request timeout (seconds)
+    pub request_timeout_secs: u64,
+}
+
+impl Default for MacroCalendarAgentConfig {
+    fn default() -> Self {
+        Self {
+            url: "https://nfs.faireconomy.media/ff_calendar_thisweek.json".to_string(),
+            api_key: None,
+            events_pointer: String::new(),
+            name_field: "title".to_string(),
+            time_field: "date".to_string(),
+            impact_field: "impact".to_string(),
+            country_field: "country".to_string(),
+            countries: vec!["USD".to_string(), "US".to_string()],
+            high_impact_keywords: ["CPI", "FOMC", "Federal Funds Rate", "Non-Farm", "Nonfarm", "NFP", "PCE", "Powell"]
+                .iter().map(|k| k.to_string()).collect(),
+            min_impact: EventImpact::Medium,
+            lookahead_hours: 168,
+            poll_interval_secs: 3600,
+            pre_event_minutes: 30,
+            post_event_minutes: 15,
+            request_timeout_secs: 10,
+        }
+    }
+}
+
+/// Upcoming macro events
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MacroCalendar {
+    /// Time the feed was read
+    pub timestamp: DateTime<Utc>,
+
+    /// Events in release order
+    pub events: Vec<ScheduledEvent>,
+}
+
+impl MacroCalendar {
+    /// Convert to a bus message
+    pub fn to_message(&self) -> Message {
+        Message::Custom(
+            MACRO_CALENDAR_TOPIC.to_string(),
+            serde_json::to_value(self).unwrap_or(Value::Null),
+        )
+    }
+
+    /// Parse a macro calendar from a message, if it is one
+    pub fn from_message(message: &Message) -> Option<Self> {
+        match message {
+            Message::Custom(topic, payload) if topic == MACRO_CALENDAR_TOPIC => {
+                match serde_json::from_value(payload.clone()) {
+                    Ok(calendar) => Some(calendar),
+                    Err(e) => {
+                        warn!("Ignoring malformed macro calendar: {}", e);
+                        None
+                    }
+                }
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Risk window around one event
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MacroRiskWindow {
+    /// Event
+    pub event: ScheduledEvent,
+
+    /// Window start
+    pub opens_at: DateTime<Utc>,
+
+    /// Window end
+    pub closes_at: DateTime<Utc>,
+}
+
+/// Macro-economic calendar agent
+pub struct MacroCalendarAgent {
+    /// Settings
+    config: MacroCalendarAgentConfig,
+
+    /// HTTP client
+    client: Client,
+
+    /// Message bus the schedule and windows are published on
+    message_bus: Arc<MessageBus>,
+
+    /// Whether the agent is running
+    running: bool,
+
+    /// Last feed read
+    last_poll: Option<DateTime<Utc>>,
+
+    /// Upcoming events
+    events: Vec<ScheduledEvent>,
+
+    /// Events whose window was announced, as (name, release time)
+    announced: HashSet<(String, DateTime<Utc>)>,
+}
+
+impl MacroCalendarAgent {
+    /// Create a new macro calendar agent
+    pub fn new(config: MacroCalendarAgentConfig, message_bus: Arc<MessageBus>) -> Self {
+        let client = Client::builder()
+            .timeout(Duration::from_secs(config.request_timeout_secs.max(1)))
+            .build()
+            .unwrap_or_default();
+        Self {
+            config,
+            client,
+            message_bus,
+            running: false,
+            last_poll: None,
+            events: Vec::new(),
+            announced: HashSet::new(),
+        }
+    }
+
+    /// Read the events of a feed response that matter between `now` and the lookahead
+    pub fn parse_calendar(&self, value: &Value, now: DateTime<Utc>) -> Vec<ScheduledEvent> {
+        let Some(items) = value.pointer(&self.config.events_pointer).and_then(Value::as_array) else {
+            return Vec::new();
+        };
+        let oldest = now - chrono::Duration::minutes(self.config.post_event_minutes);
+        let newest = now + chrono::Duration::hours(self.config.lookahead_hours);
+
+        let mut events: Vec<ScheduledEvent> = items.iter()
+            .filter_map(|item| {
+                let name = item.get(&self.config.name_field)?.as_str()?.trim().to_string();
+                if !self.config.countries.is_empty() {
+                    let country = item.get(&self.config.country_field)?.as_str()?;
+                    if !self.config.countries.iter().any(|c| c.eq_ignore_ascii_case(country)) {
+                        return None;
+                    }
+                }
+                let scheduled_at = match item.get(&self.config.time_field)? {
+                    Value::String(s) => DateTime::parse_from_rfc3339(s).ok()?.with_timezone(&Utc),
+                    Value::Number(n) => Utc.timestamp_opt(n.as_i64()?, 0).single()?,
+                    _ => return None,
+                };
+                let impact = self.impact_of(&name, item.get(&self.config.impact_field));
+                Some(ScheduledEvent { name, scheduled_at, impact })
+            })
+            .filter(|event| event.impact >= self.config.min_impact)
+            .filter(|event| event.scheduled_at >= oldest && event.scheduled_at <= newest)
+            .collect();
+
+        events.sort_by_key(|event| event.scheduled_at);
+        events
+    }
+
+    /// Impact of an event from its name and the feed's impact field
+    fn impact_of(&self, name: &str, impact: Option<&Value>) -> EventImpact {
+        let name = name.to_lowercase();
+        if self.config.high_impact_keywords.iter().any(|k| name.contains(&k.to_lowercase())) {
+            return EventImpact::High;
+        }
+        match impact {
+            Some(Value::String(s)) if s.eq_ignore_ascii_case("high") => EventImpact::High,
+            Some(Value::String(s)) if s.eq_ignore_ascii_case("medium") => EventImpact::Medium,
+            Some(Value::Number(n)) if n.as_i64() == Some(3) => EventImpact::High,
+            Some(Value::Number(n)) if n.as_i64() == Some(2) => EventImpact::Medium,
+            _ => EventImpact::Low,
+        }
+    }
+
+    /// Replace the upcoming events
+    pub fn set_events(&mut self, events: Vec<ScheduledEvent>) {
+        self.events = events;
+    }
+
+    /// Upcoming events
+    pub fn get_events(&self) -> &[ScheduledEvent] {
+        &self.events
+    }
+
+    /// Risk windows that are open at `now` and were not announced yet
+    pub fn due_windows(&mut self, now: DateTime<Utc>) -> Vec<MacroRiskWindow> {
+        let before = chrono::Duration::minutes(self.config.pre_event_minutes);
+        let after = chrono::Duration::minutes(self.config.post_event_minutes);
+        let mut windows = Vec::new();
+
+        for event in &self.events {
+            let (opens_at, closes_at) = (event.scheduled_at - before, event.scheduled_at + after);
+            if now < opens_at || now > closes_at {
+                continue;
+            }
+            if self.announced.insert((event.name.clone(), event.scheduled_at)) {
+                windows.push(MacroRiskWindow { event: event.clone(), opens_at, closes_at });
+            }
+        }
+
+        self.announced.retain(|(_, scheduled_at)| *scheduled_at + after >= now);
+        windows
+    }
+
+    /// Read the feed
+    async fn fetch(&self, now: DateTime<Utc>) -> Result<Vec<ScheduledEvent>> {
+        let mut request = self.client.get(&self.config.url);
+        if let Some(api_key) = &self.config.api_key {
+            request = request.bearer_auth(api_key);
+        }
+        let value: Value = request.send().await?.error_for_status()?.json().await?;
+        Ok(self.parse_calendar(&value, now))
+    }
+}
+
+#[async_trait]
+impl Agent for MacroCalendarAgent {
+    fn get_name(&self) -> &str {
+        "MacroCalendarAgent"
+    }
+
+    fn get_config(&self) -> Box<dyn AgentConfig> {
+        Box::new(self.config.clone())
+    }
+
+    async fn initialize(&mut self, _context: Arc<RwLock<AgentContext>>) -> Result<()> {
+        info!("Initializing Macro Calendar Agent from {}", self.config.url);
+        Ok(())
+    }
+
+    async fn start(&mut self) -> Result<()> {
+        self.running = true;
+        Ok(())
+    }
+
+    async fn stop(&mut self) -> Result<()> {
+        self.running = false;
+        Ok(())
+    }
+
+    async fn tick(&mut self) -> Result<()> {
+        if !self.running {
+            return Ok(());
+        }
+
+        let now = Utc::now();
+        let due = self.last_poll
+            .map_or(true, |last| now - last >= chrono::Duration::seconds(self.config.poll_interval_secs));
+        if due {
+            self.last_poll = Some(now);
+            match self.fetch(now).await {
+                Ok(events) => {
+                    debug!("Macro calendar holds {} upcoming events", events.len());
+                    self.events = events;
+                    let calendar = MacroCalendar { timestamp: now, events: self.events.clone() };
+                    self.message_bus.send(calendar.to_message());
+                },
+                Err(e) => warn!("Failed to read the macro calendar: {}", e),
+            }
+        }
+
+        for window in self.due_windows(now) {
+            info!("Risk window open for {} ({:?}) at {} until {}",
+                  window.event.name, window.event.impact, window.event.scheduled_at, window.closes_at);
+            self.message_bus.send(Message::Custom(
+                MACRO_RISK_WINDOW_TOPIC.to_string(),
+                serde_json::to_value(&window).unwrap_or(Value::Null),
+            ));
+        }
+        Ok(())
+    }
+
+    async fn handle_message(&mut self, _message: BusMessage) -> Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_calendar_parsing_and_risk_windows() {
+        let mut agent = MacroCalendarAgent::new(MacroCalendarAgentConfig::default(), Arc::new(MessageBus::new()));
+        let now = Utc::now();
+        let at = |minutes: i64| (now + chrono::Duration::minutes(minutes)).to_rfc3339();
+
+        let feed = serde_json::json!([
+            { "title": "Core CPI m/m", "country": "USD", "date": at(20), "impact": "Medium" },
+            { "title": "Unemployment Claims", "country": "USD", "date": at(600), "impact": "Medium" },
+            { "title": "Retail Sales", "country": "USD", "date": at(300), "impact": "Low" },
+            { "title": "ECB Press Conference", "country": "EUR", "date": at(120), "impact": "High" },
+            { "title": "FOMC Statement", "country": "USD", "date": at(-60), "impact": "High" },
+        ]);
+        let events = agent.parse_calendar(&feed, now);
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].name, "Core CPI m/m");
+        assert_eq!(events[0].impact, EventImpact::High);
+        assert_eq!(events[1].impact, EventImpact::Medium);
+
+        agent.set_events(events);
+        let windows = agent.due_windows(now);
+        assert_eq!(windows.len(), 1);
+        assert_eq!(windows[0].event.name, "Core CPI m/m");
+        assert!(agent.due_windows(now).is_empty());
+
+        let calendar = MacroCalendar { timestamp: now, events: agent.get_events().to_vec() };
+        assert_eq!(MacroCalendar::from_message(&calendar.to_message()).unwrap().events.len(), 2);
+    }
+}
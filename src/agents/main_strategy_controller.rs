@@ -5,6 +5,7 @@
 //! to execute trades based on comprehensive analysis from all OMNI components.
 
 use std::collections::HashMap;
+use std::path::Path;
 use std::sync::Arc;
 use tokio::sync::{Mutex, RwLock};
 use tokio::time::{sleep, Duration, Instant};
@@ -22,7 +23,8 @@ use crate::agents::asset_scanner_agent::{AssetScannerAgent, AssetScannerConfig};
 use crate::agents::quantum_predictor::{QuantumPredictor, QuantumPrediction};
 use crate::agents::hyperdimensional_pattern_recognizer::{HyperdimensionalPatternRecognizer, PatternRecognition};
 use crate::agents::trade_executor::ExecutionStatus;
-use crate::strategy::advanced_multi_factor_strategy::{AdvancedMultiFactorStrategy, StrategyConfig, MultiFactorAnalysis};
+use crate::strategy::advanced_multi_factor_strategy::{AdvancedMultiFactorStrategy, MultiFactorAnalysis};
+use crate::deployment::config_manager::{load_multi_factor_config, STRATEGY_DIRECTORY};
 use crate::quantum::spectral_tree_engine::SpectralTreeEngine;
 use crate::quantum::hyperdimensional_computing::HyperdimensionalComputing;
 use crate::exchange::bybit::adapter::BybitAdapter;
@@ -96,7 +98,7 @@ pub enum CommandType {
 }
 
 /// Main Strategy Controller Configuration
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MainStrategyControllerConfig {
     /// Total capital in USDT
     pub total_capital: f64,
@@ -250,7 +252,7 @@ impl MainStrategyController {
         let asset_scanner = AssetScannerAgent::new(scanner_config, exchange.clone(), message_bus.clone());
 
         // Initialize strategy
-        let strategy_config = StrategyConfig::default();
+        let strategy_config = load_multi_factor_config(Path::new(STRATEGY_DIRECTORY));
         let strategy = AdvancedMultiFactorStrategy::new(strategy_config)?;
 
         // Initialize quantum components
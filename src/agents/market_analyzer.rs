@@ -8,8 +8,15 @@ use serde::{Deserialize, Serialize};
 use anyhow::Result;
 use tracing::{info, debug};
 
+use crate::agents::onchain_agent::OnChainFeatures;
 use crate::strategy::simple_strategy::Candle;
 
+/// Most points on-chain features add to or take from the opportunity score
+const ONCHAIN_MAX_POINTS: f64 = 10.0;
+
+/// On-chain features older than this are ignored (minutes)
+const ONCHAIN_MAX_AGE_MINUTES: i64 = 60;
+
 /// Market analysis result
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MarketAnalysis {
@@ -54,6 +61,9 @@ pub struct MarketAnalysis {
 pub struct MarketAnalyzer {
     /// Analysis cache
     analysis_cache: HashMap<String, MarketAnalysis>,
+
+    /// Latest on-chain features by symbol
+    onchain_features: HashMap<String, OnChainFeatures>,
 }
 
 impl MarketAnalyzer {
@@ -61,6 +71,7 @@ impl MarketAnalyzer {
     pub fn new() -> Self {
         Self {
             analysis_cache: HashMap::new(),
+            onchain_features: HashMap::new(),
         }
     }
     
@@ -109,7 +120,18 @@ impl MarketAnalyzer {
         let (support_levels, resistance_levels) = self.find_support_resistance(candles);
         
         // Calculate technical indicators
-        let indicators = self.calculate_indicators(candles);
+        let mut indicators = self.calculate_indicators(candles);
+        
+        // Add on-chain features while they are fresh
+        let now = Utc::now();
+        let onchain = self.onchain_features.get(symbol)
+            .filter(|f| now - f.timestamp <= chrono::Duration::minutes(ONCHAIN_MAX_AGE_MINUTES));
+        if let Some(features) = onchain {
+            indicators.insert("onchain_net_flow".to_string(), features.net_flow_ratio);
+            indicators.insert("onchain_stablecoin_change".to_string(), features.stablecoin_change_pct);
+            indicators.insert("onchain_whale_flow".to_string(), features.whale_flow_ratio);
+            indicators.insert("onchain_bias".to_string(), features.bias);
+        }
         
         // Calculate opportunity score
         let opportunity_score = self.calculate_opportunity_score(
@@ -127,7 +149,7 @@ impl MarketAnalyzer {
         // Create analysis result
         let analysis = MarketAnalysis {
             symbol: symbol.to_string(),
-            timestamp: Utc::now(),
+            timestamp: now,
             current_price: latest_candle.close,
             price_change_24h,
             volume_change_24h,
@@ -427,10 +449,25 @@ impl MarketAnalyzer {
             }
         }
         
+        // On-chain flows
+        if let Some(bias) = indicators.get("onchain_bias") {
+            score += bias.clamp(-1.0, 1.0) * ONCHAIN_MAX_POINTS;
+        }
+        
         // Ensure score is within 0-100 range
         score.max(0.0).min(100.0)
     }
     
+    /// Record the latest on-chain features of a symbol
+    pub fn update_onchain_features(&mut self, features: OnChainFeatures) {
+        self.onchain_features.insert(features.symbol.clone(), features);
+    }
+    
+    /// Get the latest on-chain features of a symbol
+    pub fn get_onchain_features(&self, symbol: &str) -> Option<&OnChainFeatures> {
+        self.onchain_features.get(symbol)
+    }
+    
     /// Get cached analysis for a symbol
     pub fn get_cached_analysis(&self, symbol: &str) -> Option<&MarketAnalysis> {
         self.analysis_cache.get(symbol)
@@ -0,0 +1,399 @@
+//! Market Regime Agent
+//!
+//! This agent labels the market regime of each tracked symbol as trending,
+//! ranging, volatile or illiquid from its recent candles: bar turnover for
+//! liquidity, annualized realized volatility, ADX for trend strength and the
+//! entropy of returns for how orderly the moves are. Like the volatility regime
+//! classifier, a new label only sticks after it has been observed on several
+//! consecutive updates. Each confirmed change is published as a
+//! `MarketRegimeChange`; the risk manager blocks or resizes entries by regime and
+//! strategies are switched on the symbols whose regime they trade in.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use anyhow::Result;
+use async_trait::async_trait;
+use tokio::sync::RwLock;
+use tracing::{info, warn};
+
+use crate::engine::agent_trait::{Agent, AgentConfig, AgentContext};
+use crate::engine::entropy_calc::EntropyCalculator;
+use crate::engine::message_bus::{BusMessage, Message, MessageBus};
+use crate::exchange::bybit::adapter::BybitAdapter;
+use crate::exchange::bybit::types::BybitKline;
+use crate::strategy::indicators::calculate_adx_from_series;
+
+/// Topic used for market regime changes (`Message::Custom`)
+pub const MARKET_REGIME_TOPIC: &str = "market_regime";
+
+/// Market regime of a symbol
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum MarketRegime {
+    /// Strong, orderly directional movement
+    Trending,
+
+    /// Directionless movement within a range
+    Ranging,
+
+    /// Large, disorderly moves
+    Volatile,
+
+    /// Too little turnover to trade safely
+    Illiquid,
+}
+
+/// Market regime agent settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MarketRegimeAgentConfig {
+    /// Symbols tracked
+    pub symbols: Vec<String>,
+
+    /// Product category of the candles
+    pub category: String,
+
+    /// Candle interval (Bybit notation, e.g. "1", "5", "60")
+    pub interval: String,
+
+    /// Candles requested per update
+    pub lookback: u32,
+
+    /// Bars per year of the interval, used for annualization
+    pub bars_per_year: f64,
+
+    /// ADX period
+    pub adx_period: usize,
+
+    /// ADX at or above which the market counts as trending
+    pub trend_adx: f64,
+
+    /// Return entropy (0.0-1.0) above which moves are too disorderly to count as a trend
+    pub max_trend_entropy: f64,
+
+    /// Annualized volatility (%) at or above which the market counts as volatile
+    pub volatile_threshold: f64,
+
+    /// Average turnover per bar (quote currency) below which the market counts as illiquid
+    pub min_bar_turnover: f64,
+
+    /// Consecutive observations required before switching regime
+    pub confirmation_updates: usize,
+}
+
+impl Default for MarketRegimeAgentConfig {
+    fn default() -> Self {
+        Self {
+            symbols: vec!["BTCUSDT".to_string(), "ETHUSDT".to_string()],
+            category: "linear".to_string(),
+            interval: "1".to_string(),
+            lookback: 200,
+            bars_per_year: 365.0 * 24.0 * 60.0,
+            adx_period: 14,
+            trend_adx: 25.0,
+            max_trend_entropy: 0.85,
+            volatile_threshold: 90.0,
+            min_bar_turnover: 50_000.0,
+            confirmation_updates: 2,
+        }
+    }
+}
+
+/// Indicators behind a regime label
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RegimeReading {
+    /// Observed regime
+    pub regime: MarketRegime,
+
+    /// Average directional index
+    pub adx: f64,
+
+    /// Normalized entropy of returns (0.0-1.0)
+    pub entropy: f64,
+
+    /// Annualized realized volatility in %
+    pub annualized_volatility: f64,
+
+    /// Average turnover per bar (quote currency)
+    pub bar_turnover: f64,
+}
+
+/// Confirmed change of a symbol's market regime
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MarketRegimeChange {
+    /// Symbol
+    pub symbol: String,
+
+    /// Time of the change
+    pub timestamp: DateTime<Utc>,
+
+    /// Previous regime (none for the first label of a symbol)
+    pub from: Option<MarketRegime>,
+
+    /// New regime
+    pub to: MarketRegime,
+
+    /// Reading that confirmed the change
+    pub reading: RegimeReading,
+}
+
+impl MarketRegimeChange {
+    /// Convert to a bus message
+    pub fn to_message(&self) -> Message {
+        Message::Custom(
+            MARKET_REGIME_TOPIC.to_string(),
+            serde_json::to_value(self).unwrap_or(Value::Null),
+        )
+    }
+
+    /// Parse a market regime change from a message, if it is one
+    pub fn from_message(message: &Message) -> Option<Self> {
+        match message {
+            Message::Custom(topic, payload) if topic == MARKET_REGIME_TOPIC => {
+                match serde_json::from_value(payload.clone()) {
+                    Ok(change) => Some(change),
+                    Err(e) => {
+                        warn!("Ignoring malformed market regime change: {}", e);
+                        None
+                    }
+                }
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Confirmed and candidate regime of a symbol
+#[derive(Debug, Clone, Default)]
+struct SymbolRegime {
+    /// Confirmed regime
+    current: Option<MarketRegime>,
+
+    /// Candidate regime and how many consecutive times it was observed
+    pending: Option<(MarketRegime, usize)>,
+}
+
+/// Market regime classification agent
+pub struct MarketRegimeAgent {
+    /// Settings
+    config: MarketRegimeAgentConfig,
+
+    /// Exchange adapter
+    exchange: Arc<BybitAdapter>,
+
+    /// Message bus regime changes are published on
+    message_bus: Arc<MessageBus>,
+
+    /// Whether the agent is running
+    running: bool,
+
+    /// Regime state by symbol
+    regimes: HashMap<String, SymbolRegime>,
+}
+
+impl MarketRegimeAgent {
+    /// Create a new market regime agent
+    pub fn new(config: MarketRegimeAgentConfig, exchange: Arc<BybitAdapter>, message_bus: Arc<MessageBus>) -> Self {
+        Self {
+            config,
+            exchange,
+            message_bus,
+            running: false,
+            regimes: HashMap::new(),
+        }
+    }
+
+    /// Label candles (oldest first) without hysteresis
+    ///
+    /// Illiquidity wins over everything else, then volatility; a market that is
+    /// neither trends when ADX is high and returns are orderly, and ranges otherwise.
+    pub fn classify(&self, klines: &[BybitKline]) -> Option<RegimeReading> {
+        let window = (2 * self.config.adx_period).max(3);
+        if klines.len() < window {
+            return None;
+        }
+
+        let highs: Vec<f64> = klines.iter().map(|k| k.high).collect();
+        let lows: Vec<f64> = klines.iter().map(|k| k.low).collect();
+        let closes: Vec<f64> = klines.iter().map(|k| k.close).collect();
+
+        let adx = calculate_adx_from_series(&highs, &lows, &closes, self.config.adx_period)?.adx;
+        let calculator = EntropyCalculator::new(klines.len() - 1, self.config.bars_per_year);
+        let profile = calculator.profile(&closes)?;
+        let bar_turnover = klines.iter().map(|k| k.turnover).sum::<f64>() / klines.len() as f64;
+
+        let regime = if bar_turnover < self.config.min_bar_turnover {
+            MarketRegime::Illiquid
+        } else if profile.annualized_volatility >= self.config.volatile_threshold {
+            MarketRegime::Volatile
+        } else if adx >= self.config.trend_adx && profile.entropy <= self.config.max_trend_entropy {
+            MarketRegime::Trending
+        } else {
+            MarketRegime::Ranging
+        };
+
+        Some(RegimeReading {
+            regime,
+            adx,
+            entropy: profile.entropy,
+            annualized_volatility: profile.annualized_volatility,
+            bar_turnover,
+        })
+    }
+
+    /// Update a symbol with its latest candles; returns a change once it is confirmed
+    pub fn update(&mut self, symbol: &str, klines: &[BybitKline], now: DateTime<Utc>) -> Option<MarketRegimeChange> {
+        let reading = self.classify(klines)?;
+        let confirmation_updates = self.config.confirmation_updates.max(1);
+        let state = self.regimes.entry(symbol.to_string()).or_default();
+
+        if state.current == Some(reading.regime) {
+            state.pending = None;
+            return None;
+        }
+
+        let count = match state.pending {
+            Some((regime, count)) if regime == reading.regime => count + 1,
+            _ => 1,
+        };
+        if count < confirmation_updates {
+            state.pending = Some((reading.regime, count));
+            return None;
+        }
+
+        let change = MarketRegimeChange {
+            symbol: symbol.to_string(),
+            timestamp: now,
+            from: state.current,
+            to: reading.regime,
+            reading,
+        };
+        state.current = Some(change.to);
+        state.pending = None;
+
+        info!("Market regime of {} changed from {:?} to {:?} (ADX {:.1}, entropy {:.2}, vol {:.1}%, turnover ${:.0}/bar)",
+              symbol, change.from, change.to, change.reading.adx, change.reading.entropy,
+              change.reading.annualized_volatility, change.reading.bar_turnover);
+
+        Some(change)
+    }
+
+    /// Get the confirmed regime of a symbol
+    pub fn get_regime(&self, symbol: &str) -> Option<MarketRegime> {
+        self.regimes.get(symbol).and_then(|state| state.current)
+    }
+}
+
+#[async_trait]
+impl Agent for MarketRegimeAgent {
+    fn get_name(&self) -> &str {
+        "MarketRegimeAgent"
+    }
+
+    fn get_config(&self) -> Box<dyn AgentConfig> {
+        Box::new(self.config.clone())
+    }
+
+    async fn initialize(&mut self, _context: Arc<RwLock<AgentContext>>) -> Result<()> {
+        info!("Initializing Market Regime Agent with {} symbols", self.config.symbols.len());
+        Ok(())
+    }
+
+    async fn start(&mut self) -> Result<()> {
+        self.running = true;
+        Ok(())
+    }
+
+    async fn stop(&mut self) -> Result<()> {
+        self.running = false;
+        Ok(())
+    }
+
+    async fn tick(&mut self) -> Result<()> {
+        if !self.running {
+            return Ok(());
+        }
+
+        for symbol in self.config.symbols.clone() {
+            let mut klines = match self.exchange.get_klines(&symbol, &self.config.interval, self.config.lookback, &self.config.category).await {
+                Ok(klines) => klines,
+                Err(e) => {
+                    warn!("Failed to read the candles of {}: {}", symbol, e);
+                    continue;
+                }
+            };
+
+            // Bybit lists the newest candle first
+            klines.sort_by_key(|k| k.start_time);
+            if let Some(change) = self.update(&symbol, &klines, Utc::now()) {
+                self.message_bus.send(change.to_message());
+            }
+        }
+        Ok(())
+    }
+
+    async fn handle_message(&mut self, _message: BusMessage) -> Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn klines(count: usize, price: impl Fn(usize) -> f64, turnover: f64) -> Vec<BybitKline> {
+        (0..count)
+            .map(|i| {
+                let close = price(i);
+                BybitKline {
+                    start_time: i as i64 * 60_000,
+                    open: close,
+                    high: close * 1.0005,
+                    low: close * 0.9995,
+                    close,
+                    volume: turnover / close,
+                    turnover,
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_regimes_are_labelled_and_confirmed() {
+        let config = MarketRegimeAgentConfig {
+            bars_per_year: 1.0,
+            volatile_threshold: 5.0,
+            ..Default::default()
+        };
+        let mut agent = MarketRegimeAgent::new(config, Arc::new(BybitAdapter::new("", "", true)), Arc::new(MessageBus::new()));
+        let now = Utc::now();
+
+        // Steady 0.1% steps with a 0.2% step every tenth bar: strong and orderly
+        let trend = klines(100, |i| 100.0 * 1.001f64.powi(i as i32) * (1.002f64 / 1.001).powi((i / 10) as i32), 1_000_000.0);
+        let range = klines(100, |i| 100.0 * (1.0 + 0.001 * ((i as f64) * 0.9).sin()), 1_000_000.0);
+        let storm = klines(100, |i| 100.0 * (1.0 + 0.2 * ((i as f64) * 0.7).sin()), 1_000_000.0);
+        let thin = klines(100, |i| 100.0 * 1.001f64.powi(i as i32), 1_000.0);
+
+        assert_eq!(agent.classify(&trend).unwrap().regime, MarketRegime::Trending);
+        assert_eq!(agent.classify(&range).unwrap().regime, MarketRegime::Ranging);
+        assert_eq!(agent.classify(&storm).unwrap().regime, MarketRegime::Volatile);
+        assert_eq!(agent.classify(&thin).unwrap().regime, MarketRegime::Illiquid);
+
+        // A label needs two consecutive observations
+        assert!(agent.update("BTCUSDT", &trend, now).is_none());
+        let change = agent.update("BTCUSDT", &trend, now).unwrap();
+        assert_eq!((change.from, change.to), (None, MarketRegime::Trending));
+        assert!(agent.update("BTCUSDT", &storm, now).is_none());
+        assert!(agent.update("BTCUSDT", &trend, now).is_none());
+        assert_eq!(agent.get_regime("BTCUSDT"), Some(MarketRegime::Trending));
+
+        agent.update("BTCUSDT", &thin, now);
+        let change = agent.update("BTCUSDT", &thin, now).unwrap();
+        assert_eq!(change.from, Some(MarketRegime::Trending));
+
+        let parsed = MarketRegimeChange::from_message(&change.to_message()).unwrap();
+        assert_eq!(parsed.to, MarketRegime::Illiquid);
+    }
+}
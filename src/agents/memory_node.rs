@@ -1,10 +1,13 @@
 //! Memory Node Agent
 //!
 //! This agent is responsible for storing and retrieving trade memory and metadata,
-//! forming the core of the system's recursive intelligence loop.
+//! forming the core of the system's recursive intelligence loop. Every memory
+//! carries an embedding of the market conditions at entry, and a flat k-NN index
+//! over the embeddings answers how similar setups resolved historically.
 
 use std::sync::Arc;
 use std::collections::{HashMap, VecDeque};
+use std::path::Path;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use anyhow::Result;
@@ -13,11 +16,22 @@ use async_trait::async_trait;
 use tokio::sync::RwLock;
 
 use crate::engine::agent_trait::{Agent, AgentContext, AgentConfig};
+use crate::engine::json_store::{load_json, save_json};
 use crate::engine::message_bus::{BusMessage, MessageBus, MessageType, TradeDirection};
+use crate::agents::feedback_loop::AgentPerformance;
 
 /// Maximum number of memories to store
 const MAX_MEMORIES: usize = 10000;
 
+/// Most recent contributions a trust score is computed over
+const TRUST_WINDOW: usize = 50;
+
+/// R multiple at which a contribution earns full credit
+const TRUST_FULL_CREDIT_R: f64 = 2.0;
+
+/// Length of a market-condition embedding
+pub const EMBEDDING_DIM: usize = 7;
+
 /// Memory entry for a trade
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TradeMemory {
@@ -54,6 +68,10 @@ pub struct TradeMemory {
     /// ROI percentage
     pub roi: Option<f64>,
 
+    /// Profit/loss in multiples of the initial risk (R)
+    #[serde(default)]
+    pub r_multiple: Option<f64>,
+
     /// Duration in seconds
     pub duration_seconds: Option<u64>,
 
@@ -63,9 +81,17 @@ pub struct TradeMemory {
     /// Agent confidence scores
     pub agent_confidence: HashMap<String, f64>,
 
+    /// What each agent contributed to the trade decision
+    #[serde(default)]
+    pub agent_contributions: Vec<AgentContribution>,
+
     /// Market conditions at entry
     pub market_conditions: MarketConditions,
 
+    /// Embedding of the market conditions at entry; derived from them when empty
+    #[serde(default)]
+    pub embedding: Vec<f64>,
+
     /// Trade outcome classification
     pub outcome: Option<TradeOutcome>,
 
@@ -118,6 +144,148 @@ impl Default for MarketConditions {
     }
 }
 
+impl MarketConditions {
+    /// Feature vector of the conditions, with missing features at zero
+    ///
+    /// Volume, liquidity and open interest enter on a log scale; the trend is
+    /// encoded as 1 for up, -1 for down and 0 otherwise.
+    pub fn embedding(&self) -> Vec<f64> {
+        let log = |value: Option<f64>| value.map_or(0.0, |v| v.max(0.0).ln_1p());
+        vec![
+            match self.trend {
+                Some(MarketTrend::Up) => 1.0,
+                Some(MarketTrend::Down) => -1.0,
+                _ => 0.0,
+            },
+            self.volatility.unwrap_or(0.0),
+            log(self.volume),
+            log(self.liquidity),
+            self.funding_rate.unwrap_or(0.0),
+            log(self.open_interest),
+            self.orderbook_imbalance.unwrap_or(0.0),
+        ]
+    }
+}
+
+/// How the historical setups closest to a query resolved
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SetupOutcomes {
+    /// Closed trades found
+    pub samples: usize,
+
+    /// Share of them that made money (0.0 to 1.0)
+    pub win_rate: f64,
+
+    /// Average ROI (%)
+    pub avg_roi: f64,
+
+    /// Average R multiple of the trades that know their initial risk
+    pub avg_r_multiple: Option<f64>,
+
+    /// Average distance to the query in standardized feature space
+    pub avg_distance: f64,
+}
+
+/// Flat k-NN index over the embeddings of the trade memories
+///
+/// Rows are kept in the order of the memories. Distances are Euclidean over
+/// features scaled by their standard deviation across the index, so that e.g.
+/// log volume and funding rate weigh alike. A linear scan over ten thousand
+/// seven-dimensional rows stays well under a millisecond.
+#[derive(Debug, Clone, Default)]
+struct EmbeddingIndex {
+    /// Row-major embeddings
+    values: Vec<f64>,
+
+    /// Sum of each feature
+    sum: [f64; EMBEDDING_DIM],
+
+    /// Sum of squares of each feature
+    sum_sq: [f64; EMBEDDING_DIM],
+}
+
+impl EmbeddingIndex {
+    /// Pad or truncate an embedding to the index dimension
+    fn fit(embedding: &[f64]) -> [f64; EMBEDDING_DIM] {
+        let mut row = [0.0; EMBEDDING_DIM];
+        for (slot, value) in row.iter_mut().zip(embedding) {
+            *slot = if value.is_finite() { *value } else { 0.0 };
+        }
+        row
+    }
+
+    /// Number of rows
+    fn len(&self) -> usize {
+        self.values.len() / EMBEDDING_DIM
+    }
+
+    /// Append a row
+    fn push(&mut self, embedding: &[f64]) {
+        let row = Self::fit(embedding);
+        for i in 0..EMBEDDING_DIM {
+            self.sum[i] += row[i];
+            self.sum_sq[i] += row[i] * row[i];
+        }
+        self.values.extend_from_slice(&row);
+    }
+
+    /// Remove the oldest row
+    fn remove_first(&mut self) {
+        if self.values.is_empty() {
+            return;
+        }
+        for (i, value) in self.values.drain(..EMBEDDING_DIM).enumerate() {
+            self.sum[i] -= value;
+            self.sum_sq[i] -= value * value;
+        }
+    }
+
+    /// Inverse standard deviation of each feature; constant features get weight 1
+    fn scales(&self) -> [f64; EMBEDDING_DIM] {
+        let n = self.len() as f64;
+        let mut scales = [1.0; EMBEDDING_DIM];
+        if n < 2.0 {
+            return scales;
+        }
+        for i in 0..EMBEDDING_DIM {
+            let mean = self.sum[i] / n;
+            let variance = (self.sum_sq[i] / n - mean * mean).max(0.0);
+            if variance > 1e-12 {
+                scales[i] = 1.0 / variance.sqrt();
+            }
+        }
+        scales
+    }
+
+    /// The `k` rows nearest to `query` among those `include` accepts, nearest first
+    fn nearest(&self, query: &[f64], k: usize, include: impl Fn(usize) -> bool) -> Vec<(usize, f64)> {
+        if k == 0 {
+            return Vec::new();
+        }
+
+        let query = Self::fit(query);
+        let scales = self.scales();
+        let mut hits: Vec<(usize, f64)> = self.values.chunks_exact(EMBEDDING_DIM)
+            .enumerate()
+            .filter(|(row, _)| include(*row))
+            .map(|(row, values)| {
+                let distance_sq: f64 = values.iter().zip(&query).zip(&scales)
+                    .map(|((value, q), scale)| ((value - q) * scale).powi(2))
+                    .sum();
+                (row, distance_sq.sqrt())
+            })
+            .collect();
+
+        let by_distance = |a: &(usize, f64), b: &(usize, f64)| a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal);
+        if hits.len() > k {
+            hits.select_nth_unstable_by(k - 1, by_distance);
+            hits.truncate(k);
+        }
+        hits.sort_by(by_distance);
+        hits
+    }
+}
+
 /// Market trend
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
 pub enum MarketTrend {
@@ -167,12 +335,20 @@ pub struct Trade {
     /// ROI
     pub roi: Option<f64>,
 
+    /// Profit/loss in multiples of the initial risk (R)
+    #[serde(default)]
+    pub r_multiple: Option<f64>,
+
     /// Contributing agents
     pub contributing_agents: Vec<String>,
 
     /// Agent confidence
     pub agent_confidence: HashMap<String, f64>,
 
+    /// What each agent contributed to the trade decision
+    #[serde(default)]
+    pub agent_contributions: Vec<AgentContribution>,
+
     /// Strategy
     pub strategy: String,
 
@@ -181,6 +357,61 @@ pub struct Trade {
 
     /// Metadata
     pub metadata: HashMap<String, String>,
+
+    /// Market conditions at entry
+    #[serde(default)]
+    pub market_conditions: MarketConditions,
+}
+
+/// Contribution of one agent to a trade decision
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AgentContribution {
+    /// Agent name
+    pub agent: String,
+
+    /// Whether the agent backed the trade's direction
+    pub agreed: bool,
+
+    /// Agent confidence (0-100)
+    pub confidence: f64,
+
+    /// Weight the agent's vote carried
+    pub weight: f64,
+}
+
+/// Rolling trust in an agent, from the outcomes of the trades it contributed to
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct AgentTrust {
+    /// Agent name
+    pub agent: String,
+
+    /// Contributions the score is computed over
+    pub trades: usize,
+
+    /// Fraction of contributions that called the outcome right
+    pub hit_rate: f64,
+
+    /// Confidence-weighted credit per contribution (-1.0 to 1.0)
+    pub trust_score: f64,
+}
+
+impl AgentTrust {
+    /// Convert to an agent performance record for the god kernel
+    pub fn to_agent_performance(&self) -> AgentPerformance {
+        AgentPerformance {
+            agent_name: self.agent.clone(),
+            score: self.trust_score,
+            confidence: (self.trades as f64 / TRUST_WINDOW as f64).min(1.0),
+            trade_count: self.trades,
+            success_rate: self.hit_rate,
+            avg_roi_contribution: 0.0,
+            last_updated: Utc::now(),
+            recent_trades: VecDeque::new(),
+            mutation_eligible: false,
+            kill_eligible: false,
+            consecutive_failures: 0,
+        }
+    }
 }
 
 /// Trade outcome classification
@@ -300,11 +531,66 @@ pub struct MemoryNodeState {
 
     /// Best performing agents
     pub best_agents: Vec<(String, f64)>,
+
+    /// Expectancy in R over trades with a known initial risk
+    #[serde(default)]
+    pub expectancy_r: f64,
+}
+
+/// Expectancy of a group of trades in multiples of initial risk
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RExpectancy {
+    /// Trades with a known R multiple
+    pub trades: usize,
+
+    /// Fraction of trades with a positive R
+    pub win_rate: f64,
+
+    /// Average R of winning trades
+    pub avg_win_r: f64,
+
+    /// Average R of losing trades (negative)
+    pub avg_loss_r: f64,
+
+    /// Expected R per trade
+    pub expectancy_r: f64,
+}
+
+impl RExpectancy {
+    /// Expectancy of a set of R multiples
+    pub fn from_r_multiples(r_multiples: impl IntoIterator<Item = f64>) -> Self {
+        let r_multiples: Vec<f64> = r_multiples.into_iter().collect();
+        if r_multiples.is_empty() {
+            return Self::default();
+        }
+
+        let wins: Vec<f64> = r_multiples.iter().copied().filter(|r| *r > 0.0).collect();
+        let losses: Vec<f64> = r_multiples.iter().copied().filter(|r| *r <= 0.0).collect();
+        let mean = |values: &[f64]| if values.is_empty() { 0.0 } else { values.iter().sum::<f64>() / values.len() as f64 };
+
+        Self {
+            trades: r_multiples.len(),
+            win_rate: wins.len() as f64 / r_multiples.len() as f64,
+            avg_win_r: mean(&wins),
+            avg_loss_r: mean(&losses),
+            expectancy_r: mean(&r_multiples),
+        }
+    }
+}
+
+/// Persisted memory node state
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MemoryNodeSnapshot {
+    /// When the snapshot was taken
+    pub saved_at: DateTime<Utc>,
+
+    /// Trade memories, with the agent contributions behind each trade; trust scores are recomputed from them
+    pub memories: Vec<TradeMemory>,
 }
 
 /// Memory Node Agent
 /// Memory Node configuration
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MemoryNodeConfig {
     /// Maximum memory size
     pub max_memory_size: usize,
@@ -331,6 +617,9 @@ pub struct MemoryNode {
     /// Trade memories
     trade_memories: Vec<TradeMemory>,
 
+    /// k-NN index over the memories' embeddings
+    embedding_index: EmbeddingIndex,
+
     /// Symbol performance index
     symbol_performance: HashMap<String, f64>,
 
@@ -360,6 +649,7 @@ impl MemoryNode {
             leverage: 1.0,
             pnl: trade.pnl,
             roi: trade.roi,
+            r_multiple: trade.r_multiple,
             outcome: if let Some(roi) = trade.roi {
                 if roi > 0.0 {
                     Some(TradeOutcome::Win)
@@ -373,7 +663,9 @@ impl MemoryNode {
             },
             contributing_agents: trade.contributing_agents.clone(),
             agent_confidence: trade.agent_confidence.clone(),
-            market_conditions: MarketConditions::default(),
+            agent_contributions: trade.agent_contributions.clone(),
+            market_conditions: trade.market_conditions.clone(),
+            embedding: Vec::new(),
             fractal_signature: None,
             reinforcement: None,
             tags: trade.tags.clone(),
@@ -398,8 +690,10 @@ impl MemoryNode {
                 average_roi: 0.0,
                 best_symbols: Vec::new(),
                 best_agents: Vec::new(),
+                expectancy_r: 0.0,
             },
             trade_memories: Vec::new(),
+            embedding_index: EmbeddingIndex::default(),
             symbol_performance: HashMap::new(),
             agent_performance: HashMap::new(),
             pattern_memory: HashMap::new(),
@@ -410,12 +704,7 @@ impl MemoryNode {
     /// Store a new trade memory
     pub fn store_memory(&mut self, memory: TradeMemory) -> Result<()> {
         // Add to memories
-        self.trade_memories.push(memory.clone());
-
-        // Enforce capacity limit
-        if self.trade_memories.len() > self.config.max_memory_size {
-            self.trade_memories.remove(0);
-        }
+        self.add_trade_memory(memory.clone());
 
         // Update state
         self.state.memory_count = self.trade_memories.len();
@@ -428,10 +717,11 @@ impl MemoryNode {
             // Update symbol performance
             *self.symbol_performance.entry(memory.symbol.clone()).or_insert(0.0) += roi;
 
-            // Update agent performance
+            // Update agent performance, in R when the initial risk is known
+            let score = memory.r_multiple.unwrap_or(roi);
             for agent in &memory.contributing_agents {
                 let confidence = memory.agent_confidence.get(agent).unwrap_or(&0.5);
-                let agent_contribution = roi * confidence;
+                let agent_contribution = score * confidence;
                 *self.agent_performance.entry(agent.clone()).or_insert(0.0) += agent_contribution;
             }
 
@@ -467,6 +757,11 @@ impl MemoryNode {
                 self.state.average_roi = total_roi / roi_count as f64;
             }
 
+            // Update expectancy
+            self.state.expectancy_r = RExpectancy::from_r_multiples(
+                self.trade_memories.iter().filter_map(|m| m.r_multiple)
+            ).expectancy_r;
+
             // Update best symbols
             let mut symbol_performance: Vec<(String, f64)> = self.symbol_performance.iter()
                 .map(|(k, v)| (k.clone(), *v))
@@ -664,13 +959,163 @@ impl MemoryNode {
         self.state.clone()
     }
 
+    /// Get the R expectancy of trades an agent contributed to
+    pub fn get_agent_expectancy(&self, agent: &str) -> RExpectancy {
+        RExpectancy::from_r_multiples(
+            self.trade_memories.iter()
+                .filter(|m| m.contributing_agents.iter().any(|a| a == agent))
+                .filter_map(|m| m.r_multiple)
+        )
+    }
+
+    /// Get the R expectancy of every agent with at least one R-evaluated trade
+    pub fn get_expectancy_by_agent(&self) -> HashMap<String, RExpectancy> {
+        let mut r_by_agent: HashMap<String, Vec<f64>> = HashMap::new();
+        for memory in &self.trade_memories {
+            if let Some(r) = memory.r_multiple {
+                for agent in &memory.contributing_agents {
+                    r_by_agent.entry(agent.clone()).or_default().push(r);
+                }
+            }
+        }
+
+        r_by_agent.into_iter()
+            .map(|(agent, r_multiples)| (agent, RExpectancy::from_r_multiples(r_multiples)))
+            .collect()
+    }
+
+    /// Get the rolling trust score of an agent
+    ///
+    /// Each of the agent's last contributions to a closed trade earns credit equal to
+    /// the trade's R multiple (ROI when the initial risk is unknown), capped at
+    /// `TRUST_FULL_CREDIT_R` and scaled by the agent's confidence. Agents that voted
+    /// against the trade earn the opposite credit.
+    pub fn get_agent_trust(&self, agent: &str) -> AgentTrust {
+        let credits: Vec<f64> = self.trade_memories.iter().rev()
+            .filter_map(|memory| {
+                let result = memory.r_multiple.or(memory.roi)?;
+                let contribution = memory.agent_contributions.iter().find(|c| c.agent == agent)?;
+                let credit = (result / TRUST_FULL_CREDIT_R).clamp(-1.0, 1.0) * contribution.confidence.clamp(0.0, 100.0) / 100.0;
+                Some(if contribution.agreed { credit } else { -credit })
+            })
+            .take(TRUST_WINDOW)
+            .collect();
+
+        if credits.is_empty() {
+            return AgentTrust { agent: agent.to_string(), ..AgentTrust::default() };
+        }
+
+        let trades = credits.len();
+        AgentTrust {
+            agent: agent.to_string(),
+            trades,
+            hit_rate: credits.iter().filter(|c| **c > 0.0).count() as f64 / trades as f64,
+            trust_score: credits.iter().sum::<f64>() / trades as f64,
+        }
+    }
+
+    /// The `k` memories whose entry conditions are nearest to `conditions`, nearest first, with their distance
+    pub fn find_similar_setups(&self, conditions: &MarketConditions, k: usize) -> Vec<(&TradeMemory, f64)> {
+        self.embedding_index.nearest(&conditions.embedding(), k, |_| true)
+            .into_iter()
+            .map(|(row, distance)| (&self.trade_memories[row], distance))
+            .collect()
+    }
+
+    /// How the `k` closed trades with entry conditions nearest to `conditions` resolved
+    pub fn similar_setup_outcomes(&self, conditions: &MarketConditions, k: usize) -> SetupOutcomes {
+        let hits = self.embedding_index.nearest(&conditions.embedding(), k, |row| self.trade_memories[row].roi.is_some());
+
+        let samples = hits.len();
+        let mut wins = 0;
+        let mut total_roi = 0.0;
+        let mut r_multiples = Vec::new();
+        let mut total_distance = 0.0;
+        for (row, distance) in hits {
+            let memory = &self.trade_memories[row];
+            let roi = memory.roi.unwrap_or(0.0);
+            if roi > 0.0 {
+                wins += 1;
+            }
+            total_roi += roi;
+            r_multiples.extend(memory.r_multiple);
+            total_distance += distance;
+        }
+
+        let mean = |total: f64, count: usize| if count > 0 { total / count as f64 } else { 0.0 };
+        SetupOutcomes {
+            samples,
+            win_rate: mean(wins as f64, samples),
+            avg_roi: mean(total_roi, samples),
+            avg_r_multiple: (!r_multiples.is_empty()).then(|| mean(r_multiples.iter().sum(), r_multiples.len())),
+            avg_distance: mean(total_distance, samples),
+        }
+    }
+
+    /// Get a stored trade memory by trade ID
+    pub fn get_trade_memory(&self, id: &str) -> Option<&TradeMemory> {
+        self.trade_memories.iter().rev().find(|m| m.id == id)
+    }
+
+    /// Get the rolling trust score of every agent that contributed to a closed trade
+    pub fn get_trust_scores(&self) -> HashMap<String, AgentTrust> {
+        let mut agents: Vec<&str> = self.trade_memories.iter()
+            .filter(|m| m.r_multiple.or(m.roi).is_some())
+            .flat_map(|m| m.agent_contributions.iter().map(|c| c.agent.as_str()))
+            .collect();
+        agents.sort_unstable();
+        agents.dedup();
+
+        agents.into_iter()
+            .map(|agent| (agent.to_string(), self.get_agent_trust(agent)))
+            .collect()
+    }
+
+    /// Snapshot of the persistent state
+    pub fn snapshot(&self) -> MemoryNodeSnapshot {
+        MemoryNodeSnapshot {
+            saved_at: Utc::now(),
+            memories: self.trade_memories.clone(),
+        }
+    }
+
+    /// Replace the memories with those of a snapshot, rebuilding the indices
+    pub fn restore(&mut self, snapshot: MemoryNodeSnapshot) -> Result<()> {
+        let mut restored = Self::new(self.config.clone(), Arc::clone(&self.message_bus));
+        restored.running = self.running;
+        for memory in snapshot.memories {
+            restored.store_memory(memory)?;
+        }
+        *self = restored;
+        Ok(())
+    }
+
+    /// Persist the state to a JSON file; writes go to a temp file that is then renamed
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        save_json(path, &self.snapshot())?;
+        debug!("Memory node state saved to {}", path.display());
+        Ok(())
+    }
+
+    /// Restore the state from a JSON file; returns whether the file existed
+    pub fn load(&mut self, path: impl AsRef<Path>) -> Result<bool> {
+        let path = path.as_ref();
+        let Some(snapshot) = load_json::<MemoryNodeSnapshot>(path)? else {
+            return Ok(false);
+        };
+        info!("Restored {} trade memories from {}", snapshot.memories.len(), path.display());
+        self.restore(snapshot)?;
+        Ok(true)
+    }
+
     /// Generate reinforcement feedback
     pub fn generate_reinforcement(&self, memory: &TradeMemory) -> ReinforcementFeedback {
         let mut agent_adjustments = HashMap::new();
         let mut strategy_adjustments = HashMap::new();
 
-        // Base reward on ROI
-        let reward = memory.roi.unwrap_or(0.0);
+        // Base reward on R multiple, falling back to ROI
+        let reward = memory.r_multiple.or(memory.roi).unwrap_or(0.0);
 
         // Adjust agents based on their contribution
         for agent in &memory.contributing_agents {
@@ -718,13 +1163,20 @@ impl MemoryNode {
     }
 
     /// Add trade memory
-    fn add_trade_memory(&mut self, memory: TradeMemory) {
+    fn add_trade_memory(&mut self, mut memory: TradeMemory) {
+        // Embed the entry conditions unless the caller supplied an embedding
+        if memory.embedding.is_empty() {
+            memory.embedding = memory.market_conditions.embedding();
+        }
+
         // Add memory
+        self.embedding_index.push(&memory.embedding);
         self.trade_memories.push(memory);
 
         // Trim if necessary
         if self.trade_memories.len() > self.config.max_memory_size {
             self.trade_memories.remove(0);
+            self.embedding_index.remove_first();
         }
 
         // Update state
@@ -783,7 +1235,7 @@ impl Agent for MemoryNode {
         Ok(())
     }
 
-    async fn update(&mut self) -> Result<()> {
+    async fn tick(&mut self) -> Result<()> {
         if !self.running {
             return Ok(());
         }
@@ -838,10 +1290,13 @@ impl Agent for MemoryNode {
                     exit_time: None,
                     pnl: None,
                     roi: None,
+                    r_multiple: None,
                     leverage: 1.0,
                     contributing_agents: Vec::new(),
                     agent_confidence: HashMap::new(),
+                    agent_contributions: Vec::new(),
                     market_conditions: MarketConditions::default(),
+                    embedding: Vec::new(),
                     outcome: None,
                     fractal_signature: None,
                     tags: Vec::new(),
@@ -910,6 +1365,10 @@ impl Agent for MemoryNode {
     fn get_config(&self) -> Box<dyn AgentConfig> {
         Box::new(self.config.clone())
     }
+
+    fn topics(&self) -> Vec<String> {
+        vec!["signal.#".to_string()]
+    }
 }
 
 #[cfg(test)]
@@ -933,6 +1392,7 @@ mod tests {
             leverage: 1.0,
             pnl: Some(100.0),
             roi: Some(2.0),
+            r_multiple: Some(1.5),
             duration_seconds: Some(3600),
             contributing_agents: vec!["agent1".to_string(), "agent2".to_string()],
             agent_confidence: {
@@ -941,6 +1401,7 @@ mod tests {
                 map.insert("agent2".to_string(), 0.6);
                 map
             },
+            agent_contributions: Vec::new(),
             market_conditions: MarketConditions {
                 volatility: 0.5,
                 volume: 1000000.0,
@@ -952,6 +1413,7 @@ mod tests {
                 open_interest: Some(500000000.0),
                 orderbook_imbalance: Some(0.2),
             },
+            embedding: Vec::new(),
             outcome: Some(TradeOutcome::Win),
             fractal_signature: Some(vec![0.1, 0.2, 0.3, 0.4, 0.5]),
             tags: vec!["strategy:breakout".to_string(), "volatility:high".to_string()],
@@ -988,4 +1450,117 @@ mod tests {
         assert_eq!(similar_trades.len(), 1);
         assert_eq!(similar_trades[0].id, "test-1");
     }
+
+    #[test]
+    fn test_trust_scores_follow_contributions() {
+        let mut memory_node = MemoryNode::new(MemoryNodeConfig::default(), Arc::new(MessageBus::new()));
+        let contribution = |agent: &str, agreed: bool| AgentContribution {
+            agent: agent.to_string(),
+            agreed,
+            confidence: 100.0,
+            weight: 1.0,
+        };
+
+        for (id, r) in [("t1", 2.0), ("t2", -1.0), ("t3", 1.0)] {
+            memory_node.store_trade(Trade {
+                id: id.to_string(),
+                symbol: "BTCUSDT".to_string(),
+                entry_time: Utc::now(),
+                exit_time: Some(Utc::now()),
+                entry_price: 100.0,
+                exit_price: Some(100.0 + r),
+                direction: TradeDirection::Long,
+                position_size: 1.0,
+                pnl: Some(r),
+                roi: Some(r),
+                r_multiple: Some(r),
+                contributing_agents: vec!["trend".to_string(), "contrarian".to_string()],
+                agent_confidence: HashMap::new(),
+                agent_contributions: vec![contribution("trend", true), contribution("contrarian", false)],
+                strategy: "trend".to_string(),
+                tags: Vec::new(),
+                metadata: HashMap::new(),
+                market_conditions: MarketConditions::default(),
+            }).unwrap();
+        }
+
+        let trust = memory_node.get_trust_scores();
+        assert_eq!(trust.len(), 2);
+        assert_eq!(trust["trend"].trades, 3);
+        assert!((trust["trend"].trust_score - 1.0 / 3.0).abs() < 1e-9);
+        assert!((trust["trend"].hit_rate - 2.0 / 3.0).abs() < 1e-9);
+        assert!((trust["contrarian"].trust_score + 1.0 / 3.0).abs() < 1e-9);
+        assert_eq!(memory_node.get_agent_trust("unknown").trades, 0);
+
+        // Contributions and trust survive a restart
+        let path = std::env::temp_dir().join(format!("omni_memory_node_{}.json", std::process::id()));
+        memory_node.save(&path).unwrap();
+        let mut restored = MemoryNode::new(MemoryNodeConfig::default(), Arc::new(MessageBus::new()));
+        assert!(restored.load(&path).unwrap());
+        assert_eq!(restored.get_trade_memory("t2").unwrap().agent_contributions.len(), 2);
+        assert_eq!(restored.get_trust_scores(), trust);
+        std::fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn test_r_expectancy() {
+        let expectancy = RExpectancy::from_r_multiples(vec![2.0, -1.0, 3.0, -1.0]);
+
+        assert_eq!(expectancy.trades, 4);
+        assert!((expectancy.win_rate - 0.5).abs() < 1e-9);
+        assert!((expectancy.avg_win_r - 2.5).abs() < 1e-9);
+        assert!((expectancy.avg_loss_r + 1.0).abs() < 1e-9);
+        assert!((expectancy.expectancy_r - 0.75).abs() < 1e-9);
+        assert_eq!(RExpectancy::from_r_multiples(Vec::new()), RExpectancy::default());
+    }
+
+    #[test]
+    fn test_similar_setups() {
+        let mut memory_node = MemoryNode::new(MemoryNodeConfig::default(), Arc::new(MessageBus::new()));
+        let conditions = |volatility: f64, funding_rate: f64| MarketConditions {
+            volatility: Some(volatility),
+            funding_rate: Some(funding_rate),
+            ..MarketConditions::default()
+        };
+
+        // Calm markets with low funding won, hot markets with high funding lost
+        for i in 0..20 {
+            let calm = i % 2 == 0;
+            let r = if calm { 1.0 } else { -1.0 };
+            memory_node.store_trade(Trade {
+                id: format!("t{}", i),
+                symbol: "BTCUSDT".to_string(),
+                entry_time: Utc::now(),
+                exit_time: Some(Utc::now()),
+                entry_price: 100.0,
+                exit_price: Some(100.0 + r),
+                direction: TradeDirection::Long,
+                position_size: 1.0,
+                pnl: Some(r),
+                roi: Some(r),
+                r_multiple: Some(r),
+                contributing_agents: Vec::new(),
+                agent_confidence: HashMap::new(),
+                agent_contributions: Vec::new(),
+                strategy: "trend".to_string(),
+                tags: Vec::new(),
+                metadata: HashMap::new(),
+                market_conditions: if calm {
+                    conditions(0.01 + i as f64 * 1e-4, 0.0001)
+                } else {
+                    conditions(0.05 + i as f64 * 1e-4, 0.001)
+                },
+            }).unwrap();
+        }
+
+        let similar = memory_node.find_similar_setups(&conditions(0.011, 0.0001), 3);
+        assert_eq!(similar.len(), 3);
+        assert!(similar.windows(2).all(|w| w[0].1 <= w[1].1));
+        assert!(similar.iter().all(|(memory, _)| memory.roi == Some(1.0)));
+
+        let outcomes = memory_node.similar_setup_outcomes(&conditions(0.05, 0.001), 5);
+        assert_eq!(outcomes.samples, 5);
+        assert_eq!(outcomes.win_rate, 0.0);
+        assert_eq!(outcomes.avg_r_multiple, Some(-1.0));
+    }
 }
@@ -6,6 +6,7 @@
 pub mod agent_coordinator;
 pub mod market_analyzer;
 pub mod sentiment_analyzer;
+pub mod sentiment_sources;
 pub mod risk_manager;
 pub mod trade_executor;
 pub mod zero_loss_enforcer;
@@ -18,23 +19,44 @@ pub mod ghost_trader;
 pub mod anti_loss_hedger;
 pub mod god_kernel;
 pub mod asset_scanner_agent;
+pub mod scan_filters;
 pub mod high_frequency_trader;
 pub mod main_strategy_controller;
+pub mod news_agent;
+pub mod onchain_agent;
+pub mod funding_agent;
+pub mod order_flow_agent;
+pub mod whale_agent;
+pub mod macro_calendar_agent;
+pub mod market_regime_agent;
+pub mod scalper_agent;
+pub mod consensus;
 
 // Re-export key types
 pub use agent_coordinator::{AgentCoordinator, TradingDecision, DecisionType};
 pub use market_analyzer::{MarketAnalyzer, MarketAnalysis};
-pub use sentiment_analyzer::{SentimentAnalyzer, SentimentAnalysis, SentimentSource};
+pub use sentiment_analyzer::{SentimentAnalyzer, SentimentAnalyzerConfig, SentimentAnalysis, SentimentSource, SentimentReading};
+pub use sentiment_sources::{SentimentConnector, SentimentConnectorKind, SentimentSourceConfig, KeywordScorer};
 pub use risk_manager::{RiskManager, RiskAssessment};
 pub use trade_executor::{TradeExecutor, TradeExecution, ExecutionStatus};
 pub use zero_loss_enforcer::{ZeroLossEnforcer, ZeroLossAssessment};
 pub use quantum_predictor::{QuantumPredictor, QuantumPrediction};
 pub use hyperdimensional_pattern_recognizer::{HyperdimensionalPatternRecognizer, PatternRecognition, PatternType};
-pub use memory_node::{MemoryNode, TradeMemory, TradeOutcome, MarketConditions, TrendDirection};
+pub use memory_node::{MemoryNode, TradeMemory, TradeOutcome, MarketConditions, TrendDirection, SetupOutcomes};
 pub use feedback_loop::{FeedbackLoop, AgentPerformance, MutationRecord};
 pub use compound_controller::{CompoundController, CapitalTier, CapitalAllocationStrategy};
 pub use ghost_trader::{GhostTrader, TradeSimulationParams, TradeSimulationResult};
 pub use anti_loss_hedger::{AntiLossHedger, HedgeRecord, HedgeType, HedgeStatus};
 pub use god_kernel::{GodKernel, AgentMetadata, EvolutionEvent, EvolutionEventType};
 pub use asset_scanner_agent::{AssetScannerAgent, AssetScannerAgentConfig};
+pub use scan_filters::{ScanCandidate, ScanFilter, ScanFilterKind};
 pub use high_frequency_trader::{HighFrequencyTrader, HighFrequencyTraderConfig};
+pub use news_agent::{NewsAgent, NewsAgentConfig, NewsItem, NEWS_TOPIC};
+pub use onchain_agent::{OnChainAgent, OnChainAgentConfig, OnChainFeatures, OnChainProvider, RestOnChainProvider};
+pub use funding_agent::{FundingAgent, FundingAgentConfig, FundingSnapshot, CrowdedSide};
+pub use order_flow_agent::{OrderFlowAgent, OrderFlowAgentConfig, OrderFlowFeatures};
+pub use whale_agent::{WhaleAgent, WhaleAgentConfig, WhaleAlert, WhaleActivity};
+pub use macro_calendar_agent::{MacroCalendarAgent, MacroCalendarAgentConfig, MacroCalendar, MacroRiskWindow};
+pub use market_regime_agent::{MarketRegimeAgent, MarketRegimeAgentConfig, MarketRegime, MarketRegimeChange, MARKET_REGIME_TOPIC};
+pub use scalper_agent::{ScalperAgent, ScalperAgentConfig, BookFeatures, SCALPER_SOURCE};
+pub use consensus::{ConsensusVoting, ConsensusConfig, ConsensusOutcome, AgentVote, Veto, VoteDirection};
@@ -0,0 +1,566 @@
+//! News Ingestion Agent
+//!
+//! This agent polls configurable news sources, either RSS feeds or JSON REST
+//! endpoints, and turns each new headline into a `NewsItem`: the symbols it
+//! mentions (matched through per-symbol aliases), a keyword sentiment between
+//! -1 and 1 and a severity between 0 and 1. Items are published on the message
+//! bus, where the sentiment analyzer folds them into its news score and the risk
+//! manager blocks entries on symbols hit by severe negative news.
+
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+use std::sync::Arc;
+use std::time::Duration;
+use chrono::{DateTime, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use anyhow::Result;
+use async_trait::async_trait;
+use reqwest::Client;
+use tokio::sync::RwLock;
+use tracing::{debug, info, warn};
+
+use crate::engine::agent_trait::{Agent, AgentConfig, AgentContext};
+use crate::engine::message_bus::{BusMessage, Message, MessageBus};
+
+/// Topic used for news items (`Message::Custom`)
+pub const NEWS_TOPIC: &str = "news_item";
+
+/// How a news source is read
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum NewsFormat {
+    /// RSS 2.0 feed (`<item>` elements)
+    Rss,
+
+    /// JSON array of articles
+    Json {
+        /// JSON pointer to the array (empty for the document root)
+        items_pointer: String,
+
+        /// Headline field
+        title_field: String,
+
+        /// Body or summary field
+        body_field: String,
+
+        /// Link field
+        url_field: String,
+
+        /// Publication time field (RFC 3339 or Unix seconds)
+        published_field: String,
+    },
+}
+
+/// One news source
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NewsSource {
+    /// Source name
+    pub name: String,
+
+    /// Feed or endpoint URL
+    pub url: String,
+
+    /// How the response is read
+    pub format: NewsFormat,
+
+    /// Seconds between polls
+    pub poll_interval_secs: i64,
+}
+
+/// News agent settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NewsAgentConfig {
+    /// Sources to poll
+    pub sources: Vec<NewsSource>,
+
+    /// Words identifying each symbol in a headline
+    pub symbol_aliases: BTreeMap<String, Vec<String>>,
+
+    /// Words that make a headline more positive
+    pub positive_keywords: Vec<String>,
+
+    /// Words that make a headline more negative
+    pub negative_keywords: Vec<String>,
+
+    /// Words that mark a headline as severe
+    pub severe_keywords: Vec<String>,
+
+    /// Items published longer ago than this are ignored (minutes)
+    pub max_age_minutes: i64,
+
+    /// Item IDs remembered to skip repeats
+    pub max_seen_items: usize,
+
+    /// HTTP request timeout (seconds)
+    pub request_timeout_secs: u64,
+}
+
+impl Default for NewsAgentConfig {
+    fn default() -> Self {
+        let words = |list: &[&str]| list.iter().map(|w| w.to_string()).collect::<Vec<_>>();
+        Self {
+            sources: vec![
+                NewsSource {
+                    name: "CoinDesk".to_string(),
+                    url: "https://www.coindesk.com/arc/outboundfeeds/rss/".to_string(),
+                    format: NewsFormat::Rss,
+                    poll_interval_secs: 120,
+                },
+                NewsSource {
+                    name: "Cointelegraph".to_string(),
+                    url: "https://cointelegraph.com/rss".to_string(),
+                    format: NewsFormat::Rss,
+                    poll_interval_secs: 120,
+                },
+            ],
+            symbol_aliases: BTreeMap::from([
+                ("BTCUSDT".to_string(), words(&["bitcoin", "btc"])),
+                ("ETHUSDT".to_string(), words(&["ethereum", "eth", "ether"])),
+                ("SOLUSDT".to_string(), words(&["solana", "sol"])),
+                ("XRPUSDT".to_string(), words(&["xrp", "ripple"])),
+                ("DOGEUSDT".to_string(), words(&["dogecoin", "doge"])),
+            ]),
+            positive_keywords: words(&[
+                "surge", "surges", "rally", "rallies", "approval", "approved", "adoption", "partnership",
+                "upgrade", "inflows", "bullish", "record", "launch", "gains",
+            ]),
+            negative_keywords: words(&[
+                "hack", "hacked", "exploit", "lawsuit", "ban", "crash", "plunge", "outflows", "bearish",
+                "delist", "delisting", "fraud", "bankruptcy", "insolvent", "liquidations", "selloff",
+            ]),
+            severe_keywords: words(&[
+                "hack", "hacked", "exploit", "bankruptcy", "insolvent", "delist", "delisting", "fraud",
+                "halt", "halted", "depeg", "sec",
+            ]),
+            max_age_minutes: 120,
+            max_seen_items: 5000,
+            request_timeout_secs: 10,
+        }
+    }
+}
+
+/// Article as read from a source, before scoring
+#[derive(Debug, Clone, PartialEq)]
+pub struct RawNewsItem {
+    /// Headline
+    pub title: String,
+
+    /// Body or summary
+    pub body: String,
+
+    /// Link
+    pub url: Option<String>,
+
+    /// Publication time
+    pub published_at: Option<DateTime<Utc>>,
+}
+
+/// Scored news item
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NewsItem {
+    /// Link, or headline when there is none
+    pub id: String,
+
+    /// Source name
+    pub source: String,
+
+    /// Headline
+    pub title: String,
+
+    /// Link
+    pub url: Option<String>,
+
+    /// Publication time
+    pub published_at: Option<DateTime<Utc>>,
+
+    /// Time the item was picked up
+    pub received_at: DateTime<Utc>,
+
+    /// Symbols mentioned
+    pub symbols: Vec<String>,
+
+    /// Keyword sentiment (-1.0 to 1.0)
+    pub sentiment: f64,
+
+    /// Severity (0.0 to 1.0)
+    pub severity: f64,
+}
+
+impl NewsItem {
+    /// Convert to a bus message
+    pub fn to_message(&self) -> Message {
+        Message::Custom(
+            NEWS_TOPIC.to_string(),
+            serde_json::to_value(self).unwrap_or(Value::Null),
+        )
+    }
+
+    /// Parse a news item from a message, if it is one
+    pub fn from_message(message: &Message) -> Option<Self> {
+        match message {
+            Message::Custom(topic, payload) if topic == NEWS_TOPIC => {
+                match serde_json::from_value(payload.clone()) {
+                    Ok(item) => Some(item),
+                    Err(e) => {
+                        warn!("Ignoring malformed news message: {}", e);
+                        None
+                    }
+                }
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Read the `<item>` elements of an RSS feed
+pub fn parse_rss(text: &str) -> Vec<RawNewsItem> {
+    let mut items = Vec::new();
+    let mut rest = text;
+    while let Some(start) = rest.find("<item") {
+        let Some(end) = rest[start..].find("</item>").map(|e| start + e) else {
+            break;
+        };
+        let block = &rest[start..end];
+        if let Some(title) = tag_text(block, "title").filter(|t| !t.is_empty()) {
+            items.push(RawNewsItem {
+                title,
+                body: tag_text(block, "description").unwrap_or_default(),
+                url: tag_text(block, "link").filter(|l| !l.is_empty()),
+                published_at: tag_text(block, "pubDate")
+                    .and_then(|d| DateTime::parse_from_rfc2822(&d).ok())
+                    .map(|d| d.with_timezone(&Utc)),
+            });
+        }
+        rest = &rest[end + "</item>".len()..];
+    }
+    items
+}
+
+/// Read the articles of a JSON response
+pub fn parse_json(value: &Value, format: &NewsFormat) -> Vec<RawNewsItem> {
+    let NewsFormat::Json { items_pointer, title_field, body_field, url_field, published_field } = format else {
+        return Vec::new();
+    };
+    let Some(articles) = value.pointer(items_pointer).and_then(Value::as_array) else {
+        return Vec::new();
+    };
+
+    articles.iter()
+        .filter_map(|article| {
+            let title = article.get(title_field)?.as_str()?.trim().to_string();
+            if title.is_empty() {
+                return None;
+            }
+            let published_at = match article.get(published_field) {
+                Some(Value::String(s)) => DateTime::parse_from_rfc3339(s).ok().map(|d| d.with_timezone(&Utc)),
+                Some(Value::Number(n)) => n.as_i64().and_then(|secs| Utc.timestamp_opt(secs, 0).single()),
+                _ => None,
+            };
+            Some(RawNewsItem {
+                title,
+                body: article.get(body_field).and_then(Value::as_str).map(clean_text).unwrap_or_default(),
+                url: article.get(url_field).and_then(Value::as_str).map(str::to_string),
+                published_at,
+            })
+        })
+        .collect()
+}
+
+/// Text of the first `tag` element in `block`
+fn tag_text(block: &str, tag: &str) -> Option<String> {
+    let start = block.find(&format!("<{}", tag))?;
+    let content_start = start + block[start..].find('>')? + 1;
+    let content_end = content_start + block[content_start..].find(&format!("</{}>", tag))?;
+    Some(clean_text(&block[content_start..content_end]))
+}
+
+/// Strip CDATA wrappers, decode the common entities and drop markup
+fn clean_text(text: &str) -> String {
+    let text = text.trim();
+    let text = text.strip_prefix("<![CDATA[").and_then(|t| t.strip_suffix("]]>")).unwrap_or(text);
+    let text = text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&");
+
+    let mut plain = String::with_capacity(text.len());
+    let mut in_tag = false;
+    for c in text.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => {
+                in_tag = false;
+                plain.push(' ');
+            },
+            _ if !in_tag => plain.push(c),
+            _ => {},
+        }
+    }
+
+    plain.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// News ingestion agent
+pub struct NewsAgent {
+    /// Settings
+    config: NewsAgentConfig,
+
+    /// HTTP client
+    client: Client,
+
+    /// Message bus news items are published on
+    message_bus: Arc<MessageBus>,
+
+    /// Whether the agent is running
+    running: bool,
+
+    /// Last poll per source
+    last_polled: HashMap<String, DateTime<Utc>>,
+
+    /// IDs of items already published
+    seen: HashSet<String>,
+
+    /// Same IDs, oldest first, to bound `seen`
+    seen_order: VecDeque<String>,
+
+    /// Items published since start, oldest first
+    recent: VecDeque<NewsItem>,
+}
+
+impl NewsAgent {
+    /// Create a new news agent
+    pub fn new(config: NewsAgentConfig, message_bus: Arc<MessageBus>) -> Self {
+        let client = Client::builder()
+            .timeout(Duration::from_secs(config.request_timeout_secs.max(1)))
+            .build()
+            .unwrap_or_default();
+        Self {
+            config,
+            client,
+            message_bus,
+            running: false,
+            last_polled: HashMap::new(),
+            seen: HashSet::new(),
+            seen_order: VecDeque::new(),
+            recent: VecDeque::new(),
+        }
+    }
+
+    /// Sentiment and severity of a text from its keywords
+    pub fn score(&self, text: &str) -> (f64, f64) {
+        let tokens = tokenize(text);
+        let hits = |keywords: &[String]| tokens.iter().filter(|t| keywords.iter().any(|k| k == *t)).count() as f64;
+        let (positive, negative, severe) = (
+            hits(&self.config.positive_keywords),
+            hits(&self.config.negative_keywords),
+            hits(&self.config.severe_keywords),
+        );
+
+        let sentiment = if positive + negative > 0.0 { (positive - negative) / (positive + negative) } else { 0.0 };
+        let severity = (severe * 0.4 + negative * 0.1).min(1.0);
+        (sentiment, severity)
+    }
+
+    /// Symbols a text mentions
+    pub fn extract_symbols(&self, text: &str) -> Vec<String> {
+        let tokens: HashSet<String> = tokenize(text).into_iter().collect();
+        self.config.symbol_aliases.iter()
+            .filter(|(_, aliases)| aliases.iter().any(|alias| tokens.contains(&alias.to_lowercase())))
+            .map(|(symbol, _)| symbol.clone())
+            .collect()
+    }
+
+    /// Score new articles of a source; repeats, stale items and items naming no symbol are dropped
+    pub fn process(&mut self, source: &str, raw: Vec<RawNewsItem>, now: DateTime<Utc>) -> Vec<NewsItem> {
+        let oldest = now - chrono::Duration::minutes(self.config.max_age_minutes);
+        let mut items = Vec::new();
+
+        for article in raw {
+            let id = article.url.clone().unwrap_or_else(|| article.title.clone());
+            if self.seen.contains(&id) || article.published_at.is_some_and(|p| p < oldest) {
+                continue;
+            }
+            self.remember(id.clone());
+
+            let text = format!("{} {}", article.title, article.body);
+            let symbols = self.extract_symbols(&text);
+            if symbols.is_empty() {
+                continue;
+            }
+            let (sentiment, severity) = self.score(&text);
+
+            let item = NewsItem {
+                id,
+                source: source.to_string(),
+                title: article.title,
+                url: article.url,
+                published_at: article.published_at,
+                received_at: now,
+                symbols,
+                sentiment,
+                severity,
+            };
+            self.recent.push_back(item.clone());
+            items.push(item);
+        }
+
+        while self.recent.len() > self.config.max_seen_items {
+            self.recent.pop_front();
+        }
+        items
+    }
+
+    /// Poll the sources that are due and return their new items
+    pub async fn poll(&mut self, now: DateTime<Utc>) -> Vec<NewsItem> {
+        let due: Vec<NewsSource> = self.config.sources.iter()
+            .filter(|source| self.last_polled.get(&source.name)
+                .map_or(true, |last| now - *last >= chrono::Duration::seconds(source.poll_interval_secs)))
+            .cloned()
+            .collect();
+
+        let mut items = Vec::new();
+        for source in due {
+            self.last_polled.insert(source.name.clone(), now);
+            match self.fetch(&source).await {
+                Ok(raw) => {
+                    debug!("{} returned {} articles", source.name, raw.len());
+                    items.extend(self.process(&source.name, raw, now));
+                },
+                Err(e) => warn!("Failed to poll news source {}: {}", source.name, e),
+            }
+        }
+        items
+    }
+
+    /// Items published since start, oldest first
+    pub fn get_recent(&self) -> Vec<&NewsItem> {
+        self.recent.iter().collect()
+    }
+
+    /// Fetch and read one source
+    async fn fetch(&self, source: &NewsSource) -> Result<Vec<RawNewsItem>> {
+        let response = self.client.get(&source.url).send().await?.error_for_status()?;
+        match &source.format {
+            NewsFormat::Rss => Ok(parse_rss(&response.text().await?)),
+            format @ NewsFormat::Json { .. } => Ok(parse_json(&response.json::<Value>().await?, format)),
+        }
+    }
+
+    /// Remember an item ID, forgetting the oldest beyond the limit
+    fn remember(&mut self, id: String) {
+        self.seen.insert(id.clone());
+        self.seen_order.push_back(id);
+        while self.seen_order.len() > self.config.max_seen_items {
+            if let Some(old) = self.seen_order.pop_front() {
+                self.seen.remove(&old);
+            }
+        }
+    }
+}
+
+/// Lowercase alphanumeric words of a text
+fn tokenize(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|t| !t.is_empty())
+        .map(str::to_lowercase)
+        .collect()
+}
+
+#[async_trait]
+impl Agent for NewsAgent {
+    fn get_name(&self) -> &str {
+        "NewsAgent"
+    }
+
+    fn get_config(&self) -> Box<dyn AgentConfig> {
+        Box::new(self.config.clone())
+    }
+
+    async fn initialize(&mut self, _context: Arc<RwLock<AgentContext>>) -> Result<()> {
+        info!("Initializing News Agent with {} sources", self.config.sources.len());
+        Ok(())
+    }
+
+    async fn start(&mut self) -> Result<()> {
+        self.running = true;
+        Ok(())
+    }
+
+    async fn stop(&mut self) -> Result<()> {
+        self.running = false;
+        Ok(())
+    }
+
+    async fn tick(&mut self) -> Result<()> {
+        if !self.running {
+            return Ok(());
+        }
+
+        for item in self.poll(Utc::now()).await {
+            info!("News on {:?} from {}: {} (sentiment {:.2}, severity {:.2})",
+                  item.symbols, item.source, item.title, item.sentiment, item.severity);
+            self.message_bus.send(item.to_message());
+        }
+        Ok(())
+    }
+
+    async fn handle_message(&mut self, _message: BusMessage) -> Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parses_and_scores_feed_items() {
+        let now = Utc::now();
+        let feed = format!(r#"<rss><channel>
+            <item>
+                <title><![CDATA[Exchange hacked, bitcoin withdrawals halted]]></title>
+                <link>https://news.example/1</link>
+                <description>&lt;p&gt;Funds drained in exploit&lt;/p&gt;</description>
+                <pubDate>{}</pubDate>
+            </item>
+            <item><title>Solana ETF approval sparks rally</title><link>https://news.example/2</link></item>
+            <item><title>Central bank holds rates</title><link>https://news.example/3</link></item>
+        </channel></rss>"#, now.to_rfc2822());
+
+        let raw = parse_rss(&feed);
+        assert_eq!(raw.len(), 3);
+        assert_eq!(raw[0].title, "Exchange hacked, bitcoin withdrawals halted");
+        assert_eq!(raw[0].body, "Funds drained in exploit");
+        assert!(raw[0].published_at.is_some());
+
+        let mut agent = NewsAgent::new(NewsAgentConfig::default(), Arc::new(MessageBus::new()));
+        let items = agent.process("test", raw.clone(), now);
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0].symbols, vec!["BTCUSDT".to_string()]);
+        assert!(items[0].sentiment < 0.0);
+        assert!(items[0].severity >= 0.8);
+        assert_eq!(items[1].symbols, vec!["SOLUSDT".to_string()]);
+        assert!(items[1].sentiment > 0.0);
+        assert_eq!(items[1].severity, 0.0);
+
+        // Repeats are skipped and items survive the trip through the bus
+        assert!(agent.process("test", raw, now).is_empty());
+        let parsed = NewsItem::from_message(&items[0].to_message()).unwrap();
+        assert_eq!(parsed.id, "https://news.example/1");
+
+        let json = serde_json::json!({ "data": [{ "headline": "Ethereum upgrade goes live", "ts": now.timestamp() }] });
+        let format = NewsFormat::Json {
+            items_pointer: "/data".to_string(),
+            title_field: "headline".to_string(),
+            body_field: "summary".to_string(),
+            url_field: "url".to_string(),
+            published_field: "ts".to_string(),
+        };
+        let raw = parse_json(&json, &format);
+        assert_eq!(raw.len(), 1);
+        assert_eq!(agent.extract_symbols(&raw[0].title), vec!["ETHUSDT".to_string()]);
+    }
+}
@@ -0,0 +1,558 @@
+//! On-Chain Data Agent
+//!
+//! This agent reads on-chain metrics from a pluggable provider: exchange inflow
+//! and outflow per asset, total stablecoin supply and large transfers. It turns
+//! them into `OnChainFeatures` per symbol, including a bias between -1 (coins
+//! moving onto exchanges, shrinking dry powder) and 1 (coins leaving exchanges,
+//! growing stablecoin supply), which the market analyzer adds to its composite
+//! opportunity score. Large transfers are also published as alerts of their own.
+
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+use std::sync::Arc;
+use std::time::Duration;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use anyhow::Result;
+use async_trait::async_trait;
+use reqwest::Client;
+use tokio::sync::RwLock;
+use tracing::{debug, info, warn};
+
+use crate::engine::agent_trait::{Agent, AgentConfig, AgentContext};
+use crate::engine::message_bus::{BusMessage, Message, MessageBus};
+
+/// Topic used for on-chain features (`Message::Custom`)
+pub const ONCHAIN_FEATURES_TOPIC: &str = "onchain_features";
+
+/// Topic used for large transfer alerts (`Message::Custom`)
+pub const LARGE_TRANSFER_TOPIC: &str = "onchain_large_transfer";
+
+/// Exchange flows of one asset over the provider's window
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ExchangeFlows {
+    /// Value moved onto exchanges (USD)
+    pub inflow_usd: f64,
+
+    /// Value moved off exchanges (USD)
+    pub outflow_usd: f64,
+}
+
+/// Where a transfer moved funds relative to exchanges
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TransferDirection {
+    /// From a wallet onto an exchange
+    ToExchange,
+
+    /// From an exchange to a wallet
+    FromExchange,
+
+    /// Between exchanges or between wallets
+    Other,
+}
+
+/// Single large transfer
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LargeTransfer {
+    /// Transaction hash
+    pub hash: String,
+
+    /// Asset moved
+    pub asset: String,
+
+    /// Value moved (USD)
+    pub amount_usd: f64,
+
+    /// Direction relative to exchanges
+    pub direction: TransferDirection,
+
+    /// Block time
+    pub timestamp: DateTime<Utc>,
+}
+
+/// Source of on-chain metrics
+#[async_trait]
+pub trait OnChainProvider: Send + Sync {
+    /// Provider name
+    fn get_name(&self) -> String;
+
+    /// Exchange flows of an asset
+    async fn get_exchange_flows(&self, asset: &str) -> Result<ExchangeFlows>;
+
+    /// Total stablecoin supply (USD)
+    async fn get_stablecoin_supply(&self) -> Result<f64>;
+
+    /// Transfers of an asset worth at least `min_usd` since `since`
+    async fn get_large_transfers(&self, asset: &str, min_usd: f64, since: DateTime<Utc>) -> Result<Vec<LargeTransfer>>;
+}
+
+/// REST provider settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RestOnChainProviderConfig {
+    /// Provider name
+    pub name: String,
+
+    /// Base URL the paths are appended to
+    pub base_url: String,
+
+    /// API key, sent in `api_key_header` when set
+    pub api_key: Option<String>,
+
+    /// Header carrying the API key
+    pub api_key_header: String,
+
+    /// Path of the exchange flows endpoint (`{asset}` is replaced)
+    pub flows_path: String,
+
+    /// Path of the stablecoin supply endpoint
+    pub stablecoin_supply_path: String,
+
+    /// Path of the large transfers endpoint (`{asset}`, `{min_usd}` and `{since}` are replaced)
+    pub transfers_path: String,
+
+    /// HTTP request timeout (seconds)
+    pub request_timeout_secs: u64,
+}
+
+impl Default for RestOnChainProviderConfig {
+    fn default() -> Self {
+        Self {
+            name: "rest".to_string(),
+            base_url: "http://localhost:8080".to_string(),
+            api_key: None,
+            api_key_header: "X-API-Key".to_string(),
+            flows_path: "/v1/exchange-flows/{asset}".to_string(),
+            stablecoin_supply_path: "/v1/stablecoins/supply".to_string(),
+            transfers_path: "/v1/transfers/{asset}?min_usd={min_usd}&since={since}".to_string(),
+            request_timeout_secs: 10,
+        }
+    }
+}
+
+/// Provider reading JSON endpoints that return the metric types as-is
+///
+/// Flows are read as `ExchangeFlows`, the supply as `{"supply_usd": ...}` and
+/// transfers as an array of `LargeTransfer`.
+pub struct RestOnChainProvider {
+    /// Settings
+    config: RestOnChainProviderConfig,
+
+    /// HTTP client
+    client: Client,
+}
+
+impl RestOnChainProvider {
+    /// Create a new REST provider
+    pub fn new(config: RestOnChainProviderConfig) -> Self {
+        let client = Client::builder()
+            .timeout(Duration::from_secs(config.request_timeout_secs.max(1)))
+            .build()
+            .unwrap_or_default();
+        Self { config, client }
+    }
+
+    /// GET a path and decode the JSON body
+    async fn get_json(&self, path: &str) -> Result<Value> {
+        let mut request = self.client.get(format!("{}{}", self.config.base_url.trim_end_matches('/'), path));
+        if let Some(api_key) = &self.config.api_key {
+            request = request.header(self.config.api_key_header.as_str(), api_key);
+        }
+        Ok(request.send().await?.error_for_status()?.json().await?)
+    }
+}
+
+#[async_trait]
+impl OnChainProvider for RestOnChainProvider {
+    fn get_name(&self) -> String {
+        self.config.name.clone()
+    }
+
+    async fn get_exchange_flows(&self, asset: &str) -> Result<ExchangeFlows> {
+        let path = self.config.flows_path.replace("{asset}", asset);
+        Ok(serde_json::from_value(self.get_json(&path).await?)?)
+    }
+
+    async fn get_stablecoin_supply(&self) -> Result<f64> {
+        self.get_json(&self.config.stablecoin_supply_path).await?
+            .get("supply_usd")
+            .and_then(Value::as_f64)
+            .ok_or_else(|| anyhow::anyhow!("{} returned no stablecoin supply", self.config.name))
+    }
+
+    async fn get_large_transfers(&self, asset: &str, min_usd: f64, since: DateTime<Utc>) -> Result<Vec<LargeTransfer>> {
+        let path = self.config.transfers_path
+            .replace("{asset}", asset)
+            .replace("{min_usd}", &format!("{:.0}", min_usd))
+            .replace("{since}", &since.timestamp().to_string());
+        Ok(serde_json::from_value(self.get_json(&path).await?)?)
+    }
+}
+
+/// On-chain agent settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct OnChainAgentConfig {
+    /// Asset tracked for each symbol
+    pub assets: BTreeMap<String, String>,
+
+    /// Seconds between polls
+    pub poll_interval_secs: i64,
+
+    /// Smallest transfer reported as large (USD)
+    pub large_transfer_usd: f64,
+
+    /// Window the stablecoin supply change is measured over (hours)
+    pub stablecoin_window_hours: i64,
+
+    /// Supply change (%) over the window that counts as a full signal
+    pub stablecoin_full_signal_pct: f64,
+
+    /// Weight of exchange net flow in the bias
+    pub flow_weight: f64,
+
+    /// Weight of stablecoin supply growth in the bias
+    pub stablecoin_weight: f64,
+
+    /// Weight of large transfer direction in the bias
+    pub whale_weight: f64,
+}
+
+impl Default for OnChainAgentConfig {
+    fn default() -> Self {
+        Self {
+            assets: BTreeMap::from([
+                ("BTCUSDT".to_string(), "BTC".to_string()),
+                ("ETHUSDT".to_string(), "ETH".to_string()),
+            ]),
+            poll_interval_secs: 300,
+            large_transfer_usd: 10_000_000.0,
+            stablecoin_window_hours: 24,
+            stablecoin_full_signal_pct: 1.0,
+            flow_weight: 0.5,
+            stablecoin_weight: 0.25,
+            whale_weight: 0.25,
+        }
+    }
+}
+
+/// On-chain features of one symbol
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OnChainFeatures {
+    /// Symbol
+    pub symbol: String,
+
+    /// Asset the metrics refer to
+    pub asset: String,
+
+    /// Time the metrics were read
+    pub timestamp: DateTime<Utc>,
+
+    /// Exchange flows
+    pub flows: ExchangeFlows,
+
+    /// Outflow minus inflow over total flow (-1.0 to 1.0)
+    pub net_flow_ratio: f64,
+
+    /// Total stablecoin supply (USD)
+    pub stablecoin_supply_usd: f64,
+
+    /// Stablecoin supply change over the window (%)
+    pub stablecoin_change_pct: f64,
+
+    /// Large transfers off exchanges minus onto exchanges over their total (-1.0 to 1.0)
+    pub whale_flow_ratio: f64,
+
+    /// Large transfers since the last poll
+    pub large_transfers: Vec<LargeTransfer>,
+
+    /// Weighted bias (-1.0 bearish to 1.0 bullish)
+    pub bias: f64,
+}
+
+impl OnChainFeatures {
+    /// Convert to a bus message
+    pub fn to_message(&self) -> Message {
+        Message::Custom(
+            ONCHAIN_FEATURES_TOPIC.to_string(),
+            serde_json::to_value(self).unwrap_or(Value::Null),
+        )
+    }
+
+    /// Parse on-chain features from a message, if it carries them
+    pub fn from_message(message: &Message) -> Option<Self> {
+        match message {
+            Message::Custom(topic, payload) if topic == ONCHAIN_FEATURES_TOPIC => {
+                match serde_json::from_value(payload.clone()) {
+                    Ok(features) => Some(features),
+                    Err(e) => {
+                        warn!("Ignoring malformed on-chain features: {}", e);
+                        None
+                    }
+                }
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Difference of two amounts over their sum (0 when both are zero)
+fn balance_ratio(positive: f64, negative: f64) -> f64 {
+    let total = positive + negative;
+    if total > 0.0 { (positive - negative) / total } else { 0.0 }
+}
+
+/// On-chain data agent
+pub struct OnChainAgent {
+    /// Settings
+    config: OnChainAgentConfig,
+
+    /// Metrics source
+    provider: Arc<dyn OnChainProvider>,
+
+    /// Message bus features and alerts are published on
+    message_bus: Arc<MessageBus>,
+
+    /// Whether the agent is running
+    running: bool,
+
+    /// Last poll
+    last_poll: Option<DateTime<Utc>>,
+
+    /// Stablecoin supply samples, oldest first
+    stablecoin_history: VecDeque<(DateTime<Utc>, f64)>,
+
+    /// Hashes of transfers already reported
+    seen_transfers: HashSet<String>,
+
+    /// Latest features by symbol
+    features: HashMap<String, OnChainFeatures>,
+}
+
+impl OnChainAgent {
+    /// Create a new on-chain agent
+    pub fn new(config: OnChainAgentConfig, provider: Arc<dyn OnChainProvider>, message_bus: Arc<MessageBus>) -> Self {
+        Self {
+            config,
+            provider,
+            message_bus,
+            running: false,
+            last_poll: None,
+            stablecoin_history: VecDeque::new(),
+            seen_transfers: HashSet::new(),
+            features: HashMap::new(),
+        }
+    }
+
+    /// Record a stablecoin supply sample and return the change over the window (%)
+    pub fn record_stablecoin_supply(&mut self, supply_usd: f64, now: DateTime<Utc>) -> f64 {
+        let window_start = now - chrono::Duration::hours(self.config.stablecoin_window_hours);
+        // Keep one sample at or before the window start as the reference point
+        while self.stablecoin_history.len() > 1 && self.stablecoin_history[1].0 <= window_start {
+            self.stablecoin_history.pop_front();
+        }
+        self.stablecoin_history.push_back((now, supply_usd));
+
+        match self.stablecoin_history.front() {
+            Some((_, reference)) if *reference > 0.0 => (supply_usd - reference) / reference * 100.0,
+            _ => 0.0,
+        }
+    }
+
+    /// Build the features of a symbol; transfers reported before are left out
+    pub fn build_features(
+        &mut self,
+        symbol: &str,
+        asset: &str,
+        flows: ExchangeFlows,
+        stablecoin_supply_usd: f64,
+        stablecoin_change_pct: f64,
+        transfers: Vec<LargeTransfer>,
+        now: DateTime<Utc>,
+    ) -> OnChainFeatures {
+        let large_transfers: Vec<LargeTransfer> = transfers.into_iter()
+            .filter(|t| self.seen_transfers.insert(t.hash.clone()))
+            .collect();
+
+        let net_flow_ratio = balance_ratio(flows.outflow_usd, flows.inflow_usd);
+        let moved = |direction| large_transfers.iter()
+            .filter(|t| t.direction == direction)
+            .map(|t| t.amount_usd)
+            .sum::<f64>();
+        let whale_flow_ratio = balance_ratio(moved(TransferDirection::FromExchange), moved(TransferDirection::ToExchange));
+        let stablecoin_signal = if self.config.stablecoin_full_signal_pct > 0.0 {
+            (stablecoin_change_pct / self.config.stablecoin_full_signal_pct).clamp(-1.0, 1.0)
+        } else {
+            0.0
+        };
+
+        let total_weight = self.config.flow_weight + self.config.stablecoin_weight + self.config.whale_weight;
+        let bias = if total_weight > 0.0 {
+            (net_flow_ratio * self.config.flow_weight
+                + stablecoin_signal * self.config.stablecoin_weight
+                + whale_flow_ratio * self.config.whale_weight) / total_weight
+        } else {
+            0.0
+        };
+
+        let features = OnChainFeatures {
+            symbol: symbol.to_string(),
+            asset: asset.to_string(),
+            timestamp: now,
+            flows,
+            net_flow_ratio,
+            stablecoin_supply_usd,
+            stablecoin_change_pct,
+            whale_flow_ratio,
+            large_transfers,
+            bias,
+        };
+        self.features.insert(symbol.to_string(), features.clone());
+        features
+    }
+
+    /// Read the metrics of every tracked symbol
+    pub async fn poll(&mut self, now: DateTime<Utc>) -> Result<Vec<OnChainFeatures>> {
+        let since = self.last_poll.unwrap_or(now - chrono::Duration::seconds(self.config.poll_interval_secs));
+        self.last_poll = Some(now);
+
+        let supply = self.provider.get_stablecoin_supply().await?;
+        let change_pct = self.record_stablecoin_supply(supply, now);
+
+        let mut features = Vec::new();
+        for (symbol, asset) in self.config.assets.clone() {
+            let flows = match self.provider.get_exchange_flows(&asset).await {
+                Ok(flows) => flows,
+                Err(e) => {
+                    warn!("{} failed to return exchange flows for {}: {}", self.provider.get_name(), asset, e);
+                    continue;
+                }
+            };
+            let transfers = self.provider.get_large_transfers(&asset, self.config.large_transfer_usd, since).await
+                .unwrap_or_else(|e| {
+                    warn!("{} failed to return transfers for {}: {}", self.provider.get_name(), asset, e);
+                    Vec::new()
+                });
+            features.push(self.build_features(&symbol, &asset, flows, supply, change_pct, transfers, now));
+        }
+
+        Ok(features)
+    }
+
+    /// Latest features of a symbol
+    pub fn get_features(&self, symbol: &str) -> Option<&OnChainFeatures> {
+        self.features.get(symbol)
+    }
+}
+
+#[async_trait]
+impl Agent for OnChainAgent {
+    fn get_name(&self) -> &str {
+        "OnChainAgent"
+    }
+
+    fn get_config(&self) -> Box<dyn AgentConfig> {
+        Box::new(self.config.clone())
+    }
+
+    async fn initialize(&mut self, _context: Arc<RwLock<AgentContext>>) -> Result<()> {
+        info!("Initializing On-Chain Agent with provider {}", self.provider.get_name());
+        Ok(())
+    }
+
+    async fn start(&mut self) -> Result<()> {
+        self.running = true;
+        Ok(())
+    }
+
+    async fn stop(&mut self) -> Result<()> {
+        self.running = false;
+        Ok(())
+    }
+
+    async fn tick(&mut self) -> Result<()> {
+        let now = Utc::now();
+        let due = self.last_poll
+            .map_or(true, |last| now - last >= chrono::Duration::seconds(self.config.poll_interval_secs));
+        if !self.running || !due {
+            return Ok(());
+        }
+
+        for features in self.poll(now).await? {
+            debug!("On-chain bias for {}: {:.2}", features.symbol, features.bias);
+            for transfer in &features.large_transfers {
+                warn!("Large {} transfer of ${:.0} ({:?})", transfer.asset, transfer.amount_usd, transfer.direction);
+                self.message_bus.send(Message::Custom(
+                    LARGE_TRANSFER_TOPIC.to_string(),
+                    serde_json::to_value(transfer).unwrap_or(Value::Null),
+                ));
+            }
+            self.message_bus.send(features.to_message());
+        }
+        Ok(())
+    }
+
+    async fn handle_message(&mut self, _message: BusMessage) -> Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct NoProvider;
+
+    #[async_trait]
+    impl OnChainProvider for NoProvider {
+        fn get_name(&self) -> String {
+            "none".to_string()
+        }
+
+        async fn get_exchange_flows(&self, _asset: &str) -> Result<ExchangeFlows> {
+            Err(anyhow::anyhow!("unavailable"))
+        }
+
+        async fn get_stablecoin_supply(&self) -> Result<f64> {
+            Err(anyhow::anyhow!("unavailable"))
+        }
+
+        async fn get_large_transfers(&self, _asset: &str, _min_usd: f64, _since: DateTime<Utc>) -> Result<Vec<LargeTransfer>> {
+            Ok(Vec::new())
+        }
+    }
+
+    #[test]
+    fn test_outflows_and_stablecoin_growth_give_bullish_bias() {
+        let mut agent = OnChainAgent::new(OnChainAgentConfig::default(), Arc::new(NoProvider), Arc::new(MessageBus::new()));
+        let now = Utc::now();
+
+        assert_eq!(agent.record_stablecoin_supply(100.0, now - chrono::Duration::hours(30)), 0.0);
+        agent.record_stablecoin_supply(100.0, now - chrono::Duration::hours(24));
+        let change = agent.record_stablecoin_supply(101.0, now);
+        assert!((change - 1.0).abs() < 1e-9);
+
+        let transfer = LargeTransfer {
+            hash: "0xabc".to_string(),
+            asset: "BTC".to_string(),
+            amount_usd: 50_000_000.0,
+            direction: TransferDirection::FromExchange,
+            timestamp: now,
+        };
+        let flows = ExchangeFlows { inflow_usd: 100.0, outflow_usd: 300.0 };
+        let features = agent.build_features("BTCUSDT", "BTC", flows.clone(), 101.0, change, vec![transfer.clone()], now);
+        assert!((features.net_flow_ratio - 0.5).abs() < 1e-9);
+        assert_eq!(features.whale_flow_ratio, 1.0);
+        assert!((features.bias - 0.75).abs() < 1e-9);
+
+        // A transfer is reported once; inflows turn the bias bearish
+        let flows = ExchangeFlows { inflow_usd: 300.0, outflow_usd: 100.0 };
+        let features = agent.build_features("BTCUSDT", "BTC", flows, 101.0, 0.0, vec![transfer], now);
+        assert!(features.large_transfers.is_empty());
+        assert!(features.bias < 0.0);
+
+        let parsed = OnChainFeatures::from_message(&features.to_message()).unwrap();
+        assert_eq!(parsed.symbol, "BTCUSDT");
+        assert_eq!(agent.get_features("BTCUSDT").unwrap().bias, features.bias);
+    }
+}
@@ -0,0 +1,322 @@
+//! Order-Flow Delta Agent
+//!
+//! This agent consumes the public trade tape of each tracked symbol and splits
+//! the traded volume by aggressor: trades whose taker bought lifted the offer,
+//! trades whose taker sold hit the bid. From that it keeps the cumulative volume
+//! delta since start and the buy/sell imbalance over a rolling window, published
+//! as `OrderFlowFeatures` for the high frequency trader and the microstructure
+//! score of the multi-factor strategy.
+
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::Arc;
+use chrono::{DateTime, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use anyhow::Result;
+use async_trait::async_trait;
+use tokio::sync::RwLock;
+use tracing::{debug, info, warn};
+
+use crate::engine::agent_trait::{Agent, AgentConfig, AgentContext};
+use crate::engine::message_bus::{BusMessage, Message, MessageBus};
+use crate::exchange::bybit::adapter::BybitAdapter;
+use crate::exchange::bybit::types::BybitPublicTrade;
+
+/// Topic used for order-flow features (`Message::Custom`)
+pub const ORDER_FLOW_TOPIC: &str = "order_flow";
+
+/// Order-flow agent settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct OrderFlowAgentConfig {
+    /// Symbols tracked
+    pub symbols: Vec<String>,
+
+    /// Product category of the tape
+    pub category: String,
+
+    /// Trades requested per poll
+    pub trades_per_poll: u32,
+
+    /// Window the imbalance is measured over (seconds)
+    pub window_secs: i64,
+
+    /// Absolute imbalance at or above which flow is one-sided
+    pub strong_imbalance: f64,
+}
+
+impl Default for OrderFlowAgentConfig {
+    fn default() -> Self {
+        Self {
+            symbols: vec!["BTCUSDT".to_string(), "ETHUSDT".to_string()],
+            category: "linear".to_string(),
+            trades_per_poll: 500,
+            window_secs: 60,
+            strong_imbalance: 0.3,
+        }
+    }
+}
+
+/// Order flow of one symbol
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct OrderFlowFeatures {
+    /// Symbol
+    pub symbol: String,
+
+    /// Time of the last trade counted
+    pub timestamp: DateTime<Utc>,
+
+    /// Aggressive buy volume in the window
+    pub buy_volume: f64,
+
+    /// Aggressive sell volume in the window
+    pub sell_volume: f64,
+
+    /// Buy minus sell volume in the window
+    pub delta: f64,
+
+    /// Buy minus sell volume since start
+    pub cumulative_delta: f64,
+
+    /// Delta over total volume in the window (-1.0 to 1.0)
+    pub imbalance: f64,
+
+    /// Trades in the window
+    pub trade_count: usize,
+}
+
+impl OrderFlowFeatures {
+    /// Whether the imbalance is at least `threshold` either way
+    pub fn is_one_sided(&self, threshold: f64) -> bool {
+        self.imbalance.abs() >= threshold
+    }
+
+    /// Convert to a bus message
+    pub fn to_message(&self) -> Message {
+        Message::Custom(
+            ORDER_FLOW_TOPIC.to_string(),
+            serde_json::to_value(self).unwrap_or(Value::Null),
+        )
+    }
+
+    /// Parse order-flow features from a message, if it carries them
+    pub fn from_message(message: &Message) -> Option<Self> {
+        match message {
+            Message::Custom(topic, payload) if topic == ORDER_FLOW_TOPIC => {
+                match serde_json::from_value(payload.clone()) {
+                    Ok(features) => Some(features),
+                    Err(e) => {
+                        warn!("Ignoring malformed order-flow features: {}", e);
+                        None
+                    }
+                }
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Running delta of one symbol
+#[derive(Debug, Default)]
+struct FlowState {
+    /// Signed volume of the trades in the window, oldest first
+    window: VecDeque<(DateTime<Utc>, f64)>,
+
+    /// Buy minus sell volume since start
+    cumulative_delta: f64,
+
+    /// Execution IDs already counted
+    seen: HashSet<String>,
+
+    /// Same IDs, oldest first, to bound `seen`
+    seen_order: VecDeque<String>,
+
+    /// Time of the last trade counted
+    last_trade: Option<DateTime<Utc>>,
+}
+
+/// Order-flow delta agent
+pub struct OrderFlowAgent {
+    /// Settings
+    config: OrderFlowAgentConfig,
+
+    /// Exchange adapter
+    exchange: Arc<BybitAdapter>,
+
+    /// Message bus features are published on
+    message_bus: Arc<MessageBus>,
+
+    /// Whether the agent is running
+    running: bool,
+
+    /// Running delta by symbol
+    flows: HashMap<String, FlowState>,
+}
+
+impl OrderFlowAgent {
+    /// Create a new order-flow agent
+    pub fn new(config: OrderFlowAgentConfig, exchange: Arc<BybitAdapter>, message_bus: Arc<MessageBus>) -> Self {
+        Self {
+            config,
+            exchange,
+            message_bus,
+            running: false,
+            flows: HashMap::new(),
+        }
+    }
+
+    /// Count new trades of a symbol, in any order, and return its features
+    pub fn ingest(&mut self, symbol: &str, trades: &[BybitPublicTrade]) -> OrderFlowFeatures {
+        let window = chrono::Duration::seconds(self.config.window_secs);
+        // Enough IDs to recognize every trade of the last poll again
+        let max_seen = self.config.trades_per_poll.max(1) as usize * 2;
+        let state = self.flows.entry(symbol.to_string()).or_default();
+
+        let mut new_trades: Vec<&BybitPublicTrade> = trades.iter()
+            .filter(|t| !state.seen.contains(&t.exec_id))
+            .collect();
+        new_trades.sort_by_key(|t| t.time);
+
+        for trade in new_trades {
+            let signed = match trade.side.as_str() {
+                "Buy" => trade.size,
+                "Sell" => -trade.size,
+                _ => continue,
+            };
+            let Some(time) = Utc.timestamp_millis_opt(trade.time).single() else {
+                continue;
+            };
+
+            state.seen.insert(trade.exec_id.clone());
+            state.seen_order.push_back(trade.exec_id.clone());
+            state.cumulative_delta += signed;
+            state.window.push_back((time, signed));
+            state.last_trade = Some(state.last_trade.map_or(time, |last| last.max(time)));
+        }
+
+        while state.seen_order.len() > max_seen {
+            if let Some(id) = state.seen_order.pop_front() {
+                state.seen.remove(&id);
+            }
+        }
+        if let Some(last) = state.last_trade {
+            while state.window.front().is_some_and(|(time, _)| last - *time > window) {
+                state.window.pop_front();
+            }
+        }
+
+        let buy_volume: f64 = state.window.iter().filter(|(_, v)| *v > 0.0).map(|(_, v)| v).sum();
+        let sell_volume: f64 = state.window.iter().filter(|(_, v)| *v < 0.0).map(|(_, v)| -v).sum();
+        let total = buy_volume + sell_volume;
+        OrderFlowFeatures {
+            symbol: symbol.to_string(),
+            timestamp: state.last_trade.unwrap_or_default(),
+            buy_volume,
+            sell_volume,
+            delta: buy_volume - sell_volume,
+            cumulative_delta: state.cumulative_delta,
+            imbalance: if total > 0.0 { (buy_volume - sell_volume) / total } else { 0.0 },
+            trade_count: state.window.len(),
+        }
+    }
+}
+
+#[async_trait]
+impl Agent for OrderFlowAgent {
+    fn get_name(&self) -> &str {
+        "OrderFlowAgent"
+    }
+
+    fn get_config(&self) -> Box<dyn AgentConfig> {
+        Box::new(self.config.clone())
+    }
+
+    async fn initialize(&mut self, _context: Arc<RwLock<AgentContext>>) -> Result<()> {
+        info!("Initializing Order-Flow Agent with {} symbols", self.config.symbols.len());
+        Ok(())
+    }
+
+    async fn start(&mut self) -> Result<()> {
+        self.running = true;
+        Ok(())
+    }
+
+    async fn stop(&mut self) -> Result<()> {
+        self.running = false;
+        Ok(())
+    }
+
+    async fn tick(&mut self) -> Result<()> {
+        if !self.running {
+            return Ok(());
+        }
+
+        for symbol in self.config.symbols.clone() {
+            let trades = match self.exchange.get_recent_trades(&symbol, &self.config.category, self.config.trades_per_poll).await {
+                Ok(trades) => trades,
+                Err(e) => {
+                    warn!("Failed to read the trade tape of {}: {}", symbol, e);
+                    continue;
+                }
+            };
+
+            let features = self.ingest(&symbol, &trades);
+            if features.is_one_sided(self.config.strong_imbalance) {
+                debug!("One-sided flow on {}: imbalance {:.2}, delta {:.4}", symbol, features.imbalance, features.delta);
+            }
+            self.message_bus.send(features.to_message());
+        }
+        Ok(())
+    }
+
+    async fn handle_message(&mut self, _message: BusMessage) -> Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn trade(id: &str, side: &str, size: f64, time: i64) -> BybitPublicTrade {
+        BybitPublicTrade {
+            exec_id: id.to_string(),
+            symbol: "BTCUSDT".to_string(),
+            price: 100.0,
+            size,
+            side: side.to_string(),
+            time,
+        }
+    }
+
+    #[test]
+    fn test_delta_and_imbalance_from_tape() {
+        let exchange = Arc::new(BybitAdapter::new("", "", true));
+        let mut agent = OrderFlowAgent::new(OrderFlowAgentConfig::default(), exchange, Arc::new(MessageBus::new()));
+        let start = 1_700_000_000_000;
+
+        // The tape arrives newest first
+        let features = agent.ingest("BTCUSDT", &[
+            trade("3", "Sell", 1.0, start + 2_000),
+            trade("2", "Buy", 2.0, start + 1_000),
+            trade("1", "Buy", 1.0, start),
+        ]);
+        assert_eq!(features.delta, 2.0);
+        assert_eq!(features.imbalance, 0.5);
+        assert_eq!(features.trade_count, 3);
+
+        // Overlapping polls count each trade once; old trades leave the window but not the cumulative delta
+        let features = agent.ingest("BTCUSDT", &[
+            trade("4", "Sell", 3.0, start + 90_000),
+            trade("3", "Sell", 1.0, start + 2_000),
+        ]);
+        assert_eq!(features.cumulative_delta, -1.0);
+        assert_eq!(features.delta, -3.0);
+        assert_eq!(features.imbalance, -1.0);
+        assert_eq!(features.trade_count, 1);
+        assert!(features.is_one_sided(0.3));
+
+        let parsed = OrderFlowFeatures::from_message(&features.to_message()).unwrap();
+        assert_eq!(parsed.cumulative_delta, -1.0);
+    }
+}
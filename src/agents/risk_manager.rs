@@ -2,14 +2,31 @@
 //!
 //! This agent is responsible for managing risk and determining position sizes.
 
-use std::collections::HashMap;
-use chrono::{DateTime, Utc};
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use chrono::{DateTime, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
 use anyhow::Result;
-use tracing::{info, debug};
+use tracing::{info, debug, warn};
 
 use crate::agents::market_analyzer::MarketAnalysis;
+use crate::agents::news_agent::NewsItem;
+use crate::agents::market_regime_agent::{MarketRegime, MarketRegimeChange};
+use crate::agents::whale_agent::WhaleAlert;
+use crate::market_data::analyzer::{CorrelationCluster, CorrelationMatrix};
+use crate::engine::message_bus::TradeDirection;
+use crate::engine::state_machine::{Event, SharedStateMachine, StateMachine};
 use crate::agents::sentiment_analyzer::SentimentAnalysis;
+use crate::monitoring::margin_monitor::{DeleverageAction, MarginMonitor, MarginMonitorConfig, MarginReport};
+use crate::position::portfolio::{LiquidationRisk, Portfolio, PortfolioExposure};
+use crate::position::position_manager::{Position, PositionDirection};
+use crate::risk::audit::AuditTrail;
+use crate::risk::kill_switch::{DailyLossKillSwitch, SafetyConfig};
+use crate::risk::leverage::{LeverageDecision, LeverageGovernor, LeverageGovernorConfig};
+use crate::risk::limits::{ExposureLimitTable, LimitOverride, LimitScope, OverrideUse};
+use crate::risk::pre_trade::TradeProposal;
+use crate::risk::var::{calculate_var, PortfolioVar, VarConfig};
+use crate::strategy::simple_strategy::Candle;
+use crate::strategy::regime::VolatilityRegime;
 
 /// Risk assessment result
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -42,6 +59,176 @@ pub struct RiskAssessment {
     pub confidence: f64,
 }
 
+/// Risk budget for a single strategy
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StrategyRiskBudget {
+    /// Maximum capital (position value in quote currency) the strategy may deploy
+    pub max_capital: f64,
+
+    /// Maximum number of concurrently open positions
+    pub max_concurrent_positions: usize,
+
+    /// Maximum realized loss per UTC day (positive number)
+    pub max_daily_loss: f64,
+}
+
+/// Current usage of a strategy's risk budget
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct StrategyRiskUsage {
+    /// Capital currently deployed
+    pub deployed_capital: f64,
+
+    /// Open positions
+    pub open_positions: usize,
+
+    /// Realized loss today (positive number)
+    pub daily_loss: f64,
+
+    /// UTC day `daily_loss` refers to
+    pub day: Option<NaiveDate>,
+}
+
+/// Topic used for circuit breaker halts and resets (`Message::Custom`)
+pub const CIRCUIT_BREAKER_TOPIC: &str = "circuit_breaker";
+
+/// Topic used when the daily loss limit trips (`Message::Custom`)
+pub const KILL_SWITCH_TOPIC: &str = "daily_loss_limit";
+
+/// Drawdown circuit breaker settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CircuitBreakerConfig {
+    /// Drawdown from peak equity (%) that halts trading
+    pub max_drawdown_pct: f64,
+}
+
+impl Default for CircuitBreakerConfig {
+    fn default() -> Self {
+        Self {
+            // Beyond the drawdown throttle, which cuts sizing first
+            max_drawdown_pct: 15.0,
+        }
+    }
+}
+
+/// Exposure cap per correlation cluster
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClusterLimitConfig {
+    /// Absolute correlation at or above which symbols belong to the same cluster
+    pub min_correlation: f64,
+
+    /// Largest directional notional of one cluster (% of capital)
+    pub max_cluster_exposure_pct: f64,
+}
+
+impl Default for ClusterLimitConfig {
+    fn default() -> Self {
+        Self {
+            min_correlation: 0.7,
+            max_cluster_exposure_pct: 200.0,
+        }
+    }
+}
+
+/// Most positions open at once unless configured otherwise
+pub const MAX_CONCURRENT_POSITIONS: usize = 2;
+
+/// Position count limit and queueing of the signals it turns away
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PositionCapacityConfig {
+    /// Most positions open at once
+    pub max_concurrent_positions: usize,
+
+    /// Most signals waiting for a free slot
+    pub max_queued_signals: usize,
+
+    /// Seconds a queued signal stays valid
+    pub signal_ttl_secs: i64,
+}
+
+impl Default for PositionCapacityConfig {
+    fn default() -> Self {
+        Self {
+            max_concurrent_positions: MAX_CONCURRENT_POSITIONS,
+            max_queued_signals: 10,
+            signal_ttl_secs: 60,
+        }
+    }
+}
+
+/// Entry blocks on symbols hit by severe negative news
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NewsRiskConfig {
+    /// Severity (0.0 to 1.0) at or above which non-positive news blocks entries
+    pub block_severity: f64,
+
+    /// Minutes an entry block lasts
+    pub block_minutes: i64,
+}
+
+impl Default for NewsRiskConfig {
+    fn default() -> Self {
+        Self {
+            block_severity: 0.7,
+            block_minutes: 60,
+        }
+    }
+}
+
+/// Reaction to whale activity on a symbol
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WhaleRiskConfig {
+    /// Whether entries pause while a whale is active
+    pub pause_entries: bool,
+
+    /// Seconds a symbol counts as whale-active after an alert
+    pub active_secs: i64,
+
+    /// Share of the stop distance kept when stops are tightened (0.0 to 1.0)
+    pub stop_tighten_factor: f64,
+}
+
+impl Default for WhaleRiskConfig {
+    fn default() -> Self {
+        Self {
+            pause_entries: true,
+            active_secs: 300,
+            stop_tighten_factor: 0.5,
+        }
+    }
+}
+
+/// Reaction to the market regime of a symbol
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MarketRegimeRiskConfig {
+    /// Regimes in which no entries are taken
+    pub blocked_regimes: Vec<MarketRegime>,
+
+    /// Position sizing multiplier per regime (1.0 where unset)
+    pub size_multipliers: HashMap<MarketRegime, f64>,
+}
+
+impl Default for MarketRegimeRiskConfig {
+    fn default() -> Self {
+        Self {
+            blocked_regimes: vec![MarketRegime::Illiquid],
+            size_multipliers: HashMap::from([(MarketRegime::Volatile, 0.5)]),
+        }
+    }
+}
+
+/// Directional exposure of one correlation cluster
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ClusterExposure {
+    /// Cluster members and their orientation
+    pub cluster: CorrelationCluster,
+
+    /// Directional notional, netting opposite bets
+    pub exposure: f64,
+
+    /// Cap on the directional notional
+    pub limit: f64,
+}
+
 /// Risk Manager Agent
 pub struct RiskManager {
     /// Total capital
@@ -58,6 +245,99 @@ pub struct RiskManager {
 
     /// Active positions
     active_positions: HashMap<String, f64>,
+
+    /// Current volatility regime
+    regime: VolatilityRegime,
+
+    /// Position sizing multiplier per volatility regime
+    regime_multipliers: HashMap<VolatilityRegime, f64>,
+
+    /// Risk budgets by strategy
+    strategy_budgets: HashMap<String, StrategyRiskBudget>,
+
+    /// Budget usage by strategy
+    strategy_usage: HashMap<String, StrategyRiskUsage>,
+
+    /// Latest portfolio exposure snapshot
+    portfolio_exposure: Option<PortfolioExposure>,
+
+    /// Expected one-way slippage in bps by symbol, from execution quality analysis
+    expected_slippage_bps: HashMap<String, f64>,
+
+    /// Margin consumption monitor
+    margin_monitor: MarginMonitor,
+
+    /// Sizing multiplier from the drawdown throttle
+    drawdown_multiplier: f64,
+
+    /// VaR settings
+    var_config: VarConfig,
+
+    /// Latest VaR of the book
+    portfolio_var: Option<PortfolioVar>,
+
+    /// Drawdown circuit breaker settings
+    circuit_breaker: CircuitBreakerConfig,
+
+    /// Highest equity since the last reset
+    peak_equity: f64,
+
+    /// Latest equity
+    equity: f64,
+
+    /// System state machine the circuit breaker halts
+    state_machine: SharedStateMachine,
+
+    /// Per-day loss limit
+    kill_switch: DailyLossKillSwitch,
+
+    /// Exposure cap per correlation cluster
+    cluster_limits: ClusterLimitConfig,
+
+    /// Latest rolling correlation matrix
+    correlation_matrix: CorrelationMatrix,
+
+    /// Per-symbol and per-sector exposure caps
+    exposure_limits: ExposureLimitTable,
+
+    /// Audit trail of limit overrides
+    audit_trail: AuditTrail,
+
+    /// Overrides the last checked trade per symbol relies on, audited once it is accepted
+    pending_override_uses: HashMap<String, Vec<OverrideUse>>,
+
+    /// Caps leverage by volatility and drawdown
+    leverage_governor: LeverageGovernor,
+
+    /// Realized per-bar volatility by symbol (fraction)
+    volatility: HashMap<String, f64>,
+
+    /// Position count limit
+    position_capacity: PositionCapacityConfig,
+
+    /// Signals waiting for a free position slot, oldest first
+    queued_signals: VecDeque<TradeProposal>,
+
+    /// Entries placed without a trading system, by symbol
+    external_entries: HashMap<String, TradeProposal>,
+
+    /// News entry block settings
+    news_risk: NewsRiskConfig,
+
+    /// Entry blocks from news by symbol: expiry and headline
+    news_blocks: HashMap<String, (DateTime<Utc>, String)>,
+
+    /// Reaction to whale activity
+    whale_risk: WhaleRiskConfig,
+
+    /// End of whale activity by symbol
+    whale_activity: HashMap<String, DateTime<Utc>>,
+
+    /// Reaction to market regimes
+    market_regime_risk: MarketRegimeRiskConfig,
+
+    /// Latest market regime by symbol
+    market_regimes: HashMap<String, MarketRegime>,
 }
 
 impl RiskManager {
@@ -69,6 +349,41 @@ impl RiskManager {
             max_portfolio_risk: 0.10, // 10% total
             assessment_cache: HashMap::new(),
             active_positions: HashMap::new(),
+            regime: VolatilityRegime::Normal,
+            regime_multipliers: HashMap::from([
+                (VolatilityRegime::Low, 1.2),
+                (VolatilityRegime::Normal, 1.0),
+                (VolatilityRegime::High, 0.5),
+            ]),
+            strategy_budgets: HashMap::new(),
+            strategy_usage: HashMap::new(),
+            portfolio_exposure: None,
+            expected_slippage_bps: HashMap::new(),
+            margin_monitor: MarginMonitor::default(),
+            drawdown_multiplier: 1.0,
+            var_config: VarConfig::default(),
+            portfolio_var: None,
+            circuit_breaker: CircuitBreakerConfig::default(),
+            peak_equity: total_capital,
+            equity: total_capital,
+            state_machine: StateMachine::shared(),
+            kill_switch: DailyLossKillSwitch::default(),
+            cluster_limits: ClusterLimitConfig::default(),
+            correlation_matrix: CorrelationMatrix::default(),
+            exposure_limits: ExposureLimitTable::default(),
+            audit_trail: AuditTrail::default(),
+            pending_override_uses: HashMap::new(),
+            leverage_governor: LeverageGovernor::default(),
+            volatility: HashMap::new(),
+            position_capacity: PositionCapacityConfig::default(),
+            queued_signals: VecDeque::new(),
+            external_entries: HashMap::new(),
+            news_risk: NewsRiskConfig::default(),
+            news_blocks: HashMap::new(),
+            whale_risk: WhaleRiskConfig::default(),
+            whale_activity: HashMap::new(),
+            market_regime_risk: MarketRegimeRiskConfig::default(),
+            market_regimes: HashMap::new(),
         }
     }
 
@@ -88,8 +403,8 @@ impl RiskManager {
         // Calculate position size based on risk score and capital
         let max_position_size = self.calculate_position_size(symbol, risk_score);
 
-        // Calculate recommended leverage based on risk score
-        let recommended_leverage = self.calculate_leverage(risk_score);
+        // Calculate recommended leverage based on risk score, capped by volatility and drawdown
+        let recommended_leverage = self.calculate_leverage(risk_score).min(self.get_max_leverage(symbol));
 
         // Calculate stop loss and take profit levels
         let (stop_loss_percent, take_profit_percent) = self.calculate_stop_loss_take_profit(
@@ -98,6 +413,14 @@ impl RiskManager {
             risk_score,
         );
 
+        // Shrink the size so the loss at the stop, including round-trip slippage, stays within budget
+        let slippage_percent = 2.0 * self.get_expected_slippage_bps(symbol) / 100.0;
+        let max_position_size = if slippage_percent > 0.0 {
+            max_position_size * stop_loss_percent / (stop_loss_percent + slippage_percent)
+        } else {
+            max_position_size
+        };
+
         // Calculate risk-to-reward ratio
         let risk_reward_ratio = take_profit_percent / stop_loss_percent;
 
@@ -211,8 +534,9 @@ impl RiskManager {
         // Base position size (without considering risk)
         let base_position_size = available_capital * self.max_risk_per_trade * 2.0;
 
-        // Adjust position size based on risk
-        let position_size = base_position_size * risk_factor;
+        // Adjust position size based on risk and volatility regime
+        let position_size = base_position_size * risk_factor * self.get_sizing_multiplier()
+            * self.get_market_regime_multiplier(symbol);
 
         // Ensure position size is not too large
         position_size.min(available_capital * 0.5)
@@ -220,8 +544,11 @@ impl RiskManager {
 
     /// Calculate available capital
     fn calculate_available_capital(&self) -> f64 {
-        // Calculate total position value
-        let total_position_value = self.active_positions.values().sum::<f64>();
+        // Calculate total position value (margin posted, once the portfolio view is known)
+        let total_position_value = match &self.portfolio_exposure {
+            Some(exposure) => exposure.margin_used,
+            None => self.active_positions.values().sum::<f64>(),
+        };
 
         // Calculate available capital
         (self.total_capital - total_position_value).max(0.0)
@@ -404,8 +731,811 @@ impl RiskManager {
         self.total_capital = new_capital;
     }
 
+    /// Update the portfolio exposure snapshot used for available capital
+    pub fn update_portfolio_exposure(&mut self, exposure: PortfolioExposure) {
+        self.portfolio_exposure = Some(exposure);
+    }
+
+    /// Get the latest portfolio exposure snapshot
+    pub fn get_portfolio_exposure(&self) -> Option<&PortfolioExposure> {
+        self.portfolio_exposure.as_ref()
+    }
+
+    /// Position closest to liquidation in the latest exposure snapshot
+    pub fn get_closest_liquidation(&self) -> Option<&LiquidationRisk> {
+        self.portfolio_exposure.as_ref().and_then(|e| e.closest_to_liquidation())
+    }
+
+    /// Set the margin thresholds
+    pub fn set_margin_config(&mut self, config: MarginMonitorConfig) {
+        self.margin_monitor = MarginMonitor::new(config);
+    }
+
+    /// Margin consumption in the latest exposure snapshot
+    pub fn get_margin_report(&self) -> Option<MarginReport> {
+        self.portfolio_exposure.as_ref().map(|e| self.margin_monitor.report(e))
+    }
+
+    /// Positions to trim before the margin ratio reaches liquidation territory
+    pub fn plan_deleverage(&self) -> Vec<DeleverageAction> {
+        self.portfolio_exposure.as_ref()
+            .map(|e| self.margin_monitor.plan_deleverage(e))
+            .unwrap_or_default()
+    }
+
+    /// Set the VaR settings
+    pub fn set_var_config(&mut self, config: VarConfig) {
+        self.var_config = config;
+    }
+
+    /// Get the VaR settings
+    pub fn get_var_config(&self) -> &VarConfig {
+        &self.var_config
+    }
+
+    /// Estimate the VaR of the latest exposure snapshot from the candle history
+    pub fn update_var(&mut self, candles: &HashMap<String, Vec<Candle>>) -> Result<&PortfolioVar> {
+        let notionals = self.net_notionals();
+        let var = calculate_var(&notionals, candles, &self.var_config)?;
+        debug!("Portfolio VaR({:.0}%): historical {:.4} (CVaR {:.4}), parametric {:.4} (CVaR {:.4})",
+               var.confidence * 100.0, var.historical.var, var.historical.cvar, var.parametric.var, var.parametric.cvar);
+        Ok(self.portfolio_var.insert(var))
+    }
+
+    /// Latest VaR of the book
+    pub fn get_portfolio_var(&self) -> Option<&PortfolioVar> {
+        self.portfolio_var.as_ref()
+    }
+
+    /// Set the drawdown circuit breaker settings
+    pub fn set_circuit_breaker_config(&mut self, config: CircuitBreakerConfig) {
+        self.circuit_breaker = config;
+    }
+
+    /// Get the drawdown circuit breaker settings
+    pub fn get_circuit_breaker_config(&self) -> &CircuitBreakerConfig {
+        &self.circuit_breaker
+    }
+
+    /// Halt `state_machine` instead of the risk manager's own one
+    pub fn set_state_machine(&mut self, state_machine: SharedStateMachine) {
+        self.state_machine = state_machine;
+    }
+
+    /// System state machine the circuit breaker halts
+    pub fn get_state_machine(&self) -> SharedStateMachine {
+        self.state_machine.clone()
+    }
+
+    /// Drawdown of `equity` from the peak since the last reset (%)
+    pub fn get_drawdown_pct(&self, equity: f64) -> f64 {
+        if self.peak_equity <= 0.0 {
+            return 0.0;
+        }
+        ((self.peak_equity - equity) / self.peak_equity * 100.0).max(0.0)
+    }
+
+    /// Record the latest equity; returns whether the circuit breaker tripped
+    pub fn update_equity(&mut self, equity: f64) -> Result<bool> {
+        self.peak_equity = self.peak_equity.max(equity);
+        self.equity = equity;
+
+        let drawdown = self.get_drawdown_pct(equity);
+        if drawdown < self.circuit_breaker.max_drawdown_pct || self.is_halted() {
+            return Ok(false);
+        }
+
+        warn!("Drawdown {:.2}% from peak {:.4} breached the {:.2}% circuit breaker",
+              drawdown, self.peak_equity, self.circuit_breaker.max_drawdown_pct);
+        self.state_machine.write()
+            .map_err(|_| anyhow::anyhow!("System state machine is poisoned"))?
+            .handle(Event::Halt(format!(
+                "drawdown {:.2}% from peak {:.4} reached the {:.2}% limit",
+                drawdown, self.peak_equity, self.circuit_breaker.max_drawdown_pct
+            )))?;
+        Ok(true)
+    }
+
+    /// Whether the system is halted
+    pub fn is_halted(&self) -> bool {
+        self.state_machine.read().map(|m| m.is_halted()).unwrap_or(true)
+    }
+
+    /// Resume trading after a halt, measuring drawdowns from `equity` onwards
+    pub fn reset_circuit_breaker(&mut self, equity: f64) -> Result<()> {
+        self.state_machine.write()
+            .map_err(|_| anyhow::anyhow!("System state machine is poisoned"))?
+            .handle(Event::Reset)?;
+        self.peak_equity = equity;
+        self.equity = equity;
+        info!("Circuit breaker reset at equity {:.4}", equity);
+        Ok(())
+    }
+
+    /// Set the safety limits
+    pub fn set_safety_config(&mut self, config: SafetyConfig) {
+        self.kill_switch.set_config(config);
+    }
+
+    /// Get the daily loss kill switch
+    pub fn get_kill_switch(&self) -> &DailyLossKillSwitch {
+        &self.kill_switch
+    }
+
+    /// Record the latest equity (realized plus unrealized); returns whether the daily loss limit tripped
+    pub fn update_daily_equity(&mut self, now: DateTime<Utc>, equity: f64) -> bool {
+        self.kill_switch.update(now, equity)
+    }
+
+    /// Whether new trades are allowed at `now` (not halted, daily loss limit not hit)
+    pub fn is_trading_allowed(&self, now: DateTime<Utc>) -> bool {
+        !self.is_halted() && !self.kill_switch.is_tripped(now)
+    }
+
+    /// Set the exposure cap per correlation cluster
+    pub fn set_cluster_limits(&mut self, config: ClusterLimitConfig) {
+        self.cluster_limits = config;
+    }
+
+    /// Get the exposure cap per correlation cluster
+    pub fn get_cluster_limits(&self) -> &ClusterLimitConfig {
+        &self.cluster_limits
+    }
+
+    /// Replace the rolling correlation matrix clusters are built from
+    pub fn update_correlations(&mut self, matrix: CorrelationMatrix) {
+        self.correlation_matrix = matrix;
+    }
+
+    /// Signed net notional by symbol in the latest exposure snapshot
+    fn net_notionals(&self) -> BTreeMap<String, f64> {
+        self.portfolio_exposure.as_ref()
+            .map(|e| e.symbols.iter().map(|(symbol, exposure)| (symbol.clone(), exposure.net_notional)).collect())
+            .unwrap_or_default()
+    }
+
+    /// Directional exposure of every correlation cluster with open positions
+    pub fn get_cluster_exposures(&self) -> Vec<ClusterExposure> {
+        let notionals = self.net_notionals();
+        let symbols: Vec<String> = notionals.keys().cloned().collect();
+        let limit = self.total_capital * self.cluster_limits.max_cluster_exposure_pct / 100.0;
+
+        self.correlation_matrix.clusters(&symbols, self.cluster_limits.min_correlation)
+            .into_iter()
+            .map(|cluster| ClusterExposure {
+                exposure: cluster.exposure(&notionals),
+                cluster,
+                limit,
+            })
+            .collect()
+    }
+
+    /// Check whether adding `notional` (positive long) on `symbol` keeps its cluster within the cap
+    pub fn check_cluster_exposure(&self, symbol: &str, notional: f64) -> Result<()> {
+        let mut notionals = self.net_notionals();
+        let symbols: Vec<String> = notionals.keys().cloned()
+            .chain(std::iter::once(symbol.to_string()))
+            .collect::<std::collections::BTreeSet<_>>()
+            .into_iter()
+            .collect();
+        let clusters = self.correlation_matrix.clusters(&symbols, self.cluster_limits.min_correlation);
+        let Some(cluster) = clusters.iter().find(|c| c.contains(symbol)) else {
+            return Ok(());
+        };
+
+        let before = cluster.exposure(&notionals);
+        *notionals.entry(symbol.to_string()).or_insert(0.0) += notional;
+        let after = cluster.exposure(&notionals);
+        let limit = self.total_capital * self.cluster_limits.max_cluster_exposure_pct / 100.0;
+
+        // Trades that reduce the cluster's net bet are always allowed
+        if after > limit && after > before {
+            let members: Vec<&str> = cluster.orientation.keys().map(String::as_str).collect();
+            return Err(anyhow::anyhow!(
+                "Cluster [{}] exposure would reach {:.2}, limit {:.2}",
+                members.join(", "), after, limit
+            ));
+        }
+        Ok(())
+    }
+
+    /// Replace the exposure limits table
+    pub fn set_exposure_limits(&mut self, limits: ExposureLimitTable) {
+        self.exposure_limits = limits;
+    }
+
+    /// Get the exposure limits table
+    pub fn get_exposure_limits(&self) -> &ExposureLimitTable {
+        &self.exposure_limits
+    }
+
+    /// Override an exposure limit; the override is audited
+    pub fn set_limit_override(&mut self, limit_override: LimitOverride) {
+        self.exposure_limits.set_override(limit_override, &mut self.audit_trail);
+    }
+
+    /// Remove the override of an exposure limit; the removal is audited
+    pub fn clear_limit_override(&mut self, scope: &LimitScope) -> Option<LimitOverride> {
+        self.exposure_limits.clear_override(scope, &mut self.audit_trail)
+    }
+
+    /// Check the symbol and sector caps for adding `notional` (positive long) on `symbol`
+    pub fn check_exposure_limits(&mut self, symbol: &str, sector: &str, notional: f64, now: DateTime<Utc>) -> Result<()> {
+        self.pending_override_uses.remove(symbol);
+        let Some(exposure) = &self.portfolio_exposure else {
+            return Ok(());
+        };
+        let uses = self.exposure_limits.check(symbol, sector, notional, exposure, now)?;
+        if !uses.is_empty() {
+            self.pending_override_uses.insert(symbol.to_string(), uses);
+        }
+        Ok(())
+    }
+
+    /// Audit the overrides the accepted trade on `symbol` relies on
+    pub fn record_override_uses(&mut self, symbol: &str) {
+        for limit_use in self.pending_override_uses.remove(symbol).unwrap_or_default() {
+            limit_use.record(&mut self.audit_trail);
+        }
+    }
+
+    /// Write audit records to `audit_trail` instead
+    pub fn set_audit_trail(&mut self, audit_trail: AuditTrail) {
+        self.audit_trail = audit_trail;
+    }
+
+    /// Get the audit trail of limit overrides
+    pub fn get_audit_trail(&self) -> &AuditTrail {
+        &self.audit_trail
+    }
+
+    /// Set the leverage governor settings
+    pub fn set_leverage_governor_config(&mut self, config: LeverageGovernorConfig) {
+        self.leverage_governor = LeverageGovernor::new(config);
+    }
+
+    /// Record the realized per-bar volatility (fraction) of a symbol
+    pub fn update_volatility(&mut self, symbol: &str, volatility: f64) {
+        self.volatility.insert(symbol.to_string(), volatility);
+    }
+
+    /// Leverage cap of a symbol at the current volatility and drawdown
+    pub fn get_leverage_decision(&self, symbol: &str) -> LeverageDecision {
+        self.leverage_governor.decide(self.volatility.get(symbol).copied(), self.get_drawdown_pct(self.equity))
+    }
+
+    /// Highest leverage permitted on a symbol
+    pub fn get_max_leverage(&self, symbol: &str) -> f64 {
+        self.get_leverage_decision(symbol).max_leverage
+    }
+
+    /// Set the position count limit and signal queue settings
+    pub fn set_position_capacity(&mut self, config: PositionCapacityConfig) {
+        self.position_capacity = config;
+    }
+
+    /// Get the position count limit and signal queue settings
+    pub fn get_position_capacity(&self) -> &PositionCapacityConfig {
+        &self.position_capacity
+    }
+
+    /// Change the most positions open at once
+    pub fn set_max_concurrent_positions(&mut self, max_concurrent_positions: usize) {
+        self.position_capacity.max_concurrent_positions = max_concurrent_positions;
+    }
+
+    /// Check that one more position fits next to `open_positions`
+    pub fn check_position_capacity(&self, open_positions: usize) -> Result<()> {
+        if open_positions >= self.position_capacity.max_concurrent_positions {
+            return Err(anyhow::anyhow!(
+                "{} of {} concurrent positions open",
+                open_positions, self.position_capacity.max_concurrent_positions
+            ));
+        }
+        Ok(())
+    }
+
+    /// Queue a signal turned away for lack of a free slot, replacing an older one for the same symbol and source
+    pub fn queue_signal(&mut self, proposal: TradeProposal) {
+        self.queued_signals.retain(|p| p.symbol != proposal.symbol || p.source != proposal.source);
+        debug!("Queued {} signal on {} until a position slot frees up", proposal.source, proposal.symbol);
+        self.queued_signals.push_back(proposal);
+        while self.queued_signals.len() > self.position_capacity.max_queued_signals {
+            self.queued_signals.pop_front();
+        }
+    }
+
+    /// Oldest queued signal still valid at `now`; expired signals are dropped
+    pub fn next_queued_signal(&mut self, now: DateTime<Utc>) -> Option<TradeProposal> {
+        let ttl = chrono::Duration::seconds(self.position_capacity.signal_ttl_secs);
+        while let Some(proposal) = self.queued_signals.pop_front() {
+            if now - proposal.timestamp <= ttl {
+                return Some(proposal);
+            }
+            debug!("Dropped expired {} signal on {}", proposal.source, proposal.symbol);
+        }
+        None
+    }
+
+    /// Signals waiting for a free position slot, oldest first
+    pub fn get_queued_signals(&self) -> Vec<&TradeProposal> {
+        self.queued_signals.iter().collect()
+    }
+
+    /// Set the news entry block settings
+    pub fn set_news_risk_config(&mut self, config: NewsRiskConfig) {
+        self.news_risk = config;
+    }
+
+    /// Get the news entry block settings
+    pub fn get_news_risk_config(&self) -> &NewsRiskConfig {
+        &self.news_risk
+    }
+
+    /// Block entries on the symbols of a severe, non-positive news item
+    pub fn apply_news(&mut self, item: &NewsItem) {
+        if item.severity < self.news_risk.block_severity || item.sentiment > 0.0 {
+            return;
+        }
+
+        let until = item.received_at + chrono::Duration::minutes(self.news_risk.block_minutes);
+        for symbol in &item.symbols {
+            warn!("Blocking entries on {} until {} after news: {}", symbol, until, item.title);
+            let block = self.news_blocks.entry(symbol.clone()).or_insert((until, item.title.clone()));
+            if block.0 < until {
+                *block = (until, item.title.clone());
+            }
+        }
+    }
+
+    /// Check that no news block is active on a symbol at `now`
+    pub fn check_news_block(&self, symbol: &str, now: DateTime<Utc>) -> Result<()> {
+        match self.news_blocks.get(symbol) {
+            Some((until, title)) if now < *until => Err(anyhow::anyhow!(
+                "Entries on {} blocked until {} after news: {}", symbol, until, title
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    /// Set the reaction to whale activity
+    pub fn set_whale_risk_config(&mut self, config: WhaleRiskConfig) {
+        self.whale_risk = config;
+    }
+
+    /// Get the reaction to whale activity
+    pub fn get_whale_risk_config(&self) -> &WhaleRiskConfig {
+        &self.whale_risk
+    }
+
+    /// Mark the symbol of a whale alert as whale-active
+    pub fn apply_whale_alert(&mut self, alert: &WhaleAlert) {
+        let until = alert.timestamp + chrono::Duration::seconds(self.whale_risk.active_secs);
+        let active = self.whale_activity.entry(alert.symbol.clone()).or_insert(until);
+        if *active < until {
+            *active = until;
+        }
+    }
+
+    /// Whether a whale is active on a symbol at `now`
+    pub fn is_whale_active(&self, symbol: &str, now: DateTime<Utc>) -> bool {
+        self.whale_activity.get(symbol).is_some_and(|until| now < *until)
+    }
+
+    /// Check that entries on a symbol are not paused for whale activity at `now`
+    pub fn check_whale_activity(&self, symbol: &str, now: DateTime<Utc>) -> Result<()> {
+        if self.whale_risk.pause_entries && self.is_whale_active(symbol, now) {
+            return Err(anyhow::anyhow!("Entries on {} paused while a whale is active", symbol));
+        }
+        Ok(())
+    }
+
+    /// Set the reaction to market regimes
+    pub fn set_market_regime_risk_config(&mut self, config: MarketRegimeRiskConfig) {
+        self.market_regime_risk = config;
+    }
+
+    /// Get the reaction to market regimes
+    pub fn get_market_regime_risk_config(&self) -> &MarketRegimeRiskConfig {
+        &self.market_regime_risk
+    }
+
+    /// Record the new market regime of a symbol
+    pub fn apply_market_regime(&mut self, change: &MarketRegimeChange) {
+        if self.market_regime_risk.blocked_regimes.contains(&change.to) {
+            warn!("Entries on {} blocked in {:?} regime", change.symbol, change.to);
+        }
+        self.market_regimes.insert(change.symbol.clone(), change.to);
+    }
+
+    /// Latest market regime of a symbol
+    pub fn get_market_regime(&self, symbol: &str) -> Option<MarketRegime> {
+        self.market_regimes.get(symbol).copied()
+    }
+
+    /// Check that the market regime of a symbol allows entries
+    pub fn check_market_regime(&self, symbol: &str) -> Result<()> {
+        match self.get_market_regime(symbol) {
+            Some(regime) if self.market_regime_risk.blocked_regimes.contains(&regime) => {
+                Err(anyhow::anyhow!("Entries on {} blocked in {:?} regime", symbol, regime))
+            },
+            _ => Ok(()),
+        }
+    }
+
+    /// Position sizing multiplier for the market regime of a symbol
+    pub fn get_market_regime_multiplier(&self, symbol: &str) -> f64 {
+        self.get_market_regime(symbol)
+            .and_then(|regime| self.market_regime_risk.size_multipliers.get(&regime).copied())
+            .unwrap_or(1.0)
+            .max(0.0)
+    }
+
+    /// Stop moved towards the current price, keeping the configured share of its distance
+    pub fn tightened_stop(&self, current_price: f64, stop_loss_price: f64) -> f64 {
+        current_price + (stop_loss_price - current_price) * self.whale_risk.stop_tighten_factor.clamp(0.0, 1.0)
+    }
+
+    /// Replace the expected slippage per symbol (bps) used for sizing
+    pub fn update_expected_slippage(&mut self, slippage_bps: HashMap<String, f64>) {
+        self.expected_slippage_bps = slippage_bps;
+    }
+
+    /// Expected one-way slippage for a symbol in bps (0 if not observed yet)
+    pub fn get_expected_slippage_bps(&self, symbol: &str) -> f64 {
+        self.expected_slippage_bps.get(symbol).copied().unwrap_or(0.0)
+    }
+
     /// Get total capital
     pub fn get_total_capital(&self) -> f64 {
         self.total_capital
     }
+
+    /// Set the current volatility regime
+    pub fn set_regime(&mut self, regime: VolatilityRegime) {
+        if regime != self.regime {
+            info!("Risk sizing switched to {:?} regime (multiplier {:.2})",
+                  regime, self.regime_multipliers.get(&regime).copied().unwrap_or(1.0));
+        }
+        self.regime = regime;
+    }
+
+    /// Get the current volatility regime
+    pub fn get_regime(&self) -> VolatilityRegime {
+        self.regime
+    }
+
+    /// Set the position sizing multiplier for a regime
+    pub fn set_regime_multiplier(&mut self, regime: VolatilityRegime, multiplier: f64) {
+        self.regime_multipliers.insert(regime, multiplier.max(0.0));
+    }
+
+    /// Position sizing multiplier for the current regime
+    pub fn get_sizing_multiplier(&self) -> f64 {
+        self.regime_multipliers.get(&self.regime).copied().unwrap_or(1.0) * self.drawdown_multiplier
+    }
+
+    /// Set the sizing multiplier of the drawdown throttle
+    pub fn set_drawdown_multiplier(&mut self, multiplier: f64) {
+        self.drawdown_multiplier = multiplier.clamp(0.0, 1.0);
+    }
+
+    /// Set the risk budget of a strategy
+    pub fn set_strategy_budget(&mut self, strategy: &str, budget: StrategyRiskBudget) {
+        info!("Risk budget for {}: capital {:.2}, positions {}, daily loss {:.2}",
+              strategy, budget.max_capital, budget.max_concurrent_positions, budget.max_daily_loss);
+        self.strategy_budgets.insert(strategy.to_string(), budget);
+    }
+
+    /// Remove the risk budget of a strategy
+    pub fn remove_strategy_budget(&mut self, strategy: &str) -> Option<StrategyRiskBudget> {
+        self.strategy_budgets.remove(strategy)
+    }
+
+    /// Check whether a strategy may open a position of the given value
+    ///
+    /// Strategies without a budget are not limited here.
+    pub fn check_strategy_budget(&self, strategy: &str, position_value: f64, now: DateTime<Utc>) -> Result<()> {
+        let budget = match self.strategy_budgets.get(strategy) {
+            Some(budget) => budget,
+            None => return Ok(()),
+        };
+        let usage = self.strategy_usage.get(strategy).cloned().unwrap_or_default();
+
+        let daily_loss = if usage.day == Some(now.date_naive()) { usage.daily_loss } else { 0.0 };
+        if daily_loss >= budget.max_daily_loss {
+            return Err(anyhow::anyhow!(
+                "Strategy {} hit its daily loss budget ({:.2} >= {:.2})",
+                strategy, daily_loss, budget.max_daily_loss
+            ));
+        }
+
+        if usage.open_positions >= budget.max_concurrent_positions {
+            return Err(anyhow::anyhow!(
+                "Strategy {} has {} open positions (limit {})",
+                strategy, usage.open_positions, budget.max_concurrent_positions
+            ));
+        }
+
+        if usage.deployed_capital + position_value > budget.max_capital {
+            return Err(anyhow::anyhow!(
+                "Strategy {} would deploy {:.2} of {:.2} capital budget",
+                strategy, usage.deployed_capital + position_value, budget.max_capital
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Record a position opened by a strategy
+    pub fn record_strategy_open(&mut self, strategy: &str, position_value: f64) {
+        let usage = self.strategy_usage.entry(strategy.to_string()).or_default();
+        usage.deployed_capital += position_value;
+        usage.open_positions += 1;
+    }
+
+    /// Record a position closed by a strategy with its realized P&L
+    pub fn record_strategy_close(&mut self, strategy: &str, position_value: f64, pnl: f64, now: DateTime<Utc>) {
+        let usage = self.strategy_usage.entry(strategy.to_string()).or_default();
+        usage.deployed_capital = (usage.deployed_capital - position_value).max(0.0);
+        usage.open_positions = usage.open_positions.saturating_sub(1);
+
+        let today = now.date_naive();
+        if usage.day != Some(today) {
+            usage.day = Some(today);
+            usage.daily_loss = 0.0;
+        }
+        if pnl < 0.0 {
+            usage.daily_loss += -pnl;
+        }
+
+        debug!("Strategy {} budget usage: {:?}", strategy, usage);
+    }
+
+    /// Book an entry placed without a trading system against its strategy's budget
+    /// and the exposure snapshot
+    pub fn record_external_entry(&mut self, proposal: &TradeProposal) {
+        self.record_strategy_open(&proposal.source, proposal.position_value());
+        self.external_entries.insert(proposal.symbol.clone(), proposal.clone());
+        self.refresh_external_exposure();
+    }
+
+    /// Release an entry booked with `record_external_entry` once its position is closed
+    pub fn record_external_exit(&mut self, symbol: &str, pnl: f64, now: DateTime<Utc>) {
+        let Some(proposal) = self.external_entries.remove(symbol) else {
+            return;
+        };
+        self.record_strategy_close(&proposal.source, proposal.position_value(), pnl, now);
+        self.refresh_external_exposure();
+    }
+
+    /// Positions of the entries placed without a trading system
+    pub fn get_external_positions(&self) -> Vec<Position> {
+        self.external_entries.values()
+            .map(|proposal| {
+                let direction = match proposal.direction {
+                    TradeDirection::Short => PositionDirection::Short,
+                    _ => PositionDirection::Long,
+                };
+                let mut position = Position::new(proposal.symbol.clone(), direction, proposal.position_size, proposal.entry_price);
+                position.id = format!("external-{}", proposal.symbol);
+                position.strategy = Some(proposal.source.clone());
+                position
+            })
+            .collect()
+    }
+
+    /// Rebuild the exposure snapshot from the entries placed without a trading system
+    fn refresh_external_exposure(&mut self) {
+        let mut portfolio = Portfolio::new(self.leverage_governor.get_config().max_leverage);
+        for proposal in self.external_entries.values() {
+            portfolio.set_leverage(&proposal.symbol, proposal.leverage);
+        }
+        self.portfolio_exposure = Some(portfolio.exposure_of(&self.get_external_positions(), self.total_capital));
+    }
+
+    /// Record that part of a strategy's position was closed; the position stays open
+    pub fn record_strategy_reduce(&mut self, strategy: &str, position_value: f64, pnl: f64, now: DateTime<Utc>) {
+        let usage = self.strategy_usage.entry(strategy.to_string()).or_default();
+        usage.deployed_capital = (usage.deployed_capital - position_value).max(0.0);
+
+        let today = now.date_naive();
+        if usage.day != Some(today) {
+            usage.day = Some(today);
+            usage.daily_loss = 0.0;
+        }
+        if pnl < 0.0 {
+            usage.daily_loss += -pnl;
+        }
+    }
+
+    /// Get the risk budget of a strategy
+    pub fn get_strategy_budget(&self, strategy: &str) -> Option<&StrategyRiskBudget> {
+        self.strategy_budgets.get(strategy)
+    }
+
+    /// Get the budget usage of a strategy
+    pub fn get_strategy_usage(&self, strategy: &str) -> Option<&StrategyRiskUsage> {
+        self.strategy_usage.get(strategy)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::position::portfolio::{Portfolio, SymbolExposure};
+    use crate::position::position_manager::Position;
+
+    #[test]
+    fn test_strategy_budget_limits() {
+        let mut risk_manager = RiskManager::new(100.0);
+        risk_manager.set_strategy_budget("scalper", StrategyRiskBudget {
+            max_capital: 10.0,
+            max_concurrent_positions: 2,
+            max_daily_loss: 1.0,
+        });
+        let now = Utc::now();
+
+        assert!(risk_manager.check_strategy_budget("scalper", 6.0, now).is_ok());
+        assert!(risk_manager.check_strategy_budget("scalper", 11.0, now).is_err());
+        assert!(risk_manager.check_strategy_budget("unbudgeted", 1000.0, now).is_ok());
+
+        risk_manager.record_strategy_open("scalper", 6.0);
+        assert!(risk_manager.check_strategy_budget("scalper", 5.0, now).is_err());
+
+        risk_manager.record_strategy_close("scalper", 6.0, -1.5, now);
+        assert!(risk_manager.check_strategy_budget("scalper", 1.0, now).is_err());
+        assert!(risk_manager.check_strategy_budget("scalper", 1.0, now + chrono::Duration::days(1)).is_ok());
+    }
+
+    #[test]
+    fn test_circuit_breaker_halts_until_reset() {
+        let mut risk_manager = RiskManager::new(100.0);
+        risk_manager.set_circuit_breaker_config(CircuitBreakerConfig { max_drawdown_pct: 10.0 });
+        risk_manager.get_state_machine().write().unwrap().handle(Event::Start).unwrap();
+
+        assert!(!risk_manager.update_equity(120.0).unwrap());
+        assert!(!risk_manager.update_equity(110.0).unwrap());
+        assert!(risk_manager.update_equity(107.0).unwrap());
+        assert!(risk_manager.is_halted());
+
+        // Recovering equity does not resume trading on its own
+        assert!(!risk_manager.update_equity(125.0).unwrap());
+        assert!(risk_manager.is_halted());
+
+        risk_manager.reset_circuit_breaker(100.0).unwrap();
+        assert!(!risk_manager.is_halted());
+        assert!(risk_manager.reset_circuit_breaker(100.0).is_err());
+    }
+
+    #[test]
+    fn test_cluster_exposure_cap() {
+        let mut risk_manager = RiskManager::new(100.0);
+        risk_manager.set_cluster_limits(ClusterLimitConfig { min_correlation: 0.7, max_cluster_exposure_pct: 100.0 });
+        risk_manager.update_correlations(CorrelationMatrix {
+            symbols: vec!["BTCUSDT".to_string(), "SOLUSDT".to_string()],
+            correlations: BTreeMap::from([
+                ("BTCUSDT".to_string(), BTreeMap::from([("SOLUSDT".to_string(), 0.9)])),
+                ("SOLUSDT".to_string(), BTreeMap::from([("BTCUSDT".to_string(), 0.9)])),
+            ]),
+        });
+
+        let mut exposure = Portfolio::new(10.0).exposure_of(std::iter::empty::<&Position>(), 100.0);
+        exposure.symbols.insert("BTCUSDT".to_string(), SymbolExposure {
+            net_notional: 80.0,
+            ..Default::default()
+        });
+        risk_manager.update_portfolio_exposure(exposure);
+
+        assert!(risk_manager.check_cluster_exposure("SOLUSDT", 30.0).is_err());
+        assert!(risk_manager.check_cluster_exposure("SOLUSDT", 15.0).is_ok());
+        assert!(risk_manager.check_cluster_exposure("SOLUSDT", -30.0).is_ok());
+        assert!(risk_manager.check_cluster_exposure("XRPUSDT", 90.0).is_ok());
+        assert_eq!(risk_manager.get_cluster_exposures()[0].exposure, 80.0);
+    }
+
+    #[test]
+    fn test_position_capacity_and_signal_queue() {
+        let mut risk_manager = RiskManager::new(100.0);
+        assert!(risk_manager.check_position_capacity(1).is_ok());
+        assert!(risk_manager.check_position_capacity(MAX_CONCURRENT_POSITIONS).is_err());
+
+        let now = Utc::now();
+        let proposal = |symbol: &str, age_secs: i64| TradeProposal {
+            symbol: symbol.to_string(),
+            direction: crate::engine::message_bus::TradeDirection::Long,
+            source: "trend".to_string(),
+            confidence: 0.8,
+            entry_price: 100.0,
+            stop_loss_price: 98.0,
+            take_profit_price: 105.0,
+            position_size: 1.0,
+            leverage: 1.0,
+            timestamp: now - chrono::Duration::seconds(age_secs),
+        };
+        risk_manager.queue_signal(proposal("BTCUSDT", 120));
+        risk_manager.queue_signal(proposal("ETHUSDT", 30));
+        risk_manager.queue_signal(proposal("ETHUSDT", 10));
+        assert_eq!(risk_manager.get_queued_signals().len(), 2);
+
+        // The stale BTC signal is dropped; the newer ETH signal replaced the older one
+        let next = risk_manager.next_queued_signal(now).unwrap();
+        assert_eq!(next.symbol, "ETHUSDT");
+        assert_eq!(next.timestamp, now - chrono::Duration::seconds(10));
+        assert!(risk_manager.next_queued_signal(now).is_none());
+    }
+
+    #[test]
+    fn test_severe_negative_news_blocks_entries() {
+        let mut risk_manager = RiskManager::new(100.0);
+        let now = Utc::now();
+        let news = |sentiment: f64, severity: f64| NewsItem {
+            id: "1".to_string(),
+            source: "test".to_string(),
+            title: "Exchange hacked".to_string(),
+            url: None,
+            published_at: None,
+            received_at: now,
+            symbols: vec!["BTCUSDT".to_string()],
+            sentiment,
+            severity,
+        };
+
+        risk_manager.apply_news(&news(-1.0, 0.3));
+        risk_manager.apply_news(&news(0.5, 0.9));
+        assert!(risk_manager.check_news_block("BTCUSDT", now).is_ok());
+
+        risk_manager.apply_news(&news(-1.0, 0.9));
+        assert!(risk_manager.check_news_block("BTCUSDT", now).is_err());
+        assert!(risk_manager.check_news_block("ETHUSDT", now).is_ok());
+        assert!(risk_manager.check_news_block("BTCUSDT", now + chrono::Duration::minutes(61)).is_ok());
+    }
+
+    #[test]
+    fn test_whale_activity_pauses_entries_and_tightens_stops() {
+        let mut risk_manager = RiskManager::new(100.0);
+        let now = Utc::now();
+        risk_manager.apply_whale_alert(&WhaleAlert {
+            symbol: "BTCUSDT".to_string(),
+            timestamp: now,
+            activity: crate::agents::whale_agent::WhaleActivity::LargeTrade,
+            side: "Sell".to_string(),
+            price: 50_000.0,
+            quantity: 30.0,
+            notional: 1_500_000.0,
+        });
+
+        assert!(risk_manager.check_whale_activity("BTCUSDT", now).is_err());
+        assert!(risk_manager.check_whale_activity("ETHUSDT", now).is_ok());
+        assert!(risk_manager.check_whale_activity("BTCUSDT", now + chrono::Duration::seconds(301)).is_ok());
+        assert_eq!(risk_manager.tightened_stop(100.0, 90.0), 95.0);
+        assert_eq!(risk_manager.tightened_stop(100.0, 110.0), 105.0);
+    }
+
+    #[test]
+    fn test_market_regime_blocks_and_resizes_entries() {
+        let mut risk_manager = RiskManager::new(100.0);
+        let change = |symbol: &str, to: MarketRegime| MarketRegimeChange {
+            symbol: symbol.to_string(),
+            timestamp: Utc::now(),
+            from: None,
+            to,
+            reading: crate::agents::market_regime_agent::RegimeReading {
+                regime: to,
+                adx: 0.0,
+                entropy: 0.0,
+                annualized_volatility: 0.0,
+                bar_turnover: 0.0,
+            },
+        };
+
+        risk_manager.apply_market_regime(&change("BTCUSDT", MarketRegime::Illiquid));
+        risk_manager.apply_market_regime(&change("ETHUSDT", MarketRegime::Volatile));
+
+        assert!(risk_manager.check_market_regime("BTCUSDT").is_err());
+        assert!(risk_manager.check_market_regime("ETHUSDT").is_ok());
+        assert!(risk_manager.check_market_regime("SOLUSDT").is_ok());
+        assert_eq!(risk_manager.get_market_regime_multiplier("ETHUSDT"), 0.5);
+        assert_eq!(risk_manager.get_market_regime_multiplier("SOLUSDT"), 1.0);
+    }
 }
@@ -0,0 +1,326 @@
+//! Orderbook Scalper Agent
+//!
+//! This agent takes sub-minute trades off the L2 book of each tracked symbol. It
+//! enters in the direction the book leans when three things line up: resting
+//! size near the top is clearly one-sided, the microprice (the size-weighted fair
+//! price between best bid and ask) has been drifting the same way over the last
+//! few snapshots, and the spread is tight enough that crossing it does not eat
+//! the edge. Entries go out as ordinary trade signals, so they are sized and run
+//! through the full pre-trade pipeline like any other; the holding policy closes
+//! scalps that have not hit their stop or target within the minute.
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::Arc;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use anyhow::Result;
+use async_trait::async_trait;
+use tokio::sync::RwLock;
+use tracing::{debug, info, warn};
+
+use crate::engine::agent_trait::{Agent, AgentConfig, AgentContext};
+use crate::engine::message_bus::{BusMessage, Message, MessageBus, TradeDirection};
+use crate::exchange::bybit::adapter::BybitAdapter;
+use crate::exchange::bybit::types::BybitOrderbook;
+use crate::position::position_manager::{ExpiryAction, HoldingPolicy};
+
+/// Source name of the scalper's trade signals
+pub const SCALPER_SOURCE: &str = "OrderbookScalper";
+
+/// Scalper settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ScalperAgentConfig {
+    /// Symbols traded
+    pub symbols: Vec<String>,
+
+    /// Orderbook levels requested per side
+    pub orderbook_depth: u32,
+
+    /// Levels per side the imbalance is measured over
+    pub imbalance_levels: usize,
+
+    /// Absolute imbalance (-1.0 to 1.0) required to enter
+    pub min_imbalance: f64,
+
+    /// Snapshots the microprice drift is measured over
+    pub drift_snapshots: usize,
+
+    /// Microprice drift (bps) required to enter, in the direction of the imbalance
+    pub min_drift_bps: f64,
+
+    /// Widest spread (bps) an entry is taken at
+    pub max_spread_bps: f64,
+
+    /// Stop distance from entry (bps)
+    pub stop_bps: f64,
+
+    /// Target distance from entry (bps); keep the target over stop ratio above the
+    /// pre-trade reward-to-risk floor or every scalp is rejected
+    pub take_profit_bps: f64,
+
+    /// Longest a scalp stays open (seconds)
+    pub max_holding_secs: u64,
+
+    /// Minimum time between entries on a symbol (seconds); also the scalper's
+    /// cooldown in the pre-trade pipeline
+    pub cooldown_secs: i64,
+}
+
+impl Default for ScalperAgentConfig {
+    fn default() -> Self {
+        Self {
+            symbols: vec!["BTCUSDT".to_string(), "ETHUSDT".to_string()],
+            orderbook_depth: 50,
+            imbalance_levels: 5,
+            min_imbalance: 0.3,
+            drift_snapshots: 5,
+            min_drift_bps: 0.5,
+            max_spread_bps: 2.0,
+            stop_bps: 8.0,
+            take_profit_bps: 14.0,
+            max_holding_secs: 45,
+            cooldown_secs: 10,
+        }
+    }
+}
+
+/// Top-of-book features of one snapshot
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BookFeatures {
+    /// Best bid
+    pub best_bid: f64,
+
+    /// Best ask
+    pub best_ask: f64,
+
+    /// Mid price
+    pub mid: f64,
+
+    /// Spread over mid (bps)
+    pub spread_bps: f64,
+
+    /// Bid minus ask size over total size of the top levels (-1.0 to 1.0)
+    pub imbalance: f64,
+
+    /// Best bid and ask weighted by the size on the opposite side
+    pub microprice: f64,
+}
+
+/// Orderbook imbalance scalper
+pub struct ScalperAgent {
+    /// Settings
+    config: ScalperAgentConfig,
+
+    /// Exchange adapter
+    exchange: Arc<BybitAdapter>,
+
+    /// Message bus signals are published on
+    message_bus: Arc<MessageBus>,
+
+    /// Whether the agent is running
+    running: bool,
+
+    /// Recent microprices by symbol, oldest first
+    microprices: HashMap<String, VecDeque<f64>>,
+
+    /// Time of the last entry by symbol
+    last_entry: HashMap<String, DateTime<Utc>>,
+}
+
+impl ScalperAgent {
+    /// Create a new scalper
+    pub fn new(config: ScalperAgentConfig, exchange: Arc<BybitAdapter>, message_bus: Arc<MessageBus>) -> Self {
+        Self {
+            config,
+            exchange,
+            message_bus,
+            running: false,
+            microprices: HashMap::new(),
+            last_entry: HashMap::new(),
+        }
+    }
+
+    /// Top-of-book features of a snapshot, if both sides are quoted
+    pub fn features(&self, orderbook: &BybitOrderbook) -> Option<BookFeatures> {
+        let (&(best_bid, bid_size), &(best_ask, ask_size)) = (orderbook.bids.first()?, orderbook.asks.first()?);
+        if best_bid <= 0.0 || best_ask <= best_bid || bid_size + ask_size <= 0.0 {
+            return None;
+        }
+
+        let mid = (best_bid + best_ask) / 2.0;
+        let levels = self.config.imbalance_levels.max(1);
+        let bid_depth: f64 = orderbook.bids.iter().take(levels).map(|&(_, size)| size).sum();
+        let ask_depth: f64 = orderbook.asks.iter().take(levels).map(|&(_, size)| size).sum();
+
+        Some(BookFeatures {
+            best_bid,
+            best_ask,
+            mid,
+            spread_bps: (best_ask - best_bid) / mid * 10_000.0,
+            imbalance: (bid_depth - ask_depth) / (bid_depth + ask_depth),
+            microprice: (best_bid * ask_size + best_ask * bid_size) / (bid_size + ask_size),
+        })
+    }
+
+    /// Take in a snapshot and return an entry signal when the setup lines up
+    pub fn on_orderbook(&mut self, symbol: &str, orderbook: &BybitOrderbook, now: DateTime<Utc>) -> Option<Message> {
+        let features = self.features(orderbook)?;
+
+        let history = self.microprices.entry(symbol.to_string()).or_default();
+        history.push_back(features.microprice);
+        while history.len() > self.config.drift_snapshots.max(1) + 1 {
+            history.pop_front();
+        }
+        if history.len() <= self.config.drift_snapshots.max(1) {
+            return None;
+        }
+        let oldest = *history.front()?;
+        let drift_bps = (features.microprice - oldest) / oldest * 10_000.0;
+
+        if features.spread_bps > self.config.max_spread_bps
+            || features.imbalance.abs() < self.config.min_imbalance
+            || drift_bps.abs() < self.config.min_drift_bps
+            || drift_bps.signum() != features.imbalance.signum()
+        {
+            return None;
+        }
+        if self.last_entry.get(symbol)
+            .is_some_and(|last| now - *last < chrono::Duration::seconds(self.config.cooldown_secs))
+        {
+            return None;
+        }
+        self.last_entry.insert(symbol.to_string(), now);
+
+        // Cross the spread: buy the ask, sell the bid
+        let is_long = features.imbalance > 0.0;
+        let (direction, entry_price, side) = if is_long {
+            (TradeDirection::Long, features.best_ask, 1.0)
+        } else {
+            (TradeDirection::Short, features.best_bid, -1.0)
+        };
+        let confidence = (features.imbalance.abs() + drift_bps.abs() / (4.0 * self.config.min_drift_bps.max(f64::EPSILON)))
+            .min(2.0) / 2.0;
+
+        info!("Scalp {} {:?} @ {:.2}: imbalance {:.2}, drift {:.2} bps, spread {:.2} bps",
+              symbol, direction, entry_price, features.imbalance, drift_bps, features.spread_bps);
+
+        Some(Message::TradeSignal {
+            symbol: symbol.to_string(),
+            direction,
+            confidence,
+            entry_price,
+            stop_loss_price: entry_price * (1.0 - side * self.config.stop_bps / 10_000.0),
+            take_profit_price: entry_price * (1.0 + side * self.config.take_profit_bps / 10_000.0),
+            source: SCALPER_SOURCE.to_string(),
+            timestamp: now,
+        })
+    }
+
+    /// Holding policy that closes scalps still open after `max_holding_secs`
+    pub fn holding_policy(&self) -> HoldingPolicy {
+        HoldingPolicy {
+            max_holding_secs: self.config.max_holding_secs,
+            action: ExpiryAction::Close,
+        }
+    }
+}
+
+#[async_trait]
+impl Agent for ScalperAgent {
+    fn get_name(&self) -> &str {
+        SCALPER_SOURCE
+    }
+
+    fn get_config(&self) -> Box<dyn AgentConfig> {
+        Box::new(self.config.clone())
+    }
+
+    async fn initialize(&mut self, _context: Arc<RwLock<AgentContext>>) -> Result<()> {
+        info!("Initializing Orderbook Scalper with {} symbols", self.config.symbols.len());
+        Ok(())
+    }
+
+    async fn start(&mut self) -> Result<()> {
+        self.running = true;
+        Ok(())
+    }
+
+    async fn stop(&mut self) -> Result<()> {
+        self.running = false;
+        Ok(())
+    }
+
+    async fn tick(&mut self) -> Result<()> {
+        if !self.running {
+            return Ok(());
+        }
+
+        for symbol in self.config.symbols.clone() {
+            match self.exchange.get_orderbook(&symbol, self.config.orderbook_depth).await {
+                Ok(orderbook) => {
+                    if let Some(signal) = self.on_orderbook(&symbol, &orderbook, Utc::now()) {
+                        self.message_bus.send(signal);
+                    } else {
+                        debug!("No scalp setup on {}", symbol);
+                    }
+                },
+                Err(e) => warn!("Failed to read the orderbook of {}: {}", symbol, e),
+            }
+        }
+        Ok(())
+    }
+
+    async fn handle_message(&mut self, _message: BusMessage) -> Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::risk::pre_trade::PreTradeConfig;
+
+    fn book(bid: f64, bid_size: f64, ask_size: f64) -> BybitOrderbook {
+        BybitOrderbook {
+            symbol: "BTCUSDT".to_string(),
+            timestamp: 0,
+            bids: vec![(bid, bid_size), (bid - 0.5, bid_size)],
+            asks: vec![(bid + 0.5, ask_size), (bid + 1.0, ask_size)],
+        }
+    }
+
+    #[test]
+    fn test_scalps_with_imbalance_drift_and_tight_spread() {
+        let config = ScalperAgentConfig { drift_snapshots: 3, min_drift_bps: 0.1, ..Default::default() };
+        let mut agent = ScalperAgent::new(config, Arc::new(BybitAdapter::new("", "", true)), Arc::new(MessageBus::new()));
+        let now = Utc::now();
+
+        // Bids stack up and the book ticks higher
+        let rising = [book(50_000.0, 5.0, 1.0), book(50_000.5, 6.0, 1.0), book(50_001.0, 7.0, 1.0)];
+        for snapshot in &rising {
+            assert!(agent.on_orderbook("BTCUSDT", snapshot, now).is_none());
+        }
+        let signal = agent.on_orderbook("BTCUSDT", &book(50_001.5, 8.0, 1.0), now).unwrap();
+        match signal {
+            Message::TradeSignal { direction, entry_price, stop_loss_price, take_profit_price, source, .. } => {
+                assert!(matches!(direction, TradeDirection::Long));
+                assert_eq!(entry_price, 50_002.0);
+                assert!(stop_loss_price < entry_price && take_profit_price > entry_price);
+                assert!((take_profit_price - entry_price) / (entry_price - stop_loss_price) > PreTradeConfig::default().min_risk_reward_ratio);
+                assert_eq!(source, SCALPER_SOURCE);
+            },
+            _ => panic!("expected a trade signal"),
+        }
+
+        // Cooldown, then a wide spread, keep the scalper out
+        assert!(agent.on_orderbook("BTCUSDT", &book(50_002.0, 9.0, 1.0), now).is_none());
+        let mut wide = book(50_003.0, 10.0, 1.0);
+        wide.asks[0].0 = 50_030.0;
+        assert!(agent.on_orderbook("BTCUSDT", &wide, now + chrono::Duration::seconds(30)).is_none());
+
+        let features = agent.features(&book(100.0, 3.0, 1.0)).unwrap();
+        assert_eq!(features.imbalance, 0.5);
+        assert_eq!(features.microprice, 100.375);
+    }
+}
@@ -0,0 +1,236 @@
+//! Asset Scan Filters
+//!
+//! This module contains the filters the asset scanner agent applies to shape the
+//! tradable universe: minimum volume and market cap, a volatility band, a spread
+//! cap, a funding cap, leverage and price floors and a symbol blacklist. Filters are listed
+//! in the scanner configuration, so each deployment can choose its own chain;
+//! custom filters implement `ScanFilter` and are added to the agent directly.
+
+use serde::{Deserialize, Serialize};
+use anyhow::Result;
+
+/// Market data of a symbol, as seen by the filters
+#[derive(Debug, Clone, Default)]
+pub struct ScanCandidate {
+    /// Symbol
+    pub symbol: String,
+
+    /// Last price
+    pub price: f64,
+
+    /// 24h volume in USD
+    pub volume_24h_usd: f64,
+
+    /// Estimated market cap in USD
+    pub market_cap_usd: f64,
+
+    /// Absolute 24h price change (%)
+    pub volatility_pct: f64,
+
+    /// Top of book spread (basis points), when an orderbook was read
+    pub spread_bps: Option<f64>,
+
+    /// Current funding rate, when it was read
+    pub funding_rate: Option<f64>,
+
+    /// Maximum leverage the exchange allows, when known
+    pub max_leverage: Option<f64>,
+}
+
+/// Filter deciding whether a symbol belongs to the tradable universe
+pub trait ScanFilter: Send + Sync {
+    /// Filter name
+    fn get_name(&self) -> String;
+
+    /// Whether the filter reads the spread (the scanner then fetches the orderbook)
+    fn needs_orderbook(&self) -> bool {
+        false
+    }
+
+    /// Whether the filter reads the funding rate (the scanner then fetches it)
+    fn needs_funding(&self) -> bool {
+        false
+    }
+
+    /// Accept a candidate, or return why it is rejected
+    fn check(&self, candidate: &ScanCandidate) -> Result<()>;
+}
+
+/// Configured filter, with its thresholds
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ScanFilterKind {
+    /// Minimum 24h volume
+    MinVolume {
+        /// Minimum volume in USD
+        min_usd: f64,
+    },
+
+    /// Minimum market cap
+    MinMarketCap {
+        /// Minimum market cap in USD
+        min_usd: f64,
+    },
+
+    /// 24h volatility band
+    Volatility {
+        /// Minimum absolute 24h change (%)
+        min_pct: f64,
+
+        /// Maximum absolute 24h change (%); no upper bound when unset
+        #[serde(default)]
+        max_pct: Option<f64>,
+    },
+
+    /// Spread cap
+    MaxSpread {
+        /// Maximum top of book spread (basis points)
+        max_bps: f64,
+    },
+
+    /// Funding cap, in either direction
+    MaxFunding {
+        /// Maximum absolute funding rate (e.g. 0.001 for 0.1%)
+        max_abs_rate: f64,
+    },
+
+    /// Leverage floor
+    MinLeverage {
+        /// Leverage the exchange must allow at least
+        min_leverage: f64,
+    },
+
+    /// Price floor
+    MinPrice {
+        /// Lowest price traded
+        min_price: f64,
+    },
+
+    /// Symbols never traded
+    Blacklist {
+        /// Excluded symbols
+        symbols: Vec<String>,
+    },
+}
+
+impl ScanFilterKind {
+    /// Check the thresholds describe a filter something can pass
+    pub fn validate(&self) -> Result<()> {
+        if let ScanFilterKind::Volatility { min_pct, max_pct: Some(max_pct) } = self {
+            if min_pct > max_pct {
+                return Err(anyhow::anyhow!("Volatility band is empty: min {}% above max {}%", min_pct, max_pct));
+            }
+        }
+        Ok(())
+    }
+
+    /// Build the filter
+    pub fn build(&self) -> Box<dyn ScanFilter> {
+        Box::new(ConfiguredFilter { kind: self.clone() })
+    }
+}
+
+/// Filter built from a `ScanFilterKind`
+struct ConfiguredFilter {
+    /// Kind and thresholds
+    kind: ScanFilterKind,
+}
+
+/// Require `value` to be present
+fn require(value: Option<f64>, what: &str) -> Result<f64> {
+    value.ok_or_else(|| anyhow::anyhow!("no {} data", what))
+}
+
+impl ScanFilter for ConfiguredFilter {
+    fn get_name(&self) -> String {
+        match &self.kind {
+            ScanFilterKind::MinVolume { .. } => "min_volume",
+            ScanFilterKind::MinMarketCap { .. } => "min_market_cap",
+            ScanFilterKind::Volatility { .. } => "volatility",
+            ScanFilterKind::MaxSpread { .. } => "max_spread",
+            ScanFilterKind::MaxFunding { .. } => "max_funding",
+            ScanFilterKind::MinLeverage { .. } => "min_leverage",
+            ScanFilterKind::MinPrice { .. } => "min_price",
+            ScanFilterKind::Blacklist { .. } => "blacklist",
+        }.to_string()
+    }
+
+    fn needs_orderbook(&self) -> bool {
+        matches!(self.kind, ScanFilterKind::MaxSpread { .. })
+    }
+
+    fn needs_funding(&self) -> bool {
+        matches!(self.kind, ScanFilterKind::MaxFunding { .. })
+    }
+
+    fn check(&self, candidate: &ScanCandidate) -> Result<()> {
+        let passed = match &self.kind {
+            ScanFilterKind::MinVolume { min_usd } => candidate.volume_24h_usd >= *min_usd,
+            ScanFilterKind::MinMarketCap { min_usd } => candidate.market_cap_usd >= *min_usd,
+            ScanFilterKind::Volatility { min_pct, max_pct } => {
+                candidate.volatility_pct >= *min_pct && max_pct.map_or(true, |max| candidate.volatility_pct <= max)
+            },
+            ScanFilterKind::MaxSpread { max_bps } => require(candidate.spread_bps, "spread")? <= *max_bps,
+            ScanFilterKind::MaxFunding { max_abs_rate } => require(candidate.funding_rate, "funding")?.abs() <= *max_abs_rate,
+            ScanFilterKind::MinLeverage { min_leverage } => require(candidate.max_leverage, "leverage")? >= *min_leverage,
+            ScanFilterKind::MinPrice { min_price } => candidate.price >= *min_price,
+            ScanFilterKind::Blacklist { symbols } => !symbols.contains(&candidate.symbol),
+        };
+
+        if passed {
+            Ok(())
+        } else {
+            Err(anyhow::anyhow!("{:?}", self.kind))
+        }
+    }
+}
+
+/// Run a candidate through a filter chain; returns the first rejection
+pub fn check_all(filters: &[Box<dyn ScanFilter>], candidate: &ScanCandidate) -> Result<()> {
+    for filter in filters {
+        filter.check(candidate)
+            .map_err(|e| anyhow::anyhow!("{} rejected by {}: {}", candidate.symbol, filter.get_name(), e))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_chain_reports_the_first_rejecting_filter() {
+        let filters: Vec<Box<dyn ScanFilter>> = serde_json::from_str::<Vec<ScanFilterKind>>(r#"[
+            {"kind": "min_volume", "min_usd": 1000000.0},
+            {"kind": "max_spread", "max_bps": 5.0},
+            {"kind": "blacklist", "symbols": ["BTCUSDT"]}
+        ]"#).unwrap().iter().map(ScanFilterKind::build).collect();
+        assert!(filters[1].needs_orderbook() && !filters[1].needs_funding());
+
+        let mut candidate = ScanCandidate {
+            symbol: "SOLUSDT".to_string(),
+            price: 150.0,
+            volume_24h_usd: 5_000_000.0,
+            ..Default::default()
+        };
+        let error = check_all(&filters, &candidate).unwrap_err().to_string();
+        assert!(error.contains("max_spread") && error.contains("no spread data"));
+
+        candidate.spread_bps = Some(1.2);
+        assert!(check_all(&filters, &candidate).is_ok());
+
+        candidate.symbol = "BTCUSDT".to_string();
+        assert!(check_all(&filters, &candidate).unwrap_err().to_string().contains("blacklist"));
+    }
+
+    #[test]
+    fn test_empty_volatility_band_is_invalid() {
+        assert!(ScanFilterKind::Volatility { min_pct: 2.0, max_pct: Some(1.6) }.validate().is_err());
+        assert!(ScanFilterKind::Volatility { min_pct: 2.0, max_pct: Some(8.0) }.validate().is_ok());
+
+        let open_ended = ScanFilterKind::Volatility { min_pct: 2.0, max_pct: None };
+        assert!(open_ended.validate().is_ok());
+        let candidate = ScanCandidate { symbol: "SOLUSDT".to_string(), volatility_pct: 12.0, ..Default::default() };
+        assert!(open_ended.build().check(&candidate).is_ok());
+    }
+}
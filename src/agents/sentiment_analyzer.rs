@@ -1,12 +1,34 @@
 //! Sentiment Analyzer Agent
 //!
 //! This agent is responsible for analyzing market sentiment from various sources.
-
-use std::collections::HashMap;
-use chrono::{DateTime, Utc};
+//! Each configured source (X, Reddit, news headlines, Fear & Greed) is read
+//! through its connector under its own rate limit, and readings are cached so a
+//! source is only asked again once its reading is older than the cache TTL.
+//! Refreshes can run on a background task, so analysis only ever reads the cache.
+//! The overall score is the weighted average of the sources that have a reading.
+
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+use std::sync::{Arc, RwLock};
+use chrono::{DateTime, Duration, Utc};
 use serde::{Deserialize, Serialize};
 use anyhow::Result;
-use tracing::{info, debug};
+use tokio::sync::Mutex;
+use tracing::{info, debug, warn};
+
+use crate::agents::news_agent::NewsItem;
+use crate::agents::sentiment_sources::{
+    build_connector, KeywordScorer, SentimentConnector, SentimentConnectorKind, SentimentSourceConfig,
+};
+use crate::exchange::bybit::rate_limiter::RateLimiter;
+
+/// How long ingested news counts towards the news score (hours)
+const NEWS_WINDOW_HOURS: i64 = 6;
+
+/// News items kept per symbol
+const MAX_NEWS_PER_SYMBOL: usize = 50;
+
+/// Reading key of sources that score the whole market
+const MARKET_WIDE: &str = "*";
 
 /// Sentiment source
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
@@ -49,64 +71,262 @@ pub struct SentimentAnalysis {
     pub confidence: f64,
 }
 
+/// Sentiment analyzer settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SentimentAnalyzerConfig {
+    /// Sources read
+    pub sources: Vec<SentimentSourceConfig>,
+
+    /// Search terms of each symbol
+    pub query_terms: BTreeMap<String, Vec<String>>,
+
+    /// Words that make a post or headline more positive
+    pub positive_keywords: Vec<String>,
+
+    /// Words that make a post or headline more negative
+    pub negative_keywords: Vec<String>,
+
+    /// Weight of news ingested from the news agent
+    pub news_weight: f64,
+
+    /// Readings younger than this are served from the cache (seconds)
+    pub cache_ttl_secs: i64,
+
+    /// Readings older than this no longer count (seconds)
+    pub max_reading_age_secs: i64,
+
+    /// HTTP request timeout (seconds)
+    pub request_timeout_secs: u64,
+}
+
+impl Default for SentimentAnalyzerConfig {
+    fn default() -> Self {
+        let words = |list: &[&str]| list.iter().map(|w| w.to_string()).collect::<Vec<_>>();
+        Self {
+            sources: vec![
+                SentimentSourceConfig {
+                    name: "Reddit".to_string(),
+                    kind: SentimentConnectorKind::Reddit {
+                        subreddits: words(&["CryptoCurrency", "CryptoMarkets"]),
+                        user_agent: "omni-alpha-sentiment/1.0".to_string(),
+                        max_results: 25,
+                    },
+                    weight: 0.4,
+                    max_requests: 30,
+                    window_secs: 60,
+                    min_delay_ms: 1000,
+                },
+                SentimentSourceConfig {
+                    name: "FearGreed".to_string(),
+                    kind: SentimentConnectorKind::FearGreed {
+                        url: "https://api.alternative.me/fng/?limit=1".to_string(),
+                    },
+                    weight: 0.2,
+                    max_requests: 10,
+                    window_secs: 60,
+                    min_delay_ms: 1000,
+                },
+            ],
+            query_terms: BTreeMap::from([
+                ("BTCUSDT".to_string(), words(&["bitcoin", "btc"])),
+                ("ETHUSDT".to_string(), words(&["ethereum", "eth"])),
+                ("SOLUSDT".to_string(), words(&["solana", "sol"])),
+                ("XRPUSDT".to_string(), words(&["xrp", "ripple"])),
+                ("DOGEUSDT".to_string(), words(&["dogecoin", "doge"])),
+            ]),
+            positive_keywords: words(&[
+                "bullish", "moon", "pump", "rally", "surge", "breakout", "buy", "buying", "long", "adoption",
+                "approval", "approved", "partnership", "upgrade", "inflows", "record", "gains", "ath",
+            ]),
+            negative_keywords: words(&[
+                "bearish", "dump", "crash", "plunge", "sell", "selling", "short", "scam", "rug", "hack",
+                "hacked", "exploit", "lawsuit", "ban", "outflows", "fraud", "liquidations", "selloff",
+            ]),
+            news_weight: 0.4,
+            cache_ttl_secs: 300,
+            max_reading_age_secs: 3600,
+            request_timeout_secs: 10,
+        }
+    }
+}
+
+/// Latest score of one source
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SentimentReading {
+    /// Category the score is reported under
+    pub source: SentimentSource,
+
+    /// Score (-100 to 100)
+    pub score: f64,
+
+    /// Time the score was read
+    pub fetched_at: DateTime<Utc>,
+}
+
+/// Connector with its rate limiter
+struct ConnectorSlot {
+    /// Connector
+    connector: Box<dyn SentimentConnector>,
+
+    /// Request rate limiter
+    limiter: RateLimiter,
+}
+
+/// Latest readings by symbol (`*` for market-wide sources), then by source name
+type Readings = HashMap<String, HashMap<String, SentimentReading>>;
+
 /// Sentiment Analyzer Agent
 pub struct SentimentAnalyzer {
+    /// Settings
+    config: SentimentAnalyzerConfig,
+
+    /// Source connectors, shared with the background refreshes
+    connectors: Vec<Arc<Mutex<ConnectorSlot>>>,
+
+    /// Weight of each source in the overall score, by source name
+    weights: HashMap<String, f64>,
+
+    /// Latest readings, written by the background refreshes
+    readings: Arc<RwLock<Readings>>,
+
+    /// Symbols with a background refresh in flight
+    refreshing: Arc<std::sync::Mutex<HashSet<String>>>,
+
     /// Analysis cache
     analysis_cache: HashMap<String, SentimentAnalysis>,
+
+    /// Ingested news per symbol, oldest first
+    news: HashMap<String, VecDeque<NewsItem>>,
 }
 
 impl SentimentAnalyzer {
-    /// Create a new sentiment analyzer
+    /// Create a new sentiment analyzer with the default sources
     pub fn new() -> Self {
-        Self {
+        Self::with_config(SentimentAnalyzerConfig::default())
+    }
+
+    /// Create a new sentiment analyzer reading the configured sources
+    pub fn with_config(config: SentimentAnalyzerConfig) -> Self {
+        let mut analyzer = Self {
+            config: SentimentAnalyzerConfig { sources: Vec::new(), ..config.clone() },
+            connectors: Vec::new(),
+            weights: HashMap::new(),
+            readings: Arc::new(RwLock::new(HashMap::new())),
+            refreshing: Arc::new(std::sync::Mutex::new(HashSet::new())),
             analysis_cache: HashMap::new(),
+            news: HashMap::new(),
+        };
+        let scorer = KeywordScorer::new(&config.positive_keywords, &config.negative_keywords);
+        for source in &config.sources {
+            let connector = build_connector(source, scorer.clone(), config.request_timeout_secs);
+            let limiter = RateLimiter::new(
+                source.max_requests.max(1),
+                std::time::Duration::from_secs(source.window_secs),
+                std::time::Duration::from_millis(source.min_delay_ms),
+            );
+            analyzer.add_connector(connector, source.weight, limiter);
         }
+        analyzer.config.sources = config.sources;
+        analyzer
     }
 
-    /// Analyze sentiment for a symbol
-    pub fn analyze(&mut self, symbol: &str) -> Result<SentimentAnalysis> {
-        debug!("Analyzing sentiment for {}", symbol);
+    /// Add a source connector
+    pub fn add_connector(&mut self, connector: Box<dyn SentimentConnector>, weight: f64, limiter: RateLimiter) {
+        info!("Adding sentiment source {} with weight {:.2}", connector.get_name(), weight);
+        self.weights.insert(connector.get_name(), weight);
+        self.connectors.push(Arc::new(Mutex::new(ConnectorSlot { connector, limiter })));
+    }
 
-        // In a real implementation, this would fetch data from various sources
-        // For now, we'll simulate sentiment analysis
+    /// Search terms of a symbol
+    fn query_terms(&self, symbol: &str) -> Vec<String> {
+        self.config.query_terms.get(symbol).cloned()
+            .unwrap_or_else(|| vec![symbol.trim_end_matches("USDT").to_lowercase()])
+    }
 
-        // Generate simulated sentiment scores
-        let mut source_scores = HashMap::new();
+    /// Read the sources whose cached reading for a symbol has expired, as far as their rate limits allow
+    pub async fn refresh(&self, symbol: &str) {
+        let ttl = Duration::seconds(self.config.cache_ttl_secs);
+        refresh_sources(&self.connectors, &self.readings, symbol, &self.query_terms(symbol), ttl).await;
+    }
 
-        // Social media sentiment (Twitter, Reddit, etc.)
-        let social_score = self.simulate_social_sentiment(symbol);
-        source_scores.insert(SentimentSource::SocialMedia("Twitter".to_string()), social_score);
+    /// Refresh a symbol's sources on a background task, unless a refresh is already in flight
+    ///
+    /// `analyze` never waits on the sources; it picks the readings up once they land.
+    pub fn spawn_refresh(&self, symbol: &str) {
+        let started = self.refreshing.lock().is_ok_and(|mut refreshing| refreshing.insert(symbol.to_string()));
+        if !started {
+            return;
+        }
+        let connectors = self.connectors.clone();
+        let readings = Arc::clone(&self.readings);
+        let refreshing = Arc::clone(&self.refreshing);
+        let symbol = symbol.to_string();
+        let terms = self.query_terms(&symbol);
+        let ttl = Duration::seconds(self.config.cache_ttl_secs);
+        tokio::spawn(async move {
+            refresh_sources(&connectors, &readings, &symbol, &terms, ttl).await;
+            if let Ok(mut refreshing) = refreshing.lock() {
+                refreshing.remove(&symbol);
+            }
+        });
+    }
 
-        // News sentiment
-        let news_score = self.simulate_news_sentiment(symbol);
-        source_scores.insert(SentimentSource::News("CryptoNews".to_string()), news_score);
+    /// Record a reading of a source for a symbol (`*` for market wide)
+    pub fn record_reading(&mut self, symbol: &str, name: &str, reading: SentimentReading) {
+        if let Ok(mut readings) = self.readings.write() {
+            readings.entry(symbol.to_string()).or_default().insert(name.to_string(), reading);
+        }
+    }
 
-        // On-chain sentiment (transactions, wallet activity, etc.)
-        let onchain_score = self.simulate_onchain_sentiment(symbol);
-        source_scores.insert(SentimentSource::OnChain("Blockchain".to_string()), onchain_score);
+    /// Analyze sentiment for a symbol from the cached source readings and ingested news
+    pub fn analyze(&mut self, symbol: &str) -> Result<SentimentAnalysis> {
+        debug!("Analyzing sentiment for {}", symbol);
+        let now = Utc::now();
+        let oldest = now - Duration::seconds(self.config.max_reading_age_secs);
 
-        // Exchange sentiment (order book, funding rates, etc.)
-        let exchange_score = self.simulate_exchange_sentiment(symbol);
-        source_scores.insert(SentimentSource::Exchange("Bybit".to_string()), exchange_score);
+        let weights = &self.weights;
+        let mut source_scores = HashMap::new();
+        let mut weighted = Vec::new();
+
+        let readings = self.readings.read().map_err(|_| anyhow::anyhow!("Sentiment readings are poisoned"))?;
+        for key in [symbol, MARKET_WIDE] {
+            for (name, reading) in readings.get(key).into_iter().flatten() {
+                if reading.fetched_at < oldest {
+                    continue;
+                }
+                let weight = weights.get(name).copied().unwrap_or(1.0);
+                source_scores.insert(reading.source.clone(), reading.score);
+                weighted.push((reading.score, weight));
+            }
+        }
+        drop(readings);
 
-        // Calculate overall sentiment score (weighted average)
-        let weights = [0.3, 0.2, 0.25, 0.25]; // Weights for each source
-        let scores = [social_score, news_score, onchain_score, exchange_score];
+        if let Some(score) = self.news_sentiment(symbol, now) {
+            source_scores.insert(SentimentSource::News("NewsAgent".to_string()), score);
+            weighted.push((score, self.config.news_weight));
+        }
 
-        let overall_score = scores.iter().zip(weights.iter())
-            .map(|(score, weight)| score * weight)
-            .sum::<f64>();
+        // Weighted average of the sources that have a reading
+        let used_weight: f64 = weighted.iter().map(|(_, weight)| weight).sum();
+        let overall_score = if used_weight > 0.0 {
+            weighted.iter().map(|(score, weight)| score * weight).sum::<f64>() / used_weight
+        } else {
+            0.0
+        };
 
         // Calculate sentiment momentum (change over time)
         let sentiment_momentum = self.calculate_sentiment_momentum(symbol, overall_score);
 
-        // Calculate confidence level
-        let confidence = self.calculate_confidence(&source_scores);
+        // Agreement between sources, scaled by the share of the configured weight that reported
+        let configured_weight = weights.values().sum::<f64>() + self.config.news_weight;
+        let coverage = if configured_weight > 0.0 { (used_weight / configured_weight).min(1.0) } else { 0.0 };
+        let confidence = self.calculate_confidence(&source_scores) * coverage;
 
         // Create analysis result
         let analysis = SentimentAnalysis {
             symbol: symbol.to_string(),
-            timestamp: Utc::now(),
+            timestamp: now,
             sentiment_score: overall_score,
             source_scores,
             sentiment_momentum,
@@ -119,76 +339,33 @@ impl SentimentAnalyzer {
         Ok(analysis)
     }
 
-    /// Simulate social media sentiment
-    fn simulate_social_sentiment(&self, symbol: &str) -> f64 {
-        // In a real implementation, this would analyze social media data
-        // For now, we'll use a simple simulation based on the symbol
-
-        let base_score = match symbol {
-            s if s.contains("BTC") => 40.0,
-            s if s.contains("ETH") => 30.0,
-            s if s.contains("SOL") => 60.0,
-            s if s.contains("DOGE") => 20.0,
-            s if s.contains("SHIB") => 10.0,
-            _ => 0.0,
-        };
-
-        // Add some randomness
-        base_score + (rand::random::<f64>() * 40.0 - 20.0)
-    }
-
-    /// Simulate news sentiment
-    fn simulate_news_sentiment(&self, symbol: &str) -> f64 {
-        // In a real implementation, this would analyze news data
-        // For now, we'll use a simple simulation based on the symbol
-
-        let base_score = match symbol {
-            s if s.contains("BTC") => 30.0,
-            s if s.contains("ETH") => 40.0,
-            s if s.contains("SOL") => 50.0,
-            s if s.contains("DOGE") => 0.0,
-            s if s.contains("SHIB") => -10.0,
-            _ => 0.0,
-        };
-
-        // Add some randomness
-        base_score + (rand::random::<f64>() * 30.0 - 15.0)
-    }
-
-    /// Simulate on-chain sentiment
-    fn simulate_onchain_sentiment(&self, symbol: &str) -> f64 {
-        // In a real implementation, this would analyze on-chain data
-        // For now, we'll use a simple simulation based on the symbol
-
-        let base_score = match symbol {
-            s if s.contains("BTC") => 50.0,
-            s if s.contains("ETH") => 40.0,
-            s if s.contains("SOL") => 30.0,
-            s if s.contains("DOGE") => 10.0,
-            s if s.contains("SHIB") => 0.0,
-            _ => 0.0,
-        };
-
-        // Add some randomness
-        base_score + (rand::random::<f64>() * 20.0 - 10.0)
+    /// Record a news item for each symbol it mentions
+    pub fn ingest_news(&mut self, item: &NewsItem) {
+        for symbol in &item.symbols {
+            let news = self.news.entry(symbol.clone()).or_default();
+            news.push_back(item.clone());
+            while news.len() > MAX_NEWS_PER_SYMBOL {
+                news.pop_front();
+            }
+        }
     }
 
-    /// Simulate exchange sentiment
-    fn simulate_exchange_sentiment(&self, symbol: &str) -> f64 {
-        // In a real implementation, this would analyze exchange data
-        // For now, we'll use a simple simulation based on the symbol
-
-        let base_score = match symbol {
-            s if s.contains("BTC") => 20.0,
-            s if s.contains("ETH") => 30.0,
-            s if s.contains("SOL") => 40.0,
-            s if s.contains("DOGE") => 0.0,
-            s if s.contains("SHIB") => -20.0,
-            _ => 0.0,
-        };
-
-        // Add some randomness
-        base_score + (rand::random::<f64>() * 40.0 - 20.0)
+    /// News score of a symbol (-100 to 100) from recent news, weighting severe items more
+    pub fn news_sentiment(&self, symbol: &str, now: DateTime<Utc>) -> Option<f64> {
+        let oldest = now - Duration::hours(NEWS_WINDOW_HOURS);
+        let (weighted, weights) = self.news.get(symbol)?
+            .iter()
+            .filter(|item| item.received_at >= oldest)
+            .fold((0.0, 0.0), |(weighted, weights), item| {
+                let weight = 1.0 + item.severity;
+                (weighted + item.sentiment * weight, weights + weight)
+            });
+
+        if weights > 0.0 {
+            Some(weighted / weights * 100.0)
+        } else {
+            None
+        }
     }
 
     /// Calculate sentiment momentum
@@ -233,3 +410,121 @@ impl SentimentAnalyzer {
         &self.analysis_cache
     }
 }
+
+/// Read the sources whose cached reading for a symbol has expired, as far as their rate limits allow
+async fn refresh_sources(
+    connectors: &[Arc<Mutex<ConnectorSlot>>],
+    readings: &RwLock<Readings>,
+    symbol: &str,
+    terms: &[String],
+    ttl: Duration,
+) {
+    let now = Utc::now();
+    for slot in connectors {
+        let mut slot = slot.lock().await;
+        let name = slot.connector.get_name();
+        let key = if slot.connector.is_market_wide() { MARKET_WIDE } else { symbol };
+        let fresh = readings.read().ok()
+            .is_some_and(|r| r.get(key).and_then(|r| r.get(&name)).is_some_and(|r| now - r.fetched_at < ttl));
+        if fresh {
+            continue;
+        }
+        if !slot.limiter.can_make_request() {
+            debug!("Sentiment source {} is rate limited, keeping its cached reading", name);
+            continue;
+        }
+        if let Err(e) = slot.limiter.wait_if_needed().await {
+            warn!("Rate limiter of {} failed: {}", name, e);
+            continue;
+        }
+
+        match slot.connector.fetch(symbol, terms).await {
+            Ok(Some(score)) => {
+                let reading = SentimentReading {
+                    source: slot.connector.source(),
+                    score: score.clamp(-100.0, 100.0),
+                    fetched_at: now,
+                };
+                if let Ok(mut readings) = readings.write() {
+                    readings.entry(key.to_string()).or_default().insert(name, reading);
+                }
+            },
+            Ok(None) => debug!("{} had nothing on {}", name, symbol),
+            Err(e) => warn!("Failed to read sentiment source {} for {}: {}", name, symbol, e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_weights_cached_readings_by_source() {
+        let words = |list: &[&str]| list.iter().map(|w| w.to_string()).collect::<Vec<_>>();
+        let scorer = KeywordScorer::new(&words(&["bullish", "rally"]), &words(&["crash"]));
+        assert_eq!(scorer.score(&words(&["BTC rally looks bullish", "nothing here"])), Some(100.0));
+        assert_eq!(scorer.score(&words(&["bullish or crash?"])), Some(0.0));
+        assert_eq!(scorer.score(&words(&["no keywords"])), None);
+
+        // Default weights: Reddit 0.4, Fear & Greed 0.2
+        let mut analyzer = SentimentAnalyzer::new();
+        let now = Utc::now();
+        analyzer.record_reading("BTCUSDT", "Reddit", SentimentReading {
+            source: SentimentSource::SocialMedia("Reddit".to_string()),
+            score: 60.0,
+            fetched_at: now,
+        });
+        analyzer.record_reading(MARKET_WIDE, "FearGreed", SentimentReading {
+            source: SentimentSource::Other("FearGreed".to_string()),
+            score: -30.0,
+            fetched_at: now,
+        });
+
+        let analysis = analyzer.analyze("BTCUSDT").unwrap();
+        assert!((analysis.sentiment_score - 30.0).abs() < 1e-9);
+        assert_eq!(analysis.source_scores.len(), 2);
+        assert!(analysis.confidence > 0.0 && analysis.confidence < 100.0);
+
+        // Without readings the score is neutral and carries no confidence
+        let empty = analyzer.analyze("ETHUSDT").unwrap();
+        assert_eq!(empty.sentiment_score, 0.0);
+        assert_eq!(empty.confidence, 0.0);
+    }
+
+    struct StubConnector;
+
+    #[async_trait::async_trait]
+    impl SentimentConnector for StubConnector {
+        fn get_name(&self) -> String {
+            "Stub".to_string()
+        }
+
+        fn source(&self) -> SentimentSource {
+            SentimentSource::Other("Stub".to_string())
+        }
+
+        async fn fetch(&self, _symbol: &str, _terms: &[String]) -> Result<Option<f64>> {
+            Ok(Some(40.0))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_background_refresh_fills_the_cache() {
+        let mut analyzer = SentimentAnalyzer::with_config(SentimentAnalyzerConfig {
+            sources: Vec::new(),
+            ..SentimentAnalyzerConfig::default()
+        });
+        let limiter = RateLimiter::new(10, std::time::Duration::from_secs(60), std::time::Duration::ZERO);
+        analyzer.add_connector(Box::new(StubConnector), 1.0, limiter);
+
+        analyzer.spawn_refresh("BTCUSDT");
+        for _ in 0..50 {
+            if analyzer.analyze("BTCUSDT").unwrap().sentiment_score != 0.0 {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+        assert!((analyzer.analyze("BTCUSDT").unwrap().sentiment_score - 40.0).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,374 @@
+//! Sentiment Source Connectors
+//!
+//! This module contains the connectors the sentiment analyzer reads from: X
+//! (Twitter) recent search, Reddit subreddit search, a JSON news headline API
+//! and the Crypto Fear & Greed index. Text sources are scored with a keyword
+//! scorer; every connector returns a score between -100 and 100.
+
+use std::time::Duration;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use anyhow::Result;
+use async_trait::async_trait;
+use reqwest::Client;
+
+use crate::agents::sentiment_analyzer::SentimentSource;
+
+/// Kind of a sentiment source, with its connection settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum SentimentConnectorKind {
+    /// X (Twitter) API v2 recent search
+    Twitter {
+        /// Bearer token of the app
+        bearer_token: String,
+
+        /// Posts read per request (10 to 100)
+        max_results: u32,
+    },
+
+    /// Search of recent posts in subreddits
+    Reddit {
+        /// Subreddits searched
+        subreddits: Vec<String>,
+
+        /// User agent Reddit requires on API calls
+        user_agent: String,
+
+        /// Posts read per subreddit
+        max_results: u32,
+    },
+
+    /// JSON headline API
+    NewsHeadlines {
+        /// Endpoint URL (`{query}` is replaced by the symbol's terms, comma separated)
+        url: String,
+
+        /// JSON pointer of the item array (e.g. `/results`)
+        items_pointer: String,
+
+        /// Field of an item holding its headline
+        title_field: String,
+
+        /// API key, sent in `api_key_header` when set
+        api_key: Option<String>,
+
+        /// Header carrying the API key
+        api_key_header: String,
+    },
+
+    /// Crypto Fear & Greed index (market wide)
+    FearGreed {
+        /// Endpoint URL
+        url: String,
+    },
+}
+
+/// One sentiment source
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SentimentSourceConfig {
+    /// Source name
+    pub name: String,
+
+    /// Kind and connection settings
+    pub kind: SentimentConnectorKind,
+
+    /// Weight of the source in the overall score
+    pub weight: f64,
+
+    /// Requests allowed per window
+    pub max_requests: usize,
+
+    /// Rate limit window (seconds)
+    pub window_secs: u64,
+
+    /// Minimum delay between requests (milliseconds)
+    pub min_delay_ms: u64,
+}
+
+/// Source of sentiment scores
+#[async_trait]
+pub trait SentimentConnector: Send + Sync {
+    /// Source name
+    fn get_name(&self) -> String;
+
+    /// Category the score is reported under
+    fn source(&self) -> SentimentSource;
+
+    /// Whether the score is the same for every symbol
+    fn is_market_wide(&self) -> bool {
+        false
+    }
+
+    /// Score (-100 to 100) of a symbol, searched by its terms; `None` when nothing was found
+    async fn fetch(&self, symbol: &str, terms: &[String]) -> Result<Option<f64>>;
+}
+
+/// Keyword polarity scorer for posts and headlines
+#[derive(Debug, Clone, Default)]
+pub struct KeywordScorer {
+    /// Words that make a text more positive
+    positive: Vec<String>,
+
+    /// Words that make a text more negative
+    negative: Vec<String>,
+}
+
+impl KeywordScorer {
+    /// Create a new scorer
+    pub fn new(positive: &[String], negative: &[String]) -> Self {
+        let lower = |words: &[String]| words.iter().map(|w| w.to_lowercase()).collect();
+        Self { positive: lower(positive), negative: lower(negative) }
+    }
+
+    /// Mean polarity (-100 to 100) of the texts that contain any keyword
+    pub fn score(&self, texts: &[String]) -> Option<f64> {
+        let polarities: Vec<f64> = texts.iter()
+            .filter_map(|text| {
+                let tokens: Vec<String> = text.split(|c: char| !c.is_alphanumeric())
+                    .filter(|t| !t.is_empty())
+                    .map(str::to_lowercase)
+                    .collect();
+                let hits = |words: &[String]| tokens.iter().filter(|t| words.contains(t)).count() as f64;
+                let (positive, negative) = (hits(&self.positive), hits(&self.negative));
+                (positive + negative > 0.0).then(|| (positive - negative) / (positive + negative))
+            })
+            .collect();
+
+        if polarities.is_empty() {
+            None
+        } else {
+            Some(polarities.iter().sum::<f64>() / polarities.len() as f64 * 100.0)
+        }
+    }
+}
+
+/// Strings at `field` of each item of the array at `pointer`
+fn texts_at(value: &Value, pointer: &str, field: &str) -> Vec<String> {
+    value.pointer(pointer)
+        .and_then(Value::as_array)
+        .map(|items| items.iter()
+            .filter_map(|item| item.pointer(field).and_then(Value::as_str).map(str::to_string))
+            .collect())
+        .unwrap_or_default()
+}
+
+/// X (Twitter) recent search connector
+pub struct TwitterConnector {
+    /// Source name
+    name: String,
+
+    /// Bearer token
+    bearer_token: String,
+
+    /// Posts read per request
+    max_results: u32,
+
+    /// Post scorer
+    scorer: KeywordScorer,
+
+    /// HTTP client
+    client: Client,
+}
+
+#[async_trait]
+impl SentimentConnector for TwitterConnector {
+    fn get_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn source(&self) -> SentimentSource {
+        SentimentSource::SocialMedia(self.name.clone())
+    }
+
+    async fn fetch(&self, _symbol: &str, terms: &[String]) -> Result<Option<f64>> {
+        let query = format!("({}) lang:en -is:retweet", terms.join(" OR "));
+        let body: Value = self.client.get("https://api.twitter.com/2/tweets/search/recent")
+            .bearer_auth(&self.bearer_token)
+            .query(&[("query", query), ("max_results", self.max_results.clamp(10, 100).to_string())])
+            .send().await?
+            .error_for_status()?
+            .json().await?;
+        Ok(self.scorer.score(&texts_at(&body, "/data", "/text")))
+    }
+}
+
+/// Reddit subreddit search connector
+pub struct RedditConnector {
+    /// Source name
+    name: String,
+
+    /// Subreddits searched
+    subreddits: Vec<String>,
+
+    /// User agent
+    user_agent: String,
+
+    /// Posts read per subreddit
+    max_results: u32,
+
+    /// Post scorer
+    scorer: KeywordScorer,
+
+    /// HTTP client
+    client: Client,
+}
+
+#[async_trait]
+impl SentimentConnector for RedditConnector {
+    fn get_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn source(&self) -> SentimentSource {
+        SentimentSource::SocialMedia(self.name.clone())
+    }
+
+    async fn fetch(&self, _symbol: &str, terms: &[String]) -> Result<Option<f64>> {
+        let query = terms.join(" OR ");
+        let mut texts = Vec::new();
+        for subreddit in &self.subreddits {
+            let body: Value = self.client.get(format!("https://www.reddit.com/r/{}/search.json", subreddit))
+                .header("User-Agent", self.user_agent.as_str())
+                .query(&[
+                    ("q", query.clone()),
+                    ("restrict_sr", "1".to_string()),
+                    ("sort", "new".to_string()),
+                    ("limit", self.max_results.to_string()),
+                ])
+                .send().await?
+                .error_for_status()?
+                .json().await?;
+            let posts = body.pointer("/data/children").and_then(Value::as_array).cloned().unwrap_or_default();
+            texts.extend(posts.iter().map(|post| {
+                let field = |name: &str| post.pointer(&format!("/data/{}", name)).and_then(Value::as_str).unwrap_or("");
+                format!("{} {}", field("title"), field("selftext"))
+            }));
+        }
+        Ok(self.scorer.score(&texts))
+    }
+}
+
+/// JSON headline API connector
+pub struct NewsHeadlineConnector {
+    /// Source name
+    name: String,
+
+    /// Endpoint URL template
+    url: String,
+
+    /// JSON pointer of the item array
+    items_pointer: String,
+
+    /// Headline field
+    title_field: String,
+
+    /// API key
+    api_key: Option<String>,
+
+    /// Header carrying the API key
+    api_key_header: String,
+
+    /// Headline scorer
+    scorer: KeywordScorer,
+
+    /// HTTP client
+    client: Client,
+}
+
+#[async_trait]
+impl SentimentConnector for NewsHeadlineConnector {
+    fn get_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn source(&self) -> SentimentSource {
+        SentimentSource::News(self.name.clone())
+    }
+
+    async fn fetch(&self, _symbol: &str, terms: &[String]) -> Result<Option<f64>> {
+        let mut request = self.client.get(self.url.replace("{query}", &terms.join(",")));
+        if let Some(api_key) = &self.api_key {
+            request = request.header(self.api_key_header.as_str(), api_key);
+        }
+        let body: Value = request.send().await?.error_for_status()?.json().await?;
+        let field = format!("/{}", self.title_field.trim_start_matches('/'));
+        Ok(self.scorer.score(&texts_at(&body, &self.items_pointer, &field)))
+    }
+}
+
+/// Crypto Fear & Greed index connector
+pub struct FearGreedConnector {
+    /// Source name
+    name: String,
+
+    /// Endpoint URL
+    url: String,
+
+    /// HTTP client
+    client: Client,
+}
+
+#[async_trait]
+impl SentimentConnector for FearGreedConnector {
+    fn get_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn source(&self) -> SentimentSource {
+        SentimentSource::Other(self.name.clone())
+    }
+
+    fn is_market_wide(&self) -> bool {
+        true
+    }
+
+    async fn fetch(&self, _symbol: &str, _terms: &[String]) -> Result<Option<f64>> {
+        let body: Value = self.client.get(&self.url).send().await?.error_for_status()?.json().await?;
+        // The index runs from 0 (extreme fear) to 100 (extreme greed), sent as a string
+        let value = body.pointer("/data/0/value")
+            .and_then(|v| v.as_str().and_then(|s| s.parse::<f64>().ok()).or_else(|| v.as_f64()))
+            .ok_or_else(|| anyhow::anyhow!("{} returned no index value", self.name))?;
+        Ok(Some((value - 50.0) * 2.0))
+    }
+}
+
+/// Build the connector of a source
+pub fn build_connector(config: &SentimentSourceConfig, scorer: KeywordScorer, timeout_secs: u64) -> Box<dyn SentimentConnector> {
+    let client = Client::builder()
+        .timeout(Duration::from_secs(timeout_secs.max(1)))
+        .build()
+        .unwrap_or_default();
+    let name = config.name.clone();
+
+    match &config.kind {
+        SentimentConnectorKind::Twitter { bearer_token, max_results } => Box::new(TwitterConnector {
+            name,
+            bearer_token: bearer_token.clone(),
+            max_results: *max_results,
+            scorer,
+            client,
+        }),
+        SentimentConnectorKind::Reddit { subreddits, user_agent, max_results } => Box::new(RedditConnector {
+            name,
+            subreddits: subreddits.clone(),
+            user_agent: user_agent.clone(),
+            max_results: *max_results,
+            scorer,
+            client,
+        }),
+        SentimentConnectorKind::NewsHeadlines { url, items_pointer, title_field, api_key, api_key_header } => {
+            Box::new(NewsHeadlineConnector {
+                name,
+                url: url.clone(),
+                items_pointer: items_pointer.clone(),
+                title_field: title_field.clone(),
+                api_key: api_key.clone(),
+                api_key_header: api_key_header.clone(),
+                scorer,
+                client,
+            })
+        },
+        SentimentConnectorKind::FearGreed { url } => Box::new(FearGreedConnector { name, url: url.clone(), client }),
+    }
+}
@@ -3,7 +3,8 @@
 //! This agent is responsible for executing trades based on decisions from other agents.
 
 use std::collections::HashMap;
-use chrono::{DateTime, Utc};
+use std::sync::Arc;
+use chrono::{DateTime, TimeZone, Utc};
 use serde::{Deserialize, Serialize};
 use anyhow::Result;
 use tracing::{info, debug, error};
@@ -13,6 +14,10 @@ use crate::exchange::bybit::adapter::BybitAdapter;
 use crate::exchange::bybit::types::{OrderSide, OrderType, TimeInForce, OrderStatus, PositionSide};
 use crate::exchange::position::Position;
 use crate::agents::risk_manager::RiskAssessment;
+use crate::exchange::bybit::rate_limiter::{RateLimiter, SharedRateLimiter};
+use crate::execution::order_manager::{lock_order_manager, shared_order_manager, ExecutionReport, OrderManager, SharedOrderManager};
+use crate::execution::order_router::{OrderRouter, RoutingInputs, Urgency};
+use crate::monitoring::execution_quality::ExecutionQualityMonitor;
 
 /// Trade execution result
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -77,6 +82,21 @@ pub struct TradeExecutor {
 
     /// Active orders
     active_orders: HashMap<String, String>, // symbol -> order_id
+
+    /// Chooses Market / Limit / PostOnly for entries
+    router: OrderRouter,
+
+    /// Latest short-term volatility by symbol (fraction of price)
+    volatility: HashMap<String, f64>,
+
+    /// Intended vs filled price and latency of submitted orders
+    execution_quality: ExecutionQualityMonitor,
+
+    /// Order lifecycle and entry throttling, shared with the other executors on the account
+    order_manager: SharedOrderManager,
+
+    /// Private API rate limiter, shared with the other executors on the account
+    order_limiter: SharedRateLimiter,
 }
 
 impl TradeExecutor {
@@ -85,9 +105,39 @@ impl TradeExecutor {
         Self {
             execution_cache: HashMap::new(),
             active_orders: HashMap::new(),
+            router: OrderRouter::default(),
+            volatility: HashMap::new(),
+            execution_quality: ExecutionQualityMonitor::default(),
+            order_manager: shared_order_manager(OrderManager::new()),
+            order_limiter: Arc::new(tokio::sync::Mutex::new(RateLimiter::bybit_private())),
         }
     }
 
+    /// Get the order manager, to share with other executors on the account
+    pub fn get_order_manager(&self) -> SharedOrderManager {
+        Arc::clone(&self.order_manager)
+    }
+
+    /// Get the private API rate limiter, to share with other executors on the account
+    pub fn get_order_limiter(&self) -> SharedRateLimiter {
+        Arc::clone(&self.order_limiter)
+    }
+
+    /// Get the execution quality monitor
+    pub fn get_execution_quality(&self) -> &ExecutionQualityMonitor {
+        &self.execution_quality
+    }
+
+    /// Replace the order router
+    pub fn set_router(&mut self, router: OrderRouter) {
+        self.router = router;
+    }
+
+    /// Update the short-term volatility used for routing
+    pub fn update_volatility(&mut self, symbol: &str, volatility: f64) {
+        self.volatility.insert(symbol.to_string(), volatility);
+    }
+
     /// Execute a trade
     pub async fn execute_trade(
         &mut self,
@@ -96,6 +146,7 @@ impl TradeExecutor {
         direction: TradeDirection,
         risk_assessment: &RiskAssessment,
         current_price: f64,
+        strategy: &str,
     ) -> Result<TradeExecution> {
         debug!("Executing trade for {} ({:?})", symbol, direction);
 
@@ -146,22 +197,82 @@ impl TradeExecutor {
         // }
         debug!("Using leverage {}x for {}", leverage, symbol);
 
+        // Route the order from the top of book, falling back to the last price
+        let (best_bid, best_ask) = match adapter.get_orderbook(symbol, 1).await {
+            Ok(book) => (
+                book.bids.first().map(|(price, _)| *price).unwrap_or(current_price),
+                book.asks.first().map(|(price, _)| *price).unwrap_or(current_price),
+            ),
+            Err(e) => {
+                debug!("No orderbook for {} ({}), routing on last price", symbol, e);
+                (current_price, current_price)
+            }
+        };
+        // Entries are as urgent as the market is fast; without a volatility reading they are routine
+        let volatility = self.volatility.get(symbol).copied();
+        let route = self.router.route(side, &RoutingInputs {
+            best_bid,
+            best_ask,
+            volatility: volatility.unwrap_or(0.0),
+            urgency: volatility.map_or(Urgency::Normal, |v| self.router.urgency_for(v)),
+        });
+
+        // Refuse duplicate entries while the previous one is unacknowledged or cooling down
+        let submitted_at = Utc::now();
+        let (local_order_id, order_link_id) = {
+            let mut order_manager = lock_order_manager(&self.order_manager)?;
+            let local_order_id = order_manager.create_entry_order(
+                symbol,
+                side,
+                route.order_type(),
+                quantity,
+                route.price,
+                submitted_at,
+            )?;
+            let order_link_id = order_manager.get_order(&local_order_id).map(|o| o.order_link_id.clone());
+            (local_order_id, order_link_id)
+        };
+
         // Place the order
-        let order_result = adapter.place_order(
+        self.order_limiter.lock().await.wait_if_needed().await?;
+        let order_result = adapter.place_linked_order(
             symbol,
             side,
-            OrderType::Market,
+            route.order_type(),
             quantity,
-            Some(current_price),
-            TimeInForce::GoodTillCancel,
+            route.price,
+            route.time_in_force(),
             false,  // reduce_only
             false,  // close_on_trigger
             None,   // take_profit
             None,   // stop_loss
+            order_link_id.as_deref(),
         ).await;
 
         match order_result {
             Ok(order) => {
+                let _ = lock_order_manager(&self.order_manager)?.apply_report(&local_order_id, ExecutionReport::Submitted {
+                    exchange_order_id: order.order_id.clone(),
+                });
+
+                // Track execution quality against the decision price
+                self.execution_quality.record_submission(
+                    &order.order_id,
+                    symbol,
+                    strategy,
+                    side == OrderSide::Buy,
+                    current_price,
+                    submitted_at,
+                );
+                if order.cum_exec_qty > 0.0 && order.order_status == OrderStatus::Filled {
+                    self.execution_quality.record_fill(
+                        &order.order_id,
+                        order.cum_exec_value / order.cum_exec_qty,
+                        order.cum_exec_qty,
+                        Utc::now(),
+                    );
+                }
+
                 // Create execution result
                 let execution = TradeExecution {
                     symbol: symbol.to_string(),
@@ -190,6 +301,7 @@ impl TradeExecutor {
             },
             Err(e) => {
                 error!("Failed to place order for {}: {}", symbol, e);
+                let _ = lock_order_manager(&self.order_manager)?.apply_report(&local_order_id, ExecutionReport::Rejected { reason: e.to_string() });
 
                 // Create failed execution result
                 let execution = TradeExecution {
@@ -227,6 +339,38 @@ impl TradeExecutor {
                         execution.status = order.order_status.clone();
                     }
 
+                    // Record the fill for execution quality analysis
+                    if order.order_status == OrderStatus::Filled && order.cum_exec_qty > 0.0 {
+                        let filled_at = order.updated_time.parse::<i64>().ok()
+                            .and_then(|ms| Utc.timestamp_millis_opt(ms).single())
+                            .unwrap_or_else(Utc::now);
+                        self.execution_quality.record_fill(
+                            &order.order_id,
+                            order.cum_exec_value / order.cum_exec_qty,
+                            order.cum_exec_qty,
+                            filled_at,
+                        );
+                    }
+
+                    // Advance the managed order
+                    let mut order_manager = lock_order_manager(&self.order_manager)?;
+                    let report = match &order.order_status {
+                        OrderStatus::Filled => order_manager.get_order_by_exchange_id(&order.order_id)
+                            .and_then(|managed| managed.incremental_fill(order.cum_exec_qty, order.cum_exec_value)),
+                        status @ (OrderStatus::Cancelled | OrderStatus::Rejected) =>
+                            ExecutionReport::from_exchange_status(status.clone(), &order.order_id),
+                        _ => None,
+                    };
+                    if let Some(report) = report {
+                        let _ = order_manager.apply_exchange_report(&order.order_id, report);
+                    }
+                    drop(order_manager);
+
+                    // An order that ends without filling will never produce a sample
+                    if matches!(order.order_status, OrderStatus::Cancelled | OrderStatus::Rejected) {
+                        self.execution_quality.record_cancel(&order.order_id);
+                    }
+
                     // Remove from active orders if completed
                     if matches!(order.order_status, OrderStatus::Filled | OrderStatus::Cancelled) {
                         self.active_orders.remove(symbol);
@@ -258,6 +402,7 @@ impl TradeExecutor {
                     if let Some(execution) = self.execution_cache.get_mut(symbol) {
                         execution.status = OrderStatus::Cancelled;
                     }
+                    self.execution_quality.record_cancel(order_id);
 
                     // Remove from active orders
                     self.active_orders.remove(symbol);
@@ -0,0 +1,340 @@
+//! Whale Alert Agent
+//!
+//! This agent watches the trade tape and the orderbook of each tracked symbol
+//! for whale activity: single trades above a notional threshold and resting
+//! walls above a larger threshold close to the mid price. Each sighting is
+//! published once as a structured `WhaleAlert`; the risk manager then pauses
+//! entries on the symbol for a while and open trades have their stops tightened.
+
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::Arc;
+use chrono::{DateTime, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use anyhow::Result;
+use async_trait::async_trait;
+use tokio::sync::RwLock;
+use tracing::{info, warn};
+
+use crate::engine::agent_trait::{Agent, AgentConfig, AgentContext};
+use crate::engine::message_bus::{BusMessage, Message, MessageBus};
+use crate::exchange::bybit::adapter::BybitAdapter;
+use crate::exchange::bybit::types::{BybitOrderbook, BybitPublicTrade};
+
+/// Topic used for whale alerts (`Message::Custom`)
+pub const WHALE_ALERT_TOPIC: &str = "whale_alert";
+
+/// Whale agent settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WhaleAgentConfig {
+    /// Symbols tracked
+    pub symbols: Vec<String>,
+
+    /// Product category of the tape
+    pub category: String,
+
+    /// Smallest trade reported (quote currency)
+    pub min_trade_notional: f64,
+
+    /// Smallest resting order level reported (quote currency)
+    pub min_wall_notional: f64,
+
+    /// Walls further than this from the mid price are ignored (%)
+    pub wall_distance_pct: f64,
+
+    /// Orderbook levels requested per side
+    pub orderbook_depth: u32,
+
+    /// Trades requested per poll
+    pub trades_per_poll: u32,
+}
+
+impl Default for WhaleAgentConfig {
+    fn default() -> Self {
+        Self {
+            symbols: vec!["BTCUSDT".to_string(), "ETHUSDT".to_string()],
+            category: "linear".to_string(),
+            min_trade_notional: 1_000_000.0,
+            min_wall_notional: 5_000_000.0,
+            wall_distance_pct: 1.0,
+            orderbook_depth: 50,
+            trades_per_poll: 500,
+        }
+    }
+}
+
+/// Kind of whale activity
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum WhaleActivity {
+    /// Single large trade; the side is the taker's
+    LargeTrade,
+
+    /// Large resting bid
+    BidWall,
+
+    /// Large resting ask
+    AskWall,
+}
+
+/// Whale sighting
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WhaleAlert {
+    /// Symbol
+    pub symbol: String,
+
+    /// Time of the trade or orderbook snapshot
+    pub timestamp: DateTime<Utc>,
+
+    /// Kind of activity
+    pub activity: WhaleActivity,
+
+    /// Buying ("Buy") or selling ("Sell") pressure
+    pub side: String,
+
+    /// Price
+    pub price: f64,
+
+    /// Quantity
+    pub quantity: f64,
+
+    /// Price times quantity
+    pub notional: f64,
+}
+
+impl WhaleAlert {
+    /// Convert to a bus message
+    pub fn to_message(&self) -> Message {
+        Message::Custom(
+            WHALE_ALERT_TOPIC.to_string(),
+            serde_json::to_value(self).unwrap_or(Value::Null),
+        )
+    }
+
+    /// Parse a whale alert from a message, if it is one
+    pub fn from_message(message: &Message) -> Option<Self> {
+        match message {
+            Message::Custom(topic, payload) if topic == WHALE_ALERT_TOPIC => {
+                match serde_json::from_value(payload.clone()) {
+                    Ok(alert) => Some(alert),
+                    Err(e) => {
+                        warn!("Ignoring malformed whale alert: {}", e);
+                        None
+                    }
+                }
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Whale alert agent
+pub struct WhaleAgent {
+    /// Settings
+    config: WhaleAgentConfig,
+
+    /// Exchange adapter
+    exchange: Arc<BybitAdapter>,
+
+    /// Message bus alerts are published on
+    message_bus: Arc<MessageBus>,
+
+    /// Whether the agent is running
+    running: bool,
+
+    /// Execution IDs of large trades already reported
+    seen_trades: HashSet<String>,
+
+    /// Same IDs, oldest first, to bound `seen_trades`
+    seen_order: VecDeque<String>,
+
+    /// Walls standing at the last snapshot, by symbol, as (activity, price bits)
+    walls: HashMap<String, HashSet<(WhaleActivity, u64)>>,
+}
+
+impl WhaleAgent {
+    /// Create a new whale agent
+    pub fn new(config: WhaleAgentConfig, exchange: Arc<BybitAdapter>, message_bus: Arc<MessageBus>) -> Self {
+        Self {
+            config,
+            exchange,
+            message_bus,
+            running: false,
+            seen_trades: HashSet::new(),
+            seen_order: VecDeque::new(),
+            walls: HashMap::new(),
+        }
+    }
+
+    /// Large trades of the tape not reported before
+    pub fn detect_large_trades(&mut self, symbol: &str, trades: &[BybitPublicTrade]) -> Vec<WhaleAlert> {
+        let mut alerts = Vec::new();
+        for trade in trades {
+            let notional = trade.price * trade.size;
+            if notional < self.config.min_trade_notional || !self.seen_trades.insert(trade.exec_id.clone()) {
+                continue;
+            }
+            self.seen_order.push_back(trade.exec_id.clone());
+            alerts.push(WhaleAlert {
+                symbol: symbol.to_string(),
+                timestamp: Utc.timestamp_millis_opt(trade.time).single().unwrap_or_else(Utc::now),
+                activity: WhaleActivity::LargeTrade,
+                side: trade.side.clone(),
+                price: trade.price,
+                quantity: trade.size,
+                notional,
+            });
+        }
+
+        while self.seen_order.len() > self.config.trades_per_poll.max(1) as usize * 2 {
+            if let Some(id) = self.seen_order.pop_front() {
+                self.seen_trades.remove(&id);
+            }
+        }
+        alerts
+    }
+
+    /// Walls near the mid price that were not standing at the last snapshot
+    pub fn detect_walls(&mut self, symbol: &str, orderbook: &BybitOrderbook, now: DateTime<Utc>) -> Vec<WhaleAlert> {
+        let (Some(best_bid), Some(best_ask)) = (orderbook.bids.first(), orderbook.asks.first()) else {
+            return Vec::new();
+        };
+        let mid = (best_bid.0 + best_ask.0) / 2.0;
+        if mid <= 0.0 {
+            return Vec::new();
+        }
+
+        let levels = orderbook.bids.iter().map(|level| (WhaleActivity::BidWall, "Buy", level))
+            .chain(orderbook.asks.iter().map(|level| (WhaleActivity::AskWall, "Sell", level)));
+        let mut standing = HashSet::new();
+        let mut alerts = Vec::new();
+        let previous = self.walls.remove(symbol).unwrap_or_default();
+
+        for (activity, side, &(price, quantity)) in levels {
+            let notional = price * quantity;
+            if notional < self.config.min_wall_notional
+                || (price - mid).abs() / mid * 100.0 > self.config.wall_distance_pct {
+                continue;
+            }
+            let key = (activity, price.to_bits());
+            standing.insert(key);
+            if previous.contains(&key) {
+                continue;
+            }
+            alerts.push(WhaleAlert {
+                symbol: symbol.to_string(),
+                timestamp: now,
+                activity,
+                side: side.to_string(),
+                price,
+                quantity,
+                notional,
+            });
+        }
+
+        self.walls.insert(symbol.to_string(), standing);
+        alerts
+    }
+}
+
+#[async_trait]
+impl Agent for WhaleAgent {
+    fn get_name(&self) -> &str {
+        "WhaleAgent"
+    }
+
+    fn get_config(&self) -> Box<dyn AgentConfig> {
+        Box::new(self.config.clone())
+    }
+
+    async fn initialize(&mut self, _context: Arc<RwLock<AgentContext>>) -> Result<()> {
+        info!("Initializing Whale Agent with {} symbols", self.config.symbols.len());
+        Ok(())
+    }
+
+    async fn start(&mut self) -> Result<()> {
+        self.running = true;
+        Ok(())
+    }
+
+    async fn stop(&mut self) -> Result<()> {
+        self.running = false;
+        Ok(())
+    }
+
+    async fn tick(&mut self) -> Result<()> {
+        if !self.running {
+            return Ok(());
+        }
+
+        for symbol in self.config.symbols.clone() {
+            let mut alerts = Vec::new();
+            match self.exchange.get_recent_trades(&symbol, &self.config.category, self.config.trades_per_poll).await {
+                Ok(trades) => alerts.extend(self.detect_large_trades(&symbol, &trades)),
+                Err(e) => warn!("Failed to read the trade tape of {}: {}", symbol, e),
+            }
+            match self.exchange.get_orderbook(&symbol, self.config.orderbook_depth).await {
+                Ok(orderbook) => alerts.extend(self.detect_walls(&symbol, &orderbook, Utc::now())),
+                Err(e) => warn!("Failed to read the orderbook of {}: {}", symbol, e),
+            }
+
+            for alert in alerts {
+                info!("Whale on {}: {:?} {} {:.4} @ {:.2} (${:.0})",
+                      alert.symbol, alert.activity, alert.side, alert.quantity, alert.price, alert.notional);
+                self.message_bus.send(alert.to_message());
+            }
+        }
+        Ok(())
+    }
+
+    async fn handle_message(&mut self, _message: BusMessage) -> Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_large_trades_and_new_walls_are_reported_once() {
+        let exchange = Arc::new(BybitAdapter::new("", "", true));
+        let mut agent = WhaleAgent::new(WhaleAgentConfig::default(), exchange, Arc::new(MessageBus::new()));
+
+        let trade = |id: &str, size: f64| BybitPublicTrade {
+            exec_id: id.to_string(),
+            symbol: "BTCUSDT".to_string(),
+            price: 50_000.0,
+            size,
+            side: "Sell".to_string(),
+            time: 1_700_000_000_000,
+        };
+        let tape = vec![trade("1", 30.0), trade("2", 1.0)];
+        let alerts = agent.detect_large_trades("BTCUSDT", &tape);
+        assert_eq!(alerts.len(), 1);
+        assert_eq!(alerts[0].notional, 1_500_000.0);
+        assert!(agent.detect_large_trades("BTCUSDT", &tape).is_empty());
+
+        let now = Utc::now();
+        let mut book = BybitOrderbook {
+            symbol: "BTCUSDT".to_string(),
+            timestamp: 0,
+            bids: vec![(49_990.0, 1.0), (49_900.0, 200.0), (45_000.0, 500.0)],
+            asks: vec![(50_010.0, 1.0)],
+        };
+        let alerts = agent.detect_walls("BTCUSDT", &book, now);
+        assert_eq!(alerts.len(), 1);
+        assert_eq!(alerts[0].activity, WhaleActivity::BidWall);
+        assert_eq!(alerts[0].price, 49_900.0);
+        assert!(agent.detect_walls("BTCUSDT", &book, now).is_empty());
+
+        // A wall that is pulled and placed again is a new sighting
+        book.bids.remove(1);
+        assert!(agent.detect_walls("BTCUSDT", &book, now).is_empty());
+        book.bids.insert(1, (49_900.0, 200.0));
+        assert_eq!(agent.detect_walls("BTCUSDT", &book, now).len(), 1);
+
+        let parsed = WhaleAlert::from_message(&alerts[0].to_message()).unwrap();
+        assert_eq!(parsed.side, "Buy");
+    }
+}
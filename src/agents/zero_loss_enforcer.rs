@@ -1,6 +1,10 @@
 //! Zero-Loss Enforcer Agent
 //!
 //! This agent is responsible for ensuring that no trades result in losses.
+//! Besides scoring setups before entry, it guards every open trade: as the price
+//! moves against a trade it pulls the stop in to cap the loss, reduces the
+//! position and finally triggers a hedge. Each protective action and every vetoed
+//! order is written to the audit trail.
 
 use std::collections::HashMap;
 use std::sync::Arc;
@@ -13,10 +17,19 @@ use crate::agents::market_analyzer::MarketAnalysis;
 use crate::agents::sentiment_analyzer::SentimentAnalysis;
 use crate::agents::risk_manager::RiskAssessment;
 use crate::agents::trade_executor::TradeExecution;
-use crate::engine::message_bus::{MessageBus, TradeDirection, BusMessage};
+use crate::engine::message_bus::{Message, MessageBus, TradeDirection, BusMessage};
+use crate::risk::audit::AuditTrail;
+use crate::risk::stress::StressTestResult;
+
+/// Message bus topic protective actions are published on
+pub const ZERO_LOSS_ACTION_TOPIC: &str = "zero_loss_action";
+
+/// Audit category of the enforcer's decisions
+const AUDIT_CATEGORY: &str = "zero_loss";
 
 /// Configuration for Zero-Loss Enforcer
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct ZeroLossEnforcerConfig {
     /// Maximum allowed loss percentage
     pub max_loss_percent: f64,
@@ -24,6 +37,14 @@ pub struct ZeroLossEnforcerConfig {
     pub min_risk_reward_ratio: f64,
     /// Enable emergency stop
     pub emergency_stop_enabled: bool,
+    /// Adverse move (% of entry) at which the stop is pulled in to `max_loss_percent`
+    pub tighten_at_percent: f64,
+    /// Adverse move (% of entry) at which the position is reduced
+    pub reduce_at_percent: f64,
+    /// Share of the position closed when reducing (0.0 to 1.0)
+    pub reduce_fraction: f64,
+    /// Adverse move (% of entry) at which a hedge is triggered
+    pub hedge_at_percent: f64,
 }
 
 impl Default for ZeroLossEnforcerConfig {
@@ -32,10 +53,105 @@ impl Default for ZeroLossEnforcerConfig {
             max_loss_percent: 0.5,
             min_risk_reward_ratio: 2.0,
             emergency_stop_enabled: true,
+            tighten_at_percent: 0.25,
+            reduce_at_percent: 0.35,
+            reduce_fraction: 0.5,
+            hedge_at_percent: 0.45,
         }
     }
 }
 
+/// Protective action the enforcer takes on an open trade
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum ProtectiveAction {
+    /// Move the stop closer to the price
+    TightenStop {
+        /// Trade ID
+        trade_id: String,
+        /// Symbol
+        symbol: String,
+        /// New stop price
+        new_stop: f64,
+    },
+
+    /// Close part of the position
+    ReduceSize {
+        /// Trade ID
+        trade_id: String,
+        /// Symbol
+        symbol: String,
+        /// Share of the position to close (0.0 to 1.0)
+        fraction: f64,
+    },
+
+    /// Hedge the remaining position
+    Hedge {
+        /// Trade ID
+        trade_id: String,
+        /// Symbol
+        symbol: String,
+        /// Direction of the trade being hedged
+        direction: TradeDirection,
+        /// Size of the trade being hedged
+        position_size: f64,
+        /// Entry price of the trade being hedged
+        entry_price: f64,
+    },
+}
+
+impl ProtectiveAction {
+    /// Audit action name
+    fn name(&self) -> &'static str {
+        match self {
+            ProtectiveAction::TightenStop { .. } => "stop_tightened",
+            ProtectiveAction::ReduceSize { .. } => "size_reduced",
+            ProtectiveAction::Hedge { .. } => "hedge_triggered",
+        }
+    }
+
+    /// Trade the action applies to
+    pub fn trade_id(&self) -> &str {
+        match self {
+            ProtectiveAction::TightenStop { trade_id, .. }
+            | ProtectiveAction::ReduceSize { trade_id, .. }
+            | ProtectiveAction::Hedge { trade_id, .. } => trade_id,
+        }
+    }
+
+    /// Convert the action into a message bus message
+    pub fn to_message(&self) -> Message {
+        Message::Custom(
+            ZERO_LOSS_ACTION_TOPIC.to_string(),
+            serde_json::to_value(self).unwrap_or(serde_json::Value::Null),
+        )
+    }
+}
+
+/// Open trade guarded by the enforcer
+#[derive(Debug, Clone)]
+struct GuardedTrade {
+    /// Symbol
+    symbol: String,
+
+    /// Direction
+    direction: TradeDirection,
+
+    /// Entry price
+    entry_price: f64,
+
+    /// Current stop
+    stop_loss_price: f64,
+
+    /// Current size
+    size: f64,
+
+    /// Whether the position was already reduced
+    reduced: bool,
+
+    /// Whether a hedge was already triggered
+    hedged: bool,
+}
+
 /// Zero-loss enforcement result
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ZeroLossAssessment {
@@ -98,6 +214,18 @@ pub struct ZeroLossEnforcer {
 
     /// Assessment cache
     assessment_cache: HashMap<String, ZeroLossAssessment>,
+
+    /// Latest stress test; new trades are refused while it is a no-go
+    stress_result: Option<StressTestResult>,
+
+    /// Protective action settings
+    config: ZeroLossEnforcerConfig,
+
+    /// Open trades by ID
+    open_trades: HashMap<String, GuardedTrade>,
+
+    /// Audit trail of protective actions and vetoes
+    audit_trail: AuditTrail,
 }
 
 impl ZeroLossEnforcer {
@@ -108,6 +236,10 @@ impl ZeroLossEnforcer {
             min_risk_reward_ratio: 10.0, // Minimum 10:1 risk-reward ratio
             min_expected_value: 1.0, // Minimum $1 expected value
             assessment_cache: HashMap::new(),
+            stress_result: None,
+            config: ZeroLossEnforcerConfig::default(),
+            open_trades: HashMap::new(),
+            audit_trail: AuditTrail::default(),
         }
     }
 
@@ -122,25 +254,120 @@ impl ZeroLossEnforcer {
         Ok(Vec::new())
     }
 
-    /// Close a trade
+    /// Stop guarding a closed trade
     pub fn close_trade(&mut self, trade_id: &str, exit_price: f64, exit_time: DateTime<Utc>) -> Result<()> {
-        // This is a simplified implementation
         debug!("Closing trade {}: exit price {}, exit time {}", trade_id, exit_price, exit_time);
+        self.open_trades.remove(trade_id);
         Ok(())
     }
 
-    /// Register a trade
+    /// Start guarding an open trade
     pub fn register_trade(&mut self, trade: crate::trading_system::Trade) -> Result<()> {
-        // This is a simplified implementation
         debug!("Registering trade {}: entry price {}", trade.id, trade.entry_price);
+        if trade.entry_price <= 0.0 {
+            return Err(anyhow::anyhow!("Invalid entry price for trade {}: {}", trade.id, trade.entry_price));
+        }
+
+        self.open_trades.insert(trade.id.clone(), GuardedTrade {
+            symbol: trade.symbol,
+            direction: trade.direction,
+            entry_price: trade.entry_price,
+            stop_loss_price: trade.stop_loss_price,
+            size: trade.size,
+            reduced: false,
+            hedged: false,
+        });
         Ok(())
     }
 
-    /// Update a trade
-    pub fn update_trade(&mut self, trade_id: &str, current_price: f64) -> Result<()> {
-        // This is a simplified implementation
+    /// Record a stop moved by someone else, e.g. the break-even rule
+    pub fn sync_stop(&mut self, trade_id: &str, stop_loss_price: f64) {
+        if let Some(trade) = self.open_trades.get_mut(trade_id) {
+            trade.stop_loss_price = stop_loss_price;
+        }
+    }
+
+    /// Record the size actually left after a reduction, which may have filled partly or not at all
+    pub fn sync_size(&mut self, trade_id: &str, size: f64) {
+        if let Some(trade) = self.open_trades.get_mut(trade_id) {
+            trade.size = size;
+        }
+    }
+
+    /// Update a trade with the current price; returns the protective actions to take
+    ///
+    /// Past `tighten_at_percent` of adverse move the stop is pulled in so the loss
+    /// cannot exceed `max_loss_percent`; past `reduce_at_percent` the position is cut
+    /// by `reduce_fraction`, and past `hedge_at_percent` the remainder is hedged.
+    /// Reductions and hedges happen at most once per trade.
+    pub fn update_trade(&mut self, trade_id: &str, current_price: f64) -> Result<Vec<ProtectiveAction>> {
         debug!("Updating trade {}: current price {}", trade_id, current_price);
-        Ok(())
+        let Some(trade) = self.open_trades.get_mut(trade_id) else {
+            return Ok(Vec::new());
+        };
+
+        let (adverse_percent, cap) = match trade.direction {
+            TradeDirection::Long => (
+                (trade.entry_price - current_price) / trade.entry_price * 100.0,
+                trade.entry_price * (1.0 - self.config.max_loss_percent / 100.0),
+            ),
+            TradeDirection::Short => (
+                (current_price - trade.entry_price) / trade.entry_price * 100.0,
+                trade.entry_price * (1.0 + self.config.max_loss_percent / 100.0),
+            ),
+            TradeDirection::Neutral => return Ok(Vec::new()),
+        };
+
+        let mut actions = Vec::new();
+        if adverse_percent >= self.config.tighten_at_percent {
+            // Only ever move the stop closer, and never through the price
+            let tighter = match trade.direction {
+                TradeDirection::Long => cap > trade.stop_loss_price && cap < current_price,
+                _ => cap < trade.stop_loss_price && cap > current_price,
+            };
+            if tighter {
+                trade.stop_loss_price = cap;
+                actions.push(ProtectiveAction::TightenStop {
+                    trade_id: trade_id.to_string(),
+                    symbol: trade.symbol.clone(),
+                    new_stop: cap,
+                });
+            }
+        }
+
+        if adverse_percent >= self.config.reduce_at_percent && !trade.reduced && self.config.reduce_fraction > 0.0 {
+            let fraction = self.config.reduce_fraction.min(1.0);
+            trade.reduced = true;
+            trade.size *= 1.0 - fraction;
+            actions.push(ProtectiveAction::ReduceSize {
+                trade_id: trade_id.to_string(),
+                symbol: trade.symbol.clone(),
+                fraction,
+            });
+        }
+
+        if adverse_percent >= self.config.hedge_at_percent && !trade.hedged && trade.size > 0.0 {
+            trade.hedged = true;
+            actions.push(ProtectiveAction::Hedge {
+                trade_id: trade_id.to_string(),
+                symbol: trade.symbol.clone(),
+                direction: trade.direction.clone(),
+                position_size: trade.size,
+                entry_price: trade.entry_price,
+            });
+        }
+
+        for action in &actions {
+            warn!("Zero-loss enforcer: {} on trade {} ({:.2}% against)", action.name(), trade_id, adverse_percent);
+            let mut details = serde_json::to_value(action).unwrap_or(serde_json::Value::Null);
+            if let Some(details) = details.as_object_mut().and_then(|d| d.values_mut().next()).and_then(|d| d.as_object_mut()) {
+                details.insert("price".to_string(), current_price.into());
+                details.insert("adverse_percent".to_string(), adverse_percent.into());
+            }
+            self.audit_trail.record(AUDIT_CATEGORY, action.name(), details);
+        }
+
+        Ok(actions)
     }
 
     /// Initialize the agent
@@ -277,6 +504,82 @@ impl ZeroLossEnforcer {
         probability.max(0.0).min(100.0)
     }
 
+    /// Pre-approve a setup produced by a strategy with explicit stop and target
+    ///
+    /// Applies the same win probability, risk-reward and expected value thresholds as
+    /// `assess_trade`, for strategies that compute their own stops (e.g. ATR based)
+    /// and win probability instead of relying on the agent analyses.
+    pub fn pre_approve_setup(
+        &mut self,
+        symbol: &str,
+        direction: TradeDirection,
+        entry_price: f64,
+        stop_loss_price: f64,
+        take_profit_price: f64,
+        position_size: f64,
+        win_probability: f64,
+    ) -> Result<ZeroLossAssessment> {
+        debug!("Pre-approving {:?} setup for {}: entry {}, stop {}, target {}",
+               direction, symbol, entry_price, stop_loss_price, take_profit_price);
+
+        if entry_price <= 0.0 {
+            return Err(anyhow::anyhow!("Invalid entry price for {}: {}", symbol, entry_price));
+        }
+
+        let (risk_per_unit, reward_per_unit) = match direction {
+            TradeDirection::Long => (entry_price - stop_loss_price, take_profit_price - entry_price),
+            TradeDirection::Short => (stop_loss_price - entry_price, entry_price - take_profit_price),
+            TradeDirection::Neutral => (0.0, 0.0),
+        };
+
+        let (approved, reasoning, risk_amount, reward_amount, risk_reward_ratio, expected_value) =
+            if risk_per_unit <= 0.0 || reward_per_unit <= 0.0 {
+                (false, "REJECTED: Stop loss and take profit must be on opposite sides of the entry. ".to_string(),
+                 0.0, 0.0, 0.0, 0.0)
+            } else {
+                let risk_amount = position_size * risk_per_unit / entry_price;
+                let reward_amount = position_size * reward_per_unit / entry_price;
+                let risk_reward_ratio = reward_amount / risk_amount;
+                let expected_value = (win_probability / 100.0 * reward_amount) -
+                                    ((100.0 - win_probability) / 100.0 * risk_amount);
+                let (approved, reasoning) = self.evaluate_thresholds(win_probability, risk_reward_ratio, expected_value);
+                (approved, reasoning, risk_amount, reward_amount, risk_reward_ratio, expected_value)
+            };
+
+        let assessment = ZeroLossAssessment {
+            symbol: symbol.to_string(),
+            timestamp: Utc::now(),
+            direction,
+            entry_price,
+            stop_loss_price,
+            take_profit_price,
+            position_size,
+            leverage: 1.0,
+            risk_amount,
+            reward_amount,
+            risk_reward_ratio,
+            win_probability,
+            expected_value,
+            approved,
+            reasoning,
+        };
+
+        self.assessment_cache.insert(symbol.to_string(), assessment.clone());
+
+        // Vetoed orders never reach the exchange; keep a record of why
+        if !assessment.approved {
+            self.audit_trail.record(AUDIT_CATEGORY, "order_vetoed", serde_json::json!({
+                "symbol": symbol,
+                "direction": format!("{:?}", assessment.direction),
+                "entry_price": entry_price,
+                "position_size": position_size,
+                "reason": assessment.reasoning.trim(),
+            }));
+        }
+
+        Ok(assessment)
+    }
+
     /// Evaluate trade for approval
     fn evaluate_trade(
         &self,
@@ -285,9 +588,67 @@ impl ZeroLossEnforcer {
         expected_value: f64,
         market_analysis: &MarketAnalysis,
         sentiment_analysis: &SentimentAnalysis,
+    ) -> (bool, String) {
+        let (approved, mut reasoning) = self.evaluate_thresholds(win_probability, risk_reward_ratio, expected_value);
+        if !approved {
+            return (false, reasoning);
+        }
+
+        // Check market conditions
+        if market_analysis.volatility > 10.0 {
+            reasoning.push_str(&format!(
+                "CAUTION: High volatility detected ({:.1}%). ",
+                market_analysis.volatility
+            ));
+
+            // Require higher win probability for high volatility
+            if win_probability < 90.0 {
+                reasoning.push_str(
+                    "REJECTED: High volatility requires win probability of at least 90%. "
+                );
+                return (false, reasoning);
+            }
+        }
+
+        // Check sentiment extremes
+        if sentiment_analysis.sentiment_score.abs() > 80.0 {
+            reasoning.push_str(&format!(
+                "CAUTION: Extreme sentiment detected ({:.1}). ",
+                sentiment_analysis.sentiment_score
+            ));
+
+            // Require higher risk-reward for extreme sentiment
+            if risk_reward_ratio < 15.0 {
+                reasoning.push_str(
+                    "REJECTED: Extreme sentiment requires risk-reward ratio of at least 15:1. "
+                );
+                return (false, reasoning);
+            }
+        }
+
+        // Trade approved
+        reasoning.push_str("APPROVED: Trade meets all zero-loss enforcement criteria. ");
+        (true, reasoning)
+    }
+
+    /// Check win probability, risk-reward ratio and expected value thresholds
+    fn evaluate_thresholds(
+        &self,
+        win_probability: f64,
+        risk_reward_ratio: f64,
+        expected_value: f64,
     ) -> (bool, String) {
         let mut reasoning = String::new();
 
+        // Refuse new risk while the portfolio fails its stress test
+        if let Some(stress) = self.stress_result.as_ref().filter(|s| !s.go) {
+            reasoning.push_str(&format!(
+                "REJECTED: Stress test no-go, worst case equity ${:.2} ({}). ",
+                stress.worst_case_equity, stress.worst_scenario.as_deref().unwrap_or("n/a")
+            ));
+            return (false, reasoning);
+        }
+
         // Check win probability
         if win_probability < self.min_win_probability {
             reasoning.push_str(&format!(
@@ -330,40 +691,6 @@ impl ZeroLossEnforcer {
             expected_value, self.min_expected_value
         ));
 
-        // Check market conditions
-        if market_analysis.volatility > 10.0 {
-            reasoning.push_str(&format!(
-                "CAUTION: High volatility detected ({:.1}%). ",
-                market_analysis.volatility
-            ));
-
-            // Require higher win probability for high volatility
-            if win_probability < 90.0 {
-                reasoning.push_str(
-                    "REJECTED: High volatility requires win probability of at least 90%. "
-                );
-                return (false, reasoning);
-            }
-        }
-
-        // Check sentiment extremes
-        if sentiment_analysis.sentiment_score.abs() > 80.0 {
-            reasoning.push_str(&format!(
-                "CAUTION: Extreme sentiment detected ({:.1}). ",
-                sentiment_analysis.sentiment_score
-            ));
-
-            // Require higher risk-reward for extreme sentiment
-            if risk_reward_ratio < 15.0 {
-                reasoning.push_str(
-                    "REJECTED: Extreme sentiment requires risk-reward ratio of at least 15:1. "
-                );
-                return (false, reasoning);
-            }
-        }
-
-        // Trade approved
-        reasoning.push_str("APPROVED: Trade meets all zero-loss enforcement criteria. ");
         (true, reasoning)
     }
 
@@ -387,8 +714,91 @@ impl ZeroLossEnforcer {
         self.min_risk_reward_ratio = min_risk_reward_ratio;
     }
 
+    /// Record the latest stress test of the portfolio
+    pub fn set_stress_result(&mut self, result: StressTestResult) {
+        if !result.go && self.is_stress_go() {
+            warn!("Stress test no-go: worst case equity {:.2} ({}); refusing new trades",
+                  result.worst_case_equity, result.worst_scenario.as_deref().unwrap_or("n/a"));
+        }
+        self.stress_result = Some(result);
+    }
+
+    /// Latest stress test of the portfolio
+    pub fn get_stress_result(&self) -> Option<&StressTestResult> {
+        self.stress_result.as_ref()
+    }
+
+    /// Whether the latest stress test allows new trades
+    pub fn is_stress_go(&self) -> bool {
+        self.stress_result.as_ref().map_or(true, |s| s.go)
+    }
+
     /// Set minimum expected value
     pub fn set_min_expected_value(&mut self, min_expected_value: f64) {
         self.min_expected_value = min_expected_value;
     }
+
+    /// Set the protective action settings
+    pub fn set_config(&mut self, config: ZeroLossEnforcerConfig) {
+        self.config = config;
+    }
+
+    /// Get the protective action settings
+    pub fn get_config(&self) -> &ZeroLossEnforcerConfig {
+        &self.config
+    }
+
+    /// Write audit records to `audit_trail` instead
+    pub fn set_audit_trail(&mut self, audit_trail: AuditTrail) {
+        self.audit_trail = audit_trail;
+    }
+
+    /// Get the audit trail of protective actions and vetoed orders
+    pub fn get_audit_trail(&self) -> &AuditTrail {
+        &self.audit_trail
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_protective_actions_escalate_and_are_audited() {
+        let mut enforcer = ZeroLossEnforcer::new();
+        enforcer.open_trades.insert("trade-1".to_string(), GuardedTrade {
+            symbol: "BTCUSDT".to_string(),
+            direction: TradeDirection::Long,
+            entry_price: 100.0,
+            stop_loss_price: 98.0,
+            size: 2.0,
+            reduced: false,
+            hedged: false,
+        });
+
+        assert!(enforcer.update_trade("trade-1", 99.9).unwrap().is_empty());
+
+        // The stop is pulled in to cap the loss at 0.5%
+        let actions = enforcer.update_trade("trade-1", 99.7).unwrap();
+        assert!(matches!(actions.as_slice(), [ProtectiveAction::TightenStop { new_stop, .. }] if (*new_stop - 99.5).abs() < 1e-9));
+
+        // Then half the position is closed, once
+        let actions = enforcer.update_trade("trade-1", 99.6).unwrap();
+        assert!(matches!(actions.as_slice(), [ProtectiveAction::ReduceSize { fraction, .. }] if *fraction == 0.5));
+
+        // And the remainder hedged, sized from what the reduction actually left
+        enforcer.sync_size("trade-1", 1.5);
+        let actions = enforcer.update_trade("trade-1", 99.52).unwrap();
+        assert!(matches!(actions.as_slice(), [ProtectiveAction::Hedge { position_size, .. }] if *position_size == 1.5));
+        assert!(enforcer.update_trade("trade-1", 99.51).unwrap().is_empty());
+
+        // Vetoed orders are audited too
+        let assessment = enforcer.pre_approve_setup("BTCUSDT", TradeDirection::Long, 100.0, 101.0, 102.0, 1.0, 90.0).unwrap();
+        assert!(!assessment.approved);
+        let actions: Vec<&str> = enforcer.get_audit_trail().get_records_by_category("zero_loss").iter().map(|r| r.action.as_str()).collect();
+        assert_eq!(actions, vec!["stop_tightened", "size_reduced", "hedge_triggered", "order_vetoed"]);
+
+        enforcer.close_trade("trade-1", 99.5, Utc::now()).unwrap();
+        assert!(enforcer.update_trade("trade-1", 90.0).unwrap().is_empty());
+    }
 }
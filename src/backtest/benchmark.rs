@@ -0,0 +1,206 @@
+//! Benchmark Comparison
+//!
+//! This module builds benchmark equity curves from historical close prices (single
+//! asset buy-and-hold, e.g. BTC, or an equal-weight basket such as the top 10 by
+//! market cap) and compares a backtest against them. The strategy's equity is
+//! sampled on the benchmark's time grid to compute per-period returns, beta,
+//! annualized alpha and correlation.
+
+use std::collections::HashMap;
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+use crate::backtest::BacktestResult;
+
+/// Benchmark definition
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum Benchmark {
+    /// Buy and hold a single symbol
+    BuyAndHold { symbol: String },
+
+    /// Equal capital in each symbol at the start, never rebalanced
+    EqualWeight { name: String, symbols: Vec<String> },
+}
+
+impl Benchmark {
+    /// Display name
+    pub fn get_name(&self) -> String {
+        match self {
+            Benchmark::BuyAndHold { symbol } => format!("{} buy-and-hold", symbol),
+            Benchmark::EqualWeight { name, .. } => name.clone(),
+        }
+    }
+
+    /// Build the benchmark equity curve from close prices (seconds, price) by symbol
+    pub fn equity_curve(&self, prices: &HashMap<String, Vec<(u64, f64)>>, initial_capital: f64) -> Result<Vec<(u64, f64)>> {
+        let symbols: Vec<&String> = match self {
+            Benchmark::BuyAndHold { symbol } => vec![symbol],
+            Benchmark::EqualWeight { symbols, .. } => symbols.iter().collect(),
+        };
+        if symbols.is_empty() {
+            return Err(anyhow::anyhow!("Benchmark has no symbols"));
+        }
+
+        let mut series = Vec::with_capacity(symbols.len());
+        for symbol in &symbols {
+            let mut history = prices.get(*symbol)
+                .cloned()
+                .ok_or_else(|| anyhow::anyhow!("No price history for benchmark symbol {}", symbol))?;
+            history.sort_by_key(|(t, _)| *t);
+            history.retain(|(_, p)| *p > 0.0);
+            if history.is_empty() {
+                return Err(anyhow::anyhow!("No valid prices for benchmark symbol {}", symbol));
+            }
+            series.push(history);
+        }
+
+        // Start once every symbol has a price; use the first symbol's timestamps as the grid
+        let start = series.iter().map(|s| s[0].0).max().unwrap_or(0);
+        let allocation = initial_capital / series.len() as f64;
+        let units: Vec<f64> = series.iter()
+            .map(|s| allocation / price_at(s, start).unwrap_or(s[0].1))
+            .collect();
+
+        Ok(series[0].iter()
+            .filter(|(t, _)| *t >= start)
+            .map(|(t, _)| {
+                let value = series.iter()
+                    .zip(&units)
+                    .map(|(s, u)| u * price_at(s, *t).unwrap_or(0.0))
+                    .sum();
+                (*t, value)
+            })
+            .collect())
+    }
+}
+
+/// Latest price at or before `time`
+fn price_at(series: &[(u64, f64)], time: u64) -> Option<f64> {
+    match series.binary_search_by_key(&time, |(t, _)| *t) {
+        Ok(i) => Some(series[i].1),
+        Err(0) => None,
+        Err(i) => Some(series[i - 1].1),
+    }
+}
+
+/// Strategy versus benchmark statistics
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BenchmarkComparison {
+    /// Benchmark name
+    pub name: String,
+
+    /// Benchmark equity curve
+    pub equity_curve: Vec<(u64, f64)>,
+
+    /// Benchmark total return in %
+    pub benchmark_return: f64,
+
+    /// Strategy total return over the same period in %
+    pub strategy_return: f64,
+
+    /// Strategy return minus benchmark return, in percentage points
+    pub excess_return: f64,
+
+    /// Sensitivity of strategy returns to benchmark returns
+    pub beta: f64,
+
+    /// Annualized alpha in %
+    pub alpha: f64,
+
+    /// Correlation of per-period returns
+    pub correlation: f64,
+}
+
+/// Compare a backtest against a benchmark
+pub fn compare(result: &BacktestResult, benchmark: &Benchmark, prices: &HashMap<String, Vec<(u64, f64)>>) -> Result<BenchmarkComparison> {
+    let initial_capital = result.config.initial_capital;
+    let equity_curve: Vec<(u64, f64)> = benchmark.equity_curve(prices, initial_capital)?
+        .into_iter()
+        .filter(|(t, _)| *t >= result.config.start_date && *t <= result.config.end_date)
+        .collect();
+    if equity_curve.len() < 3 {
+        return Err(anyhow::anyhow!("Not enough benchmark prices inside the backtest period"));
+    }
+
+    // Strategy capital as a step function of closed trades
+    let mut trades: Vec<(u64, f64)> = result.trades.iter().map(|t| (t.exit_time, t.profit_loss)).collect();
+    trades.sort_by_key(|(t, _)| *t);
+    let mut strategy_curve = Vec::with_capacity(equity_curve.len());
+    let mut capital = initial_capital;
+    let mut next = 0;
+    for (time, _) in &equity_curve {
+        while next < trades.len() && trades[next].0 <= *time {
+            capital += trades[next].1;
+            next += 1;
+        }
+        strategy_curve.push(capital);
+    }
+
+    let returns = |curve: &[f64]| -> Vec<f64> {
+        curve.windows(2).map(|w| if w[0] > 0.0 { w[1] / w[0] - 1.0 } else { 0.0 }).collect()
+    };
+    let benchmark_values: Vec<f64> = equity_curve.iter().map(|(_, v)| *v).collect();
+    let benchmark_returns = returns(&benchmark_values);
+    let strategy_returns = returns(&strategy_curve);
+
+    let n = benchmark_returns.len() as f64;
+    let mean_b = benchmark_returns.iter().sum::<f64>() / n;
+    let mean_s = strategy_returns.iter().sum::<f64>() / n;
+    let covariance = benchmark_returns.iter().zip(&strategy_returns).map(|(b, s)| (b - mean_b) * (s - mean_s)).sum::<f64>() / n;
+    let var_b = benchmark_returns.iter().map(|b| (b - mean_b).powi(2)).sum::<f64>() / n;
+    let var_s = strategy_returns.iter().map(|s| (s - mean_s).powi(2)).sum::<f64>() / n;
+
+    let beta = if var_b > 0.0 { covariance / var_b } else { 0.0 };
+    let correlation = if var_b > 0.0 && var_s > 0.0 { covariance / (var_b.sqrt() * var_s.sqrt()) } else { 0.0 };
+
+    let span = equity_curve[equity_curve.len() - 1].0 - equity_curve[0].0;
+    let periods_per_year = if span > 0 { n * 365.0 * 86_400.0 / span as f64 } else { 0.0 };
+    let alpha = (mean_s - beta * mean_b) * periods_per_year * 100.0;
+
+    let first = benchmark_values[0];
+    let benchmark_return = (benchmark_values[benchmark_values.len() - 1] / first - 1.0) * 100.0;
+    let strategy_return = (strategy_curve[strategy_curve.len() - 1] / strategy_curve[0] - 1.0) * 100.0;
+
+    Ok(BenchmarkComparison {
+        name: benchmark.get_name(),
+        equity_curve,
+        benchmark_return,
+        strategy_return,
+        excess_return: strategy_return - benchmark_return,
+        beta,
+        alpha,
+        correlation,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backtest::{BacktestConfig, BacktestTrade};
+
+    #[test]
+    fn test_buy_and_hold_and_equal_weight_comparison() {
+        let day = 86_400;
+        let mut prices = HashMap::new();
+        prices.insert("BTCUSDT".to_string(), (0..=10).map(|i| (i * day, 100.0 + i as f64 * 10.0)).collect());
+        prices.insert("ETHUSDT".to_string(), (0..=10).map(|i| (i * day, 50.0)).collect());
+
+        let config = BacktestConfig::new(0, 10 * day, 1_000.0, vec!["BTCUSDT".to_string()]);
+        let mut trade = BacktestTrade::new("BTCUSDT".to_string(), 0, 100.0, 1.0, "long".to_string());
+        trade.exit_time = 5 * day;
+        trade.profit_loss = 50.0;
+        let result = BacktestResult::new(config, vec![trade]);
+
+        let btc = compare(&result, &Benchmark::BuyAndHold { symbol: "BTCUSDT".to_string() }, &prices).unwrap();
+        assert!((btc.benchmark_return - 100.0).abs() < 1e-9);
+        assert!((btc.strategy_return - 5.0).abs() < 1e-9);
+        assert!(btc.excess_return < 0.0);
+
+        let basket = Benchmark::EqualWeight {
+            name: "Equal-weight basket".to_string(),
+            symbols: vec!["BTCUSDT".to_string(), "ETHUSDT".to_string()],
+        };
+        let curve = basket.equity_curve(&prices, 1_000.0).unwrap();
+        assert!((curve.last().unwrap().1 - 1_500.0).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,240 @@
+//! Backtest Checkpointing
+//!
+//! This module snapshots `BacktestEngine` state (open positions, closed trades,
+//! capital, in-flight orders and RNG state) to disk so long tick-level simulations
+//! can resume after an interruption instead of restarting. Fee and slippage models
+//! are rebuilt from the stored config and funding history must be reloaded, since
+//! neither is part of the snapshot.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+use anyhow::Result;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+use tracing::{debug, info};
+
+use crate::backtest::latency::{LatencyCheckpoint, LatencySimulator};
+use crate::backtest::{BacktestConfig, BacktestEngine, BacktestTrade, MarketConditions};
+
+/// Checkpoint format version
+const CHECKPOINT_VERSION: u32 = 2;
+
+/// Serializable engine state
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EngineCheckpoint {
+    /// Format version
+    pub version: u32,
+
+    /// Data time (seconds) processed up to when the checkpoint was taken
+    pub processed_until: u64,
+
+    /// Configuration
+    pub config: BacktestConfig,
+
+    /// Closed trades
+    pub trades: Vec<BacktestTrade>,
+
+    /// Open positions
+    pub open_positions: BTreeMap<String, BacktestTrade>,
+
+    /// Free capital
+    pub current_capital: f64,
+
+    /// Latest mark prices
+    pub mark_prices: HashMap<String, f64>,
+
+    /// Latest spread and bar volume by symbol
+    pub market_conditions: HashMap<String, MarketConditions>,
+
+    /// Mark price history still needed for funding, by symbol
+    pub mark_history: HashMap<String, Vec<(u64, f64)>>,
+
+    /// Strategy ID tagged on new trades
+    pub strategy_id: String,
+
+    /// Seed the engine RNG continues from
+    pub rng_seed: u64,
+
+    /// Latency simulator state, if enabled
+    pub latency: Option<LatencyCheckpoint>,
+}
+
+impl EngineCheckpoint {
+    /// Write the checkpoint atomically (temp file, then rename)
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            fs::create_dir_all(parent)?;
+        }
+
+        let tmp = path.with_extension("tmp");
+        fs::write(&tmp, serde_json::to_vec(self)?)?;
+        fs::rename(&tmp, path)?;
+        Ok(())
+    }
+
+    /// Read a checkpoint
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let checkpoint: Self = serde_json::from_slice(&fs::read(path)?)?;
+        if checkpoint.version != CHECKPOINT_VERSION {
+            return Err(anyhow::anyhow!("Unsupported checkpoint version {}", checkpoint.version));
+        }
+        Ok(checkpoint)
+    }
+}
+
+impl BacktestEngine {
+    /// Capture the engine state after processing data up to `processed_until`
+    ///
+    /// The RNG is reseeded from itself so a run that continues and a run restored
+    /// from this checkpoint produce identical results.
+    pub fn checkpoint(&mut self, processed_until: u64) -> EngineCheckpoint {
+        let rng_seed = self.rng.gen();
+        self.rng = StdRng::seed_from_u64(rng_seed);
+
+        EngineCheckpoint {
+            version: CHECKPOINT_VERSION,
+            processed_until,
+            config: self.config.clone(),
+            trades: self.trades.clone(),
+            open_positions: self.open_positions.clone(),
+            current_capital: self.current_capital,
+            mark_prices: self.mark_prices.clone(),
+            market_conditions: self.market_conditions.clone(),
+            mark_history: self.mark_history.clone(),
+            strategy_id: self.strategy_id.clone(),
+            rng_seed,
+            latency: self.latency.as_mut().map(|l| l.checkpoint()),
+        }
+    }
+
+    /// Restore an engine from a checkpoint
+    pub fn from_checkpoint(checkpoint: EngineCheckpoint) -> Self {
+        let mut engine = BacktestEngine::new(checkpoint.config);
+        engine.trades = checkpoint.trades;
+        engine.open_positions = checkpoint.open_positions;
+        engine.current_capital = checkpoint.current_capital;
+        engine.mark_prices = checkpoint.mark_prices;
+        engine.market_conditions = checkpoint.market_conditions;
+        engine.mark_history = checkpoint.mark_history;
+        engine.strategy_id = checkpoint.strategy_id;
+        engine.rng = StdRng::seed_from_u64(checkpoint.rng_seed);
+        engine.latency = checkpoint.latency.map(LatencySimulator::from_checkpoint);
+        engine
+    }
+}
+
+/// Writes engine checkpoints at a fixed interval of simulated time
+#[derive(Debug, Clone)]
+pub struct Checkpointer {
+    /// Checkpoint file
+    path: PathBuf,
+
+    /// Simulated seconds between checkpoints
+    interval_seconds: u64,
+
+    /// Time of the last checkpoint
+    last_checkpoint: Option<u64>,
+}
+
+impl Checkpointer {
+    /// Create a new checkpointer
+    pub fn new(path: impl Into<PathBuf>, interval_seconds: u64) -> Self {
+        Self {
+            path: path.into(),
+            interval_seconds: interval_seconds.max(1),
+            last_checkpoint: None,
+        }
+    }
+
+    /// Restore the engine from the checkpoint file, or start fresh if there is none
+    ///
+    /// Returns the engine and the data time to resume after, if resuming.
+    pub fn resume_or_new(&mut self, config: BacktestConfig) -> Result<(BacktestEngine, Option<u64>)> {
+        if !self.path.exists() {
+            return Ok((BacktestEngine::new(config), None));
+        }
+
+        let checkpoint = EngineCheckpoint::load(&self.path)?;
+        let processed_until = checkpoint.processed_until;
+        info!("Resuming backtest from {} at t={} ({} closed, {} open trades)",
+              self.path.display(), processed_until, checkpoint.trades.len(), checkpoint.open_positions.len());
+
+        self.last_checkpoint = Some(processed_until);
+        Ok((BacktestEngine::from_checkpoint(checkpoint), Some(processed_until)))
+    }
+
+    /// Checkpoint if the interval elapsed since the last one; returns whether it did
+    pub fn maybe_checkpoint(&mut self, engine: &mut BacktestEngine, now: u64) -> Result<bool> {
+        let due = match self.last_checkpoint {
+            Some(last) => now >= last + self.interval_seconds,
+            None => {
+                self.last_checkpoint = Some(now);
+                false
+            }
+        };
+        if !due {
+            return Ok(false);
+        }
+
+        engine.checkpoint(now).save(&self.path)?;
+        self.last_checkpoint = Some(now);
+        debug!("Backtest checkpoint written to {} at t={}", self.path.display(), now);
+        Ok(true)
+    }
+
+    /// Remove the checkpoint once the run completed
+    pub fn finish(&self) -> Result<()> {
+        if self.path.exists() {
+            fs::remove_file(&self.path)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resumed_run_matches_uninterrupted_run() {
+        let mut config = BacktestConfig::new(0, 10_000, 10_000.0, vec!["BTCUSDT".to_string()]);
+        config.seed = Some(7);
+        let prices: Vec<(u64, f64)> = (0..10).map(|i| (i * 1_000, 100.0 + i as f64)).collect();
+
+        let step = |engine: &mut BacktestEngine, time: u64, price: f64| {
+            engine.update_mark_price("BTCUSDT", time, price);
+            if (time / 1_000) % 3 == 0 {
+                engine.open_position("BTCUSDT".to_string(), time, price, 1.0, "long".to_string()).unwrap();
+            }
+        };
+
+        let mut uninterrupted = BacktestEngine::new(config.clone());
+        for (i, (time, price)) in prices.iter().enumerate() {
+            step(&mut uninterrupted, *time, *price);
+            if i == 4 {
+                uninterrupted.checkpoint(*time);
+            }
+        }
+
+        let path = std::env::temp_dir().join(format!("omni_checkpoint_{}.json", std::process::id()));
+        let mut first = BacktestEngine::new(config.clone());
+        for (time, price) in &prices[..5] {
+            step(&mut first, *time, *price);
+        }
+        first.checkpoint(prices[4].0).save(&path).unwrap();
+
+        let (mut resumed, processed_until) = Checkpointer::new(&path, 1_000).resume_or_new(config).unwrap();
+        assert_eq!(processed_until, Some(4_000));
+        for (time, price) in prices.iter().filter(|(t, _)| *t > 4_000) {
+            step(&mut resumed, *time, *price);
+        }
+
+        let ids = |engine: &BacktestEngine| engine.get_trade_records().into_iter().map(|r| r.trade_id).collect::<Vec<_>>();
+        assert_eq!(ids(&resumed), ids(&uninterrupted));
+        assert!((resumed.get_current_capital() - uninterrupted.get_current_capital()).abs() < 1e-9);
+        let _ = fs::remove_file(&path);
+    }
+}
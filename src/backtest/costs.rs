@@ -0,0 +1,282 @@
+//! Transaction Cost Models
+//!
+//! This module defines the `FeeModel` and `SlippageModel` traits used by the
+//! `BacktestEngine` to price every fill, with fixed basis-point, spread-proportional
+//! volume-impact and per-symbol (calibrated from live fills) implementations. `FeeModelConfig` and `SlippageModelConfig`
+//! describe the built-in models in a serializable form for `BacktestConfig`.
+
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::sync::Arc;
+use serde::{Deserialize, Serialize};
+
+/// Market context of a single fill
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FillContext {
+    /// Symbol
+    pub symbol: String,
+
+    /// Reference price before costs
+    pub price: f64,
+
+    /// Quantity
+    pub quantity: f64,
+
+    /// Whether the fill buys (long entry or short exit)
+    pub is_buy: bool,
+
+    /// Whether the fill adds liquidity (limit order resting in the book)
+    pub is_maker: bool,
+
+    /// Bid-ask spread in price units, if known
+    pub spread: Option<f64>,
+
+    /// Volume traded in the bar, if known
+    pub bar_volume: Option<f64>,
+}
+
+impl FillContext {
+    /// Notional value of the fill
+    pub fn notional(&self) -> f64 {
+        self.price * self.quantity
+    }
+}
+
+/// Model of exchange fees
+pub trait FeeModel: Debug + Send + Sync {
+    /// Fee for a fill in quote currency
+    fn fee(&self, fill: &FillContext) -> f64;
+}
+
+/// Model of execution slippage
+pub trait SlippageModel: Debug + Send + Sync {
+    /// Adverse price move as a fraction of price (e.g. 0.0005 for 5 bps)
+    fn slippage(&self, fill: &FillContext) -> f64;
+
+    /// Execution price after slippage
+    fn execution_price(&self, fill: &FillContext) -> f64 {
+        let slippage = self.slippage(fill).max(0.0);
+        if fill.is_buy {
+            fill.price * (1.0 + slippage)
+        } else {
+            fill.price * (1.0 - slippage)
+        }
+    }
+}
+
+/// Fixed maker/taker fee in basis points of notional
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FixedBpsFee {
+    /// Maker fee in bps (may be negative for rebates)
+    pub maker_bps: f64,
+
+    /// Taker fee in bps
+    pub taker_bps: f64,
+}
+
+impl FeeModel for FixedBpsFee {
+    fn fee(&self, fill: &FillContext) -> f64 {
+        let bps = if fill.is_maker { self.maker_bps } else { self.taker_bps };
+        fill.notional() * bps / 10_000.0
+    }
+}
+
+/// Fixed slippage in basis points
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FixedBpsSlippage {
+    /// Slippage in bps
+    pub bps: f64,
+}
+
+impl SlippageModel for FixedBpsSlippage {
+    fn slippage(&self, fill: &FillContext) -> f64 {
+        if fill.is_maker { 0.0 } else { self.bps / 10_000.0 }
+    }
+}
+
+/// Slippage proportional to the bid-ask spread
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpreadProportionalSlippage {
+    /// Fraction of the spread paid (0.5 = crossing from mid to the touch)
+    pub spread_fraction: f64,
+
+    /// Slippage in bps when the spread is unknown
+    pub fallback_bps: f64,
+}
+
+impl SlippageModel for SpreadProportionalSlippage {
+    fn slippage(&self, fill: &FillContext) -> f64 {
+        if fill.is_maker || fill.price <= 0.0 {
+            return 0.0;
+        }
+
+        match fill.spread {
+            Some(spread) => spread * self.spread_fraction / fill.price,
+            None => self.fallback_bps / 10_000.0,
+        }
+    }
+}
+
+/// Market-impact slippage growing with the order's share of bar volume
+///
+/// Slippage = `base_bps + impact_bps * (quantity / bar_volume)^exponent`, capped at
+/// `max_bps`. An exponent of 0.5 gives the usual square-root impact law.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VolumeImpactSlippage {
+    /// Slippage applied regardless of size, in bps
+    pub base_bps: f64,
+
+    /// Impact in bps when the order equals the bar volume
+    pub impact_bps: f64,
+
+    /// Impact exponent
+    pub exponent: f64,
+
+    /// Maximum slippage in bps
+    pub max_bps: f64,
+}
+
+impl SlippageModel for VolumeImpactSlippage {
+    fn slippage(&self, fill: &FillContext) -> f64 {
+        if fill.is_maker {
+            return 0.0;
+        }
+
+        let participation = match fill.bar_volume {
+            Some(volume) if volume > 0.0 => fill.quantity / volume,
+            _ => 1.0,
+        };
+
+        let bps = self.base_bps + self.impact_bps * participation.powf(self.exponent);
+        bps.min(self.max_bps) / 10_000.0
+    }
+}
+
+/// Fixed slippage per symbol, e.g. calibrated from observed live executions
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PerSymbolSlippage {
+    /// Slippage in bps by symbol
+    pub by_symbol_bps: HashMap<String, f64>,
+
+    /// Slippage in bps for symbols without an entry
+    pub fallback_bps: f64,
+}
+
+impl SlippageModel for PerSymbolSlippage {
+    fn slippage(&self, fill: &FillContext) -> f64 {
+        if fill.is_maker {
+            return 0.0;
+        }
+        self.by_symbol_bps.get(&fill.symbol).copied().unwrap_or(self.fallback_bps) / 10_000.0
+    }
+}
+
+/// Serializable description of a built-in fee model
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum FeeModelConfig {
+    /// Fixed maker/taker bps
+    FixedBps { maker_bps: f64, taker_bps: f64 },
+}
+
+impl FeeModelConfig {
+    /// Build the model
+    pub fn build(&self) -> Arc<dyn FeeModel> {
+        match self {
+            FeeModelConfig::FixedBps { maker_bps, taker_bps } => Arc::new(FixedBpsFee {
+                maker_bps: *maker_bps,
+                taker_bps: *taker_bps,
+            }),
+        }
+    }
+}
+
+impl Default for FeeModelConfig {
+    fn default() -> Self {
+        // 0.1% taker fee, matching the previous flat commission rate
+        FeeModelConfig::FixedBps { maker_bps: 2.0, taker_bps: 10.0 }
+    }
+}
+
+/// Serializable description of a built-in slippage model
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum SlippageModelConfig {
+    /// Fixed bps
+    FixedBps { bps: f64 },
+
+    /// Fraction of the spread
+    SpreadProportional { spread_fraction: f64, fallback_bps: f64 },
+
+    /// Volume participation impact
+    VolumeImpact { base_bps: f64, impact_bps: f64, exponent: f64, max_bps: f64 },
+
+    /// Fixed bps per symbol
+    PerSymbol { by_symbol_bps: HashMap<String, f64>, fallback_bps: f64 },
+}
+
+impl SlippageModelConfig {
+    /// Build the model
+    pub fn build(&self) -> Arc<dyn SlippageModel> {
+        match self {
+            SlippageModelConfig::FixedBps { bps } => Arc::new(FixedBpsSlippage { bps: *bps }),
+            SlippageModelConfig::SpreadProportional { spread_fraction, fallback_bps } => {
+                Arc::new(SpreadProportionalSlippage {
+                    spread_fraction: *spread_fraction,
+                    fallback_bps: *fallback_bps,
+                })
+            }
+            SlippageModelConfig::VolumeImpact { base_bps, impact_bps, exponent, max_bps } => {
+                Arc::new(VolumeImpactSlippage {
+                    base_bps: *base_bps,
+                    impact_bps: *impact_bps,
+                    exponent: *exponent,
+                    max_bps: *max_bps,
+                })
+            }
+            SlippageModelConfig::PerSymbol { by_symbol_bps, fallback_bps } => Arc::new(PerSymbolSlippage {
+                by_symbol_bps: by_symbol_bps.clone(),
+                fallback_bps: *fallback_bps,
+            }),
+        }
+    }
+}
+
+impl Default for SlippageModelConfig {
+    fn default() -> Self {
+        // 0.05%, matching the previous flat slippage
+        SlippageModelConfig::FixedBps { bps: 5.0 }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fill(quantity: f64, bar_volume: Option<f64>) -> FillContext {
+        FillContext {
+            symbol: "BTCUSDT".to_string(),
+            price: 100.0,
+            quantity,
+            is_buy: true,
+            is_maker: false,
+            spread: Some(0.1),
+            bar_volume,
+        }
+    }
+
+    #[test]
+    fn test_cost_models() {
+        let fee = FixedBpsFee { maker_bps: 2.0, taker_bps: 10.0 };
+        assert!((fee.fee(&fill(10.0, None)) - 1.0).abs() < 1e-12);
+
+        let spread = SpreadProportionalSlippage { spread_fraction: 0.5, fallback_bps: 5.0 };
+        assert!((spread.execution_price(&fill(1.0, None)) - 100.05).abs() < 1e-9);
+
+        let impact = VolumeImpactSlippage { base_bps: 1.0, impact_bps: 100.0, exponent: 0.5, max_bps: 50.0 };
+        let small = impact.slippage(&fill(1.0, Some(10_000.0)));
+        let large = impact.slippage(&fill(100.0, Some(10_000.0)));
+        assert!(small < large);
+        assert!(impact.slippage(&fill(1.0, None)) <= 50.0 / 10_000.0);
+    }
+}
@@ -0,0 +1,453 @@
+//! Historical Data Loader
+//!
+//! This module reads OHLCV history from CSV or Parquet files into `Candle` series
+//! for the backtester. Column names are configurable, every row is validated
+//! (finite positive prices, consistent high/low, non-negative volume) and all
+//! timestamps are normalized to UTC. Parquet support requires the `parquet` feature.
+
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+use anyhow::{Context, Result};
+use chrono::{DateTime, FixedOffset, NaiveDateTime, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+use tracing::{info, warn};
+
+use crate::exchange::types::Candle;
+use crate::strategy::simple_strategy::Candle as StrategyCandle;
+
+/// How timestamps are encoded in the source data
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TimestampFormat {
+    /// Detect epoch unit from magnitude, or parse RFC 3339 / common date-time strings
+    Auto,
+
+    /// Seconds since the Unix epoch
+    UnixSeconds,
+
+    /// Milliseconds since the Unix epoch
+    UnixMillis,
+
+    /// RFC 3339 with offset
+    Rfc3339,
+
+    /// Naive date-time in the given `chrono` format, in the configured source offset
+    Naive(String),
+}
+
+/// Data loader configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DataLoaderConfig {
+    /// Timestamp column
+    pub timestamp_column: String,
+
+    /// Open column
+    pub open_column: String,
+
+    /// High column
+    pub high_column: String,
+
+    /// Low column
+    pub low_column: String,
+
+    /// Close column
+    pub close_column: String,
+
+    /// Volume column
+    pub volume_column: String,
+
+    /// Timestamp encoding
+    pub timestamp_format: TimestampFormat,
+
+    /// UTC offset (minutes) of naive timestamps in the source data
+    pub source_utc_offset_minutes: i32,
+
+    /// CSV delimiter
+    pub delimiter: u8,
+
+    /// Skip invalid rows instead of failing the whole load
+    pub skip_invalid_rows: bool,
+}
+
+impl Default for DataLoaderConfig {
+    fn default() -> Self {
+        Self {
+            timestamp_column: "timestamp".to_string(),
+            open_column: "open".to_string(),
+            high_column: "high".to_string(),
+            low_column: "low".to_string(),
+            close_column: "close".to_string(),
+            volume_column: "volume".to_string(),
+            timestamp_format: TimestampFormat::Auto,
+            source_utc_offset_minutes: 0,
+            delimiter: b',',
+            skip_invalid_rows: false,
+        }
+    }
+}
+
+/// Raw timestamp value before normalization
+enum RawTimestamp {
+    /// Integer epoch value
+    Epoch(i64),
+
+    /// Epoch value with a known unit, in milliseconds
+    EpochMillis(i64),
+
+    /// Text value
+    Text(String),
+}
+
+/// OHLCV data loader
+#[derive(Debug, Clone)]
+pub struct DataLoader {
+    /// Configuration
+    config: DataLoaderConfig,
+}
+
+impl DataLoader {
+    /// Create a new data loader
+    pub fn new(config: DataLoaderConfig) -> Self {
+        Self { config }
+    }
+
+    /// Load a file, choosing the format by extension
+    pub fn load(&self, path: &Path) -> Result<Vec<Candle>> {
+        match path.extension().and_then(|e| e.to_str()) {
+            Some("csv") | Some("txt") => self.load_csv(path),
+            Some("parquet") => self.load_parquet(path),
+            _ => Err(anyhow::anyhow!("Unsupported data file format: {}", path.display())),
+        }
+    }
+
+    /// Load candles from a CSV file
+    pub fn load_csv(&self, path: &Path) -> Result<Vec<Candle>> {
+        let file = File::open(path)
+            .with_context(|| format!("Failed to open {}", path.display()))?;
+        let candles = self.load_csv_reader(file)
+            .with_context(|| format!("Failed to load {}", path.display()))?;
+
+        info!("Loaded {} candles from {}", candles.len(), path.display());
+        Ok(candles)
+    }
+
+    /// Load candles from any CSV reader
+    pub fn load_csv_reader<R: Read>(&self, reader: R) -> Result<Vec<Candle>> {
+        let mut reader = csv::ReaderBuilder::new()
+            .delimiter(self.config.delimiter)
+            .trim(csv::Trim::All)
+            .from_reader(reader);
+
+        let headers = reader.headers()?.clone();
+        let column = |name: &str| -> Result<usize> {
+            headers.iter()
+                .position(|h| h.eq_ignore_ascii_case(name))
+                .ok_or_else(|| anyhow::anyhow!("Missing required column '{}' (found: {:?})", name, headers))
+        };
+
+        let timestamp_idx = column(&self.config.timestamp_column)?;
+        let open_idx = column(&self.config.open_column)?;
+        let high_idx = column(&self.config.high_column)?;
+        let low_idx = column(&self.config.low_column)?;
+        let close_idx = column(&self.config.close_column)?;
+        let volume_idx = column(&self.config.volume_column)?;
+
+        let mut candles = Vec::new();
+        for (line, record) in reader.records().enumerate() {
+            let row = line + 2;
+            let parsed = record.map_err(anyhow::Error::from).and_then(|record| {
+                let field = |idx: usize| record.get(idx).unwrap_or("");
+                let number = |idx: usize, name: &str| -> Result<f64> {
+                    field(idx).parse::<f64>()
+                        .with_context(|| format!("Invalid {} value '{}'", name, field(idx)))
+                };
+
+                let raw = field(timestamp_idx);
+                let timestamp = match raw.parse::<i64>() {
+                    Ok(epoch) => RawTimestamp::Epoch(epoch),
+                    Err(_) => RawTimestamp::Text(raw.to_string()),
+                };
+
+                self.build_candle(
+                    timestamp,
+                    number(open_idx, "open")?,
+                    number(high_idx, "high")?,
+                    number(low_idx, "low")?,
+                    number(close_idx, "close")?,
+                    number(volume_idx, "volume")?,
+                )
+            });
+
+            match parsed {
+                Ok(candle) => candles.push(candle),
+                Err(e) if self.config.skip_invalid_rows => warn!("Skipping row {}: {}", row, e),
+                Err(e) => return Err(e.context(format!("Invalid row {}", row))),
+            }
+        }
+
+        Ok(normalize_series(candles))
+    }
+
+    /// Load candles from a Parquet file
+    #[cfg(feature = "parquet")]
+    pub fn load_parquet(&self, path: &Path) -> Result<Vec<Candle>> {
+        use parquet::file::reader::{FileReader, SerializedFileReader};
+        use parquet::record::Field;
+
+        let file = File::open(path)
+            .with_context(|| format!("Failed to open {}", path.display()))?;
+        let reader = SerializedFileReader::new(file)
+            .with_context(|| format!("Invalid Parquet file {}", path.display()))?;
+
+        let schema = reader.metadata().file_metadata().schema_descr();
+        for name in [
+            &self.config.timestamp_column,
+            &self.config.open_column,
+            &self.config.high_column,
+            &self.config.low_column,
+            &self.config.close_column,
+            &self.config.volume_column,
+        ] {
+            if !schema.columns().iter().any(|c| c.name().eq_ignore_ascii_case(name)) {
+                return Err(anyhow::anyhow!("Missing required column '{}' in {}", name, path.display()));
+            }
+        }
+
+        let number = |field: &Field| -> Option<f64> {
+            match field {
+                Field::Double(v) => Some(*v),
+                Field::Float(v) => Some(*v as f64),
+                Field::Long(v) => Some(*v as f64),
+                Field::Int(v) => Some(*v as f64),
+                Field::Str(v) => v.parse().ok(),
+                _ => None,
+            }
+        };
+
+        let mut candles = Vec::new();
+        for (index, row) in reader.get_row_iter(None)?.enumerate() {
+            let row = row?;
+            let mut timestamp = None;
+            let mut values = [None; 5];
+
+            for (name, field) in row.get_column_iter() {
+                if name.eq_ignore_ascii_case(&self.config.timestamp_column) {
+                    timestamp = match field {
+                        Field::TimestampMillis(v) => Some(RawTimestamp::EpochMillis(*v)),
+                        Field::TimestampMicros(v) => Some(RawTimestamp::EpochMillis(*v / 1000)),
+                        Field::Long(v) => Some(RawTimestamp::Epoch(*v)),
+                        Field::Int(v) => Some(RawTimestamp::Epoch(*v as i64)),
+                        Field::Str(v) => Some(RawTimestamp::Text(v.clone())),
+                        _ => None,
+                    };
+                } else {
+                    let slot = [
+                        &self.config.open_column,
+                        &self.config.high_column,
+                        &self.config.low_column,
+                        &self.config.close_column,
+                        &self.config.volume_column,
+                    ].iter().position(|c| name.eq_ignore_ascii_case(c));
+
+                    if let Some(slot) = slot {
+                        values[slot] = number(field);
+                    }
+                }
+            }
+
+            let parsed = match (timestamp, values) {
+                (Some(timestamp), [Some(open), Some(high), Some(low), Some(close), Some(volume)]) => {
+                    self.build_candle(timestamp, open, high, low, close, volume)
+                }
+                _ => Err(anyhow::anyhow!("Missing or non-numeric values")),
+            };
+
+            match parsed {
+                Ok(candle) => candles.push(candle),
+                Err(e) if self.config.skip_invalid_rows => warn!("Skipping row {}: {}", index, e),
+                Err(e) => return Err(e.context(format!("Invalid row {} in {}", index, path.display()))),
+            }
+        }
+
+        info!("Loaded {} candles from {}", candles.len(), path.display());
+        Ok(normalize_series(candles))
+    }
+
+    /// Load candles from a Parquet file
+    #[cfg(not(feature = "parquet"))]
+    pub fn load_parquet(&self, path: &Path) -> Result<Vec<Candle>> {
+        Err(anyhow::anyhow!(
+            "Cannot load {}: Parquet support requires the `parquet` feature",
+            path.display()
+        ))
+    }
+
+    /// Validate one row and build a candle
+    fn build_candle(&self, timestamp: RawTimestamp, open: f64, high: f64, low: f64, close: f64, volume: f64) -> Result<Candle> {
+        for (name, value) in [("open", open), ("high", high), ("low", low), ("close", close)] {
+            if !value.is_finite() || value <= 0.0 {
+                return Err(anyhow::anyhow!("{} must be a positive number, got {}", name, value));
+            }
+        }
+
+        if !volume.is_finite() || volume < 0.0 {
+            return Err(anyhow::anyhow!("volume must be non-negative, got {}", volume));
+        }
+
+        if high < open.max(close) || low > open.min(close) || low > high {
+            return Err(anyhow::anyhow!(
+                "inconsistent OHLC: open {} high {} low {} close {}",
+                open, high, low, close
+            ));
+        }
+
+        Ok(Candle {
+            timestamp: self.normalize_timestamp(timestamp)?,
+            open,
+            high,
+            low,
+            close,
+            volume,
+        })
+    }
+
+    /// Convert a raw timestamp to UTC
+    fn normalize_timestamp(&self, raw: RawTimestamp) -> Result<DateTime<Utc>> {
+        let from_millis = |millis: i64| {
+            Utc.timestamp_millis_opt(millis).single()
+                .ok_or_else(|| anyhow::anyhow!("timestamp out of range: {}", millis))
+        };
+
+        match (raw, &self.config.timestamp_format) {
+            (RawTimestamp::EpochMillis(millis), _) => from_millis(millis),
+            (RawTimestamp::Epoch(seconds), TimestampFormat::UnixSeconds) => from_millis(seconds.saturating_mul(1000)),
+            (RawTimestamp::Epoch(millis), TimestampFormat::UnixMillis) => from_millis(millis),
+            (RawTimestamp::Epoch(value), TimestampFormat::Auto) => {
+                // Pick the unit from the magnitude: s < 1e11 <= ms < 1e14 <= us < 1e17 <= ns
+                let magnitude = value.unsigned_abs();
+                let millis = if magnitude < 100_000_000_000 {
+                    value.saturating_mul(1000)
+                } else if magnitude < 100_000_000_000_000 {
+                    value
+                } else if magnitude < 100_000_000_000_000_000 {
+                    value / 1000
+                } else {
+                    value / 1_000_000
+                };
+                from_millis(millis)
+            }
+            (RawTimestamp::Epoch(value), format) => self.parse_text(&value.to_string(), format),
+            (RawTimestamp::Text(text), format) => self.parse_text(&text, format),
+        }
+    }
+
+    /// Parse a textual timestamp
+    fn parse_text(&self, text: &str, format: &TimestampFormat) -> Result<DateTime<Utc>> {
+        let offset = FixedOffset::east_opt(self.config.source_utc_offset_minutes * 60)
+            .ok_or_else(|| anyhow::anyhow!("Invalid source UTC offset"))?;
+        let localize = |naive: NaiveDateTime| -> Result<DateTime<Utc>> {
+            offset.from_local_datetime(&naive).single()
+                .map(|dt| dt.with_timezone(&Utc))
+                .ok_or_else(|| anyhow::anyhow!("ambiguous local time '{}'", naive))
+        };
+
+        match format {
+            TimestampFormat::Naive(pattern) => {
+                let naive = NaiveDateTime::parse_from_str(text, pattern)
+                    .with_context(|| format!("timestamp '{}' does not match '{}'", text, pattern))?;
+                localize(naive)
+            }
+            TimestampFormat::Rfc3339 => DateTime::parse_from_rfc3339(text)
+                .map(|dt| dt.with_timezone(&Utc))
+                .with_context(|| format!("invalid RFC 3339 timestamp '{}'", text)),
+            _ => {
+                if let Ok(dt) = DateTime::parse_from_rfc3339(text) {
+                    return Ok(dt.with_timezone(&Utc));
+                }
+
+                for pattern in ["%Y-%m-%d %H:%M:%S", "%Y-%m-%dT%H:%M:%S", "%Y-%m-%d %H:%M", "%Y/%m/%d %H:%M:%S"] {
+                    if let Ok(naive) = NaiveDateTime::parse_from_str(text, pattern) {
+                        return localize(naive);
+                    }
+                }
+
+                Err(anyhow::anyhow!("unrecognized timestamp '{}'", text))
+            }
+        }
+    }
+
+    /// Get configuration
+    pub fn get_config(&self) -> &DataLoaderConfig {
+        &self.config
+    }
+}
+
+impl Default for DataLoader {
+    fn default() -> Self {
+        Self::new(DataLoaderConfig::default())
+    }
+}
+
+/// Sort candles by time and drop duplicate timestamps (keeping the last)
+fn normalize_series(mut candles: Vec<Candle>) -> Vec<Candle> {
+    candles.sort_by_key(|c| c.timestamp);
+
+    let before = candles.len();
+    let mut deduped: Vec<Candle> = Vec::with_capacity(candles.len());
+    for candle in candles {
+        match deduped.last_mut() {
+            Some(last) if last.timestamp == candle.timestamp => *last = candle,
+            _ => deduped.push(candle),
+        }
+    }
+
+    if deduped.len() < before {
+        warn!("Dropped {} duplicate candles", before - deduped.len());
+    }
+
+    deduped
+}
+
+/// Convert loaded candles into the strategy candle type (millisecond open times)
+pub fn to_strategy_candles(candles: &[Candle]) -> Vec<StrategyCandle> {
+    candles.iter()
+        .map(|c| StrategyCandle {
+            open_time: c.timestamp.timestamp_millis(),
+            open: c.open,
+            high: c.high,
+            low: c.low,
+            close: c.close,
+            volume: c.volume,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_load_csv_normalizes_and_validates() {
+        let csv = "Timestamp,Open,High,Low,Close,Volume\n\
+                   1700000060000,101,102,100,101.5,10\n\
+                   1700000000,100,101,99,100.5,12\n\
+                   1700000060000,101,103,100,102,11\n";
+        let candles = DataLoader::default().load_csv_reader(csv.as_bytes()).unwrap();
+
+        assert_eq!(candles.len(), 2);
+        assert_eq!(candles[0].timestamp.timestamp(), 1_700_000_000);
+        assert_eq!(candles[1].close, 102.0);
+
+        let naive = DataLoader::new(DataLoaderConfig {
+            timestamp_format: TimestampFormat::Naive("%Y-%m-%d %H:%M".to_string()),
+            source_utc_offset_minutes: 120,
+            ..DataLoaderConfig::default()
+        });
+        let candles = naive.load_csv_reader("timestamp,open,high,low,close,volume\n2024-01-01 02:00,1,2,1,2,0\n".as_bytes()).unwrap();
+        assert_eq!(candles[0].timestamp, Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap());
+
+        let invalid = "timestamp,open,high,low,close,volume\n1700000000,100,99,98,100,1\n";
+        assert!(DataLoader::default().load_csv_reader(invalid.as_bytes()).is_err());
+        assert!(DataLoader::default().load_csv_reader("time,open\n".as_bytes()).is_err());
+    }
+}
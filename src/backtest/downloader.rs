@@ -0,0 +1,590 @@
+//! Historical Data Downloader
+//!
+//! This module pulls kline, funding-rate and open-interest history for any Bybit
+//! symbol from the public v5 market endpoints. History is walked forward in time
+//! windows; within a window, requests are paged backward by row count (1000
+//! klines, 200 funding/open-interest rows) until a page comes back short, so no
+//! row interval has to be assumed. Every window is appended to a CSV cache file
+//! as soon as it is complete, a new download resumes after the last cached
+//! timestamp, so interrupted downloads continue where they stopped, and history
+//! requested before the cached range is backfilled in front of it.
+
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use anyhow::{Context, Result};
+use chrono::{DateTime, TimeZone, Utc};
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tracing::{debug, info, warn};
+
+use crate::backtest::data_loader::{DataLoader, DataLoaderConfig, TimestampFormat};
+use crate::exchange::types::Candle;
+
+/// Maximum klines per request
+const KLINE_PAGE_LIMIT: i64 = 1000;
+
+/// Maximum funding or open-interest rows per request
+const SERIES_PAGE_LIMIT: i64 = 200;
+
+/// Span of funding history per cache window (30 days); pages within it go by row count
+const FUNDING_WINDOW_MS: i64 = 30 * 24 * 60 * 60 * 1000;
+
+/// Kind of history to download
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum HistoryKind {
+    /// OHLCV klines
+    Klines,
+
+    /// Funding rates
+    Funding,
+
+    /// Open interest
+    OpenInterest,
+}
+
+/// Historical funding rate
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FundingRecord {
+    /// Funding timestamp
+    pub timestamp: DateTime<Utc>,
+
+    /// Funding rate (fraction per interval)
+    pub funding_rate: f64,
+}
+
+/// Historical open interest
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OpenInterestRecord {
+    /// Snapshot timestamp
+    pub timestamp: DateTime<Utc>,
+
+    /// Open interest (contracts)
+    pub open_interest: f64,
+}
+
+/// Downloader configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DownloaderConfig {
+    /// API base URL
+    pub base_url: String,
+
+    /// Product category (`linear`, `inverse`, `spot`)
+    pub category: String,
+
+    /// Cache directory
+    pub cache_dir: PathBuf,
+
+    /// Delay between requests in milliseconds
+    pub request_delay_ms: u64,
+
+    /// Retries per page before giving up
+    pub max_retries: u32,
+}
+
+impl Default for DownloaderConfig {
+    fn default() -> Self {
+        Self {
+            base_url: "https://api.bybit.com".to_string(),
+            category: "linear".to_string(),
+            cache_dir: PathBuf::from("data/history"),
+            request_delay_ms: 100,
+            max_retries: 5,
+        }
+    }
+}
+
+/// Bybit historical data downloader
+pub struct HistoryDownloader {
+    /// Configuration
+    config: DownloaderConfig,
+
+    /// HTTP client
+    client: Client,
+}
+
+impl HistoryDownloader {
+    /// Create a new downloader
+    pub fn new(config: DownloaderConfig) -> Self {
+        Self {
+            config,
+            client: Client::new(),
+        }
+    }
+
+    /// Download klines for `[start, end)` and return the cached series
+    ///
+    /// `interval` uses Bybit notation (`1`, `5`, `60`, `240`, `D`, `W`).
+    pub async fn download_klines(&self, symbol: &str, interval: &str, start: DateTime<Utc>, end: DateTime<Utc>) -> Result<Vec<Candle>> {
+        let step = interval_millis(interval)?;
+        let path = self.cache_path(symbol, HistoryKind::Klines, interval);
+
+        self.download(&path, "timestamp,open,high,low,close,volume,turnover", start, end, step * KLINE_PAGE_LIMIT, step, KLINE_PAGE_LIMIT, |from, to| {
+            self.fetch_klines(symbol, interval, from, to)
+        }).await?;
+
+        let loader = DataLoader::new(DataLoaderConfig {
+            timestamp_format: TimestampFormat::UnixMillis,
+            skip_invalid_rows: true,
+            ..DataLoaderConfig::default()
+        });
+        let candles = loader.load_csv(&path)?;
+        Ok(candles.into_iter()
+            .filter(|c| c.timestamp >= start && c.timestamp < end)
+            .collect())
+    }
+
+    /// Download funding rates for `[start, end)` and return the cached series
+    pub async fn download_funding(&self, symbol: &str, start: DateTime<Utc>, end: DateTime<Utc>) -> Result<Vec<FundingRecord>> {
+        let path = self.cache_path(symbol, HistoryKind::Funding, "8h");
+
+        self.download(&path, "timestamp,funding_rate", start, end, FUNDING_WINDOW_MS, 1, SERIES_PAGE_LIMIT, |from, to| {
+            self.fetch_funding(symbol, from, to)
+        }).await?;
+
+        Ok(load_funding(&path)?.into_iter()
+            .filter(|f| f.timestamp >= start && f.timestamp < end)
+            .collect())
+    }
+
+    /// Download open interest for `[start, end)` and return the cached series
+    ///
+    /// `interval` is in minutes and must be one of 5, 15, 30, 60, 240 or 1440.
+    pub async fn download_open_interest(&self, symbol: &str, interval_minutes: u32, start: DateTime<Utc>, end: DateTime<Utc>) -> Result<Vec<OpenInterestRecord>> {
+        let interval_time = open_interest_interval(interval_minutes)?;
+        let step = interval_minutes as i64 * 60_000;
+        let path = self.cache_path(symbol, HistoryKind::OpenInterest, interval_time);
+
+        self.download(&path, "timestamp,open_interest", start, end, step * SERIES_PAGE_LIMIT, step, SERIES_PAGE_LIMIT, |from, to| {
+            self.fetch_open_interest(symbol, interval_time, from, to)
+        }).await?;
+
+        Ok(load_open_interest(&path)?.into_iter()
+            .filter(|r| r.timestamp >= start && r.timestamp < end)
+            .collect())
+    }
+
+    /// Cache file for a symbol and kind
+    pub fn cache_path(&self, symbol: &str, kind: HistoryKind, interval: &str) -> PathBuf {
+        let kind = match kind {
+            HistoryKind::Klines => "klines",
+            HistoryKind::Funding => "funding",
+            HistoryKind::OpenInterest => "open_interest",
+        };
+
+        self.config.cache_dir.join(format!("{}_{}_{}_{}.csv", self.config.category, symbol, kind, interval))
+    }
+
+    /// Walk `[start, end)` forward in windows of `page_span`, appending rows to the cache file
+    ///
+    /// A download resumes after the last cached row; history before the first cached
+    /// row is fetched and written in front of it. `fetch` returns (timestamp ms, CSV row)
+    /// pairs for a window, at most `page_limit` of them and the newest first when the
+    /// window holds more.
+    #[allow(clippy::too_many_arguments)]
+    async fn download<F, Fut>(
+        &self,
+        path: &Path,
+        header: &str,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+        page_span: i64,
+        step: i64,
+        page_limit: i64,
+        fetch: F,
+    ) -> Result<()>
+    where
+        F: Fn(i64, i64) -> Fut,
+        Fut: std::future::Future<Output = Result<Vec<(i64, String)>>>,
+    {
+        let end_ms = end.timestamp_millis();
+        let mut cursor = start.timestamp_millis();
+
+        if let Some((first, last)) = cached_range(path)? {
+            if cursor < first {
+                let backfill_end = first.min(end_ms);
+                let mut rows = Vec::new();
+                let mut window_start = cursor;
+                while window_start < backfill_end {
+                    let window_end = (window_start + page_span - 1).min(backfill_end - 1);
+                    rows.extend(self.fetch_window(window_start, window_end, page_limit, &fetch).await?);
+                    window_start = window_end + 1;
+                }
+                rows.retain(|(ts, _)| *ts < first);
+                prepend_to_cache(path, header, &rows)?;
+                info!("Backfilled {} rows before {} in {}", rows.len(), first, path.display());
+            }
+            if last + step > cursor {
+                info!("Resuming {} after {}", path.display(), last);
+                cursor = last + step;
+            }
+        }
+
+        if cursor >= end_ms {
+            debug!("{} already covers the requested range", path.display());
+            return Ok(());
+        }
+
+        let mut file = open_cache(path, header)?;
+        let mut written = 0usize;
+
+        while cursor < end_ms {
+            let window_end = (cursor + page_span - 1).min(end_ms - 1);
+            let rows = self.fetch_window(cursor, window_end, page_limit, &fetch).await?;
+
+            for (_, row) in &rows {
+                writeln!(file, "{}", row)?;
+            }
+            file.flush()?;
+            written += rows.len();
+
+            // The whole window is on disk
+            cursor = window_end + 1;
+        }
+
+        info!("Downloaded {} rows into {}", written, path.display());
+        Ok(())
+    }
+
+    /// Fetch every row of `[from, to]`, oldest first, paging backward from `to` while pages come back full
+    async fn fetch_window<F, Fut>(&self, from: i64, to: i64, page_limit: i64, fetch: &F) -> Result<Vec<(i64, String)>>
+    where
+        F: Fn(i64, i64) -> Fut,
+        Fut: std::future::Future<Output = Result<Vec<(i64, String)>>>,
+    {
+        let mut rows = Vec::new();
+        let mut page_end = to;
+        loop {
+            let page = self.with_retries(|| fetch(from, page_end)).await?;
+            let full = page.len() as i64 >= page_limit;
+            let oldest = page.iter().map(|(ts, _)| *ts).min();
+            rows.extend(page.into_iter().filter(|(ts, _)| *ts >= from && *ts <= page_end));
+            tokio::time::sleep(Duration::from_millis(self.config.request_delay_ms)).await;
+
+            // A short page holds the rest of the window
+            match oldest {
+                Some(oldest) if full && oldest > from && oldest <= page_end => page_end = oldest - 1,
+                _ => break,
+            }
+        }
+
+        rows.sort_by_key(|(ts, _)| *ts);
+        rows.dedup_by_key(|(ts, _)| *ts);
+        Ok(rows)
+    }
+
+    /// Run a request with exponential backoff
+    async fn with_retries<F, Fut, T>(&self, request: F) -> Result<T>
+    where
+        F: Fn() -> Fut,
+        Fut: std::future::Future<Output = Result<T>>,
+    {
+        let mut attempt = 0;
+        loop {
+            match request().await {
+                Ok(value) => return Ok(value),
+                Err(e) if attempt < self.config.max_retries => {
+                    attempt += 1;
+                    let delay = self.config.request_delay_ms.max(100) * 2u64.pow(attempt);
+                    warn!("Request failed (attempt {}/{}), retrying in {}ms: {}", attempt, self.config.max_retries, delay, e);
+                    tokio::time::sleep(Duration::from_millis(delay)).await;
+                }
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    /// GET a public market endpoint and return its `result` object
+    async fn get(&self, endpoint: &str, params: &[(&str, String)]) -> Result<Value> {
+        let url = format!("{}{}", self.config.base_url, endpoint);
+        let response = self.client.get(&url)
+            .query(params)
+            .send()
+            .await?
+            .json::<Value>()
+            .await?;
+
+        if response["retCode"].as_i64() != Some(0) {
+            return Err(anyhow::anyhow!("Bybit API error: {}", response["retMsg"].as_str().unwrap_or("unknown")));
+        }
+
+        Ok(response["result"].clone())
+    }
+
+    /// Fetch one page of klines
+    async fn fetch_klines(&self, symbol: &str, interval: &str, from: i64, to: i64) -> Result<Vec<(i64, String)>> {
+        let result = self.get("/v5/market/kline", &[
+            ("category", self.config.category.clone()),
+            ("symbol", symbol.to_string()),
+            ("interval", interval.to_string()),
+            ("start", from.to_string()),
+            ("end", to.to_string()),
+            ("limit", KLINE_PAGE_LIMIT.to_string()),
+        ]).await?;
+
+        list(&result)?.iter()
+            .map(|item| {
+                let fields: Vec<&str> = item.as_array()
+                    .ok_or_else(|| anyhow::anyhow!("Invalid kline item"))?
+                    .iter()
+                    .map(|v| v.as_str().unwrap_or(""))
+                    .collect();
+                if fields.len() < 7 {
+                    return Err(anyhow::anyhow!("Truncated kline item: {:?}", fields));
+                }
+                let timestamp: i64 = fields[0].parse()?;
+                Ok((timestamp, fields[..7].join(",")))
+            })
+            .collect()
+    }
+
+    /// Fetch one page of funding rates
+    async fn fetch_funding(&self, symbol: &str, from: i64, to: i64) -> Result<Vec<(i64, String)>> {
+        let result = self.get("/v5/market/funding/history", &[
+            ("category", self.config.category.clone()),
+            ("symbol", symbol.to_string()),
+            ("startTime", from.to_string()),
+            ("endTime", to.to_string()),
+            ("limit", SERIES_PAGE_LIMIT.to_string()),
+        ]).await?;
+
+        list(&result)?.iter()
+            .map(|item| {
+                let timestamp: i64 = item["fundingRateTimestamp"].as_str().unwrap_or("").parse()
+                    .context("Invalid funding timestamp")?;
+                let rate = item["fundingRate"].as_str().unwrap_or("0");
+                Ok((timestamp, format!("{},{}", timestamp, rate)))
+            })
+            .collect()
+    }
+
+    /// Fetch one page of open interest
+    async fn fetch_open_interest(&self, symbol: &str, interval_time: &str, from: i64, to: i64) -> Result<Vec<(i64, String)>> {
+        let result = self.get("/v5/market/open-interest", &[
+            ("category", self.config.category.clone()),
+            ("symbol", symbol.to_string()),
+            ("intervalTime", interval_time.to_string()),
+            ("startTime", from.to_string()),
+            ("endTime", to.to_string()),
+            ("limit", SERIES_PAGE_LIMIT.to_string()),
+        ]).await?;
+
+        list(&result)?.iter()
+            .map(|item| {
+                let timestamp: i64 = item["timestamp"].as_str().unwrap_or("").parse()
+                    .context("Invalid open interest timestamp")?;
+                let open_interest = item["openInterest"].as_str().unwrap_or("0");
+                Ok((timestamp, format!("{},{}", timestamp, open_interest)))
+            })
+            .collect()
+    }
+
+    /// Get configuration
+    pub fn get_config(&self) -> &DownloaderConfig {
+        &self.config
+    }
+}
+
+/// `list` array of a result object
+fn list(result: &Value) -> Result<&Vec<Value>> {
+    result["list"].as_array().ok_or_else(|| anyhow::anyhow!("No list"))
+}
+
+/// Kline interval in milliseconds for Bybit interval notation
+pub fn interval_millis(interval: &str) -> Result<i64> {
+    let minutes: i64 = match interval {
+        "D" => 1440,
+        "W" => 10080,
+        minutes => minutes.parse()
+            .map_err(|_| anyhow::anyhow!("Unsupported kline interval: {}", interval))?,
+    };
+
+    Ok(minutes * 60_000)
+}
+
+/// Bybit `intervalTime` for open-interest history
+fn open_interest_interval(minutes: u32) -> Result<&'static str> {
+    match minutes {
+        5 => Ok("5min"),
+        15 => Ok("15min"),
+        30 => Ok("30min"),
+        60 => Ok("1h"),
+        240 => Ok("4h"),
+        1440 => Ok("1d"),
+        _ => Err(anyhow::anyhow!("Unsupported open interest interval: {} minutes", minutes)),
+    }
+}
+
+/// Open a cache file for appending, writing the header if it is new
+///
+/// A partially written trailing line from an interrupted download is dropped.
+fn open_cache(path: &Path, header: &str) -> Result<File> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    if path.exists() {
+        let contents = fs::read(path)?;
+        if !contents.is_empty() && !contents.ends_with(b"\n") {
+            let keep = contents.iter().rposition(|b| *b == b'\n').map_or(0, |i| i + 1);
+            warn!("Dropping incomplete trailing row in {}", path.display());
+            OpenOptions::new().write(true).open(path)?.set_len(keep as u64)?;
+        }
+    }
+
+    let is_new = !path.exists() || fs::metadata(path)?.len() == 0;
+    let mut file = OpenOptions::new().create(true).append(true).open(path)
+        .with_context(|| format!("Failed to open cache {}", path.display()))?;
+    if is_new {
+        writeln!(file, "{}", header)?;
+    }
+
+    Ok(file)
+}
+
+/// Timestamps (ms) of the first and last complete rows in a cache file
+fn cached_range(path: &Path) -> Result<Option<(i64, i64)>> {
+    if !path.exists() {
+        return Ok(None);
+    }
+
+    let contents = fs::read_to_string(path)?;
+    let columns = match contents.lines().next() {
+        Some(header) => header.split(',').count(),
+        None => return Ok(None),
+    };
+
+    // Only newline-terminated rows with every column are complete
+    let mut rows: Vec<&str> = contents.lines().skip(1).collect();
+    if !contents.ends_with('\n') {
+        rows.pop();
+    }
+
+    let timestamps: Vec<i64> = rows.into_iter()
+        .filter(|line| line.split(',').count() == columns)
+        .filter_map(|line| line.split(',').next().and_then(|t| t.parse::<i64>().ok()))
+        .collect();
+    Ok(timestamps.iter().min().copied().zip(timestamps.iter().max().copied()))
+}
+
+/// Write `rows` between the header and the existing rows of a cache file
+///
+/// The file is replaced in one rename, so an interrupted backfill leaves the cache as it was.
+fn prepend_to_cache(path: &Path, header: &str, rows: &[(i64, String)]) -> Result<()> {
+    if rows.is_empty() {
+        return Ok(());
+    }
+
+    let contents = fs::read_to_string(path)?;
+    let existing = contents.lines().skip(1)
+        .take(contents.lines().count().saturating_sub(1 + usize::from(!contents.ends_with('\n'))));
+
+    let staging = path.with_extension("csv.partial");
+    let mut file = File::create(&staging)
+        .with_context(|| format!("Failed to create {}", staging.display()))?;
+    writeln!(file, "{}", header)?;
+    for (_, row) in rows {
+        writeln!(file, "{}", row)?;
+    }
+    for line in existing {
+        writeln!(file, "{}", line)?;
+    }
+    file.flush()?;
+    fs::rename(&staging, path)?;
+    Ok(())
+}
+
+/// Read `timestamp,value` rows from a cache file
+fn load_series(path: &Path) -> Result<Vec<(DateTime<Utc>, f64)>> {
+    let reader = BufReader::new(File::open(path)
+        .with_context(|| format!("Failed to open {}", path.display()))?);
+
+    let mut rows = Vec::new();
+    for line in reader.lines().skip(1) {
+        let line = line?;
+        let mut parts = line.split(',');
+        let parsed = parts.next().and_then(|t| t.parse::<i64>().ok())
+            .zip(parts.next().and_then(|v| v.parse::<f64>().ok()));
+
+        match parsed.and_then(|(ts, value)| Utc.timestamp_millis_opt(ts).single().map(|t| (t, value))) {
+            Some(row) => rows.push(row),
+            None => warn!("Skipping malformed row in {}: {}", path.display(), line),
+        }
+    }
+
+    rows.sort_by_key(|(t, _)| *t);
+    rows.dedup_by_key(|(t, _)| *t);
+    Ok(rows)
+}
+
+/// Load cached funding rates
+pub fn load_funding(path: &Path) -> Result<Vec<FundingRecord>> {
+    Ok(load_series(path)?.into_iter()
+        .map(|(timestamp, funding_rate)| FundingRecord { timestamp, funding_rate })
+        .collect())
+}
+
+/// Load cached open interest
+pub fn load_open_interest(path: &Path) -> Result<Vec<OpenInterestRecord>> {
+    Ok(load_series(path)?.into_iter()
+        .map(|(timestamp, open_interest)| OpenInterestRecord { timestamp, open_interest })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resume_ignores_incomplete_trailing_row() {
+        let path = std::env::temp_dir().join(format!("omni_downloader_{}.csv", std::process::id()));
+        fs::write(&path, "timestamp,funding_rate\n1000,0.0001\n2000,0.0002\n30").unwrap();
+
+        assert_eq!(cached_range(&path).unwrap(), Some((1000, 2000)));
+
+        let mut file = open_cache(&path, "timestamp,funding_rate").unwrap();
+        writeln!(file, "3000,0.0003").unwrap();
+        drop(file);
+
+        let funding = load_funding(&path).unwrap();
+        assert_eq!(funding.len(), 3);
+        assert_eq!(funding[2].funding_rate, 0.0003);
+
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_pages_by_row_count_and_backfills() {
+        let path = std::env::temp_dir().join(format!("omni_downloader_paging_{}.csv", std::process::id()));
+        let _ = fs::remove_file(&path);
+        let downloader = HistoryDownloader::new(DownloaderConfig { request_delay_ms: 0, ..DownloaderConfig::default() });
+
+        // Hourly rows, served newest first and at most 3 per page
+        let hour = 60 * 60 * 1000;
+        let fetch = |from: i64, to: i64| async move {
+            let mut rows: Vec<(i64, String)> = (0..48i64).map(|h| h * hour)
+                .filter(|ts| *ts >= from && *ts <= to)
+                .map(|ts| (ts, format!("{},0.0001", ts)))
+                .collect();
+            rows.reverse();
+            rows.truncate(3);
+            Ok(rows)
+        };
+        let at = |ms: i64| Utc.timestamp_millis_opt(ms).unwrap();
+
+        downloader.download(&path, "timestamp,funding_rate", at(24 * hour), at(48 * hour), 10 * hour, 1, 3, fetch).await.unwrap();
+        assert_eq!(load_funding(&path).unwrap().len(), 24);
+
+        // An earlier start fills the gap in front of the cached range
+        downloader.download(&path, "timestamp,funding_rate", at(0), at(48 * hour), 10 * hour, 1, 3, fetch).await.unwrap();
+        let funding = load_funding(&path).unwrap();
+        assert_eq!(funding.len(), 48);
+        assert_eq!(cached_range(&path).unwrap(), Some((0, 47 * hour)));
+        let contents = fs::read_to_string(&path).unwrap();
+        assert!(contents.lines().nth(1).is_some_and(|row| row.starts_with("0,")));
+
+        fs::remove_file(&path).unwrap();
+    }
+}
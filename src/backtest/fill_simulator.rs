@@ -0,0 +1,382 @@
+//! Order-Book Fill Simulator
+//!
+//! This module simulates fills against recorded order-book snapshots and trades.
+//! Market orders walk the book level by level, so slippage grows with size and
+//! thin depth. Limit orders join the back of the queue at their price level and
+//! only fill once the volume ahead of them has traded or been cancelled, which is
+//! what market-making and TWAP execution need to be validated offline.
+
+use std::collections::HashMap;
+use serde::{Deserialize, Serialize};
+use tracing::debug;
+
+use crate::exchange::bybit::types::BybitOrderbook;
+
+/// Order side
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SimOrderSide {
+    /// Buy
+    Buy,
+
+    /// Sell
+    Sell,
+}
+
+/// Order-book snapshot with bids sorted high to low and asks low to high
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OrderBookSnapshot {
+    /// Snapshot time (ms)
+    pub timestamp: i64,
+
+    /// Bid levels (price, size)
+    pub bids: Vec<(f64, f64)>,
+
+    /// Ask levels (price, size)
+    pub asks: Vec<(f64, f64)>,
+}
+
+impl OrderBookSnapshot {
+    /// Create a snapshot, sorting the levels
+    pub fn new(timestamp: i64, mut bids: Vec<(f64, f64)>, mut asks: Vec<(f64, f64)>) -> Self {
+        bids.sort_by(|a, b| b.0.partial_cmp(&a.0).unwrap_or(std::cmp::Ordering::Equal));
+        asks.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Equal));
+        Self { timestamp, bids, asks }
+    }
+
+    /// Build from a Bybit order book
+    pub fn from_bybit(book: &BybitOrderbook) -> Self {
+        Self::new(book.timestamp, book.bids.clone(), book.asks.clone())
+    }
+
+    /// Best bid price
+    pub fn best_bid(&self) -> Option<f64> {
+        self.bids.first().map(|l| l.0)
+    }
+
+    /// Best ask price
+    pub fn best_ask(&self) -> Option<f64> {
+        self.asks.first().map(|l| l.0)
+    }
+
+    /// Mid price
+    pub fn mid_price(&self) -> Option<f64> {
+        Some((self.best_bid()? + self.best_ask()?) / 2.0)
+    }
+
+    /// Bid-ask spread
+    pub fn spread(&self) -> Option<f64> {
+        Some(self.best_ask()? - self.best_bid()?)
+    }
+
+    /// Resting size at a price on one side
+    pub fn size_at(&self, side: SimOrderSide, price: f64) -> f64 {
+        let levels = match side {
+            SimOrderSide::Buy => &self.bids,
+            SimOrderSide::Sell => &self.asks,
+        };
+
+        levels.iter()
+            .find(|(level, _)| (level - price).abs() <= f64::EPSILON * price.abs().max(1.0))
+            .map(|(_, size)| *size)
+            .unwrap_or(0.0)
+    }
+}
+
+/// Result of a simulated market order
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MarketFill {
+    /// Side
+    pub side: SimOrderSide,
+
+    /// Requested quantity
+    pub requested_quantity: f64,
+
+    /// Filled quantity (less than requested if the book ran out)
+    pub filled_quantity: f64,
+
+    /// Volume-weighted fill price
+    pub average_price: f64,
+
+    /// Slippage versus the mid price in bps
+    pub slippage_bps: f64,
+
+    /// Number of price levels consumed
+    pub levels_consumed: usize,
+}
+
+/// Walk the book for a market order, optionally bounded by a limit price
+pub fn simulate_market_order(book: &OrderBookSnapshot, side: SimOrderSide, quantity: f64, limit_price: Option<f64>) -> MarketFill {
+    let levels = match side {
+        SimOrderSide::Buy => &book.asks,
+        SimOrderSide::Sell => &book.bids,
+    };
+
+    let mut remaining = quantity;
+    let mut cost = 0.0;
+    let mut levels_consumed = 0;
+
+    for &(price, size) in levels {
+        if remaining <= 0.0 {
+            break;
+        }
+        let within_limit = match (side, limit_price) {
+            (SimOrderSide::Buy, Some(limit)) => price <= limit,
+            (SimOrderSide::Sell, Some(limit)) => price >= limit,
+            (_, None) => true,
+        };
+        if !within_limit {
+            break;
+        }
+
+        let take = remaining.min(size);
+        cost += take * price;
+        remaining -= take;
+        levels_consumed += 1;
+    }
+
+    let filled_quantity = quantity - remaining;
+    let average_price = if filled_quantity > 0.0 { cost / filled_quantity } else { 0.0 };
+    let slippage_bps = match book.mid_price() {
+        Some(mid) if filled_quantity > 0.0 && mid > 0.0 => {
+            let adverse = match side {
+                SimOrderSide::Buy => average_price - mid,
+                SimOrderSide::Sell => mid - average_price,
+            };
+            adverse / mid * 10_000.0
+        }
+        _ => 0.0,
+    };
+
+    MarketFill {
+        side,
+        requested_quantity: quantity,
+        filled_quantity,
+        average_price,
+        slippage_bps,
+        levels_consumed,
+    }
+}
+
+/// Resting simulated limit order
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SimLimitOrder {
+    /// Order ID
+    pub id: String,
+
+    /// Side
+    pub side: SimOrderSide,
+
+    /// Limit price
+    pub price: f64,
+
+    /// Original quantity
+    pub quantity: f64,
+
+    /// Filled quantity
+    pub filled_quantity: f64,
+
+    /// Volume queued ahead of the order at its price level
+    pub queue_ahead: f64,
+}
+
+impl SimLimitOrder {
+    /// Unfilled quantity
+    pub fn remaining(&self) -> f64 {
+        (self.quantity - self.filled_quantity).max(0.0)
+    }
+}
+
+/// Fill of a simulated limit order
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LimitFill {
+    /// Order ID
+    pub order_id: String,
+
+    /// Fill price
+    pub price: f64,
+
+    /// Filled quantity
+    pub quantity: f64,
+
+    /// Whether the order added liquidity
+    pub is_maker: bool,
+
+    /// Quantity left after this fill
+    pub remaining: f64,
+}
+
+/// Queue-position-aware fill simulator for one symbol
+#[derive(Debug, Clone, Default)]
+pub struct FillSimulator {
+    /// Resting orders by ID
+    orders: HashMap<String, SimLimitOrder>,
+
+    /// Next order number
+    next_id: u64,
+}
+
+impl FillSimulator {
+    /// Create a new fill simulator
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Place a limit order against the current book
+    ///
+    /// A marketable order first takes liquidity up to its price; the remainder rests
+    /// at the back of the queue behind the size already displayed at that price.
+    pub fn place_limit(&mut self, book: &OrderBookSnapshot, side: SimOrderSide, price: f64, quantity: f64) -> (String, Vec<LimitFill>) {
+        self.next_id += 1;
+        let id = format!("sim-{}", self.next_id);
+        let mut fills = Vec::new();
+
+        let taker = simulate_market_order(book, side, quantity, Some(price));
+        if taker.filled_quantity > 0.0 {
+            fills.push(LimitFill {
+                order_id: id.clone(),
+                price: taker.average_price,
+                quantity: taker.filled_quantity,
+                is_maker: false,
+                remaining: quantity - taker.filled_quantity,
+            });
+        }
+
+        if taker.filled_quantity < quantity {
+            let order = SimLimitOrder {
+                id: id.clone(),
+                side,
+                price,
+                quantity,
+                filled_quantity: taker.filled_quantity,
+                queue_ahead: book.size_at(side, price),
+            };
+            debug!("Resting {:?} {} @ {} with {} ahead", side, order.remaining(), price, order.queue_ahead);
+            self.orders.insert(id.clone(), order);
+        }
+
+        (id, fills)
+    }
+
+    /// Process a public trade; `aggressor` is the side that crossed the spread
+    ///
+    /// Trades through a resting price fill the order completely; trades at the price
+    /// first consume the queue ahead and only then fill the order.
+    pub fn on_trade(&mut self, price: f64, quantity: f64, aggressor: SimOrderSide) -> Vec<LimitFill> {
+        let mut fills = Vec::new();
+
+        // Oldest orders first so queue priority between our own orders is respected
+        let mut ids: Vec<String> = self.orders.keys().cloned().collect();
+        ids.sort_by_key(|id| id.trim_start_matches("sim-").parse::<u64>().unwrap_or(u64::MAX));
+
+        let mut available = quantity;
+        for id in ids {
+            let order = match self.orders.get_mut(&id) {
+                Some(order) => order,
+                None => continue,
+            };
+            if order.side == aggressor {
+                continue;
+            }
+
+            let through = match order.side {
+                SimOrderSide::Buy => price < order.price,
+                SimOrderSide::Sell => price > order.price,
+            };
+            let at_level = (price - order.price).abs() <= f64::EPSILON * price.abs().max(1.0);
+
+            let fill_quantity = if through {
+                order.remaining()
+            } else if at_level && available > 0.0 {
+                let consumed_queue = available.min(order.queue_ahead);
+                order.queue_ahead -= consumed_queue;
+                available -= consumed_queue;
+
+                let fill = available.min(order.remaining());
+                available -= fill;
+                fill
+            } else {
+                0.0
+            };
+
+            if fill_quantity > 0.0 {
+                order.filled_quantity += fill_quantity;
+                fills.push(LimitFill {
+                    order_id: id.clone(),
+                    price: order.price,
+                    quantity: fill_quantity,
+                    is_maker: true,
+                    remaining: order.remaining(),
+                });
+            }
+        }
+
+        self.orders.retain(|_, order| order.remaining() > 0.0);
+        fills
+    }
+
+    /// Process a book update: cancellations ahead shrink the queue
+    pub fn on_book_update(&mut self, book: &OrderBookSnapshot) {
+        for order in self.orders.values_mut() {
+            let displayed = book.size_at(order.side, order.price);
+            order.queue_ahead = order.queue_ahead.min(displayed);
+        }
+    }
+
+    /// Cancel a resting order
+    pub fn cancel(&mut self, order_id: &str) -> Option<SimLimitOrder> {
+        self.orders.remove(order_id)
+    }
+
+    /// Get a resting order
+    pub fn get_order(&self, order_id: &str) -> Option<&SimLimitOrder> {
+        self.orders.get(order_id)
+    }
+
+    /// All resting orders
+    pub fn get_open_orders(&self) -> Vec<&SimLimitOrder> {
+        self.orders.values().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn book() -> OrderBookSnapshot {
+        OrderBookSnapshot::new(
+            0,
+            vec![(99.0, 5.0), (98.0, 10.0)],
+            vec![(101.0, 2.0), (102.0, 3.0), (103.0, 10.0)],
+        )
+    }
+
+    #[test]
+    fn test_market_order_walks_depth() {
+        let small = simulate_market_order(&book(), SimOrderSide::Buy, 1.0, None);
+        let large = simulate_market_order(&book(), SimOrderSide::Buy, 5.0, None);
+
+        assert_eq!(small.average_price, 101.0);
+        assert!((large.average_price - 101.6).abs() < 1e-9);
+        assert_eq!(large.levels_consumed, 2);
+        assert!(large.slippage_bps > small.slippage_bps);
+    }
+
+    #[test]
+    fn test_limit_order_waits_for_queue() {
+        let mut simulator = FillSimulator::new();
+        let (id, fills) = simulator.place_limit(&book(), SimOrderSide::Buy, 99.0, 2.0);
+        assert!(fills.is_empty());
+        assert_eq!(simulator.get_order(&id).unwrap().queue_ahead, 5.0);
+
+        // 4 of the 5 ahead trade, then 1 is cancelled
+        assert!(simulator.on_trade(99.0, 4.0, SimOrderSide::Sell).is_empty());
+        simulator.on_book_update(&OrderBookSnapshot::new(1, vec![(99.0, 0.0)], vec![(101.0, 2.0)]));
+
+        let fills = simulator.on_trade(99.0, 1.5, SimOrderSide::Sell);
+        assert_eq!(fills[0].quantity, 1.5);
+
+        let fills = simulator.on_trade(98.5, 1.0, SimOrderSide::Sell);
+        assert_eq!(fills[0].quantity, 0.5);
+        assert!(simulator.get_open_orders().is_empty());
+    }
+}
@@ -0,0 +1,291 @@
+//! Order Latency and Rejection Simulation
+//!
+//! This module delays orders between submission and exchange acknowledgement and
+//! rejects a configurable share of them. An order is filled at the first price
+//! observed after its acknowledgement time rather than at the price the strategy
+//! saw, so strategies that depend on reacting within milliseconds are no longer
+//! evaluated under zero-latency assumptions.
+
+use std::collections::VecDeque;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+use tracing::debug;
+
+/// Latency and rejection configuration (times in milliseconds)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LatencyConfig {
+    /// Minimum time from submission to acknowledgement
+    pub base_latency_ms: u64,
+
+    /// Additional uniformly distributed latency
+    pub jitter_ms: u64,
+
+    /// Probability (0.0-1.0) that the exchange rejects an order
+    pub rejection_probability: f64,
+
+    /// Reject orders whose fill price moved more than this many bps from submission
+    pub max_price_deviation_bps: Option<f64>,
+
+    /// RNG seed for reproducible runs
+    pub seed: Option<u64>,
+}
+
+impl Default for LatencyConfig {
+    fn default() -> Self {
+        Self {
+            base_latency_ms: 50,
+            jitter_ms: 100,
+            rejection_probability: 0.01,
+            max_price_deviation_bps: None,
+            seed: None,
+        }
+    }
+}
+
+/// What a submitted order does once acknowledged
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum OrderIntent {
+    /// Open a position
+    Open { symbol: String, side: String, quantity: f64 },
+
+    /// Close an open position
+    Close { symbol: String, trade_id: String },
+}
+
+impl OrderIntent {
+    /// Symbol the order trades
+    pub fn symbol(&self) -> &str {
+        match self {
+            OrderIntent::Open { symbol, .. } | OrderIntent::Close { symbol, .. } => symbol,
+        }
+    }
+}
+
+/// Order waiting for acknowledgement
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PendingOrder {
+    /// Order ID
+    pub id: u64,
+
+    /// Intent
+    pub intent: OrderIntent,
+
+    /// Submission time
+    pub submitted_at_ms: u64,
+
+    /// Acknowledgement time
+    pub ack_at_ms: u64,
+
+    /// Price seen by the strategy at submission
+    pub reference_price: f64,
+}
+
+/// Outcome of a pending order
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum OrderOutcome {
+    /// Acknowledged and filled at `price`; `trade_id` is set by the engine on execution
+    Accepted { order: PendingOrder, price: f64, time_ms: u64, trade_id: Option<String> },
+
+    /// Rejected by the exchange
+    Rejected { order: PendingOrder, time_ms: u64, reason: String },
+}
+
+/// Serializable latency simulator state
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LatencyCheckpoint {
+    /// Configuration
+    pub config: LatencyConfig,
+
+    /// In-flight orders
+    pub pending: Vec<PendingOrder>,
+
+    /// Seed the RNG continues from
+    pub rng_seed: u64,
+
+    /// Next order ID
+    pub next_id: u64,
+
+    /// Orders rejected so far
+    pub rejected_count: u64,
+
+    /// Orders accepted so far
+    pub accepted_count: u64,
+}
+
+/// Queue of in-flight orders with simulated latency and rejections
+#[derive(Debug, Clone)]
+pub struct LatencySimulator {
+    /// Configuration
+    config: LatencyConfig,
+
+    /// In-flight orders ordered by acknowledgement time
+    pending: VecDeque<PendingOrder>,
+
+    /// Random number generator
+    rng: StdRng,
+
+    /// Next order ID
+    next_id: u64,
+
+    /// Orders rejected so far
+    rejected_count: u64,
+
+    /// Orders accepted so far
+    accepted_count: u64,
+}
+
+impl LatencySimulator {
+    /// Create a new latency simulator
+    pub fn new(config: LatencyConfig) -> Self {
+        let rng = match config.seed {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => StdRng::from_entropy(),
+        };
+
+        Self {
+            config,
+            pending: VecDeque::new(),
+            rng,
+            next_id: 0,
+            rejected_count: 0,
+            accepted_count: 0,
+        }
+    }
+
+    /// Submit an order; returns its ID
+    pub fn submit(&mut self, intent: OrderIntent, now_ms: u64, reference_price: f64) -> u64 {
+        self.next_id += 1;
+        let jitter = if self.config.jitter_ms > 0 { self.rng.gen_range(0..=self.config.jitter_ms) } else { 0 };
+        let order = PendingOrder {
+            id: self.next_id,
+            intent,
+            submitted_at_ms: now_ms,
+            ack_at_ms: now_ms + self.config.base_latency_ms + jitter,
+            reference_price,
+        };
+
+        debug!("Order {} submitted at {} ms, ack at {} ms", order.id, now_ms, order.ack_at_ms);
+        let position = self.pending.iter().position(|o| o.ack_at_ms > order.ack_at_ms).unwrap_or(self.pending.len());
+        self.pending.insert(position, order);
+        self.next_id
+    }
+
+    /// Resolve orders on `symbol` acknowledged by `now_ms`, filling at `price`
+    pub fn on_price(&mut self, symbol: &str, price: f64, now_ms: u64) -> Vec<OrderOutcome> {
+        let mut outcomes = Vec::new();
+        let mut remaining = VecDeque::with_capacity(self.pending.len());
+
+        while let Some(order) = self.pending.pop_front() {
+            if order.ack_at_ms > now_ms || order.intent.symbol() != symbol {
+                remaining.push_back(order);
+                continue;
+            }
+
+            let deviation_bps = if order.reference_price > 0.0 {
+                (price - order.reference_price).abs() / order.reference_price * 10_000.0
+            } else {
+                0.0
+            };
+
+            // Closing orders are never rejected for price protection
+            let price_rejected = matches!(order.intent, OrderIntent::Open { .. })
+                && self.config.max_price_deviation_bps.map_or(false, |max| deviation_bps > max);
+
+            if price_rejected {
+                self.rejected_count += 1;
+                outcomes.push(OrderOutcome::Rejected {
+                    reason: format!("Price moved {:.1} bps during latency", deviation_bps),
+                    order,
+                    time_ms: now_ms,
+                });
+            } else if self.rng.gen::<f64>() < self.config.rejection_probability {
+                self.rejected_count += 1;
+                outcomes.push(OrderOutcome::Rejected {
+                    order,
+                    time_ms: now_ms,
+                    reason: "Simulated exchange rejection".to_string(),
+                });
+            } else {
+                self.accepted_count += 1;
+                outcomes.push(OrderOutcome::Accepted { order, price, time_ms: now_ms, trade_id: None });
+            }
+        }
+
+        self.pending = remaining;
+        outcomes
+    }
+
+    /// Capture the simulator state
+    ///
+    /// The RNG is reseeded from itself so a run that continues and a run restored
+    /// from this checkpoint draw the same sequence.
+    pub fn checkpoint(&mut self) -> LatencyCheckpoint {
+        let rng_seed = self.rng.gen();
+        self.rng = StdRng::seed_from_u64(rng_seed);
+
+        LatencyCheckpoint {
+            config: self.config.clone(),
+            pending: self.pending.iter().cloned().collect(),
+            rng_seed,
+            next_id: self.next_id,
+            rejected_count: self.rejected_count,
+            accepted_count: self.accepted_count,
+        }
+    }
+
+    /// Restore a simulator from a checkpoint
+    pub fn from_checkpoint(checkpoint: LatencyCheckpoint) -> Self {
+        Self {
+            config: checkpoint.config,
+            pending: checkpoint.pending.into(),
+            rng: StdRng::seed_from_u64(checkpoint.rng_seed),
+            next_id: checkpoint.next_id,
+            rejected_count: checkpoint.rejected_count,
+            accepted_count: checkpoint.accepted_count,
+        }
+    }
+
+    /// Orders still in flight
+    pub fn get_pending(&self) -> impl Iterator<Item = &PendingOrder> {
+        self.pending.iter()
+    }
+
+    /// Share of resolved orders that were rejected
+    pub fn get_rejection_rate(&self) -> f64 {
+        let total = self.accepted_count + self.rejected_count;
+        if total > 0 { self.rejected_count as f64 / total as f64 } else { 0.0 }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_orders_wait_for_ack_and_can_be_rejected() {
+        let mut simulator = LatencySimulator::new(LatencyConfig {
+            base_latency_ms: 100,
+            jitter_ms: 0,
+            rejection_probability: 0.0,
+            max_price_deviation_bps: Some(50.0),
+            seed: Some(1),
+        });
+
+        let open = OrderIntent::Open { symbol: "BTCUSDT".to_string(), side: "long".to_string(), quantity: 1.0 };
+        simulator.submit(open.clone(), 1_000, 100.0);
+        simulator.submit(open, 1_000, 100.0);
+
+        assert!(simulator.on_price("BTCUSDT", 100.0, 1_050).is_empty());
+        assert!(simulator.on_price("ETHUSDT", 100.0, 1_100).is_empty());
+
+        let outcomes = simulator.on_price("BTCUSDT", 100.2, 1_100);
+        assert_eq!(outcomes.len(), 2);
+        assert!(matches!(outcomes[0], OrderOutcome::Accepted { price, .. } if price == 100.2));
+
+        simulator.submit(OrderIntent::Open { symbol: "BTCUSDT".to_string(), side: "long".to_string(), quantity: 1.0 }, 2_000, 100.0);
+        let outcomes = simulator.on_price("BTCUSDT", 101.0, 2_200);
+        assert!(matches!(outcomes[0], OrderOutcome::Rejected { .. }));
+        assert!(simulator.get_rejection_rate() > 0.0);
+    }
+}
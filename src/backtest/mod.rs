@@ -0,0 +1,932 @@
+//! Backtesting Framework Module for OMNI Trading System
+//!
+//! This module provides comprehensive backtesting capabilities for strategy validation.
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+use anyhow::Result;
+
+use crate::exchange::asset_scanner::SymbolUniverse;
+use crate::position::calculator::{price_pnl, PnlBreakdown};
+
+pub mod benchmark;
+pub mod checkpoint;
+pub mod costs;
+pub mod data_loader;
+pub mod downloader;
+pub mod fill_simulator;
+pub mod latency;
+pub mod monte_carlo;
+pub mod parallel;
+pub mod parity;
+pub mod portfolio;
+pub mod report;
+pub mod trade_record;
+pub mod walk_forward;
+
+pub use checkpoint::{Checkpointer, EngineCheckpoint};
+pub use costs::{FeeModel, FeeModelConfig, FillContext, SlippageModel, SlippageModelConfig};
+
+use latency::{LatencyConfig, LatencySimulator, OrderIntent, OrderOutcome};
+use monte_carlo::{MonteCarloConfig, MonteCarloReport};
+
+pub use data_loader::{DataLoader, DataLoaderConfig, TimestampFormat};
+pub use downloader::{HistoryDownloader, DownloaderConfig, FundingRecord, OpenInterestRecord};
+pub use report::BacktestReport;
+pub use trade_record::{TradeExportFormat, TradeOrigin, TradeRecord};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BacktestConfig {
+    pub start_date: u64,
+    pub end_date: u64,
+    pub initial_capital: f64,
+    pub symbols: Vec<String>,
+    pub timeframe: String,
+    pub fee_model: FeeModelConfig,
+    pub slippage_model: SlippageModelConfig,
+    pub max_positions: usize,
+    /// RNG seed for reproducible runs (trade IDs, latency and rejections)
+    #[serde(default)]
+    pub seed: Option<u64>,
+    /// Listing history; when set, only symbols listed at the simulated time can be traded
+    #[serde(default)]
+    pub universe: Option<SymbolUniverse>,
+}
+
+impl BacktestConfig {
+    pub fn new(
+        start_date: u64,
+        end_date: u64,
+        initial_capital: f64,
+        symbols: Vec<String>,
+    ) -> Self {
+        Self {
+            start_date,
+            end_date,
+            initial_capital,
+            symbols,
+            timeframe: "1h".to_string(),
+            fee_model: FeeModelConfig::default(),
+            slippage_model: SlippageModelConfig::default(),
+            max_positions: 10,
+            seed: None,
+            universe: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BacktestTrade {
+    pub id: String,
+    pub symbol: String,
+    pub entry_time: u64,
+    pub exit_time: u64,
+    pub entry_price: f64,
+    pub exit_price: f64,
+    pub quantity: f64,
+    pub side: String, // "long" or "short"
+    pub profit_loss: f64,
+    pub commission: f64,
+    /// Net funding paid while the position was open (negative when received)
+    #[serde(default)]
+    pub funding: f64,
+    /// Funding settled up to this time (seconds)
+    #[serde(default)]
+    pub funding_settled_until: u64,
+    pub return_percentage: f64,
+    /// Strategy that opened the trade
+    #[serde(default)]
+    pub strategy_id: String,
+    /// Largest move against the position in % of entry price
+    #[serde(default)]
+    pub max_adverse_excursion: f64,
+    /// Largest move in favor of the position in % of entry price
+    #[serde(default)]
+    pub max_favorable_excursion: f64,
+    /// Loss at the initial stop (1R) in quote currency, if a stop was set
+    #[serde(default)]
+    pub initial_risk: Option<f64>,
+}
+
+impl BacktestTrade {
+    pub fn new(
+        symbol: String,
+        entry_time: u64,
+        entry_price: f64,
+        quantity: f64,
+        side: String,
+    ) -> Self {
+        let id = format!("trade_{}_{}", entry_time, rand::random::<u32>());
+        
+        Self {
+            id,
+            symbol,
+            entry_time,
+            exit_time: 0,
+            entry_price,
+            exit_price: 0.0,
+            quantity,
+            side,
+            profit_loss: 0.0,
+            commission: 0.0,
+            funding: 0.0,
+            funding_settled_until: entry_time,
+            return_percentage: 0.0,
+            strategy_id: String::new(),
+            max_adverse_excursion: 0.0,
+            max_favorable_excursion: 0.0,
+            initial_risk: None,
+        }
+    }
+
+    /// Record the initial stop; defines the trade's 1R
+    pub fn set_initial_stop(&mut self, stop: f64) {
+        let risk = (self.entry_price - stop).abs() * self.quantity;
+        self.initial_risk = (risk > 0.0).then_some(risk);
+    }
+
+    /// Net P&L in multiples of the initial risk
+    pub fn r_multiple(&self) -> Option<f64> {
+        self.initial_risk.map(|risk| self.profit_loss / risk)
+    }
+
+    /// Update MAE/MFE with an observed price
+    pub fn track_excursion(&mut self, price: f64) {
+        let excursion = trade_record::excursion_percent(self.entry_price, price, self.side == "long");
+        self.max_favorable_excursion = self.max_favorable_excursion.max(excursion);
+        self.max_adverse_excursion = self.max_adverse_excursion.max(-excursion);
+    }
+
+    pub fn close_trade(&mut self, exit_time: u64, exit_price: f64, exit_commission: f64) {
+        self.exit_time = exit_time;
+        self.exit_price = exit_price;
+        self.track_excursion(exit_price);
+        
+        // `commission` already holds the entry fee
+        self.commission += exit_commission;
+        let pnl = PnlBreakdown::new(
+            price_pnl(self.side == "long", self.entry_price, exit_price, self.quantity),
+            self.commission,
+            self.funding,
+            self.entry_price * self.quantity,
+        );
+        self.profit_loss = pnl.net_pnl;
+        self.return_percentage = pnl.return_percent;
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BacktestResult {
+    pub config: BacktestConfig,
+    pub trades: Vec<BacktestTrade>,
+    pub total_trades: u32,
+    pub winning_trades: u32,
+    pub losing_trades: u32,
+    pub win_rate: f64,
+    pub total_profit_loss: f64,
+    pub total_return: f64,
+    pub max_drawdown: f64,
+    pub sharpe_ratio: f64,
+    pub sortino_ratio: f64,
+    pub profit_factor: f64,
+    pub average_win: f64,
+    pub average_loss: f64,
+    pub largest_win: f64,
+    pub largest_loss: f64,
+    pub total_commission: f64,
+    pub total_funding: f64,
+    pub final_capital: f64,
+    pub duration_days: f64,
+    /// Compound annual growth rate in %
+    #[serde(default)]
+    pub cagr: f64,
+    /// CAGR divided by max drawdown
+    #[serde(default)]
+    pub calmar_ratio: f64,
+    /// Average P&L per trade
+    #[serde(default)]
+    pub expectancy: f64,
+    /// Average time a position was held, in hours
+    #[serde(default)]
+    pub average_holding_hours: f64,
+    /// Share of the backtest period with at least one open position, in %
+    #[serde(default)]
+    pub exposure_percent: f64,
+    /// Per-symbol breakdown
+    #[serde(default)]
+    pub symbol_stats: BTreeMap<String, SymbolStats>,
+}
+
+/// Per-symbol backtest statistics
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SymbolStats {
+    pub total_trades: u32,
+    pub winning_trades: u32,
+    pub win_rate: f64,
+    pub total_profit_loss: f64,
+    pub profit_factor: f64,
+    pub expectancy: f64,
+    pub average_holding_hours: f64,
+    pub total_commission: f64,
+}
+
+impl BacktestResult {
+    pub fn new(config: BacktestConfig, trades: Vec<BacktestTrade>) -> Self {
+        let mut result = Self {
+            config: config.clone(),
+            trades: trades.clone(),
+            total_trades: trades.len() as u32,
+            winning_trades: 0,
+            losing_trades: 0,
+            win_rate: 0.0,
+            total_profit_loss: 0.0,
+            total_return: 0.0,
+            max_drawdown: 0.0,
+            sharpe_ratio: 0.0,
+            sortino_ratio: 0.0,
+            profit_factor: 0.0,
+            average_win: 0.0,
+            average_loss: 0.0,
+            largest_win: 0.0,
+            largest_loss: 0.0,
+            total_commission: 0.0,
+            total_funding: 0.0,
+            final_capital: config.initial_capital,
+            duration_days: ((config.end_date - config.start_date) as f64) / 86400.0,
+            cagr: 0.0,
+            calmar_ratio: 0.0,
+            expectancy: 0.0,
+            average_holding_hours: 0.0,
+            exposure_percent: 0.0,
+            symbol_stats: BTreeMap::new(),
+        };
+
+        result.calculate_metrics();
+        result
+    }
+
+    fn calculate_metrics(&mut self) {
+        if self.trades.is_empty() {
+            return;
+        }
+
+        let mut wins = Vec::new();
+        let mut losses = Vec::new();
+        let mut running_capital = self.config.initial_capital;
+        let mut peak_capital = running_capital;
+        let mut max_drawdown = 0.0;
+
+        for trade in &self.trades {
+            self.total_profit_loss += trade.profit_loss;
+            self.total_commission += trade.commission;
+            self.total_funding += trade.funding;
+            running_capital += trade.profit_loss;
+
+            if trade.profit_loss > 0.0 {
+                self.winning_trades += 1;
+                wins.push(trade.profit_loss);
+                if trade.profit_loss > self.largest_win {
+                    self.largest_win = trade.profit_loss;
+                }
+            } else if trade.profit_loss < 0.0 {
+                self.losing_trades += 1;
+                losses.push(trade.profit_loss.abs());
+                if trade.profit_loss < self.largest_loss {
+                    self.largest_loss = trade.profit_loss;
+                }
+            }
+
+            // Track drawdown
+            if running_capital > peak_capital {
+                peak_capital = running_capital;
+            } else {
+                let drawdown = (peak_capital - running_capital) / peak_capital;
+                if drawdown > max_drawdown {
+                    max_drawdown = drawdown;
+                }
+            }
+        }
+
+        self.final_capital = running_capital;
+        self.max_drawdown = max_drawdown * 100.0; // Convert to percentage
+
+        // Calculate win rate
+        if self.total_trades > 0 {
+            self.win_rate = (self.winning_trades as f64 / self.total_trades as f64) * 100.0;
+        }
+
+        // Calculate total return
+        if self.config.initial_capital > 0.0 {
+            self.total_return = ((self.final_capital - self.config.initial_capital) / self.config.initial_capital) * 100.0;
+        }
+
+        // Calculate average win/loss
+        if !wins.is_empty() {
+            self.average_win = wins.iter().sum::<f64>() / wins.len() as f64;
+        }
+        if !losses.is_empty() {
+            self.average_loss = losses.iter().sum::<f64>() / losses.len() as f64;
+        }
+
+        // Calculate profit factor
+        let total_wins: f64 = wins.iter().sum();
+        let total_losses: f64 = losses.iter().sum();
+        if total_losses > 0.0 {
+            self.profit_factor = total_wins / total_losses;
+        }
+
+        // Calculate Sharpe ratio (simplified)
+        if self.duration_days > 0.0 {
+            let daily_return = self.total_return / self.duration_days;
+            let risk_free_rate = 0.02; // 2% annual risk-free rate
+            let daily_risk_free = risk_free_rate / 365.0;
+            
+            // Simplified Sharpe calculation
+            if self.max_drawdown > 0.0 {
+                self.sharpe_ratio = (daily_return - daily_risk_free) / (self.max_drawdown / 100.0);
+            }
+        }
+
+        self.expectancy = self.total_profit_loss / self.total_trades as f64;
+        self.average_holding_hours = self.trades.iter()
+            .map(|t| t.exit_time.saturating_sub(t.entry_time) as f64 / 3600.0)
+            .sum::<f64>() / self.total_trades as f64;
+
+        // Calculate Sortino ratio from per-trade returns on running capital, annualized
+        // by the number of trades per year
+        let mut capital = self.config.initial_capital;
+        let mut returns = Vec::with_capacity(self.trades.len());
+        for trade in &self.trades {
+            if capital > 0.0 {
+                returns.push(trade.profit_loss / capital);
+            }
+            capital += trade.profit_loss;
+        }
+        if !returns.is_empty() {
+            let mean = returns.iter().sum::<f64>() / returns.len() as f64;
+            let downside_deviation = (returns.iter().map(|r| r.min(0.0).powi(2)).sum::<f64>() / returns.len() as f64).sqrt();
+            if downside_deviation > 0.0 {
+                let trades_per_year = if self.duration_days > 0.0 {
+                    returns.len() as f64 * 365.0 / self.duration_days
+                } else {
+                    returns.len() as f64
+                };
+                self.sortino_ratio = mean / downside_deviation * trades_per_year.sqrt();
+            }
+        }
+
+        // Calculate CAGR and Calmar ratio
+        if self.config.initial_capital > 0.0 && self.duration_days > 0.0 {
+            let growth = (self.final_capital / self.config.initial_capital).max(0.0);
+            self.cagr = (growth.powf(365.0 / self.duration_days) - 1.0) * 100.0;
+            if self.max_drawdown > 0.0 {
+                self.calmar_ratio = self.cagr / self.max_drawdown;
+            }
+        }
+
+        self.exposure_percent = self.calculate_exposure();
+        self.symbol_stats = self.calculate_symbol_stats();
+    }
+
+    /// Share of the backtest period covered by the union of open positions, in %
+    fn calculate_exposure(&self) -> f64 {
+        let duration = self.config.end_date.saturating_sub(self.config.start_date);
+        if duration == 0 {
+            return 0.0;
+        }
+
+        let mut intervals: Vec<(u64, u64)> = self.trades.iter()
+            .map(|t| (t.entry_time.max(self.config.start_date), t.exit_time.min(self.config.end_date)))
+            .filter(|(start, end)| end > start)
+            .collect();
+        intervals.sort();
+
+        let mut covered = 0;
+        let mut current: Option<(u64, u64)> = None;
+        for (start, end) in intervals {
+            current = match current {
+                Some((s, e)) if start <= e => Some((s, e.max(end))),
+                Some((s, e)) => {
+                    covered += e - s;
+                    Some((start, end))
+                }
+                None => Some((start, end)),
+            };
+        }
+        if let Some((s, e)) = current {
+            covered += e - s;
+        }
+
+        covered as f64 / duration as f64 * 100.0
+    }
+
+    fn calculate_symbol_stats(&self) -> BTreeMap<String, SymbolStats> {
+        let mut stats: BTreeMap<String, SymbolStats> = BTreeMap::new();
+        let mut gross: HashMap<String, (f64, f64, f64)> = HashMap::new();
+
+        for trade in &self.trades {
+            let entry = stats.entry(trade.symbol.clone()).or_default();
+            entry.total_trades += 1;
+            entry.total_profit_loss += trade.profit_loss;
+            entry.total_commission += trade.commission;
+            if trade.profit_loss > 0.0 {
+                entry.winning_trades += 1;
+            }
+
+            let (wins, losses, hours) = gross.entry(trade.symbol.clone()).or_insert((0.0, 0.0, 0.0));
+            if trade.profit_loss > 0.0 {
+                *wins += trade.profit_loss;
+            } else {
+                *losses += trade.profit_loss.abs();
+            }
+            *hours += trade.exit_time.saturating_sub(trade.entry_time) as f64 / 3600.0;
+        }
+
+        for (symbol, entry) in stats.iter_mut() {
+            let (wins, losses, hours) = gross[symbol];
+            let count = entry.total_trades as f64;
+            entry.win_rate = entry.winning_trades as f64 / count * 100.0;
+            entry.expectancy = entry.total_profit_loss / count;
+            entry.average_holding_hours = hours / count;
+            if losses > 0.0 {
+                entry.profit_factor = wins / losses;
+            }
+        }
+
+        stats
+    }
+
+    pub fn print_summary(&self) {
+        println!("=== Backtest Results ===");
+        println!("Total Trades: {}", self.total_trades);
+        println!("Winning Trades: {}", self.winning_trades);
+        println!("Losing Trades: {}", self.losing_trades);
+        println!("Win Rate: {:.2}%", self.win_rate);
+        println!("Total P&L: ${:.2}", self.total_profit_loss);
+        println!("Total Return: {:.2}%", self.total_return);
+        println!("Max Drawdown: {:.2}%", self.max_drawdown);
+        println!("Sharpe Ratio: {:.2}", self.sharpe_ratio);
+        println!("Sortino Ratio: {:.2}", self.sortino_ratio);
+        println!("Calmar Ratio: {:.2}", self.calmar_ratio);
+        println!("Profit Factor: {:.2}", self.profit_factor);
+        println!("Expectancy: ${:.2}", self.expectancy);
+        println!("Average Win: ${:.2}", self.average_win);
+        println!("Average Loss: ${:.2}", self.average_loss);
+        println!("Average Holding Time: {:.1}h", self.average_holding_hours);
+        println!("Exposure: {:.2}%", self.exposure_percent);
+        println!("Total Funding: ${:.2}", self.total_funding);
+        println!("Final Capital: ${:.2}", self.final_capital);
+        for (symbol, stats) in &self.symbol_stats {
+            println!("  {}: {} trades, {:.2}% win rate, P&L ${:.2}, PF {:.2}",
+                     symbol, stats.total_trades, stats.win_rate, stats.total_profit_loss, stats.profit_factor);
+        }
+    }
+
+    /// Run a Monte Carlo robustness analysis on the trade sequence
+    pub fn monte_carlo(&self, config: &MonteCarloConfig) -> MonteCarloReport {
+        monte_carlo::run_monte_carlo(self, config)
+    }
+
+    /// Print the summary followed by the Monte Carlo robustness analysis
+    pub fn print_report(&self, monte_carlo: &MonteCarloConfig) -> MonteCarloReport {
+        self.print_summary();
+        let report = self.monte_carlo(monte_carlo);
+        report.print_summary();
+        report
+    }
+}
+
+/// Latest known market conditions for a symbol, used to price fills
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MarketConditions {
+    pub spread: Option<f64>,
+    pub bar_volume: Option<f64>,
+}
+
+/// Last mark price at or before `time` in a time-ordered history
+fn mark_price_at(history: &[(u64, f64)], time: u64) -> Option<f64> {
+    let index = history.partition_point(|(t, _)| *t <= time);
+    index.checked_sub(1).map(|i| history[i].1)
+}
+
+#[derive(Debug, Clone)]
+pub struct BacktestEngine {
+    config: BacktestConfig,
+    trades: Vec<BacktestTrade>,
+    current_capital: f64,
+    open_positions: BTreeMap<String, BacktestTrade>,
+    fee_model: Arc<dyn FeeModel>,
+    slippage_model: Arc<dyn SlippageModel>,
+    market_conditions: HashMap<String, MarketConditions>,
+    funding_rates: HashMap<String, Vec<FundingRecord>>,
+    mark_prices: HashMap<String, f64>,
+    mark_history: HashMap<String, Vec<(u64, f64)>>,
+    latency: Option<LatencySimulator>,
+    rng: StdRng,
+    strategy_id: String,
+}
+
+impl BacktestEngine {
+    pub fn new(config: BacktestConfig) -> Self {
+        let current_capital = config.initial_capital;
+        let fee_model = config.fee_model.build();
+        let slippage_model = config.slippage_model.build();
+        let rng = match config.seed {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => StdRng::from_entropy(),
+        };
+        Self {
+            config,
+            trades: Vec::new(),
+            current_capital,
+            open_positions: BTreeMap::new(),
+            fee_model,
+            slippage_model,
+            market_conditions: HashMap::new(),
+            funding_rates: HashMap::new(),
+            mark_prices: HashMap::new(),
+            mark_history: HashMap::new(),
+            latency: None,
+            rng,
+            strategy_id: String::new(),
+        }
+    }
+
+    /// Load historical funding rates for a symbol (e.g. from `HistoryDownloader`)
+    pub fn load_funding_rates(&mut self, symbol: &str, mut rates: Vec<FundingRecord>) {
+        rates.sort_by_key(|r| r.timestamp);
+        self.funding_rates.insert(symbol.to_string(), rates);
+    }
+
+    /// Record the mark price of a symbol at `time` (seconds), used to value positions at funding time
+    pub fn update_mark_price(&mut self, symbol: &str, time: u64, price: f64) {
+        self.mark_prices.insert(symbol.to_string(), price);
+        let history = self.mark_history.entry(symbol.to_string()).or_default();
+        let index = history.partition_point(|(t, _)| *t <= time);
+        history.insert(index, (time, price));
+        for trade in self.open_positions.values_mut().filter(|t| t.symbol == symbol) {
+            trade.track_excursion(price);
+        }
+    }
+
+    /// Apply every funding settlement up to `now` (seconds) to open positions
+    ///
+    /// Longs pay and shorts receive a positive rate on the position's notional at the
+    /// mark price of the settlement time. Each position tracks the settlements it has
+    /// paid, so only those after its entry and after its last settlement accrue. Symbols
+    /// without loaded funding history accrue nothing. Returns the net funding paid.
+    pub fn apply_funding(&mut self, now: u64) -> f64 {
+        let mut total = 0.0;
+        for trade in self.open_positions.values_mut() {
+            let since = trade.funding_settled_until.max(trade.entry_time);
+            if now <= since {
+                continue;
+            }
+            trade.funding_settled_until = now;
+
+            let rates = match self.funding_rates.get(&trade.symbol) {
+                Some(rates) => rates,
+                None => continue,
+            };
+            let history = self.mark_history.get(&trade.symbol).map(Vec::as_slice).unwrap_or(&[]);
+            let direction = if trade.side == "long" { 1.0 } else { -1.0 };
+
+            for rate in rates {
+                let settlement = rate.timestamp.timestamp().max(0) as u64;
+                if settlement <= since || settlement > now {
+                    continue;
+                }
+
+                let mark_price = mark_price_at(history, settlement).unwrap_or(trade.entry_price);
+                let payment = direction * rate.funding_rate * mark_price * trade.quantity;
+                trade.funding += payment;
+                total += payment;
+            }
+        }
+
+        self.prune_mark_history(now);
+        self.current_capital -= total;
+        total
+    }
+
+    /// Drop mark prices no open position can still be charged funding at
+    fn prune_mark_history(&mut self, now: u64) {
+        for (symbol, history) in self.mark_history.iter_mut() {
+            let cutoff = self.open_positions.values()
+                .filter(|t| &t.symbol == symbol)
+                .map(|t| t.funding_settled_until.max(t.entry_time))
+                .min()
+                .unwrap_or(now);
+            // Keep the last price at or before the cutoff to value the next settlement
+            let keep_from = history.partition_point(|(t, _)| *t <= cutoff).saturating_sub(1);
+            history.drain(..keep_from);
+        }
+    }
+
+    /// Use a custom fee model
+    pub fn with_fee_model(mut self, fee_model: Arc<dyn FeeModel>) -> Self {
+        self.fee_model = fee_model;
+        self
+    }
+
+    /// Use a custom slippage model
+    pub fn with_slippage_model(mut self, slippage_model: Arc<dyn SlippageModel>) -> Self {
+        self.slippage_model = slippage_model;
+        self
+    }
+
+    /// Tag trades opened by this engine with a strategy ID
+    pub fn with_strategy_id(mut self, strategy_id: impl Into<String>) -> Self {
+        self.strategy_id = strategy_id.into();
+        self
+    }
+
+    /// Simulate order acknowledgement latency and exchange rejections
+    ///
+    /// Without its own seed the simulator is seeded from the engine's RNG.
+    pub fn with_latency(mut self, mut config: LatencyConfig) -> Self {
+        if config.seed.is_none() && self.config.seed.is_some() {
+            config.seed = Some(self.rng.gen());
+        }
+        self.latency = Some(LatencySimulator::new(config));
+        self
+    }
+
+    /// Submit an order through the latency simulator; returns the order ID
+    ///
+    /// The order executes in `on_price` once acknowledged, at the first price seen
+    /// after its acknowledgement time.
+    pub fn submit_order(&mut self, intent: OrderIntent, now_ms: u64, reference_price: f64) -> Result<u64> {
+        match self.latency.as_mut() {
+            Some(latency) => Ok(latency.submit(intent, now_ms, reference_price)),
+            None => Err(anyhow::anyhow!("Latency simulation is not enabled")),
+        }
+    }
+
+    /// Feed a price and execute orders on the symbol acknowledged by `now_ms`
+    ///
+    /// Accepted orders that fail to execute (e.g. insufficient capital) are reported
+    /// as rejected.
+    pub fn on_price(&mut self, symbol: &str, price: f64, now_ms: u64) -> Vec<OrderOutcome> {
+        self.update_mark_price(symbol, now_ms / 1000, price);
+        let outcomes = match self.latency.as_mut() {
+            Some(latency) => latency.on_price(symbol, price, now_ms),
+            None => return Vec::new(),
+        };
+
+        let now = now_ms / 1000;
+        outcomes.into_iter()
+            .map(|outcome| match outcome {
+                OrderOutcome::Accepted { order, price, time_ms, .. } => {
+                    let executed = match &order.intent {
+                        OrderIntent::Open { symbol, side, quantity } => {
+                            self.open_position(symbol.clone(), now, price, *quantity, side.clone())
+                        }
+                        OrderIntent::Close { trade_id, .. } => {
+                            self.close_position(trade_id, now, price).map(|_| trade_id.clone())
+                        }
+                    };
+                    match executed {
+                        Ok(trade_id) => OrderOutcome::Accepted { order, price, time_ms, trade_id: Some(trade_id) },
+                        Err(e) => OrderOutcome::Rejected { order, time_ms, reason: e.to_string() },
+                    }
+                }
+                rejected => rejected,
+            })
+            .collect()
+    }
+
+    /// Update the spread and bar volume used to price fills for a symbol
+    pub fn update_market_conditions(&mut self, symbol: &str, spread: Option<f64>, bar_volume: Option<f64>) {
+        self.market_conditions.insert(symbol.to_string(), MarketConditions { spread, bar_volume });
+    }
+
+    /// Apply slippage and fees to a taker fill; returns (execution price, fee)
+    fn price_fill(&self, symbol: &str, price: f64, quantity: f64, is_buy: bool) -> (f64, f64) {
+        let conditions = self.market_conditions.get(symbol).cloned().unwrap_or_default();
+        let mut fill = FillContext {
+            symbol: symbol.to_string(),
+            price,
+            quantity,
+            is_buy,
+            is_maker: false,
+            spread: conditions.spread,
+            bar_volume: conditions.bar_volume,
+        };
+
+        fill.price = self.slippage_model.execution_price(&fill);
+        let fee = self.fee_model.fee(&fill);
+        (fill.price, fee)
+    }
+
+    pub fn open_position(
+        &mut self,
+        symbol: String,
+        entry_time: u64,
+        entry_price: f64,
+        quantity: f64,
+        side: String,
+    ) -> Result<String> {
+        if self.open_positions.len() >= self.config.max_positions {
+            return Err(anyhow::anyhow!("Maximum positions reached"));
+        }
+        if !self.is_listed(&symbol, entry_time) {
+            return Err(anyhow::anyhow!("Symbol {} was not listed at {}", symbol, entry_time));
+        }
+
+        let (entry_price, entry_fee) = self.price_fill(&symbol, entry_price, quantity, side == "long");
+        let position_cost = entry_price * quantity;
+        if position_cost + entry_fee > self.current_capital {
+            return Err(anyhow::anyhow!("Insufficient capital"));
+        }
+
+        let mut trade = BacktestTrade::new(symbol.clone(), entry_time, entry_price, quantity, side);
+        trade.id = format!("trade_{}_{}", entry_time, self.rng.gen::<u32>());
+        trade.commission = entry_fee;
+        trade.strategy_id = self.strategy_id.clone();
+        let trade_id = trade.id.clone();
+        
+        self.open_positions.insert(trade_id.clone(), trade);
+        self.current_capital -= position_cost + entry_fee;
+        
+        Ok(trade_id)
+    }
+
+    pub fn close_position(&mut self, trade_id: &str, exit_time: u64, exit_price: f64) -> Result<f64> {
+        if self.open_positions.contains_key(trade_id) {
+            self.apply_funding(exit_time);
+        }
+
+        if let Some(mut trade) = self.open_positions.remove(trade_id) {
+            let (exit_price, exit_fee) = self.price_fill(&trade.symbol, exit_price, trade.quantity, trade.side != "long");
+            trade.close_trade(exit_time, exit_price, exit_fee);
+            
+            let position_value = exit_price * trade.quantity;
+            self.current_capital += position_value - exit_fee;
+            
+            let profit_loss = trade.profit_loss;
+            self.trades.push(trade);
+            
+            Ok(profit_loss)
+        } else {
+            Err(anyhow::anyhow!("Trade not found: {}", trade_id))
+        }
+    }
+
+    pub fn run_backtest(&mut self) -> Result<BacktestResult> {
+        // Settle funding up to the end, then close any remaining open positions
+        let end_time = self.config.end_date;
+        self.apply_funding(end_time);
+        let open_trade_ids: Vec<String> = self.open_positions.keys().cloned().collect();
+        
+        for trade_id in open_trade_ids {
+            // Use last known price (simplified)
+            let exit_price = 45000.0; // This should come from actual market data
+            let _ = self.close_position(&trade_id, end_time, exit_price);
+        }
+
+        Ok(BacktestResult::new(self.config.clone(), self.trades.clone()))
+    }
+
+    pub fn get_current_capital(&self) -> f64 {
+        self.current_capital
+    }
+
+    /// Whether `symbol` can be traded at `time` (seconds) under the configured universe
+    pub fn is_listed(&self, symbol: &str, time: u64) -> bool {
+        self.config.universe.as_ref().map_or(true, |u| u.is_listed(symbol, time))
+    }
+
+    pub fn get_open_positions_count(&self) -> usize {
+        self.open_positions.len()
+    }
+
+    pub fn get_open_position(&self, trade_id: &str) -> Option<&BacktestTrade> {
+        self.open_positions.get(trade_id)
+    }
+
+    /// Record the initial stop of an open position for R-multiple reporting
+    pub fn set_initial_stop(&mut self, trade_id: &str, stop: f64) -> Result<()> {
+        let trade = self.open_positions.get_mut(trade_id)
+            .ok_or_else(|| anyhow::anyhow!("Position {} not found", trade_id))?;
+        trade.set_initial_stop(stop);
+        Ok(())
+    }
+
+    /// Closed trades followed by open positions in the common export schema
+    pub fn get_trade_records(&self) -> Vec<TradeRecord> {
+        self.trades.iter()
+            .chain(self.open_positions.values())
+            .map(TradeRecord::from)
+            .collect()
+    }
+
+    /// Export trades as CSV or JSON depending on the file extension
+    pub fn export_trades(&self, path: impl AsRef<std::path::Path>) -> Result<()> {
+        trade_record::export_trades(path, &self.get_trade_records())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{TimeZone, Utc};
+
+    #[test]
+    fn test_seeded_engines_are_reproducible() {
+        let mut config = BacktestConfig::new(0, 86_400, 10_000.0, vec!["BTCUSDT".to_string()]);
+        config.seed = Some(42);
+
+        let run = || {
+            let mut engine = BacktestEngine::new(config.clone());
+            let id = engine.open_position("BTCUSDT".to_string(), 100, 100.0, 1.0, "long".to_string()).unwrap();
+            engine.close_position(&id, 200, 105.0).unwrap();
+            engine.run_backtest().unwrap()
+        };
+
+        let (first, second) = (run(), run());
+        assert_eq!(first.trades[0].id, second.trades[0].id);
+        assert_eq!(first.final_capital, second.final_capital);
+    }
+
+    #[test]
+    fn test_funding_accrues_on_open_positions() {
+        let mut config = BacktestConfig::new(0, 86_400, 10_000.0, vec!["BTCUSDT".to_string()]);
+        config.fee_model = FeeModelConfig::FixedBps { maker_bps: 0.0, taker_bps: 0.0 };
+        config.slippage_model = SlippageModelConfig::FixedBps { bps: 0.0 };
+        let mut engine = BacktestEngine::new(config);
+
+        engine.load_funding_rates("BTCUSDT", (1..=3)
+            .map(|i| FundingRecord {
+                timestamp: Utc.timestamp_opt(i * 28_800, 0).unwrap(),
+                funding_rate: 0.0001,
+            })
+            .collect());
+
+        let long = engine.open_position("BTCUSDT".to_string(), 1_000, 100.0, 10.0, "long".to_string()).unwrap();
+        let pnl = engine.close_position(&long, 60_000, 100.0).unwrap();
+
+        // Two settlements (8h, 16h) at 0.01% of 1000 notional
+        assert!((pnl + 0.2).abs() < 1e-9);
+        assert!((engine.get_current_capital() - 9_999.8).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_funding_per_position_at_settlement_prices() {
+        let mut config = BacktestConfig::new(0, 86_400, 10_000.0, vec!["BTCUSDT".to_string(), "ETHUSDT".to_string()]);
+        config.fee_model = FeeModelConfig::FixedBps { maker_bps: 0.0, taker_bps: 0.0 };
+        config.slippage_model = SlippageModelConfig::FixedBps { bps: 0.0 };
+        let mut engine = BacktestEngine::new(config);
+
+        let rates: Vec<FundingRecord> = (1..=3)
+            .map(|i| FundingRecord {
+                timestamp: Utc.timestamp_opt(i * 28_800, 0).unwrap(),
+                funding_rate: 0.0001,
+            })
+            .collect();
+        engine.load_funding_rates("BTCUSDT", rates.clone());
+        engine.load_funding_rates("ETHUSDT", rates);
+
+        // BTC settles at 8h (mark 100) and 16h (mark 120)
+        engine.update_mark_price("BTCUSDT", 0, 100.0);
+        let btc = engine.open_position("BTCUSDT".to_string(), 1_000, 100.0, 10.0, "long".to_string()).unwrap();
+        engine.update_mark_price("BTCUSDT", 30_000, 120.0);
+        engine.close_position(&btc, 60_000, 120.0).unwrap();
+
+        // ETH is replayed after BTC already settled up to 60 000s; it still pays its own settlements
+        engine.update_mark_price("ETHUSDT", 10_000, 50.0);
+        let eth = engine.open_position("ETHUSDT".to_string(), 10_000, 50.0, 20.0, "short".to_string()).unwrap();
+        engine.update_mark_price("ETHUSDT", 50_000, 40.0);
+        engine.close_position(&eth, 60_000, 40.0).unwrap();
+
+        let funding: HashMap<&str, f64> = engine.trades.iter().map(|t| (t.symbol.as_str(), t.funding)).collect();
+        assert!((funding["BTCUSDT"] - 0.0001 * 10.0 * (100.0 + 120.0)).abs() < 1e-9);
+        assert!((funding["ETHUSDT"] + 0.0001 * 20.0 * (50.0 + 40.0)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_extended_statistics() {
+        let config = BacktestConfig::new(0, 10 * 3600, 1_000.0, vec!["BTCUSDT".to_string(), "ETHUSDT".to_string()]);
+        let trades = [("BTCUSDT", 0, 2, 30.0), ("ETHUSDT", 1, 3, -10.0), ("BTCUSDT", 6, 8, 10.0)].iter()
+            .map(|(symbol, entry, exit, pnl)| {
+                let mut trade = BacktestTrade::new(symbol.to_string(), entry * 3600, 100.0, 1.0, "long".to_string());
+                trade.exit_time = exit * 3600;
+                trade.profit_loss = *pnl;
+                trade
+            })
+            .collect();
+        let result = BacktestResult::new(config, trades);
+
+        assert!((result.expectancy - 10.0).abs() < 1e-9);
+        assert!((result.average_holding_hours - 2.0).abs() < 1e-9);
+        // Positions cover hours 0-3 and 6-8 of 10
+        assert!((result.exposure_percent - 50.0).abs() < 1e-9);
+        assert_eq!(result.symbol_stats["BTCUSDT"].total_trades, 2);
+        assert!((result.symbol_stats["BTCUSDT"].win_rate - 100.0).abs() < 1e-9);
+        assert!(result.sortino_ratio > 0.0);
+        assert!(result.calmar_ratio > 0.0);
+    }
+}
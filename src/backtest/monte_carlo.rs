@@ -0,0 +1,255 @@
+//! Monte Carlo Robustness Analysis
+//!
+//! This module resamples the trade sequence of a `BacktestResult` (plain bootstrap
+//! or block bootstrap, which keeps streaks of consecutive trades together) and
+//! replays each sample on the initial capital. The resulting distributions of max
+//! drawdown, CAGR and final capital, and the risk of ruin, show how much of a
+//! backtest's result depends on the particular order trades happened in.
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+
+use crate::backtest::BacktestResult;
+
+/// Resampling method
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ResamplingMethod {
+    /// Draw trades independently with replacement
+    Bootstrap,
+
+    /// Draw contiguous blocks of trades with replacement
+    BlockBootstrap { block_size: usize },
+}
+
+/// Monte Carlo configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MonteCarloConfig {
+    /// Number of simulated equity paths
+    pub simulations: usize,
+
+    /// Resampling method
+    pub method: ResamplingMethod,
+
+    /// Drawdown (%) considered ruin
+    pub ruin_drawdown_percent: f64,
+
+    /// RNG seed for reproducible runs
+    pub seed: Option<u64>,
+}
+
+impl Default for MonteCarloConfig {
+    fn default() -> Self {
+        Self {
+            simulations: 1000,
+            method: ResamplingMethod::Bootstrap,
+            ruin_drawdown_percent: 50.0,
+            seed: None,
+        }
+    }
+}
+
+/// Summary statistics of a simulated distribution
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Distribution {
+    /// Mean
+    pub mean: f64,
+
+    /// 5th percentile
+    pub p5: f64,
+
+    /// Median
+    pub p50: f64,
+
+    /// 95th percentile
+    pub p95: f64,
+
+    /// Worst value (minimum, or maximum for drawdown)
+    pub worst: f64,
+}
+
+impl Distribution {
+    /// Summarize samples; `higher_is_worse` selects which tail is reported as worst
+    pub fn from_samples(mut samples: Vec<f64>, higher_is_worse: bool) -> Self {
+        if samples.is_empty() {
+            return Self::default();
+        }
+
+        samples.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+        let percentile = |p: f64| samples[((samples.len() - 1) as f64 * p).round() as usize];
+
+        Self {
+            mean: samples.iter().sum::<f64>() / samples.len() as f64,
+            p5: percentile(0.05),
+            p50: percentile(0.5),
+            p95: percentile(0.95),
+            worst: if higher_is_worse { samples[samples.len() - 1] } else { samples[0] },
+        }
+    }
+}
+
+/// Monte Carlo analysis report
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MonteCarloReport {
+    /// Configuration used
+    pub config: MonteCarloConfig,
+
+    /// Max drawdown distribution (%)
+    pub max_drawdown: Distribution,
+
+    /// CAGR distribution (%)
+    pub cagr: Distribution,
+
+    /// Final capital distribution
+    pub final_capital: Distribution,
+
+    /// Share of paths (0.0-1.0) whose drawdown reached the ruin threshold
+    pub risk_of_ruin: f64,
+
+    /// Share of paths ending with a profit
+    pub probability_of_profit: f64,
+}
+
+impl MonteCarloReport {
+    /// Print the report
+    pub fn print_summary(&self) {
+        println!("=== Monte Carlo ({} paths, {:?}) ===", self.config.simulations, self.config.method);
+        println!("Max Drawdown: median {:.2}%, 95th pct {:.2}%, worst {:.2}%",
+                 self.max_drawdown.p50, self.max_drawdown.p95, self.max_drawdown.worst);
+        println!("CAGR: 5th pct {:.2}%, median {:.2}%, 95th pct {:.2}%",
+                 self.cagr.p5, self.cagr.p50, self.cagr.p95);
+        println!("Final Capital: 5th pct ${:.2}, median ${:.2}, 95th pct ${:.2}",
+                 self.final_capital.p5, self.final_capital.p50, self.final_capital.p95);
+        println!("Risk of Ruin (>= {:.0}% drawdown): {:.2}%",
+                 self.config.ruin_drawdown_percent, self.risk_of_ruin * 100.0);
+        println!("Probability of Profit: {:.2}%", self.probability_of_profit * 100.0);
+    }
+}
+
+/// Run a Monte Carlo analysis on a backtest result
+pub fn run_monte_carlo(result: &BacktestResult, config: &MonteCarloConfig) -> MonteCarloReport {
+    let pnls: Vec<f64> = result.trades.iter().map(|t| t.profit_loss).collect();
+    let initial_capital = result.config.initial_capital;
+    let years = (result.duration_days / 365.0).max(1.0 / 365.0);
+
+    let mut rng = match config.seed {
+        Some(seed) => StdRng::seed_from_u64(seed),
+        None => StdRng::from_entropy(),
+    };
+
+    let mut drawdowns = Vec::with_capacity(config.simulations);
+    let mut cagrs = Vec::with_capacity(config.simulations);
+    let mut finals = Vec::with_capacity(config.simulations);
+    let mut ruined = 0usize;
+    let mut profitable = 0usize;
+
+    if !pnls.is_empty() && initial_capital > 0.0 {
+        for _ in 0..config.simulations {
+            let sample = resample(&pnls, config.method, &mut rng);
+            let (final_capital, max_drawdown) = replay(initial_capital, &sample);
+
+            let growth = (final_capital / initial_capital).max(0.0);
+            cagrs.push((growth.powf(1.0 / years) - 1.0) * 100.0);
+            drawdowns.push(max_drawdown);
+            finals.push(final_capital);
+
+            if max_drawdown >= config.ruin_drawdown_percent {
+                ruined += 1;
+            }
+            if final_capital > initial_capital {
+                profitable += 1;
+            }
+        }
+    }
+
+    let paths = drawdowns.len().max(1) as f64;
+
+    MonteCarloReport {
+        config: config.clone(),
+        max_drawdown: Distribution::from_samples(drawdowns, true),
+        cagr: Distribution::from_samples(cagrs, false),
+        final_capital: Distribution::from_samples(finals, false),
+        risk_of_ruin: ruined as f64 / paths,
+        probability_of_profit: profitable as f64 / paths,
+    }
+}
+
+/// Resample a P&L sequence to the same length
+fn resample(pnls: &[f64], method: ResamplingMethod, rng: &mut StdRng) -> Vec<f64> {
+    let n = pnls.len();
+    let mut sample = Vec::with_capacity(n);
+
+    match method {
+        ResamplingMethod::Bootstrap => {
+            for _ in 0..n {
+                sample.push(pnls[rng.gen_range(0..n)]);
+            }
+        }
+        ResamplingMethod::BlockBootstrap { block_size } => {
+            let block_size = block_size.clamp(1, n);
+            while sample.len() < n {
+                let start = rng.gen_range(0..=n - block_size);
+                let take = block_size.min(n - sample.len());
+                sample.extend_from_slice(&pnls[start..start + take]);
+            }
+        }
+    }
+
+    sample
+}
+
+/// Replay P&Ls on a starting capital; returns (final capital, max drawdown %)
+///
+/// The path stops once capital is exhausted.
+fn replay(initial_capital: f64, pnls: &[f64]) -> (f64, f64) {
+    let mut capital = initial_capital;
+    let mut peak = initial_capital;
+    let mut max_drawdown: f64 = 0.0;
+
+    for pnl in pnls {
+        capital += pnl;
+        peak = peak.max(capital);
+        max_drawdown = max_drawdown.max((peak - capital) / peak * 100.0);
+        if capital <= 0.0 {
+            return (0.0, 100.0);
+        }
+    }
+
+    (capital, max_drawdown)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backtest::{BacktestConfig, BacktestTrade};
+
+    fn result_with(pnls: &[f64]) -> BacktestResult {
+        let config = BacktestConfig::new(0, 365 * 86_400, 1_000.0, vec!["BTCUSDT".to_string()]);
+        let trades = pnls.iter()
+            .map(|pnl| {
+                let mut trade = BacktestTrade::new("BTCUSDT".to_string(), 0, 100.0, 1.0, "long".to_string());
+                trade.profit_loss = *pnl;
+                trade
+            })
+            .collect();
+        BacktestResult::new(config, trades)
+    }
+
+    #[test]
+    fn test_monte_carlo_is_seeded_and_bounded() {
+        let result = result_with(&[50.0, -30.0, 20.0, -10.0, 40.0, -60.0, 30.0]);
+        let config = MonteCarloConfig {
+            simulations: 200,
+            method: ResamplingMethod::BlockBootstrap { block_size: 3 },
+            seed: Some(7),
+            ..MonteCarloConfig::default()
+        };
+
+        let first = run_monte_carlo(&result, &config);
+        let second = run_monte_carlo(&result, &config);
+        assert_eq!(first.max_drawdown.p95, second.max_drawdown.p95);
+        assert!(first.max_drawdown.p5 <= first.max_drawdown.p95);
+        assert!(first.risk_of_ruin == 0.0);
+        assert!(first.cagr.p5 <= first.cagr.p95);
+    }
+}
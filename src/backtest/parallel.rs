@@ -0,0 +1,282 @@
+//! Parallel Backtesting
+//!
+//! This module evaluates independent backtest jobs (one symbol with one parameter
+//! set) concurrently on a rayon thread pool. A shared `SweepProgress` handle can be
+//! polled from another thread, or a callback registered, to follow large sweeps
+//! across hundreds of symbols while they run.
+
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Instant;
+use anyhow::Result;
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use tracing::{info, warn};
+
+use crate::backtest::BacktestResult;
+use crate::strategy::optimizer::ParameterSet;
+
+/// One unit of work in a sweep
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BacktestJob {
+    /// Job index
+    pub index: usize,
+
+    /// Symbol
+    pub symbol: String,
+
+    /// Strategy parameters
+    pub parameters: ParameterSet,
+}
+
+/// Build jobs for every symbol and parameter set combination
+pub fn sweep_jobs(symbols: &[String], parameter_sets: &[ParameterSet]) -> Vec<BacktestJob> {
+    let mut jobs = Vec::with_capacity(symbols.len() * parameter_sets.len().max(1));
+    for symbol in symbols {
+        if parameter_sets.is_empty() {
+            jobs.push(BacktestJob { index: jobs.len(), symbol: symbol.clone(), parameters: ParameterSet::new() });
+        }
+        for parameters in parameter_sets {
+            jobs.push(BacktestJob { index: jobs.len(), symbol: symbol.clone(), parameters: parameters.clone() });
+        }
+    }
+    jobs
+}
+
+/// Outcome of a job
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JobOutcome {
+    /// Job
+    pub job: BacktestJob,
+
+    /// Result, if the backtest succeeded
+    pub result: Option<BacktestResult>,
+
+    /// Error message, if it failed
+    pub error: Option<String>,
+}
+
+/// Point-in-time view of sweep progress
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct ProgressUpdate {
+    /// Jobs finished (succeeded or failed)
+    pub completed: usize,
+
+    /// Jobs failed
+    pub failed: usize,
+
+    /// Total jobs
+    pub total: usize,
+
+    /// Seconds since the sweep started
+    pub elapsed_secs: f64,
+
+    /// Estimated seconds remaining
+    pub eta_secs: Option<f64>,
+}
+
+/// Shared, thread-safe sweep progress
+#[derive(Debug, Clone)]
+pub struct SweepProgress {
+    completed: Arc<AtomicUsize>,
+    failed: Arc<AtomicUsize>,
+    total: Arc<AtomicUsize>,
+    cancelled: Arc<AtomicBool>,
+    started: Arc<std::sync::Mutex<Option<Instant>>>,
+}
+
+impl Default for SweepProgress {
+    fn default() -> Self {
+        Self {
+            completed: Arc::new(AtomicUsize::new(0)),
+            failed: Arc::new(AtomicUsize::new(0)),
+            total: Arc::new(AtomicUsize::new(0)),
+            cancelled: Arc::new(AtomicBool::new(false)),
+            started: Arc::new(std::sync::Mutex::new(None)),
+        }
+    }
+}
+
+impl SweepProgress {
+    /// Current progress
+    pub fn snapshot(&self) -> ProgressUpdate {
+        let completed = self.completed.load(Ordering::Relaxed);
+        let total = self.total.load(Ordering::Relaxed);
+        let elapsed_secs = self.started.lock()
+            .ok()
+            .and_then(|started| started.map(|s| s.elapsed().as_secs_f64()))
+            .unwrap_or(0.0);
+        let eta_secs = (completed > 0)
+            .then(|| elapsed_secs / completed as f64 * total.saturating_sub(completed) as f64);
+
+        ProgressUpdate {
+            completed,
+            failed: self.failed.load(Ordering::Relaxed),
+            total,
+            elapsed_secs,
+            eta_secs,
+        }
+    }
+
+    /// Completed share (0.0-1.0)
+    pub fn fraction(&self) -> f64 {
+        let total = self.total.load(Ordering::Relaxed);
+        if total == 0 { 0.0 } else { self.completed.load(Ordering::Relaxed) as f64 / total as f64 }
+    }
+
+    /// Stop starting new jobs; jobs already running finish
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    /// Whether the sweep was cancelled
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+
+    fn start(&self, total: usize) {
+        self.completed.store(0, Ordering::Relaxed);
+        self.failed.store(0, Ordering::Relaxed);
+        self.total.store(total, Ordering::Relaxed);
+        if let Ok(mut started) = self.started.lock() {
+            *started = Some(Instant::now());
+        }
+    }
+}
+
+/// Callback invoked after every finished job
+pub type ProgressCallback = Box<dyn Fn(ProgressUpdate) + Send + Sync>;
+
+/// Runs backtest jobs in parallel
+pub struct ParallelBacktester {
+    /// Worker threads (defaults to the number of CPUs)
+    threads: Option<usize>,
+
+    /// Shared progress
+    progress: SweepProgress,
+
+    /// Progress callback
+    on_progress: Option<ProgressCallback>,
+}
+
+impl ParallelBacktester {
+    /// Create a new parallel backtester using all CPUs
+    pub fn new() -> Self {
+        Self {
+            threads: None,
+            progress: SweepProgress::default(),
+            on_progress: None,
+        }
+    }
+
+    /// Limit the number of worker threads
+    pub fn with_threads(mut self, threads: usize) -> Self {
+        self.threads = Some(threads.max(1));
+        self
+    }
+
+    /// Register a progress callback
+    pub fn with_progress_callback(mut self, callback: ProgressCallback) -> Self {
+        self.on_progress = Some(callback);
+        self
+    }
+
+    /// Progress handle that can be polled or cancelled from another thread
+    pub fn get_progress(&self) -> SweepProgress {
+        self.progress.clone()
+    }
+
+    /// Run all jobs; outcomes are returned in job order
+    ///
+    /// Jobs skipped after cancellation are not included.
+    pub fn run<F>(&self, jobs: Vec<BacktestJob>, evaluate: F) -> Result<Vec<JobOutcome>>
+    where
+        F: Fn(&BacktestJob) -> Result<BacktestResult> + Send + Sync,
+    {
+        self.progress.start(jobs.len());
+        info!("Starting parallel backtest sweep of {} jobs", jobs.len());
+
+        let execute = || -> Vec<JobOutcome> {
+            jobs.into_par_iter()
+                .filter_map(|job| {
+                    if self.progress.is_cancelled() {
+                        return None;
+                    }
+
+                    let outcome = match evaluate(&job) {
+                        Ok(result) => JobOutcome { job, result: Some(result), error: None },
+                        Err(e) => {
+                            warn!("Backtest job {} ({}) failed: {}", job.index, job.symbol, e);
+                            self.progress.failed.fetch_add(1, Ordering::Relaxed);
+                            JobOutcome { job, result: None, error: Some(e.to_string()) }
+                        }
+                    };
+
+                    self.progress.completed.fetch_add(1, Ordering::Relaxed);
+                    if let Some(callback) = &self.on_progress {
+                        callback(self.progress.snapshot());
+                    }
+                    Some(outcome)
+                })
+                .collect()
+        };
+
+        let outcomes = match self.threads {
+            Some(threads) => rayon::ThreadPoolBuilder::new()
+                .num_threads(threads)
+                .build()?
+                .install(execute),
+            None => execute(),
+        };
+
+        let summary = self.progress.snapshot();
+        info!("Parallel sweep finished: {}/{} jobs, {} failed in {:.1}s",
+              summary.completed, summary.total, summary.failed, summary.elapsed_secs);
+
+        Ok(outcomes)
+    }
+}
+
+impl Default for ParallelBacktester {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backtest::BacktestConfig;
+
+    #[test]
+    fn test_sweep_runs_all_jobs_and_reports_progress() {
+        let symbols: Vec<String> = ["BTCUSDT", "ETHUSDT", "SOLUSDT"].iter().map(|s| s.to_string()).collect();
+        let parameter_sets: Vec<ParameterSet> = (0..4)
+            .map(|i| [("period".to_string(), i as f64)].into_iter().collect())
+            .collect();
+        let jobs = sweep_jobs(&symbols, &parameter_sets);
+        assert_eq!(jobs.len(), 12);
+
+        let calls = Arc::new(AtomicUsize::new(0));
+        let counter = calls.clone();
+        let backtester = ParallelBacktester::new()
+            .with_threads(2)
+            .with_progress_callback(Box::new(move |_| {
+                counter.fetch_add(1, Ordering::Relaxed);
+            }));
+
+        let outcomes = backtester.run(jobs, |job| {
+            if job.symbol == "SOLUSDT" && job.parameters["period"] == 0.0 {
+                return Err(anyhow::anyhow!("no data"));
+            }
+            Ok(BacktestResult::new(BacktestConfig::new(0, 86_400, 1_000.0, vec![job.symbol.clone()]), Vec::new()))
+        }).unwrap();
+
+        assert_eq!(outcomes.len(), 12);
+        assert!(outcomes.windows(2).all(|w| w[0].job.index < w[1].job.index));
+        assert_eq!(calls.load(Ordering::Relaxed), 12);
+
+        let progress = backtester.get_progress().snapshot();
+        assert_eq!((progress.completed, progress.failed), (12, 1));
+    }
+}
@@ -0,0 +1,305 @@
+//! Backtest/Live Parity Harness
+//!
+//! This module feeds the same historical candles through the portfolio backtester
+//! and through the live `TradingSystem` in paper mode, then diffs the entry orders
+//! both produced bar by bar. Missing orders, opposite sides or sizes and prices
+//! outside tolerance point at divergent indicator, signal or sizing logic before a
+//! strategy is deployed.
+
+use std::collections::{BTreeSet, HashMap};
+use anyhow::Result;
+use chrono::{TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+use tracing::{info, warn};
+
+use crate::backtest::portfolio::{PortfolioBacktest, PortfolioBacktestConfig, PortfolioSleeve};
+use crate::engine::message_bus::TradeDirection;
+use crate::strategy::simple_strategy::Candle;
+use crate::strategy::strategy_trait::Strategy;
+use crate::trading_system::{MarketData, TradingMode, TradingSystem, TradingSystemConfig};
+
+/// Parity tolerances
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ParityConfig {
+    /// Allowed quantity difference in %
+    pub quantity_tolerance_percent: f64,
+
+    /// Allowed entry price difference in bps
+    pub price_tolerance_bps: f64,
+
+    /// Candle timeframe in minutes, as configured in the trading system
+    pub timeframe_minutes: u64,
+}
+
+impl Default for ParityConfig {
+    fn default() -> Self {
+        Self {
+            quantity_tolerance_percent: 1.0,
+            price_tolerance_bps: 1.0,
+            timeframe_minutes: 1,
+        }
+    }
+}
+
+/// Entry order produced by either side
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ParityOrder {
+    /// Bar time (seconds)
+    pub timestamp: u64,
+
+    /// Symbol
+    pub symbol: String,
+
+    /// "long" or "short"
+    pub side: String,
+
+    /// Quantity
+    pub quantity: f64,
+
+    /// Entry price
+    pub price: f64,
+}
+
+/// Kind of divergence
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ParityDiffKind {
+    /// Backtest ordered, live did not
+    MissingInLive,
+
+    /// Live ordered, backtest did not
+    MissingInBacktest,
+
+    /// Both ordered on opposite sides
+    SideMismatch,
+
+    /// Quantities differ beyond tolerance
+    QuantityMismatch,
+
+    /// Entry prices differ beyond tolerance
+    PriceMismatch,
+}
+
+/// One divergence between backtest and live
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ParityDiff {
+    /// Kind
+    pub kind: ParityDiffKind,
+
+    /// Backtest order, if any
+    pub backtest: Option<ParityOrder>,
+
+    /// Live order, if any
+    pub live: Option<ParityOrder>,
+
+    /// Description
+    pub detail: String,
+}
+
+/// Parity report
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ParityReport {
+    /// Backtest orders
+    pub backtest_orders: Vec<ParityOrder>,
+
+    /// Live (paper) orders
+    pub live_orders: Vec<ParityOrder>,
+
+    /// Divergences
+    pub diffs: Vec<ParityDiff>,
+}
+
+impl ParityReport {
+    /// Whether both sides produced the same orders within tolerance
+    pub fn is_consistent(&self) -> bool {
+        self.diffs.is_empty()
+    }
+}
+
+/// Diff two order streams matched by bar time and symbol
+pub fn diff_orders(backtest: &[ParityOrder], live: &[ParityOrder], config: &ParityConfig) -> Vec<ParityDiff> {
+    let key = |o: &ParityOrder| (o.timestamp, o.symbol.clone());
+    let mut live_by_key: HashMap<(u64, String), &ParityOrder> = live.iter().map(|o| (key(o), o)).collect();
+    let mut diffs = Vec::new();
+
+    for order in backtest {
+        let other = match live_by_key.remove(&key(order)) {
+            Some(other) => other,
+            None => {
+                diffs.push(ParityDiff {
+                    kind: ParityDiffKind::MissingInLive,
+                    backtest: Some(order.clone()),
+                    live: None,
+                    detail: format!("{} {} at {} not ordered live", order.side, order.symbol, order.timestamp),
+                });
+                continue;
+            }
+        };
+
+        let mismatch = if order.side != other.side {
+            Some((ParityDiffKind::SideMismatch, format!("backtest {} vs live {}", order.side, other.side)))
+        } else if relative_diff(order.quantity, other.quantity) * 100.0 > config.quantity_tolerance_percent {
+            Some((ParityDiffKind::QuantityMismatch, format!("quantity {:.6} vs {:.6}", order.quantity, other.quantity)))
+        } else if relative_diff(order.price, other.price) * 10_000.0 > config.price_tolerance_bps {
+            Some((ParityDiffKind::PriceMismatch, format!("price {:.6} vs {:.6}", order.price, other.price)))
+        } else {
+            None
+        };
+
+        if let Some((kind, detail)) = mismatch {
+            diffs.push(ParityDiff {
+                kind,
+                backtest: Some(order.clone()),
+                live: Some(other.clone()),
+                detail: format!("{} at {}: {}", order.symbol, order.timestamp, detail),
+            });
+        }
+    }
+
+    let mut unmatched: Vec<&ParityOrder> = live_by_key.into_values().collect();
+    unmatched.sort_by_key(|o| (o.timestamp, o.symbol.clone()));
+    for order in unmatched {
+        diffs.push(ParityDiff {
+            kind: ParityDiffKind::MissingInBacktest,
+            backtest: None,
+            live: Some(order.clone()),
+            detail: format!("{} {} at {} not ordered in backtest", order.side, order.symbol, order.timestamp),
+        });
+    }
+
+    diffs
+}
+
+fn relative_diff(a: f64, b: f64) -> f64 {
+    let scale = a.abs().max(b.abs());
+    if scale > 0.0 { (a - b).abs() / scale } else { 0.0 }
+}
+
+/// Runs the same data through backtest and paper trading
+pub struct ParityHarness {
+    /// Configuration
+    config: ParityConfig,
+}
+
+impl ParityHarness {
+    /// Create a new parity harness
+    pub fn new(config: ParityConfig) -> Self {
+        Self { config }
+    }
+
+    /// Run both sides; `strategy` builds a fresh instance for each side and symbol
+    ///
+    /// Candles are keyed by symbol with `open_time` in milliseconds.
+    pub async fn run<F>(
+        &self,
+        candles: &HashMap<String, Vec<Candle>>,
+        backtest_config: PortfolioBacktestConfig,
+        mut live_config: TradingSystemConfig,
+        strategy: F,
+    ) -> Result<ParityReport>
+    where
+        F: Fn() -> Box<dyn Strategy>,
+    {
+        let mut symbols: Vec<String> = candles.keys().cloned().collect();
+        symbols.sort();
+
+        // Backtest side
+        let mut portfolio = PortfolioBacktest::new(backtest_config);
+        for symbol in &symbols {
+            portfolio.add_sleeve(PortfolioSleeve::new(symbol, symbol, strategy()))?;
+        }
+        let backtest_orders: Vec<ParityOrder> = portfolio.run(candles)?
+            .orders
+            .into_iter()
+            .map(|o| ParityOrder { timestamp: o.timestamp, symbol: o.symbol, side: o.side, quantity: o.quantity, price: o.price })
+            .collect();
+
+        // Live side in paper mode
+        live_config.mode = TradingMode::Simulation;
+        live_config.assets = symbols.clone();
+        live_config.timeframes = vec![self.config.timeframe_minutes];
+        let mut system = TradingSystem::new(live_config);
+        system.register_strategy(strategy())?;
+
+        let times: BTreeSet<i64> = candles.values().flatten().map(|c| c.open_time).collect();
+        let mut live_orders = Vec::new();
+        for time in times {
+            let bars: Vec<MarketData> = symbols.iter()
+                .filter_map(|symbol| {
+                    let candle = candles[symbol].iter().find(|c| c.open_time == time)?;
+                    Some(MarketData {
+                        symbol: symbol.clone(),
+                        timestamp: Utc.timestamp_millis_opt(time).single()?,
+                        open: candle.open,
+                        high: candle.high,
+                        low: candle.low,
+                        close: candle.close,
+                        volume: candle.volume,
+                        timeframe: self.config.timeframe_minutes,
+                    })
+                })
+                .collect();
+
+            for trade in system.replay_bars(bars).await? {
+                live_orders.push(ParityOrder {
+                    timestamp: (time / 1000).max(0) as u64,
+                    symbol: trade.symbol,
+                    side: match trade.direction {
+                        TradeDirection::Short => "short".to_string(),
+                        _ => "long".to_string(),
+                    },
+                    quantity: trade.size,
+                    price: trade.entry_price,
+                });
+            }
+        }
+
+        let diffs = diff_orders(&backtest_orders, &live_orders, &self.config);
+        if diffs.is_empty() {
+            info!("Parity check passed: {} matching orders", backtest_orders.len());
+        } else {
+            warn!("Parity check found {} divergences between backtest and live", diffs.len());
+        }
+
+        Ok(ParityReport {
+            backtest_orders,
+            live_orders,
+            diffs,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn order(timestamp: u64, symbol: &str, side: &str, quantity: f64) -> ParityOrder {
+        ParityOrder { timestamp, symbol: symbol.to_string(), side: side.to_string(), quantity, price: 100.0 }
+    }
+
+    #[test]
+    fn test_diff_orders_classifies_divergences() {
+        let backtest = vec![
+            order(1, "BTCUSDT", "long", 1.0),
+            order(2, "BTCUSDT", "long", 1.0),
+            order(3, "ETHUSDT", "short", 2.0),
+            order(4, "ETHUSDT", "long", 1.0),
+        ];
+        let live = vec![
+            order(1, "BTCUSDT", "long", 1.005),
+            order(2, "BTCUSDT", "long", 1.5),
+            order(3, "ETHUSDT", "long", 2.0),
+            order(5, "SOLUSDT", "long", 1.0),
+        ];
+
+        let kinds: Vec<ParityDiffKind> = diff_orders(&backtest, &live, &ParityConfig::default())
+            .into_iter()
+            .map(|d| d.kind)
+            .collect();
+        assert_eq!(kinds, vec![
+            ParityDiffKind::QuantityMismatch,
+            ParityDiffKind::SideMismatch,
+            ParityDiffKind::MissingInLive,
+            ParityDiffKind::MissingInBacktest,
+        ]);
+    }
+}
@@ -0,0 +1,331 @@
+//! Backtest Report Generation
+//!
+//! This module turns a `BacktestResult` into a `BacktestReport` holding the equity
+//! and drawdown curves, monthly returns, trade list and parameter summary. The
+//! report can be written as machine-readable JSON or as a self-contained HTML page
+//! with inline SVG charts and no external assets.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+use anyhow::Result;
+use chrono::{Datelike, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+use tracing::info;
+
+use crate::backtest::benchmark::BenchmarkComparison;
+use crate::backtest::{BacktestResult, BacktestTrade};
+use crate::strategy::optimizer::ParameterSet;
+
+/// Point on the equity curve
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EquityPoint {
+    /// Time (seconds)
+    pub timestamp: u64,
+
+    /// Capital after the trades closed at this time
+    pub capital: f64,
+
+    /// Drawdown from the running peak in %
+    pub drawdown: f64,
+}
+
+/// Return of one calendar month
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MonthlyReturn {
+    /// Year
+    pub year: i32,
+
+    /// Month (1-12)
+    pub month: u32,
+
+    /// Return in % of the capital at the start of the month
+    pub return_percent: f64,
+
+    /// Trades closed in the month
+    pub trades: u32,
+}
+
+/// Backtest report
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BacktestReport {
+    /// Report title
+    pub title: String,
+
+    /// Full backtest result
+    pub result: BacktestResult,
+
+    /// Strategy parameters used for the run
+    pub parameters: BTreeMap<String, f64>,
+
+    /// Equity and drawdown curve
+    pub equity_curve: Vec<EquityPoint>,
+
+    /// Monthly returns in chronological order
+    pub monthly_returns: Vec<MonthlyReturn>,
+
+    /// Benchmark comparisons
+    #[serde(default)]
+    pub benchmarks: Vec<BenchmarkComparison>,
+}
+
+impl BacktestReport {
+    /// Build a report from a backtest result
+    pub fn new(title: &str, result: &BacktestResult, parameters: Option<&ParameterSet>) -> Self {
+        let mut trades: Vec<&BacktestTrade> = result.trades.iter().collect();
+        trades.sort_by_key(|t| t.exit_time);
+
+        let mut capital = result.config.initial_capital;
+        let mut peak = capital;
+        let mut equity_curve = vec![EquityPoint { timestamp: result.config.start_date, capital, drawdown: 0.0 }];
+        let mut months: BTreeMap<(i32, u32), (f64, f64, u32)> = BTreeMap::new();
+
+        for trade in trades {
+            let key = Utc.timestamp_opt(trade.exit_time as i64, 0)
+                .single()
+                .map(|t| (t.year(), t.month()))
+                .unwrap_or((1970, 1));
+            let month = months.entry(key).or_insert((capital, 0.0, 0));
+            month.1 += trade.profit_loss;
+            month.2 += 1;
+
+            capital += trade.profit_loss;
+            peak = peak.max(capital);
+            let drawdown = if peak > 0.0 { (peak - capital) / peak * 100.0 } else { 0.0 };
+            equity_curve.push(EquityPoint { timestamp: trade.exit_time, capital, drawdown });
+        }
+
+        let monthly_returns = months.into_iter()
+            .map(|((year, month), (start_capital, pnl, trades))| MonthlyReturn {
+                year,
+                month,
+                return_percent: if start_capital > 0.0 { pnl / start_capital * 100.0 } else { 0.0 },
+                trades,
+            })
+            .collect();
+
+        Self {
+            title: title.to_string(),
+            result: result.clone(),
+            parameters: parameters.map(|p| p.iter().map(|(k, v)| (k.clone(), *v)).collect()).unwrap_or_default(),
+            equity_curve,
+            monthly_returns,
+            benchmarks: Vec::new(),
+        }
+    }
+
+    /// Attach benchmark comparisons (see `backtest::benchmark::compare`)
+    pub fn with_benchmarks(mut self, benchmarks: Vec<BenchmarkComparison>) -> Self {
+        self.benchmarks = benchmarks;
+        self
+    }
+
+    /// Serialize the report as pretty-printed JSON
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    /// Render the report as a self-contained HTML page
+    pub fn to_html(&self) -> String {
+        let r = &self.result;
+        let mut html = String::new();
+
+        html.push_str("<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n");
+        html.push_str(&format!("<title>{}</title>\n", escape(&self.title)));
+        html.push_str("<style>\nbody{font-family:sans-serif;margin:24px;color:#222}\
+            table{border-collapse:collapse;margin-bottom:24px}\
+            td,th{border:1px solid #ccc;padding:4px 8px;text-align:right}\
+            th{background:#f0f0f0}.pos{color:#1a7f37}.neg{color:#c62828}\n</style>\n");
+        html.push_str("</head>\n<body>\n");
+        html.push_str(&format!("<h1>{}</h1>\n", escape(&self.title)));
+
+        // Summary
+        html.push_str("<h2>Summary</h2>\n<table>\n");
+        let summary = [
+            ("Symbols", escape(&r.config.symbols.join(", "))),
+            ("Timeframe", escape(&r.config.timeframe)),
+            ("Initial Capital", format!("${:.2}", r.config.initial_capital)),
+            ("Final Capital", format!("${:.2}", r.final_capital)),
+            ("Total Return", format!("{:.2}%", r.total_return)),
+            ("Max Drawdown", format!("{:.2}%", r.max_drawdown)),
+            ("Sharpe Ratio", format!("{:.2}", r.sharpe_ratio)),
+            ("Sortino Ratio", format!("{:.2}", r.sortino_ratio)),
+            ("Calmar Ratio", format!("{:.2}", r.calmar_ratio)),
+            ("Profit Factor", format!("{:.2}", r.profit_factor)),
+            ("Expectancy", format!("${:.2}", r.expectancy)),
+            ("Average Holding Time", format!("{:.1}h", r.average_holding_hours)),
+            ("Exposure", format!("{:.2}%", r.exposure_percent)),
+            ("Trades", r.total_trades.to_string()),
+            ("Win Rate", format!("{:.2}%", r.win_rate)),
+            ("Total Commission", format!("${:.2}", r.total_commission)),
+            ("Total Funding", format!("${:.2}", r.total_funding)),
+        ];
+        for (label, value) in summary {
+            html.push_str(&format!("<tr><th>{}</th><td>{}</td></tr>\n", label, value));
+        }
+        html.push_str("</table>\n");
+
+        // Parameters
+        if !self.parameters.is_empty() {
+            html.push_str("<h2>Parameters</h2>\n<table>\n");
+            for (name, value) in &self.parameters {
+                html.push_str(&format!("<tr><th>{}</th><td>{}</td></tr>\n", escape(name), value));
+            }
+            html.push_str("</table>\n");
+        }
+
+        // Charts
+        let equity: Vec<f64> = self.equity_curve.iter().map(|p| p.capital).collect();
+        let drawdown: Vec<f64> = self.equity_curve.iter().map(|p| -p.drawdown).collect();
+        html.push_str("<h2>Equity Curve</h2>\n");
+        html.push_str(&svg_line_chart(&equity, "#1565c0"));
+        html.push_str("<h2>Drawdown (%)</h2>\n");
+        html.push_str(&svg_line_chart(&drawdown, "#c62828"));
+
+        // Benchmarks
+        if !self.benchmarks.is_empty() {
+            html.push_str("<h2>Benchmarks</h2>\n<table>\n<tr><th>Benchmark</th><th>Benchmark Return</th>\
+                <th>Strategy Return</th><th>Excess</th><th>Alpha (ann.)</th><th>Beta</th><th>Correlation</th></tr>\n");
+            for benchmark in &self.benchmarks {
+                html.push_str(&format!(
+                    "<tr><td>{}</td><td>{:.2}%</td><td>{:.2}%</td><td class=\"{}\">{:.2}%</td><td>{:.2}%</td><td>{:.2}</td><td>{:.2}</td></tr>\n",
+                    escape(&benchmark.name), benchmark.benchmark_return, benchmark.strategy_return,
+                    sign_class(benchmark.excess_return), benchmark.excess_return,
+                    benchmark.alpha, benchmark.beta, benchmark.correlation
+                ));
+            }
+            html.push_str("</table>\n");
+            for benchmark in &self.benchmarks {
+                let values: Vec<f64> = benchmark.equity_curve.iter().map(|(_, v)| *v).collect();
+                html.push_str(&format!("<h3>{}</h3>\n", escape(&benchmark.name)));
+                html.push_str(&svg_line_chart(&values, "#757575"));
+            }
+        }
+
+        // Per-symbol breakdown
+        html.push_str("<h2>Symbols</h2>\n<table>\n<tr><th>Symbol</th><th>Trades</th><th>Win Rate</th>\
+            <th>P&amp;L</th><th>Profit Factor</th><th>Expectancy</th></tr>\n");
+        for (symbol, stats) in &r.symbol_stats {
+            html.push_str(&format!(
+                "<tr><td>{}</td><td>{}</td><td>{:.2}%</td><td class=\"{}\">{:.2}</td><td>{:.2}</td><td>{:.2}</td></tr>\n",
+                escape(symbol), stats.total_trades, stats.win_rate,
+                sign_class(stats.total_profit_loss), stats.total_profit_loss, stats.profit_factor, stats.expectancy
+            ));
+        }
+        html.push_str("</table>\n");
+
+        // Monthly returns
+        html.push_str("<h2>Monthly Returns</h2>\n<table>\n<tr><th>Month</th><th>Return</th><th>Trades</th></tr>\n");
+        for month in &self.monthly_returns {
+            html.push_str(&format!(
+                "<tr><td>{}-{:02}</td><td class=\"{}\">{:.2}%</td><td>{}</td></tr>\n",
+                month.year, month.month, sign_class(month.return_percent), month.return_percent, month.trades
+            ));
+        }
+        html.push_str("</table>\n");
+
+        // Trades
+        html.push_str("<h2>Trades</h2>\n<table>\n<tr><th>Symbol</th><th>Side</th><th>Entry</th><th>Exit</th>\
+            <th>Entry Price</th><th>Exit Price</th><th>Quantity</th><th>P&amp;L</th><th>Return</th></tr>\n");
+        for trade in &r.trades {
+            html.push_str(&format!(
+                "<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{:.4}</td><td>{:.4}</td><td>{:.6}</td>\
+                 <td class=\"{}\">{:.2}</td><td>{:.2}%</td></tr>\n",
+                escape(&trade.symbol), escape(&trade.side),
+                format_time(trade.entry_time), format_time(trade.exit_time),
+                trade.entry_price, trade.exit_price, trade.quantity,
+                sign_class(trade.profit_loss), trade.profit_loss, trade.return_percentage
+            ));
+        }
+        html.push_str("</table>\n</body>\n</html>\n");
+
+        html
+    }
+
+    /// Write `<stem>.json` and `<stem>.html` into a directory
+    pub fn write(&self, dir: impl AsRef<Path>, stem: &str) -> Result<()> {
+        let dir = dir.as_ref();
+        fs::create_dir_all(dir)?;
+        fs::write(dir.join(format!("{}.json", stem)), self.to_json()?)?;
+        fs::write(dir.join(format!("{}.html", stem)), self.to_html())?;
+        info!("Wrote backtest report {} to {}", stem, dir.display());
+        Ok(())
+    }
+}
+
+/// Render a series as an inline SVG polyline
+pub(crate) fn svg_line_chart(values: &[f64], color: &str) -> String {
+    const WIDTH: f64 = 800.0;
+    const HEIGHT: f64 = 240.0;
+
+    if values.len() < 2 {
+        return "<p>Not enough data</p>\n".to_string();
+    }
+
+    let min = values.iter().cloned().fold(f64::INFINITY, f64::min);
+    let max = values.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+    let range = if max > min { max - min } else { 1.0 };
+    let step = WIDTH / (values.len() - 1) as f64;
+
+    let points: Vec<String> = values.iter()
+        .enumerate()
+        .map(|(i, v)| format!("{:.1},{:.1}", i as f64 * step, HEIGHT - (v - min) / range * HEIGHT))
+        .collect();
+
+    format!(
+        "<svg width=\"{w}\" height=\"{h}\" viewBox=\"0 0 {w} {h}\" style=\"border:1px solid #ddd\">\
+         <polyline fill=\"none\" stroke=\"{c}\" stroke-width=\"1.5\" points=\"{p}\"/>\
+         <text x=\"4\" y=\"12\" font-size=\"11\">{max:.2}</text>\
+         <text x=\"4\" y=\"{hb}\" font-size=\"11\">{min:.2}</text></svg>\n",
+        w = WIDTH, h = HEIGHT, c = color, p = points.join(" "), max = max, min = min, hb = HEIGHT - 4.0
+    )
+}
+
+pub(crate) fn sign_class(value: f64) -> &'static str {
+    if value >= 0.0 { "pos" } else { "neg" }
+}
+
+fn format_time(timestamp: u64) -> String {
+    Utc.timestamp_opt(timestamp as i64, 0)
+        .single()
+        .map(|t| t.format("%Y-%m-%d %H:%M").to_string())
+        .unwrap_or_default()
+}
+
+pub(crate) fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backtest::BacktestConfig;
+
+    #[test]
+    fn test_report_monthly_returns_and_html() {
+        let config = BacktestConfig::new(0, 90 * 86_400, 1_000.0, vec!["BTC<USDT>".to_string()]);
+        let trades = [(10u64, 100.0), (20, -50.0), (40, 21.0)].iter()
+            .map(|(day, pnl)| {
+                let mut trade = BacktestTrade::new("BTCUSDT".to_string(), 0, 100.0, 1.0, "long".to_string());
+                trade.exit_time = day * 86_400;
+                trade.profit_loss = *pnl;
+                trade
+            })
+            .collect();
+        let result = BacktestResult::new(config, trades);
+
+        let report = BacktestReport::new("Test", &result, None);
+        assert_eq!(report.equity_curve.len(), 4);
+        assert_eq!(report.monthly_returns.len(), 2);
+        assert!((report.monthly_returns[0].return_percent - 5.0).abs() < 1e-9);
+        assert!((report.monthly_returns[1].return_percent - 2.0).abs() < 1e-9);
+
+        let html = report.to_html();
+        assert!(html.contains("<svg"));
+        assert!(html.contains("BTC&lt;USDT&gt;"));
+        assert!(report.to_json().unwrap().contains("monthly_returns"));
+    }
+}
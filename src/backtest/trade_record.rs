@@ -0,0 +1,249 @@
+//! Trade Record Export
+//!
+//! This module defines the canonical trade-by-trade schema shared by backtests and
+//! live trading. `BacktestEngine` and the live trade journal in `TradingSystem` both
+//! convert their trades into `TradeRecord`s so results can be exported to CSV or
+//! JSON and compared or analyzed with the same tooling.
+
+use std::fs::{self, File};
+use std::path::Path;
+use anyhow::Result;
+use chrono::{DateTime, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::backtest::BacktestTrade;
+use crate::engine::message_bus::TradeDirection;
+use crate::trading_system::Trade;
+
+/// Where a trade was executed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TradeOrigin {
+    /// Simulated by the backtest engine
+    Backtest,
+
+    /// Executed by the live (or paper) trading system
+    Live,
+}
+
+/// Export file format
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TradeExportFormat {
+    /// Comma-separated values with a header row
+    Csv,
+
+    /// Pretty-printed JSON array
+    Json,
+}
+
+impl TradeExportFormat {
+    /// Infer the format from a file extension (defaults to CSV)
+    pub fn from_path(path: &Path) -> Self {
+        match path.extension().and_then(|e| e.to_str()) {
+            Some(ext) if ext.eq_ignore_ascii_case("json") => TradeExportFormat::Json,
+            _ => TradeExportFormat::Csv,
+        }
+    }
+}
+
+/// One trade in the common export schema
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TradeRecord {
+    /// Trade ID
+    pub trade_id: String,
+
+    /// Backtest or live
+    pub origin: TradeOrigin,
+
+    /// Strategy that opened the trade
+    pub strategy_id: String,
+
+    /// Symbol
+    pub symbol: String,
+
+    /// "long" or "short"
+    pub side: String,
+
+    /// Entry time
+    pub entry_time: DateTime<Utc>,
+
+    /// Exit time, if closed
+    pub exit_time: Option<DateTime<Utc>>,
+
+    /// Entry price
+    pub entry_price: f64,
+
+    /// Exit price, if closed
+    pub exit_price: Option<f64>,
+
+    /// Quantity
+    pub quantity: f64,
+
+    /// Leverage
+    pub leverage: f64,
+
+    /// Entry and exit fees
+    pub fees: f64,
+
+    /// Net funding paid (negative when received)
+    pub funding: f64,
+
+    /// P&L before fees and funding
+    pub gross_pnl: f64,
+
+    /// P&L after fees and funding
+    pub net_pnl: f64,
+
+    /// Net return on entry notional in %
+    pub return_percent: f64,
+
+    /// Maximum adverse excursion in % of entry price
+    pub mae_percent: f64,
+
+    /// Maximum favorable excursion in % of entry price
+    pub mfe_percent: f64,
+
+    /// Loss at the initial stop (1R) in quote currency
+    #[serde(default)]
+    pub initial_risk: Option<f64>,
+
+    /// Net P&L in multiples of the initial risk
+    #[serde(default)]
+    pub r_multiple: Option<f64>,
+}
+
+/// Signed price move in % of entry, positive when in the trade's favor
+pub fn excursion_percent(entry_price: f64, price: f64, is_long: bool) -> f64 {
+    if entry_price <= 0.0 {
+        return 0.0;
+    }
+    let change = (price - entry_price) / entry_price * 100.0;
+    if is_long { change } else { -change }
+}
+
+impl From<&BacktestTrade> for TradeRecord {
+    fn from(trade: &BacktestTrade) -> Self {
+        let to_time = |secs: u64| Utc.timestamp_opt(secs as i64, 0).single().unwrap_or_default();
+        let closed = trade.exit_time > 0;
+
+        Self {
+            trade_id: trade.id.clone(),
+            origin: TradeOrigin::Backtest,
+            strategy_id: trade.strategy_id.clone(),
+            symbol: trade.symbol.clone(),
+            side: trade.side.clone(),
+            entry_time: to_time(trade.entry_time),
+            exit_time: closed.then(|| to_time(trade.exit_time)),
+            entry_price: trade.entry_price,
+            exit_price: closed.then_some(trade.exit_price),
+            quantity: trade.quantity,
+            leverage: 1.0,
+            fees: trade.commission,
+            funding: trade.funding,
+            gross_pnl: trade.profit_loss + trade.commission + trade.funding,
+            net_pnl: trade.profit_loss,
+            return_percent: trade.return_percentage,
+            mae_percent: trade.max_adverse_excursion,
+            mfe_percent: trade.max_favorable_excursion,
+            initial_risk: trade.initial_risk,
+            r_multiple: if closed { trade.r_multiple() } else { None },
+        }
+    }
+}
+
+impl From<&Trade> for TradeRecord {
+    fn from(trade: &Trade) -> Self {
+        // Realized P&L is net of the fees and funding the trade booked
+        let pnl = trade.realized_pnl.unwrap_or(trade.unrealized_pnl - trade.fees - trade.funding);
+        let notional = trade.entry_price * trade.size;
+
+        Self {
+            trade_id: trade.id.clone(),
+            origin: TradeOrigin::Live,
+            strategy_id: trade.source.clone(),
+            symbol: trade.symbol.clone(),
+            side: match trade.direction {
+                TradeDirection::Short => "short".to_string(),
+                _ => "long".to_string(),
+            },
+            entry_time: trade.entry_time,
+            exit_time: trade.exit_time,
+            entry_price: trade.entry_price,
+            exit_price: trade.exit_price,
+            quantity: trade.size,
+            leverage: trade.leverage,
+            fees: trade.fees,
+            funding: trade.funding,
+            gross_pnl: pnl + trade.fees + trade.funding,
+            net_pnl: pnl,
+            return_percent: trade.roi.unwrap_or(if notional > 0.0 { pnl / notional * 100.0 } else { 0.0 }),
+            mae_percent: trade.max_adverse_excursion,
+            mfe_percent: trade.max_favorable_excursion,
+            initial_risk: trade.initial_risk,
+            r_multiple: trade.r_multiple,
+        }
+    }
+}
+
+/// Write records as CSV
+pub fn write_csv(path: &Path, records: &[TradeRecord]) -> Result<()> {
+    let mut writer = csv::Writer::from_writer(File::create(path)?);
+    for record in records {
+        writer.serialize(record)?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+/// Write records as a JSON array
+pub fn write_json(path: &Path, records: &[TradeRecord]) -> Result<()> {
+    fs::write(path, serde_json::to_string_pretty(records)?)?;
+    Ok(())
+}
+
+/// Write records in the format implied by the file extension
+pub fn export_trades(path: impl AsRef<Path>, records: &[TradeRecord]) -> Result<()> {
+    let path = path.as_ref();
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        fs::create_dir_all(parent)?;
+    }
+
+    match TradeExportFormat::from_path(path) {
+        TradeExportFormat::Csv => write_csv(path, records),
+        TradeExportFormat::Json => write_json(path, records),
+    }
+}
+
+/// Read records back from a CSV export
+pub fn read_csv(path: impl AsRef<Path>) -> Result<Vec<TradeRecord>> {
+    let mut reader = csv::Reader::from_path(path)?;
+    let records = reader.deserialize().collect::<std::result::Result<Vec<TradeRecord>, _>>()?;
+    Ok(records)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backtest_trade_round_trips_through_csv() {
+        let mut trade = BacktestTrade::new("BTCUSDT".to_string(), 3_600, 100.0, 2.0, "short".to_string());
+        trade.strategy_id = "mean_reversion".to_string();
+        trade.commission = 0.1;
+        trade.track_excursion(103.0);
+        trade.track_excursion(95.0);
+        trade.close_trade(7_200, 96.0, 0.1);
+
+        let record = TradeRecord::from(&trade);
+        assert_eq!(record.origin, TradeOrigin::Backtest);
+        assert!((record.gross_pnl - 8.0).abs() < 1e-9);
+        assert!((record.net_pnl - 7.8).abs() < 1e-9);
+        assert!((record.mae_percent - 3.0).abs() < 1e-9);
+        assert!((record.mfe_percent - 5.0).abs() < 1e-9);
+
+        let path = std::env::temp_dir().join(format!("omni_trades_{}.csv", std::process::id()));
+        export_trades(&path, &[record.clone()]).unwrap();
+        assert_eq!(read_csv(&path).unwrap(), vec![record]);
+        let _ = fs::remove_file(&path);
+    }
+}
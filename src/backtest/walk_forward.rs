@@ -0,0 +1,327 @@
+//! Walk-Forward Analysis
+//!
+//! This module splits the backtest period into rolling (or anchored) in-sample and
+//! out-of-sample windows. Parameters are fitted on each in-sample window, then
+//! evaluated on the following out-of-sample window with the `BacktestEngine`. The
+//! report contains per-window metrics, the stitched out-of-sample equity curve and
+//! warnings for windows where out-of-sample performance falls far short of the
+//! in-sample fit.
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use tracing::{info, warn};
+
+use crate::backtest::{BacktestConfig, BacktestResult};
+use crate::strategy::optimizer::ParameterSet;
+
+/// Walk-forward configuration (durations in seconds)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WalkForwardConfig {
+    /// In-sample (fitting) window length
+    pub in_sample_seconds: u64,
+
+    /// Out-of-sample (validation) window length
+    pub out_of_sample_seconds: u64,
+
+    /// Shift between consecutive windows; defaults to the out-of-sample length
+    pub step_seconds: Option<u64>,
+
+    /// Keep the in-sample start fixed at the beginning of the period
+    pub anchored: bool,
+
+    /// Minimum walk-forward efficiency (OOS daily return / IS daily return)
+    pub min_efficiency: f64,
+}
+
+impl Default for WalkForwardConfig {
+    fn default() -> Self {
+        Self {
+            in_sample_seconds: 90 * 86_400,
+            out_of_sample_seconds: 30 * 86_400,
+            step_seconds: None,
+            anchored: false,
+            min_efficiency: 0.5,
+        }
+    }
+}
+
+/// One in-sample/out-of-sample split
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WalkForwardWindow {
+    /// Window index
+    pub index: usize,
+
+    /// In-sample start
+    pub in_sample_start: u64,
+
+    /// In-sample end (= out-of-sample start)
+    pub in_sample_end: u64,
+
+    /// Out-of-sample end
+    pub out_of_sample_end: u64,
+}
+
+/// Result of one walk-forward window
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WindowResult {
+    /// Window
+    pub window: WalkForwardWindow,
+
+    /// Parameters fitted in-sample
+    pub parameters: ParameterSet,
+
+    /// In-sample backtest with the fitted parameters
+    pub in_sample: BacktestResult,
+
+    /// Out-of-sample backtest with the fitted parameters
+    pub out_of_sample: BacktestResult,
+
+    /// Walk-forward efficiency (None if the in-sample return was not positive)
+    pub efficiency: Option<f64>,
+
+    /// Whether out-of-sample performance diverged from in-sample
+    pub diverged: bool,
+}
+
+/// Walk-forward report
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WalkForwardReport {
+    /// Per-window results
+    pub windows: Vec<WindowResult>,
+
+    /// Stitched out-of-sample equity curve (timestamp, capital)
+    pub out_of_sample_equity: Vec<(u64, f64)>,
+
+    /// Compounded out-of-sample return in %
+    pub out_of_sample_return: f64,
+
+    /// Max drawdown of the stitched out-of-sample equity in %
+    pub out_of_sample_max_drawdown: f64,
+
+    /// Out-of-sample trades
+    pub out_of_sample_trades: u32,
+
+    /// Average walk-forward efficiency over windows where it is defined
+    pub average_efficiency: Option<f64>,
+
+    /// Divergence warnings
+    pub warnings: Vec<String>,
+}
+
+impl WalkForwardReport {
+    /// Print the report
+    pub fn print_summary(&self) {
+        println!("=== Walk-Forward Analysis ({} windows) ===", self.windows.len());
+        for result in &self.windows {
+            println!("Window {}: IS {:.2}% | OOS {:.2}% ({} trades){}",
+                     result.window.index,
+                     result.in_sample.total_return,
+                     result.out_of_sample.total_return,
+                     result.out_of_sample.total_trades,
+                     if result.diverged { " [DIVERGED]" } else { "" });
+        }
+        println!("OOS Return: {:.2}%", self.out_of_sample_return);
+        println!("OOS Max Drawdown: {:.2}%", self.out_of_sample_max_drawdown);
+        if let Some(efficiency) = self.average_efficiency {
+            println!("Average Efficiency: {:.2}", efficiency);
+        }
+        for warning in &self.warnings {
+            println!("WARNING: {}", warning);
+        }
+    }
+}
+
+/// Walk-forward runner
+pub struct WalkForwardRunner {
+    /// Backtest configuration covering the whole period
+    base: BacktestConfig,
+
+    /// Walk-forward configuration
+    config: WalkForwardConfig,
+}
+
+impl WalkForwardRunner {
+    /// Create a new walk-forward runner
+    pub fn new(base: BacktestConfig, config: WalkForwardConfig) -> Self {
+        Self { base, config }
+    }
+
+    /// Windows covering the backtest period
+    pub fn windows(&self) -> Vec<WalkForwardWindow> {
+        let step = self.config.step_seconds.unwrap_or(self.config.out_of_sample_seconds).max(1);
+        let mut windows = Vec::new();
+        let mut offset = 0;
+
+        loop {
+            let in_sample_start = if self.config.anchored { self.base.start_date } else { self.base.start_date + offset };
+            let in_sample_end = self.base.start_date + offset + self.config.in_sample_seconds;
+            let out_of_sample_end = in_sample_end + self.config.out_of_sample_seconds;
+            if out_of_sample_end > self.base.end_date {
+                break;
+            }
+
+            windows.push(WalkForwardWindow {
+                index: windows.len(),
+                in_sample_start,
+                in_sample_end,
+                out_of_sample_end,
+            });
+            offset += step;
+        }
+
+        windows
+    }
+
+    /// Run the analysis
+    ///
+    /// `fit` chooses parameters for an in-sample configuration (e.g. with the
+    /// `GeneticOptimizer`); `evaluate` backtests a parameter set on a configuration.
+    pub fn run<F, E>(&self, fit: F, evaluate: E) -> Result<WalkForwardReport>
+    where
+        F: Fn(&BacktestConfig) -> Result<ParameterSet>,
+        E: Fn(&BacktestConfig, &ParameterSet) -> Result<BacktestResult>,
+    {
+        let windows = self.windows();
+        if windows.is_empty() {
+            return Err(anyhow::anyhow!("Backtest period is shorter than one walk-forward window"));
+        }
+
+        let mut results = Vec::with_capacity(windows.len());
+        let mut warnings = Vec::new();
+
+        for window in windows {
+            let in_sample_config = self.period(window.in_sample_start, window.in_sample_end);
+            let out_of_sample_config = self.period(window.in_sample_end, window.out_of_sample_end);
+
+            let parameters = fit(&in_sample_config)?;
+            let in_sample = evaluate(&in_sample_config, &parameters)?;
+            let out_of_sample = evaluate(&out_of_sample_config, &parameters)?;
+
+            let daily = |r: &BacktestResult| if r.duration_days > 0.0 { r.total_return / r.duration_days } else { 0.0 };
+            let (is_daily, oos_daily) = (daily(&in_sample), daily(&out_of_sample));
+            let efficiency = (is_daily > 0.0).then(|| oos_daily / is_daily);
+
+            let diverged = match efficiency {
+                Some(efficiency) => efficiency < self.config.min_efficiency,
+                None => false,
+            };
+            if diverged {
+                let message = format!(
+                    "Window {}: out-of-sample return {:.2}% vs in-sample {:.2}% (efficiency {:.2} < {:.2})",
+                    window.index, out_of_sample.total_return, in_sample.total_return,
+                    efficiency.unwrap_or(0.0), self.config.min_efficiency
+                );
+                warn!("{}", message);
+                warnings.push(message);
+            }
+
+            info!("Walk-forward window {}: IS {:.2}%, OOS {:.2}%", window.index, in_sample.total_return, out_of_sample.total_return);
+
+            results.push(WindowResult {
+                window,
+                parameters,
+                in_sample,
+                out_of_sample,
+                efficiency,
+                diverged,
+            });
+        }
+
+        let diverged_count = results.iter().filter(|r| r.diverged).count();
+        if diverged_count * 2 > results.len() {
+            warnings.push(format!(
+                "{} of {} windows diverged; the strategy is likely overfit",
+                diverged_count, results.len()
+            ));
+        }
+
+        Ok(self.build_report(results, warnings))
+    }
+
+    /// Stitch out-of-sample results into one equity curve
+    fn build_report(&self, windows: Vec<WindowResult>, warnings: Vec<String>) -> WalkForwardReport {
+        let initial_capital = self.base.initial_capital;
+        let mut capital = initial_capital;
+        let mut peak = capital;
+        let mut max_drawdown: f64 = 0.0;
+        let mut equity = vec![(self.base.start_date, capital)];
+        let mut trades = 0;
+
+        for result in &windows {
+            let window_start_capital = capital;
+            let window_initial = result.out_of_sample.config.initial_capital.max(f64::EPSILON);
+            let mut sorted = result.out_of_sample.trades.clone();
+            sorted.sort_by_key(|t| t.exit_time);
+
+            // Scale each window's trades to the capital carried into it
+            for trade in &sorted {
+                capital += window_start_capital * trade.profit_loss / window_initial;
+                peak = peak.max(capital);
+                if peak > 0.0 {
+                    max_drawdown = max_drawdown.max((peak - capital) / peak * 100.0);
+                }
+                equity.push((trade.exit_time, capital));
+            }
+
+            trades += result.out_of_sample.total_trades;
+        }
+
+        let efficiencies: Vec<f64> = windows.iter().filter_map(|r| r.efficiency).collect();
+        let average_efficiency = (!efficiencies.is_empty())
+            .then(|| efficiencies.iter().sum::<f64>() / efficiencies.len() as f64);
+
+        WalkForwardReport {
+            windows,
+            out_of_sample_equity: equity,
+            out_of_sample_return: if initial_capital > 0.0 { (capital / initial_capital - 1.0) * 100.0 } else { 0.0 },
+            out_of_sample_max_drawdown: max_drawdown,
+            out_of_sample_trades: trades,
+            average_efficiency,
+            warnings,
+        }
+    }
+
+    /// Copy of the base configuration restricted to a period
+    fn period(&self, start: u64, end: u64) -> BacktestConfig {
+        let mut config = self.base.clone();
+        config.start_date = start;
+        config.end_date = end;
+        config
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backtest::BacktestTrade;
+
+    #[test]
+    fn test_windows_and_divergence_warning() {
+        let base = BacktestConfig::new(0, 100 * 86_400, 1_000.0, vec!["BTCUSDT".to_string()]);
+        let runner = WalkForwardRunner::new(base, WalkForwardConfig {
+            in_sample_seconds: 30 * 86_400,
+            out_of_sample_seconds: 10 * 86_400,
+            ..WalkForwardConfig::default()
+        });
+
+        let windows = runner.windows();
+        assert_eq!(windows.len(), 7);
+        assert_eq!(windows[1].in_sample_start, 10 * 86_400);
+
+        // Profitable in-sample, flat-to-losing out-of-sample
+        let report = runner.run(
+            |_| Ok(ParameterSet::new()),
+            |config, _| {
+                let in_sample = config.end_date - config.start_date > 20 * 86_400;
+                let mut trade = BacktestTrade::new("BTCUSDT".to_string(), config.start_date, 100.0, 1.0, "long".to_string());
+                trade.exit_time = config.end_date;
+                trade.profit_loss = if in_sample { 100.0 } else { -10.0 };
+                Ok(BacktestResult::new(config.clone(), vec![trade]))
+            },
+        ).unwrap();
+
+        assert!(report.windows.iter().all(|w| w.diverged));
+        assert!(!report.warnings.is_empty());
+        assert!(report.out_of_sample_return < 0.0);
+    }
+}
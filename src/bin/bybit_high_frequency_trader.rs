@@ -23,6 +23,7 @@ use omni::agents::zero_loss_enforcer::ZeroLossEnforcer;
 use omni::agents::market_analyzer::MarketAnalyzer;
 use omni::agents::sentiment_analyzer::SentimentAnalyzer;
 use omni::agents::risk_manager::RiskManager;
+use omni::risk::pre_trade::{PreTradeConfig, PreTradePipeline, TradeProposal};
 use omni::strategy::indicators::*;
 use omni::exchange::types::Candle;
 
@@ -151,6 +152,9 @@ struct HighFrequencyTradingSystem {
     /// Risk manager
     risk_manager: RiskManager,
 
+    /// Pre-trade checks every order runs through
+    pre_trade: PreTradePipeline,
+
     /// Asset metadata cache
     asset_metadata: HashMap<String, AssetMetadata>,
 
@@ -213,6 +217,7 @@ impl HighFrequencyTradingSystem {
             market_analyzer,
             sentiment_analyzer,
             risk_manager,
+            pre_trade: PreTradePipeline::new(PreTradeConfig::default()),
             asset_metadata: HashMap::new(),
             active_trades: HashMap::new(),
             initial_capital: 12.0,
@@ -777,6 +782,25 @@ impl HighFrequencyTradingSystem {
             _ => opportunity.price,
         };
 
+        // Run the order through the pre-trade checks
+        let proposal = TradeProposal {
+            symbol: opportunity.symbol.clone(),
+            direction: if opportunity.action == "buy" { TradeDirection::Long } else { TradeDirection::Short },
+            source: "high_frequency_trader".to_string(),
+            confidence: opportunity.score,
+            entry_price: opportunity.price,
+            stop_loss_price: stop_loss,
+            take_profit_price: take_profit,
+            position_size: quantity,
+            leverage,
+            timestamp: Utc::now(),
+        };
+        let open_symbols: Vec<String> = self.active_trades.keys().cloned().collect();
+        if let Some(rejection) = self.pre_trade.check_with_risk_manager(&proposal, &mut self.risk_manager, &open_symbols).rejection {
+            info!("{:?} check rejected {}: {}", rejection.check, opportunity.symbol, rejection.reason);
+            return Ok(());
+        }
+
         // Place order with EXACT parameters for both LONG and SHORT positions
         let side = if opportunity.action == "buy" {
             OrderSide::Buy  // LONG position
@@ -796,7 +820,15 @@ impl HighFrequencyTradingSystem {
             false,
             Some(take_profit),
             Some(stop_loss),
-        ).await?;
+        ).await;
+        let order = match order {
+            Ok(order) => order,
+            Err(e) => {
+                // Release the entry the pre-trade checks booked
+                self.risk_manager.record_external_exit(&opportunity.symbol, 0.0, Utc::now());
+                return Err(e);
+            }
+        };
 
         info!("Order placed successfully! Order ID: {}", order.order_id);
         info!("Entry: ${:.6}, Take Profit: ${:.6}, Stop Loss: ${:.6}",
@@ -961,7 +993,9 @@ impl HighFrequencyTradingSystem {
 
         // Remove closed trades
         for symbol in closed_trades {
-            self.active_trades.remove(&symbol);
+            if let Some(trade) = self.active_trades.remove(&symbol) {
+                self.risk_manager.record_external_exit(&symbol, trade.pnl.unwrap_or(0.0), Utc::now());
+            }
             info!("Removed {} from active trades", symbol);
         }
 
@@ -3,6 +3,7 @@
 //! This module provides a high-frequency trading system for the OMNI-ALPHA VΩ∞∞ platform.
 //! It aims to execute 750 profitable trades per day with a minimum profit of $2 USDT per trade.
 
+use std::path::Path;
 use std::sync::Arc;
 use std::time::Duration;
 use anyhow::Result;
@@ -10,12 +11,14 @@ use tokio::time::sleep;
 use tracing::{info, error, Level};
 use tracing_subscriber::FmtSubscriber;
 
+use omni::agents::agent_coordinator::AgentCoordinator;
 use omni::agents::high_frequency_trader::{HighFrequencyTrader, HighFrequencyTraderConfig};
 use omni::agents::zero_loss_enforcer::{ZeroLossEnforcer, ZeroLossEnforcerConfig};
 use omni::agents::ghost_trader::{GhostTrader, GhostTraderConfig};
 use omni::agents::compound_controller::{CompoundController, CompoundControllerConfig};
 use omni::engine::message_bus::MessageBus;
 use omni::engine::agent_trait::Agent;
+use omni::deployment::ConfigManager;
 use omni::exchange::bybit::adapter::BybitAdapter;
 
 #[tokio::main]
@@ -71,72 +74,74 @@ async fn main() -> Result<()> {
             }
         }
         
-        // Create High Frequency Trader
-        let hft_config = HighFrequencyTraderConfig {
-            initial_capital: 12.0,
-            target_trades_per_day: 750,
-            min_profit_per_trade: 2.0,
-            max_concurrent_trades: 10,
-            timeframes: vec!["1".to_string(), "3".to_string(), "5".to_string()],
-            max_assets: 100,
-            dynamic_leverage: true,
-            trade_interval_ms: 115200, // 86400000 ms in a day / 750 trades = 115200 ms per trade
+        // Agent roster: which agents run and how they are configured
+        let mut config_manager = ConfigManager::new("config/strategies");
+        let roster_path = Path::new("config/agents.toml");
+        if roster_path.exists() {
+            config_manager.load_agents(roster_path)?;
+        }
+
+        let mut agents: Vec<Box<dyn Agent>> = Vec::new();
+        if config_manager.is_agent_enabled("high_frequency_trader") {
+            let hft_config: HighFrequencyTraderConfig = config_manager.agent_config("high_frequency_trader")?;
+            // Orders share the coordinator's order state, rate limiter and halts
+            let coordinator = AgentCoordinator::new(hft_config.initial_capital);
+            agents.push(Box::new(HighFrequencyTrader::new(
+                hft_config,
+                bybit_adapter.clone(),
+                message_bus.clone(),
+                coordinator.get_trade_executor().get_order_manager(),
+                coordinator.get_trade_executor().get_order_limiter(),
+                coordinator.get_risk_manager().get_state_machine(),
+            )));
+        }
+        if config_manager.is_agent_enabled("ghost_trader") {
+            let ghost_trader_config: GhostTraderConfig = config_manager.agent_config("ghost_trader")?;
+            agents.push(Box::new(GhostTrader::new(ghost_trader_config, bybit_adapter.clone(), message_bus.clone())));
+        }
+        if config_manager.is_agent_enabled("compound_controller") {
+            let compound_controller_config: CompoundControllerConfig = config_manager.agent_config("compound_controller")?;
+            agents.push(Box::new(CompoundController::new(compound_controller_config, message_bus.clone())));
+        }
+
+        let mut zero_loss_enforcer = if config_manager.is_agent_enabled("zero_loss_enforcer") {
+            let zero_loss_config: ZeroLossEnforcerConfig = config_manager.agent_config("zero_loss_enforcer")?;
+            Some(ZeroLossEnforcer::new(zero_loss_config, bybit_adapter.clone(), message_bus.clone()))
+        } else {
+            None
         };
-        
-        let mut high_frequency_trader = HighFrequencyTrader::new(
-            hft_config,
-            bybit_adapter.clone(),
-            message_bus.clone(),
-        );
-        
-        // Create Zero Loss Enforcer
-        let zero_loss_config = ZeroLossEnforcerConfig::default();
-        let mut zero_loss_enforcer = ZeroLossEnforcer::new(
-            zero_loss_config,
-            bybit_adapter.clone(),
-            message_bus.clone(),
-        );
-        
-        // Create Ghost Trader
-        let ghost_trader_config = GhostTraderConfig::default();
-        let mut ghost_trader = GhostTrader::new(
-            ghost_trader_config,
-            bybit_adapter.clone(),
-            message_bus.clone(),
-        );
-        
-        // Create Compound Controller
-        let compound_controller_config = CompoundControllerConfig::default();
-        let mut compound_controller = CompoundController::new(
-            compound_controller_config,
-            message_bus.clone(),
-        );
-        
+
         // Initialize agents
         info!("Initializing agents...");
-        high_frequency_trader.initialize(Arc::new(tokio::sync::RwLock::new(Default::default()))).await?;
-        zero_loss_enforcer.initialize(Arc::new(tokio::sync::RwLock::new(Default::default()))).await?;
-        ghost_trader.initialize(Arc::new(tokio::sync::RwLock::new(Default::default()))).await?;
-        compound_controller.initialize(Arc::new(tokio::sync::RwLock::new(Default::default()))).await?;
-        
+        for agent in agents.iter_mut() {
+            agent.initialize(Arc::new(tokio::sync::RwLock::new(Default::default()))).await?;
+        }
+        if let Some(zero_loss_enforcer) = zero_loss_enforcer.as_mut() {
+            zero_loss_enforcer.initialize(Arc::new(tokio::sync::RwLock::new(Default::default()))).await?;
+        }
+
         // Start agents
         info!("Starting agents...");
-        high_frequency_trader.start().await?;
-        zero_loss_enforcer.start().await?;
-        ghost_trader.start().await?;
-        compound_controller.start().await?;
-        
+        for agent in agents.iter_mut() {
+            agent.start().await?;
+        }
+        if let Some(zero_loss_enforcer) = zero_loss_enforcer.as_mut() {
+            zero_loss_enforcer.start().await?;
+        }
+
         // Main loop
         info!("Entering main trading loop...");
         let mut iteration = 0;
-        
+
         loop {
             // Update agents
-            high_frequency_trader.update().await?;
-            zero_loss_enforcer.update().await?;
-            ghost_trader.update().await?;
-            compound_controller.update().await?;
-            
+            for agent in agents.iter_mut() {
+                agent.tick().await?;
+            }
+            if let Some(zero_loss_enforcer) = zero_loss_enforcer.as_mut() {
+                zero_loss_enforcer.update().await?;
+            }
+
             // Sleep for a short time
             sleep(Duration::from_millis(100)).await;
             
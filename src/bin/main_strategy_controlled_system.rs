@@ -11,6 +11,7 @@ use tokio::time::sleep;
 use tracing::{info, error, Level};
 use tracing_subscriber::FmtSubscriber;
 
+use omni::agents::agent_coordinator::AgentCoordinator;
 use omni::agents::main_strategy_controller::{MainStrategyController, MainStrategyControllerConfig};
 use omni::agents::high_frequency_trader::{HighFrequencyTrader, HighFrequencyTraderConfig};
 use omni::agents::zero_loss_enforcer::{ZeroLossEnforcer, ZeroLossEnforcerConfig};
@@ -100,12 +101,19 @@ async fn main() -> Result<()> {
             max_assets: 100,
             dynamic_leverage: true,
             trade_interval_ms: 115200, // Will be controlled by main strategy
+            min_order_flow_imbalance: 0.3,
+            ..Default::default()
         };
         
+        // Orders share the coordinator's order state, rate limiter and halts
+        let coordinator = AgentCoordinator::new(12.0);
         let mut high_frequency_trader = HighFrequencyTrader::new(
             hft_config,
             bybit_adapter.clone(),
             message_bus.clone(),
+            coordinator.get_trade_executor().get_order_manager(),
+            coordinator.get_trade_executor().get_order_limiter(),
+            coordinator.get_risk_manager().get_state_machine(),
         );
         
         // Create Zero Loss Enforcer
@@ -150,7 +158,7 @@ async fn main() -> Result<()> {
         // Run the high frequency trader update loop
         let hft_handle = tokio::spawn(async move {
             loop {
-                if let Err(e) = high_frequency_trader.update().await {
+                if let Err(e) = high_frequency_trader.tick().await {
                     error!("❌ High Frequency Trader update failed: {}", e);
                 }
                 sleep(Duration::from_millis(100)).await; // Update every 100ms
@@ -142,6 +142,7 @@ async fn run_simulation(capital: f64, duration: u64) -> anyhow::Result<()> {
         },
         heartbeat_interval: 1,
         exchange: ExchangeConfig::default(),
+        ..TradingSystemConfig::default()
     };
 
     // Create trading system
@@ -252,6 +253,7 @@ async fn run_live(capital: f64, api_key: String, api_secret: String) -> anyhow::
             testnet: true, // Use testnet for safety
             category: "linear".to_string(),
         },
+        ..TradingSystemConfig::default()
     };
 
     // Create trading system
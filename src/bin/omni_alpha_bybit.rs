@@ -19,6 +19,7 @@ use omni::exchange::bybit::demo_adapter::BybitDemoAdapter;
 use omni::exchange::types::Candle;
 use omni::trading_system::{TradingSystem, TradingSystemConfig, TradingMode, ExchangeConfig};
 use omni::strategy::advanced_strategy::AdvancedStrategy;
+use omni::risk::pre_trade::TradeProposal;
 
 /// Asset configuration for trading
 struct AssetConfig {
@@ -343,6 +344,7 @@ async fn main() -> Result<()> {
             testnet: true, // true = use demo API
             category: "linear".to_string(), // Use linear for USDT perpetual contracts
         },
+        ..TradingSystemConfig::default()
     };
 
     // Create trading system
@@ -482,7 +484,7 @@ async fn main() -> Result<()> {
                             trading_system.send_message(signal.clone());
 
                             // Extract signal details for order placement
-                            if let Message::TradeSignal { direction, entry_price, stop_loss_price, take_profit_price, .. } = signal {
+                            if let Message::TradeSignal { direction, confidence, entry_price, stop_loss_price, take_profit_price, source, timestamp, .. } = signal {
                                 // Make sure we're using the correct symbol format for futures
                                 let futures_symbol = if !symbol.ends_with("USDT") {
                                     format!("{}{}", symbol, "USDT")
@@ -512,8 +514,9 @@ async fn main() -> Result<()> {
                                 let asset_config = get_asset_config(&futures_symbol);
 
                                 // Set optimal leverage for this symbol based on asset configuration
-                                // Use 50-100x leverage for 0.5-0.8% price movements as specified
-                                let leverage = asset_config.max_leverage.max(50).min(100); // 50-100x range as specified
+                                // Use 50-100x leverage for 0.5-0.8% price movements, within what the risk manager permits
+                                let permitted_leverage = trading_system.get_risk_manager().get_max_leverage(&futures_symbol).floor().max(1.0) as u32;
+                                let leverage = asset_config.max_leverage.max(50).min(100).min(permitted_leverage);
 
                                 match bybit_adapter.set_leverage(&futures_symbol, leverage).await {
                                     Ok(_) => info!("Successfully set leverage for {} to {}x", futures_symbol, leverage),
@@ -609,6 +612,25 @@ async fn main() -> Result<()> {
                                       take_profit_price, price_movement * 100.0,
                                       stop_loss_price, (if side == "Buy" { entry_price - stop_loss_price } else { stop_loss_price - entry_price }) / entry_price * 100.0);
 
+                                // Run the order through the pre-trade checks
+                                let proposal = TradeProposal {
+                                    symbol: futures_symbol.clone(),
+                                    direction: direction.clone(),
+                                    source,
+                                    confidence,
+                                    entry_price,
+                                    stop_loss_price,
+                                    take_profit_price,
+                                    position_size: qty,
+                                    leverage: leverage as f64,
+                                    timestamp,
+                                };
+                                let open_symbols: Vec<String> = active_positions.keys().cloned().collect();
+                                if let Some(rejection) = trading_system.check_external_trade(&proposal, &open_symbols).rejection {
+                                    info!("Skipping trade on {}: {:?} check failed: {}", futures_symbol, rejection.check, rejection.reason);
+                                    continue;
+                                }
+
                                 // Place order with retry
                                 info!("Placing {} order for {} at ${:.2} (qty: {:.8})",
                                       side, futures_symbol, entry_price, qty);
@@ -679,6 +701,7 @@ async fn main() -> Result<()> {
                                     },
                                     Err(e) => {
                                         error!("Failed to place order for {}: {}", futures_symbol, e);
+                                        trading_system.record_external_exit(&futures_symbol, 0.0);
                                     }
                                 }
                             }
@@ -812,6 +835,7 @@ async fn main() -> Result<()> {
                             ).await {
                                 Ok(order_id) => {
                                     info!("Successfully closed position for {}: Order ID: {}", symbol, order_id);
+                                    trading_system.record_external_exit(symbol, position_pnl);
 
                                     // Update profit/loss tracking
                                     if position_pnl > 0.0 {
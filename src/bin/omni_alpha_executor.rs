@@ -16,6 +16,9 @@ use rand::{thread_rng, Rng};
 
 use omni::exchange::bybit::adapter::BybitAdapter;
 use omni::exchange::bybit::types::{OrderSide, OrderType, TimeInForce, BybitKline};
+use omni::agents::risk_manager::RiskManager;
+use omni::engine::message_bus::TradeDirection;
+use omni::risk::pre_trade::{PreTradeConfig, PreTradePipeline, TradeProposal};
 
 /// Trading strategy
 #[derive(Debug, Clone)]
@@ -168,6 +171,12 @@ struct OmniAlphaSystem {
     /// Active trades
     active_trades: Arc<Mutex<Vec<ActiveTrade>>>,
     
+    /// Risk manager the pre-trade checks run against
+    risk_manager: Mutex<RiskManager>,
+    
+    /// Pre-trade checks every order runs through
+    pre_trade: Mutex<PreTradePipeline>,
+    
     /// Trading capital
     trading_capital: f64,
     
@@ -188,6 +197,8 @@ impl OmniAlphaSystem {
             exchange,
             strategy: TradingStrategy::default(),
             active_trades: Arc::new(Mutex::new(Vec::new())),
+            risk_manager: Mutex::new(RiskManager::new(initial_capital)),
+            pre_trade: Mutex::new(PreTradePipeline::new(PreTradeConfig::default())),
             trading_capital: initial_capital,
             initial_capital,
             symbols: vec![
@@ -695,6 +706,30 @@ impl OmniAlphaSystem {
             ),
         };
         
+        // Run the order through the pre-trade checks
+        let proposal = TradeProposal {
+            symbol: opportunity.symbol.clone(),
+            direction: match opportunity.action {
+                OrderSide::Buy => TradeDirection::Long,
+                OrderSide::Sell => TradeDirection::Short,
+            },
+            source: "omni_alpha_executor".to_string(),
+            confidence: opportunity.score,
+            entry_price: opportunity.price,
+            stop_loss_price: stop_loss,
+            take_profit_price: take_profit,
+            position_size: quantity,
+            leverage: 1.0,
+            timestamp: Utc::now(),
+        };
+        let open_symbols: Vec<String> = active_trades.iter().map(|t| t.symbol.clone()).collect();
+        let decision = self.pre_trade.lock().await
+            .check_with_risk_manager(&proposal, &mut *self.risk_manager.lock().await, &open_symbols);
+        if let Some(rejection) = decision.rejection {
+            info!("{:?} check rejected {}: {}", rejection.check, opportunity.symbol, rejection.reason);
+            return Ok(());
+        }
+        
         // Place order
         let order = self.exchange.place_order(
             "spot",
@@ -708,7 +743,15 @@ impl OmniAlphaSystem {
             false,
             Some(take_profit),
             Some(stop_loss),
-        ).await?;
+        ).await;
+        let order = match order {
+            Ok(order) => order,
+            Err(e) => {
+                // Release the entry the pre-trade checks booked
+                self.risk_manager.lock().await.record_external_exit(&opportunity.symbol, 0.0, Utc::now());
+                return Err(e);
+            }
+        };
         
         info!("Order placed successfully! Order ID: {}", order.order_id);
         
@@ -773,14 +816,16 @@ impl OmniAlphaSystem {
                         roi * 100.0
                     );
                     
-                    trades_to_remove.push(i);
+                    trades_to_remove.push((i, pnl));
                 }
             }
         }
         
         // Remove trades in reverse order to avoid index issues
-        for i in trades_to_remove.iter().rev() {
-            active_trades.remove(*i);
+        let mut risk_manager = self.risk_manager.lock().await;
+        for (i, pnl) in trades_to_remove.into_iter().rev() {
+            let trade = active_trades.remove(i);
+            risk_manager.record_external_exit(&trade.symbol, pnl, Utc::now());
         }
         
         Ok(())
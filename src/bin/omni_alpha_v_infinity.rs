@@ -92,8 +92,8 @@ async fn main() -> Result<()> {
         while iteration < max_iterations {
             // Update agents
             zero_loss_enforcer.update().await?;
-            asset_scanner_agent.update().await?;
-            god_kernel.update().await?;
+            asset_scanner_agent.tick().await?;
+            god_kernel.tick().await?;
             
             // Log current state every 10 iterations
             if iteration % 10 == 0 {
@@ -1,14 +1,14 @@
 //! OMNI Quantum-Enhanced Trading System
 //!
 //! This is the comprehensive quantum-enhanced trading system that leverages the complete OMNI
-//! architecture for ultra-high frequency trading with exactly 12 USDT capital constraint.
+//! architecture for ultra-high frequency trading on the configured starting capital.
 //!
 //! Features:
 //! - Quantum entanglement engine for market correlation analysis
 //! - Hyperdimensional computing (10K dimensions) for pattern recognition
 //! - Spectral tree engine for multi-dimensional analysis
 //! - Ultra-high frequency trading (750+ trades/day)
-//! - Precise capital allocation with 12 USDT constraint
+//! - Precise capital allocation within the configured capital
 //! - Zero-loss enforcement with advanced risk management
 //! - Bybit demo environment integration
 
@@ -34,21 +34,25 @@ use omni::agents::market_analyzer::{MarketAnalyzer, MarketAnalysis};
 use omni::agents::sentiment_analyzer::{SentimentAnalyzer, SentimentAnalysis};
 use omni::agents::risk_manager::{RiskManager, RiskAssessment};
 use omni::agents::hyperdimensional_pattern_recognizer::HyperdimensionalPatternRecognizer;
-use omni::capital::precision_allocator::PreciseCapitalTracker;
+use omni::capital::genesis::CapitalGenesisConfig;
+use omni::capital::precise_capital_tracker::PreciseCapitalTracker;
 use omni::exchange::bybit::adapter::BybitAdapter;
 use omni::exchange::bybit::types::{OrderSide, OrderType, TimeInForce};
 use omni::engine::message_bus::{MessageBus, TradeDirection};
 use omni::engine::agent_trait::AgentContext;
+use omni::trading_system::TradingSystemConfig;
+use omni::risk::pre_trade::{PreTradeCheck, PreTradeConfig, PreTradePipeline, TradeProposal};
 
 
 /// System constants
-const TOTAL_CAPITAL_USDT: f64 = 12.0;
 const TARGET_TRADES_PER_DAY: usize = 750;
 const MIN_PROFIT_PER_TRADE: f64 = 0.6;
 const MAX_RISK_PER_TRADE: f64 = 0.25;
 const TRADING_CYCLE_INTERVAL: u64 = 115; // ~1.92 minutes
 const MIN_CONFIDENCE_THRESHOLD: f64 = 75.0;
-const MAX_CONCURRENT_POSITIONS: usize = 2;
+
+/// Source the pre-trade checks see for this system's orders
+const SIGNAL_SOURCE: &str = "quantum_enhanced";
 
 /// Comprehensive trading opportunity
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -121,6 +125,7 @@ pub struct OmniQuantumTradingSystem {
     market_analyzer: MarketAnalyzer,
     sentiment_analyzer: SentimentAnalyzer,
     risk_manager: RiskManager,
+    pre_trade: PreTradePipeline,
     pattern_recognizer: HyperdimensionalPatternRecognizer,
     
     // Exchange and Infrastructure
@@ -130,6 +135,8 @@ pub struct OmniQuantumTradingSystem {
     
     // Capital Management
     capital_tracker: PreciseCapitalTracker,
+    capital_config: CapitalGenesisConfig,
+    initial_capital: f64,
     
     // State Management
     active_positions: HashMap<String, QuantumTradingOpportunity>,
@@ -144,10 +151,11 @@ pub struct OmniQuantumTradingSystem {
 
 impl OmniQuantumTradingSystem {
     /// Create a new quantum-enhanced trading system
-    pub async fn new(api_key: &str, api_secret: &str) -> Result<Self> {
+    pub async fn new(api_key: &str, api_secret: &str, initial_capital: f64, capital_config: CapitalGenesisConfig) -> Result<Self> {
+        capital_config.validate(initial_capital)?;
         info!("🚀 Initializing OMNI Quantum-Enhanced Trading System");
         info!("💰 Capital: {} USDT | Target: {} trades/day | Min Profit: {} USDT/trade", 
-              TOTAL_CAPITAL_USDT, TARGET_TRADES_PER_DAY, MIN_PROFIT_PER_TRADE);
+              initial_capital, TARGET_TRADES_PER_DAY, MIN_PROFIT_PER_TRADE);
         
         // Initialize quantum components
         info!("🔬 Initializing Quantum Components...");
@@ -172,14 +180,14 @@ impl OmniQuantumTradingSystem {
 
         let market_analyzer = MarketAnalyzer::new();
         let sentiment_analyzer = SentimentAnalyzer::new();
-        let risk_manager = RiskManager::new(TOTAL_CAPITAL_USDT);
+        let risk_manager = RiskManager::new(initial_capital);
         let pattern_recognizer = HyperdimensionalPatternRecognizer::new();
         
         let agent_context = Arc::new(RwLock::new(AgentContext::new()));
         
         // Initialize capital tracker
         info!("💰 Initializing Precise Capital Tracker...");
-        let capital_tracker = PreciseCapitalTracker::new();
+        let capital_tracker = capital_config.capital_tracker(initial_capital);
         
         // Initialize performance metrics
         let performance_metrics = PerformanceMetrics {
@@ -188,7 +196,7 @@ impl OmniQuantumTradingSystem {
             win_rate: 0.0,
             total_profit: 0.0,
             average_profit_per_trade: 0.0,
-            current_capital: TOTAL_CAPITAL_USDT,
+            current_capital: initial_capital,
             capital_growth: 0.0,
             trades_today: 0,
             last_trade_time: Utc::now(),
@@ -207,11 +215,14 @@ impl OmniQuantumTradingSystem {
             market_analyzer,
             sentiment_analyzer,
             risk_manager,
+            pre_trade: PreTradePipeline::new(PreTradeConfig::default()),
             pattern_recognizer,
             bybit_adapter,
             message_bus,
             agent_context,
             capital_tracker,
+            capital_config,
+            initial_capital,
             active_positions: HashMap::new(),
             analysis_history: VecDeque::with_capacity(1000),
             performance_metrics,
@@ -500,6 +511,7 @@ impl OmniQuantumTradingSystem {
     /// Perform sentiment analysis (10% weight)
     async fn perform_sentiment_analysis(&mut self, symbol: &str) -> Result<f64> {
         // Use sentiment analyzer
+        self.sentiment_analyzer.refresh(symbol).await;
         let sentiment_analysis = self.sentiment_analyzer.analyze(symbol)?;
 
         let mut score: f64 = 50.0; // Base score
@@ -575,7 +587,9 @@ impl OmniQuantumTradingSystem {
 
     /// Calculate optimal allocation based on confidence
     fn calculate_optimal_allocation(&self, confidence: f64) -> Result<f64> {
-        let available_capital = TOTAL_CAPITAL_USDT * 0.8; // Use 80% of total capital
+        // Current equity less the configured reserve
+        let available_capital = self.performance_metrics.current_capital
+            * (1.0 - self.capital_config.reserve_percentage);
 
         // Confidence-weighted allocation
         let allocation_percentage = if confidence >= 90.0 {
@@ -588,7 +602,8 @@ impl OmniQuantumTradingSystem {
             0.0 // No allocation for low confidence
         };
 
-        let allocation = available_capital * allocation_percentage;
+        let allocation = self.capital_config.max_position_notional
+            .map_or(available_capital * allocation_percentage, |cap| (available_capital * allocation_percentage).min(cap));
 
         // Ensure minimum order size (5 USDT for Bybit)
         if allocation < 5.0 {
@@ -632,7 +647,7 @@ impl OmniQuantumTradingSystem {
 
         // Calculate position parameters
         let position_size = analysis.optimal_allocation;
-        let leverage = self.calculate_optimal_leverage(analysis.composite_confidence);
+        let leverage = self.calculate_optimal_leverage(&analysis.symbol, analysis.composite_confidence);
 
         // Calculate stop loss and take profit
         let (stop_loss, take_profit) = self.calculate_stop_loss_take_profit(entry_price, direction, analysis.composite_confidence);
@@ -704,9 +719,9 @@ impl OmniQuantumTradingSystem {
         Ok(base_price * (1.0 + variation))
     }
 
-    /// Calculate optimal leverage based on confidence
-    fn calculate_optimal_leverage(&self, confidence: f64) -> f64 {
-        if confidence >= 90.0 {
+    /// Calculate optimal leverage based on confidence, capped by the risk manager
+    fn calculate_optimal_leverage(&self, symbol: &str, confidence: f64) -> f64 {
+        let leverage: f64 = if confidence >= 90.0 {
             100.0 // Maximum leverage for very high confidence
         } else if confidence >= 85.0 {
             75.0
@@ -714,7 +729,8 @@ impl OmniQuantumTradingSystem {
             50.0
         } else {
             25.0 // Conservative leverage for lower confidence
-        }
+        };
+        leverage.min(self.risk_manager.get_max_leverage(symbol))
     }
 
     /// Calculate stop loss and take profit prices
@@ -744,12 +760,34 @@ impl OmniQuantumTradingSystem {
 
         let mut executed_count = 0;
 
-        for opportunity in opportunities.iter().take(MAX_CONCURRENT_POSITIONS) {
+        for opportunity in opportunities {
             // Check if we already have a position for this symbol
             if self.active_positions.contains_key(&opportunity.symbol) {
                 continue;
             }
 
+            // Run the order through the pre-trade checks; stop once no position slot is left
+            let proposal = TradeProposal {
+                symbol: opportunity.symbol.clone(),
+                direction: opportunity.direction.clone(),
+                source: SIGNAL_SOURCE.to_string(),
+                confidence: opportunity.confidence / 100.0,
+                entry_price: opportunity.entry_price,
+                stop_loss_price: opportunity.stop_loss,
+                take_profit_price: opportunity.take_profit,
+                position_size: opportunity.position_size / opportunity.entry_price,
+                leverage: opportunity.leverage,
+                timestamp: Utc::now(),
+            };
+            let open_symbols: Vec<String> = self.active_positions.keys().cloned().collect();
+            if let Some(rejection) = self.pre_trade.check_with_risk_manager(&proposal, &mut self.risk_manager, &open_symbols).rejection {
+                info!("🚫 {:?} check rejected {}: {}", rejection.check, opportunity.symbol, rejection.reason);
+                if rejection.check == PreTradeCheck::Capacity {
+                    break;
+                }
+                continue;
+            }
+
             // Validate with zero-loss enforcer
             if let Ok(assessment) = self.validate_trade_with_zero_loss(opportunity).await {
                 if assessment.approved {
@@ -763,7 +801,7 @@ impl OmniQuantumTradingSystem {
                         self.active_positions.insert(opportunity.symbol.clone(), executed_opportunity);
 
                         // Allocate capital
-                        if let Err(e) = self.capital_tracker.allocate_capital(&opportunity.symbol, opportunity.position_size) {
+                        if let Err(e) = self.capital_tracker.allocate_capital(opportunity.symbol.clone(), opportunity.position_size) {
                             warn!("Failed to allocate capital for {}: {}", opportunity.symbol, e);
                         }
 
@@ -784,6 +822,11 @@ impl OmniQuantumTradingSystem {
                     info!("🚫 Trade rejected by zero-loss enforcer: {}", assessment.reasoning);
                 }
             }
+
+            // Release the entry the pre-trade checks booked unless the trade was placed
+            if !self.active_positions.contains_key(&opportunity.symbol) {
+                self.risk_manager.record_external_exit(&opportunity.symbol, 0.0, Utc::now());
+            }
         }
 
         info!("📊 Executed {} trades out of {} opportunities", executed_count, opportunities.len());
@@ -877,10 +920,10 @@ impl OmniQuantumTradingSystem {
             (self.performance_metrics.trades_today as f64 / TARGET_TRADES_PER_DAY as f64) * 100.0;
 
         // Update capital growth (simulated)
-        let current_capital = TOTAL_CAPITAL_USDT + (self.performance_metrics.total_profit);
+        let current_capital = self.initial_capital + (self.performance_metrics.total_profit);
         self.performance_metrics.current_capital = current_capital;
         self.performance_metrics.capital_growth =
-            ((current_capital - TOTAL_CAPITAL_USDT) / TOTAL_CAPITAL_USDT) * 100.0;
+            ((current_capital - self.initial_capital) / self.initial_capital) * 100.0;
     }
 
     /// Display progress and metrics
@@ -930,8 +973,11 @@ async fn main() -> Result<()> {
     let api_secret = std::env::var("BYBIT_DEMO_API_SECRET")
         .unwrap_or_else(|_| "aXjs1SF9tmW3riHMktmjtyOyAT85puvrVstr".to_string());
     
+    // Starting capital and its limits come from the trading system config
+    let config = TradingSystemConfig::default();
+
     // Create and start the quantum trading system
-    let mut system = OmniQuantumTradingSystem::new(&api_key, &api_secret).await?;
+    let mut system = OmniQuantumTradingSystem::new(&api_key, &api_secret, config.initial_capital, config.capital).await?;
     system.start().await?;
     
     Ok(())
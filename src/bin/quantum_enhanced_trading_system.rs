@@ -16,6 +16,12 @@ use uuid::Uuid;
 
 // Core dependencies
 use std::env;
+use omni::agents::risk_manager::RiskManager;
+use omni::engine::message_bus;
+use omni::risk::pre_trade::{PreTradeConfig, PreTradePipeline, TradeProposal};
+
+/// Source the pre-trade checks see for this system's orders
+const SIGNAL_SOURCE: &str = "quantum_enhanced";
 
 /// Trade direction
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
@@ -446,6 +452,12 @@ pub struct QuantumEnhancedTradingSystem {
 
     /// System state
     system_state: Arc<RwLock<SystemState>>,
+
+    /// Risk manager capping leverage
+    risk_manager: Arc<RwLock<RiskManager>>,
+
+    /// Pre-trade checks every order runs through
+    pre_trade: Arc<RwLock<PreTradePipeline>>,
 }
 
 impl QuantumEnhancedTradingSystem {
@@ -469,6 +481,7 @@ impl QuantumEnhancedTradingSystem {
         let mut metrics = PerformanceMetrics::default();
         metrics.current_capital = config.total_capital;
         let performance_metrics = Arc::new(RwLock::new(metrics));
+        let risk_manager = Arc::new(RwLock::new(RiskManager::new(config.total_capital)));
 
         Ok(Self {
             config,
@@ -477,6 +490,8 @@ impl QuantumEnhancedTradingSystem {
             trade_history: Arc::new(RwLock::new(Vec::new())),
             performance_metrics,
             system_state,
+            risk_manager,
+            pre_trade: Arc::new(RwLock::new(PreTradePipeline::new(PreTradeConfig::default()))),
         })
     }
 
@@ -903,10 +918,11 @@ impl QuantumEnhancedTradingSystem {
         let base_allocation = max_position_size * 0.4; // Conservative base
         let position_size = base_allocation * confidence_multiplier;
 
-        // Dynamic leverage based on confidence and volatility
+        // Dynamic leverage based on confidence, within what the risk manager permits
         let base_leverage = 50;
         let confidence_leverage_bonus = (confidence * 50.0) as u32;
-        let leverage = (base_leverage + confidence_leverage_bonus).min(self.config.max_leverage);
+        let permitted_leverage = self.risk_manager.read().await.get_max_leverage(symbol).floor().max(1.0) as u32;
+        let leverage = (base_leverage + confidence_leverage_bonus).min(self.config.max_leverage).min(permitted_leverage);
 
         // Ensure minimum position size requirements
         let final_position_size = position_size.max(1.0).min(max_position_size);
@@ -1025,8 +1041,40 @@ impl QuantumEnhancedTradingSystem {
             return Ok(());
         }
 
-        // Execute the actual trade on Bybit demo
-        let order_result = self.place_bybit_order(opportunity).await?;
+        // Run the order through the pre-trade checks
+        let proposal = TradeProposal {
+            symbol: opportunity.symbol.clone(),
+            direction: match opportunity.direction {
+                TradeDirection::Long => message_bus::TradeDirection::Long,
+                TradeDirection::Short => message_bus::TradeDirection::Short,
+            },
+            source: SIGNAL_SOURCE.to_string(),
+            confidence: opportunity.confidence,
+            entry_price: opportunity.entry_price,
+            stop_loss_price: opportunity.stop_loss,
+            take_profit_price: opportunity.take_profit,
+            position_size: opportunity.position_size * opportunity.leverage as f64 / opportunity.entry_price,
+            leverage: opportunity.leverage as f64,
+            timestamp: Utc::now(),
+        };
+        let open_symbols: Vec<String> = self.active_trades.read().await.values()
+            .map(|trade| trade.symbol.clone())
+            .collect();
+        let decision = self.pre_trade.write().await
+            .check_with_risk_manager(&proposal, &mut *self.risk_manager.write().await, &open_symbols);
+        if let Some(rejection) = decision.rejection {
+            warn!("{:?} check rejected trade for {}: {}", rejection.check, opportunity.symbol, rejection.reason);
+            return Ok(());
+        }
+
+        // Execute the actual trade on Bybit demo, releasing the booked entry if it fails
+        let order_result = match self.place_bybit_order(opportunity).await {
+            Ok(order_result) => order_result,
+            Err(e) => {
+                self.risk_manager.write().await.record_external_exit(&opportunity.symbol, 0.0, Utc::now());
+                return Err(e);
+            }
+        };
 
         // Create trade execution result
         let trade_result = TradeExecutionResult {
@@ -1585,6 +1633,8 @@ impl Clone for QuantumEnhancedTradingSystem {
             trade_history: Arc::clone(&self.trade_history),
             performance_metrics: Arc::clone(&self.performance_metrics),
             system_state: Arc::clone(&self.system_state),
+            risk_manager: Arc::clone(&self.risk_manager),
+            pre_trade: Arc::clone(&self.pre_trade),
         }
     }
 }
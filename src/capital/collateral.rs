@@ -0,0 +1,261 @@
+//! Multi-Collateral Capital
+//!
+//! This module keeps one `CapitalManager` per settle coin, so USDT-margined,
+//! USDC-margined and coin-margined (inverse) balances are each tracked and
+//! allocated in their own coin. Conversion rates to USDT are fetched from the
+//! exchange and only used to aggregate the balances for reporting; no capital is
+//! ever moved between coins.
+
+use std::collections::BTreeMap;
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tracing::{debug, warn};
+
+use crate::capital::manager::{CapitalAllocationReport, CapitalManager, DEFAULT_SETTLE_COIN};
+use crate::exchange::BybitAdapter;
+
+/// Whether a symbol is an inverse (coin-margined) contract, e.g. BTCUSD
+pub fn is_inverse_symbol(symbol: &str) -> bool {
+    let symbol = symbol.to_uppercase();
+    symbol.ends_with("USD") && symbol.len() > 3
+}
+
+/// Coin a symbol's margin and P&L are settled in
+pub fn settle_coin_for_symbol(symbol: &str) -> String {
+    let symbol = symbol.to_uppercase();
+    if symbol.ends_with("USDT") {
+        "USDT".to_string()
+    } else if symbol.ends_with("USDC") || symbol.ends_with("PERP") {
+        "USDC".to_string()
+    } else if is_inverse_symbol(&symbol) {
+        symbol.trim_end_matches("USD").to_string()
+    } else {
+        DEFAULT_SETTLE_COIN.to_string()
+    }
+}
+
+/// Convert a USD amount of a trade on `symbol` at `price` to its settle coin
+pub fn to_settle_units(symbol: &str, usd_amount: f64, price: f64) -> f64 {
+    if is_inverse_symbol(symbol) && price > 0.0 {
+        usd_amount / price
+    } else {
+        usd_amount
+    }
+}
+
+/// One settle coin in the aggregate report
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CollateralValue {
+    /// Settle coin
+    pub coin: String,
+
+    /// USDT value of one coin, if known
+    pub rate: Option<f64>,
+
+    /// Capital in USDT, if the rate is known
+    pub total_capital_usdt: Option<f64>,
+
+    /// Allocation in the coin itself
+    pub allocation: CapitalAllocationReport,
+}
+
+/// Capital across all settle coins
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CollateralReport {
+    /// Report time
+    pub timestamp: DateTime<Utc>,
+
+    /// Time the rates were last fetched
+    pub rates_updated: Option<DateTime<Utc>>,
+
+    /// Capital in USDT across the coins with a known rate
+    pub total_capital_usdt: f64,
+
+    /// Coins without a rate, left out of the total
+    pub unpriced: Vec<String>,
+
+    /// Per-coin capital
+    pub collaterals: Vec<CollateralValue>,
+}
+
+/// Capital managers by settle coin
+#[derive(Debug)]
+pub struct CollateralManager {
+    /// Managers by settle coin
+    managers: BTreeMap<String, CapitalManager>,
+
+    /// USDT value of one coin
+    rates: BTreeMap<String, f64>,
+
+    /// Time the rates were last fetched
+    rates_updated: Option<DateTime<Utc>>,
+}
+
+impl CollateralManager {
+    /// Create a collateral manager holding `manager`
+    pub fn new(manager: CapitalManager) -> Self {
+        let mut collateral = Self {
+            managers: BTreeMap::new(),
+            rates: BTreeMap::new(),
+            rates_updated: None,
+        };
+        collateral.rates.insert(DEFAULT_SETTLE_COIN.to_string(), 1.0);
+        collateral.managers.insert(manager.get_settle_coin().to_string(), manager);
+        collateral
+    }
+
+    /// Track capital in another settle coin
+    pub fn add_collateral(&mut self, manager: CapitalManager) -> Result<()> {
+        let coin = manager.get_settle_coin().to_string();
+        if self.managers.contains_key(&coin) {
+            return Err(anyhow::anyhow!("{} collateral is already tracked", coin));
+        }
+        self.managers.insert(coin, manager);
+        Ok(())
+    }
+
+    /// Track capital in a settle coin, returning the manager it replaces
+    pub fn replace_collateral(&mut self, manager: CapitalManager) -> Option<CapitalManager> {
+        self.managers.insert(manager.get_settle_coin().to_string(), manager)
+    }
+
+    /// Stop tracking a settle coin
+    pub fn remove_collateral(&mut self, coin: &str) -> Result<CapitalManager> {
+        if self.managers.len() == 1 {
+            return Err(anyhow::anyhow!("Cannot remove the only collateral"));
+        }
+        self.managers.remove(&coin.to_uppercase())
+            .ok_or_else(|| anyhow::anyhow!("No {} collateral", coin))
+    }
+
+    /// Get the manager of a settle coin
+    pub fn get_manager(&self, coin: &str) -> Option<&CapitalManager> {
+        self.managers.get(&coin.to_uppercase())
+    }
+
+    /// Get the manager of a settle coin for changes
+    pub fn get_manager_mut(&mut self, coin: &str) -> Option<&mut CapitalManager> {
+        self.managers.get_mut(&coin.to_uppercase())
+    }
+
+    /// Get the manager funding trades on `symbol`
+    pub fn get_manager_for_symbol(&self, symbol: &str) -> Option<&CapitalManager> {
+        self.get_manager(&settle_coin_for_symbol(symbol))
+    }
+
+    /// Get the manager funding trades on `symbol` for changes
+    pub fn get_manager_for_symbol_mut(&mut self, symbol: &str) -> Option<&mut CapitalManager> {
+        self.get_manager_mut(&settle_coin_for_symbol(symbol))
+    }
+
+    /// Get all managers
+    pub fn get_managers_mut(&mut self) -> impl Iterator<Item = &mut CapitalManager> {
+        self.managers.values_mut()
+    }
+
+    /// Set the USDT value of one coin
+    pub fn set_rate(&mut self, coin: &str, rate: f64) {
+        if rate > 0.0 {
+            self.rates.insert(coin.to_uppercase(), rate);
+        }
+    }
+
+    /// Get the USDT value of one coin
+    pub fn get_rate(&self, coin: &str) -> Option<f64> {
+        self.rates.get(&coin.to_uppercase()).copied()
+    }
+
+    /// Time the rates were last fetched
+    pub fn get_rates_updated(&self) -> Option<DateTime<Utc>> {
+        self.rates_updated
+    }
+
+    /// Convert an amount of `coin` to USDT
+    pub fn to_usdt(&self, coin: &str, amount: f64) -> Option<f64> {
+        self.get_rate(coin).map(|rate| amount * rate)
+    }
+
+    /// Fetch the USDT rate of every tracked coin from the exchange
+    pub async fn refresh_rates(&mut self, exchange: &BybitAdapter) -> Result<()> {
+        let coins: Vec<String> = self.managers.keys()
+            .filter(|coin| coin.as_str() != DEFAULT_SETTLE_COIN)
+            .cloned()
+            .collect();
+
+        let mut failed = Vec::new();
+        for coin in coins {
+            // Stablecoins only trade spot against USDT; other coins have a linear perpetual
+            let symbol = format!("{}USDT", coin);
+            let category = if coin == "USDC" { "spot" } else { "linear" };
+            match exchange.get_category_ticker(&symbol, category).await {
+                Ok(tickers) => {
+                    let price = tickers.first().map(|t| t.last_price).unwrap_or(0.0);
+                    debug!("{} rate: {} USDT", coin, price);
+                    self.set_rate(&coin, price);
+                }
+                Err(e) => {
+                    warn!("Failed to fetch the {} rate: {}", coin, e);
+                    failed.push(coin);
+                }
+            }
+        }
+        self.rates_updated = Some(Utc::now());
+
+        if failed.is_empty() {
+            Ok(())
+        } else {
+            Err(anyhow::anyhow!("No rate for {}", failed.join(", ")))
+        }
+    }
+
+    /// Report capital per coin and in aggregate (USDT)
+    pub fn report(&self) -> CollateralReport {
+        let collaterals: Vec<CollateralValue> = self.managers.iter()
+            .map(|(coin, manager)| {
+                let rate = self.get_rate(coin);
+                CollateralValue {
+                    coin: coin.clone(),
+                    rate,
+                    total_capital_usdt: rate.map(|rate| manager.get_total_capital() * rate),
+                    allocation: manager.report(),
+                }
+            })
+            .collect();
+
+        CollateralReport {
+            timestamp: Utc::now(),
+            rates_updated: self.rates_updated,
+            total_capital_usdt: collaterals.iter().filter_map(|c| c.total_capital_usdt).sum(),
+            unpriced: collaterals.iter().filter(|c| c.rate.is_none()).map(|c| c.coin.clone()).collect(),
+            collaterals,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_aggregate_across_settle_coins() {
+        assert_eq!(settle_coin_for_symbol("BTCUSDT"), "USDT");
+        assert_eq!(settle_coin_for_symbol("ETHPERP"), "USDC");
+        assert_eq!(settle_coin_for_symbol("BTCUSD"), "BTC");
+        assert!((to_settle_units("BTCUSD", 500.0, 50000.0) - 0.01).abs() < 1e-12);
+
+        let mut collateral = CollateralManager::new(CapitalManager::new(100.0));
+        collateral.add_collateral(CapitalManager::for_settle_coin("usdc", 50.0)).unwrap();
+        collateral.add_collateral(CapitalManager::for_settle_coin("BTC", 0.01)).unwrap();
+        assert!(collateral.add_collateral(CapitalManager::for_settle_coin("USDC", 1.0)).is_err());
+        collateral.set_rate("USDC", 0.999);
+
+        let report = collateral.report();
+        assert!((report.total_capital_usdt - 149.95).abs() < 1e-9);
+        assert_eq!(report.unpriced, vec!["BTC".to_string()]);
+
+        collateral.set_rate("BTC", 60000.0);
+        assert!((collateral.report().total_capital_usdt - 749.95).abs() < 1e-9);
+        assert_eq!(collateral.get_manager_for_symbol("BTCUSD").unwrap().get_total_capital(), 0.01);
+    }
+}
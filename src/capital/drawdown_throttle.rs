@@ -0,0 +1,124 @@
+//! Drawdown Throttle
+//!
+//! This module watches the equity curve and cuts position sizing once equity has
+//! fallen a configured percentage from its peak. Full sizing only returns after
+//! equity recovers to within a smaller drawdown, so the throttle does not flip on
+//! every trade around the trigger level.
+
+use serde::{Deserialize, Serialize};
+use tracing::{info, warn};
+
+/// Drawdown throttle thresholds
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DrawdownThrottleConfig {
+    /// Drawdown from peak (%) at which sizing is cut
+    pub throttle_drawdown_pct: f64,
+
+    /// Drawdown from peak (%) at or below which full sizing returns
+    pub recovery_drawdown_pct: f64,
+
+    /// Sizing multiplier while throttled
+    pub throttled_multiplier: f64,
+}
+
+impl Default for DrawdownThrottleConfig {
+    fn default() -> Self {
+        Self {
+            throttle_drawdown_pct: 10.0,
+            recovery_drawdown_pct: 5.0,
+            throttled_multiplier: 0.5,
+        }
+    }
+}
+
+/// Equity-curve based sizing throttle
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DrawdownThrottle {
+    /// Thresholds
+    config: DrawdownThrottleConfig,
+
+    /// Highest equity seen
+    peak_equity: f64,
+
+    /// Latest equity
+    equity: f64,
+
+    /// Whether sizing is currently cut
+    throttled: bool,
+}
+
+impl DrawdownThrottle {
+    /// Create a new throttle starting at `equity`
+    pub fn new(config: DrawdownThrottleConfig, equity: f64) -> Self {
+        Self {
+            config,
+            peak_equity: equity,
+            equity,
+            throttled: false,
+        }
+    }
+
+    /// Record the latest equity; returns whether the throttle switched
+    pub fn update(&mut self, equity: f64) -> bool {
+        self.equity = equity;
+        self.peak_equity = self.peak_equity.max(equity);
+
+        let drawdown = self.get_drawdown_pct();
+        let throttled = if self.throttled {
+            drawdown > self.config.recovery_drawdown_pct
+        } else {
+            drawdown >= self.config.throttle_drawdown_pct
+        };
+        if throttled == self.throttled {
+            return false;
+        }
+
+        self.throttled = throttled;
+        if throttled {
+            warn!("Drawdown {:.2}% from peak {:.2}: sizing cut to {:.0}%",
+                  drawdown, self.peak_equity, self.config.throttled_multiplier * 100.0);
+        } else {
+            info!("Equity recovered to {:.2}% drawdown: full sizing restored", drawdown);
+        }
+        true
+    }
+
+    /// Drawdown from peak in %
+    pub fn get_drawdown_pct(&self) -> f64 {
+        if self.peak_equity > 0.0 {
+            ((self.peak_equity - self.equity) / self.peak_equity * 100.0).max(0.0)
+        } else {
+            0.0
+        }
+    }
+
+    /// Whether sizing is currently cut
+    pub fn is_throttled(&self) -> bool {
+        self.throttled
+    }
+
+    /// Multiplier to apply to every position size
+    pub fn get_multiplier(&self) -> f64 {
+        if self.throttled { self.config.throttled_multiplier } else { 1.0 }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_throttle_and_recovery() {
+        let mut throttle = DrawdownThrottle::new(DrawdownThrottleConfig::default(), 100.0);
+        assert!(!throttle.update(95.0));
+        assert!(throttle.update(89.0));
+        assert_eq!(throttle.get_multiplier(), 0.5);
+
+        // Still 6% below the peak
+        assert!(!throttle.update(94.0));
+        assert!(throttle.is_throttled());
+
+        assert!(throttle.update(96.0));
+        assert_eq!(throttle.get_multiplier(), 1.0);
+    }
+}
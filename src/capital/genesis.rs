@@ -0,0 +1,124 @@
+//! Capital Genesis
+//!
+//! This module holds the starting-capital settings that used to be compile-time
+//! constants (the 12 USDT account, its settle coin, reserve and position cap)
+//! and checks them against the wallet before any capital is allocated. Trading
+//! with more capital than the wallet holds is refused; a wallet holding more than
+//! configured is allowed, with only the configured amount allocated.
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use tracing::{info, warn};
+
+use crate::capital::manager::{CapitalManager, DEFAULT_SETTLE_COIN};
+use crate::capital::precise_capital_tracker::PreciseCapitalTracker;
+
+/// Starting-capital settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CapitalGenesisConfig {
+    /// Coin the starting capital is held in
+    pub settle_coin: String,
+
+    /// Share of capital kept out of every allocation (0.0-1.0)
+    pub reserve_percentage: f64,
+
+    /// Largest position notional; the capital tier's position size when unset
+    pub max_position_notional: Option<f64>,
+
+    /// Shortfall of the wallet against the configured capital (%) tolerated at startup
+    pub balance_tolerance_pct: f64,
+
+    /// Taker fee rate used to reserve the costs of each position
+    pub taker_fee_rate: f64,
+}
+
+impl Default for CapitalGenesisConfig {
+    fn default() -> Self {
+        Self {
+            settle_coin: DEFAULT_SETTLE_COIN.to_string(),
+            reserve_percentage: 0.1,
+            max_position_notional: None,
+            balance_tolerance_pct: 1.0,
+            taker_fee_rate: 0.00055,
+        }
+    }
+}
+
+impl CapitalGenesisConfig {
+    /// Check the settings for a starting capital of `initial_capital`
+    pub fn validate(&self, initial_capital: f64) -> Result<()> {
+        if !initial_capital.is_finite() || initial_capital <= 0.0 {
+            return Err(anyhow::anyhow!("Initial capital must be positive, got {}", initial_capital));
+        }
+        if self.settle_coin.trim().is_empty() {
+            return Err(anyhow::anyhow!("Settle coin must not be empty"));
+        }
+        if !(0.0..1.0).contains(&self.reserve_percentage) {
+            return Err(anyhow::anyhow!("Reserve percentage must be in [0, 1), got {}", self.reserve_percentage));
+        }
+        if let Some(notional) = self.max_position_notional.filter(|n| *n <= 0.0) {
+            return Err(anyhow::anyhow!("Max position notional must be positive, got {}", notional));
+        }
+        if !(0.0..0.01).contains(&self.taker_fee_rate) {
+            return Err(anyhow::anyhow!("Taker fee rate must be in [0, 0.01), got {}", self.taker_fee_rate));
+        }
+        if self.balance_tolerance_pct < 0.0 {
+            return Err(anyhow::anyhow!("Balance tolerance must not be negative, got {}", self.balance_tolerance_pct));
+        }
+        Ok(())
+    }
+
+    /// Check the configured capital against the wallet equity in the settle coin
+    pub fn check_wallet_balance(&self, initial_capital: f64, wallet_equity: f64) -> Result<()> {
+        let minimum = initial_capital * (1.0 - self.balance_tolerance_pct / 100.0);
+        if wallet_equity < minimum {
+            return Err(anyhow::anyhow!(
+                "Wallet holds {:.6} {}, configured capital is {:.6} {}",
+                wallet_equity, self.settle_coin, initial_capital, self.settle_coin
+            ));
+        }
+
+        if wallet_equity > initial_capital {
+            warn!("Wallet holds {:.6} {}; only the configured {:.6} will be allocated",
+                  wallet_equity, self.settle_coin, initial_capital);
+        } else {
+            info!("Wallet balance {:.6} {} covers the configured capital", wallet_equity, self.settle_coin);
+        }
+        Ok(())
+    }
+
+    /// Capital manager for `initial_capital`
+    pub fn capital_manager(&self, initial_capital: f64) -> CapitalManager {
+        let mut manager = CapitalManager::for_settle_coin(&self.settle_coin, initial_capital);
+        manager.set_reserve_percentage(self.reserve_percentage);
+        manager
+    }
+
+    /// Precise capital tracker for `initial_capital`
+    pub fn capital_tracker(&self, initial_capital: f64) -> PreciseCapitalTracker {
+        let mut tracker = PreciseCapitalTracker::new(initial_capital);
+        tracker.set_reserve_percentage(self.reserve_percentage);
+        tracker
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wallet_balance_check() {
+        let config = CapitalGenesisConfig::default();
+        assert!(config.validate(50.0).is_ok());
+        assert!(config.validate(0.0).is_err());
+
+        assert!(config.check_wallet_balance(50.0, 49.9).is_ok());
+        assert!(config.check_wallet_balance(50.0, 80.0).is_ok());
+        assert!(config.check_wallet_balance(50.0, 40.0).is_err());
+
+        let tracker = config.capital_tracker(50.0);
+        assert_eq!(tracker.get_initial_capital(), 50.0);
+        assert!((tracker.get_reserve_amount() - 5.0).abs() < 1e-12);
+    }
+}
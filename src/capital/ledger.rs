@@ -0,0 +1,282 @@
+//! Capital Ledger
+//!
+//! This module keeps an append-only ledger of every change to the capital
+//! tranches: allocations, reservations, releases with the fees and funding they
+//! include, rebalances and reserve changes. Entries are never rewritten, so the
+//! `CapitalAllocationReport` after a restart is rebuilt exactly by replaying them,
+//! and the same entries can be audited against exchange statements. Entries go to
+//! a JSON-lines file by default, or to SQLite with the `sqlite` feature.
+
+use std::collections::BTreeMap;
+use std::fs::{self, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tracing::info;
+
+use crate::capital::manager::{default_settle_coin, CapitalManager};
+
+/// Change to the capital tranches
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum LedgerEvent {
+    /// Manager created with its starting capital
+    Opened {
+        #[serde(default = "default_settle_coin")]
+        settle_coin: String,
+        initial_capital: f64,
+        reserve_percentage: f64,
+    },
+
+    /// Share of capital kept out of every tranche changed
+    ReserveChanged { reserve_percentage: f64 },
+
+    /// Tranche added
+    TrancheAdded { tranche: String, weight: f64 },
+
+    /// Tranche removed
+    TrancheRemoved { tranche: String },
+
+    /// Capital reserved for a position, plus its estimated fees and slippage
+    Reserved {
+        tranche: String,
+        amount: f64,
+        #[serde(default)]
+        costs: f64,
+    },
+
+    /// Reservation handed back because its position never opened
+    Cancelled {
+        tranche: String,
+        amount: f64,
+        #[serde(default)]
+        costs: f64,
+    },
+
+    /// Fee paid by a position; already included in the release that follows
+    Fee { tranche: String, amount: f64 },
+
+    /// Funding paid by a position; already included in the release that follows
+    Funding { tranche: String, amount: f64 },
+
+    /// Position closed: funding and cost reserve released and net P&L realized
+    Released {
+        tranche: String,
+        amount: f64,
+        #[serde(default)]
+        costs: f64,
+        pnl: f64,
+    },
+
+    /// Part of a position closed: its share released and net P&L realized; the position stays open
+    Reduced {
+        tranche: String,
+        amount: f64,
+        #[serde(default)]
+        costs: f64,
+        pnl: f64,
+    },
+
+    /// Rebalance result: weight and contributed capital by tranche
+    Rebalanced { weights: BTreeMap<String, f64>, contributed: BTreeMap<String, f64> },
+}
+
+/// One ledger line
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LedgerEntry {
+    /// Position in the ledger, starting at 1
+    pub sequence: u64,
+
+    /// Time the entry was written
+    pub timestamp: DateTime<Utc>,
+
+    /// What changed
+    pub event: LedgerEvent,
+}
+
+/// Durable append-only storage for ledger entries
+pub trait LedgerStore: Send + std::fmt::Debug {
+    /// Append an entry
+    fn append(&mut self, entry: &LedgerEntry) -> Result<()>;
+
+    /// Load all entries in order
+    fn load(&self) -> Result<Vec<LedgerEntry>>;
+}
+
+/// JSON-lines file store; every entry is synced before `append` returns
+#[derive(Debug)]
+pub struct JsonLinesLedgerStore {
+    /// Ledger file
+    path: PathBuf,
+}
+
+impl JsonLinesLedgerStore {
+    /// Create a store at `path`
+    pub fn new(path: impl AsRef<Path>) -> Self {
+        Self { path: path.as_ref().to_path_buf() }
+    }
+}
+
+impl LedgerStore for JsonLinesLedgerStore {
+    fn append(&mut self, entry: &LedgerEntry) -> Result<()> {
+        if let Some(parent) = self.path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            fs::create_dir_all(parent)?;
+        }
+
+        let mut file = OpenOptions::new().create(true).append(true).open(&self.path)?;
+        let mut line = serde_json::to_vec(entry)?;
+        line.push(b'\n');
+        file.write_all(&line)?;
+        file.sync_data()?;
+        Ok(())
+    }
+
+    fn load(&self) -> Result<Vec<LedgerEntry>> {
+        if !self.path.exists() {
+            return Ok(Vec::new());
+        }
+
+        let mut entries = Vec::new();
+        for line in BufReader::new(fs::File::open(&self.path)?).lines() {
+            let line = line?;
+            if !line.trim().is_empty() {
+                entries.push(serde_json::from_str(&line)?);
+            }
+        }
+        Ok(entries)
+    }
+}
+
+/// SQLite store; updates and deletes are refused by triggers
+#[cfg(feature = "sqlite")]
+#[derive(Debug)]
+pub struct SqliteLedgerStore {
+    /// Connection
+    connection: rusqlite::Connection,
+}
+
+#[cfg(feature = "sqlite")]
+impl SqliteLedgerStore {
+    /// Open (or create) a store at `path`
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let connection = rusqlite::Connection::open(path)?;
+        connection.execute_batch(
+            "PRAGMA journal_mode = WAL;
+             CREATE TABLE IF NOT EXISTS capital_ledger (
+                sequence INTEGER PRIMARY KEY,
+                timestamp TEXT NOT NULL,
+                entry TEXT NOT NULL
+            );
+            CREATE TRIGGER IF NOT EXISTS capital_ledger_no_update BEFORE UPDATE ON capital_ledger
+            BEGIN SELECT RAISE(ABORT, 'capital ledger is append-only'); END;
+            CREATE TRIGGER IF NOT EXISTS capital_ledger_no_delete BEFORE DELETE ON capital_ledger
+            BEGIN SELECT RAISE(ABORT, 'capital ledger is append-only'); END;",
+        )?;
+        Ok(Self { connection })
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl LedgerStore for SqliteLedgerStore {
+    fn append(&mut self, entry: &LedgerEntry) -> Result<()> {
+        self.connection.execute(
+            "INSERT INTO capital_ledger (sequence, timestamp, entry) VALUES (?1, ?2, ?3)",
+            rusqlite::params![entry.sequence as i64, entry.timestamp.to_rfc3339(), serde_json::to_string(entry)?],
+        )?;
+        Ok(())
+    }
+
+    fn load(&self) -> Result<Vec<LedgerEntry>> {
+        let mut statement = self.connection.prepare("SELECT entry FROM capital_ledger ORDER BY sequence")?;
+        let rows = statement.query_map([], |row| row.get::<_, String>(0))?;
+        let mut entries = Vec::new();
+        for json in rows {
+            entries.push(serde_json::from_str(&json?)?);
+        }
+        Ok(entries)
+    }
+}
+
+/// Writes ledger entries with increasing sequence numbers
+#[derive(Debug)]
+pub struct CapitalLedger {
+    /// Storage backend
+    store: Box<dyn LedgerStore>,
+
+    /// Sequence number of the next entry
+    next_sequence: u64,
+}
+
+impl CapitalLedger {
+    /// Open a ledger on `store`, continuing after its last entry
+    pub fn open(store: Box<dyn LedgerStore>) -> Result<Self> {
+        let next_sequence = store.load()?.last().map(|e| e.sequence + 1).unwrap_or(1);
+        Ok(Self { store, next_sequence })
+    }
+
+    /// Append an event
+    pub fn record(&mut self, event: LedgerEvent) -> Result<LedgerEntry> {
+        let entry = LedgerEntry {
+            sequence: self.next_sequence,
+            timestamp: Utc::now(),
+            event,
+        };
+        self.store.append(&entry)?;
+        self.next_sequence += 1;
+        Ok(entry)
+    }
+
+    /// All entries in order
+    pub fn get_entries(&self) -> Result<Vec<LedgerEntry>> {
+        self.store.load()
+    }
+
+    /// Rebuild the capital manager the entries describe
+    pub fn replay(entries: &[LedgerEntry]) -> Result<CapitalManager> {
+        let mut entries = entries.iter();
+        let mut manager = match entries.next().map(|e| &e.event) {
+            Some(LedgerEvent::Opened { settle_coin, initial_capital, reserve_percentage }) => {
+                let mut manager = CapitalManager::for_settle_coin(settle_coin, *initial_capital);
+                manager.set_reserve_percentage(*reserve_percentage);
+                manager
+            }
+            Some(other) => return Err(anyhow::anyhow!("Capital ledger must start with Opened, found {:?}", other)),
+            None => return Err(anyhow::anyhow!("Capital ledger is empty")),
+        };
+
+        for entry in entries {
+            manager.apply(&entry.event)
+                .map_err(|e| anyhow::anyhow!("Replaying ledger entry {}: {}", entry.sequence, e))?;
+        }
+        info!("Replayed capital ledger: {:.4} {} total capital in {} tranches",
+              manager.get_total_capital(), manager.get_settle_coin(), manager.get_tranches().len());
+        Ok(manager)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_report_rebuilt_from_ledger() {
+        let path = std::env::temp_dir().join(format!("omni_capital_ledger_{}.jsonl", std::process::id()));
+        let _ = fs::remove_file(&path);
+
+        let mut manager = CapitalManager::with_ledger(100.0, Box::new(JsonLinesLedgerStore::new(&path))).unwrap();
+        manager.add_tranche("momentum", 0.5).unwrap();
+        manager.reserve_with_costs("momentum", 10.0, 0.25).unwrap();
+        manager.release_with_costs("momentum", 10.0, 0.25, 3.0, 0.2, 0.1).unwrap();
+        manager.rebalance(0);
+        manager.reserve("momentum", 5.0).unwrap();
+        let before = manager.report();
+
+        let restored = CapitalManager::with_ledger(100.0, Box::new(JsonLinesLedgerStore::new(&path))).unwrap();
+        let after = restored.report();
+        assert_eq!(before.tranches, after.tranches);
+        assert!((after.total_capital - 102.7).abs() < 1e-9);
+        let _ = fs::remove_file(&path);
+    }
+}
@@ -0,0 +1,701 @@
+//! Capital Manager
+//!
+//! This module splits account capital into tranches owned by named strategies or
+//! agents. Each tranche funds only its own positions and keeps its own P&L, so one
+//! strategy cannot starve another of capital. Tranches are rebalanced periodically:
+//! target weights are tilted towards tranches that made money since the last
+//! rebalance, clamped to configured bounds, and only free capital is moved. With a
+//! ledger attached, every change is appended to it so the state survives restarts.
+//! Amounts are in the manager's settle coin; USDT unless created for another coin.
+
+use std::collections::BTreeMap;
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use tracing::{debug, error, info};
+
+use crate::capital::ledger::{CapitalLedger, LedgerEvent, LedgerStore};
+
+/// Tranche funding strategies without a tranche of their own
+pub const DEFAULT_TRANCHE: &str = "default";
+
+/// Settle coin of capital managers created without one
+pub const DEFAULT_SETTLE_COIN: &str = "USDT";
+
+pub(crate) fn default_settle_coin() -> String {
+    DEFAULT_SETTLE_COIN.to_string()
+}
+
+/// Capital owned by one strategy or agent
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CapitalTranche {
+    /// Strategy or agent name
+    pub name: String,
+
+    /// Target share of allocatable capital (0.0-1.0)
+    pub weight: f64,
+
+    /// Capital moved into the tranche, net of rebalancing
+    pub contributed: f64,
+
+    /// Capital currently funding open positions, including their cost reserve
+    pub used: f64,
+
+    /// Estimated fees and slippage held for open positions (part of `used`)
+    #[serde(default)]
+    pub cost_reserve: f64,
+
+    /// Estimated costs of closed positions
+    #[serde(default)]
+    pub estimated_costs: f64,
+
+    /// Actual fees and funding of closed positions
+    #[serde(default)]
+    pub actual_costs: f64,
+
+    /// Realized P&L since the tranche was created
+    pub realized_pnl: f64,
+
+    /// Realized P&L since the last rebalance
+    pub period_pnl: f64,
+
+    /// Closed trades
+    pub trades: usize,
+
+    /// Closed trades with positive P&L
+    pub wins: usize,
+}
+
+impl CapitalTranche {
+    fn new(name: &str, weight: f64) -> Self {
+        Self {
+            name: name.to_string(),
+            weight,
+            contributed: 0.0,
+            used: 0.0,
+            cost_reserve: 0.0,
+            estimated_costs: 0.0,
+            actual_costs: 0.0,
+            realized_pnl: 0.0,
+            period_pnl: 0.0,
+            trades: 0,
+            wins: 0,
+        }
+    }
+
+    /// Capital owned by the tranche
+    pub fn equity(&self) -> f64 {
+        self.contributed + self.realized_pnl
+    }
+
+    /// Capital free to fund new positions
+    pub fn available(&self) -> f64 {
+        (self.equity() - self.used).max(0.0)
+    }
+
+    /// Realized P&L in % of contributed capital
+    pub fn return_percent(&self) -> f64 {
+        if self.contributed > 0.0 { self.realized_pnl / self.contributed * 100.0 } else { 0.0 }
+    }
+}
+
+/// Rebalancing rules
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RebalanceConfig {
+    /// Minimum time between rebalances in seconds
+    pub interval_secs: u64,
+
+    /// How strongly period returns tilt the weights (1.0: +10% return → weight × 1.1)
+    pub performance_tilt: f64,
+
+    /// Lowest weight a tranche can be rebalanced to
+    pub min_weight: f64,
+
+    /// Highest weight a tranche can be rebalanced to
+    pub max_weight: f64,
+}
+
+impl Default for RebalanceConfig {
+    fn default() -> Self {
+        Self {
+            interval_secs: 24 * 60 * 60,
+            performance_tilt: 1.0,
+            min_weight: 0.05,
+            max_weight: 0.6,
+        }
+    }
+}
+
+/// Capital moved into (positive) or out of (negative) a tranche by a rebalance
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RebalanceTransfer {
+    /// Tranche name
+    pub tranche: String,
+
+    /// Amount moved
+    pub amount: f64,
+
+    /// Weight after the rebalance
+    pub weight: f64,
+}
+
+/// Allocation and P&L of one tranche
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TrancheReport {
+    /// Tranche name
+    pub name: String,
+
+    /// Target weight
+    pub weight: f64,
+
+    /// Capital owned
+    pub equity: f64,
+
+    /// Capital funding open positions
+    pub used: f64,
+
+    /// Estimated costs held for open positions
+    #[serde(default)]
+    pub cost_reserve: f64,
+
+    /// Estimated costs of closed positions
+    #[serde(default)]
+    pub estimated_costs: f64,
+
+    /// Actual costs of closed positions
+    #[serde(default)]
+    pub actual_costs: f64,
+
+    /// Capital free for new positions
+    pub available: f64,
+
+    /// Realized P&L
+    pub realized_pnl: f64,
+
+    /// Realized P&L in % of contributed capital
+    pub return_percent: f64,
+
+    /// Closed trades
+    pub trades: usize,
+
+    /// Share of closed trades that won
+    pub win_rate: f64,
+}
+
+/// Allocation of the account across tranches
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CapitalAllocationReport {
+    /// Report time (unix seconds)
+    pub timestamp: u64,
+
+    /// Coin all amounts are in
+    #[serde(default = "default_settle_coin")]
+    pub settle_coin: String,
+
+    /// Account capital
+    pub total_capital: f64,
+
+    /// Capital held back from every tranche
+    pub reserve: f64,
+
+    /// Capital not owned by any tranche (excluding the reserve)
+    pub unallocated: f64,
+
+    /// Tranches by name
+    pub tranches: Vec<TrancheReport>,
+}
+
+/// Allocates account capital to strategy tranches
+#[derive(Debug)]
+pub struct CapitalManager {
+    /// Coin the capital is held in
+    settle_coin: String,
+
+    /// Account capital
+    total_capital: f64,
+
+    /// Share of capital kept out of every tranche
+    reserve_percentage: f64,
+
+    /// Tranches by name
+    tranches: BTreeMap<String, CapitalTranche>,
+
+    /// Rebalancing rules
+    rebalance_config: RebalanceConfig,
+
+    /// Time of the last rebalance (unix seconds)
+    last_rebalance: Option<u64>,
+
+    /// Audit ledger every change is recorded in
+    ledger: Option<CapitalLedger>,
+}
+
+impl CapitalManager {
+    /// Create a USDT manager with all allocatable capital in the default tranche
+    pub fn new(total_capital: f64) -> Self {
+        Self::for_settle_coin(DEFAULT_SETTLE_COIN, total_capital)
+    }
+
+    /// Create a manager for capital held in `settle_coin`
+    pub fn for_settle_coin(settle_coin: &str, total_capital: f64) -> Self {
+        let mut manager = Self {
+            settle_coin: settle_coin.to_uppercase(),
+            total_capital,
+            reserve_percentage: 0.1,
+            tranches: BTreeMap::new(),
+            rebalance_config: RebalanceConfig::default(),
+            last_rebalance: None,
+            ledger: None,
+        };
+        manager.tranches.insert(DEFAULT_TRANCHE.to_string(), CapitalTranche::new(DEFAULT_TRANCHE, 1.0));
+        manager.redistribute_all_capital();
+        manager
+    }
+
+    /// Create a USDT manager recorded in a ledger, replaying the ledger if it has entries
+    pub fn with_ledger(initial_capital: f64, store: Box<dyn LedgerStore>) -> Result<Self> {
+        Self::for_settle_coin_with_ledger(DEFAULT_SETTLE_COIN, initial_capital, store)
+    }
+
+    /// Create a manager for `settle_coin` recorded in a ledger, replaying the ledger if it has entries
+    pub fn for_settle_coin_with_ledger(settle_coin: &str, initial_capital: f64, store: Box<dyn LedgerStore>) -> Result<Self> {
+        let mut ledger = CapitalLedger::open(store)?;
+        let entries = ledger.get_entries()?;
+        let mut manager = if entries.is_empty() {
+            let manager = Self::for_settle_coin(settle_coin, initial_capital);
+            ledger.record(LedgerEvent::Opened {
+                settle_coin: manager.settle_coin.clone(),
+                initial_capital,
+                reserve_percentage: manager.reserve_percentage,
+            })?;
+            manager
+        } else {
+            CapitalLedger::replay(&entries)?
+        };
+        if manager.settle_coin != settle_coin.to_uppercase() {
+            return Err(anyhow::anyhow!("Capital ledger holds {} capital, not {}", manager.settle_coin, settle_coin));
+        }
+        manager.ledger = Some(ledger);
+        Ok(manager)
+    }
+
+    fn record(&mut self, event: LedgerEvent) -> Result<()> {
+        if let Some(ledger) = self.ledger.as_mut() {
+            ledger.record(event)?;
+        }
+        Ok(())
+    }
+
+    /// Apply a recorded event (ledger replay)
+    pub(crate) fn apply(&mut self, event: &LedgerEvent) -> Result<()> {
+        match event {
+            LedgerEvent::Opened { .. } => Err(anyhow::anyhow!("Ledger opened twice")),
+            LedgerEvent::ReserveChanged { reserve_percentage } => {
+                self.set_reserve_percentage(*reserve_percentage);
+                Ok(())
+            }
+            LedgerEvent::TrancheAdded { tranche, weight } => self.add_tranche(tranche, *weight),
+            LedgerEvent::TrancheRemoved { tranche } => self.remove_tranche(tranche).map(|_| ()),
+            LedgerEvent::Reserved { tranche, amount, costs } => self.reserve_with_costs(tranche, *amount, *costs),
+            LedgerEvent::Cancelled { tranche, amount, costs } => self.cancel_reservation(tranche, *amount, *costs),
+            // Itemized costs; the release that follows carries them in its net P&L
+            LedgerEvent::Fee { tranche, amount } | LedgerEvent::Funding { tranche, amount } => {
+                if let Some(tranche) = self.tranches.get_mut(tranche) {
+                    tranche.actual_costs += amount;
+                }
+                Ok(())
+            }
+            LedgerEvent::Released { tranche, amount, costs, pnl } => self.release_position(tranche, *amount, *costs, *pnl, true),
+            LedgerEvent::Reduced { tranche, amount, costs, pnl } => self.release_position(tranche, *amount, *costs, *pnl, false),
+            LedgerEvent::Rebalanced { weights, contributed } => {
+                for tranche in self.tranches.values_mut() {
+                    tranche.weight = weights.get(&tranche.name).copied().unwrap_or(tranche.weight);
+                    tranche.contributed = contributed.get(&tranche.name).copied().unwrap_or(tranche.contributed);
+                    tranche.period_pnl = 0.0;
+                }
+                Ok(())
+            }
+        }
+    }
+
+    /// Set the rebalancing rules
+    pub fn set_rebalance_config(&mut self, config: RebalanceConfig) {
+        self.rebalance_config = config;
+    }
+
+    /// Set the share of capital kept out of every tranche
+    pub fn set_reserve_percentage(&mut self, percentage: f64) {
+        self.reserve_percentage = percentage.clamp(0.0, 1.0);
+        self.redistribute_all_capital();
+        if let Err(e) = self.record(LedgerEvent::ReserveChanged { reserve_percentage: self.reserve_percentage }) {
+            error!("Failed to record reserve change in the capital ledger: {}", e);
+        }
+    }
+
+    /// Add a tranche with a target weight, scaling the other weights down to make room
+    pub fn add_tranche(&mut self, name: &str, weight: f64) -> Result<()> {
+        if self.tranches.contains_key(name) {
+            return Err(anyhow::anyhow!("Tranche {} already exists", name));
+        }
+        if !(weight > 0.0 && weight <= 1.0) {
+            return Err(anyhow::anyhow!("Tranche weight must be in (0, 1], got {}", weight));
+        }
+
+        self.record(LedgerEvent::TrancheAdded { tranche: name.to_string(), weight })?;
+        for tranche in self.tranches.values_mut() {
+            tranche.weight *= 1.0 - weight;
+        }
+        self.tranches.insert(name.to_string(), CapitalTranche::new(name, weight));
+        self.normalize_weights();
+        self.redistribute_all_capital();
+        info!("Added capital tranche {} with weight {:.2}", name, weight);
+        Ok(())
+    }
+
+    /// Remove a tranche without open positions; its capital goes back to the others
+    pub fn remove_tranche(&mut self, name: &str) -> Result<CapitalTranche> {
+        if name == DEFAULT_TRANCHE {
+            return Err(anyhow::anyhow!("The default tranche cannot be removed"));
+        }
+        match self.tranches.get(name) {
+            Some(tranche) if tranche.used > 0.0 => {
+                return Err(anyhow::anyhow!("Tranche {} still funds {} of open positions", name, tranche.used));
+            }
+            Some(_) => {}
+            None => return Err(anyhow::anyhow!("No tranche {}", name)),
+        }
+
+        self.record(LedgerEvent::TrancheRemoved { tranche: name.to_string() })?;
+        let tranche = self.tranches.remove(name).unwrap();
+        self.normalize_weights();
+        self.redistribute_all_capital();
+        Ok(tranche)
+    }
+
+    /// Tranche that funds `strategy`
+    pub fn tranche_for<'a>(&'a self, strategy: &'a str) -> &'a str {
+        if self.tranches.contains_key(strategy) { strategy } else { DEFAULT_TRANCHE }
+    }
+
+    /// Fund a position of `amount` from a tranche
+    pub fn reserve(&mut self, tranche: &str, amount: f64) -> Result<()> {
+        self.reserve_with_costs(tranche, amount, 0.0)
+    }
+
+    /// Fund a position of `amount` plus its estimated fees and slippage from a tranche
+    pub fn reserve_with_costs(&mut self, tranche: &str, amount: f64, costs: f64) -> Result<()> {
+        let available = self.tranches.get(tranche)
+            .ok_or_else(|| anyhow::anyhow!("No tranche {}", tranche))?
+            .available();
+        if amount + costs > available {
+            return Err(anyhow::anyhow!(
+                "Tranche {} has {:.4} available, {:.4} requested ({:.4} for costs)",
+                tranche, available, amount + costs, costs
+            ));
+        }
+        self.record(LedgerEvent::Reserved { tranche: tranche.to_string(), amount, costs })?;
+
+        let tranche = self.tranches.get_mut(tranche).unwrap();
+        tranche.used += amount + costs;
+        tranche.cost_reserve += costs;
+        debug!("Tranche {} reserved {:.4} + {:.4} costs ({:.4} in use)", tranche.name, amount, costs, tranche.used);
+        Ok(())
+    }
+
+    /// Hand back a reservation whose position never opened; nothing is booked as a trade
+    pub fn cancel_reservation(&mut self, tranche: &str, amount: f64, costs: f64) -> Result<()> {
+        if !self.tranches.contains_key(tranche) {
+            return Err(anyhow::anyhow!("No tranche {}", tranche));
+        }
+        self.record(LedgerEvent::Cancelled { tranche: tranche.to_string(), amount, costs })?;
+
+        let tranche = self.tranches.get_mut(tranche).unwrap();
+        tranche.used = (tranche.used - amount - costs).max(0.0);
+        tranche.cost_reserve = (tranche.cost_reserve - costs).max(0.0);
+        debug!("Tranche {} cancelled {:.4} + {:.4} costs ({:.4} in use)", tranche.name, amount, costs, tranche.used);
+        Ok(())
+    }
+
+    /// Release the funding of a closed position and book its P&L to the tranche
+    pub fn release(&mut self, tranche: &str, amount: f64, pnl: f64) -> Result<()> {
+        self.release_position(tranche, amount, 0.0, pnl, true)
+    }
+
+    /// Release capital and realize P&L; only a closed position counts as a trade
+    fn release_position(&mut self, tranche: &str, amount: f64, costs: f64, pnl: f64, closed: bool) -> Result<()> {
+        if !self.tranches.contains_key(tranche) {
+            return Err(anyhow::anyhow!("No tranche {}", tranche));
+        }
+        let event = if closed {
+            LedgerEvent::Released { tranche: tranche.to_string(), amount, costs, pnl }
+        } else {
+            LedgerEvent::Reduced { tranche: tranche.to_string(), amount, costs, pnl }
+        };
+        self.record(event)?;
+
+        let tranche = self.tranches.get_mut(tranche).unwrap();
+        tranche.used = (tranche.used - amount - costs).max(0.0);
+        tranche.cost_reserve = (tranche.cost_reserve - costs).max(0.0);
+        tranche.estimated_costs += costs;
+        tranche.realized_pnl += pnl;
+        tranche.period_pnl += pnl;
+        if closed {
+            tranche.trades += 1;
+            if pnl > 0.0 {
+                tranche.wins += 1;
+            }
+        }
+        self.total_capital += pnl;
+        Ok(())
+    }
+
+    /// Release a closed position and its cost reserve, itemizing the fees and funding
+    /// netted into its P&L so the reserve can be reconciled against them
+    pub fn release_with_costs(&mut self, tranche: &str, amount: f64, cost_reserve: f64, gross_pnl: f64, fees: f64, funding: f64) -> Result<()> {
+        self.settle(tranche, amount, cost_reserve, gross_pnl, fees, funding, true)
+    }
+
+    /// Release the share of a position closed by a partial reduction; unlike
+    /// `release_with_costs` it does not count a trade
+    pub fn reduce_with_costs(&mut self, tranche: &str, amount: f64, cost_reserve: f64, gross_pnl: f64, fees: f64, funding: f64) -> Result<()> {
+        self.settle(tranche, amount, cost_reserve, gross_pnl, fees, funding, false)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn settle(&mut self, tranche: &str, amount: f64, cost_reserve: f64, gross_pnl: f64, fees: f64, funding: f64, closed: bool) -> Result<()> {
+        if !self.tranches.contains_key(tranche) {
+            return Err(anyhow::anyhow!("No tranche {}", tranche));
+        }
+        if fees != 0.0 {
+            self.record(LedgerEvent::Fee { tranche: tranche.to_string(), amount: fees })?;
+        }
+        if funding != 0.0 {
+            self.record(LedgerEvent::Funding { tranche: tranche.to_string(), amount: funding })?;
+        }
+        self.release_position(tranche, amount, cost_reserve, gross_pnl - fees - funding, closed)?;
+
+        let tranche = self.tranches.get_mut(tranche).unwrap();
+        tranche.actual_costs += fees + funding;
+        if fees + funding > cost_reserve {
+            debug!("Tranche {} costs {:.4} exceeded the {:.4} reserved", tranche.name, fees + funding, cost_reserve);
+        }
+        Ok(())
+    }
+
+    /// Get the coin the capital is held in
+    pub fn get_settle_coin(&self) -> &str {
+        &self.settle_coin
+    }
+
+    /// Get a tranche
+    pub fn get_tranche(&self, name: &str) -> Option<&CapitalTranche> {
+        self.tranches.get(name)
+    }
+
+    /// Get all tranches by name
+    pub fn get_tranches(&self) -> &BTreeMap<String, CapitalTranche> {
+        &self.tranches
+    }
+
+    /// Get the account capital
+    pub fn get_total_capital(&self) -> f64 {
+        self.total_capital
+    }
+
+    /// Capital held back from every tranche
+    pub fn get_reserve(&self) -> f64 {
+        self.total_capital * self.reserve_percentage
+    }
+
+    /// Capital not owned by a tranche, excluding the reserve
+    pub fn get_unallocated(&self) -> f64 {
+        let owned: f64 = self.tranches.values().map(|t| t.equity()).sum();
+        self.total_capital - self.get_reserve() - owned
+    }
+
+    /// Rebalance if the interval elapsed since the last rebalance
+    pub fn maybe_rebalance(&mut self, now: u64) -> Option<Vec<RebalanceTransfer>> {
+        match self.last_rebalance {
+            Some(last) if now.saturating_sub(last) < self.rebalance_config.interval_secs => None,
+            None => {
+                // The first call only starts the clock
+                self.last_rebalance = Some(now);
+                None
+            }
+            Some(_) => Some(self.rebalance(now)),
+        }
+    }
+
+    /// Tilt weights by period returns and move free capital towards the new weights
+    pub fn rebalance(&mut self, now: u64) -> Vec<RebalanceTransfer> {
+        let config = self.rebalance_config.clone();
+        for tranche in self.tranches.values_mut() {
+            let start_equity = tranche.equity() - tranche.period_pnl;
+            let period_return = if start_equity > 0.0 { tranche.period_pnl / start_equity } else { 0.0 };
+            tranche.weight = (tranche.weight * (1.0 + config.performance_tilt * period_return).max(0.0))
+                .clamp(config.min_weight, config.max_weight);
+            tranche.period_pnl = 0.0;
+        }
+        self.normalize_weights();
+
+        let before: BTreeMap<String, f64> = self.tranches.iter().map(|(n, t)| (n.clone(), t.equity())).collect();
+        self.redistribute_all_capital();
+        self.last_rebalance = Some(now);
+
+        let event = LedgerEvent::Rebalanced {
+            weights: self.tranches.iter().map(|(n, t)| (n.clone(), t.weight)).collect(),
+            contributed: self.tranches.iter().map(|(n, t)| (n.clone(), t.contributed)).collect(),
+        };
+        if let Err(e) = self.record(event) {
+            error!("Failed to record rebalance in the capital ledger: {}", e);
+        }
+
+        let transfers: Vec<RebalanceTransfer> = self.tranches.values()
+            .map(|t| RebalanceTransfer {
+                tranche: t.name.clone(),
+                amount: t.equity() - before.get(&t.name).copied().unwrap_or(0.0),
+                weight: t.weight,
+            })
+            .filter(|transfer| transfer.amount.abs() > f64::EPSILON)
+            .collect();
+        info!("Rebalanced {} capital tranches", transfers.len());
+        transfers
+    }
+
+    /// Allocation of the account across tranches
+    pub fn report(&self) -> CapitalAllocationReport {
+        CapitalAllocationReport {
+            timestamp: std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap()
+                .as_secs(),
+            settle_coin: self.settle_coin.clone(),
+            total_capital: self.total_capital,
+            reserve: self.get_reserve(),
+            unallocated: self.get_unallocated(),
+            tranches: self.tranches.values().map(|t| TrancheReport {
+                name: t.name.clone(),
+                weight: t.weight,
+                equity: t.equity(),
+                used: t.used,
+                cost_reserve: t.cost_reserve,
+                estimated_costs: t.estimated_costs,
+                actual_costs: t.actual_costs,
+                available: t.available(),
+                realized_pnl: t.realized_pnl,
+                return_percent: t.return_percent(),
+                trades: t.trades,
+                win_rate: if t.trades > 0 { t.wins as f64 / t.trades as f64 } else { 0.0 },
+            }).collect(),
+        }
+    }
+
+    fn normalize_weights(&mut self) {
+        let total: f64 = self.tranches.values().map(|t| t.weight).sum();
+        if total > 0.0 {
+            for tranche in self.tranches.values_mut() {
+                tranche.weight /= total;
+            }
+        }
+    }
+
+    /// Move every tranche as close to its weight as its open positions allow
+    fn redistribute_all_capital(&mut self) {
+        let allocatable = self.total_capital - self.get_reserve();
+        let mut shortfall = 0.0;
+        let mut flexible_weight = 0.0;
+        for tranche in self.tranches.values_mut() {
+            let target = allocatable * tranche.weight;
+            // A tranche cannot shrink below what its open positions use
+            if target < tranche.used {
+                shortfall += tranche.used - target;
+                tranche.contributed += tranche.used - tranche.equity();
+            } else {
+                flexible_weight += tranche.weight;
+                tranche.contributed += target - tranche.equity();
+            }
+        }
+
+        // Take what pinned tranches kept from the others, by weight
+        if shortfall > 0.0 && flexible_weight > 0.0 {
+            for tranche in self.tranches.values_mut().filter(|t| t.equity() > t.used) {
+                let cut = (shortfall * tranche.weight / flexible_weight).min(tranche.available());
+                tranche.contributed -= cut;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tranches_and_performance_rebalance() {
+        let mut manager = CapitalManager::new(100.0);
+        manager.set_reserve_percentage(0.0);
+        manager.add_tranche("momentum", 0.5).unwrap();
+        manager.add_tranche("mean_reversion", 0.5).unwrap();
+        assert_eq!(manager.tranche_for("unknown"), DEFAULT_TRANCHE);
+
+        // Tranches only fund their own positions
+        let momentum = manager.get_tranche("momentum").unwrap().equity();
+        assert!(manager.reserve("momentum", momentum + 1.0).is_err());
+        manager.reserve("momentum", 10.0).unwrap();
+        manager.release("momentum", 10.0, 5.0).unwrap();
+        manager.reserve("mean_reversion", 10.0).unwrap();
+        manager.release("mean_reversion", 10.0, -5.0).unwrap();
+        assert!((manager.get_total_capital() - 100.0).abs() < 1e-9);
+
+        // Weights 0.25 / 0.25 / 0.5; momentum made 20% on its tranche, mean reversion lost 10%
+        manager.set_rebalance_config(RebalanceConfig { performance_tilt: 2.0, ..RebalanceConfig::default() });
+        let transfers = manager.rebalance(0);
+        assert_eq!(transfers.len(), 2);
+        assert!((manager.get_tranche("momentum").unwrap().weight - 0.35).abs() < 1e-9);
+        assert!((manager.get_tranche("mean_reversion").unwrap().weight - 0.4).abs() < 1e-9);
+        assert!((manager.get_tranche("momentum").unwrap().equity() - 35.0).abs() < 1e-9);
+        assert!(manager.get_unallocated().abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_strategy_without_tranche_uses_default() {
+        let mut manager = CapitalManager::new(100.0);
+        manager.set_reserve_percentage(0.0);
+        manager.add_tranche("momentum", 0.5).unwrap();
+        assert_eq!(manager.tranche_for("momentum"), "momentum");
+
+        let tranche = manager.tranche_for("breakout").to_string();
+        assert_eq!(tranche, DEFAULT_TRANCHE);
+        let available = manager.get_tranche(&tranche).unwrap().available();
+        manager.reserve(&tranche, 10.0).unwrap();
+        assert!((manager.get_tranche(DEFAULT_TRANCHE).unwrap().available() - (available - 10.0)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_cost_reserve_reconciled_at_close() {
+        let mut manager = CapitalManager::new(100.0);
+        manager.reserve_with_costs(DEFAULT_TRANCHE, 50.0, 1.0).unwrap();
+        assert!((manager.get_tranche(DEFAULT_TRANCHE).unwrap().available() - 39.0).abs() < 1e-9);
+        assert!(manager.reserve(DEFAULT_TRANCHE, 39.5).is_err());
+
+        manager.release_with_costs(DEFAULT_TRANCHE, 50.0, 1.0, 2.0, 0.6, 0.1).unwrap();
+        let tranche = manager.get_tranche(DEFAULT_TRANCHE).unwrap();
+        assert_eq!(tranche.used, 0.0);
+        assert_eq!(tranche.cost_reserve, 0.0);
+        assert!((tranche.actual_costs - 0.7).abs() < 1e-9);
+        assert!((tranche.realized_pnl - 1.3).abs() < 1e-9);
+
+        // A partial reduction realizes its share without counting a trade
+        manager.reserve_with_costs(DEFAULT_TRANCHE, 50.0, 1.0).unwrap();
+        manager.reduce_with_costs(DEFAULT_TRANCHE, 25.0, 0.5, 1.0, 0.0, 0.0).unwrap();
+        let tranche = manager.get_tranche(DEFAULT_TRANCHE).unwrap();
+        assert_eq!((tranche.used, tranche.trades, tranche.wins), (25.5, 1, 1));
+        manager.release_with_costs(DEFAULT_TRANCHE, 25.0, 0.5, 1.0, 0.0, 0.0).unwrap();
+        assert_eq!(manager.get_tranche(DEFAULT_TRANCHE).unwrap().trades, 2);
+
+        // A reservation whose position never opened is handed back without a trade
+        manager.reserve_with_costs(DEFAULT_TRANCHE, 50.0, 1.0).unwrap();
+        manager.cancel_reservation(DEFAULT_TRANCHE, 50.0, 1.0).unwrap();
+        let tranche = manager.get_tranche(DEFAULT_TRANCHE).unwrap();
+        assert_eq!((tranche.used, tranche.cost_reserve, tranche.trades), (0.0, 0.0, 2));
+    }
+}
@@ -3,10 +3,22 @@
 //! This module provides capital management, position sizing, and risk
 //! management capabilities for the trading system.
 
+pub mod collateral;
+pub mod drawdown_throttle;
+pub mod genesis;
+pub mod ledger;
 pub mod manager;
 pub mod position_sizing;
+pub mod precise_capital_tracker;
 pub mod risk_calculator;
+pub mod venues;
 
+pub use collateral::*;
+pub use drawdown_throttle::*;
+pub use genesis::*;
+pub use ledger::*;
 pub use manager::*;
 pub use position_sizing::*;
+pub use precise_capital_tracker::*;
 pub use risk_calculator::*;
+pub use venues::*;
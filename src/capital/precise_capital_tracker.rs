@@ -1,10 +1,14 @@
 //! Precise Capital Tracker Module for OMNI Trading System
 //!
 //! This module provides precise capital tracking and allocation management.
+//! Realized profits above a watermark can be swept on a schedule into a reserve
+//! bucket that no allocation can draw from, so principal keeps trading while the
+//! profits it made are locked away for withdrawal.
 
 use std::collections::HashMap;
 use serde::{Deserialize, Serialize};
 use anyhow::Result;
+use tracing::info;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CapitalAllocation {
@@ -95,10 +99,40 @@ impl CapitalAllocation {
     }
 }
 
+/// Profit sweeping policy
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProfitSweepConfig {
+    /// Capital level profits are measured above; the initial capital when unset.
+    /// Raised after every sweep so the same profit is never swept twice.
+    pub watermark: Option<f64>,
+
+    /// Share of the profit above the watermark swept each time (0.0-1.0)
+    pub sweep_percentage: f64,
+
+    /// Seconds between sweeps
+    pub interval_secs: u64,
+
+    /// Smallest amount worth sweeping
+    pub min_sweep: f64,
+}
+
+impl Default for ProfitSweepConfig {
+    fn default() -> Self {
+        Self {
+            watermark: None,
+            sweep_percentage: 0.5,
+            interval_secs: 86400,
+            min_sweep: 0.01,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CapitalSnapshot {
     pub timestamp: u64,
     pub total_capital: f64,
+    #[serde(default)]
+    pub swept_reserve: f64,
     pub allocated_capital: f64,
     pub available_capital: f64,
     pub total_profit_loss: f64,
@@ -113,6 +147,9 @@ pub struct PreciseCapitalTracker {
     capital_history: Vec<CapitalSnapshot>,
     max_history_size: usize,
     reserve_percentage: f64, // Percentage to keep as reserve
+    profit_sweep: Option<ProfitSweepConfig>,
+    swept_reserve: f64, // Swept profits, never allocated
+    last_sweep: Option<u64>,
 }
 
 impl PreciseCapitalTracker {
@@ -124,6 +161,9 @@ impl PreciseCapitalTracker {
             capital_history: Vec::new(),
             max_history_size: 1000,
             reserve_percentage: 0.1, // 10% reserve by default
+            profit_sweep: None,
+            swept_reserve: 0.0,
+            last_sweep: None,
         }
     }
 
@@ -214,8 +254,12 @@ impl PreciseCapitalTracker {
 
     pub fn get_available_capital(&self) -> f64 {
         let allocated = self.get_allocated_capital();
-        let reserve = self.total_capital * self.reserve_percentage;
-        (self.total_capital - allocated - reserve).max(0.0)
+        (self.get_trading_capital() - allocated - self.get_reserve_amount()).max(0.0)
+    }
+
+    /// Capital excluding swept profits
+    pub fn get_trading_capital(&self) -> f64 {
+        self.total_capital - self.swept_reserve
     }
 
     pub fn get_total_profit_loss(&self) -> f64 {
@@ -249,7 +293,101 @@ impl PreciseCapitalTracker {
     }
 
     pub fn get_reserve_amount(&self) -> f64 {
-        self.total_capital * self.reserve_percentage
+        self.get_trading_capital() * self.reserve_percentage
+    }
+
+    pub fn set_profit_sweep_config(&mut self, config: Option<ProfitSweepConfig>) {
+        self.profit_sweep = config;
+        self.last_sweep = None;
+    }
+
+    pub fn get_profit_sweep_config(&self) -> Option<&ProfitSweepConfig> {
+        self.profit_sweep.as_ref()
+    }
+
+    pub fn get_swept_reserve(&self) -> f64 {
+        self.swept_reserve
+    }
+
+    /// Sweep profits if the policy is set and its interval has passed; the first call starts the clock
+    pub fn maybe_sweep_profits(&mut self, now: u64) -> Option<f64> {
+        let interval = self.profit_sweep.as_ref()?.interval_secs;
+        match self.last_sweep {
+            None => {
+                self.last_sweep = Some(now);
+                None
+            }
+            Some(last) if now.saturating_sub(last) >= interval => {
+                self.last_sweep = Some(now);
+                let swept = self.sweep_profits();
+                if swept > 0.0 { Some(swept) } else { None }
+            }
+            Some(_) => None,
+        }
+    }
+
+    /// Move the configured share of realized profit above the watermark into the swept reserve.
+    /// Only free capital is swept: unallocated capital first, then what allocations have available.
+    /// The watermark then rises past the profit the sweep accounted for.
+    pub fn sweep_profits(&mut self) -> f64 {
+        let config = match &self.profit_sweep {
+            Some(config) => config.clone(),
+            None => return 0.0,
+        };
+        let watermark = config.watermark.unwrap_or(self.initial_capital);
+        let sweep_percentage = config.sweep_percentage.clamp(0.0, 1.0);
+        let excess = self.get_trading_capital() - watermark;
+        let mut remaining = excess.max(0.0) * sweep_percentage;
+        if remaining < config.min_sweep {
+            return 0.0;
+        }
+
+        let mut swept = remaining.min(self.get_available_capital());
+        remaining -= swept;
+
+        let mut agent_ids: Vec<String> = self.allocations.keys().cloned().collect();
+        agent_ids.sort();
+        for agent_id in agent_ids {
+            if remaining <= 0.0 {
+                break;
+            }
+            let allocation = self.allocations.get_mut(&agent_id).unwrap();
+            let amount = remaining.min(allocation.available_amount);
+            allocation.available_amount -= amount;
+            allocation.allocated_amount -= amount;
+            allocation.update_timestamp();
+            remaining -= amount;
+            swept += amount;
+        }
+
+        if swept > 0.0 {
+            // The profit behind this sweep stays traded as principal; any share not
+            // swept for lack of free capital stays above the watermark for next time
+            let retained = swept * (1.0 - sweep_percentage) / sweep_percentage;
+            if let Some(config) = self.profit_sweep.as_mut() {
+                config.watermark = Some(watermark + retained);
+            }
+            self.swept_reserve += swept;
+            self.take_snapshot();
+            info!("Swept {:.6} of profit into reserve ({:.6} swept in total)", swept, self.swept_reserve);
+        }
+        swept
+    }
+
+    /// Withdraw swept profits from the account
+    pub fn withdraw_swept(&mut self, amount: f64) -> Result<()> {
+        if amount > self.swept_reserve {
+            return Err(anyhow::anyhow!(
+                "Cannot withdraw more than swept: requested {}, swept {}",
+                amount,
+                self.swept_reserve
+            ));
+        }
+
+        self.swept_reserve -= amount;
+        self.total_capital -= amount;
+        self.take_snapshot();
+        Ok(())
     }
 
     fn take_snapshot(&mut self) {
@@ -259,6 +397,7 @@ impl PreciseCapitalTracker {
                 .unwrap()
                 .as_secs(),
             total_capital: self.total_capital,
+            swept_reserve: self.swept_reserve,
             allocated_capital: self.get_allocated_capital(),
             available_capital: self.get_available_capital(),
             total_profit_loss: self.get_total_profit_loss(),
@@ -292,6 +431,7 @@ impl PreciseCapitalTracker {
         summary.insert("used_capital".to_string(), self.get_used_capital());
         summary.insert("available_capital".to_string(), self.get_available_capital());
         summary.insert("reserve_amount".to_string(), self.get_reserve_amount());
+        summary.insert("swept_reserve".to_string(), self.swept_reserve);
         summary.insert("total_profit_loss".to_string(), self.get_total_profit_loss());
         summary.insert("total_return_percentage".to_string(), self.get_total_return_percentage());
         summary.insert("capital_utilization".to_string(), self.get_capital_utilization());
@@ -339,3 +479,37 @@ impl Default for PreciseCapitalTracker {
         Self::new(0.0)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_profit_sweep_above_watermark() {
+        let mut tracker = PreciseCapitalTracker::new(100.0);
+        tracker.set_profit_sweep_config(Some(ProfitSweepConfig::default()));
+        tracker.allocate_capital("agent".to_string(), 90.0).unwrap();
+        tracker.use_capital("agent", 50.0).unwrap();
+        tracker.release_capital("agent", 50.0, 20.0).unwrap();
+
+        assert_eq!(tracker.maybe_sweep_profits(0), None);
+        assert_eq!(tracker.maybe_sweep_profits(86400), Some(10.0));
+        assert_eq!(tracker.get_swept_reserve(), 10.0);
+        assert_eq!(tracker.get_trading_capital(), 110.0);
+        assert_eq!(tracker.get_allocation("agent").unwrap().available_amount, 100.0);
+        assert_eq!(tracker.get_profit_sweep_config().unwrap().watermark, Some(110.0));
+
+        // The profit already swept is not swept again
+        assert_eq!(tracker.maybe_sweep_profits(2 * 86400), None);
+        assert_eq!(tracker.get_swept_reserve(), 10.0);
+
+        // Losses below the watermark are never made up from the swept reserve
+        tracker.use_capital("agent", 50.0).unwrap();
+        tracker.release_capital("agent", 50.0, -15.0).unwrap();
+        assert_eq!(tracker.sweep_profits(), 0.0);
+        assert_eq!(tracker.get_swept_reserve(), 10.0);
+
+        tracker.withdraw_swept(10.0).unwrap();
+        assert_eq!(tracker.get_total_capital(), 95.0);
+    }
+}
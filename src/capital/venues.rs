@@ -0,0 +1,317 @@
+//! Cross-Venue Capital
+//!
+//! This module consolidates wallet balances across every exchange account the
+//! system trades on and plans transfers between them. A venue is starved when its
+//! available balance falls below a floor relative to its target share of the
+//! combined equity; the planner then suggests moving capital to it from venues
+//! with free capital above their own floor. Transfers are only suggested, never
+//! executed, so the planner is safe to run against demo and live accounts alike.
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tracing::{debug, warn};
+
+use crate::exchange::bybit::demo_adapter::BybitDemoAdapter;
+use crate::exchange::bybit::types::BybitBalance;
+use crate::exchange::BybitAdapter;
+
+/// Exchange account balances can be read from
+#[async_trait]
+pub trait BalanceSource: Send + Sync {
+    /// Venue name, unique across sources
+    fn get_venue(&self) -> String;
+
+    /// Balances by coin
+    async fn get_balances(&self) -> Result<HashMap<String, BybitBalance>>;
+}
+
+#[async_trait]
+impl BalanceSource for BybitAdapter {
+    fn get_venue(&self) -> String {
+        if self.is_demo() { "bybit-demo".to_string() } else { "bybit".to_string() }
+    }
+
+    async fn get_balances(&self) -> Result<HashMap<String, BybitBalance>> {
+        self.get_wallet_balance(None).await
+    }
+}
+
+#[async_trait]
+impl BalanceSource for BybitDemoAdapter {
+    fn get_venue(&self) -> String {
+        "bybit-demo".to_string()
+    }
+
+    async fn get_balances(&self) -> Result<HashMap<String, BybitBalance>> {
+        self.get_wallet_balance(None).await
+    }
+}
+
+/// Balance of one coin on one venue
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VenueBalance {
+    /// Venue name
+    pub venue: String,
+
+    /// Coin
+    pub coin: String,
+
+    /// Equity
+    pub equity: f64,
+
+    /// Balance free to trade or transfer
+    pub available: f64,
+
+    /// Margin in use
+    pub used_margin: f64,
+}
+
+/// Balances across all venues
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConsolidatedBalances {
+    /// Time the balances were read
+    pub timestamp: DateTime<Utc>,
+
+    /// Balance per venue and coin
+    pub balances: Vec<VenueBalance>,
+
+    /// Equity per coin across venues
+    pub total_equity: BTreeMap<String, f64>,
+
+    /// Venues that could not be read, with the error
+    pub errors: BTreeMap<String, String>,
+}
+
+impl ConsolidatedBalances {
+    /// Consolidate balances read from each venue
+    pub fn from_balances(balances: Vec<VenueBalance>, errors: BTreeMap<String, String>) -> Self {
+        let mut total_equity = BTreeMap::new();
+        for balance in &balances {
+            *total_equity.entry(balance.coin.clone()).or_insert(0.0) += balance.equity;
+        }
+        Self {
+            timestamp: Utc::now(),
+            balances,
+            total_equity,
+            errors,
+        }
+    }
+
+    /// Balances of one coin
+    pub fn get_coin(&self, coin: &str) -> Vec<&VenueBalance> {
+        self.balances.iter().filter(|b| b.coin == coin).collect()
+    }
+}
+
+/// Transfer planning rules
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TransferPlannerConfig {
+    /// Target share of a coin's combined equity per venue; equal shares when empty
+    pub target_weights: BTreeMap<String, f64>,
+
+    /// A venue is starved below this share of its target held available
+    pub starved_share: f64,
+
+    /// Smallest transfer worth suggesting
+    pub min_transfer: f64,
+}
+
+impl Default for TransferPlannerConfig {
+    fn default() -> Self {
+        Self {
+            target_weights: BTreeMap::new(),
+            starved_share: 0.2,
+            min_transfer: 5.0,
+        }
+    }
+}
+
+/// Suggested transfer between venues
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TransferSuggestion {
+    /// Coin to move
+    pub coin: String,
+
+    /// Venue to move it from
+    pub from: String,
+
+    /// Venue to move it to
+    pub to: String,
+
+    /// Amount
+    pub amount: f64,
+
+    /// Why the transfer is suggested
+    pub reason: String,
+}
+
+/// Plan transfers that bring starved venues back to their target share
+pub fn plan_transfers(balances: &ConsolidatedBalances, config: &TransferPlannerConfig) -> Vec<TransferSuggestion> {
+    let mut suggestions = Vec::new();
+
+    for (coin, total) in &balances.total_equity {
+        let venues = balances.get_coin(coin);
+        if venues.len() < 2 || *total <= 0.0 {
+            continue;
+        }
+
+        let weight_of = |venue: &str| -> f64 {
+            if config.target_weights.is_empty() {
+                1.0
+            } else {
+                config.target_weights.get(venue).copied().unwrap_or(0.0)
+            }
+        };
+        let total_weight: f64 = venues.iter().map(|v| weight_of(&v.venue)).sum();
+        if total_weight <= 0.0 {
+            continue;
+        }
+        let target = |venue: &str| total * weight_of(venue) / total_weight;
+
+        // Free capital above each venue's own floor, largest first
+        let mut donors: Vec<(String, f64)> = venues.iter()
+            .map(|v| (v.venue.clone(), v.available - target(&v.venue) * config.starved_share))
+            .filter(|(_, surplus)| *surplus > 0.0)
+            .collect();
+        donors.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+
+        for venue in venues.iter().filter(|v| v.available < target(&v.venue) * config.starved_share) {
+            let mut need = target(&venue.venue) - venue.equity;
+            debug!("{} {} starved: {:.4} available, {:.4} short of target", venue.venue, coin, venue.available, need);
+
+            for (donor, surplus) in donors.iter_mut().filter(|(donor, _)| donor != &venue.venue) {
+                if need < config.min_transfer {
+                    break;
+                }
+                let amount = need.min(*surplus);
+                if amount < config.min_transfer {
+                    continue;
+                }
+                suggestions.push(TransferSuggestion {
+                    coin: coin.clone(),
+                    from: donor.clone(),
+                    to: venue.venue.clone(),
+                    amount,
+                    reason: format!(
+                        "{} has {:.4} {} available against a {:.4} target",
+                        venue.venue, venue.available, coin, target(&venue.venue)
+                    ),
+                });
+                *surplus -= amount;
+                need -= amount;
+            }
+        }
+    }
+
+    suggestions
+}
+
+/// Balance view over every venue the system trades on
+pub struct CrossVenueCapital {
+    /// Balance sources by venue
+    sources: BTreeMap<String, Arc<dyn BalanceSource>>,
+
+    /// Transfer planning rules
+    planner_config: TransferPlannerConfig,
+}
+
+impl CrossVenueCapital {
+    /// Create an empty view
+    pub fn new(planner_config: TransferPlannerConfig) -> Self {
+        Self {
+            sources: BTreeMap::new(),
+            planner_config,
+        }
+    }
+
+    /// Add a venue
+    pub fn add_venue(&mut self, source: Arc<dyn BalanceSource>) -> Result<()> {
+        let venue = source.get_venue();
+        if self.sources.contains_key(&venue) {
+            return Err(anyhow::anyhow!("Venue {} is already added", venue));
+        }
+        self.sources.insert(venue, source);
+        Ok(())
+    }
+
+    /// Venue names
+    pub fn get_venues(&self) -> Vec<String> {
+        self.sources.keys().cloned().collect()
+    }
+
+    /// Read and consolidate the balances of every venue
+    pub async fn consolidate(&self) -> ConsolidatedBalances {
+        let mut balances = Vec::new();
+        let mut errors = BTreeMap::new();
+
+        for (venue, source) in &self.sources {
+            match source.get_balances().await {
+                Ok(coins) => {
+                    let mut coins: Vec<BybitBalance> = coins.into_values().collect();
+                    coins.sort_by(|a, b| a.coin.cmp(&b.coin));
+                    balances.extend(coins.into_iter().map(|b| VenueBalance {
+                        venue: venue.clone(),
+                        coin: b.coin,
+                        equity: b.equity,
+                        available: b.available_balance,
+                        used_margin: b.used_margin,
+                    }));
+                }
+                Err(e) => {
+                    warn!("Failed to read balances from {}: {}", venue, e);
+                    errors.insert(venue.clone(), e.to_string());
+                }
+            }
+        }
+
+        ConsolidatedBalances::from_balances(balances, errors)
+    }
+
+    /// Consolidate balances and suggest transfers to starved venues
+    pub async fn plan(&self) -> (ConsolidatedBalances, Vec<TransferSuggestion>) {
+        let balances = self.consolidate().await;
+        let suggestions = plan_transfers(&balances, &self.planner_config);
+        (balances, suggestions)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn balance(venue: &str, equity: f64, available: f64) -> VenueBalance {
+        VenueBalance {
+            venue: venue.to_string(),
+            coin: "USDT".to_string(),
+            equity,
+            available,
+            used_margin: equity - available,
+        }
+    }
+
+    #[test]
+    fn test_starved_venue_gets_transfer() {
+        let balances = ConsolidatedBalances::from_balances(
+            vec![balance("a", 90.0, 80.0), balance("b", 10.0, 2.0)],
+            BTreeMap::new(),
+        );
+        assert_eq!(balances.total_equity["USDT"], 100.0);
+
+        // Targets are 50 each; b is starved below 10 available and 40 short
+        let suggestions = plan_transfers(&balances, &TransferPlannerConfig::default());
+        assert_eq!(suggestions.len(), 1);
+        assert_eq!(suggestions[0].from, "a");
+        assert_eq!(suggestions[0].to, "b");
+        assert!((suggestions[0].amount - 40.0).abs() < 1e-9);
+
+        let balanced = ConsolidatedBalances::from_balances(
+            vec![balance("a", 50.0, 40.0), balance("b", 50.0, 40.0)],
+            BTreeMap::new(),
+        );
+        assert!(plan_transfers(&balanced, &TransferPlannerConfig::default()).is_empty());
+    }
+}
@@ -0,0 +1,603 @@
+//! Strategy Configuration Manager
+//!
+//! This module loads strategy definitions from TOML or YAML files (one file per
+//! strategy, e.g. `config/strategies/*.toml`) and reloads them when they change on
+//! disk, so binaries no longer hard-code `StrategyConfig::default()`. It also
+//! loads the agent roster, a single file with one `[agents.<name>]` table per
+//! agent holding an `enabled` flag and any settings that override the agent's
+//! default configuration, so deployments choose which agents run without
+//! recompiling.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+use anyhow::{Context, Result};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tracing::{info, warn};
+
+use crate::strategy::advanced_multi_factor_strategy::StrategyConfig as MultiFactorConfig;
+use crate::strategy::simple_strategy::StrategyConfig;
+
+/// Directory strategy files are loaded from
+pub const STRATEGY_DIRECTORY: &str = "config/strategies";
+
+/// Name of the multi-factor strategy in its strategy file
+pub const MULTI_FACTOR_STRATEGY: &str = "advanced_multi_factor";
+
+/// Strategy metadata (`[strategy]` section)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StrategyMetadata {
+    /// Unique strategy name
+    pub name: String,
+
+    /// Version
+    #[serde(default)]
+    pub version: String,
+
+    /// Description
+    #[serde(default)]
+    pub description: String,
+
+    /// Whether the strategy is enabled
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+/// Symbol sets (`[symbols]` section)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SymbolSets {
+    /// Symbols actively traded
+    #[serde(default)]
+    pub primary: Vec<String>,
+
+    /// Symbols traded with reduced allocation
+    #[serde(default)]
+    pub secondary: Vec<String>,
+
+    /// Symbols only monitored
+    #[serde(default)]
+    pub watchlist: Vec<String>,
+}
+
+/// Strategy definition loaded from a file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StrategyFileConfig {
+    /// Strategy metadata
+    pub strategy: StrategyMetadata,
+
+    /// Strategy parameters
+    #[serde(default)]
+    pub parameters: HashMap<String, Value>,
+
+    /// Symbols
+    #[serde(default)]
+    pub symbols: SymbolSets,
+
+    /// Any other strategy-specific sections (factors, signals, risk_management, ...)
+    #[serde(flatten)]
+    pub sections: HashMap<String, Value>,
+}
+
+impl StrategyFileConfig {
+    /// Parse from TOML text
+    pub fn from_toml_str(text: &str) -> Result<Self> {
+        let config: Self = toml::from_str(text).context("Invalid strategy TOML")?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Parse from YAML text
+    pub fn from_yaml_str(text: &str) -> Result<Self> {
+        let config: Self = serde_yaml::from_str(text).context("Invalid strategy YAML")?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Load from a file, choosing the format by extension
+    pub fn from_file(path: &Path) -> Result<Self> {
+        let text = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read strategy config {}", path.display()))?;
+
+        match path.extension().and_then(|e| e.to_str()) {
+            Some("toml") => Self::from_toml_str(&text),
+            Some("yaml") | Some("yml") => Self::from_yaml_str(&text),
+            _ => Err(anyhow::anyhow!("Unsupported strategy config format: {}", path.display())),
+        }
+    }
+
+    /// Validate required fields
+    pub fn validate(&self) -> Result<()> {
+        if self.strategy.name.trim().is_empty() {
+            return Err(anyhow::anyhow!("Strategy name must not be empty"));
+        }
+
+        for (key, value) in &self.parameters {
+            if let Some(number) = value.as_f64() {
+                if !number.is_finite() {
+                    return Err(anyhow::anyhow!("Parameter {} of {} is not finite", key, self.strategy.name));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Get a numeric parameter
+    pub fn get_f64(&self, key: &str) -> Option<f64> {
+        self.parameters.get(key).and_then(Value::as_f64)
+    }
+
+    /// Get a string parameter
+    pub fn get_str(&self, key: &str) -> Option<&str> {
+        self.parameters.get(key).and_then(Value::as_str)
+    }
+
+    /// Get a numeric parameter with a default
+    pub fn get_f64_or(&self, key: &str, default: f64) -> f64 {
+        self.get_f64(key).unwrap_or(default)
+    }
+
+    /// Get a numeric value nested in a section by dotted path (e.g. `factors.momentum.rsi_period`)
+    pub fn get_section_f64(&self, path: &str) -> Option<f64> {
+        let (section, rest) = path.split_once('.')?;
+        self.sections.get(section)?
+            .pointer(&format!("/{}", rest.replace('.', "/")))?
+            .as_f64()
+    }
+
+    /// Get a section (e.g. `risk_management`) by name
+    pub fn get_section(&self, name: &str) -> Option<&Value> {
+        self.sections.get(name)
+    }
+
+    /// Primary timeframe (`primary_timeframe` parameter), defaulting to 1h
+    pub fn primary_timeframe(&self) -> &str {
+        self.get_str("primary_timeframe").unwrap_or("1h")
+    }
+
+    /// Primary timeframe in minutes
+    pub fn timeframe_minutes(&self) -> u64 {
+        parse_timeframe_minutes(self.primary_timeframe()).unwrap_or(60)
+    }
+
+    /// Build a `StrategyConfig` for one symbol
+    pub fn to_strategy_config(&self, symbol: &str) -> StrategyConfig {
+        StrategyConfig {
+            symbol: symbol.to_string(),
+            timeframe: self.timeframe_minutes(),
+            take_profit_percent: self.get_f64_or("take_profit_percentage", 0.05) * 100.0,
+            stop_loss_percent: self.get_f64_or("stop_loss_percentage", 0.02) * 100.0,
+            risk_per_trade: self.get_f64_or("base_position_size", 0.1),
+        }
+    }
+
+    /// Build `StrategyConfig`s for all primary symbols
+    pub fn to_strategy_configs(&self) -> Vec<StrategyConfig> {
+        self.symbols.primary.iter().map(|s| self.to_strategy_config(s)).collect()
+    }
+
+    /// Build the multi-factor strategy configuration; settings the file leaves out keep their defaults
+    pub fn to_multi_factor_config(&self) -> MultiFactorConfig {
+        let mut config = MultiFactorConfig::default();
+        let period = |path: &str, default: usize| self.get_section_f64(path).map_or(default, |v| v as usize);
+
+        config.min_composite_score = self.get_f64_or("min_composite_score", config.min_composite_score);
+        config.min_confidence = self.get_f64_or("min_confidence", config.min_confidence);
+        config.risk_settings.max_risk_per_trade = self.get_f64_or("max_risk_per_trade", config.risk_settings.max_risk_per_trade);
+
+        let technical = &mut config.technical_settings;
+        technical.rsi_period = period("factors.momentum.rsi_period", technical.rsi_period);
+        technical.macd_fast = period("factors.momentum.macd_fast", technical.macd_fast);
+        technical.macd_slow = period("factors.momentum.macd_slow", technical.macd_slow);
+        technical.macd_signal = period("factors.momentum.macd_signal", technical.macd_signal);
+        technical.bb_period = period("factors.mean_reversion.bollinger_period", technical.bb_period);
+        technical.bb_std_dev = self.get_section_f64("factors.mean_reversion.bollinger_std").unwrap_or(technical.bb_std_dev);
+        technical.atr_period = period("factors.volatility.atr_period", technical.atr_period);
+
+        config
+    }
+}
+
+/// Multi-factor strategy configuration from its file in `directory`, or the defaults without one
+pub fn load_multi_factor_config(directory: &Path) -> MultiFactorConfig {
+    if !directory.exists() {
+        return MultiFactorConfig::default();
+    }
+
+    let mut manager = ConfigManager::new(directory);
+    match manager.load_all() {
+        Ok(_) => match manager.get_strategy(MULTI_FACTOR_STRATEGY) {
+            Some(strategy) => strategy.to_multi_factor_config(),
+            None => MultiFactorConfig::default(),
+        },
+        Err(e) => {
+            warn!("Using default multi-factor configuration, failed to load {}: {}", directory.display(), e);
+            MultiFactorConfig::default()
+        }
+    }
+}
+
+/// Parse a timeframe such as `15m`, `1h`, `4h` or `1d` into minutes
+pub fn parse_timeframe_minutes(timeframe: &str) -> Option<u64> {
+    let timeframe = timeframe.trim();
+    let (split, _) = timeframe.char_indices().last()?;
+    let (number, unit) = timeframe.split_at(split);
+    let value: u64 = number.parse().ok()?;
+
+    match unit {
+        "m" => Some(value),
+        "h" => Some(value * 60),
+        "d" => Some(value * 60 * 24),
+        "w" => Some(value * 60 * 24 * 7),
+        _ => None,
+    }
+}
+
+/// One agent in the roster (`[agents.<name>]` table)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AgentEntry {
+    /// Whether the agent runs
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+
+    /// Settings overriding the agent's default configuration
+    #[serde(flatten)]
+    pub settings: serde_json::Map<String, Value>,
+}
+
+/// Agent roster loaded from a single file
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AgentRosterConfig {
+    /// Agents by name; agents not listed run with their defaults
+    #[serde(default)]
+    pub agents: BTreeMap<String, AgentEntry>,
+}
+
+impl AgentRosterConfig {
+    /// Parse from TOML text
+    pub fn from_toml_str(text: &str) -> Result<Self> {
+        toml::from_str(text).context("Invalid agent roster TOML")
+    }
+
+    /// Load from a TOML file
+    pub fn from_file(path: &Path) -> Result<Self> {
+        let text = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read agent roster {}", path.display()))?;
+        Self::from_toml_str(&text)
+    }
+
+    /// Whether an agent runs; agents not listed do
+    pub fn is_enabled(&self, name: &str) -> bool {
+        self.agents.get(name).map_or(true, |entry| entry.enabled)
+    }
+
+    /// Names of the listed agents that are enabled
+    pub fn enabled_agents(&self) -> Vec<String> {
+        self.agents.iter()
+            .filter(|(_, entry)| entry.enabled)
+            .map(|(name, _)| name.clone())
+            .collect()
+    }
+
+    /// Configuration of an agent: its default with the roster settings applied on top
+    pub fn agent_config<T: Default + Serialize + DeserializeOwned>(&self, name: &str) -> Result<T> {
+        let Some(entry) = self.agents.get(name).filter(|entry| !entry.settings.is_empty()) else {
+            return Ok(T::default());
+        };
+
+        let mut config = serde_json::to_value(T::default())?;
+        let Value::Object(fields) = &mut config else {
+            return Err(anyhow::anyhow!("Configuration of agent {} is not a table", name));
+        };
+        for (key, value) in &entry.settings {
+            if !fields.contains_key(key) {
+                return Err(anyhow::anyhow!("Unknown setting {} for agent {}", key, name));
+            }
+            fields.insert(key.clone(), value.clone());
+        }
+
+        serde_json::from_value(config).with_context(|| format!("Invalid configuration for agent {}", name))
+    }
+}
+
+/// Loaded strategy file with its modification time
+#[derive(Debug, Clone)]
+struct LoadedStrategy {
+    path: PathBuf,
+    modified: Option<SystemTime>,
+    config: StrategyFileConfig,
+}
+
+/// Strategy configuration manager
+#[derive(Debug, Clone)]
+pub struct ConfigManager {
+    /// Directory containing strategy files
+    directory: PathBuf,
+
+    /// Loaded strategies by name
+    strategies: HashMap<String, LoadedStrategy>,
+
+    /// Agent roster
+    agents: AgentRosterConfig,
+}
+
+impl ConfigManager {
+    /// Create a manager for a strategy directory
+    pub fn new<P: Into<PathBuf>>(directory: P) -> Self {
+        Self {
+            directory: directory.into(),
+            strategies: HashMap::new(),
+            agents: AgentRosterConfig::default(),
+        }
+    }
+
+    /// Load every TOML/YAML file in the directory
+    pub fn load_all(&mut self) -> Result<usize> {
+        self.strategies.clear();
+
+        for path in self.strategy_files()? {
+            let config = StrategyFileConfig::from_file(&path)?;
+            let name = config.strategy.name.clone();
+            if self.strategies.contains_key(&name) {
+                return Err(anyhow::anyhow!("Duplicate strategy name {} in {}", name, path.display()));
+            }
+
+            self.strategies.insert(name, LoadedStrategy {
+                modified: modified_time(&path),
+                path,
+                config,
+            });
+        }
+
+        info!("Loaded {} strategy configurations from {}", self.strategies.len(), self.directory.display());
+        Ok(self.strategies.len())
+    }
+
+    /// Reload files that changed on disk; returns the names of changed or removed strategies
+    ///
+    /// Files that fail to parse, or that declare the name of a strategy loaded from
+    /// another file, keep their previous configuration so a bad edit does not take a
+    /// running strategy down. Strategies whose file was deleted are dropped.
+    pub fn reload_changed(&mut self) -> Result<Vec<String>> {
+        let files = self.strategy_files()?;
+        let mut changed: Vec<String> = self.strategies.iter()
+            .filter(|(_, s)| !files.contains(&s.path))
+            .map(|(name, _)| name.clone())
+            .collect();
+        for name in &changed {
+            self.strategies.remove(name);
+        }
+
+        for path in files {
+            let modified = modified_time(&path);
+            let known = self.strategies.values().find(|s| s.path == path);
+            if known.map_or(false, |s| s.modified == modified) {
+                continue;
+            }
+
+            match StrategyFileConfig::from_file(&path) {
+                Ok(config) => {
+                    let name = config.strategy.name.clone();
+                    if self.strategies.get(&name).is_some_and(|s| s.path != path) {
+                        warn!("Keeping previous configuration, duplicate strategy name {} in {}", name, path.display());
+                        continue;
+                    }
+                    self.strategies.retain(|_, s| s.path != path);
+                    self.strategies.insert(name.clone(), LoadedStrategy { path, modified, config });
+                    changed.push(name);
+                }
+                Err(e) => warn!("Keeping previous configuration, failed to reload {}: {}", path.display(), e),
+            }
+        }
+
+        if !changed.is_empty() {
+            info!("Reloaded strategy configurations: {:?}", changed);
+        }
+
+        Ok(changed)
+    }
+
+    /// Get a strategy configuration by name
+    pub fn get_strategy(&self, name: &str) -> Option<&StrategyFileConfig> {
+        self.strategies.get(name).map(|s| &s.config)
+    }
+
+    /// Enabled strategy configurations
+    pub fn enabled_strategies(&self) -> Vec<&StrategyFileConfig> {
+        self.strategies.values()
+            .map(|s| &s.config)
+            .filter(|c| c.strategy.enabled)
+            .collect()
+    }
+
+    /// Names of all loaded strategies
+    pub fn strategy_names(&self) -> Vec<String> {
+        self.strategies.keys().cloned().collect()
+    }
+
+    /// Load the agent roster from a TOML file
+    pub fn load_agents(&mut self, path: &Path) -> Result<usize> {
+        self.agents = AgentRosterConfig::from_file(path)?;
+        info!("Loaded agent roster from {}: {} of {} listed agents enabled",
+              path.display(), self.agents.enabled_agents().len(), self.agents.agents.len());
+        Ok(self.agents.agents.len())
+    }
+
+    /// Get the agent roster
+    pub fn get_agents(&self) -> &AgentRosterConfig {
+        &self.agents
+    }
+
+    /// Whether an agent runs
+    pub fn is_agent_enabled(&self, name: &str) -> bool {
+        self.agents.is_enabled(name)
+    }
+
+    /// Configuration of an agent from the roster
+    pub fn agent_config<T: Default + Serialize + DeserializeOwned>(&self, name: &str) -> Result<T> {
+        self.agents.agent_config(name)
+    }
+
+    /// Strategy directory
+    pub fn get_directory(&self) -> &Path {
+        &self.directory
+    }
+
+    /// List strategy files in the directory
+    fn strategy_files(&self) -> Result<Vec<PathBuf>> {
+        let mut files: Vec<PathBuf> = fs::read_dir(&self.directory)
+            .with_context(|| format!("Failed to read strategy directory {}", self.directory.display()))?
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|path| matches!(path.extension().and_then(|e| e.to_str()), Some("toml") | Some("yaml") | Some("yml")))
+            .collect();
+
+        files.sort();
+        Ok(files)
+    }
+}
+
+fn modified_time(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agents::ghost_trader::GhostTraderConfig;
+    use crate::agents::high_frequency_trader::HighFrequencyTraderConfig;
+
+    #[test]
+    fn test_agent_roster_overrides_defaults() {
+        let roster = AgentRosterConfig::from_toml_str(r#"
+            [agents.high_frequency_trader]
+            target_trades_per_day = 300
+            timeframes = ["5", "15"]
+
+            [agents.ghost_trader]
+            enabled = false
+
+            [agents.anti_loss_hedger]
+            hedge_ratios = 0.5
+        "#).unwrap();
+
+        assert!(roster.is_enabled("high_frequency_trader"));
+        assert!(!roster.is_enabled("ghost_trader"));
+        assert!(roster.is_enabled("god_kernel"));
+
+        let hft: HighFrequencyTraderConfig = roster.agent_config("high_frequency_trader").unwrap();
+        let defaults = HighFrequencyTraderConfig::default();
+        assert_eq!(hft.target_trades_per_day, 300);
+        assert_eq!(hft.timeframes, vec!["5".to_string(), "15".to_string()]);
+        assert_eq!(hft.max_assets, defaults.max_assets);
+
+        let ghost: GhostTraderConfig = roster.agent_config("ghost_trader").unwrap();
+        assert_eq!(ghost.simulation_depth, GhostTraderConfig::default().simulation_depth);
+
+        // Misspelled settings are refused rather than silently ignored
+        let hedger: Result<crate::agents::anti_loss_hedger::AntiLossHedgerConfig> = roster.agent_config("anti_loss_hedger");
+        assert!(hedger.is_err());
+    }
+
+    #[test]
+    fn test_strategy_files_parse_from_toml_and_yaml() {
+        let toml = StrategyFileConfig::from_toml_str(r#"
+            [strategy]
+            name = "trend"
+
+            [parameters]
+            primary_timeframe = "4h"
+            min_confidence = 70.0
+            take_profit_percentage = 0.03
+
+            [symbols]
+            primary = ["BTCUSDT", "ETHUSDT"]
+
+            [factors.momentum]
+            rsi_period = 21
+            macd_fast = 8
+
+            [factors.mean_reversion]
+            bollinger_std = 2.5
+        "#).unwrap();
+        let yaml = StrategyFileConfig::from_yaml_str("
+strategy:
+  name: trend
+parameters:
+  primary_timeframe: 4h
+  min_confidence: 70.0
+  take_profit_percentage: 0.03
+symbols:
+  primary: [BTCUSDT, ETHUSDT]
+factors:
+  momentum:
+    rsi_period: 21
+    macd_fast: 8
+  mean_reversion:
+    bollinger_std: 2.5
+").unwrap();
+
+        for config in [&toml, &yaml] {
+            assert!(config.strategy.enabled);
+            assert_eq!(config.timeframe_minutes(), 240);
+            assert_eq!(config.get_section_f64("factors.momentum.rsi_period"), Some(21.0));
+
+            let configs = config.to_strategy_configs();
+            assert_eq!(configs.len(), 2);
+            assert!((configs[0].take_profit_percent - 3.0).abs() < 1e-9);
+
+            let multi_factor = config.to_multi_factor_config();
+            let defaults = MultiFactorConfig::default();
+            assert_eq!(multi_factor.min_confidence, 70.0);
+            assert_eq!(multi_factor.technical_settings.rsi_period, 21);
+            assert_eq!(multi_factor.technical_settings.macd_fast, 8);
+            assert_eq!(multi_factor.technical_settings.bb_std_dev, 2.5);
+            assert_eq!(multi_factor.technical_settings.macd_slow, defaults.technical_settings.macd_slow);
+        }
+
+        assert!(StrategyFileConfig::from_toml_str("[strategy]\nname = \"\"").is_err());
+        let shipped = StrategyFileConfig::from_toml_str(include_str!("../../config/strategies/advanced_multi_factor.toml")).unwrap();
+        assert_eq!(shipped.strategy.name, MULTI_FACTOR_STRATEGY);
+    }
+
+    #[test]
+    fn test_timeframe_with_multibyte_unit_is_rejected() {
+        assert_eq!(parse_timeframe_minutes("15m"), Some(15));
+        assert_eq!(parse_timeframe_minutes("1d"), Some(1440));
+        assert_eq!(parse_timeframe_minutes("1分"), None);
+        assert_eq!(parse_timeframe_minutes(""), None);
+    }
+
+    #[test]
+    fn test_reload_drops_deleted_files_and_rejects_duplicates() {
+        let directory = std::env::temp_dir().join(format!("omni_strategies_{}", std::process::id()));
+        fs::create_dir_all(&directory).unwrap();
+        fs::write(directory.join("a.toml"), "[strategy]\nname = \"alpha\"\n[parameters]\nprimary_timeframe = \"1h\"\n").unwrap();
+        fs::write(directory.join("b.yaml"), "strategy:\n  name: beta\n").unwrap();
+
+        let mut manager = ConfigManager::new(&directory);
+        assert_eq!(manager.load_all().unwrap(), 2);
+        assert!(manager.reload_changed().unwrap().is_empty());
+
+        std::thread::sleep(std::time::Duration::from_millis(20));
+        fs::write(directory.join("a.toml"), "[strategy]\nname = \"alpha\"\n[parameters]\nprimary_timeframe = \"15m\"\n").unwrap();
+        fs::remove_file(directory.join("b.yaml")).unwrap();
+        fs::write(directory.join("c.toml"), "[strategy]\nname = \"alpha\"\n").unwrap();
+
+        let mut changed = manager.reload_changed().unwrap();
+        changed.sort();
+        assert_eq!(changed, vec!["alpha".to_string(), "beta".to_string()]);
+        assert_eq!(manager.strategy_names(), vec!["alpha".to_string()]);
+        assert_eq!(manager.get_strategy("alpha").unwrap().timeframe_minutes(), 15);
+
+        // load_all refuses the same duplicate outright
+        assert!(manager.load_all().is_err());
+        fs::remove_dir_all(&directory).unwrap();
+    }
+}
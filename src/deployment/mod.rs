@@ -7,6 +7,10 @@ use std::collections::HashMap;
 use serde::{Deserialize, Serialize};
 use anyhow::Result;
 
+pub mod config_manager;
+
+pub use config_manager::{ConfigManager, StrategyFileConfig};
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum DeploymentEnvironment {
     Development,
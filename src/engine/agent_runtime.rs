@@ -0,0 +1,587 @@
+//! Agent Runtime
+//!
+//! This module runs each agent in a task of its own. The task owns the agent,
+//! so nothing else holds a reference to it: messages reach it through a bounded
+//! inbox and its periodic work runs on a tick interval inside the same loop. An
+//! agent is never shared behind a lock, and a slow agent only backs up its own
+//! inbox instead of blocking the others. Every handled message and tick stamps a
+//! heartbeat the supervisor uses to spot stalled agents, and updates the agent's
+//! counters: messages handled, errors and how long its last decision took. When
+//! the runtime has a message bus, each agent task also publishes an
+//! `AgentHeartbeat` with those counters and its queue depth on an interval.
+//! Agents declare the topic patterns they care about: when the runtime has a
+//! message bus it subscribes each agent to its patterns and the agent task reads
+//! its subscription directly, and `dispatch` only routes to agents whose
+//! patterns match, so no agent receives and filters every message.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicI64, AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+use chrono::{DateTime, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use anyhow::Result;
+use tokio::sync::{mpsc, oneshot, RwLock};
+use tokio::task::JoinHandle;
+use tokio::time::MissedTickBehavior;
+use tracing::{info, warn};
+
+use crate::engine::agent_trait::{Agent, AgentContext};
+use crate::engine::message_bus::{topic_matches, BusMessage, Message, MessageBus};
+
+/// Topic used for agent heartbeats (`Message::Custom`)
+pub const AGENT_HEARTBEAT_TOPIC: &str = "agent_heartbeat";
+
+/// Agent runtime settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AgentRuntimeConfig {
+    /// Interval between ticks of each agent (milliseconds)
+    pub tick_interval_ms: u64,
+
+    /// Messages an agent's inbox holds before new ones are dropped
+    pub inbox_capacity: usize,
+
+    /// Interval between published heartbeats of each agent (milliseconds)
+    pub heartbeat_interval_ms: u64,
+}
+
+impl Default for AgentRuntimeConfig {
+    fn default() -> Self {
+        Self {
+            tick_interval_ms: 1000,
+            inbox_capacity: 256,
+            heartbeat_interval_ms: 5000,
+        }
+    }
+}
+
+/// Command sent to an agent task
+enum AgentCommand {
+    /// Message to handle
+    Message(BusMessage),
+
+    /// Stop the agent and report the outcome
+    Stop(oneshot::Sender<Result<()>>),
+}
+
+/// Counters an agent task updates as it works
+#[derive(Debug, Default)]
+struct AgentStats {
+    /// Time of the last handled message or tick (Unix milliseconds)
+    heartbeat: AtomicI64,
+
+    /// Messages handled
+    messages_handled: AtomicU64,
+
+    /// Failed message handlers and ticks
+    errors: AtomicU64,
+
+    /// Duration of the last handled message or tick (microseconds)
+    last_latency_us: AtomicU64,
+}
+
+impl AgentStats {
+    /// Record a handled message or tick that took `latency`
+    fn record(&self, latency: Duration, is_message: bool, failed: bool) {
+        if is_message {
+            self.messages_handled.fetch_add(1, Ordering::Relaxed);
+        }
+        if failed {
+            self.errors.fetch_add(1, Ordering::Relaxed);
+        }
+        self.last_latency_us.store(latency.as_micros() as u64, Ordering::Relaxed);
+        self.heartbeat.store(Utc::now().timestamp_millis(), Ordering::Relaxed);
+    }
+
+    /// Heartbeat of an agent from its counters and inbox
+    fn heartbeat(&self, agent: &str, inbox: &mpsc::Sender<AgentCommand>) -> AgentHeartbeat {
+        AgentHeartbeat {
+            agent: agent.to_string(),
+            timestamp: Utc.timestamp_millis_opt(self.heartbeat.load(Ordering::Relaxed)).single().unwrap_or_default(),
+            queue_depth: inbox.max_capacity() - inbox.capacity(),
+            queue_capacity: inbox.max_capacity(),
+            last_latency_ms: self.last_latency_us.load(Ordering::Relaxed) as f64 / 1000.0,
+            messages_handled: self.messages_handled.load(Ordering::Relaxed),
+            error_count: self.errors.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// Health report of one agent
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AgentHeartbeat {
+    /// Agent name
+    pub agent: String,
+
+    /// Time of the last handled message or tick
+    pub timestamp: DateTime<Utc>,
+
+    /// Messages waiting in the inbox
+    pub queue_depth: usize,
+
+    /// Messages the inbox holds
+    pub queue_capacity: usize,
+
+    /// Duration of the last handled message or tick (milliseconds)
+    pub last_latency_ms: f64,
+
+    /// Messages handled since start
+    pub messages_handled: u64,
+
+    /// Failed message handlers and ticks since start
+    pub error_count: u64,
+}
+
+impl AgentHeartbeat {
+    /// Wrap the heartbeat in a bus message
+    pub fn to_message(&self) -> Message {
+        Message::Custom(
+            AGENT_HEARTBEAT_TOPIC.to_string(),
+            serde_json::to_value(self).unwrap_or(Value::Null),
+        )
+    }
+
+    /// Parse a heartbeat from a message, if it is one
+    pub fn from_message(message: &Message) -> Option<Self> {
+        match message {
+            Message::Custom(topic, payload) if topic == AGENT_HEARTBEAT_TOPIC => {
+                match serde_json::from_value(payload.clone()) {
+                    Ok(heartbeat) => Some(heartbeat),
+                    Err(e) => {
+                        warn!("Ignoring malformed agent heartbeat: {}", e);
+                        None
+                    }
+                }
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Handle to a running agent task
+pub struct AgentHandle {
+    /// Agent name
+    name: String,
+
+    /// Inbox of the agent task
+    inbox: mpsc::Sender<AgentCommand>,
+
+    /// Topic patterns the agent receives
+    topics: Vec<String>,
+
+    /// Agent task
+    task: JoinHandle<()>,
+
+    /// Counters updated by the agent task
+    stats: Arc<AgentStats>,
+}
+
+impl AgentHandle {
+    /// Get the agent name
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    /// Topic patterns the agent receives
+    pub fn get_topics(&self) -> &[String] {
+        &self.topics
+    }
+
+    /// Whether the agent receives messages on `topic`
+    pub fn accepts(&self, topic: &str) -> bool {
+        self.topics.iter().any(|pattern| topic_matches(pattern, topic))
+    }
+
+    /// Queue a message for the agent without waiting
+    pub fn send(&self, message: BusMessage) -> Result<()> {
+        self.inbox.try_send(AgentCommand::Message(message))
+            .map_err(|e| anyhow::anyhow!("Agent {} did not accept message: {}", self.name, e))
+    }
+
+    /// Whether the agent task is still running
+    pub fn is_running(&self) -> bool {
+        !self.task.is_finished()
+    }
+
+    /// Time of the last handled message or tick
+    pub fn get_heartbeat(&self) -> DateTime<Utc> {
+        Utc.timestamp_millis_opt(self.stats.heartbeat.load(Ordering::Relaxed)).single().unwrap_or_default()
+    }
+
+    /// Current heartbeat with the agent's counters and queue depth
+    pub fn get_heartbeat_report(&self) -> AgentHeartbeat {
+        self.stats.heartbeat(&self.name, &self.inbox)
+    }
+
+    /// Stop the agent and wait for its task to finish
+    pub async fn stop(self) -> Result<()> {
+        let (reply, outcome) = oneshot::channel();
+        if self.inbox.send(AgentCommand::Stop(reply)).await.is_err() {
+            return Err(anyhow::anyhow!("Agent {} is not running", self.name));
+        }
+        let result = outcome.await
+            .map_err(|_| anyhow::anyhow!("Agent {} exited before stopping", self.name))?;
+        let _ = self.task.await;
+        result
+    }
+}
+
+/// Runs agents in their own tasks and routes messages to them
+#[derive(Default)]
+pub struct AgentRuntime {
+    /// Settings
+    config: AgentRuntimeConfig,
+
+    /// Running agents by name
+    agents: HashMap<String, AgentHandle>,
+
+    /// Bus heartbeats are published on
+    message_bus: Option<Arc<MessageBus>>,
+}
+
+impl AgentRuntime {
+    /// Create a new agent runtime
+    pub fn new(config: AgentRuntimeConfig) -> Self {
+        Self {
+            config,
+            agents: HashMap::new(),
+            message_bus: None,
+        }
+    }
+
+    /// Publish the heartbeats of agents spawned from now on to `message_bus`
+    pub fn set_message_bus(&mut self, message_bus: Arc<MessageBus>) {
+        self.message_bus = Some(message_bus);
+    }
+
+    /// Current heartbeats of every agent handed to the runtime
+    pub fn get_heartbeats(&self) -> Vec<AgentHeartbeat> {
+        self.agents.values().map(AgentHandle::get_heartbeat_report).collect()
+    }
+
+    /// Get the settings
+    pub fn get_config(&self) -> &AgentRuntimeConfig {
+        &self.config
+    }
+
+    /// Initialize and start an agent, then hand it to a task of its own
+    pub async fn spawn(&mut self, mut agent: Box<dyn Agent>, context: Arc<RwLock<AgentContext>>) -> Result<()> {
+        let name = agent.get_name().to_string();
+        if self.agents.get(&name).is_some_and(AgentHandle::is_running) {
+            return Err(anyhow::anyhow!("Agent {} is already running", name));
+        }
+
+        agent.initialize(context).await?;
+        agent.start().await?;
+
+        // Agents without topics still subscribe so messages addressed to them arrive
+        let topics = agent.topics();
+        let subscription = match &self.message_bus {
+            Some(bus) => Some(bus.subscribe_topics(name.clone(), topics.clone()).await?),
+            None => None,
+        };
+
+        let (inbox, commands) = mpsc::channel(self.config.inbox_capacity.max(1));
+        let tick_interval = Duration::from_millis(self.config.tick_interval_ms.max(1));
+        let stats = Arc::new(AgentStats::default());
+        stats.heartbeat.store(Utc::now().timestamp_millis(), Ordering::Relaxed);
+        let publisher = self.message_bus.clone().map(|bus| HeartbeatPublisher {
+            message_bus: bus,
+            inbox: inbox.clone(),
+            interval: Duration::from_millis(self.config.heartbeat_interval_ms.max(1)),
+        });
+        let task = tokio::spawn(run_agent(agent, commands, subscription, tick_interval, stats.clone(), publisher));
+        info!("Agent {} running on topics {:?}", name, topics);
+        self.agents.insert(name.clone(), AgentHandle { name, inbox, topics, task, stats });
+        Ok(())
+    }
+
+    /// Queue a message for the agents whose topic patterns match its topic; returns how many accepted it
+    ///
+    /// For messages that do not travel over the bus (replays, shadow feeds); bus
+    /// messages reach subscribed agents through their subscriptions.
+    pub fn dispatch(&self, message: &BusMessage) -> usize {
+        self.agents.values()
+            .filter(|handle| handle.accepts(&message.topic))
+            .filter(|handle| match handle.send(message.clone()) {
+                Ok(()) => true,
+                Err(e) => {
+                    warn!("{}", e);
+                    false
+                }
+            })
+            .count()
+    }
+
+    /// Queue a message for one agent
+    pub fn send_to(&self, name: &str, message: BusMessage) -> Result<()> {
+        self.agents.get(name)
+            .ok_or_else(|| anyhow::anyhow!("Agent {} not found", name))?
+            .send(message)
+    }
+
+    /// Names of the agents handed to the runtime
+    pub fn get_agent_names(&self) -> Vec<String> {
+        self.agents.keys().cloned().collect()
+    }
+
+    /// Whether an agent is running
+    pub fn is_running(&self, name: &str) -> bool {
+        self.agents.get(name).is_some_and(AgentHandle::is_running)
+    }
+
+    /// Get the handle of an agent handed to the runtime and not stopped since
+    pub fn get_handle(&self, name: &str) -> Option<&AgentHandle> {
+        self.agents.get(name)
+    }
+
+    /// Abort an agent's task without stopping the agent (for stalled or dead tasks)
+    pub fn abort(&mut self, name: &str) -> bool {
+        match self.agents.remove(name) {
+            Some(handle) => {
+                handle.task.abort();
+                true
+            },
+            None => false,
+        }
+    }
+
+    /// Stop one agent
+    pub async fn stop(&mut self, name: &str) -> Result<()> {
+        let handle = self.agents.remove(name)
+            .ok_or_else(|| anyhow::anyhow!("Agent {} not found", name))?;
+        handle.stop().await
+    }
+
+    /// Stop every agent, returning the first failure
+    pub async fn stop_all(&mut self) -> Result<()> {
+        let mut result = Ok(());
+        for (name, handle) in self.agents.drain() {
+            if let Err(e) = handle.stop().await {
+                warn!("Failed to stop agent {}: {}", name, e);
+                if result.is_ok() {
+                    result = Err(e);
+                }
+            }
+        }
+        result
+    }
+}
+
+/// Where and how often an agent task publishes its heartbeat
+struct HeartbeatPublisher {
+    /// Bus heartbeats are published on
+    message_bus: Arc<MessageBus>,
+
+    /// Sender side of the agent's inbox, for its queue depth
+    inbox: mpsc::Sender<AgentCommand>,
+
+    /// Interval between heartbeats
+    interval: Duration,
+}
+
+/// Bus message handed to an agent, from a message on its subscription
+fn to_bus_message(message: Message) -> BusMessage {
+    BusMessage {
+        message_type: message.message_type,
+        topic: message.topic,
+        content: serde_json::to_string(&message.payload).unwrap_or_default(),
+        timestamp: Utc.timestamp_opt(message.timestamp as i64, 0).single().unwrap_or_default(),
+    }
+}
+
+/// Next message on an agent's bus subscription; never resolves without one
+async fn next_subscribed(subscription: &mut Option<mpsc::Receiver<Message>>) -> Option<Message> {
+    match subscription {
+        Some(receiver) => receiver.recv().await,
+        None => std::future::pending().await,
+    }
+}
+
+/// Agent task: handle messages as they arrive, tick on the interval and publish heartbeats
+async fn run_agent(
+    mut agent: Box<dyn Agent>,
+    mut commands: mpsc::Receiver<AgentCommand>,
+    mut subscription: Option<mpsc::Receiver<Message>>,
+    tick_interval: Duration,
+    stats: Arc<AgentStats>,
+    publisher: Option<HeartbeatPublisher>,
+) {
+    let mut ticker = tokio::time::interval(tick_interval);
+    ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+    // Without a bus the heartbeat timer never needs to fire
+    let mut heartbeats = tokio::time::interval(publisher.as_ref().map_or(Duration::from_secs(3600), |p| p.interval));
+    heartbeats.set_missed_tick_behavior(MissedTickBehavior::Skip);
+
+    loop {
+        tokio::select! {
+            command = commands.recv() => match command {
+                Some(AgentCommand::Message(message)) => {
+                    let started = Instant::now();
+                    let result = agent.handle_message(message).await;
+                    stats.record(started.elapsed(), true, result.is_err());
+                    if let Err(e) = result {
+                        warn!("Agent {} failed to handle message: {}", agent.get_name(), e);
+                    }
+                },
+                Some(AgentCommand::Stop(reply)) => {
+                    let _ = reply.send(agent.stop().await);
+                    return;
+                },
+                None => {
+                    if let Err(e) = agent.stop().await {
+                        warn!("Agent {} failed to stop: {}", agent.get_name(), e);
+                    }
+                    return;
+                },
+            },
+            message = next_subscribed(&mut subscription) => match message {
+                Some(message) => {
+                    let started = Instant::now();
+                    let result = agent.handle_message(to_bus_message(message)).await;
+                    stats.record(started.elapsed(), true, result.is_err());
+                    if let Err(e) = result {
+                        warn!("Agent {} failed to handle message: {}", agent.get_name(), e);
+                    }
+                },
+                None => {
+                    warn!("Subscription of agent {} closed", agent.get_name());
+                    subscription = None;
+                },
+            },
+            _ = ticker.tick() => {
+                let started = Instant::now();
+                let result = agent.tick().await;
+                stats.record(started.elapsed(), false, result.is_err());
+                if let Err(e) = result {
+                    warn!("Agent {} tick failed: {}", agent.get_name(), e);
+                }
+            },
+            _ = heartbeats.tick() => {
+                if let Some(publisher) = &publisher {
+                    publisher.message_bus.send(stats.heartbeat(agent.get_name(), &publisher.inbox).to_message());
+                }
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use async_trait::async_trait;
+    use crate::engine::agent_trait::AgentConfig;
+    use crate::engine::message_bus::MessageType;
+
+    #[derive(Default)]
+    struct CountingAgent {
+        topics: Vec<String>,
+        ticks: Arc<AtomicUsize>,
+        messages: Arc<AtomicUsize>,
+        stopped: Arc<AtomicUsize>,
+    }
+
+    #[async_trait]
+    impl Agent for CountingAgent {
+        fn get_name(&self) -> &str {
+            "counter"
+        }
+
+        fn get_config(&self) -> Box<dyn AgentConfig> {
+            Box::new(())
+        }
+
+        fn topics(&self) -> Vec<String> {
+            self.topics.clone()
+        }
+
+        async fn initialize(&mut self, _context: Arc<RwLock<AgentContext>>) -> Result<()> {
+            Ok(())
+        }
+
+        async fn start(&mut self) -> Result<()> {
+            Ok(())
+        }
+
+        async fn stop(&mut self) -> Result<()> {
+            self.stopped.fetch_add(1, Ordering::SeqCst);
+            Ok(())
+        }
+
+        async fn tick(&mut self) -> Result<()> {
+            self.ticks.fetch_add(1, Ordering::SeqCst);
+            Ok(())
+        }
+
+        async fn handle_message(&mut self, _message: BusMessage) -> Result<()> {
+            self.messages.fetch_add(1, Ordering::SeqCst);
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_agents_tick_and_handle_messages_in_their_own_task() {
+        let agent = CountingAgent { topics: vec!["system.*".to_string()], ..CountingAgent::default() };
+        let (ticks, messages, stopped) = (agent.ticks.clone(), agent.messages.clone(), agent.stopped.clone());
+
+        let mut runtime = AgentRuntime::new(AgentRuntimeConfig { tick_interval_ms: 10, ..AgentRuntimeConfig::default() });
+        let context = Arc::new(RwLock::new(AgentContext::new("counter".to_string())));
+        runtime.spawn(Box::new(agent), context.clone()).await.unwrap();
+        assert!(runtime.spawn(Box::new(CountingAgent::default()), context).await.is_err());
+
+        let message = BusMessage {
+            message_type: MessageType::SystemStatus,
+            topic: "system.status".to_string(),
+            content: "ping".to_string(),
+            timestamp: chrono::Utc::now(),
+        };
+        assert_eq!(runtime.dispatch(&message), 1);
+        runtime.send_to("counter", message).unwrap();
+        tokio::time::sleep(Duration::from_millis(50)).await;
+
+        assert_eq!(messages.load(Ordering::SeqCst), 2);
+        assert!(ticks.load(Ordering::SeqCst) >= 2);
+        assert!(runtime.is_running("counter"));
+
+        let heartbeat = &runtime.get_heartbeats()[0];
+        assert_eq!((heartbeat.agent.as_str(), heartbeat.messages_handled, heartbeat.error_count), ("counter", 2, 0));
+        assert_eq!(heartbeat.queue_depth, 0);
+        assert_eq!(AgentHeartbeat::from_message(&heartbeat.to_message()).as_ref(), Some(heartbeat));
+
+        runtime.stop_all().await.unwrap();
+        assert_eq!(stopped.load(Ordering::SeqCst), 1);
+        assert!(runtime.get_agent_names().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_agents_only_receive_their_topics() {
+        let agent = CountingAgent { topics: vec!["market.BTCUSDT.#".to_string()], ..CountingAgent::default() };
+        let messages = agent.messages.clone();
+
+        let bus = Arc::new(MessageBus::new());
+        let mut runtime = AgentRuntime::new(AgentRuntimeConfig::default());
+        runtime.set_message_bus(bus.clone());
+        runtime.spawn(Box::new(agent), Arc::new(RwLock::new(AgentContext::new("counter".to_string())))).await.unwrap();
+        assert_eq!(runtime.get_handle("counter").unwrap().get_topics(), ["market.BTCUSDT.#".to_string()]);
+        assert_eq!(bus.get_subscribers_count().await, 1);
+
+        for symbol in ["BTCUSDT", "ETHUSDT"] {
+            let candle = Message::create_candle_message("feed".to_string(), symbol.to_string(), "1m".to_string(), 1.0, 2.0, 0.5, 1.5, 10.0);
+            bus.publish(candle).await.unwrap();
+        }
+        let status = BusMessage {
+            message_type: MessageType::SystemStatus,
+            topic: "system.status".to_string(),
+            content: "ping".to_string(),
+            timestamp: chrono::Utc::now(),
+        };
+        assert_eq!(runtime.dispatch(&status), 0);
+        tokio::time::sleep(Duration::from_millis(50)).await;
+
+        assert_eq!(messages.load(Ordering::SeqCst), 1);
+        runtime.stop_all().await.unwrap();
+    }
+}
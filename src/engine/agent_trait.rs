@@ -3,9 +3,13 @@
 //! This module defines the core Agent trait that all trading agents must implement.
 
 use std::collections::HashMap;
+use std::sync::Arc;
 use serde::{Deserialize, Serialize};
 use anyhow::Result;
 use async_trait::async_trait;
+use tokio::sync::RwLock;
+
+use crate::engine::message_bus::BusMessage;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AgentContext {
@@ -129,71 +133,44 @@ impl Default for AgentPerformance {
     }
 }
 
+/// Configuration an agent exposes for inspection
+pub trait AgentConfig: std::fmt::Debug + Send + Sync {}
+
+impl<T: std::fmt::Debug + Send + Sync> AgentConfig for T {}
+
 /// Core trait that all trading agents must implement
+///
+/// Agents are message-driven: each one is owned by its own task, which calls
+/// `handle_message` for every message routed to it and `tick` on a fixed
+/// interval, so an agent never has to be shared behind a lock.
 #[async_trait]
-pub trait Agent: Send + Sync {
-    /// Get the agent's unique identifier
-    fn get_id(&self) -> String;
-
+pub trait Agent: Send {
     /// Get the agent's name
-    fn get_name(&self) -> String;
-
-    /// Get the agent's description
-    fn get_description(&self) -> String;
-
-    /// Initialize the agent with context
-    async fn initialize(&mut self, context: &mut AgentContext) -> Result<()>;
-
-    /// Process market data and make trading decisions
-    async fn process_market_data(
-        &mut self,
-        context: &mut AgentContext,
-        market_data: &HashMap<String, f64>,
-    ) -> Result<Vec<AgentDecision>>;
+    fn get_name(&self) -> &str;
 
-    /// Update the agent with trade execution results
-    async fn update_trade_result(
-        &mut self,
-        context: &mut AgentContext,
-        decision: &AgentDecision,
-        executed_price: f64,
-        executed_quantity: f64,
-        profit_loss: f64,
-    ) -> Result<()>;
-
-    /// Get the agent's current performance metrics
-    async fn get_performance(&self) -> Result<AgentPerformance>;
-
-    /// Check if the agent is ready to trade
-    async fn is_ready(&self) -> bool;
-
-    /// Get the agent's risk tolerance (0.0 to 1.0)
-    async fn get_risk_tolerance(&self) -> f64;
-
-    /// Get the agent's preferred trading symbols
-    async fn get_preferred_symbols(&self) -> Vec<String>;
+    /// Get agent-specific configuration
+    fn get_config(&self) -> Box<dyn AgentConfig>;
 
-    /// Shutdown the agent gracefully
-    async fn shutdown(&mut self) -> Result<()>;
+    /// Topic patterns the agent receives (e.g. `market.*.candle.1m`, `risk.alert.*`);
+    /// an agent without any only receives messages sent to it by name
+    fn topics(&self) -> Vec<String> {
+        Vec::new()
+    }
 
-    /// Get agent-specific configuration
-    async fn get_config(&self) -> Result<HashMap<String, String>>;
+    /// Initialize the agent with the shared context
+    async fn initialize(&mut self, context: Arc<RwLock<AgentContext>>) -> Result<()>;
 
-    /// Update agent configuration
-    async fn update_config(&mut self, config: HashMap<String, String>) -> Result<()>;
+    /// Start the agent
+    async fn start(&mut self) -> Result<()>;
 
-    /// Validate a trading decision before execution
-    async fn validate_decision(
-        &self,
-        context: &AgentContext,
-        decision: &AgentDecision,
-    ) -> Result<bool>;
+    /// Stop the agent gracefully
+    async fn stop(&mut self) -> Result<()>;
 
-    /// Get the agent's current status
-    async fn get_status(&self) -> String;
+    /// Periodic work (scans, evaluations, housekeeping)
+    async fn tick(&mut self) -> Result<()>;
 
-    /// Handle emergency stop signal
-    async fn emergency_stop(&mut self) -> Result<()>;
+    /// Handle a message routed to the agent
+    async fn handle_message(&mut self, message: BusMessage) -> Result<()>;
 }
 
 /// Utility functions for agent implementations
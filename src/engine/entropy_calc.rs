@@ -0,0 +1,163 @@
+//! Entropy Calculator
+//!
+//! This module measures how disordered a price series is (normalized Shannon
+//! entropy of returns) together with its realized volatility. The resulting
+//! `VolatilityProfile` is used to classify market regimes.
+
+use serde::{Deserialize, Serialize};
+
+/// Coarse entropy level
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum EntropyLevel {
+    /// Ordered, trending or quiet market
+    Low,
+
+    /// Typical market noise
+    Medium,
+
+    /// Disordered, choppy market
+    High,
+}
+
+/// Volatility and entropy profile of a price series
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VolatilityProfile {
+    /// Realized volatility per bar in %
+    pub realized_volatility: f64,
+
+    /// Realized volatility annualized with the configured bars per year, in %
+    pub annualized_volatility: f64,
+
+    /// Normalized Shannon entropy of returns (0.0-1.0)
+    pub entropy: f64,
+
+    /// Entropy level
+    pub entropy_level: EntropyLevel,
+
+    /// Number of returns used
+    pub sample_size: usize,
+}
+
+/// Entropy calculator
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EntropyCalculator {
+    /// Number of most recent returns to use
+    window: usize,
+
+    /// Bars per year used for annualization
+    bars_per_year: f64,
+
+    /// Entropy below which the level is `Low`
+    low_threshold: f64,
+
+    /// Entropy above which the level is `High`
+    high_threshold: f64,
+}
+
+impl EntropyCalculator {
+    /// Create a new entropy calculator
+    pub fn new(window: usize, bars_per_year: f64) -> Self {
+        Self {
+            window: window.max(2),
+            bars_per_year,
+            low_threshold: 0.6,
+            high_threshold: 0.85,
+        }
+    }
+
+    /// Set the entropy level thresholds
+    pub fn with_thresholds(mut self, low_threshold: f64, high_threshold: f64) -> Self {
+        self.low_threshold = low_threshold;
+        self.high_threshold = high_threshold;
+        self
+    }
+
+    /// Log returns of a price series
+    pub fn log_returns(prices: &[f64]) -> Vec<f64> {
+        prices.windows(2)
+            .filter(|w| w[0] > 0.0 && w[1] > 0.0)
+            .map(|w| (w[1] / w[0]).ln())
+            .collect()
+    }
+
+    /// Normalized Shannon entropy (0.0-1.0) of a return series
+    pub fn calculate_entropy(&self, returns: &[f64]) -> f64 {
+        let n = returns.len();
+        if n < 2 {
+            return 0.0;
+        }
+
+        let bin_count = ((n as f64).sqrt().ceil() as usize).max(2);
+        let min_return = returns.iter().copied().fold(f64::MAX, f64::min);
+        let max_return = returns.iter().copied().fold(f64::MIN, f64::max);
+        let range = max_return - min_return;
+
+        if range == 0.0 {
+            return 0.0;
+        }
+
+        let mut bins = vec![0usize; bin_count];
+        for &r in returns {
+            let bin = ((r - min_return) / range * (bin_count - 1) as f64).round() as usize;
+            bins[bin.min(bin_count - 1)] += 1;
+        }
+
+        let entropy: f64 = bins.iter()
+            .filter(|&&count| count > 0)
+            .map(|&count| {
+                let p = count as f64 / n as f64;
+                -p * p.ln()
+            })
+            .sum();
+
+        entropy / (bin_count as f64).ln()
+    }
+
+    /// Realized volatility (standard deviation of returns) in %
+    pub fn realized_volatility(returns: &[f64]) -> f64 {
+        if returns.len() < 2 {
+            return 0.0;
+        }
+
+        let mean = returns.iter().sum::<f64>() / returns.len() as f64;
+        let variance = returns.iter().map(|r| (r - mean).powi(2)).sum::<f64>() / (returns.len() - 1) as f64;
+
+        variance.sqrt() * 100.0
+    }
+
+    /// Classify an entropy value
+    pub fn entropy_level(&self, entropy: f64) -> EntropyLevel {
+        if entropy < self.low_threshold {
+            EntropyLevel::Low
+        } else if entropy > self.high_threshold {
+            EntropyLevel::High
+        } else {
+            EntropyLevel::Medium
+        }
+    }
+
+    /// Volatility profile of the most recent `window` returns of a price series
+    pub fn profile(&self, prices: &[f64]) -> Option<VolatilityProfile> {
+        let returns = Self::log_returns(prices);
+        if returns.len() < self.window {
+            return None;
+        }
+
+        let recent = &returns[returns.len() - self.window..];
+        let realized_volatility = Self::realized_volatility(recent);
+        let entropy = self.calculate_entropy(recent);
+
+        Some(VolatilityProfile {
+            realized_volatility,
+            annualized_volatility: realized_volatility * self.bars_per_year.sqrt(),
+            entropy,
+            entropy_level: self.entropy_level(entropy),
+            sample_size: recent.len(),
+        })
+    }
+
+    /// Get the window size
+    pub fn get_window(&self) -> usize {
+        self.window
+    }
+}
@@ -0,0 +1,53 @@
+//! Atomic JSON Files
+//!
+//! This module persists state kept in a single JSON file. Writes go to a temp
+//! file next to the target that is synced and then renamed over it, so a crash
+//! mid-write leaves the previous file intact.
+
+use std::fs;
+use std::path::Path;
+use anyhow::Result;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+/// Write `value` as JSON to `path`, creating missing parent directories
+pub fn save_json<T: Serialize>(path: impl AsRef<Path>, value: &T) -> Result<()> {
+    let path = path.as_ref();
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        fs::create_dir_all(parent)?;
+    }
+
+    let tmp = path.with_extension("tmp");
+    fs::write(&tmp, serde_json::to_vec_pretty(value)?)?;
+    fs::File::open(&tmp)?.sync_all()?;
+    fs::rename(&tmp, path)?;
+    Ok(())
+}
+
+/// Read a JSON file written by `save_json`; `None` if it does not exist
+pub fn load_json<T: DeserializeOwned>(path: impl AsRef<Path>) -> Result<Option<T>> {
+    let path = path.as_ref();
+    if !path.exists() {
+        return Ok(None);
+    }
+    Ok(Some(serde_json::from_slice(&fs::read(path)?)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_round_trip_and_missing_file() {
+        let dir = std::env::temp_dir().join(format!("omni_json_store_{}", std::process::id()));
+        let path = dir.join("state.json");
+        assert!(load_json::<HashMap<String, f64>>(&path).unwrap().is_none());
+
+        let value = HashMap::from([("equity".to_string(), 1000.0)]);
+        save_json(&path, &value).unwrap();
+        assert_eq!(load_json::<HashMap<String, f64>>(&path).unwrap(), Some(value));
+        assert!(!path.with_extension("tmp").exists());
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
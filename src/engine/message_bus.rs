@@ -1,12 +1,42 @@
 //! Message Bus Module for OMNI Trading System
 //!
 //! This module provides inter-agent communication and message routing capabilities.
+//! Every message carries a hierarchical topic (dot separated, e.g.
+//! `market.BTCUSDT.candle.1m`). Agents subscribe to topic patterns, where `*` matches
+//! one segment and a trailing `#` matches any remaining segments (e.g. `risk.alert.*`,
+//! `market.BTCUSDT.#`), and only receive the messages their patterns match.
 
 use std::collections::{HashMap, VecDeque};
 use std::sync::{Arc, Mutex};
 use serde::{Deserialize, Serialize};
 use anyhow::Result;
-use tokio::sync::broadcast;
+use tokio::sync::mpsc;
+use tracing::warn;
+
+/// Messages buffered per subscription before new ones are dropped
+const SUBSCRIPTION_CAPACITY: usize = 1000;
+
+/// Topic segment from free text (dots would split it)
+fn topic_segment(value: &str) -> String {
+    value.replace('.', "_")
+}
+
+/// Whether `topic` matches `pattern`; `*` matches one segment, a trailing `#` any remaining ones
+pub fn topic_matches(pattern: &str, topic: &str) -> bool {
+    let mut topic_segments = topic.split('.');
+    let mut pattern_segments = pattern.split('.').peekable();
+
+    while let Some(pattern_segment) = pattern_segments.next() {
+        if pattern_segment == "#" && pattern_segments.peek().is_none() {
+            return true;
+        }
+        match topic_segments.next() {
+            Some(topic_segment) if pattern_segment == "*" || pattern_segment == topic_segment => {},
+            _ => return false,
+        }
+    }
+    topic_segments.next().is_none()
+}
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum TradeDirection {
@@ -26,10 +56,26 @@ pub enum MessageType {
     EmergencyStop,
 }
 
+impl MessageType {
+    /// Pattern matching every topic of this message type
+    pub fn topic_pattern(&self) -> &'static str {
+        match self {
+            MessageType::MarketData => "market.#",
+            MessageType::TradeSignal => "signal.#",
+            MessageType::RiskAlert => "risk.#",
+            MessageType::PerformanceUpdate => "performance.#",
+            MessageType::SystemStatus => "system.status",
+            MessageType::AgentCommunication => "agent.#",
+            MessageType::EmergencyStop => "system.emergency_stop",
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Message {
     pub id: String,
     pub message_type: MessageType,
+    pub topic: String, // e.g. market.BTCUSDT.candle.1m
     pub sender: String,
     pub recipient: Option<String>, // None for broadcast
     pub payload: HashMap<String, String>,
@@ -67,9 +113,22 @@ impl Message {
             MessageType::AgentCommunication => 6,
         };
 
+        let symbol = payload.get("symbol").map(|s| topic_segment(s));
+        let topic = match (&message_type, symbol) {
+            (MessageType::MarketData, Some(symbol)) => format!("market.{}", symbol),
+            (MessageType::TradeSignal, Some(symbol)) => format!("signal.{}", symbol),
+            (MessageType::RiskAlert, _) => match payload.get("alert_type") {
+                Some(alert_type) => format!("risk.alert.{}", topic_segment(alert_type)),
+                None => "risk.alert".to_string(),
+            },
+            (MessageType::AgentCommunication, _) => format!("agent.{}", topic_segment(&sender)),
+            (message_type, _) => message_type.topic_pattern().trim_end_matches(".#").to_string(),
+        };
+
         Self {
             id,
             message_type,
+            topic,
             sender,
             recipient,
             payload,
@@ -84,12 +143,43 @@ impl Message {
         price: f64,
         volume: f64,
     ) -> Self {
+        let symbol_topic = symbol.clone();
         let mut payload = HashMap::new();
         payload.insert("symbol".to_string(), symbol);
         payload.insert("price".to_string(), price.to_string());
         payload.insert("volume".to_string(), volume.to_string());
 
         Self::new(MessageType::MarketData, sender, None, payload)
+            .with_topic(format!("market.{}.ticker", topic_segment(&symbol_topic)))
+    }
+
+    pub fn create_candle_message(
+        sender: String,
+        symbol: String,
+        interval: String,
+        open: f64,
+        high: f64,
+        low: f64,
+        close: f64,
+        volume: f64,
+    ) -> Self {
+        let topic = format!("market.{}.candle.{}", topic_segment(&symbol), topic_segment(&interval));
+        let mut payload = HashMap::new();
+        payload.insert("symbol".to_string(), symbol);
+        payload.insert("interval".to_string(), interval);
+        payload.insert("open".to_string(), open.to_string());
+        payload.insert("high".to_string(), high.to_string());
+        payload.insert("low".to_string(), low.to_string());
+        payload.insert("close".to_string(), close.to_string());
+        payload.insert("volume".to_string(), volume.to_string());
+
+        Self::new(MessageType::MarketData, sender, None, payload).with_topic(topic)
+    }
+
+    /// Publish under `topic` instead of the default topic of the message type
+    pub fn with_topic(mut self, topic: impl Into<String>) -> Self {
+        self.topic = topic.into();
+        self
     }
 
     pub fn create_trade_signal_message(
@@ -131,23 +221,36 @@ impl Message {
     }
 }
 
+#[derive(Debug, Clone)]
+struct Subscription {
+    agent_id: String,
+    patterns: Vec<String>,
+    sender: mpsc::Sender<Message>,
+}
+
+impl Subscription {
+    /// Messages addressed to the agent are delivered whatever their topic; broadcasts by pattern
+    fn matches(&self, message: &Message) -> bool {
+        match &message.recipient {
+            Some(recipient) => recipient == &self.agent_id,
+            None => self.patterns.iter().any(|pattern| topic_matches(pattern, &message.topic)),
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct MessageBus {
     message_queue: Arc<Mutex<VecDeque<Message>>>,
-    subscribers: Arc<Mutex<HashMap<String, Vec<String>>>>, // message_type -> agent_ids
-    broadcast_sender: broadcast::Sender<Message>,
+    subscriptions: Arc<Mutex<Vec<Subscription>>>,
     message_history: Arc<Mutex<VecDeque<Message>>>,
     max_history_size: usize,
 }
 
 impl MessageBus {
     pub fn new() -> Self {
-        let (broadcast_sender, _) = broadcast::channel(1000);
-        
         Self {
             message_queue: Arc::new(Mutex::new(VecDeque::new())),
-            subscribers: Arc::new(Mutex::new(HashMap::new())),
-            broadcast_sender,
+            subscriptions: Arc::new(Mutex::new(Vec::new())),
             message_history: Arc::new(Mutex::new(VecDeque::new())),
             max_history_size: 10000,
         }
@@ -176,29 +279,43 @@ impl MessageBus {
             }
         }
 
-        // Broadcast to all subscribers
-        let _ = self.broadcast_sender.send(message);
+        // Deliver to the subscriptions whose patterns match the topic
+        {
+            let mut subscriptions = self.subscriptions.lock().unwrap();
+            subscriptions.retain(|subscription| !subscription.sender.is_closed());
+
+            for subscription in subscriptions.iter().filter(|s| s.matches(&message)) {
+                if subscription.sender.try_send(message.clone()).is_err() {
+                    warn!("Subscription of {} is full, dropping message on {}", subscription.agent_id, message.topic);
+                }
+            }
+        }
 
         Ok(())
     }
 
-    pub async fn subscribe(&self, agent_id: String, message_types: Vec<MessageType>) -> Result<broadcast::Receiver<Message>> {
-        let mut subscribers = self.subscribers.lock().unwrap();
-        
-        for message_type in message_types {
-            let type_key = format!("{:?}", message_type);
-            subscribers.entry(type_key).or_insert_with(Vec::new).push(agent_id.clone());
+    /// Subscribe to every topic of the given message types
+    pub async fn subscribe(&self, agent_id: String, message_types: Vec<MessageType>) -> Result<mpsc::Receiver<Message>> {
+        let patterns = message_types.iter().map(|t| t.topic_pattern().to_string()).collect();
+        self.subscribe_topics(agent_id, patterns).await
+    }
+
+    /// Subscribe to topic patterns (e.g. `market.BTCUSDT.candle.*`, `risk.alert.*`)
+    pub async fn subscribe_topics(&self, agent_id: String, patterns: Vec<String>) -> Result<mpsc::Receiver<Message>> {
+        if let Some(pattern) = patterns.iter().find(|p| p.is_empty() || p.split('.').any(str::is_empty)) {
+            return Err(anyhow::anyhow!("Invalid topic pattern '{}'", pattern));
         }
 
-        Ok(self.broadcast_sender.subscribe())
+        let (sender, receiver) = mpsc::channel(SUBSCRIPTION_CAPACITY);
+        let mut subscriptions = self.subscriptions.lock().unwrap();
+        subscriptions.push(Subscription { agent_id, patterns, sender });
+
+        Ok(receiver)
     }
 
     pub async fn unsubscribe(&self, agent_id: String) -> Result<()> {
-        let mut subscribers = self.subscribers.lock().unwrap();
-        
-        for (_, agent_list) in subscribers.iter_mut() {
-            agent_list.retain(|id| id != &agent_id);
-        }
+        let mut subscriptions = self.subscriptions.lock().unwrap();
+        subscriptions.retain(|subscription| subscription.agent_id != agent_id);
 
         Ok(())
     }
@@ -208,17 +325,37 @@ impl MessageBus {
         queue.pop_front()
     }
 
+    /// Queued messages for an agent: everything addressed to it, plus broadcasts on its
+    /// topic patterns once it has subscribed
     pub async fn get_messages_for_agent(&self, agent_id: &str) -> Vec<Message> {
+        let (subscribed, patterns): (bool, Vec<String>) = {
+            let subscriptions = self.subscriptions.lock().unwrap();
+            let own: Vec<&Subscription> = subscriptions.iter()
+                .filter(|subscription| subscription.agent_id == agent_id)
+                .collect();
+            (!own.is_empty(), own.iter().flat_map(|subscription| subscription.patterns.iter().cloned()).collect())
+        };
         let queue = self.message_queue.lock().unwrap();
         
         queue.iter()
-            .filter(|msg| {
-                msg.recipient.as_ref().map_or(true, |recipient| recipient == agent_id)
+            .filter(|msg| match &msg.recipient {
+                Some(recipient) => recipient == agent_id,
+                None => !subscribed || patterns.iter().any(|pattern| topic_matches(pattern, &msg.topic)),
             })
             .cloned()
             .collect()
     }
 
+    /// Queued messages whose topic matches `pattern`
+    pub async fn get_messages_for_topic(&self, pattern: &str) -> Vec<Message> {
+        let queue = self.message_queue.lock().unwrap();
+
+        queue.iter()
+            .filter(|msg| topic_matches(pattern, &msg.topic))
+            .cloned()
+            .collect()
+    }
+
     pub async fn get_message_history(&self, limit: Option<usize>) -> Vec<Message> {
         let history = self.message_history.lock().unwrap();
         
@@ -242,8 +379,8 @@ impl MessageBus {
     }
 
     pub async fn get_subscribers_count(&self) -> usize {
-        let subscribers = self.subscribers.lock().unwrap();
-        subscribers.values().map(|v| v.len()).sum()
+        let subscriptions = self.subscriptions.lock().unwrap();
+        subscriptions.iter().filter(|subscription| !subscription.sender.is_closed()).count()
     }
 
     pub async fn broadcast_emergency_stop(&self, reason: String) -> Result<()> {
@@ -298,3 +435,54 @@ impl Default for MessageBus {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_topic_patterns() {
+        assert!(topic_matches("market.BTCUSDT.candle.1m", "market.BTCUSDT.candle.1m"));
+        assert!(topic_matches("market.*.candle.1m", "market.ETHUSDT.candle.1m"));
+        assert!(topic_matches("risk.alert.*", "risk.alert.drawdown"));
+        assert!(!topic_matches("risk.alert.*", "risk.alert"));
+        assert!(!topic_matches("risk.alert.*", "risk.alert.drawdown.daily"));
+        assert!(topic_matches("market.BTCUSDT.#", "market.BTCUSDT.candle.5m"));
+        assert!(topic_matches("market.#", "market"));
+        assert!(!topic_matches("market.BTCUSDT.#", "market.ETHUSDT.ticker"));
+    }
+
+    #[tokio::test]
+    async fn test_subscribers_only_receive_matching_topics() {
+        let bus = MessageBus::new();
+        let mut candles = bus.subscribe_topics("scalper".to_string(), vec!["market.BTCUSDT.candle.*".to_string()]).await.unwrap();
+        let mut alerts = bus.subscribe("risk_monitor".to_string(), vec![MessageType::RiskAlert]).await.unwrap();
+        assert!(bus.subscribe_topics("bad".to_string(), vec!["risk..alert".to_string()]).await.is_err());
+
+        bus.publish(Message::create_candle_message(
+            "feed".to_string(), "BTCUSDT".to_string(), "1m".to_string(), 1.0, 2.0, 0.5, 1.5, 10.0,
+        )).await.unwrap();
+        bus.broadcast_market_data("BTCUSDT".to_string(), 1.5, 10.0).await.unwrap();
+        bus.send_risk_alert("risk".to_string(), "drawdown".to_string(), "5% drawdown".to_string(), 2).await.unwrap();
+
+        assert_eq!(candles.try_recv().unwrap().topic, "market.BTCUSDT.candle.1m");
+        assert!(candles.try_recv().is_err());
+        assert_eq!(alerts.try_recv().unwrap().topic, "risk.alert.drawdown");
+        assert!(alerts.try_recv().is_err());
+
+        assert_eq!(bus.get_messages_for_agent("scalper").await.len(), 1);
+        assert_eq!(bus.get_messages_for_topic("market.#").await.len(), 2);
+
+        // Messages addressed to an agent reach it whatever their topic, even with no patterns
+        let mut silent = bus.subscribe_topics("silent".to_string(), Vec::new()).await.unwrap();
+        let mut payload = HashMap::new();
+        payload.insert("action".to_string(), "pause".to_string());
+        bus.publish(Message::new(MessageType::AgentCommunication, "kernel".to_string(), Some("silent".to_string()), payload)).await.unwrap();
+        assert_eq!(silent.try_recv().unwrap().recipient.as_deref(), Some("silent"));
+        assert!(candles.try_recv().is_err());
+        assert_eq!(bus.get_messages_for_agent("silent").await.len(), 1);
+
+        drop(candles);
+        assert_eq!(bus.get_subscribers_count().await, 2);
+    }
+}
@@ -0,0 +1,436 @@
+//! Message Log and Replay
+//!
+//! This module records every message routed to the agents, in order, to durable
+//! storage and replays a recorded stretch of traffic into a single agent in
+//! isolation. Replay hands the agent exactly the messages it saw live, with ticks
+//! injected at fixed points in the sequence instead of on a wall-clock timer, so a
+//! bad decision can be reproduced and stepped through: replay up to the message
+//! before it, inspect the agent, then feed the next one. Agents that read the clock
+//! or an unseeded RNG inside their handlers are only as deterministic as those
+//! inputs. Records go to an append-only JSON-lines file by default, or to SQLite
+//! with the `sqlite` feature.
+
+use std::fs;
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tokio::sync::RwLock;
+use tracing::{debug, info, warn};
+
+use crate::engine::agent_trait::{Agent, AgentContext};
+use crate::engine::message_bus::{BusMessage, MessageType};
+
+/// A message as it was routed to the agents
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecordedMessage {
+    /// Position in the recorded traffic, starting at 1
+    pub sequence: u64,
+
+    /// Time the message was routed
+    pub recorded_at: DateTime<Utc>,
+
+    /// Message type
+    pub message_type: MessageType,
+
+    /// Topic the message was published on; empty in logs recorded before topics were kept
+    #[serde(default)]
+    pub topic: String,
+
+    /// Message content
+    pub content: String,
+
+    /// Timestamp the message carried
+    pub timestamp: DateTime<Utc>,
+}
+
+impl RecordedMessage {
+    /// Rebuild the bus message
+    pub fn to_bus_message(&self) -> BusMessage {
+        BusMessage {
+            message_type: self.message_type.clone(),
+            topic: self.topic.clone(),
+            content: self.content.clone(),
+            timestamp: self.timestamp,
+        }
+    }
+}
+
+/// Durable, append-only storage for recorded messages
+pub trait MessageStore: Send {
+    /// Append a message
+    fn append(&mut self, message: &RecordedMessage) -> Result<()>;
+
+    /// Load the messages with a sequence in `from..=to` (to the end without `to`)
+    fn load(&self, from: u64, to: Option<u64>) -> Result<Vec<RecordedMessage>>;
+
+    /// Sequence of the last stored message, if any
+    fn last_sequence(&self) -> Result<Option<u64>>;
+}
+
+/// JSON-lines file store; one message per line, appended as it is recorded
+pub struct JsonLinesMessageStore {
+    /// Log file
+    path: PathBuf,
+
+    /// Log file opened for appending, once the first message is written
+    file: Option<fs::File>,
+}
+
+impl JsonLinesMessageStore {
+    /// Create a store at `path`
+    pub fn new(path: impl AsRef<Path>) -> Self {
+        Self { path: path.as_ref().to_path_buf(), file: None }
+    }
+
+    /// Read every well-formed line; a line torn by a crash mid-write is skipped
+    fn read_all(&self) -> Result<Vec<RecordedMessage>> {
+        if !self.path.exists() {
+            return Ok(Vec::new());
+        }
+
+        let mut messages = Vec::new();
+        for (number, line) in BufReader::new(fs::File::open(&self.path)?).lines().enumerate() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            match serde_json::from_str(&line) {
+                Ok(message) => messages.push(message),
+                Err(e) => warn!("Skipping unreadable message log line {} in {}: {}", number + 1, self.path.display(), e),
+            }
+        }
+        Ok(messages)
+    }
+}
+
+impl MessageStore for JsonLinesMessageStore {
+    fn append(&mut self, message: &RecordedMessage) -> Result<()> {
+        if self.file.is_none() {
+            if let Some(parent) = self.path.parent().filter(|p| !p.as_os_str().is_empty()) {
+                fs::create_dir_all(parent)?;
+            }
+            self.file = Some(fs::OpenOptions::new().create(true).append(true).open(&self.path)?);
+        }
+
+        let mut line = serde_json::to_vec(message)?;
+        line.push(b'\n');
+        if let Some(file) = self.file.as_mut() {
+            file.write_all(&line)?;
+        }
+        Ok(())
+    }
+
+    fn load(&self, from: u64, to: Option<u64>) -> Result<Vec<RecordedMessage>> {
+        Ok(self.read_all()?
+            .into_iter()
+            .filter(|m| m.sequence >= from && to.map_or(true, |to| m.sequence <= to))
+            .collect())
+    }
+
+    fn last_sequence(&self) -> Result<Option<u64>> {
+        Ok(self.read_all()?.iter().map(|m| m.sequence).max())
+    }
+}
+
+/// SQLite store keeping one row per message
+#[cfg(feature = "sqlite")]
+pub struct SqliteMessageStore {
+    /// Connection
+    connection: rusqlite::Connection,
+}
+
+#[cfg(feature = "sqlite")]
+impl SqliteMessageStore {
+    /// Open (or create) a store at `path`
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let connection = rusqlite::Connection::open(path)?;
+        connection.execute_batch(
+            "PRAGMA journal_mode = WAL;
+             CREATE TABLE IF NOT EXISTS bus_messages (
+                sequence INTEGER PRIMARY KEY,
+                recorded_at TEXT NOT NULL,
+                message TEXT NOT NULL
+            );",
+        )?;
+        Ok(Self { connection })
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl MessageStore for SqliteMessageStore {
+    fn append(&mut self, message: &RecordedMessage) -> Result<()> {
+        self.connection.execute(
+            "INSERT INTO bus_messages VALUES (?1, ?2, ?3)",
+            rusqlite::params![message.sequence as i64, message.recorded_at.to_rfc3339(), serde_json::to_string(message)?],
+        )?;
+        Ok(())
+    }
+
+    fn load(&self, from: u64, to: Option<u64>) -> Result<Vec<RecordedMessage>> {
+        let to = to.map_or(i64::MAX, |to| to.min(i64::MAX as u64) as i64);
+        let mut statement = self.connection.prepare(
+            "SELECT message FROM bus_messages WHERE sequence >= ?1 AND sequence <= ?2 ORDER BY sequence",
+        )?;
+        let rows = statement.query_map(rusqlite::params![from as i64, to], |row| row.get::<_, String>(0))?;
+
+        let mut messages = Vec::new();
+        for json in rows {
+            messages.push(serde_json::from_str(&json?)?);
+        }
+        Ok(messages)
+    }
+
+    fn last_sequence(&self) -> Result<Option<u64>> {
+        let last: Option<i64> = self.connection
+            .query_row("SELECT MAX(sequence) FROM bus_messages", [], |row| row.get(0))?;
+        Ok(last.map(|sequence| sequence as u64))
+    }
+}
+
+/// Records routed messages to a store, numbering them in order
+pub struct MessageRecorder {
+    /// Storage backend
+    store: Box<dyn MessageStore>,
+
+    /// Sequence of the next message
+    next_sequence: u64,
+}
+
+impl MessageRecorder {
+    /// Create a recorder that continues the sequence already in `store`
+    pub fn new(store: Box<dyn MessageStore>) -> Result<Self> {
+        let next_sequence = store.last_sequence()?.map_or(1, |last| last + 1);
+        Ok(Self { store, next_sequence })
+    }
+
+    /// Record a message; returns its sequence
+    pub fn record(&mut self, message: &BusMessage) -> Result<u64> {
+        let recorded = RecordedMessage {
+            sequence: self.next_sequence,
+            recorded_at: Utc::now(),
+            message_type: message.message_type.clone(),
+            topic: message.topic.clone(),
+            content: message.content.clone(),
+            timestamp: message.timestamp,
+        };
+        self.store.append(&recorded)?;
+        self.next_sequence += 1;
+        Ok(recorded.sequence)
+    }
+
+    /// Load recorded messages with a sequence in `from..=to`
+    pub fn load(&self, from: u64, to: Option<u64>) -> Result<Vec<RecordedMessage>> {
+        self.store.load(from, to)
+    }
+
+    /// Sequence the next recorded message will get
+    pub fn get_next_sequence(&self) -> u64 {
+        self.next_sequence
+    }
+}
+
+/// Which recorded messages to replay and when to tick
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ReplayOptions {
+    /// First sequence to replay
+    pub from_sequence: u64,
+
+    /// Last sequence to replay (to the end of the log if unset)
+    pub to_sequence: Option<u64>,
+
+    /// Only replay these message types (debug names, e.g. "TradeEntry"); all if empty
+    pub message_types: Vec<String>,
+
+    /// Tick the agent after every this many replayed messages (never if 0)
+    pub tick_every: usize,
+
+    /// Stop the agent once the replay is done; leave it running to keep stepping
+    pub stop_after: bool,
+}
+
+/// Outcome of a replay
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ReplayReport {
+    /// Messages handed to the agent
+    pub messages_replayed: usize,
+
+    /// Ticks injected
+    pub ticks: usize,
+
+    /// Sequence of the last replayed message
+    pub last_sequence: Option<u64>,
+
+    /// Handler and tick failures by the sequence they followed
+    pub errors: Vec<(u64, String)>,
+}
+
+/// Feeds recorded traffic back into one agent, outside the runtime
+pub struct ReplayDriver {
+    /// Options
+    options: ReplayOptions,
+}
+
+impl ReplayDriver {
+    /// Create a new replay driver
+    pub fn new(options: ReplayOptions) -> Self {
+        Self { options }
+    }
+
+    /// Initialize and start a fresh agent in a context of its own, then replay into it
+    pub async fn run(&self, agent: &mut dyn Agent, messages: &[RecordedMessage]) -> Result<ReplayReport> {
+        let context = Arc::new(RwLock::new(AgentContext::new(agent.get_name().to_string())));
+        agent.initialize(context).await?;
+        agent.start().await?;
+        self.replay(agent, messages).await
+    }
+
+    /// Replay the selected messages into an agent that is already started
+    ///
+    /// Call again with a later range to keep stepping the same agent forward.
+    pub async fn replay(&self, agent: &mut dyn Agent, messages: &[RecordedMessage]) -> Result<ReplayReport> {
+        let mut selected: Vec<&RecordedMessage> = messages.iter().filter(|m| self.selects(m)).collect();
+        selected.sort_by_key(|m| m.sequence);
+
+        info!("Replaying {} messages into {}", selected.len(), agent.get_name());
+
+        let mut report = ReplayReport::default();
+        for message in selected {
+            if let Err(e) = agent.handle_message(message.to_bus_message()).await {
+                debug!("Replayed message {} failed in {}: {}", message.sequence, agent.get_name(), e);
+                report.errors.push((message.sequence, e.to_string()));
+            }
+            report.messages_replayed += 1;
+            report.last_sequence = Some(message.sequence);
+
+            if self.options.tick_every > 0 && report.messages_replayed % self.options.tick_every == 0 {
+                if let Err(e) = agent.tick().await {
+                    report.errors.push((message.sequence, e.to_string()));
+                }
+                report.ticks += 1;
+            }
+        }
+
+        if self.options.stop_after {
+            agent.stop().await?;
+        }
+
+        Ok(report)
+    }
+
+    /// Whether a recorded message falls within the options
+    fn selects(&self, message: &RecordedMessage) -> bool {
+        message.sequence >= self.options.from_sequence
+            && self.options.to_sequence.map_or(true, |to| message.sequence <= to)
+            && (self.options.message_types.is_empty()
+                || self.options.message_types.contains(&format!("{:?}", message.message_type)))
+    }
+
+    /// Get the options
+    pub fn get_options(&self) -> &ReplayOptions {
+        &self.options
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use crate::engine::agent_trait::AgentConfig;
+
+    /// Keeps the contents it handled and a running sum, like an agent's state
+    #[derive(Default)]
+    struct SummingAgent {
+        seen: Vec<String>,
+        total: i64,
+        ticks: usize,
+    }
+
+    #[async_trait]
+    impl Agent for SummingAgent {
+        fn get_name(&self) -> &str {
+            "summer"
+        }
+
+        fn get_config(&self) -> Box<dyn AgentConfig> {
+            Box::new(())
+        }
+
+        async fn initialize(&mut self, _context: Arc<RwLock<AgentContext>>) -> Result<()> {
+            Ok(())
+        }
+
+        async fn start(&mut self) -> Result<()> {
+            Ok(())
+        }
+
+        async fn stop(&mut self) -> Result<()> {
+            Ok(())
+        }
+
+        async fn tick(&mut self) -> Result<()> {
+            self.ticks += 1;
+            Ok(())
+        }
+
+        async fn handle_message(&mut self, message: BusMessage) -> Result<()> {
+            self.seen.push(message.content.clone());
+            self.total += message.content.parse::<i64>()?;
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_recorded_traffic_replays_deterministically() {
+        let path = std::env::temp_dir().join(format!("omni_messages_{}.jsonl", std::process::id()));
+        let _ = fs::remove_file(&path);
+
+        let mut recorder = MessageRecorder::new(Box::new(JsonLinesMessageStore::new(&path))).unwrap();
+        for content in ["1", "2", "oops", "4"] {
+            recorder.record(&BusMessage {
+                message_type: MessageType::SystemStatus,
+                topic: "system.status".to_string(),
+                content: content.to_string(),
+                timestamp: Utc::now(),
+            }).unwrap();
+        }
+
+        // A recorder reopened on the same log continues the sequence
+        let mut recorder = MessageRecorder::new(Box::new(JsonLinesMessageStore::new(&path))).unwrap();
+        assert_eq!(recorder.get_next_sequence(), 5);
+        recorder.record(&BusMessage {
+            message_type: MessageType::SystemStatus,
+            topic: "system.status".to_string(),
+            content: "5".to_string(),
+            timestamp: Utc::now(),
+        }).unwrap();
+
+        let messages = recorder.load(1, None).unwrap();
+        assert_eq!(messages.len(), 5);
+
+        // Replaying up to the bad message reproduces the state just before it
+        let driver = ReplayDriver::new(ReplayOptions { to_sequence: Some(2), tick_every: 2, ..Default::default() });
+        let mut agent = SummingAgent::default();
+        let report = driver.run(&mut agent, &messages).await.unwrap();
+        assert_eq!(report.messages_replayed, 2);
+        assert_eq!(report.ticks, 1);
+        assert_eq!(agent.total, 3);
+
+        // Stepping on surfaces the failure at its sequence; a second run is identical
+        let step = ReplayDriver::new(ReplayOptions { from_sequence: 3, ..Default::default() });
+        let report = step.replay(&mut agent, &messages).await.unwrap();
+        assert_eq!(report.errors.len(), 1);
+        assert_eq!(report.errors[0].0, 3);
+        assert_eq!(agent.total, 12);
+
+        let mut again = SummingAgent::default();
+        ReplayDriver::new(ReplayOptions::default()).run(&mut again, &messages).await.unwrap();
+        assert_eq!(again.seen, agent.seen);
+        assert_eq!(again.total, agent.total);
+
+        let _ = fs::remove_file(&path);
+    }
+}
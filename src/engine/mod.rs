@@ -5,10 +5,23 @@
 
 pub mod message_bus;
 pub mod agent_trait;
+pub mod agent_runtime;
+pub mod message_log;
+pub mod supervisor;
+pub mod shadow_mode;
 pub mod orchestrator;
 pub mod coordinator;
+pub mod entropy_calc;
+pub mod state_machine;
+pub mod json_store;
 
 pub use message_bus::*;
 pub use agent_trait::*;
+pub use agent_runtime::*;
+pub use message_log::*;
+pub use supervisor::*;
+pub use shadow_mode::*;
 pub use orchestrator::*;
 pub use coordinator::*;
+pub use state_machine::*;
+pub use json_store::*;
@@ -0,0 +1,382 @@
+//! Shadow Mode
+//!
+//! This module runs candidate agents in shadow mode. Each candidate is built on
+//! a message bus of its own, so whatever it publishes never reaches the trading
+//! system: it ticks and receives the same live messages as the real agents, but
+//! its trade signals are only recorded as `ShadowDecision`s. Open decisions are
+//! marked against live prices and closed at their stop, their target or after
+//! the holding limit, and the closed ones score the candidate for promotion.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use anyhow::Result;
+use tokio::sync::RwLock;
+use tracing::{debug, info, warn};
+
+use crate::engine::agent_trait::{Agent, AgentContext};
+use crate::engine::message_bus::{BusMessage, Message, MessageBus, TradeDirection};
+use crate::engine::supervisor::AgentFactory;
+
+/// Reader name the shadow runner drains candidate buses with
+const SHADOW_READER: &str = "shadow_runner";
+
+/// Builds a candidate agent publishing on the given bus
+pub type ShadowAgentFactory = Box<dyn Fn(Arc<MessageBus>) -> Box<dyn Agent> + Send + Sync>;
+
+/// Shadow mode settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ShadowConfig {
+    /// Longest a shadow decision stays open (seconds)
+    pub max_holding_secs: i64,
+
+    /// Fee charged per side, as a fraction of notional
+    pub fee_rate: f64,
+}
+
+impl Default for ShadowConfig {
+    fn default() -> Self {
+        Self {
+            max_holding_secs: 3600,
+            fee_rate: 0.00055,
+        }
+    }
+}
+
+/// How a shadow decision closed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ShadowOutcome {
+    /// Target reached
+    TakeProfit,
+
+    /// Stop reached
+    StopLoss,
+
+    /// Closed at the holding limit
+    Expired,
+}
+
+/// Trade signal of a candidate, recorded instead of executed
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ShadowDecision {
+    /// Candidate agent
+    pub agent: String,
+
+    /// Symbol
+    pub symbol: String,
+
+    /// Direction
+    pub direction: TradeDirection,
+
+    /// Signal confidence
+    pub confidence: f64,
+
+    /// Entry price
+    pub entry_price: f64,
+
+    /// Stop price
+    pub stop_loss_price: f64,
+
+    /// Target price
+    pub take_profit_price: f64,
+
+    /// Signal time
+    pub opened_at: DateTime<Utc>,
+
+    /// Close time
+    pub closed_at: Option<DateTime<Utc>>,
+
+    /// Close price
+    pub exit_price: Option<f64>,
+
+    /// Return after fees, as a fraction of entry
+    pub return_pct: Option<f64>,
+
+    /// How the decision closed
+    pub outcome: Option<ShadowOutcome>,
+}
+
+impl ShadowDecision {
+    /// Whether the decision is long
+    fn is_long(&self) -> bool {
+        matches!(self.direction, TradeDirection::Long)
+    }
+
+    /// Close the decision at `price` if its stop, target or holding limit was reached
+    fn mark(&mut self, price: f64, now: DateTime<Utc>, config: &ShadowConfig) -> bool {
+        let is_long = self.is_long();
+        let outcome = if (is_long && price <= self.stop_loss_price) || (!is_long && price >= self.stop_loss_price) {
+            ShadowOutcome::StopLoss
+        } else if (is_long && price >= self.take_profit_price) || (!is_long && price <= self.take_profit_price) {
+            ShadowOutcome::TakeProfit
+        } else if now - self.opened_at >= Duration::seconds(config.max_holding_secs) {
+            ShadowOutcome::Expired
+        } else {
+            return false;
+        };
+
+        let side = if is_long { 1.0 } else { -1.0 };
+        self.return_pct = Some(side * (price - self.entry_price) / self.entry_price - 2.0 * config.fee_rate);
+        self.exit_price = Some(price);
+        self.closed_at = Some(now);
+        self.outcome = Some(outcome);
+        true
+    }
+}
+
+/// Score of a candidate from its closed decisions
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ShadowScore {
+    /// Decisions recorded
+    pub decisions: usize,
+
+    /// Decisions closed
+    pub closed: usize,
+
+    /// Share of closed decisions with a positive return
+    pub win_rate: f64,
+
+    /// Mean return of the closed decisions
+    pub mean_return: f64,
+
+    /// Win rate mapped to (-1.0 to 1.0), the scale promotion thresholds use
+    pub score: f64,
+}
+
+/// Candidate running in shadow mode
+struct ShadowCandidate {
+    /// Agent instance, publishing on `bus`
+    agent: Box<dyn Agent>,
+
+    /// Bus of the candidate alone
+    bus: Arc<MessageBus>,
+
+    /// Builds the candidate again, for promotion onto the live bus
+    factory: Arc<ShadowAgentFactory>,
+
+    /// Decisions recorded, oldest first
+    decisions: Vec<ShadowDecision>,
+}
+
+/// Runs candidate agents in shadow mode and scores their decisions
+pub struct ShadowRunner {
+    /// Settings
+    config: ShadowConfig,
+
+    /// Candidates by name
+    candidates: HashMap<String, ShadowCandidate>,
+}
+
+impl ShadowRunner {
+    /// Create a new shadow runner
+    pub fn new(config: ShadowConfig) -> Self {
+        Self {
+            config,
+            candidates: HashMap::new(),
+        }
+    }
+
+    /// Build a candidate on a bus of its own, then initialize and start it
+    pub async fn add(&mut self, factory: ShadowAgentFactory, context: Arc<RwLock<AgentContext>>) -> Result<String> {
+        let bus = Arc::new(MessageBus::new());
+        let mut agent = factory(Arc::clone(&bus));
+        let name = agent.get_name().to_string();
+        if self.candidates.contains_key(&name) {
+            return Err(anyhow::anyhow!("Agent {} is already in shadow mode", name));
+        }
+
+        agent.initialize(context).await?;
+        agent.start().await?;
+        info!("Agent {} running in shadow mode", name);
+        self.candidates.insert(name.clone(), ShadowCandidate {
+            agent,
+            bus,
+            factory: Arc::new(factory),
+            decisions: Vec::new(),
+        });
+        Ok(name)
+    }
+
+    /// Stop a candidate and return its factory
+    pub async fn remove(&mut self, name: &str) -> Option<Arc<ShadowAgentFactory>> {
+        let mut candidate = self.candidates.remove(name)?;
+        if let Err(e) = candidate.agent.stop().await {
+            warn!("Shadow agent {} failed to stop: {}", name, e);
+        }
+        Some(candidate.factory)
+    }
+
+    /// Hand a live message to every candidate
+    pub async fn dispatch(&mut self, message: &BusMessage) {
+        for (name, candidate) in self.candidates.iter_mut() {
+            if let Err(e) = candidate.agent.handle_message(message.clone()).await {
+                debug!("Shadow agent {} failed to handle message: {}", name, e);
+            }
+        }
+    }
+
+    /// Tick every candidate, record its new signals and mark open decisions against `prices`
+    pub async fn run(&mut self, prices: &HashMap<String, f64>, now: DateTime<Utc>) {
+        for (name, candidate) in self.candidates.iter_mut() {
+            if let Err(e) = candidate.agent.tick().await {
+                debug!("Shadow agent {} tick failed: {}", name, e);
+            }
+
+            // Signals go no further than the candidate's own bus
+            let messages = candidate.bus.get_messages_for_agent(SHADOW_READER).await;
+            candidate.bus.clear_queue().await;
+            for message in messages {
+                if let Message::TradeSignal { symbol, direction, confidence, entry_price, stop_loss_price, take_profit_price, .. } = message {
+                    if entry_price <= 0.0 {
+                        continue;
+                    }
+                    debug!("Shadow agent {} would trade {} {:?} @ {:.4}", name, symbol, direction, entry_price);
+                    candidate.decisions.push(ShadowDecision {
+                        agent: name.clone(),
+                        symbol,
+                        direction,
+                        confidence,
+                        entry_price,
+                        stop_loss_price,
+                        take_profit_price,
+                        opened_at: now,
+                        closed_at: None,
+                        exit_price: None,
+                        return_pct: None,
+                        outcome: None,
+                    });
+                }
+            }
+
+            for decision in candidate.decisions.iter_mut().filter(|d| d.outcome.is_none()) {
+                if let Some(&price) = prices.get(&decision.symbol) {
+                    decision.mark(price, now, &self.config);
+                }
+            }
+        }
+    }
+
+    /// Score of a candidate, once it has closed decisions
+    pub fn score(&self, name: &str) -> Option<ShadowScore> {
+        let candidate = self.candidates.get(name)?;
+        let returns: Vec<f64> = candidate.decisions.iter().filter_map(|d| d.return_pct).collect();
+        if returns.is_empty() {
+            return None;
+        }
+
+        let win_rate = returns.iter().filter(|r| **r > 0.0).count() as f64 / returns.len() as f64;
+        Some(ShadowScore {
+            decisions: candidate.decisions.len(),
+            closed: returns.len(),
+            win_rate,
+            mean_return: returns.iter().sum::<f64>() / returns.len() as f64,
+            score: win_rate * 2.0 - 1.0,
+        })
+    }
+
+    /// Decisions recorded for a candidate
+    pub fn get_decisions(&self, name: &str) -> Vec<ShadowDecision> {
+        self.candidates.get(name).map(|c| c.decisions.clone()).unwrap_or_default()
+    }
+
+    /// Names of the candidates in shadow mode
+    pub fn get_candidates(&self) -> Vec<String> {
+        let mut names: Vec<String> = self.candidates.keys().cloned().collect();
+        names.sort();
+        names
+    }
+}
+
+/// Supervisor factory building a promoted candidate on the live bus
+pub fn live_factory(factory: Arc<ShadowAgentFactory>, message_bus: Arc<MessageBus>) -> AgentFactory {
+    Box::new(move || factory(Arc::clone(&message_bus)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use crate::engine::agent_trait::AgentConfig;
+
+    /// Candidate that goes long BTC on every tick
+    struct AlwaysLong {
+        bus: Arc<MessageBus>,
+    }
+
+    #[async_trait]
+    impl Agent for AlwaysLong {
+        fn get_name(&self) -> &str {
+            "always_long"
+        }
+
+        fn get_config(&self) -> Box<dyn AgentConfig> {
+            Box::new(())
+        }
+
+        async fn initialize(&mut self, _context: Arc<RwLock<AgentContext>>) -> Result<()> {
+            Ok(())
+        }
+
+        async fn start(&mut self) -> Result<()> {
+            Ok(())
+        }
+
+        async fn stop(&mut self) -> Result<()> {
+            Ok(())
+        }
+
+        async fn tick(&mut self) -> Result<()> {
+            self.bus.send(Message::TradeSignal {
+                symbol: "BTCUSDT".to_string(),
+                direction: TradeDirection::Long,
+                confidence: 0.8,
+                entry_price: 100.0,
+                stop_loss_price: 99.0,
+                take_profit_price: 102.0,
+                source: "always_long".to_string(),
+                timestamp: Utc::now(),
+            });
+            Ok(())
+        }
+
+        async fn handle_message(&mut self, _message: BusMessage) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_candidate_signals_are_recorded_and_scored_not_executed() {
+        let live_bus = Arc::new(MessageBus::new());
+        let mut runner = ShadowRunner::new(ShadowConfig::default());
+        let context = Arc::new(RwLock::new(AgentContext::new("always_long".to_string())));
+        let name = runner.add(Box::new(|bus| Box::new(AlwaysLong { bus }) as Box<dyn Agent>), context).await.unwrap();
+
+        let now = Utc::now();
+        runner.run(&HashMap::from([("BTCUSDT".to_string(), 100.5)]), now).await;
+        assert_eq!(runner.get_decisions(&name).len(), 1);
+        assert!(runner.score(&name).is_none());
+        assert!(live_bus.get_messages_for_agent("trading_system").await.is_empty());
+
+        // The signal was read back from the candidate's bus, which is left empty
+        let decision = &runner.get_decisions(&name)[0];
+        assert_eq!(decision.symbol, "BTCUSDT");
+        assert!(matches!(decision.direction, TradeDirection::Long));
+        assert_eq!((decision.entry_price, decision.take_profit_price), (100.0, 102.0));
+        assert_eq!(runner.candidates[&name].bus.get_queue_size().await, 0);
+
+        // The first two decisions reach their target, the third its stop
+        runner.run(&HashMap::from([("BTCUSDT".to_string(), 102.5)]), now).await;
+        runner.run(&HashMap::from([("BTCUSDT".to_string(), 98.0)]), now).await;
+        let outcomes: Vec<_> = runner.get_decisions(&name).iter().map(|d| d.outcome).collect();
+        assert_eq!(outcomes, vec![Some(ShadowOutcome::TakeProfit), Some(ShadowOutcome::TakeProfit), Some(ShadowOutcome::StopLoss)]);
+
+        let score = runner.score(&name).unwrap();
+        assert_eq!((score.decisions, score.closed), (3, 3));
+        assert!((score.win_rate - 2.0 / 3.0).abs() < 1e-9);
+
+        assert!(runner.remove(&name).await.is_some());
+        assert!(runner.get_candidates().is_empty());
+    }
+}
@@ -0,0 +1,179 @@
+//! System State Machine
+//!
+//! This module tracks the trading state of the whole system (Initializing →
+//! Running → Halted / Stopped). Every component that may open risk reads the same
+//! shared state machine, so a halt raised anywhere stops new orders everywhere. A
+//! halted system never resumes on its own: only an explicit `Reset` event brings
+//! it back to `Running`.
+
+use std::sync::{Arc, RwLock};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use anyhow::Result;
+use tracing::{info, warn};
+
+/// System state
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum State {
+    /// Components are being set up
+    Initializing,
+
+    /// Trading normally
+    Running,
+
+    /// New orders blocked until an explicit reset
+    Halted,
+
+    /// Shut down
+    Stopped,
+}
+
+/// Event driving the state machine
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum Event {
+    /// Start trading
+    Start,
+
+    /// Block new orders, with the reason
+    Halt(String),
+
+    /// Resume trading after a halt
+    Reset,
+
+    /// Shut down
+    Stop,
+}
+
+/// Accepted state change
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StateTransition {
+    /// State before the event
+    pub from: State,
+
+    /// State after the event
+    pub to: State,
+
+    /// Event that caused the change
+    pub event: Event,
+
+    /// Transition time
+    pub timestamp: DateTime<Utc>,
+}
+
+/// State machine shared between the components that can open risk
+pub type SharedStateMachine = Arc<RwLock<StateMachine>>;
+
+/// System state machine
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StateMachine {
+    /// Current state
+    state: State,
+
+    /// Reason of the current halt
+    halt_reason: Option<String>,
+
+    /// Accepted transitions, oldest first
+    transitions: Vec<StateTransition>,
+}
+
+impl StateMachine {
+    /// Create a state machine in the `Initializing` state
+    pub fn new() -> Self {
+        Self {
+            state: State::Initializing,
+            halt_reason: None,
+            transitions: Vec::new(),
+        }
+    }
+
+    /// Create a state machine to share between components
+    pub fn shared() -> SharedStateMachine {
+        Arc::new(RwLock::new(Self::new()))
+    }
+
+    /// Apply an event; returns the new state or an error for an invalid transition
+    pub fn handle(&mut self, event: Event) -> Result<State> {
+        use State::*;
+
+        let next = match (self.state, &event) {
+            (Initializing | Stopped, Event::Start) => Running,
+            (Initializing | Running, Event::Halt(_)) => Halted,
+            // A second halt keeps the first reason
+            (Halted, Event::Halt(_)) => return Ok(Halted),
+            (Halted, Event::Reset) => Running,
+            (_, Event::Stop) => Stopped,
+            (state, event) => {
+                return Err(anyhow::anyhow!("Invalid state transition: {:?} on {:?}", event, state));
+            }
+        };
+
+        match &event {
+            Event::Halt(reason) => {
+                warn!("Trading halted: {}", reason);
+                self.halt_reason = Some(reason.clone());
+            }
+            Event::Reset => {
+                info!("Trading halt reset (was: {})", self.halt_reason.as_deref().unwrap_or("unknown"));
+                self.halt_reason = None;
+            }
+            _ => info!("System state {:?} -> {:?}", self.state, next),
+        }
+
+        self.transitions.push(StateTransition {
+            from: self.state,
+            to: next,
+            event,
+            timestamp: Utc::now(),
+        });
+        self.state = next;
+        Ok(next)
+    }
+
+    /// Get the current state
+    pub fn get_state(&self) -> State {
+        self.state
+    }
+
+    /// Whether new orders are blocked
+    pub fn is_halted(&self) -> bool {
+        self.state == State::Halted
+    }
+
+    /// Reason of the current halt
+    pub fn get_halt_reason(&self) -> Option<&str> {
+        self.halt_reason.as_deref()
+    }
+
+    /// Accepted transitions, oldest first
+    pub fn get_transitions(&self) -> &[StateTransition] {
+        &self.transitions
+    }
+}
+
+impl Default for StateMachine {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_halt_requires_reset() {
+        let mut machine = StateMachine::new();
+        assert_eq!(machine.handle(Event::Start).unwrap(), State::Running);
+        assert!(machine.handle(Event::Reset).is_err());
+
+        machine.handle(Event::Halt("drawdown".to_string())).unwrap();
+        machine.handle(Event::Halt("second".to_string())).unwrap();
+        assert!(machine.is_halted());
+        assert_eq!(machine.get_halt_reason(), Some("drawdown"));
+        assert!(machine.handle(Event::Start).is_err());
+
+        assert_eq!(machine.handle(Event::Reset).unwrap(), State::Running);
+        assert_eq!(machine.get_halt_reason(), None);
+        assert_eq!(machine.get_transitions().len(), 3);
+    }
+}
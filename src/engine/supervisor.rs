@@ -0,0 +1,472 @@
+//! Agent Supervisor
+//!
+//! This module watches the agents running in the agent runtime. An agent whose
+//! task has died (it panicked or returned) or whose heartbeat is older than the
+//! timeout (it is stuck) counts as a failure: it is reported to the
+//! `UnifiedErrorManager` and, if its restart policy allows, rebuilt from its
+//! factory and restarted after an exponential backoff. An agent that keeps
+//! failing within the failure window is escalated: either the whole system is
+//! halted through the shared state machine or the agent is given up on. An agent
+//! that halted the system is restarted with a clean window once it resumes.
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::Arc;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use anyhow::Result;
+use tokio::sync::RwLock;
+use tracing::info;
+
+use crate::engine::agent_runtime::AgentRuntime;
+use crate::engine::agent_trait::{Agent, AgentContext};
+use crate::engine::state_machine::{Event, SharedStateMachine};
+use crate::monitoring::unified_error_manager::{ErrorCategory, ErrorSeverity, UnifiedErrorManager};
+
+/// Builds a fresh instance of an agent for each (re)start
+pub type AgentFactory = Box<dyn Fn() -> Box<dyn Agent> + Send + Sync>;
+
+/// Supervision settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SupervisorConfig {
+    /// Seconds without a heartbeat after which an agent counts as stalled
+    pub heartbeat_timeout_secs: i64,
+
+    /// Delay before the first restart (seconds)
+    pub initial_backoff_secs: i64,
+
+    /// Longest delay between restarts (seconds)
+    pub max_backoff_secs: i64,
+
+    /// Factor the delay grows by with each failure in the window
+    pub backoff_multiplier: f64,
+
+    /// Failures within the window that trigger the escalation policy
+    pub max_failures: usize,
+
+    /// Window failures are counted over (seconds)
+    pub failure_window_secs: i64,
+}
+
+impl Default for SupervisorConfig {
+    fn default() -> Self {
+        Self {
+            heartbeat_timeout_secs: 30,
+            initial_backoff_secs: 1,
+            max_backoff_secs: 60,
+            backoff_multiplier: 2.0,
+            max_failures: 5,
+            failure_window_secs: 600,
+        }
+    }
+}
+
+/// Whether a failed agent is restarted
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RestartPolicy {
+    /// Restart after a backoff
+    Always,
+
+    /// Leave the agent stopped
+    Never,
+}
+
+/// What happens once an agent fails too often
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum EscalationPolicy {
+    /// Halt the whole system
+    HaltSystem,
+
+    /// Stop restarting the agent and carry on without it
+    Abandon,
+}
+
+/// How an agent failed
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum AgentFailure {
+    /// Task ended without being stopped
+    Exited,
+
+    /// No heartbeat within the timeout
+    Stalled {
+        /// Seconds since the last heartbeat
+        silent_secs: i64,
+    },
+
+    /// Rebuilding or starting the agent failed
+    RestartFailed(String),
+}
+
+/// What the supervisor did about a failure
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum SupervisorAction {
+    /// Restart scheduled after a backoff
+    RestartScheduled {
+        /// Restart time
+        at: DateTime<Utc>,
+    },
+
+    /// Agent restarted
+    Restarted,
+
+    /// Agent left stopped
+    Abandoned,
+
+    /// System halted
+    Halted,
+}
+
+/// One supervision decision
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SupervisorEvent {
+    /// Decision time
+    pub timestamp: DateTime<Utc>,
+
+    /// Agent name
+    pub agent: String,
+
+    /// Failure that led to the decision, if any
+    pub failure: Option<AgentFailure>,
+
+    /// Decision
+    pub action: SupervisorAction,
+}
+
+/// Agent under supervision
+struct SupervisedAgent {
+    /// Builds the agent
+    factory: AgentFactory,
+
+    /// Context the agent is initialized with
+    context: Arc<RwLock<AgentContext>>,
+
+    /// Restart policy
+    restart: RestartPolicy,
+
+    /// Escalation policy
+    escalation: EscalationPolicy,
+
+    /// Failure times within the window, oldest first
+    failures: VecDeque<DateTime<Utc>>,
+
+    /// Pending restart time
+    restart_at: Option<DateTime<Utc>>,
+
+    /// Whether supervision has given up on the agent
+    abandoned: bool,
+
+    /// Whether the agent halted the system and waits for it to resume
+    halted: bool,
+}
+
+/// Restarts failed agents and escalates repeated failures
+pub struct AgentSupervisor {
+    /// Settings
+    config: SupervisorConfig,
+
+    /// State machine halted on escalation
+    state_machine: SharedStateMachine,
+
+    /// Supervised agents by name
+    agents: HashMap<String, SupervisedAgent>,
+
+    /// Decisions since start
+    events: Vec<SupervisorEvent>,
+}
+
+impl AgentSupervisor {
+    /// Create a new supervisor halting `state_machine` on escalation
+    pub fn new(config: SupervisorConfig, state_machine: SharedStateMachine) -> Self {
+        Self {
+            config,
+            state_machine,
+            agents: HashMap::new(),
+            events: Vec::new(),
+        }
+    }
+
+    /// Get the settings
+    pub fn get_config(&self) -> &SupervisorConfig {
+        &self.config
+    }
+
+    /// Start an agent built by `factory` in `runtime` and supervise it
+    pub async fn supervise(
+        &mut self,
+        runtime: &mut AgentRuntime,
+        factory: AgentFactory,
+        context: Arc<RwLock<AgentContext>>,
+        restart: RestartPolicy,
+        escalation: EscalationPolicy,
+    ) -> Result<()> {
+        let agent = factory();
+        let name = agent.get_name().to_string();
+        runtime.spawn(agent, context.clone()).await?;
+        self.agents.insert(name, SupervisedAgent {
+            factory,
+            context,
+            restart,
+            escalation,
+            failures: VecDeque::new(),
+            restart_at: None,
+            abandoned: false,
+            halted: false,
+        });
+        Ok(())
+    }
+
+    /// Stop supervising an agent (it keeps running if it is)
+    pub fn unsupervise(&mut self, name: &str) -> bool {
+        self.agents.remove(name).is_some()
+    }
+
+    /// Delay before the restart following the `failures`-th failure in the window
+    pub fn backoff(&self, failures: usize) -> Duration {
+        let exponent = failures.saturating_sub(1).min(32) as i32;
+        let secs = self.config.initial_backoff_secs as f64 * self.config.backoff_multiplier.max(1.0).powi(exponent);
+        Duration::seconds(secs.min(self.config.max_backoff_secs as f64).max(0.0) as i64)
+    }
+
+    /// Detect failed agents, perform due restarts and escalate; returns the decisions taken
+    pub async fn check(&mut self, runtime: &mut AgentRuntime, errors: &mut UnifiedErrorManager, now: DateTime<Utc>) -> Vec<SupervisorEvent> {
+        let mut events = Vec::new();
+        let names: Vec<String> = self.agents.keys().cloned().collect();
+
+        for name in names {
+            let (restart_at, halted) = match self.agents.get(&name) {
+                Some(supervised) if !supervised.abandoned => (supervised.restart_at, supervised.halted),
+                _ => continue,
+            };
+
+            // Give an agent that halted the system a fresh start once trading resumes
+            if halted {
+                let resumed = self.state_machine.read().map(|machine| !machine.is_halted()).unwrap_or(false);
+                if !resumed {
+                    continue;
+                }
+                let at = now + self.backoff(1);
+                if let Some(supervised) = self.agents.get_mut(&name) {
+                    supervised.failures.clear();
+                    supervised.halted = false;
+                    supervised.restart_at = Some(at);
+                }
+                events.push(self.record(now, &name, None, SupervisorAction::RestartScheduled { at }));
+                continue;
+            }
+
+            let failure = match restart_at {
+                Some(at) if now < at => continue,
+                Some(_) => match self.restart(runtime, &name).await {
+                    Ok(()) => {
+                        errors.report(ErrorCategory::Agent, ErrorSeverity::Info, &name, "agent restarted");
+                        events.push(self.record(now, &name, None, SupervisorAction::Restarted));
+                        continue;
+                    },
+                    Err(e) => AgentFailure::RestartFailed(e.to_string()),
+                },
+                None => match runtime.get_handle(&name) {
+                    // Stopped on purpose through the runtime
+                    None => continue,
+                    Some(handle) if !handle.is_running() => AgentFailure::Exited,
+                    Some(handle) => {
+                        let silent_secs = (now - handle.get_heartbeat()).num_seconds();
+                        if silent_secs <= self.config.heartbeat_timeout_secs {
+                            continue;
+                        }
+                        AgentFailure::Stalled { silent_secs }
+                    },
+                },
+            };
+
+            runtime.abort(&name);
+            let action = self.handle_failure(&name, &failure, errors, now);
+            events.push(self.record(now, &name, Some(failure), action));
+        }
+
+        events
+    }
+
+    /// Decisions since start
+    pub fn get_events(&self) -> &[SupervisorEvent] {
+        &self.events
+    }
+
+    /// Failures of an agent within the window
+    pub fn get_failure_count(&self, name: &str) -> usize {
+        self.agents.get(name).map_or(0, |agent| agent.failures.len())
+    }
+
+    /// Whether supervision has given up on an agent
+    pub fn is_abandoned(&self, name: &str) -> bool {
+        self.agents.get(name).is_some_and(|agent| agent.abandoned)
+    }
+
+    /// Rebuild an agent from its factory and start it
+    async fn restart(&mut self, runtime: &mut AgentRuntime, name: &str) -> Result<()> {
+        let supervised = self.agents.get_mut(name)
+            .ok_or_else(|| anyhow::anyhow!("Agent {} is not supervised", name))?;
+        supervised.restart_at = None;
+        runtime.spawn((supervised.factory)(), supervised.context.clone()).await
+    }
+
+    /// Count a failure, report it and pick the follow-up
+    fn handle_failure(&mut self, name: &str, failure: &AgentFailure, errors: &mut UnifiedErrorManager, now: DateTime<Utc>) -> SupervisorAction {
+        let window_start = now - Duration::seconds(self.config.failure_window_secs);
+        let max_failures = self.config.max_failures.max(1);
+        let (count, restart, escalation) = {
+            let Some(supervised) = self.agents.get_mut(name) else {
+                return SupervisorAction::Abandoned;
+            };
+            supervised.failures.push_back(now);
+            while supervised.failures.front().is_some_and(|t| *t < window_start) {
+                supervised.failures.pop_front();
+            }
+            (supervised.failures.len(), supervised.restart, supervised.escalation)
+        };
+
+        let message = format!("{:?} ({} failures in {}s)", failure, count, self.config.failure_window_secs);
+        let context = HashMap::from([("failures".to_string(), count.to_string())]);
+
+        let action = if count >= max_failures {
+            errors.report_with_context(ErrorCategory::Agent, ErrorSeverity::Critical, name, &message, context);
+            match escalation {
+                EscalationPolicy::HaltSystem => {
+                    let reason = format!("agent {} failed {} times", name, count);
+                    match self.state_machine.write() {
+                        Ok(mut machine) => {
+                            if let Err(e) = machine.handle(Event::Halt(reason)) {
+                                errors.report(ErrorCategory::Agent, ErrorSeverity::Critical, name, &format!("halt rejected: {}", e));
+                            }
+                        },
+                        Err(_) => {
+                            errors.report(ErrorCategory::Agent, ErrorSeverity::Critical, name, "state machine lock poisoned");
+                        },
+                    }
+                    SupervisorAction::Halted
+                },
+                EscalationPolicy::Abandon => SupervisorAction::Abandoned,
+            }
+        } else {
+            errors.report_with_context(ErrorCategory::Agent, ErrorSeverity::Error, name, &message, context);
+            match restart {
+                RestartPolicy::Always => SupervisorAction::RestartScheduled { at: now + self.backoff(count) },
+                RestartPolicy::Never => SupervisorAction::Abandoned,
+            }
+        };
+
+        if let Some(supervised) = self.agents.get_mut(name) {
+            match &action {
+                SupervisorAction::RestartScheduled { at } => supervised.restart_at = Some(*at),
+                SupervisorAction::Halted => supervised.halted = true,
+                _ => supervised.abandoned = true,
+            }
+        }
+        action
+    }
+
+    /// Keep a decision
+    fn record(&mut self, now: DateTime<Utc>, name: &str, failure: Option<AgentFailure>, action: SupervisorAction) -> SupervisorEvent {
+        info!("Supervisor: {} {:?} after {:?}", name, action, failure);
+        let event = SupervisorEvent {
+            timestamp: now,
+            agent: name.to_string(),
+            failure,
+            action,
+        };
+        self.events.push(event.clone());
+        event
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use crate::engine::agent_runtime::AgentRuntimeConfig;
+    use crate::engine::agent_trait::AgentConfig;
+    use crate::engine::message_bus::BusMessage;
+    use crate::engine::state_machine::StateMachine;
+
+    /// Agent whose first tick panics
+    struct PanickingAgent;
+
+    #[async_trait]
+    impl Agent for PanickingAgent {
+        fn get_name(&self) -> &str {
+            "panicky"
+        }
+
+        fn get_config(&self) -> Box<dyn AgentConfig> {
+            Box::new(())
+        }
+
+        async fn initialize(&mut self, _context: Arc<RwLock<AgentContext>>) -> Result<()> {
+            Ok(())
+        }
+
+        async fn start(&mut self) -> Result<()> {
+            Ok(())
+        }
+
+        async fn stop(&mut self) -> Result<()> {
+            Ok(())
+        }
+
+        async fn tick(&mut self) -> Result<()> {
+            panic!("tick failed");
+        }
+
+        async fn handle_message(&mut self, _message: BusMessage) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_restarts_with_backoff_then_halts() {
+        let state_machine = StateMachine::shared();
+        state_machine.write().unwrap().handle(Event::Start).unwrap();
+        let mut supervisor = AgentSupervisor::new(
+            SupervisorConfig { max_failures: 2, ..SupervisorConfig::default() },
+            state_machine.clone(),
+        );
+        assert_eq!(supervisor.backoff(1), Duration::seconds(1));
+        assert_eq!(supervisor.backoff(3), Duration::seconds(4));
+        assert_eq!(supervisor.backoff(10), Duration::seconds(60));
+
+        let mut runtime = AgentRuntime::new(AgentRuntimeConfig { tick_interval_ms: 5, ..AgentRuntimeConfig::default() });
+        let mut errors = UnifiedErrorManager::default();
+        let context = Arc::new(RwLock::new(AgentContext::new("panicky".to_string())));
+        supervisor.supervise(&mut runtime, Box::new(|| Box::new(PanickingAgent) as Box<dyn Agent>), context,
+                             RestartPolicy::Always, EscalationPolicy::HaltSystem).await.unwrap();
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+
+        let now = Utc::now();
+        let events = supervisor.check(&mut runtime, &mut errors, now).await;
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].failure, Some(AgentFailure::Exited));
+        assert!(matches!(events[0].action, SupervisorAction::RestartScheduled { .. }));
+
+        // Nothing happens before the backoff has passed
+        assert!(supervisor.check(&mut runtime, &mut errors, now).await.is_empty());
+        let later = now + Duration::seconds(2);
+        let events = supervisor.check(&mut runtime, &mut errors, later).await;
+        assert_eq!(events[0].action, SupervisorAction::Restarted);
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+
+        // Second failure within the window escalates
+        let events = supervisor.check(&mut runtime, &mut errors, later).await;
+        assert_eq!(events[0].action, SupervisorAction::Halted);
+        assert!(state_machine.read().unwrap().is_halted());
+        assert!(!supervisor.is_abandoned("panicky"));
+        assert_eq!(errors.get_count(ErrorCategory::Agent), 3);
+
+        // Parked while halted, then restarted with a clean window after a reset
+        assert!(supervisor.check(&mut runtime, &mut errors, later).await.is_empty());
+        state_machine.write().unwrap().handle(Event::Reset).unwrap();
+        let events = supervisor.check(&mut runtime, &mut errors, later).await;
+        assert!(matches!(events[0].action, SupervisorAction::RestartScheduled { .. }));
+        assert_eq!(supervisor.get_failure_count("panicky"), 0);
+        let events = supervisor.check(&mut runtime, &mut errors, later + Duration::seconds(2)).await;
+        assert_eq!(events[0].action, SupervisorAction::Restarted);
+    }
+}
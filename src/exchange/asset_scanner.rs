@@ -2,15 +2,17 @@
 //!
 //! This module provides functionality for scanning and analyzing trading assets.
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
 use std::sync::Arc;
 use anyhow::Result;
 use chrono::Utc;
 use tracing::{info, debug, warn};
 use serde::{Serialize, Deserialize};
 
+use crate::engine::json_store::{load_json, save_json};
 use crate::exchange::bybit::adapter::BybitAdapter;
-use crate::exchange::bybit::types::BybitKline;
+use crate::exchange::bybit::types::{BybitInstrument, BybitKline};
 use crate::exchange::types::Candle;
 use crate::strategy::indicators::*;
 use crate::agents::quantum_predictor::QuantumPredictor;
@@ -96,6 +98,137 @@ impl Default for AssetMetadata {
     }
 }
 
+/// Listing history of a symbol (times in seconds)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SymbolListing {
+    /// Symbol
+    pub symbol: String,
+
+    /// Listing time
+    pub listed_at: u64,
+
+    /// Delisting time, if delisted
+    pub delisted_at: Option<u64>,
+}
+
+impl SymbolListing {
+    /// Whether the symbol was tradable at `time`
+    pub fn is_listed_at(&self, time: u64) -> bool {
+        time >= self.listed_at && self.delisted_at.map_or(true, |delisted| time < delisted)
+    }
+}
+
+/// Point-in-time symbol universe
+///
+/// Records when each symbol was listed and delisted so backtests only trade
+/// symbols that existed at simulation time instead of today's survivors.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SymbolUniverse {
+    /// Listings by symbol
+    listings: BTreeMap<String, SymbolListing>,
+}
+
+impl SymbolUniverse {
+    /// Create an empty universe
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record a listing, replacing any previous record for the symbol
+    pub fn record_listing(&mut self, symbol: &str, listed_at: u64, delisted_at: Option<u64>) {
+        self.listings.insert(symbol.to_string(), SymbolListing {
+            symbol: symbol.to_string(),
+            listed_at,
+            delisted_at,
+        });
+    }
+
+    /// Merge an instruments snapshot observed at `observed_at` (seconds)
+    ///
+    /// New symbols are listed at their launch time (or `observed_at` if unknown).
+    /// Known symbols missing from the snapshot, or no longer trading, are marked
+    /// delisted. Returns the symbols newly delisted by this snapshot.
+    pub fn update_from_instruments(&mut self, instruments: &[BybitInstrument], observed_at: u64) -> Vec<String> {
+        let mut seen = std::collections::HashSet::new();
+        let mut delisted = Vec::new();
+
+        for instrument in instruments {
+            seen.insert(instrument.symbol.as_str());
+            let listed_at = instrument.launch_time.map(|ms| (ms / 1000).max(0) as u64).unwrap_or(observed_at);
+            let closed = !instrument.status.is_empty() && instrument.status != "Trading";
+            let delivery = instrument.delivery_time.map(|ms| (ms / 1000).max(0) as u64);
+
+            let listing = self.listings.entry(instrument.symbol.clone()).or_insert_with(|| SymbolListing {
+                symbol: instrument.symbol.clone(),
+                listed_at,
+                delisted_at: None,
+            });
+            listing.listed_at = listing.listed_at.min(listed_at);
+
+            let delisted_at = match (closed, delivery) {
+                (_, Some(delivery)) if delivery <= observed_at => Some(delivery),
+                (true, _) => Some(delivery.unwrap_or(observed_at)),
+                _ => None,
+            };
+            if listing.delisted_at.is_none() && delisted_at.is_some() {
+                delisted.push(instrument.symbol.clone());
+            }
+            listing.delisted_at = listing.delisted_at.or(delisted_at);
+        }
+
+        for listing in self.listings.values_mut() {
+            if listing.delisted_at.is_none() && !seen.contains(listing.symbol.as_str()) {
+                listing.delisted_at = Some(observed_at);
+                delisted.push(listing.symbol.clone());
+            }
+        }
+
+        if !delisted.is_empty() {
+            info!("Symbols delisted since last scan: {:?}", delisted);
+        }
+        delisted
+    }
+
+    /// Listing record of a symbol
+    pub fn get_listing(&self, symbol: &str) -> Option<&SymbolListing> {
+        self.listings.get(symbol)
+    }
+
+    /// Whether `symbol` was tradable at `time` (seconds); unknown symbols are not
+    pub fn is_listed(&self, symbol: &str, time: u64) -> bool {
+        self.listings.get(symbol).map_or(false, |l| l.is_listed_at(time))
+    }
+
+    /// Symbols tradable at `time` (seconds)
+    pub fn symbols_at(&self, time: u64) -> Vec<String> {
+        self.listings.values()
+            .filter(|l| l.is_listed_at(time))
+            .map(|l| l.symbol.clone())
+            .collect()
+    }
+
+    /// Number of known symbols
+    pub fn len(&self) -> usize {
+        self.listings.len()
+    }
+
+    /// Whether no symbol is known
+    pub fn is_empty(&self) -> bool {
+        self.listings.is_empty()
+    }
+
+    /// Save the universe as JSON
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        save_json(path, self)
+    }
+
+    /// Load a universe saved with `save`
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        load_json(path)?.ok_or_else(|| anyhow::anyhow!("Asset universe {} not found", path.display()))
+    }
+}
+
 /// Asset scanner
 pub struct AssetScanner {
     /// Exchange adapter
@@ -112,6 +245,9 @@ pub struct AssetScanner {
 
     /// Quantum predictor
     quantum_predictor: QuantumPredictor,
+
+    /// Listing and delisting history
+    universe: SymbolUniverse,
 }
 
 impl AssetScanner {
@@ -123,15 +259,32 @@ impl AssetScanner {
             timeframes,
             asset_metadata: HashMap::new(),
             quantum_predictor: QuantumPredictor::new(),
+            universe: SymbolUniverse::new(),
         }
     }
 
+    /// Listing and delisting history collected from instrument scans
+    pub fn get_universe(&self) -> &SymbolUniverse {
+        &self.universe
+    }
+
+    /// Replace the universe, e.g. with history loaded from disk
+    pub fn set_universe(&mut self, universe: SymbolUniverse) {
+        self.universe = universe;
+    }
+
+    /// Record an instruments snapshot in the universe; returns newly delisted symbols
+    pub fn update_universe(&mut self, instruments: &[BybitInstrument]) -> Vec<String> {
+        self.universe.update_from_instruments(instruments, Utc::now().timestamp().max(0) as u64)
+    }
+
     /// Scan all assets
     pub async fn scan_all_assets(&mut self) -> Result<Vec<TradingOpportunity>> {
         debug!("Scanning all assets...");
 
         // Get all instruments
         let instrument_info = self.exchange.get_instruments("linear").await?;
+        self.update_universe(&instrument_info.list);
 
         // Filter USDT pairs
         let usdt_pairs: Vec<_> = instrument_info.list
@@ -340,3 +493,36 @@ impl AssetScanner {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::exchange::bybit::types::{BybitLeverageFilter, BybitLotSizeFilter, BybitPriceFilter};
+
+    fn instrument(symbol: &str, launch_time_ms: i64) -> BybitInstrument {
+        BybitInstrument {
+            symbol: symbol.to_string(),
+            leverage_filter: BybitLeverageFilter { min_leverage: 1.0, max_leverage: 100.0, leverage_step: 0.01 },
+            price_filter: BybitPriceFilter { min_price: 0.0, max_price: 0.0, tick_size: 0.01 },
+            lot_size_filter: BybitLotSizeFilter { max_trading_qty: 0.0, min_trading_qty: 0.0, qty_step: 0.01 },
+            status: "Trading".to_string(),
+            launch_time: Some(launch_time_ms),
+            delivery_time: None,
+        }
+    }
+
+    #[test]
+    fn test_universe_tracks_listings_and_delistings() {
+        let mut universe = SymbolUniverse::new();
+        universe.update_from_instruments(&[instrument("BTCUSDT", 1_000_000), instrument("LUNAUSDT", 2_000_000)], 5_000);
+
+        let delisted = universe.update_from_instruments(&[instrument("BTCUSDT", 1_000_000)], 9_000);
+        assert_eq!(delisted, vec!["LUNAUSDT".to_string()]);
+
+        assert!(!universe.is_listed("LUNAUSDT", 1_500));
+        assert!(universe.is_listed("LUNAUSDT", 5_000));
+        assert!(!universe.is_listed("LUNAUSDT", 9_000));
+        assert_eq!(universe.symbols_at(1_200), vec!["BTCUSDT".to_string()]);
+        assert!(!universe.is_listed("SOLUSDT", 5_000));
+    }
+}
@@ -51,6 +51,11 @@ impl BybitAdapter {
         }
     }
 
+    /// Whether the adapter talks to the demo environment
+    pub fn is_demo(&self) -> bool {
+        self.is_demo
+    }
+
     /// Generate signature for GET requests
     fn generate_signature(&self, timestamp: u64, params: &HashMap<String, String>) -> String {
         // Sort parameters
@@ -162,10 +167,15 @@ impl BybitAdapter {
 
     /// Get ticker
     pub async fn get_ticker(&self, symbol: &str) -> Result<Vec<BybitTicker>> {
+        self.get_category_ticker(symbol, "linear").await
+    }
+
+    /// Get ticker in a product category ("linear", "inverse" or "spot")
+    pub async fn get_category_ticker(&self, symbol: &str, category: &str) -> Result<Vec<BybitTicker>> {
         let url = format!("{}/v5/market/tickers", self.base_url);
 
         let params = [
-            ("category", "linear"),
+            ("category", category),
             ("symbol", symbol),
         ];
 
@@ -250,7 +260,7 @@ impl BybitAdapter {
         let url = format!("{}/v5/account/wallet-balance", self.base_url);
 
         let mut params = HashMap::new();
-        params.insert("category".to_string(), "linear".to_string());
+        params.insert("accountType".to_string(), "UNIFIED".to_string());
 
         if let Some(coin) = coin {
             params.insert("coin".to_string(), coin.to_string());
@@ -270,7 +280,7 @@ impl BybitAdapter {
             .text()
             .await?;
 
-        println!("API Response: {}", response_text);
+        debug!("Wallet balance response: {}", response_text);
 
         // Parse the response manually since the format might be different
         let json_response = serde_json::from_str::<serde_json::Value>(&response_text)?;
@@ -283,21 +293,38 @@ impl BybitAdapter {
             }
         }
 
-        // Create a dummy response for now, but log the actual response
+        // Amounts come back as strings, empty when not applicable
+        let number = |value: &serde_json::Value, key: &str| -> f64 {
+            value.get(key).and_then(|v| v.as_str()).and_then(|v| v.parse::<f64>().ok()).unwrap_or(0.0)
+        };
+
         let mut balances = HashMap::new();
-        balances.insert("USDT".to_string(), BybitBalance {
-            coin: "USDT".to_string(),
-            equity: 12.0,
-            available_balance: 12.0,
-            used_margin: 0.0,
-            order_margin: 0.0,
-            position_margin: 0.0,
-            unrealised_pnl: 0.0,
-            realised_pnl: 0.0,
-            cum_realised_pnl: 0.0,
-        });
+        let accounts = json_response["result"]["list"].as_array().cloned().unwrap_or_default();
+        for account in &accounts {
+            for entry in account["coin"].as_array().into_iter().flatten() {
+                let coin = entry["coin"].as_str().unwrap_or_default().to_string();
+                let position_margin = number(entry, "totalPositionIM");
+                let order_margin = number(entry, "totalOrderIM");
+                let equity = number(entry, "equity");
+                let available_balance = match number(entry, "availableToWithdraw") {
+                    available if available > 0.0 => available,
+                    _ => (number(entry, "walletBalance") - position_margin - order_margin).max(0.0),
+                };
+                balances.insert(coin.clone(), BybitBalance {
+                    coin,
+                    equity,
+                    available_balance,
+                    used_margin: position_margin + order_margin,
+                    order_margin,
+                    position_margin,
+                    unrealised_pnl: number(entry, "unrealisedPnl"),
+                    realised_pnl: 0.0,
+                    cum_realised_pnl: number(entry, "cumRealisedPnl"),
+                });
+            }
+        }
 
-        return Ok(balances);
+        Ok(balances)
     }
 
     /// Place order
@@ -313,12 +340,39 @@ impl BybitAdapter {
         close_on_trigger: bool,
         take_profit: Option<f64>,
         stop_loss: Option<f64>,
+    ) -> Result<BybitOrder> {
+        self.place_linked_order(
+            symbol, side, order_type, qty, price, time_in_force,
+            reduce_only, close_on_trigger, take_profit, stop_loss, None,
+        ).await
+    }
+
+    /// Place order with a client order ID (`orderLinkId`)
+    ///
+    /// Bybit refuses a second order with the same `orderLinkId`, so resubmitting
+    /// after a timeout cannot open the position twice.
+    pub async fn place_linked_order(
+        &self,
+        symbol: &str,
+        side: OrderSide,
+        order_type: OrderType,
+        qty: f64,
+        price: Option<f64>,
+        time_in_force: TimeInForce,
+        reduce_only: bool,
+        close_on_trigger: bool,
+        take_profit: Option<f64>,
+        stop_loss: Option<f64>,
+        order_link_id: Option<&str>,
     ) -> Result<BybitOrder> {
         let url = format!("{}/v5/order/create", self.base_url);
 
         let mut params = HashMap::new();
         params.insert("category".to_string(), "linear".to_string());
         params.insert("symbol".to_string(), symbol.to_string());
+        if let Some(order_link_id) = order_link_id {
+            params.insert("orderLinkId".to_string(), order_link_id.to_string());
+        }
 
         let side_str = match side {
             OrderSide::Buy => "Buy",
@@ -406,6 +460,81 @@ impl BybitAdapter {
         Ok(order)
     }
 
+    /// Place a market order on one side of a position
+    ///
+    /// `position_idx` is 0 in one-way mode, 1 for the long and 2 for the short side in hedge mode.
+    pub async fn place_position_order(
+        &self,
+        symbol: &str,
+        side: OrderSide,
+        qty: f64,
+        position_idx: u8,
+        reduce_only: bool,
+    ) -> Result<BybitOrder> {
+        let url = format!("{}/v5/order/create", self.base_url);
+
+        let side_str = match side {
+            OrderSide::Buy => "Buy",
+            OrderSide::Sell => "Sell",
+        };
+
+        let mut params = HashMap::new();
+        params.insert("category".to_string(), "linear".to_string());
+        params.insert("symbol".to_string(), symbol.to_string());
+        params.insert("side".to_string(), side_str.to_string());
+        params.insert("orderType".to_string(), "Market".to_string());
+        params.insert("qty".to_string(), qty.to_string());
+        params.insert("timeInForce".to_string(), "IOC".to_string());
+        params.insert("positionIdx".to_string(), position_idx.to_string());
+        params.insert("reduceOnly".to_string(), reduce_only.to_string());
+
+        let timestamp = self.get_timestamp();
+
+        // Convert params to JSON for signature
+        let json_body = serde_json::to_string(&params)?;
+        let signature = self.generate_signature_post(timestamp, &json_body);
+
+        let response = self.client.post(&url)
+            .json(&params)
+            .header("X-BAPI-API-KEY", &self.api_key)
+            .header("X-BAPI-SIGN", signature)
+            .header("X-BAPI-TIMESTAMP", timestamp.to_string())
+            .header("X-BAPI-RECV-WINDOW", "5000")
+            .send()
+            .await?
+            .json::<BybitResponse<serde_json::Value>>()
+            .await?;
+
+        if response.ret_code != 0 {
+            return Err(anyhow::anyhow!("Bybit API error: {}", response.ret_msg));
+        }
+
+        let result = response.result.ok_or_else(|| anyhow::anyhow!("No result"))?;
+
+        Ok(BybitOrder {
+            order_id: result["orderId"].as_str().unwrap_or("").to_string(),
+            symbol: symbol.to_string(),
+            side,
+            order_type: OrderType::Market,
+            price: None,
+            qty,
+            time_in_force: TimeInForce::ImmediateOrCancel,
+            order_status: OrderStatus::Created,
+            last_exec_price: None,
+            cum_exec_qty: 0.0,
+            cum_exec_value: 0.0,
+            cum_exec_fee: 0.0,
+            created_time: Utc::now().to_string(),
+            updated_time: Utc::now().to_string(),
+            take_profit: None,
+            stop_loss: None,
+            trigger_price: None,
+            reduce_only,
+            close_on_trigger: false,
+            position_idx,
+        })
+    }
+
     /// Get order
     pub async fn get_order(&self, symbol: &str, order_id: &str) -> Result<BybitOrder> {
         let url = format!("{}/v5/order/realtime", self.base_url);
@@ -794,6 +923,57 @@ impl BybitAdapter {
         Ok(positions)
     }
 
+    /// Get execution history, newest first
+    pub async fn get_executions(&self, symbol: Option<&str>, start_time_ms: Option<i64>) -> Result<Vec<BybitExecution>> {
+        let url = format!("{}/v5/execution/list", self.base_url);
+
+        let mut params = HashMap::new();
+        params.insert("category".to_string(), "linear".to_string());
+        params.insert("limit".to_string(), "100".to_string());
+
+        if let Some(symbol) = symbol {
+            params.insert("symbol".to_string(), symbol.to_string());
+        }
+        if let Some(start_time_ms) = start_time_ms {
+            params.insert("startTime".to_string(), start_time_ms.to_string());
+        }
+
+        let timestamp = self.get_timestamp();
+        let signature = self.generate_signature(timestamp, &params);
+
+        let response = self.client.get(&url)
+            .query(&params)
+            .header("X-BAPI-API-KEY", &self.api_key)
+            .header("X-BAPI-SIGN", signature)
+            .header("X-BAPI-TIMESTAMP", timestamp.to_string())
+            .header("X-BAPI-RECV-WINDOW", "5000")
+            .send()
+            .await?
+            .json::<BybitResponse<serde_json::Value>>()
+            .await?;
+
+        if response.ret_code != 0 {
+            return Err(anyhow::anyhow!("Bybit API error: {}", response.ret_msg));
+        }
+
+        let result = response.result.ok_or_else(|| anyhow::anyhow!("No result"))?;
+        let list = result["list"].as_array().ok_or_else(|| anyhow::anyhow!("No list"))?;
+
+        let executions = list.iter()
+            .map(|item| BybitExecution {
+                symbol: item["symbol"].as_str().unwrap_or("").to_string(),
+                order_id: item["orderId"].as_str().unwrap_or("").to_string(),
+                side: item["side"].as_str().unwrap_or("").to_string(),
+                exec_price: item["execPrice"].as_str().unwrap_or("0").parse::<f64>().unwrap_or(0.0),
+                exec_qty: item["execQty"].as_str().unwrap_or("0").parse::<f64>().unwrap_or(0.0),
+                exec_type: item["execType"].as_str().unwrap_or("").to_string(),
+                exec_time: item["execTime"].as_str().unwrap_or("0").parse::<i64>().unwrap_or(0),
+            })
+            .collect();
+
+        Ok(executions)
+    }
+
     /// Request demo funds
     pub async fn request_demo_funds(&self, coin: &str, amount: f64) -> Result<()> {
         let url = format!("{}/v5/account/demo-apply-money", self.base_url);
@@ -883,11 +1063,19 @@ impl BybitAdapter {
                 qty_step: item["lotSizeFilter"]["qtyStep"].as_str().unwrap_or("0.01").parse::<f64>().unwrap_or(0.01),
             };
 
+            // Times are millisecond strings; "0" means not set
+            let parse_time = |value: &serde_json::Value| {
+                value.as_str().and_then(|t| t.parse::<i64>().ok()).filter(|t| *t > 0)
+            };
+
             let instrument = BybitInstrument {
                 symbol,
                 leverage_filter,
                 price_filter,
                 lot_size_filter,
+                status: item["status"].as_str().unwrap_or("Trading").to_string(),
+                launch_time: parse_time(&item["launchTime"]),
+                delivery_time: parse_time(&item["deliveryTime"]),
             };
 
             instruments.push(instrument);
@@ -977,6 +1165,85 @@ impl BybitAdapter {
         Ok(funding_rate)
     }
 
+    /// Get the most recent public trades, newest first
+    pub async fn get_recent_trades(&self, symbol: &str, category: &str, limit: u32) -> Result<Vec<BybitPublicTrade>> {
+        let url = format!("{}/v5/market/recent-trade", self.base_url);
+
+        let limit = limit.to_string();
+        let params = [
+            ("category", category),
+            ("symbol", symbol),
+            ("limit", limit.as_str()),
+        ];
+
+        let response = self.client.get(&url)
+            .query(&params)
+            .send()
+            .await?
+            .json::<BybitResponse<serde_json::Value>>()
+            .await?;
+
+        if response.ret_code != 0 {
+            return Err(anyhow::anyhow!("Bybit API error: {}", response.ret_msg));
+        }
+
+        let result = response.result.ok_or_else(|| anyhow::anyhow!("No result"))?;
+        let list = result["list"].as_array().ok_or_else(|| anyhow::anyhow!("No list"))?;
+
+        let trades = list.iter()
+            .map(|item| BybitPublicTrade {
+                exec_id: item["execId"].as_str().unwrap_or("").to_string(),
+                symbol: item["symbol"].as_str().unwrap_or("").to_string(),
+                price: item["price"].as_str().unwrap_or("0").parse::<f64>().unwrap_or(0.0),
+                size: item["size"].as_str().unwrap_or("0").parse::<f64>().unwrap_or(0.0),
+                side: item["side"].as_str().unwrap_or("").to_string(),
+                time: item["time"].as_str().unwrap_or("0").parse::<i64>().unwrap_or(0),
+            })
+            .collect();
+
+        Ok(trades)
+    }
+
+    /// Set or amend the position's exchange-side stop loss and take profit
+    pub async fn set_trading_stop(&self, symbol: &str, stop_loss: Option<f64>, take_profit: Option<f64>) -> Result<()> {
+        let url = format!("{}/v5/position/trading-stop", self.base_url);
+
+        let mut params = HashMap::new();
+        params.insert("category".to_string(), "linear".to_string());
+        params.insert("symbol".to_string(), symbol.to_string());
+        params.insert("positionIdx".to_string(), "0".to_string());
+        params.insert("tpslMode".to_string(), "Full".to_string());
+        if let Some(stop_loss) = stop_loss {
+            params.insert("stopLoss".to_string(), stop_loss.to_string());
+        }
+        if let Some(take_profit) = take_profit {
+            params.insert("takeProfit".to_string(), take_profit.to_string());
+        }
+
+        let timestamp = self.get_timestamp();
+
+        // Convert params to JSON for signature
+        let json_body = serde_json::to_string(&params)?;
+        let signature = self.generate_signature_post(timestamp, &json_body);
+
+        let response = self.client.post(&url)
+            .json(&params)
+            .header("X-BAPI-API-KEY", &self.api_key)
+            .header("X-BAPI-SIGN", signature)
+            .header("X-BAPI-TIMESTAMP", timestamp.to_string())
+            .header("X-BAPI-RECV-WINDOW", "5000")
+            .send()
+            .await?
+            .json::<BybitResponse<serde_json::Value>>()
+            .await?;
+
+        if response.ret_code != 0 {
+            return Err(anyhow::anyhow!("Bybit API error: {}", response.ret_msg));
+        }
+
+        Ok(())
+    }
+
     /// Set leverage
     pub async fn set_leverage(&self, symbol: &str, leverage: u32) -> Result<()> {
         let url = format!("{}/v5/position/set-leverage", self.base_url);
@@ -2,12 +2,17 @@
 //!
 //! This module provides rate limiting functionality to ensure compliance with Bybit API limits.
 
+use std::sync::Arc;
 use std::time::{Duration, Instant};
 use std::collections::VecDeque;
+use tokio::sync::Mutex;
 use tokio::time::sleep;
 use anyhow::Result;
 use tracing::{debug, warn};
 
+/// Rate limiter shared by the callers of one API key
+pub type SharedRateLimiter = Arc<Mutex<RateLimiter>>;
+
 /// Rate limiter for API calls
 pub struct RateLimiter {
     /// Maximum requests per window
@@ -305,6 +305,31 @@ pub struct BybitOrder {
     pub position_idx: u8,
 }
 
+/// Bybit execution (fill) record
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BybitExecution {
+    /// Symbol
+    pub symbol: String,
+
+    /// Order ID
+    pub order_id: String,
+
+    /// Side ("Buy" or "Sell")
+    pub side: String,
+
+    /// Execution price
+    pub exec_price: f64,
+
+    /// Executed quantity
+    pub exec_qty: f64,
+
+    /// Execution type ("Trade", "AdlTrade", "BustTrade", "Funding", ...)
+    pub exec_type: String,
+
+    /// Execution time in milliseconds
+    pub exec_time: i64,
+}
+
 /// Bybit position
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BybitPosition {
@@ -549,6 +574,18 @@ pub struct BybitInstrument {
 
     /// Lot size filter
     pub lot_size_filter: BybitLotSizeFilter,
+
+    /// Trading status (e.g. "Trading", "Closed")
+    #[serde(default)]
+    pub status: String,
+
+    /// Listing time in milliseconds
+    #[serde(default)]
+    pub launch_time: Option<i64>,
+
+    /// Delivery or delisting time in milliseconds (None for perpetuals)
+    #[serde(default)]
+    pub delivery_time: Option<i64>,
 }
 
 /// Bybit instrument info
@@ -594,6 +631,28 @@ pub struct BybitFundingRate {
     pub funding_rate_timestamp: i64,
 }
 
+/// Bybit public trade from the trade tape
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BybitPublicTrade {
+    /// Execution ID
+    pub exec_id: String,
+
+    /// Symbol
+    pub symbol: String,
+
+    /// Price
+    pub price: f64,
+
+    /// Quantity
+    pub size: f64,
+
+    /// Taker side ("Buy" or "Sell")
+    pub side: String,
+
+    /// Execution time (milliseconds)
+    pub time: i64,
+}
+
 /// Bybit API response
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BybitResponse<T> {
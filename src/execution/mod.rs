@@ -7,8 +7,10 @@ pub mod order_manager;
 pub mod position_tracker;
 pub mod risk_calculator;
 pub mod executor;
+pub mod order_router;
 
 pub use order_manager::*;
 pub use position_tracker::*;
 pub use risk_calculator::*;
 pub use executor::*;
+pub use order_router::*;
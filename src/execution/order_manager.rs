@@ -0,0 +1,615 @@
+//! Order Manager
+//!
+//! This module tracks the lifecycle of every order as an explicit state machine
+//! (Created → Submitted → PartiallyFilled → Filled / Cancelled / Rejected / Expired)
+//! driven by execution reports. Invalid transitions are rejected instead of silently
+//! overwriting the order status, and every accepted transition is published on the
+//! message bus. Entries are throttled per symbol: a new entry is refused while an
+//! earlier one is still waiting for its acknowledgment or within the cooldown, so a
+//! fast scanning loop cannot fire the same signal twice. While the shared system
+//! state machine is halted no new entry is created; exits are still allowed.
+//! Every order carries a client order ID sent to the exchange as `orderLinkId`,
+//! so a resubmitted order is refused by the exchange instead of filling twice.
+//! Executors placing orders on the same account share one manager through
+//! `SharedOrderManager`, so throttling and halts apply across all of them.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, MutexGuard};
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tracing::{debug, warn};
+
+use crate::engine::message_bus::{Message, MessageBus};
+use crate::engine::state_machine::SharedStateMachine;
+use crate::exchange::bybit::types::{OrderSide, OrderStatus, OrderType};
+
+/// Topic used for order state transitions (`Message::Custom`)
+pub const ORDER_STATE_TOPIC: &str = "order_state";
+
+/// Quantity tolerance when deciding whether an order is completely filled
+const FILL_EPSILON: f64 = 1e-9;
+
+/// Order manager shared by the executors trading one account
+pub type SharedOrderManager = Arc<Mutex<OrderManager>>;
+
+/// Entry throttling limits
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OrderThrottleConfig {
+    /// Minimum time between two entries on the same symbol in milliseconds
+    pub symbol_cooldown_ms: i64,
+
+    /// Maximum unacknowledged orders per symbol
+    pub max_in_flight_per_symbol: usize,
+}
+
+impl Default for OrderThrottleConfig {
+    fn default() -> Self {
+        Self {
+            symbol_cooldown_ms: 1_000,
+            max_in_flight_per_symbol: 1,
+        }
+    }
+}
+
+/// Lifecycle state of an order
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum OrderState {
+    /// Created locally, not yet sent
+    Created,
+
+    /// Acknowledged by the exchange
+    Submitted,
+
+    /// Some quantity filled
+    PartiallyFilled,
+
+    /// Completely filled
+    Filled,
+
+    /// Cancelled (remaining quantity will not fill)
+    Cancelled,
+
+    /// Rejected by the exchange or a pre-trade check
+    Rejected,
+
+    /// Expired by its time in force
+    Expired,
+}
+
+impl OrderState {
+    /// Whether no further transitions are possible
+    pub fn is_terminal(&self) -> bool {
+        matches!(self, OrderState::Filled | OrderState::Cancelled | OrderState::Rejected | OrderState::Expired)
+    }
+
+    /// Whether the state machine allows moving from `self` to `next`
+    pub fn can_transition_to(&self, next: OrderState) -> bool {
+        use OrderState::*;
+        match self {
+            Created => matches!(next, Submitted | Cancelled | Rejected),
+            Submitted => matches!(next, PartiallyFilled | Filled | Cancelled | Rejected | Expired),
+            PartiallyFilled => matches!(next, PartiallyFilled | Filled | Cancelled | Expired),
+            Filled | Cancelled | Rejected | Expired => false,
+        }
+    }
+}
+
+/// Execution report driving an order's state
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum ExecutionReport {
+    /// The exchange accepted the order
+    Submitted { exchange_order_id: String },
+
+    /// A fill of `quantity` at `price`
+    Fill { quantity: f64, price: f64 },
+
+    /// The order was cancelled
+    Cancelled,
+
+    /// The order was rejected
+    Rejected { reason: String },
+
+    /// The order expired
+    Expired,
+}
+
+impl ExecutionReport {
+    /// Report equivalent to an exchange order status, if the status is one the state machine tracks
+    ///
+    /// Fills are not derived from status alone; feed them as `Fill` reports from executions.
+    pub fn from_exchange_status(status: OrderStatus, exchange_order_id: &str) -> Option<Self> {
+        match status {
+            OrderStatus::New => Some(ExecutionReport::Submitted { exchange_order_id: exchange_order_id.to_string() }),
+            OrderStatus::Cancelled => Some(ExecutionReport::Cancelled),
+            OrderStatus::Rejected => Some(ExecutionReport::Rejected { reason: "Rejected by exchange".to_string() }),
+            OrderStatus::Created | OrderStatus::PartiallyFilled | OrderStatus::Filled | OrderStatus::PendingCancel => None,
+        }
+    }
+}
+
+/// One accepted state transition
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OrderTransition {
+    /// Order ID
+    pub order_id: String,
+
+    /// Symbol
+    pub symbol: String,
+
+    /// State before the transition (`None` when the order was created)
+    pub from: Option<OrderState>,
+
+    /// State after the transition
+    pub to: OrderState,
+
+    /// Filled quantity after the transition
+    pub filled_quantity: f64,
+
+    /// Average fill price after the transition
+    pub average_fill_price: Option<f64>,
+
+    /// Time of the transition
+    pub timestamp: DateTime<Utc>,
+}
+
+impl OrderTransition {
+    /// Build the transition message to publish on the message bus
+    pub fn to_message(&self) -> Message {
+        Message::Custom(
+            ORDER_STATE_TOPIC.to_string(),
+            serde_json::to_value(self).unwrap_or(Value::Null),
+        )
+    }
+}
+
+/// An order tracked by the order manager
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ManagedOrder {
+    /// Local order ID
+    pub id: String,
+
+    /// Exchange order ID, once submitted
+    pub exchange_order_id: Option<String>,
+
+    /// Client order ID sent as `orderLinkId`; unique per order, so a retried submission cannot fill twice
+    #[serde(default)]
+    pub order_link_id: String,
+
+    /// Symbol
+    pub symbol: String,
+
+    /// Side
+    pub side: OrderSide,
+
+    /// Order type
+    pub order_type: OrderType,
+
+    /// Quantity
+    pub quantity: f64,
+
+    /// Limit price
+    pub price: Option<f64>,
+
+    /// Current state
+    pub state: OrderState,
+
+    /// Filled quantity
+    pub filled_quantity: f64,
+
+    /// Volume-weighted average fill price
+    pub average_fill_price: Option<f64>,
+
+    /// Rejection reason
+    pub reject_reason: Option<String>,
+
+    /// Creation time
+    pub created_at: DateTime<Utc>,
+
+    /// Last transition time
+    pub updated_at: DateTime<Utc>,
+}
+
+impl ManagedOrder {
+    /// Quantity still to fill
+    pub fn remaining_quantity(&self) -> f64 {
+        (self.quantity - self.filled_quantity).max(0.0)
+    }
+
+    /// Fill report for the exchange's cumulative executed quantity and value, if it grew.
+    /// The new slice is priced from the value added since the last booked fill.
+    pub fn incremental_fill(&self, cum_exec_qty: f64, cum_exec_value: f64) -> Option<ExecutionReport> {
+        let quantity = cum_exec_qty - self.filled_quantity;
+        if quantity <= 0.0 {
+            return None;
+        }
+        let booked_value = self.average_fill_price.unwrap_or(0.0) * self.filled_quantity;
+        Some(ExecutionReport::Fill {
+            quantity,
+            price: (cum_exec_value - booked_value) / quantity,
+        })
+    }
+
+    /// State an execution report moves this order to
+    fn next_state(&self, report: &ExecutionReport) -> Result<OrderState> {
+        let next = match report {
+            ExecutionReport::Submitted { .. } => OrderState::Submitted,
+            ExecutionReport::Fill { quantity, price } => {
+                if *quantity <= 0.0 || *price <= 0.0 {
+                    return Err(anyhow::anyhow!("Invalid fill {} @ {} for order {}", quantity, price, self.id));
+                }
+                if *quantity > self.remaining_quantity() + FILL_EPSILON {
+                    return Err(anyhow::anyhow!(
+                        "Fill of {} exceeds remaining {} for order {}",
+                        quantity, self.remaining_quantity(), self.id
+                    ));
+                }
+                if self.filled_quantity + quantity >= self.quantity - FILL_EPSILON {
+                    OrderState::Filled
+                } else {
+                    OrderState::PartiallyFilled
+                }
+            }
+            ExecutionReport::Cancelled => OrderState::Cancelled,
+            ExecutionReport::Rejected { .. } => OrderState::Rejected,
+            ExecutionReport::Expired => OrderState::Expired,
+        };
+
+        if !self.state.can_transition_to(next) {
+            return Err(anyhow::anyhow!("Invalid order transition {:?} -> {:?} for order {}", self.state, next, self.id));
+        }
+        Ok(next)
+    }
+}
+
+/// Tracks orders through their lifecycle
+#[derive(Debug)]
+pub struct OrderManager {
+    /// Orders by local ID
+    orders: HashMap<String, ManagedOrder>,
+
+    /// Local ID by exchange order ID
+    exchange_ids: HashMap<String, String>,
+
+    /// Next local order number
+    next_order_id: u64,
+
+    /// Bus transitions are published on
+    message_bus: Option<Arc<MessageBus>>,
+
+    /// Entry throttling limits
+    throttle: OrderThrottleConfig,
+
+    /// Time of the last entry by symbol
+    last_entry: HashMap<String, DateTime<Utc>>,
+
+    /// System state machine whose halt blocks new entries
+    state_machine: Option<SharedStateMachine>,
+}
+
+impl OrderManager {
+    /// Create a new order manager
+    pub fn new() -> Self {
+        Self {
+            orders: HashMap::new(),
+            exchange_ids: HashMap::new(),
+            next_order_id: 1,
+            message_bus: None,
+            throttle: OrderThrottleConfig::default(),
+            last_entry: HashMap::new(),
+            state_machine: None,
+        }
+    }
+
+    /// Set the entry throttling limits
+    pub fn set_throttle(&mut self, throttle: OrderThrottleConfig) {
+        self.throttle = throttle;
+    }
+
+    /// Get the entry throttling limits
+    pub fn get_throttle(&self) -> &OrderThrottleConfig {
+        &self.throttle
+    }
+
+    /// Publish transitions on `message_bus`
+    pub fn with_message_bus(mut self, message_bus: Arc<MessageBus>) -> Self {
+        self.message_bus = Some(message_bus);
+        self
+    }
+
+    /// Block new entries while `state_machine` is halted
+    pub fn set_state_machine(&mut self, state_machine: SharedStateMachine) {
+        self.state_machine = Some(state_machine);
+    }
+
+    /// Check whether the system state allows new entries
+    pub fn check_trading_allowed(&self) -> Result<()> {
+        let Some(state_machine) = &self.state_machine else {
+            return Ok(());
+        };
+        let state_machine = state_machine.read()
+            .map_err(|_| anyhow::anyhow!("System state machine is poisoned"))?;
+        if state_machine.is_halted() {
+            return Err(anyhow::anyhow!(
+                "Trading halted: {}",
+                state_machine.get_halt_reason().unwrap_or("no reason given")
+            ));
+        }
+        Ok(())
+    }
+
+    /// Create an order in the `Created` state; returns its local ID
+    pub fn create_order(
+        &mut self,
+        symbol: &str,
+        side: OrderSide,
+        order_type: OrderType,
+        quantity: f64,
+        price: Option<f64>,
+    ) -> Result<String> {
+        if quantity <= 0.0 {
+            return Err(anyhow::anyhow!("Order quantity must be positive, got {}", quantity));
+        }
+
+        let id = format!("order-{}", self.next_order_id);
+        self.next_order_id += 1;
+        let now = Utc::now();
+        let order = ManagedOrder {
+            id: id.clone(),
+            exchange_order_id: None,
+            order_link_id: uuid::Uuid::new_v4().to_string(),
+            symbol: symbol.to_string(),
+            side,
+            order_type,
+            quantity,
+            price,
+            state: OrderState::Created,
+            filled_quantity: 0.0,
+            average_fill_price: None,
+            reject_reason: None,
+            created_at: now,
+            updated_at: now,
+        };
+
+        self.publish(OrderTransition {
+            order_id: id.clone(),
+            symbol: order.symbol.clone(),
+            from: None,
+            to: OrderState::Created,
+            filled_quantity: 0.0,
+            average_fill_price: None,
+            timestamp: now,
+        });
+        self.orders.insert(id.clone(), order);
+        Ok(id)
+    }
+
+    /// Check whether a new entry on `symbol` is allowed at `now`
+    pub fn check_throttle(&self, symbol: &str, now: DateTime<Utc>) -> Result<()> {
+        let in_flight = self.get_in_flight_orders(symbol).len();
+        if in_flight >= self.throttle.max_in_flight_per_symbol {
+            return Err(anyhow::anyhow!("{} unacknowledged order(s) in flight for {}", in_flight, symbol));
+        }
+
+        if let Some(last) = self.last_entry.get(symbol) {
+            let elapsed = (now - *last).num_milliseconds();
+            if elapsed < self.throttle.symbol_cooldown_ms {
+                return Err(anyhow::anyhow!(
+                    "Entry on {} throttled: {} ms since the last one, cooldown {} ms",
+                    symbol, elapsed, self.throttle.symbol_cooldown_ms
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Create an entry order if the symbol is not throttled; returns its local ID
+    pub fn create_entry_order(
+        &mut self,
+        symbol: &str,
+        side: OrderSide,
+        order_type: OrderType,
+        quantity: f64,
+        price: Option<f64>,
+        now: DateTime<Utc>,
+    ) -> Result<String> {
+        if let Err(e) = self.check_trading_allowed() {
+            warn!("Entry on {} refused: {}", symbol, e);
+            return Err(e);
+        }
+        if let Err(e) = self.check_throttle(symbol, now) {
+            debug!("{}", e);
+            return Err(e);
+        }
+
+        let id = self.create_order(symbol, side, order_type, quantity, price)?;
+        self.last_entry.insert(symbol.to_string(), now);
+        Ok(id)
+    }
+
+    /// Apply an execution report; invalid transitions leave the order unchanged
+    pub fn apply_report(&mut self, order_id: &str, report: ExecutionReport) -> Result<OrderTransition> {
+        let order = self.orders.get_mut(order_id)
+            .ok_or_else(|| anyhow::anyhow!("Unknown order {}", order_id))?;
+        let next = match order.next_state(&report) {
+            Ok(next) => next,
+            Err(e) => {
+                warn!("{}", e);
+                return Err(e);
+            }
+        };
+
+        let previous = order.state;
+        match report {
+            ExecutionReport::Submitted { exchange_order_id } => {
+                self.exchange_ids.insert(exchange_order_id.clone(), order_id.to_string());
+                order.exchange_order_id = Some(exchange_order_id);
+            }
+            ExecutionReport::Fill { quantity, price } => {
+                let filled = order.filled_quantity + quantity;
+                let notional = order.average_fill_price.unwrap_or(0.0) * order.filled_quantity + price * quantity;
+                order.average_fill_price = Some(notional / filled);
+                order.filled_quantity = filled.min(order.quantity);
+            }
+            ExecutionReport::Rejected { reason } => order.reject_reason = Some(reason),
+            ExecutionReport::Cancelled | ExecutionReport::Expired => {}
+        }
+        order.state = next;
+        order.updated_at = Utc::now();
+
+        let transition = OrderTransition {
+            order_id: order.id.clone(),
+            symbol: order.symbol.clone(),
+            from: Some(previous),
+            to: next,
+            filled_quantity: order.filled_quantity,
+            average_fill_price: order.average_fill_price,
+            timestamp: order.updated_at,
+        };
+        debug!("Order {} {:?} -> {:?}", order_id, previous, next);
+        self.publish(transition.clone());
+        Ok(transition)
+    }
+
+    /// Apply an execution report addressed by exchange order ID
+    pub fn apply_exchange_report(&mut self, exchange_order_id: &str, report: ExecutionReport) -> Result<OrderTransition> {
+        let order_id = self.exchange_ids.get(exchange_order_id).cloned()
+            .ok_or_else(|| anyhow::anyhow!("Unknown exchange order {}", exchange_order_id))?;
+        self.apply_report(&order_id, report)
+    }
+
+    fn publish(&self, transition: OrderTransition) {
+        if let Some(message_bus) = &self.message_bus {
+            message_bus.send(transition.to_message());
+        }
+    }
+
+    /// Re-insert an order restored from persisted state (no transition is published)
+    pub fn restore_order(&mut self, order: ManagedOrder) {
+        if let Some(number) = order.id.strip_prefix("order-").and_then(|n| n.parse::<u64>().ok()) {
+            self.next_order_id = self.next_order_id.max(number + 1);
+        }
+        if let Some(exchange_order_id) = &order.exchange_order_id {
+            self.exchange_ids.insert(exchange_order_id.clone(), order.id.clone());
+        }
+        self.orders.insert(order.id.clone(), order);
+    }
+
+    /// Get an order
+    pub fn get_order(&self, order_id: &str) -> Option<&ManagedOrder> {
+        self.orders.get(order_id)
+    }
+
+    /// Get an order by its exchange order ID
+    pub fn get_order_by_exchange_id(&self, exchange_order_id: &str) -> Option<&ManagedOrder> {
+        self.exchange_ids.get(exchange_order_id).and_then(|id| self.orders.get(id))
+    }
+
+    /// Orders that can still transition
+    pub fn get_open_orders(&self) -> Vec<&ManagedOrder> {
+        self.orders.values().filter(|o| !o.state.is_terminal()).collect()
+    }
+
+    /// Orders on `symbol` created but not yet acknowledged by the exchange
+    pub fn get_in_flight_orders(&self, symbol: &str) -> Vec<&ManagedOrder> {
+        self.orders.values()
+            .filter(|o| o.symbol == symbol && o.state == OrderState::Created)
+            .collect()
+    }
+
+    /// Orders in a given state
+    pub fn get_orders_by_state(&self, state: OrderState) -> Vec<&ManagedOrder> {
+        self.orders.values().filter(|o| o.state == state).collect()
+    }
+
+    /// Drop orders in a terminal state
+    pub fn clear_terminal_orders(&mut self) {
+        self.orders.retain(|_, o| !o.state.is_terminal());
+        let orders = &self.orders;
+        self.exchange_ids.retain(|_, id| orders.contains_key(id));
+    }
+}
+
+impl Default for OrderManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Create an order manager to share between executors
+pub fn shared_order_manager(order_manager: OrderManager) -> SharedOrderManager {
+    Arc::new(Mutex::new(order_manager))
+}
+
+/// Lock a shared order manager
+pub fn lock_order_manager(order_manager: &SharedOrderManager) -> Result<MutexGuard<'_, OrderManager>> {
+    order_manager.lock().map_err(|_| anyhow::anyhow!("Order manager is poisoned"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::state_machine::{Event, StateMachine};
+
+    #[test]
+    fn test_order_lifecycle_rejects_invalid_transitions() {
+        let mut manager = OrderManager::new();
+        let id = manager.create_order("BTCUSDT", OrderSide::Buy, OrderType::Limit, 2.0, Some(100.0)).unwrap();
+
+        // Each order gets its own client order ID
+        let other = manager.create_order("BTCUSDT", OrderSide::Buy, OrderType::Limit, 2.0, Some(100.0)).unwrap();
+        assert_ne!(manager.get_order(&id).unwrap().order_link_id, manager.get_order(&other).unwrap().order_link_id);
+
+        // Cannot fill before the exchange acknowledged the order
+        assert!(manager.apply_report(&id, ExecutionReport::Fill { quantity: 1.0, price: 100.0 }).is_err());
+        assert_eq!(manager.get_order(&id).unwrap().state, OrderState::Created);
+
+        manager.apply_report(&id, ExecutionReport::Submitted { exchange_order_id: "ex-1".to_string() }).unwrap();
+        let partial = manager.apply_exchange_report("ex-1", ExecutionReport::Fill { quantity: 1.0, price: 100.0 }).unwrap();
+        assert_eq!(partial.to, OrderState::PartiallyFilled);
+
+        // Cumulative exchange totals price only the new slice
+        let fill = manager.get_order(&id).unwrap().incremental_fill(2.0, 202.0).unwrap();
+        assert_eq!(fill, ExecutionReport::Fill { quantity: 1.0, price: 102.0 });
+        assert!(manager.get_order(&id).unwrap().incremental_fill(1.0, 100.0).is_none());
+        let filled = manager.apply_report(&id, ExecutionReport::Fill { quantity: 1.0, price: 102.0 }).unwrap();
+        assert_eq!((filled.from, filled.to), (Some(OrderState::PartiallyFilled), OrderState::Filled));
+        assert!((filled.average_fill_price.unwrap() - 101.0).abs() < 1e-9);
+
+        // Terminal states are final
+        assert!(manager.apply_report(&id, ExecutionReport::Cancelled).is_err());
+        assert!(manager.get_open_orders().is_empty());
+    }
+
+    #[test]
+    fn test_entry_throttle_blocks_duplicates() {
+        let mut manager = OrderManager::new();
+        let t0 = Utc::now();
+        let id = manager.create_entry_order("BTCUSDT", OrderSide::Buy, OrderType::Market, 1.0, None, t0).unwrap();
+
+        // Same signal on the next scan, before the acknowledgment
+        assert!(manager.create_entry_order("BTCUSDT", OrderSide::Buy, OrderType::Market, 1.0, None, t0 + chrono::Duration::milliseconds(200)).is_err());
+        assert!(manager.create_entry_order("ETHUSDT", OrderSide::Buy, OrderType::Market, 1.0, None, t0).is_ok());
+
+        // Acknowledged, but still within the cooldown
+        manager.apply_report(&id, ExecutionReport::Submitted { exchange_order_id: "ex-1".to_string() }).unwrap();
+        assert!(manager.check_throttle("BTCUSDT", t0 + chrono::Duration::milliseconds(400)).is_err());
+        assert!(manager.check_throttle("BTCUSDT", t0 + chrono::Duration::milliseconds(1_000)).is_ok());
+    }
+
+    #[test]
+    fn test_halt_blocks_entries() {
+        let state_machine = StateMachine::shared();
+        let mut manager = OrderManager::new();
+        manager.set_state_machine(state_machine.clone());
+        state_machine.write().unwrap().handle(Event::Halt("drawdown".to_string())).unwrap();
+
+        assert!(manager.create_entry_order("BTCUSDT", OrderSide::Buy, OrderType::Market, 1.0, None, Utc::now()).is_err());
+        // Exits go through plain orders and stay possible
+        assert!(manager.create_order("BTCUSDT", OrderSide::Sell, OrderType::Market, 1.0, None).is_ok());
+
+        state_machine.write().unwrap().handle(Event::Reset).unwrap();
+        assert!(manager.create_entry_order("BTCUSDT", OrderSide::Buy, OrderType::Market, 1.0, None, Utc::now()).is_ok());
+    }
+}
@@ -0,0 +1,230 @@
+//! Smart Order Router
+//!
+//! This module decides how an order is worked — Market, Limit or PostOnly — from
+//! the current spread, short-term volatility and the urgency of the order. Calm,
+//! tight markets favor passive orders that earn the spread; fast markets and urgent
+//! orders cross it, with a marketable limit capping slippage when the book is thin.
+
+use serde::{Deserialize, Serialize};
+use tracing::debug;
+
+use crate::exchange::bybit::types::{OrderSide, OrderType, TimeInForce};
+
+/// How urgently an order must fill
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Urgency {
+    /// Price matters more than time; happy to wait for a passive fill
+    Low,
+
+    /// Default for entries
+    Normal,
+
+    /// Must fill now (exits, stops, hedges)
+    High,
+}
+
+/// Order style chosen by the router
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum OrderStyle {
+    /// Market order
+    Market,
+
+    /// Limit order
+    Limit,
+
+    /// Limit order that only adds liquidity
+    PostOnly,
+}
+
+/// Market state the routing decision is based on
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct RoutingInputs {
+    /// Best bid
+    pub best_bid: f64,
+
+    /// Best ask
+    pub best_ask: f64,
+
+    /// Short-term volatility as a fraction of price (e.g. 0.02 = 2%)
+    pub volatility: f64,
+
+    /// Urgency of the order
+    pub urgency: Urgency,
+}
+
+impl RoutingInputs {
+    /// Mid price
+    pub fn mid(&self) -> f64 {
+        (self.best_bid + self.best_ask) / 2.0
+    }
+
+    /// Spread in bps of mid
+    pub fn spread_bps(&self) -> f64 {
+        let mid = self.mid();
+        if mid > 0.0 { (self.best_ask - self.best_bid).max(0.0) / mid * 10_000.0 } else { 0.0 }
+    }
+}
+
+/// Router thresholds
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct OrderRouterConfig {
+    /// Spread (bps) at or above which crossing the book is considered expensive
+    pub wide_spread_bps: f64,
+
+    /// Volatility at or above which passive orders are unlikely to fill in time
+    pub high_volatility: f64,
+
+    /// Volatility at or below which entries can wait for a passive fill
+    pub calm_volatility: f64,
+
+    /// Maximum slippage (bps beyond the touch) allowed for marketable limit orders
+    pub max_slippage_bps: f64,
+}
+
+impl Default for OrderRouterConfig {
+    fn default() -> Self {
+        Self {
+            wide_spread_bps: 10.0,
+            high_volatility: 0.03,
+            calm_volatility: 0.005,
+            max_slippage_bps: 20.0,
+        }
+    }
+}
+
+/// Routing decision
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RouteDecision {
+    /// Chosen order style
+    pub style: OrderStyle,
+
+    /// Limit price (`None` for market orders)
+    pub price: Option<f64>,
+
+    /// Why the style was chosen
+    pub reason: String,
+}
+
+impl RouteDecision {
+    /// Exchange order type for the decision
+    pub fn order_type(&self) -> OrderType {
+        match self.style {
+            OrderStyle::Market => OrderType::Market,
+            OrderStyle::Limit | OrderStyle::PostOnly => OrderType::Limit,
+        }
+    }
+
+    /// Exchange time in force for the decision
+    pub fn time_in_force(&self) -> TimeInForce {
+        match self.style {
+            OrderStyle::Market => TimeInForce::ImmediateOrCancel,
+            OrderStyle::Limit => TimeInForce::GoodTillCancel,
+            OrderStyle::PostOnly => TimeInForce::PostOnly,
+        }
+    }
+}
+
+/// Chooses the order style for each order
+#[derive(Debug, Clone, Default)]
+pub struct OrderRouter {
+    /// Thresholds
+    config: OrderRouterConfig,
+}
+
+impl OrderRouter {
+    /// Create a new order router
+    pub fn new(config: OrderRouterConfig) -> Self {
+        Self { config }
+    }
+
+    /// Get the router thresholds
+    pub fn get_config(&self) -> &OrderRouterConfig {
+        &self.config
+    }
+
+    /// Urgency of an entry at `volatility`: fast markets must fill now, calm ones can wait
+    pub fn urgency_for(&self, volatility: f64) -> Urgency {
+        if volatility >= self.config.high_volatility {
+            Urgency::High
+        } else if volatility <= self.config.calm_volatility {
+            Urgency::Low
+        } else {
+            Urgency::Normal
+        }
+    }
+
+    /// Decide how to work an order on `side`
+    pub fn route(&self, side: OrderSide, inputs: &RoutingInputs) -> RouteDecision {
+        let is_buy = side == OrderSide::Buy;
+        let (near_touch, far_touch) = if is_buy {
+            (inputs.best_bid, inputs.best_ask)
+        } else {
+            (inputs.best_ask, inputs.best_bid)
+        };
+        let spread_bps = inputs.spread_bps();
+        let wide = spread_bps >= self.config.wide_spread_bps;
+        let volatile = inputs.volatility >= self.config.high_volatility;
+
+        let decision = match inputs.urgency {
+            // Urgent orders cross; a marketable limit caps the damage of a thin book
+            Urgency::High if wide => self.marketable_limit(is_buy, far_touch, "urgent order, wide spread"),
+            Urgency::High => Self::decision(OrderStyle::Market, None, "urgent order"),
+
+            // Fast markets run away from resting orders
+            _ if volatile && wide => self.marketable_limit(is_buy, far_touch, "high volatility, wide spread"),
+            _ if volatile => Self::decision(OrderStyle::Market, None, "high volatility"),
+
+            // Calm markets: earn the spread when there is time, else rest at mid in a wide book
+            Urgency::Low => Self::decision(OrderStyle::PostOnly, Some(near_touch), "low urgency, calm market"),
+            Urgency::Normal if wide => Self::decision(OrderStyle::Limit, Some(inputs.mid()), "wide spread, calm market"),
+            Urgency::Normal => Self::decision(OrderStyle::Market, None, "tight spread, calm market"),
+        };
+
+        debug!("Routed {:?} order as {:?} ({}; spread {:.1} bps, volatility {:.4})",
+               side, decision.style, decision.reason, spread_bps, inputs.volatility);
+        decision
+    }
+
+    fn marketable_limit(&self, is_buy: bool, far_touch: f64, reason: &str) -> RouteDecision {
+        let slippage = self.config.max_slippage_bps / 10_000.0;
+        let price = if is_buy { far_touch * (1.0 + slippage) } else { far_touch * (1.0 - slippage) };
+        Self::decision(OrderStyle::Limit, Some(price), reason)
+    }
+
+    fn decision(style: OrderStyle, price: Option<f64>, reason: &str) -> RouteDecision {
+        RouteDecision { style, price, reason: reason.to_string() }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn inputs(best_bid: f64, best_ask: f64, volatility: f64, urgency: Urgency) -> RoutingInputs {
+        RoutingInputs { best_bid, best_ask, volatility, urgency }
+    }
+
+    #[test]
+    fn test_routing_by_spread_volatility_and_urgency() {
+        let router = OrderRouter::default();
+
+        let calm = router.route(OrderSide::Buy, &inputs(99.99, 100.01, 0.005, Urgency::Low));
+        assert_eq!((calm.style, calm.price), (OrderStyle::PostOnly, Some(99.99)));
+        assert_eq!(calm.time_in_force(), TimeInForce::PostOnly);
+
+        let tight = router.route(OrderSide::Buy, &inputs(99.99, 100.01, 0.005, Urgency::Normal));
+        assert_eq!(tight.style, OrderStyle::Market);
+
+        let wide = router.route(OrderSide::Sell, &inputs(99.8, 100.2, 0.005, Urgency::Normal));
+        assert_eq!((wide.style, wide.price), (OrderStyle::Limit, Some(100.0)));
+
+        let fast = router.route(OrderSide::Sell, &inputs(99.8, 100.2, 0.05, Urgency::Low));
+        assert_eq!(fast.style, OrderStyle::Limit);
+        assert!((fast.price.unwrap() - 99.8 * 0.998).abs() < 1e-9);
+
+        assert_eq!(router.urgency_for(0.002), Urgency::Low);
+        assert_eq!(router.urgency_for(0.01), Urgency::Normal);
+        assert_eq!(router.urgency_for(0.05), Urgency::High);
+    }
+}
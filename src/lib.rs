@@ -66,6 +66,9 @@ pub mod bybit;
 // Market data
 pub mod market_data;
 
+// Portfolio risk measures
+pub mod risk;
+
 // Re-export adapters for backwards compatibility
 pub mod adapters {
     pub use crate::exchange::bybit::adapter::BybitAdapter;
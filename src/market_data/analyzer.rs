@@ -0,0 +1,360 @@
+//! Market Data Analyzer
+//!
+//! This module keeps rolling price histories per symbol and computes return
+//! correlations between symbols. It is used to keep new trades from piling
+//! exposure into a single cluster of highly correlated assets. Clusters link
+//! symbols whose returns move together or exactly against each other, and record
+//! the orientation of every member, so a short in an inversely correlated asset
+//! counts as the same bet as a long in the cluster's first symbol.
+
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use serde::{Deserialize, Serialize};
+use tracing::debug;
+
+/// Correlation filter configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CorrelationFilterConfig {
+    /// Number of returns used for the rolling correlation
+    pub window: usize,
+
+    /// Correlation at or above which two exposures count as the same cluster
+    pub max_correlation: f64,
+
+    /// Maximum number of open positions allowed in one correlated cluster
+    pub max_cluster_positions: usize,
+}
+
+impl Default for CorrelationFilterConfig {
+    fn default() -> Self {
+        Self {
+            window: 100,
+            max_correlation: 0.8,
+            max_cluster_positions: 1,
+        }
+    }
+}
+
+/// Result of a correlation exposure check
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CorrelationCheck {
+    /// Candidate symbol
+    pub symbol: String,
+
+    /// Whether the trade may be opened
+    pub approved: bool,
+
+    /// Open positions in the same cluster with their direction-adjusted correlation
+    pub correlated_positions: Vec<(String, f64)>,
+
+    /// Reasoning
+    pub reasoning: String,
+}
+
+/// Rolling correlation matrix of a set of symbols
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CorrelationMatrix {
+    /// Symbols the matrix covers
+    pub symbols: Vec<String>,
+
+    /// Correlation by symbol pair (pairs without enough data are omitted)
+    pub correlations: BTreeMap<String, BTreeMap<String, f64>>,
+}
+
+impl CorrelationMatrix {
+    /// Correlation of two symbols, if known
+    pub fn get(&self, a: &str, b: &str) -> Option<f64> {
+        if a == b {
+            return Some(1.0);
+        }
+        self.correlations.get(a).and_then(|row| row.get(b)).copied()
+    }
+
+    /// Group `symbols` into clusters linked by an absolute correlation of at least `threshold`
+    pub fn clusters(&self, symbols: &[String], threshold: f64) -> Vec<CorrelationCluster> {
+        let mut clusters: Vec<CorrelationCluster> = Vec::new();
+        let mut assigned: HashMap<&str, usize> = HashMap::new();
+
+        for root in symbols {
+            if assigned.contains_key(root.as_str()) {
+                continue;
+            }
+
+            // Walk the links from the first unassigned symbol, flipping the orientation on inverse links
+            let mut cluster = CorrelationCluster::default();
+            let mut queue = VecDeque::from([(root.as_str(), 1.0)]);
+            assigned.insert(root.as_str(), clusters.len());
+            while let Some((symbol, orientation)) = queue.pop_front() {
+                cluster.orientation.insert(symbol.to_string(), orientation);
+                for other in symbols {
+                    if assigned.contains_key(other.as_str()) {
+                        continue;
+                    }
+                    if let Some(correlation) = self.get(symbol, other).filter(|c| c.abs() >= threshold) {
+                        assigned.insert(other.as_str(), clusters.len());
+                        queue.push_back((other.as_str(), orientation * correlation.signum()));
+                    }
+                }
+            }
+            clusters.push(cluster);
+        }
+
+        clusters
+    }
+}
+
+/// Symbols that move together, with the orientation of each member
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CorrelationCluster {
+    /// +1.0 for symbols moving with the cluster's first symbol, -1.0 for those moving against it
+    pub orientation: BTreeMap<String, f64>,
+}
+
+impl CorrelationCluster {
+    /// Whether the cluster contains `symbol`
+    pub fn contains(&self, symbol: &str) -> bool {
+        self.orientation.contains_key(symbol)
+    }
+
+    /// Directional exposure of signed notionals (positive long), netting opposite bets
+    pub fn exposure(&self, notionals: &BTreeMap<String, f64>) -> f64 {
+        self.orientation.iter()
+            .map(|(symbol, orientation)| notionals.get(symbol).copied().unwrap_or(0.0) * orientation)
+            .sum::<f64>()
+            .abs()
+    }
+}
+
+/// Rolling cross-asset correlation analyzer
+#[derive(Debug, Clone)]
+pub struct CorrelationAnalyzer {
+    /// Configuration
+    config: CorrelationFilterConfig,
+
+    /// Recent prices by symbol
+    prices: HashMap<String, VecDeque<f64>>,
+}
+
+impl CorrelationAnalyzer {
+    /// Create a new correlation analyzer
+    pub fn new(config: CorrelationFilterConfig) -> Self {
+        Self {
+            config,
+            prices: HashMap::new(),
+        }
+    }
+
+    /// Record the latest price of a symbol
+    pub fn update_price(&mut self, symbol: &str, price: f64) {
+        if price <= 0.0 || !price.is_finite() {
+            return;
+        }
+
+        let history = self.prices.entry(symbol.to_string()).or_insert_with(VecDeque::new);
+        history.push_back(price);
+        while history.len() > self.config.window + 1 {
+            history.pop_front();
+        }
+    }
+
+    /// Log returns of a symbol's recorded prices
+    pub fn returns(&self, symbol: &str) -> Vec<f64> {
+        self.prices.get(symbol)
+            .map(|history| {
+                history.iter()
+                    .zip(history.iter().skip(1))
+                    .map(|(prev, next)| (next / prev).ln())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Rolling correlation of returns between two symbols
+    ///
+    /// Uses the most recent returns both symbols have in common; needs at least
+    /// half a window of data.
+    pub fn correlation(&self, a: &str, b: &str) -> Option<f64> {
+        if a == b {
+            return Some(1.0);
+        }
+
+        let returns_a = self.returns(a);
+        let returns_b = self.returns(b);
+        let n = returns_a.len().min(returns_b.len());
+        if n < (self.config.window / 2).max(3) {
+            return None;
+        }
+
+        pearson(&returns_a[returns_a.len() - n..], &returns_b[returns_b.len() - n..])
+    }
+
+    /// Rolling beta of `a`'s returns against `b`'s (covariance over `b`'s variance)
+    ///
+    /// Same data requirements as [`correlation`](Self::correlation).
+    pub fn beta(&self, a: &str, b: &str) -> Option<f64> {
+        if a == b {
+            return Some(1.0);
+        }
+
+        let returns_a = self.returns(a);
+        let returns_b = self.returns(b);
+        let n = returns_a.len().min(returns_b.len());
+        if n < (self.config.window / 2).max(3) {
+            return None;
+        }
+
+        let returns_a = &returns_a[returns_a.len() - n..];
+        let returns_b = &returns_b[returns_b.len() - n..];
+        let mean_a = returns_a.iter().sum::<f64>() / n as f64;
+        let mean_b = returns_b.iter().sum::<f64>() / n as f64;
+        let covariance: f64 = returns_a.iter().zip(returns_b).map(|(x, y)| (x - mean_a) * (y - mean_b)).sum();
+        let variance_b: f64 = returns_b.iter().map(|y| (y - mean_b).powi(2)).sum();
+        if variance_b <= 0.0 {
+            return None;
+        }
+
+        Some(covariance / variance_b)
+    }
+
+    /// Correlation matrix for a set of symbols (pairs without enough data are omitted)
+    pub fn correlation_matrix(&self, symbols: &[String]) -> HashMap<(String, String), f64> {
+        let mut matrix = HashMap::new();
+
+        for (i, a) in symbols.iter().enumerate() {
+            for b in symbols.iter().skip(i + 1) {
+                if let Some(correlation) = self.correlation(a, b) {
+                    matrix.insert((a.clone(), b.clone()), correlation);
+                    matrix.insert((b.clone(), a.clone()), correlation);
+                }
+            }
+        }
+
+        matrix
+    }
+
+    /// Rolling correlation matrix for a set of symbols
+    pub fn matrix(&self, symbols: &[String]) -> CorrelationMatrix {
+        let mut correlations: BTreeMap<String, BTreeMap<String, f64>> = BTreeMap::new();
+        for ((a, b), correlation) in self.correlation_matrix(symbols) {
+            correlations.entry(a).or_default().insert(b, correlation);
+        }
+
+        CorrelationMatrix {
+            symbols: symbols.to_vec(),
+            correlations,
+        }
+    }
+
+    /// Check whether a new position would concentrate exposure in a correlated cluster
+    ///
+    /// `open_positions` holds (symbol, is_long). Correlation is adjusted for direction:
+    /// a long in one asset and a short in a negatively correlated asset are the same bet.
+    pub fn check_exposure(&self, symbol: &str, is_long: bool, open_positions: &[(String, bool)]) -> CorrelationCheck {
+        let correlated_positions: Vec<(String, f64)> = open_positions.iter()
+            .filter_map(|(open_symbol, open_long)| {
+                let correlation = self.correlation(symbol, open_symbol)?;
+                let same_direction = if is_long == *open_long { 1.0 } else { -1.0 };
+                let exposure = correlation * same_direction;
+                (exposure >= self.config.max_correlation).then(|| (open_symbol.clone(), exposure))
+            })
+            .collect();
+
+        let approved = correlated_positions.len() < self.config.max_cluster_positions;
+        let reasoning = if approved {
+            format!("{} correlated open positions (limit {})", correlated_positions.len(), self.config.max_cluster_positions)
+        } else {
+            let names: Vec<String> = correlated_positions.iter()
+                .map(|(s, c)| format!("{} ({:.2})", s, c))
+                .collect();
+            format!("Rejected: would join correlated cluster with {}", names.join(", "))
+        };
+
+        debug!("Correlation check for {}: {}", symbol, reasoning);
+
+        CorrelationCheck {
+            symbol: symbol.to_string(),
+            approved,
+            correlated_positions,
+            reasoning,
+        }
+    }
+
+    /// Get configuration
+    pub fn get_config(&self) -> &CorrelationFilterConfig {
+        &self.config
+    }
+}
+
+/// Pearson correlation of two equally long series
+fn pearson(a: &[f64], b: &[f64]) -> Option<f64> {
+    let n = a.len() as f64;
+    let mean_a = a.iter().sum::<f64>() / n;
+    let mean_b = b.iter().sum::<f64>() / n;
+
+    let mut covariance = 0.0;
+    let mut variance_a = 0.0;
+    let mut variance_b = 0.0;
+    for (x, y) in a.iter().zip(b.iter()) {
+        covariance += (x - mean_a) * (y - mean_b);
+        variance_a += (x - mean_a).powi(2);
+        variance_b += (y - mean_b).powi(2);
+    }
+
+    if variance_a <= 0.0 || variance_b <= 0.0 {
+        return None;
+    }
+
+    Some(covariance / (variance_a.sqrt() * variance_b.sqrt()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rejects_correlated_cluster() {
+        let mut analyzer = CorrelationAnalyzer::new(CorrelationFilterConfig {
+            window: 20,
+            ..CorrelationFilterConfig::default()
+        });
+
+        for i in 0..30 {
+            let wave = (i as f64 * 0.9).sin();
+            analyzer.update_price("BTCUSDT", 100.0 + wave);
+            analyzer.update_price("ETHUSDT", 50.0 + wave * 0.5);
+            analyzer.update_price("XRPUSDT", 10.0 + (i as f64 * 2.3).cos() * 0.1);
+        }
+
+        let open = vec![("BTCUSDT".to_string(), true)];
+        assert!(!analyzer.check_exposure("ETHUSDT", true, &open).approved);
+        assert!(analyzer.check_exposure("ETHUSDT", false, &open).approved);
+        assert!(analyzer.check_exposure("XRPUSDT", true, &open).approved);
+    }
+
+    #[test]
+    fn test_clusters_orient_inverse_members() {
+        let mut analyzer = CorrelationAnalyzer::new(CorrelationFilterConfig {
+            window: 20,
+            ..CorrelationFilterConfig::default()
+        });
+
+        for i in 0..30 {
+            let wave = (i as f64 * 0.9).sin();
+            analyzer.update_price("BTCUSDT", 100.0 + wave);
+            analyzer.update_price("ETHUSDT", 50.0 + wave * 0.5);
+            analyzer.update_price("BTCDOWN", 20.0 - wave * 0.2);
+            analyzer.update_price("XRPUSDT", 10.0 + (i as f64 * 2.3).cos() * 0.1);
+        }
+
+        let symbols: Vec<String> = ["BTCUSDT", "ETHUSDT", "BTCDOWN", "XRPUSDT"].iter().map(|s| s.to_string()).collect();
+        let clusters = analyzer.matrix(&symbols).clusters(&symbols, 0.7);
+        assert_eq!(clusters.len(), 2);
+        assert_eq!(clusters[0].orientation["BTCDOWN"], -1.0);
+        assert!(!clusters[0].contains("XRPUSDT"));
+
+        // Long BTC and short its inverse are one bet; long ETH against short BTC nets out
+        let same_bet = BTreeMap::from([("BTCUSDT".to_string(), 100.0), ("BTCDOWN".to_string(), -50.0)]);
+        assert!((clusters[0].exposure(&same_bet) - 150.0).abs() < 1e-9);
+        let hedged = BTreeMap::from([("BTCUSDT".to_string(), -100.0), ("ETHUSDT".to_string(), 100.0)]);
+        assert!(clusters[0].exposure(&hedged).abs() < 1e-9);
+    }
+}
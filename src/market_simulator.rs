@@ -4,6 +4,8 @@
 //! and strategy validation.
 
 use std::collections::HashMap;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
 use serde::{Deserialize, Serialize};
 use anyhow::Result;
 
@@ -25,6 +27,22 @@ pub struct SimulationConfig {
     pub initial_capital: f64,
     pub symbols: Vec<String>,
     pub timeframe: String,
+    /// RNG seed for reproducible simulations (random when unset)
+    #[serde(default)]
+    pub seed: Option<u64>,
+}
+
+impl Default for SimulationConfig {
+    fn default() -> Self {
+        Self {
+            start_time: 0,
+            end_time: 0,
+            initial_capital: 0.0,
+            symbols: Vec::new(),
+            timeframe: "1h".to_string(),
+            seed: None,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -44,19 +62,36 @@ pub struct MarketSimulator {
     market_data: HashMap<String, Vec<MarketData>>,
     current_time: u64,
     current_capital: f64,
+    rng: StdRng,
 }
 
 impl MarketSimulator {
     pub fn new(config: SimulationConfig) -> Self {
         let initial_capital = config.initial_capital;
+        let rng = match config.seed {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => StdRng::from_entropy(),
+        };
         Self {
             config,
             market_data: HashMap::new(),
             current_time: 0,
             current_capital: initial_capital,
+            rng,
         }
     }
 
+    /// Random number generator for stochastic simulation, seeded from the config
+    pub fn rng(&mut self) -> &mut StdRng {
+        &mut self.rng
+    }
+
+    /// Restart the random sequence from a seed
+    pub fn reseed(&mut self, seed: u64) {
+        self.config.seed = Some(seed);
+        self.rng = StdRng::seed_from_u64(seed);
+    }
+
     pub fn load_market_data(&mut self, symbol: String, data: Vec<MarketData>) {
         self.market_data.insert(symbol, data);
     }
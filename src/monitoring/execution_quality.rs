@@ -0,0 +1,333 @@
+//! Execution Quality Monitor
+//!
+//! This module performs transaction cost analysis on live orders: for every order
+//! it records the intended (decision) price against the realized fill price and the
+//! submit-to-fill latency, and aggregates slippage and latency per symbol and per
+//! strategy. The observed per-symbol slippage is exported back to the backtest
+//! slippage model and to position sizing so both use costs seen in practice.
+
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use chrono::{DateTime, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+use tracing::debug;
+
+use crate::backtest::costs::SlippageModelConfig;
+use crate::exchange::bybit::types::BybitExecution;
+
+/// Order submitted and waiting for its fill
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct PendingExecution {
+    symbol: String,
+    strategy: String,
+    is_buy: bool,
+    intended_price: f64,
+    submitted_at: DateTime<Utc>,
+}
+
+/// Intended vs realized execution of one order
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExecutionSample {
+    /// Order ID
+    pub order_id: String,
+
+    /// Symbol
+    pub symbol: String,
+
+    /// Strategy that originated the order
+    pub strategy: String,
+
+    /// Whether the order bought
+    pub is_buy: bool,
+
+    /// Price at decision time
+    pub intended_price: f64,
+
+    /// Volume-weighted fill price
+    pub fill_price: f64,
+
+    /// Filled quantity
+    pub quantity: f64,
+
+    /// Submission time
+    pub submitted_at: DateTime<Utc>,
+
+    /// Time of the (last) fill
+    pub filled_at: DateTime<Utc>,
+}
+
+impl ExecutionSample {
+    /// Slippage in bps of the intended price; positive when the fill was worse
+    pub fn slippage_bps(&self) -> f64 {
+        if self.intended_price <= 0.0 {
+            return 0.0;
+        }
+        let difference = if self.is_buy {
+            self.fill_price - self.intended_price
+        } else {
+            self.intended_price - self.fill_price
+        };
+        difference / self.intended_price * 10_000.0
+    }
+
+    /// Slippage cost in quote currency
+    pub fn slippage_cost(&self) -> f64 {
+        self.slippage_bps() / 10_000.0 * self.intended_price * self.quantity
+    }
+
+    /// Submit-to-fill latency in milliseconds
+    pub fn latency_ms(&self) -> i64 {
+        (self.filled_at - self.submitted_at).num_milliseconds().max(0)
+    }
+}
+
+/// Aggregated execution quality of a group of orders
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ExecutionStats {
+    /// Number of filled orders
+    pub count: usize,
+
+    /// Mean slippage in bps
+    pub mean_slippage_bps: f64,
+
+    /// Median slippage in bps
+    pub median_slippage_bps: f64,
+
+    /// 95th percentile slippage in bps
+    pub p95_slippage_bps: f64,
+
+    /// Total slippage cost in quote currency
+    pub total_slippage_cost: f64,
+
+    /// Mean submit-to-fill latency in milliseconds
+    pub mean_latency_ms: f64,
+
+    /// 95th percentile latency in milliseconds
+    pub p95_latency_ms: f64,
+}
+
+impl ExecutionStats {
+    fn from_samples<'a>(samples: impl Iterator<Item = &'a ExecutionSample>) -> Self {
+        let samples: Vec<&ExecutionSample> = samples.collect();
+        if samples.is_empty() {
+            return Self::default();
+        }
+
+        let mut slippage: Vec<f64> = samples.iter().map(|s| s.slippage_bps()).collect();
+        let mut latency: Vec<f64> = samples.iter().map(|s| s.latency_ms() as f64).collect();
+        slippage.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+        latency.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+        let count = samples.len();
+
+        Self {
+            count,
+            mean_slippage_bps: slippage.iter().sum::<f64>() / count as f64,
+            median_slippage_bps: percentile(&slippage, 0.5),
+            p95_slippage_bps: percentile(&slippage, 0.95),
+            total_slippage_cost: samples.iter().map(|s| s.slippage_cost()).sum(),
+            mean_latency_ms: latency.iter().sum::<f64>() / count as f64,
+            p95_latency_ms: percentile(&latency, 0.95),
+        }
+    }
+}
+
+/// Nearest-rank percentile of sorted values
+fn percentile(sorted: &[f64], q: f64) -> f64 {
+    let rank = ((sorted.len() as f64 * q).ceil() as usize).clamp(1, sorted.len());
+    sorted[rank - 1]
+}
+
+/// Records and aggregates execution quality of live orders
+#[derive(Debug, Clone)]
+pub struct ExecutionQualityMonitor {
+    /// Orders waiting for their fill, by order ID
+    pending: HashMap<String, PendingExecution>,
+
+    /// Filled orders, oldest first
+    samples: VecDeque<ExecutionSample>,
+
+    /// Maximum samples kept
+    max_samples: usize,
+}
+
+impl ExecutionQualityMonitor {
+    /// Create a new monitor keeping at most `max_samples` filled orders
+    pub fn new(max_samples: usize) -> Self {
+        Self {
+            pending: HashMap::new(),
+            samples: VecDeque::new(),
+            max_samples: max_samples.max(1),
+        }
+    }
+
+    /// Record an order submission at its intended price
+    pub fn record_submission(
+        &mut self,
+        order_id: &str,
+        symbol: &str,
+        strategy: &str,
+        is_buy: bool,
+        intended_price: f64,
+        submitted_at: DateTime<Utc>,
+    ) {
+        self.pending.insert(order_id.to_string(), PendingExecution {
+            symbol: symbol.to_string(),
+            strategy: strategy.to_string(),
+            is_buy,
+            intended_price,
+            submitted_at,
+        });
+    }
+
+    /// Record the fill of a submitted order; returns the sample, if the order was known
+    pub fn record_fill(&mut self, order_id: &str, fill_price: f64, quantity: f64, filled_at: DateTime<Utc>) -> Option<ExecutionSample> {
+        let pending = self.pending.remove(order_id)?;
+        let sample = ExecutionSample {
+            order_id: order_id.to_string(),
+            symbol: pending.symbol,
+            strategy: pending.strategy,
+            is_buy: pending.is_buy,
+            intended_price: pending.intended_price,
+            fill_price,
+            quantity,
+            submitted_at: pending.submitted_at,
+            filled_at,
+        };
+        debug!("Execution {} on {}: {:.2} bps slippage, {} ms latency",
+               order_id, sample.symbol, sample.slippage_bps(), sample.latency_ms());
+
+        self.samples.push_back(sample.clone());
+        while self.samples.len() > self.max_samples {
+            self.samples.pop_front();
+        }
+        Some(sample)
+    }
+
+    /// Forget a submitted order that will not fill; returns whether it was pending
+    pub fn record_cancel(&mut self, order_id: &str) -> bool {
+        self.pending.remove(order_id).is_some()
+    }
+
+    /// Resolve pending orders from exchange execution records; returns the number filled
+    pub fn record_executions(&mut self, executions: &[BybitExecution]) -> usize {
+        // Aggregate partial fills into one volume-weighted fill per order
+        let mut fills: HashMap<&str, (f64, f64, i64)> = HashMap::new();
+        for execution in executions.iter().filter(|e| self.pending.contains_key(&e.order_id)) {
+            let fill = fills.entry(execution.order_id.as_str()).or_insert((0.0, 0.0, 0));
+            fill.0 += execution.exec_price * execution.exec_qty;
+            fill.1 += execution.exec_qty;
+            fill.2 = fill.2.max(execution.exec_time);
+        }
+
+        let mut filled = 0;
+        for (order_id, (value, quantity, time_ms)) in fills {
+            if quantity <= 0.0 {
+                continue;
+            }
+            let filled_at = Utc.timestamp_millis_opt(time_ms).single().unwrap_or_else(Utc::now);
+            if self.record_fill(order_id, value / quantity, quantity, filled_at).is_some() {
+                filled += 1;
+            }
+        }
+        filled
+    }
+
+    /// Number of orders waiting for a fill
+    pub fn get_pending_count(&self) -> usize {
+        self.pending.len()
+    }
+
+    /// Recorded samples, oldest first
+    pub fn get_samples(&self) -> &VecDeque<ExecutionSample> {
+        &self.samples
+    }
+
+    /// Stats of all recorded orders
+    pub fn get_overall_stats(&self) -> ExecutionStats {
+        ExecutionStats::from_samples(self.samples.iter())
+    }
+
+    /// Stats of one symbol
+    pub fn get_symbol_stats(&self, symbol: &str) -> ExecutionStats {
+        ExecutionStats::from_samples(self.samples.iter().filter(|s| s.symbol == symbol))
+    }
+
+    /// Stats per symbol
+    pub fn stats_by_symbol(&self) -> BTreeMap<String, ExecutionStats> {
+        self.group_stats(|s| &s.symbol)
+    }
+
+    /// Stats per strategy
+    pub fn stats_by_strategy(&self) -> BTreeMap<String, ExecutionStats> {
+        self.group_stats(|s| &s.strategy)
+    }
+
+    fn group_stats<F: Fn(&ExecutionSample) -> &String>(&self, key: F) -> BTreeMap<String, ExecutionStats> {
+        let mut groups: BTreeMap<String, Vec<&ExecutionSample>> = BTreeMap::new();
+        for sample in &self.samples {
+            groups.entry(key(sample).clone()).or_default().push(sample);
+        }
+        groups.into_iter()
+            .map(|(k, samples)| (k, ExecutionStats::from_samples(samples.into_iter())))
+            .collect()
+    }
+
+    /// Mean observed slippage (bps, never negative) for symbols with at least `min_samples` fills
+    pub fn expected_slippage_by_symbol(&self, min_samples: usize) -> HashMap<String, f64> {
+        self.stats_by_symbol()
+            .into_iter()
+            .filter(|(_, stats)| stats.count >= min_samples.max(1))
+            .map(|(symbol, stats)| (symbol, stats.mean_slippage_bps.max(0.0)))
+            .collect()
+    }
+
+    /// Slippage model calibrated on observed fills, falling back to `fallback_bps`
+    pub fn slippage_model_config(&self, min_samples: usize, fallback_bps: f64) -> SlippageModelConfig {
+        SlippageModelConfig::PerSymbol {
+            by_symbol_bps: self.expected_slippage_by_symbol(min_samples),
+            fallback_bps,
+        }
+    }
+}
+
+impl Default for ExecutionQualityMonitor {
+    fn default() -> Self {
+        Self::new(10_000)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    #[test]
+    fn test_slippage_and_latency_aggregation() {
+        let mut monitor = ExecutionQualityMonitor::default();
+        let t0 = Utc::now();
+        monitor.record_submission("a", "BTCUSDT", "momentum", true, 100.0, t0);
+        monitor.record_submission("b", "BTCUSDT", "mean_reversion", false, 100.0, t0);
+        monitor.record_submission("c", "ETHUSDT", "momentum", true, 50.0, t0);
+
+        monitor.record_fill("a", 100.1, 1.0, t0 + Duration::milliseconds(40));
+        monitor.record_fill("b", 99.95, 1.0, t0 + Duration::milliseconds(60));
+        assert!(monitor.record_fill("unknown", 1.0, 1.0, t0).is_none());
+        assert_eq!(monitor.get_pending_count(), 1);
+        assert!(monitor.record_cancel("c"));
+        assert!(!monitor.record_cancel("c"));
+        assert_eq!(monitor.get_pending_count(), 0);
+
+        let btc = monitor.get_symbol_stats("BTCUSDT");
+        assert_eq!(btc.count, 2);
+        assert!((btc.mean_slippage_bps - 7.5).abs() < 1e-6);
+        assert!((btc.mean_latency_ms - 50.0).abs() < 1e-9);
+        assert_eq!(monitor.stats_by_strategy()["momentum"].count, 1);
+
+        match monitor.slippage_model_config(2, 5.0) {
+            SlippageModelConfig::PerSymbol { by_symbol_bps, fallback_bps } => {
+                assert!((by_symbol_bps["BTCUSDT"] - 7.5).abs() < 1e-6);
+                assert_eq!(fallback_bps, 5.0);
+            }
+            other => panic!("unexpected config {:?}", other),
+        }
+    }
+}
@@ -0,0 +1,238 @@
+//! Agent Health Checker
+//!
+//! This module collects the heartbeats agents publish and turns the latest one
+//! of each agent into a `SystemHealth` for the dashboard. An agent whose last
+//! heartbeat is older than the stale limit is unhealthy; one whose inbox is
+//! filling up, whose last decision was slow or which keeps failing is degraded.
+//! The system takes the status of its worst agent.
+
+use std::collections::HashMap;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+
+use crate::engine::agent_runtime::AgentHeartbeat;
+
+/// Health check settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HealthCheckerConfig {
+    /// Seconds since an agent last worked after which it is unhealthy
+    pub stale_after_secs: i64,
+
+    /// Share of the inbox in use (0.0-1.0) at which an agent is degraded
+    pub max_queue_utilization: f64,
+
+    /// Last decision latency (milliseconds) above which an agent is degraded
+    pub max_latency_ms: f64,
+
+    /// New errors between two heartbeats at which an agent is degraded
+    pub max_new_errors: u64,
+}
+
+impl Default for HealthCheckerConfig {
+    fn default() -> Self {
+        Self {
+            stale_after_secs: 30,
+            max_queue_utilization: 0.8,
+            max_latency_ms: 1000.0,
+            max_new_errors: 5,
+        }
+    }
+}
+
+/// Health status, from best to worst
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum HealthStatus {
+    /// Working normally
+    Healthy,
+
+    /// Working, but slow, backed up or failing
+    Degraded,
+
+    /// Not working
+    Unhealthy,
+}
+
+/// Health of one agent
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AgentHealth {
+    /// Latest heartbeat
+    pub heartbeat: AgentHeartbeat,
+
+    /// Status
+    pub status: HealthStatus,
+
+    /// Errors since the previous heartbeat
+    pub new_errors: u64,
+
+    /// Why the agent is not healthy
+    pub issues: Vec<String>,
+}
+
+/// Health of all agents
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SystemHealth {
+    /// Check time
+    pub timestamp: DateTime<Utc>,
+
+    /// Status of the worst agent
+    pub status: HealthStatus,
+
+    /// Agents by name
+    pub agents: Vec<AgentHealth>,
+
+    /// Healthy agents
+    pub healthy_agents: usize,
+
+    /// Degraded agents
+    pub degraded_agents: usize,
+
+    /// Unhealthy agents
+    pub unhealthy_agents: usize,
+
+    /// Errors of all agents since start
+    pub total_errors: u64,
+
+    /// Deepest agent inbox
+    pub max_queue_depth: usize,
+
+    /// Slowest last decision (milliseconds)
+    pub max_latency_ms: f64,
+}
+
+/// Aggregates agent heartbeats into system health
+#[derive(Debug, Clone, Default)]
+pub struct HealthChecker {
+    /// Settings
+    config: HealthCheckerConfig,
+
+    /// Latest heartbeat by agent
+    heartbeats: HashMap<String, AgentHeartbeat>,
+
+    /// Error count of the heartbeat before the latest, by agent
+    previous_errors: HashMap<String, u64>,
+}
+
+impl HealthChecker {
+    /// Create a new health checker
+    pub fn new(config: HealthCheckerConfig) -> Self {
+        Self {
+            config,
+            heartbeats: HashMap::new(),
+            previous_errors: HashMap::new(),
+        }
+    }
+
+    /// Get the settings
+    pub fn get_config(&self) -> &HealthCheckerConfig {
+        &self.config
+    }
+
+    /// Record an agent's heartbeat
+    pub fn record(&mut self, heartbeat: AgentHeartbeat) {
+        let previous = self.heartbeats.get(&heartbeat.agent).map_or(heartbeat.error_count, |h| h.error_count);
+        if heartbeat.error_count > previous {
+            warn!("Agent {} reported {} new errors", heartbeat.agent, heartbeat.error_count - previous);
+        }
+        self.previous_errors.insert(heartbeat.agent.clone(), previous);
+        self.heartbeats.insert(heartbeat.agent.clone(), heartbeat);
+    }
+
+    /// Forget an agent that was stopped on purpose
+    pub fn remove(&mut self, agent: &str) {
+        self.heartbeats.remove(agent);
+        self.previous_errors.remove(agent);
+    }
+
+    /// Health of one agent from its latest heartbeat
+    fn agent_health(&self, heartbeat: &AgentHeartbeat, now: DateTime<Utc>) -> AgentHealth {
+        let new_errors = heartbeat.error_count
+            .saturating_sub(self.previous_errors.get(&heartbeat.agent).copied().unwrap_or(0));
+        let mut status = HealthStatus::Healthy;
+        let mut issues = Vec::new();
+
+        let silent = now - heartbeat.timestamp;
+        if silent > Duration::seconds(self.config.stale_after_secs) {
+            status = HealthStatus::Unhealthy;
+            issues.push(format!("no heartbeat for {}s", silent.num_seconds()));
+        }
+
+        let mut degrade = |issue: String| {
+            status = status.max(HealthStatus::Degraded);
+            issues.push(issue);
+        };
+        let utilization = heartbeat.queue_depth as f64 / heartbeat.queue_capacity.max(1) as f64;
+        if utilization >= self.config.max_queue_utilization {
+            degrade(format!("inbox {:.0}% full", utilization * 100.0));
+        }
+        if heartbeat.last_latency_ms > self.config.max_latency_ms {
+            degrade(format!("last decision took {:.0}ms", heartbeat.last_latency_ms));
+        }
+        if new_errors >= self.config.max_new_errors {
+            degrade(format!("{} new errors", new_errors));
+        }
+
+        AgentHealth { heartbeat: heartbeat.clone(), status, new_errors, issues }
+    }
+
+    /// Health of every agent that has reported
+    pub fn check(&self, now: DateTime<Utc>) -> SystemHealth {
+        let mut agents: Vec<AgentHealth> = self.heartbeats.values()
+            .map(|heartbeat| self.agent_health(heartbeat, now))
+            .collect();
+        agents.sort_by(|a, b| a.heartbeat.agent.cmp(&b.heartbeat.agent));
+        let count = |status: HealthStatus| agents.iter().filter(|a| a.status == status).count();
+
+        SystemHealth {
+            timestamp: now,
+            status: agents.iter().map(|a| a.status).max().unwrap_or(HealthStatus::Healthy),
+            healthy_agents: count(HealthStatus::Healthy),
+            degraded_agents: count(HealthStatus::Degraded),
+            unhealthy_agents: count(HealthStatus::Unhealthy),
+            total_errors: agents.iter().map(|a| a.heartbeat.error_count).sum(),
+            max_queue_depth: agents.iter().map(|a| a.heartbeat.queue_depth).max().unwrap_or(0),
+            max_latency_ms: agents.iter().map(|a| a.heartbeat.last_latency_ms).fold(0.0, f64::max),
+            agents,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn heartbeat(agent: &str, timestamp: DateTime<Utc>, queue_depth: usize, error_count: u64) -> AgentHeartbeat {
+        AgentHeartbeat {
+            agent: agent.to_string(),
+            timestamp,
+            queue_depth,
+            queue_capacity: 100,
+            last_latency_ms: 5.0,
+            messages_handled: 10,
+            error_count,
+        }
+    }
+
+    #[test]
+    fn test_system_takes_the_status_of_its_worst_agent() {
+        let now = Utc::now();
+        let mut checker = HealthChecker::new(HealthCheckerConfig::default());
+        assert_eq!(checker.check(now).status, HealthStatus::Healthy);
+
+        checker.record(heartbeat("news", now, 2, 0));
+        checker.record(heartbeat("whale", now, 90, 1));
+        checker.record(heartbeat("whale", now, 90, 7));
+        let health = checker.check(now);
+        assert_eq!(health.status, HealthStatus::Degraded);
+        assert_eq!((health.healthy_agents, health.degraded_agents), (1, 1));
+        assert_eq!(health.agents[1].new_errors, 6);
+        assert_eq!(health.agents[1].issues.len(), 2);
+        assert_eq!((health.total_errors, health.max_queue_depth), (7, 90));
+
+        checker.record(heartbeat("funding", now - Duration::seconds(120), 0, 0));
+        let health = checker.check(now);
+        assert_eq!(health.status, HealthStatus::Unhealthy);
+        assert_eq!(health.unhealthy_agents, 1);
+    }
+}
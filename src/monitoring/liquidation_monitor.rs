@@ -0,0 +1,242 @@
+//! Liquidation Proximity Monitor
+//!
+//! This module watches how far the mark price of each open position is from its
+//! liquidation price, as computed by the position calculator, and raises alerts
+//! that escalate through warning, critical and emergency buffers. An alert is
+//! raised only when a position enters a tighter buffer than the last one it was
+//! alerted for; moving back out re-arms the lower levels. At the emergency level
+//! the position can optionally be closed straight away.
+
+use std::collections::HashMap;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+
+use crate::position::portfolio::PortfolioExposure;
+
+/// Topic used for liquidation proximity alerts (`Message::Custom`)
+pub const LIQUIDATION_ALERT_TOPIC: &str = "liquidation_alert";
+
+/// Buffers as adverse move from mark to liquidation, in %
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LiquidationAlertConfig {
+    /// Distance below which a warning is raised
+    pub warning_buffer_pct: f64,
+
+    /// Distance below which a critical alert is raised
+    pub critical_buffer_pct: f64,
+
+    /// Distance below which an emergency alert is raised
+    pub emergency_buffer_pct: f64,
+
+    /// Whether positions reaching the emergency buffer are closed
+    pub auto_deleverage: bool,
+}
+
+impl Default for LiquidationAlertConfig {
+    fn default() -> Self {
+        Self {
+            warning_buffer_pct: 15.0,
+            critical_buffer_pct: 8.0,
+            emergency_buffer_pct: 4.0,
+            auto_deleverage: false,
+        }
+    }
+}
+
+/// How close a position is to liquidation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum LiquidationAlertLevel {
+    /// Outside every buffer
+    Safe,
+
+    /// Inside the warning buffer
+    Warning,
+
+    /// Inside the critical buffer
+    Critical,
+
+    /// Inside the emergency buffer
+    Emergency,
+}
+
+/// Position moving closer to liquidation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LiquidationAlert {
+    /// Detection time
+    pub timestamp: DateTime<Utc>,
+
+    /// Position ID
+    pub position_id: String,
+
+    /// Symbol
+    pub symbol: String,
+
+    /// Liquidation price
+    pub liquidation_price: f64,
+
+    /// Adverse move from mark to liquidation in %
+    pub distance_percent: f64,
+
+    /// Buffer the position entered
+    pub level: LiquidationAlertLevel,
+
+    /// Whether the position should be closed
+    pub deleverage: bool,
+}
+
+/// Escalating liquidation proximity alerts
+#[derive(Debug, Clone, Default)]
+pub struct LiquidationMonitor {
+    /// Buffers
+    config: LiquidationAlertConfig,
+
+    /// Last level alerted per position
+    levels: HashMap<String, LiquidationAlertLevel>,
+
+    /// Alerts raised since start
+    alerts: Vec<LiquidationAlert>,
+}
+
+impl LiquidationMonitor {
+    /// Create a new liquidation monitor
+    pub fn new(config: LiquidationAlertConfig) -> Self {
+        Self {
+            config,
+            ..Self::default()
+        }
+    }
+
+    /// Get the buffers
+    pub fn get_config(&self) -> &LiquidationAlertConfig {
+        &self.config
+    }
+
+    /// Level of a position `distance_percent` away from liquidation
+    pub fn level_for(&self, distance_percent: f64) -> LiquidationAlertLevel {
+        if distance_percent <= self.config.emergency_buffer_pct {
+            LiquidationAlertLevel::Emergency
+        } else if distance_percent <= self.config.critical_buffer_pct {
+            LiquidationAlertLevel::Critical
+        } else if distance_percent <= self.config.warning_buffer_pct {
+            LiquidationAlertLevel::Warning
+        } else {
+            LiquidationAlertLevel::Safe
+        }
+    }
+
+    /// Check every position of an exposure snapshot and return the escalations
+    pub fn check(&mut self, exposure: &PortfolioExposure) -> Vec<LiquidationAlert> {
+        self.levels.retain(|id, _| exposure.liquidation.iter().any(|risk| &risk.position_id == id));
+
+        let mut alerts = Vec::new();
+        for risk in &exposure.liquidation {
+            let (Some(liquidation_price), Some(distance_percent)) = (risk.liquidation_price, risk.distance_percent) else {
+                continue;
+            };
+            let level = self.level_for(distance_percent);
+            let last = self.levels.insert(risk.position_id.clone(), level).unwrap_or(LiquidationAlertLevel::Safe);
+            if level <= last {
+                continue;
+            }
+
+            let deleverage = level == LiquidationAlertLevel::Emergency && self.config.auto_deleverage;
+            warn!("Position {} on {} {:.2}% from liquidation at {:.4} ({:?})",
+                  risk.position_id, risk.symbol, distance_percent, liquidation_price, level);
+            let alert = LiquidationAlert {
+                timestamp: exposure.timestamp,
+                position_id: risk.position_id.clone(),
+                symbol: risk.symbol.clone(),
+                liquidation_price,
+                distance_percent,
+                level,
+                deleverage,
+            };
+            self.alerts.push(alert.clone());
+            alerts.push(alert);
+        }
+
+        alerts
+    }
+
+    /// Last level alerted for a position
+    pub fn get_level(&self, position_id: &str) -> LiquidationAlertLevel {
+        self.levels.get(position_id).copied().unwrap_or(LiquidationAlertLevel::Safe)
+    }
+
+    /// Positions at the emergency level when auto-deleverage is on.
+    /// They stay listed until they leave the book, so a failed close is retried.
+    pub fn get_deleverage_positions(&self) -> Vec<String> {
+        if !self.config.auto_deleverage {
+            return Vec::new();
+        }
+        let mut positions: Vec<String> = self.levels.iter()
+            .filter(|(_, level)| **level == LiquidationAlertLevel::Emergency)
+            .map(|(id, _)| id.clone())
+            .collect();
+        positions.sort();
+        positions
+    }
+
+    /// Alerts raised since start
+    pub fn get_alerts(&self) -> &[LiquidationAlert] {
+        &self.alerts
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::position::portfolio::{LiquidationRisk, Portfolio};
+    use crate::position::position_manager::Position;
+
+    fn exposure_at(distance_percent: f64) -> PortfolioExposure {
+        let mut exposure = Portfolio::new(10.0).exposure_of(std::iter::empty::<&Position>(), 1000.0);
+        exposure.liquidation.push(LiquidationRisk {
+            position_id: "p1".to_string(),
+            symbol: "BTCUSDT".to_string(),
+            liquidation_price: Some(90.0),
+            distance_percent: Some(distance_percent),
+        });
+        exposure
+    }
+
+    #[test]
+    fn test_alerts_escalate_once_per_level() {
+        let mut monitor = LiquidationMonitor::new(LiquidationAlertConfig {
+            auto_deleverage: true,
+            ..LiquidationAlertConfig::default()
+        });
+
+        assert!(monitor.check(&exposure_at(20.0)).is_empty());
+
+        let alerts = monitor.check(&exposure_at(12.0));
+        assert_eq!(alerts.len(), 1);
+        assert_eq!(alerts[0].level, LiquidationAlertLevel::Warning);
+        assert!(!alerts[0].deleverage);
+        assert!(monitor.check(&exposure_at(10.0)).is_empty());
+
+        // Jumping straight into the emergency buffer raises a single alert
+        let alerts = monitor.check(&exposure_at(3.0));
+        assert_eq!(alerts.len(), 1);
+        assert_eq!(alerts[0].level, LiquidationAlertLevel::Emergency);
+        assert!(alerts[0].deleverage);
+
+        // Still flagged on later passes until the position is gone
+        assert!(monitor.check(&exposure_at(2.0)).is_empty());
+        assert_eq!(monitor.get_deleverage_positions(), vec!["p1".to_string()]);
+
+        // Recovering re-arms the tighter buffers
+        assert!(monitor.check(&exposure_at(9.0)).is_empty());
+        assert_eq!(monitor.get_level("p1"), LiquidationAlertLevel::Warning);
+        assert!(monitor.get_deleverage_positions().is_empty());
+        assert_eq!(monitor.check(&exposure_at(7.0))[0].level, LiquidationAlertLevel::Critical);
+
+        let mut closed = exposure_at(7.0);
+        closed.liquidation.clear();
+        monitor.check(&closed);
+        assert_eq!(monitor.get_level("p1"), LiquidationAlertLevel::Safe);
+        assert_eq!(monitor.get_alerts().len(), 3);
+    }
+}
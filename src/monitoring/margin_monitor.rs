@@ -0,0 +1,232 @@
+//! Margin Monitor
+//!
+//! This module tracks initial and maintenance margin consumption against the
+//! wallet balance and plans pre-emptive deleveraging: once the maintenance margin
+//! ratio (or initial margin usage) crosses its threshold, it picks the symbols to
+//! trim, largest maintenance margin first, so the ratio falls back to a target well
+//! before the exchange would start liquidating.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tracing::{info, warn};
+
+use crate::position::portfolio::PortfolioExposure;
+
+/// Margin thresholds as fractions of wallet balance
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MarginMonitorConfig {
+    /// Maintenance margin ratio at which a warning is raised
+    pub warning_ratio: f64,
+
+    /// Maintenance margin ratio at which positions are trimmed
+    pub deleverage_ratio: f64,
+
+    /// Maintenance margin ratio trimming brings the account back to
+    pub target_ratio: f64,
+
+    /// Initial margin usage at which positions are trimmed
+    pub max_initial_usage: f64,
+
+    /// Initial margin usage trimming brings the account back to
+    pub target_initial_usage: f64,
+}
+
+impl Default for MarginMonitorConfig {
+    fn default() -> Self {
+        Self {
+            warning_ratio: 0.3,
+            deleverage_ratio: 0.5,
+            target_ratio: 0.3,
+            max_initial_usage: 0.9,
+            target_initial_usage: 0.7,
+        }
+    }
+}
+
+/// Margin health level
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MarginLevel {
+    /// Below the warning threshold
+    Healthy,
+
+    /// Above the warning threshold
+    Warning,
+
+    /// Above a deleverage threshold
+    Deleverage,
+}
+
+/// Margin consumption at one point in time
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MarginReport {
+    /// Snapshot time
+    pub timestamp: DateTime<Utc>,
+
+    /// Wallet balance the ratios refer to
+    pub wallet_balance: f64,
+
+    /// Initial margin posted
+    pub initial_margin: f64,
+
+    /// Maintenance margin required
+    pub maintenance_margin: f64,
+
+    /// Initial margin divided by wallet balance
+    pub initial_margin_usage: f64,
+
+    /// Maintenance margin divided by wallet balance (liquidation at 1.0)
+    pub maintenance_margin_ratio: f64,
+
+    /// Health level
+    pub level: MarginLevel,
+}
+
+/// Notional to cut from one symbol
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DeleverageAction {
+    /// Symbol
+    pub symbol: String,
+
+    /// Notional to close
+    pub reduce_notional: f64,
+
+    /// Threshold that triggered the cut
+    pub reason: String,
+}
+
+/// Watches margin consumption and plans deleveraging
+#[derive(Debug, Clone, Default)]
+pub struct MarginMonitor {
+    /// Thresholds
+    config: MarginMonitorConfig,
+}
+
+impl MarginMonitor {
+    /// Create a new margin monitor
+    pub fn new(config: MarginMonitorConfig) -> Self {
+        Self { config }
+    }
+
+    /// Get the thresholds
+    pub fn get_config(&self) -> &MarginMonitorConfig {
+        &self.config
+    }
+
+    /// Margin consumption of an exposure snapshot
+    pub fn report(&self, exposure: &PortfolioExposure) -> MarginReport {
+        let ratio = |margin: f64| if exposure.equity > 0.0 { margin / exposure.equity } else if margin > 0.0 { f64::INFINITY } else { 0.0 };
+        let initial_margin_usage = ratio(exposure.margin_used);
+        let maintenance_margin_ratio = ratio(exposure.maintenance_margin);
+
+        let level = if maintenance_margin_ratio >= self.config.deleverage_ratio
+            || initial_margin_usage >= self.config.max_initial_usage
+        {
+            MarginLevel::Deleverage
+        } else if maintenance_margin_ratio >= self.config.warning_ratio {
+            MarginLevel::Warning
+        } else {
+            MarginLevel::Healthy
+        };
+
+        MarginReport {
+            timestamp: exposure.timestamp,
+            wallet_balance: exposure.equity,
+            initial_margin: exposure.margin_used,
+            maintenance_margin: exposure.maintenance_margin,
+            initial_margin_usage,
+            maintenance_margin_ratio,
+            level,
+        }
+    }
+
+    /// Symbols to trim, and by how much, to get back to the target ratios
+    pub fn plan_deleverage(&self, exposure: &PortfolioExposure) -> Vec<DeleverageAction> {
+        let report = self.report(exposure);
+        match report.level {
+            MarginLevel::Healthy => return Vec::new(),
+            MarginLevel::Warning => {
+                warn!("Maintenance margin ratio {:.1}% above warning level", report.maintenance_margin_ratio * 100.0);
+                return Vec::new();
+            }
+            MarginLevel::Deleverage => {}
+        }
+
+        // Share of each margin that has to go; trimming a symbol frees both in proportion to its notional
+        let equity = exposure.equity.max(0.0);
+        let excess_share = |margin: f64, target: f64| if margin > 0.0 { ((margin - target * equity) / margin).max(0.0) } else { 0.0 };
+        let maintenance_excess = exposure.maintenance_margin * excess_share(exposure.maintenance_margin, self.config.target_ratio);
+        let initial_excess = exposure.margin_used * excess_share(exposure.margin_used, self.config.target_initial_usage);
+        let reason = if maintenance_excess > 0.0 && report.maintenance_margin_ratio >= self.config.deleverage_ratio {
+            format!("maintenance margin ratio {:.1}%", report.maintenance_margin_ratio * 100.0)
+        } else {
+            format!("initial margin usage {:.1}%", report.initial_margin_usage * 100.0)
+        };
+
+        let mut symbols: Vec<_> = exposure.symbols.iter().collect();
+        symbols.sort_by(|a, b| b.1.maintenance_margin.partial_cmp(&a.1.maintenance_margin).unwrap_or(std::cmp::Ordering::Equal));
+
+        let (mut maintenance_left, mut initial_left) = (maintenance_excess, initial_excess);
+        let mut actions = Vec::new();
+        for (symbol, symbol_exposure) in symbols {
+            if maintenance_left <= 0.0 && initial_left <= 0.0 {
+                break;
+            }
+            let notional = symbol_exposure.long_notional + symbol_exposure.short_notional;
+            if notional <= 0.0 {
+                continue;
+            }
+
+            let share_for = |left: f64, margin: f64| if margin > 0.0 { (left / margin).clamp(0.0, 1.0) } else { 0.0 };
+            let share = share_for(maintenance_left, symbol_exposure.maintenance_margin)
+                .max(share_for(initial_left, symbol_exposure.margin));
+            if share <= 0.0 {
+                continue;
+            }
+
+            maintenance_left -= symbol_exposure.maintenance_margin * share;
+            initial_left -= symbol_exposure.margin * share;
+            actions.push(DeleverageAction {
+                symbol: symbol.clone(),
+                reduce_notional: notional * share,
+                reason: reason.clone(),
+            });
+        }
+
+        info!("Deleveraging {} symbols on {}", actions.len(), reason);
+        actions
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::position::calculator::MaintenanceMarginTiers;
+    use crate::position::portfolio::Portfolio;
+    use crate::position::position_manager::{Position, PositionDirection};
+
+    #[test]
+    fn test_deleverage_plan_restores_target_ratio() {
+        let mut portfolio = Portfolio::new(20.0);
+        portfolio.set_leverage("BTCUSDT", 10.0);
+        portfolio.set_leverage("ETHUSDT", 10.0);
+        portfolio.set_maintenance_tiers("BTCUSDT", MaintenanceMarginTiers::flat(0.05));
+        portfolio.set_maintenance_tiers("ETHUSDT", MaintenanceMarginTiers::flat(0.05));
+
+        let positions = vec![
+            Position::new("BTCUSDT".to_string(), PositionDirection::Long, 1.0, 600.0),
+            Position::new("ETHUSDT".to_string(), PositionDirection::Short, 1.0, 400.0),
+        ];
+        let monitor = MarginMonitor::default();
+
+        // 50 maintenance margin on 200 equity: 25%, healthy
+        assert_eq!(monitor.report(&portfolio.exposure_of(&positions, 200.0)).level, MarginLevel::Healthy);
+
+        // 50 maintenance margin on 80 equity: 62.5%, trim 26 of it, all from BTC
+        let exposure = portfolio.exposure_of(&positions, 80.0);
+        assert_eq!(monitor.report(&exposure).level, MarginLevel::Deleverage);
+        let actions = monitor.plan_deleverage(&exposure);
+        assert_eq!(actions.len(), 1);
+        assert_eq!(actions[0].symbol, "BTCUSDT");
+        assert!((actions[0].reduce_notional - 520.0).abs() < 1e-6);
+    }
+}
@@ -6,9 +6,21 @@
 pub mod performance_monitor;
 pub mod real_time_monitor;
 pub mod unified_error_manager;
+pub mod execution_quality;
+pub mod margin_monitor;
+pub mod liquidation_monitor;
 pub mod system_monitor;
+pub mod surveillance;
+pub mod risk_report;
+pub mod health_checker;
 
 pub use performance_monitor::*;
 pub use real_time_monitor::*;
 pub use unified_error_manager::*;
+pub use execution_quality::*;
+pub use margin_monitor::*;
+pub use liquidation_monitor::*;
 pub use system_monitor::*;
+pub use surveillance::*;
+pub use risk_report::*;
+pub use health_checker::*;
@@ -0,0 +1,267 @@
+//! Performance Monitor
+//!
+//! This module attributes every closed trade to the strategy or agent that
+//! originated it and aggregates P&L, win rate and drawdown per strategy, so the
+//! `GodKernel` can promote or retire strategies based on real results.
+
+use std::collections::{HashMap, VecDeque};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tracing::debug;
+
+use crate::agents::feedback_loop::AgentPerformance;
+
+/// Maximum number of attributed trades to keep per strategy
+const MAX_TRADES_PER_STRATEGY: usize = 1000;
+
+/// Closed trade attributed to its originating strategy
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TradeAttribution {
+    /// Trade ID
+    pub trade_id: String,
+
+    /// Originating strategy or agent
+    pub strategy: String,
+
+    /// Symbol
+    pub symbol: String,
+
+    /// Realized P&L (net of fees and funding)
+    pub pnl: f64,
+
+    /// Realized P&L before fees and funding
+    #[serde(default)]
+    pub gross_pnl: f64,
+
+    /// Fees paid
+    #[serde(default)]
+    pub fees: f64,
+
+    /// Net funding paid (negative when received)
+    #[serde(default)]
+    pub funding: f64,
+
+    /// Return on the position in %
+    pub roi: f64,
+
+    /// Entry time
+    pub entry_time: DateTime<Utc>,
+
+    /// Exit time
+    pub exit_time: DateTime<Utc>,
+}
+
+/// Aggregated performance of one strategy
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StrategyPerformance {
+    /// Strategy name
+    pub strategy: String,
+
+    /// Number of closed trades
+    pub total_trades: usize,
+
+    /// Number of winning trades
+    pub winning_trades: usize,
+
+    /// Number of losing trades
+    pub losing_trades: usize,
+
+    /// Net realized P&L
+    pub total_pnl: f64,
+
+    /// Realized P&L before fees and funding
+    #[serde(default)]
+    pub total_gross_pnl: f64,
+
+    /// Fees paid
+    #[serde(default)]
+    pub total_fees: f64,
+
+    /// Net funding paid
+    #[serde(default)]
+    pub total_funding: f64,
+
+    /// Sum of winning P&L
+    pub gross_profit: f64,
+
+    /// Sum of losing P&L (positive number)
+    pub gross_loss: f64,
+
+    /// Win rate in %
+    pub win_rate: f64,
+
+    /// Highest cumulative P&L reached
+    pub peak_pnl: f64,
+
+    /// Maximum drawdown of cumulative P&L (absolute)
+    pub max_drawdown: f64,
+
+    /// Consecutive losing trades at the end of the record
+    pub consecutive_losses: usize,
+
+    /// Last trade time
+    pub last_trade: Option<DateTime<Utc>>,
+
+    /// Recent trades
+    pub recent_trades: VecDeque<TradeAttribution>,
+}
+
+impl StrategyPerformance {
+    /// Create an empty record
+    pub fn new(strategy: &str) -> Self {
+        Self {
+            strategy: strategy.to_string(),
+            total_trades: 0,
+            winning_trades: 0,
+            losing_trades: 0,
+            total_pnl: 0.0,
+            total_gross_pnl: 0.0,
+            total_fees: 0.0,
+            total_funding: 0.0,
+            gross_profit: 0.0,
+            gross_loss: 0.0,
+            win_rate: 0.0,
+            peak_pnl: 0.0,
+            max_drawdown: 0.0,
+            consecutive_losses: 0,
+            last_trade: None,
+            recent_trades: VecDeque::new(),
+        }
+    }
+
+    /// Add a closed trade
+    pub fn record(&mut self, trade: TradeAttribution) {
+        self.total_trades += 1;
+        self.total_pnl += trade.pnl;
+        self.total_gross_pnl += trade.gross_pnl;
+        self.total_fees += trade.fees;
+        self.total_funding += trade.funding;
+
+        if trade.pnl > 0.0 {
+            self.winning_trades += 1;
+            self.gross_profit += trade.pnl;
+            self.consecutive_losses = 0;
+        } else if trade.pnl < 0.0 {
+            self.losing_trades += 1;
+            self.gross_loss += -trade.pnl;
+            self.consecutive_losses += 1;
+        }
+
+        self.win_rate = self.winning_trades as f64 / self.total_trades as f64 * 100.0;
+        self.peak_pnl = self.peak_pnl.max(self.total_pnl);
+        self.max_drawdown = self.max_drawdown.max(self.peak_pnl - self.total_pnl);
+        self.last_trade = Some(trade.exit_time);
+
+        self.recent_trades.push_back(trade);
+        while self.recent_trades.len() > MAX_TRADES_PER_STRATEGY {
+            self.recent_trades.pop_front();
+        }
+    }
+
+    /// Profit factor (gross profit / gross loss)
+    pub fn profit_factor(&self) -> f64 {
+        if self.gross_loss > 0.0 {
+            self.gross_profit / self.gross_loss
+        } else if self.gross_profit > 0.0 {
+            f64::INFINITY
+        } else {
+            0.0
+        }
+    }
+
+    /// Average P&L per trade
+    pub fn expectancy(&self) -> f64 {
+        if self.total_trades > 0 {
+            self.total_pnl / self.total_trades as f64
+        } else {
+            0.0
+        }
+    }
+
+    /// Performance score (-1.0 to 1.0) combining win rate and profit factor
+    pub fn score(&self) -> f64 {
+        if self.total_trades == 0 {
+            return 0.0;
+        }
+
+        let win_component = (self.win_rate / 100.0 - 0.5) * 2.0;
+        let profit_factor = self.profit_factor();
+        let pf_component = if profit_factor.is_infinite() {
+            1.0
+        } else {
+            (profit_factor - 1.0) / (profit_factor + 1.0)
+        };
+
+        (0.5 * win_component + 0.5 * pf_component).clamp(-1.0, 1.0)
+    }
+
+    /// Convert into the `AgentPerformance` record used by `FeedbackLoop` and `GodKernel`
+    pub fn to_agent_performance(&self) -> AgentPerformance {
+        AgentPerformance {
+            agent_name: self.strategy.clone(),
+            score: self.score(),
+            confidence: (self.total_trades as f64 / 30.0).min(1.0),
+            trade_count: self.total_trades,
+            success_rate: self.win_rate / 100.0,
+            avg_roi_contribution: if self.recent_trades.is_empty() {
+                0.0
+            } else {
+                self.recent_trades.iter().map(|t| t.roi).sum::<f64>() / self.recent_trades.len() as f64
+            },
+            last_updated: self.last_trade.unwrap_or_else(Utc::now),
+            recent_trades: self.recent_trades.iter().map(|t| t.trade_id.clone()).collect(),
+            mutation_eligible: false,
+            kill_eligible: false,
+            consecutive_failures: self.consecutive_losses,
+        }
+    }
+}
+
+/// Per-strategy performance monitor
+#[derive(Debug, Clone, Default)]
+pub struct PerformanceMonitor {
+    /// Performance by strategy
+    strategies: HashMap<String, StrategyPerformance>,
+}
+
+impl PerformanceMonitor {
+    /// Create a new performance monitor
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record a closed trade against its strategy
+    pub fn record_trade(&mut self, trade: TradeAttribution) {
+        debug!("Attributing trade {} ({:.4}) to {}", trade.trade_id, trade.pnl, trade.strategy);
+
+        self.strategies
+            .entry(trade.strategy.clone())
+            .or_insert_with(|| StrategyPerformance::new(&trade.strategy))
+            .record(trade);
+    }
+
+    /// Get performance for a strategy
+    pub fn get_strategy_performance(&self, strategy: &str) -> Option<&StrategyPerformance> {
+        self.strategies.get(strategy)
+    }
+
+    /// Get performance for all strategies
+    pub fn get_all_strategy_performance(&self) -> &HashMap<String, StrategyPerformance> {
+        &self.strategies
+    }
+
+    /// Strategies ranked by score (best first), limited to those with `min_trades`
+    pub fn ranked_strategies(&self, min_trades: usize) -> Vec<&StrategyPerformance> {
+        let mut ranked: Vec<&StrategyPerformance> = self.strategies.values()
+            .filter(|p| p.total_trades >= min_trades)
+            .collect();
+
+        ranked.sort_by(|a, b| b.score().partial_cmp(&a.score()).unwrap_or(std::cmp::Ordering::Equal));
+        ranked
+    }
+
+    /// Total P&L across strategies
+    pub fn total_pnl(&self) -> f64 {
+        self.strategies.values().map(|p| p.total_pnl).sum()
+    }
+}
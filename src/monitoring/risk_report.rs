@@ -0,0 +1,329 @@
+//! Daily Risk Report
+//!
+//! This module keeps a history of risk snapshots taken from the live exposure,
+//! VaR and limit data, and turns one UTC day of it into a `DailyRiskReport`:
+//! exposure, VaR, leverage, margin usage, concentration and limit utilization,
+//! with the day's peaks and charts. Like the backtest report it is written as
+//! JSON and as a self-contained HTML page.
+
+use std::collections::{BTreeMap, VecDeque};
+use std::fs;
+use std::path::Path;
+use anyhow::Result;
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use tracing::info;
+
+use crate::backtest::report::{escape, sign_class, svg_line_chart};
+use crate::position::portfolio::PortfolioExposure;
+use crate::risk::limits::ExposureLimitTable;
+use crate::risk::var::PortfolioVar;
+
+/// Seconds between risk snapshots
+pub const SNAPSHOT_INTERVAL_SECS: i64 = 60;
+
+/// Risk measures at one point in time
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RiskSnapshot {
+    /// Snapshot time
+    pub timestamp: DateTime<Utc>,
+
+    /// Account equity
+    pub equity: f64,
+
+    /// Long plus short notional
+    pub gross_exposure: f64,
+
+    /// Long minus short notional
+    pub net_exposure: f64,
+
+    /// Gross exposure divided by equity
+    pub leverage: f64,
+
+    /// Margin used as a fraction of equity
+    pub margin_usage: f64,
+
+    /// Historical VaR of the book, if estimated
+    pub var: Option<f64>,
+
+    /// Historical CVaR of the book, if estimated
+    pub cvar: Option<f64>,
+
+    /// Symbol with the largest absolute net notional
+    pub largest_symbol: Option<String>,
+
+    /// Largest symbol's share of gross exposure (0.0-1.0)
+    pub concentration: f64,
+
+    /// Exposure of each limited scope as a fraction of its cap
+    pub limit_utilization: BTreeMap<String, f64>,
+}
+
+impl RiskSnapshot {
+    /// Take a snapshot from the current exposure, VaR and limits
+    pub fn capture(
+        exposure: &PortfolioExposure,
+        var: Option<&PortfolioVar>,
+        limits: &ExposureLimitTable,
+        now: DateTime<Utc>,
+    ) -> Self {
+        let largest = exposure.symbols.iter()
+            .max_by(|a, b| a.1.net_notional.abs().total_cmp(&b.1.net_notional.abs()));
+        let concentration = match largest {
+            Some((_, e)) if exposure.gross_exposure > 0.0 => e.net_notional.abs() / exposure.gross_exposure,
+            _ => 0.0,
+        };
+
+        Self {
+            timestamp: now,
+            equity: exposure.equity,
+            gross_exposure: exposure.gross_exposure,
+            net_exposure: exposure.net_exposure,
+            leverage: exposure.leverage,
+            margin_usage: exposure.margin_usage,
+            var: var.map(|v| v.historical.var),
+            cvar: var.map(|v| v.historical.cvar),
+            largest_symbol: largest.map(|(symbol, _)| symbol.clone()),
+            concentration,
+            limit_utilization: limits.utilization(exposure, now),
+        }
+    }
+}
+
+/// Rolling history of risk snapshots
+#[derive(Debug, Clone)]
+pub struct RiskHistory {
+    /// Snapshots, oldest first
+    snapshots: VecDeque<RiskSnapshot>,
+
+    /// Snapshots kept
+    max_snapshots: usize,
+}
+
+impl RiskHistory {
+    /// Create a history keeping `max_snapshots`
+    pub fn new(max_snapshots: usize) -> Self {
+        Self {
+            snapshots: VecDeque::new(),
+            max_snapshots,
+        }
+    }
+
+    /// Add a snapshot
+    pub fn record(&mut self, snapshot: RiskSnapshot) {
+        self.snapshots.push_back(snapshot);
+        while self.snapshots.len() > self.max_snapshots {
+            self.snapshots.pop_front();
+        }
+    }
+
+    /// Snapshots of one UTC day, oldest first
+    pub fn get_day(&self, date: NaiveDate) -> Vec<&RiskSnapshot> {
+        self.snapshots.iter().filter(|s| s.timestamp.date_naive() == date).collect()
+    }
+
+    /// Latest snapshot
+    pub fn get_latest(&self) -> Option<&RiskSnapshot> {
+        self.snapshots.back()
+    }
+
+    /// Whether a snapshot interval has passed since the latest snapshot
+    pub fn is_due(&self, now: DateTime<Utc>) -> bool {
+        self.get_latest()
+            .map_or(true, |latest| (now - latest.timestamp).num_seconds() >= SNAPSHOT_INTERVAL_SECS)
+    }
+}
+
+impl Default for RiskHistory {
+    fn default() -> Self {
+        // One snapshot a minute for two days
+        Self::new(2 * 24 * 60)
+    }
+}
+
+/// Risk report of one UTC day
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DailyRiskReport {
+    /// Day covered
+    pub date: NaiveDate,
+
+    /// Generation time
+    pub generated_at: DateTime<Utc>,
+
+    /// Last snapshot of the day
+    pub latest: Option<RiskSnapshot>,
+
+    /// Highest gross exposure
+    pub peak_gross_exposure: f64,
+
+    /// Highest leverage
+    pub peak_leverage: f64,
+
+    /// Highest margin usage
+    pub peak_margin_usage: f64,
+
+    /// Highest VaR
+    pub peak_var: Option<f64>,
+
+    /// Highest concentration
+    pub peak_concentration: f64,
+
+    /// Highest utilization per limited scope
+    pub peak_limit_utilization: BTreeMap<String, f64>,
+
+    /// The day's snapshots
+    pub snapshots: Vec<RiskSnapshot>,
+}
+
+impl DailyRiskReport {
+    /// Build the report of `date` from the history
+    pub fn new(date: NaiveDate, history: &RiskHistory) -> Self {
+        let snapshots: Vec<RiskSnapshot> = history.get_day(date).into_iter().cloned().collect();
+        let peak = |f: fn(&RiskSnapshot) -> f64| snapshots.iter().map(f).fold(0.0, f64::max);
+
+        let mut peak_limit_utilization = BTreeMap::new();
+        for snapshot in &snapshots {
+            for (scope, utilization) in &snapshot.limit_utilization {
+                let entry = peak_limit_utilization.entry(scope.clone()).or_insert(0.0_f64);
+                *entry = entry.max(*utilization);
+            }
+        }
+
+        Self {
+            date,
+            generated_at: Utc::now(),
+            latest: snapshots.last().cloned(),
+            peak_gross_exposure: peak(|s| s.gross_exposure),
+            peak_leverage: peak(|s| s.leverage),
+            peak_margin_usage: peak(|s| s.margin_usage),
+            peak_var: snapshots.iter().filter_map(|s| s.var).reduce(f64::max),
+            peak_concentration: peak(|s| s.concentration),
+            peak_limit_utilization,
+            snapshots,
+        }
+    }
+
+    /// Serialize the report as pretty-printed JSON
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    /// Render the report as a self-contained HTML page
+    pub fn to_html(&self) -> String {
+        let title = format!("Risk Report {}", self.date);
+        let mut html = String::new();
+
+        html.push_str("<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n");
+        html.push_str(&format!("<title>{}</title>\n", title));
+        html.push_str("<style>\nbody{font-family:sans-serif;margin:24px;color:#222}\
+            table{border-collapse:collapse;margin-bottom:24px}\
+            td,th{border:1px solid #ccc;padding:4px 8px;text-align:right}\
+            th{background:#f0f0f0}.pos{color:#1a7f37}.neg{color:#c62828}\n</style>\n");
+        html.push_str("</head>\n<body>\n");
+        html.push_str(&format!("<h1>{}</h1>\n", title));
+
+        // Summary
+        let latest = self.latest.as_ref();
+        let optional = |value: Option<f64>| value.map_or("n/a".to_string(), |v| format!("${:.2}", v));
+        html.push_str("<h2>Summary</h2>\n<table>\n<tr><th></th><th>Close</th><th>Peak</th></tr>\n");
+        let rows = [
+            ("Equity", latest.map_or("n/a".to_string(), |s| format!("${:.2}", s.equity)), String::new()),
+            ("Gross Exposure", latest.map_or("n/a".to_string(), |s| format!("${:.2}", s.gross_exposure)),
+             format!("${:.2}", self.peak_gross_exposure)),
+            ("Net Exposure", latest.map_or("n/a".to_string(), |s| format!("${:.2}", s.net_exposure)), String::new()),
+            ("Leverage", latest.map_or("n/a".to_string(), |s| format!("{:.2}x", s.leverage)),
+             format!("{:.2}x", self.peak_leverage)),
+            ("Margin Usage", latest.map_or("n/a".to_string(), |s| format!("{:.2}%", s.margin_usage * 100.0)),
+             format!("{:.2}%", self.peak_margin_usage * 100.0)),
+            ("VaR", optional(latest.and_then(|s| s.var)), optional(self.peak_var)),
+            ("CVaR", optional(latest.and_then(|s| s.cvar)), String::new()),
+            ("Concentration", latest.map_or("n/a".to_string(), |s| format!(
+                "{:.1}% ({})", s.concentration * 100.0, escape(s.largest_symbol.as_deref().unwrap_or("-"))
+            )), format!("{:.1}%", self.peak_concentration * 100.0)),
+        ];
+        for (label, close, peak) in rows {
+            html.push_str(&format!("<tr><th>{}</th><td>{}</td><td>{}</td></tr>\n", label, close, peak));
+        }
+        html.push_str("</table>\n");
+
+        // Limit utilization
+        if !self.peak_limit_utilization.is_empty() {
+            html.push_str("<h2>Limit Utilization</h2>\n<table>\n<tr><th>Scope</th><th>Close</th><th>Peak</th></tr>\n");
+            for (scope, peak) in &self.peak_limit_utilization {
+                let close = latest.and_then(|s| s.limit_utilization.get(scope)).copied().unwrap_or(0.0);
+                html.push_str(&format!(
+                    "<tr><td>{}</td><td>{:.1}%</td><td class=\"{}\">{:.1}%</td></tr>\n",
+                    escape(scope), close * 100.0, sign_class(1.0 - peak), peak * 100.0
+                ));
+            }
+            html.push_str("</table>\n");
+        }
+
+        // Charts
+        let series = |f: fn(&RiskSnapshot) -> f64| self.snapshots.iter().map(f).collect::<Vec<f64>>();
+        html.push_str("<h2>Gross Exposure</h2>\n");
+        html.push_str(&svg_line_chart(&series(|s| s.gross_exposure), "#1565c0"));
+        html.push_str("<h2>Leverage</h2>\n");
+        html.push_str(&svg_line_chart(&series(|s| s.leverage), "#6a1b9a"));
+        html.push_str("<h2>Margin Usage (%)</h2>\n");
+        html.push_str(&svg_line_chart(&series(|s| s.margin_usage * 100.0), "#ef6c00"));
+        html.push_str("<h2>VaR</h2>\n");
+        html.push_str(&svg_line_chart(&series(|s| s.var.unwrap_or(0.0)), "#c62828"));
+        html.push_str("</body>\n</html>\n");
+
+        html
+    }
+
+    /// Write `risk-<date>.json` and `risk-<date>.html` into a directory
+    pub fn write(&self, dir: impl AsRef<Path>) -> Result<()> {
+        let dir = dir.as_ref();
+        let stem = format!("risk-{}", self.date);
+        fs::create_dir_all(dir)?;
+        fs::write(dir.join(format!("{}.json", stem)), self.to_json()?)?;
+        fs::write(dir.join(format!("{}.html", stem)), self.to_html())?;
+        info!("Wrote risk report {} to {}", stem, dir.display());
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::position::portfolio::{Portfolio, SymbolExposure};
+    use crate::position::position_manager::Position;
+    use crate::risk::limits::{ExposureLimit, LimitScope};
+
+    #[test]
+    fn test_daily_report_peaks_and_html() {
+        let limits = ExposureLimitTable::new(vec![
+            ExposureLimit { scope: LimitScope::Symbol("BTCUSDT".to_string()), max_notional: Some(1000.0), max_equity_pct: None },
+        ]);
+        let mut history = RiskHistory::default();
+        let now = Utc::now();
+
+        for (i, notional) in [400.0, 800.0, 500.0].iter().enumerate() {
+            let mut exposure = Portfolio::new(10.0).exposure_of(std::iter::empty::<&Position>(), 1000.0);
+            exposure.symbols.insert("BTCUSDT".to_string(), SymbolExposure {
+                long_notional: *notional,
+                net_notional: *notional,
+                ..SymbolExposure::default()
+            });
+            exposure.gross_exposure = *notional;
+            exposure.leverage = notional / 1000.0;
+            history.record(RiskSnapshot::capture(&exposure, None, &limits, now + chrono::Duration::seconds(i as i64)));
+        }
+
+        assert!(!history.is_due(now + chrono::Duration::seconds(30)));
+        assert!(history.is_due(now + chrono::Duration::seconds(2 + SNAPSHOT_INTERVAL_SECS)));
+
+        let report = DailyRiskReport::new(now.date_naive(), &history);
+        assert_eq!(report.snapshots.len(), 3);
+        assert_eq!(report.peak_gross_exposure, 800.0);
+        assert_eq!(report.peak_limit_utilization.get("symbol:BTCUSDT"), Some(&0.8));
+        assert_eq!(report.latest.as_ref().map(|s| s.concentration), Some(1.0));
+
+        let html = report.to_html();
+        assert!(html.contains("Limit Utilization"));
+        assert!(html.contains("<svg"));
+    }
+}
@@ -0,0 +1,263 @@
+//! Post-Trade Surveillance
+//!
+//! This module watches closed trades for self-inflicted problems: a strategy
+//! churning through too many trades, repeated stop-outs on one symbol, and fees
+//! eating more than the gross profit. Each finding is raised as an alert; alerts
+//! marked for it lead the feedback loop to disable the offending strategy. An
+//! alert is raised at most once per window for the same strategy, kind and
+//! symbol.
+
+use std::collections::{HashMap, VecDeque};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+
+use crate::monitoring::performance_monitor::TradeAttribution;
+
+/// Topic used for surveillance alerts (`Message::Custom`)
+pub const SURVEILLANCE_TOPIC: &str = "surveillance_alert";
+
+/// Surveillance thresholds
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SurveillanceConfig {
+    /// Window for churn and stop-out counting (seconds)
+    pub window_secs: i64,
+
+    /// Most trades a strategy may close within the window
+    pub max_trades_per_window: usize,
+
+    /// Most stop-outs a strategy may take on one symbol within the window
+    pub max_stop_outs_per_symbol: usize,
+
+    /// Recent trades per strategy the fee check looks at
+    pub fee_lookback_trades: usize,
+
+    /// Trades needed before the fee check applies
+    pub min_trades_for_fee_check: usize,
+
+    /// Largest ratio of fees to gross profit
+    pub max_fee_to_gross_ratio: f64,
+
+    /// Whether alerts disable the offending strategy
+    pub auto_disable: bool,
+}
+
+impl Default for SurveillanceConfig {
+    fn default() -> Self {
+        Self {
+            window_secs: 3600,
+            max_trades_per_window: 20,
+            max_stop_outs_per_symbol: 3,
+            fee_lookback_trades: 50,
+            min_trades_for_fee_check: 10,
+            max_fee_to_gross_ratio: 1.0,
+            auto_disable: true,
+        }
+    }
+}
+
+/// Kind of anomaly
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum SurveillanceAlertKind {
+    /// Too many trades within the window
+    Churn,
+
+    /// Too many stop-outs on one symbol within the window
+    RepeatedStopOuts,
+
+    /// Fees exceed gross profit
+    FeeBurn,
+}
+
+/// Anomaly found in a strategy's trades
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SurveillanceAlert {
+    /// Detection time
+    pub timestamp: DateTime<Utc>,
+
+    /// Offending strategy
+    pub strategy: String,
+
+    /// Symbol, for per-symbol anomalies
+    pub symbol: Option<String>,
+
+    /// Kind of anomaly
+    pub kind: SurveillanceAlertKind,
+
+    /// What was observed
+    pub details: String,
+
+    /// Whether the strategy should be disabled
+    pub disable_strategy: bool,
+}
+
+/// Closed trade as seen by surveillance
+#[derive(Debug, Clone)]
+struct SurveilledTrade {
+    /// Symbol
+    symbol: String,
+
+    /// Exit time
+    exit_time: DateTime<Utc>,
+
+    /// Realized P&L before costs
+    gross_pnl: f64,
+
+    /// Fees paid
+    fees: f64,
+
+    /// Whether the trade was closed at its stop
+    stopped_out: bool,
+}
+
+/// Post-trade surveillance of strategy behavior
+#[derive(Debug, Clone, Default)]
+pub struct TradeSurveillance {
+    /// Thresholds
+    config: SurveillanceConfig,
+
+    /// Recent closed trades per strategy, oldest first
+    trades: HashMap<String, VecDeque<SurveilledTrade>>,
+
+    /// Last alert per strategy, kind and symbol
+    last_alerts: HashMap<(String, SurveillanceAlertKind, Option<String>), DateTime<Utc>>,
+
+    /// Alerts raised since start
+    alerts: Vec<SurveillanceAlert>,
+}
+
+impl TradeSurveillance {
+    /// Create a new surveillance monitor
+    pub fn new(config: SurveillanceConfig) -> Self {
+        Self {
+            config,
+            ..Self::default()
+        }
+    }
+
+    /// Get the thresholds
+    pub fn get_config(&self) -> &SurveillanceConfig {
+        &self.config
+    }
+
+    /// Record a closed trade and return the anomalies it reveals
+    pub fn record_trade(&mut self, trade: &TradeAttribution, stopped_out: bool) -> Vec<SurveillanceAlert> {
+        let now = trade.exit_time;
+        let window_start = now - Duration::seconds(self.config.window_secs);
+        let keep = self.config.fee_lookback_trades.max(self.config.max_trades_per_window + 1);
+
+        let history = self.trades.entry(trade.strategy.clone()).or_default();
+        history.push_back(SurveilledTrade {
+            symbol: trade.symbol.clone(),
+            exit_time: now,
+            gross_pnl: trade.gross_pnl,
+            fees: trade.fees,
+            stopped_out,
+        });
+        while history.len() > keep {
+            history.pop_front();
+        }
+
+        let mut findings = Vec::new();
+
+        let recent = history.iter().filter(|t| t.exit_time >= window_start).count();
+        if recent > self.config.max_trades_per_window {
+            findings.push((SurveillanceAlertKind::Churn, None, format!(
+                "{} trades within {}s (max {})", recent, self.config.window_secs, self.config.max_trades_per_window
+            )));
+        }
+
+        if stopped_out {
+            let stop_outs = history.iter()
+                .filter(|t| t.exit_time >= window_start && t.stopped_out && t.symbol == trade.symbol)
+                .count();
+            if stop_outs >= self.config.max_stop_outs_per_symbol {
+                findings.push((SurveillanceAlertKind::RepeatedStopOuts, Some(trade.symbol.clone()), format!(
+                    "{} stop-outs on {} within {}s", stop_outs, trade.symbol, self.config.window_secs
+                )));
+            }
+        }
+
+        let lookback = history.len().min(self.config.fee_lookback_trades);
+        if lookback >= self.config.min_trades_for_fee_check {
+            let (gross, fees) = history.iter().rev().take(lookback)
+                .fold((0.0, 0.0), |(gross, fees), t| (gross + t.gross_pnl, fees + t.fees));
+            if fees > gross.max(0.0) * self.config.max_fee_to_gross_ratio {
+                findings.push((SurveillanceAlertKind::FeeBurn, None, format!(
+                    "fees {:.4} against gross profit {:.4} over {} trades", fees, gross, lookback
+                )));
+            }
+        }
+
+        let mut alerts = Vec::new();
+        for (kind, symbol, details) in findings {
+            let key = (trade.strategy.clone(), kind, symbol.clone());
+            if self.last_alerts.get(&key).is_some_and(|last| *last >= window_start) {
+                continue;
+            }
+            self.last_alerts.insert(key, now);
+
+            warn!("Surveillance {:?} alert for {}: {}", kind, trade.strategy, details);
+            let alert = SurveillanceAlert {
+                timestamp: now,
+                strategy: trade.strategy.clone(),
+                symbol,
+                kind,
+                details,
+                disable_strategy: self.config.auto_disable,
+            };
+            self.alerts.push(alert.clone());
+            alerts.push(alert);
+        }
+
+        alerts
+    }
+
+    /// Alerts raised since start
+    pub fn get_alerts(&self) -> &[SurveillanceAlert] {
+        &self.alerts
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn trade(symbol: &str, exit_time: DateTime<Utc>, gross_pnl: f64, fees: f64) -> TradeAttribution {
+        TradeAttribution {
+            trade_id: "t".to_string(),
+            strategy: "scalper".to_string(),
+            symbol: symbol.to_string(),
+            pnl: gross_pnl - fees,
+            gross_pnl,
+            fees,
+            funding: 0.0,
+            roi: 0.0,
+            entry_time: exit_time,
+            exit_time,
+        }
+    }
+
+    #[test]
+    fn test_flags_stop_outs_and_fee_burn_once() {
+        let mut surveillance = TradeSurveillance::new(SurveillanceConfig {
+            min_trades_for_fee_check: 4,
+            ..SurveillanceConfig::default()
+        });
+        let start = Utc::now();
+
+        assert!(surveillance.record_trade(&trade("SOLUSDT", start, -1.0, 0.1), true).is_empty());
+        assert!(surveillance.record_trade(&trade("SOLUSDT", start + Duration::minutes(5), -1.0, 0.1), true).is_empty());
+        let alerts = surveillance.record_trade(&trade("SOLUSDT", start + Duration::minutes(10), -1.0, 0.1), true);
+        assert_eq!(alerts.len(), 1);
+        assert_eq!(alerts[0].kind, SurveillanceAlertKind::RepeatedStopOuts);
+        assert_eq!(alerts[0].symbol.as_deref(), Some("SOLUSDT"));
+        assert!(alerts[0].disable_strategy);
+
+        // Gross losses with fees on top: fee burn, but the stop-out alert is not repeated
+        let alerts = surveillance.record_trade(&trade("SOLUSDT", start + Duration::minutes(15), -1.0, 0.1), true);
+        assert_eq!(alerts.len(), 1);
+        assert_eq!(alerts[0].kind, SurveillanceAlertKind::FeeBurn);
+        assert_eq!(surveillance.get_alerts().len(), 2);
+    }
+}
@@ -0,0 +1,171 @@
+//! Unified Error Manager
+//!
+//! This module collects operational error events from every component (exchange
+//! calls, reconciliation, risk checks) into a single bounded log with severity and
+//! category, so drift and failures are surfaced and counted instead of only being
+//! written to the tracing output.
+
+use std::collections::{HashMap, VecDeque};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tracing::{error, info, warn};
+
+/// Severity of an error event
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum ErrorSeverity {
+    /// Informational, no action needed
+    Info,
+
+    /// Degraded but handled
+    Warning,
+
+    /// Failed operation
+    Error,
+
+    /// Requires immediate attention
+    Critical,
+}
+
+/// Area an error event originates from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ErrorCategory {
+    /// Exchange API errors
+    Exchange,
+
+    /// Network and connectivity
+    Network,
+
+    /// Local state diverged from the exchange
+    Reconciliation,
+
+    /// Risk limit breaches
+    Risk,
+
+    /// Strategy failures
+    Strategy,
+
+    /// Agent task failures and restarts
+    Agent,
+
+    /// Everything else
+    System,
+}
+
+/// One recorded error event
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ErrorEvent {
+    /// Event ID
+    pub id: u64,
+
+    /// Time recorded
+    pub timestamp: DateTime<Utc>,
+
+    /// Category
+    pub category: ErrorCategory,
+
+    /// Severity
+    pub severity: ErrorSeverity,
+
+    /// Component that reported the event
+    pub source: String,
+
+    /// Description
+    pub message: String,
+
+    /// Additional key/value context
+    pub context: HashMap<String, String>,
+}
+
+/// Bounded log of error events from all components
+#[derive(Debug, Clone)]
+pub struct UnifiedErrorManager {
+    /// Recent events, oldest first
+    events: VecDeque<ErrorEvent>,
+
+    /// Maximum events kept
+    max_events: usize,
+
+    /// Next event ID
+    next_id: u64,
+
+    /// Lifetime counts by category
+    counts: HashMap<ErrorCategory, u64>,
+}
+
+impl UnifiedErrorManager {
+    /// Create a new error manager keeping at most `max_events` events
+    pub fn new(max_events: usize) -> Self {
+        Self {
+            events: VecDeque::new(),
+            max_events: max_events.max(1),
+            next_id: 1,
+            counts: HashMap::new(),
+        }
+    }
+
+    /// Record an event; returns its ID
+    pub fn report(&mut self, category: ErrorCategory, severity: ErrorSeverity, source: &str, message: &str) -> u64 {
+        self.report_with_context(category, severity, source, message, HashMap::new())
+    }
+
+    /// Record an event with context; returns its ID
+    pub fn report_with_context(
+        &mut self,
+        category: ErrorCategory,
+        severity: ErrorSeverity,
+        source: &str,
+        message: &str,
+        context: HashMap<String, String>,
+    ) -> u64 {
+        match severity {
+            ErrorSeverity::Info => info!("[{:?}] {}: {}", category, source, message),
+            ErrorSeverity::Warning => warn!("[{:?}] {}: {}", category, source, message),
+            ErrorSeverity::Error | ErrorSeverity::Critical => error!("[{:?}] {}: {}", category, source, message),
+        }
+
+        let id = self.next_id;
+        self.next_id += 1;
+        *self.counts.entry(category).or_insert(0) += 1;
+
+        self.events.push_back(ErrorEvent {
+            id,
+            timestamp: Utc::now(),
+            category,
+            severity,
+            source: source.to_string(),
+            message: message.to_string(),
+            context,
+        });
+        while self.events.len() > self.max_events {
+            self.events.pop_front();
+        }
+
+        id
+    }
+
+    /// Most recent `limit` events, newest first
+    pub fn get_recent(&self, limit: usize) -> Vec<&ErrorEvent> {
+        self.events.iter().rev().take(limit).collect()
+    }
+
+    /// Retained events of a category, oldest first
+    pub fn get_events_by_category(&self, category: ErrorCategory) -> Vec<&ErrorEvent> {
+        self.events.iter().filter(|e| e.category == category).collect()
+    }
+
+    /// Lifetime number of events in a category
+    pub fn get_count(&self, category: ErrorCategory) -> u64 {
+        self.counts.get(&category).copied().unwrap_or(0)
+    }
+
+    /// Whether a critical event was recorded at or after `since`
+    pub fn has_critical_since(&self, since: DateTime<Utc>) -> bool {
+        self.events.iter().any(|e| e.severity == ErrorSeverity::Critical && e.timestamp >= since)
+    }
+}
+
+impl Default for UnifiedErrorManager {
+    fn default() -> Self {
+        Self::new(1000)
+    }
+}
@@ -0,0 +1,248 @@
+//! P&L Calculator
+//!
+//! This module computes position P&L from price moves, fees and funding in one
+//! place. Every figure is exposed both gross (price move only) and net (after entry
+//! and exit fees and accrued funding), so positions, backtests, the performance
+//! monitor and reports all report the same numbers. It also computes liquidation
+//! prices from leverage, margin mode and tiered maintenance margin.
+
+use serde::{Deserialize, Serialize};
+
+use crate::position::position_manager::{Position, PositionDirection};
+
+/// Gross and net P&L of a position
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct PnlBreakdown {
+    /// P&L from the price move only
+    pub gross_pnl: f64,
+
+    /// Entry and exit fees paid
+    pub fees: f64,
+
+    /// Net funding paid (negative when received)
+    pub funding: f64,
+
+    /// Gross P&L minus fees and funding
+    pub net_pnl: f64,
+
+    /// Entry notional
+    pub notional: f64,
+
+    /// Net P&L in % of entry notional
+    pub return_percent: f64,
+}
+
+impl PnlBreakdown {
+    /// Build a breakdown from its components
+    pub fn new(gross_pnl: f64, fees: f64, funding: f64, notional: f64) -> Self {
+        let net_pnl = gross_pnl - fees - funding;
+        Self {
+            gross_pnl,
+            fees,
+            funding,
+            net_pnl,
+            notional,
+            return_percent: if notional > 0.0 { net_pnl / notional * 100.0 } else { 0.0 },
+        }
+    }
+
+    /// Sum two breakdowns (e.g. to aggregate several positions)
+    pub fn combine(&self, other: &PnlBreakdown) -> Self {
+        Self::new(
+            self.gross_pnl + other.gross_pnl,
+            self.fees + other.fees,
+            self.funding + other.funding,
+            self.notional + other.notional,
+        )
+    }
+}
+
+/// P&L from a price move
+pub fn price_pnl(is_long: bool, entry_price: f64, exit_price: f64, size: f64) -> f64 {
+    let difference = if is_long { exit_price - entry_price } else { entry_price - exit_price };
+    difference * size
+}
+
+/// Funding paid for one settlement; longs pay and shorts receive a positive rate
+pub fn funding_payment(is_long: bool, size: f64, mark_price: f64, funding_rate: f64) -> f64 {
+    let payment = size * mark_price * funding_rate;
+    if is_long { payment } else { -payment }
+}
+
+fn is_long(position: &Position) -> bool {
+    matches!(position.direction, PositionDirection::Long)
+}
+
+/// Realized P&L of closing `position` at `exit_price` paying `exit_fee`
+pub fn realized_pnl(position: &Position, exit_price: f64, exit_fee: f64) -> PnlBreakdown {
+    PnlBreakdown::new(
+        price_pnl(is_long(position), position.entry_price, exit_price, position.size),
+        position.fees + exit_fee,
+        position.funding,
+        position.entry_price * position.size,
+    )
+}
+
+/// Unrealized P&L at `mark_price`, including an estimated exit fee at `exit_fee_rate`
+pub fn unrealized_pnl(position: &Position, mark_price: f64, exit_fee_rate: f64) -> PnlBreakdown {
+    realized_pnl(position, mark_price, mark_price * position.size * exit_fee_rate)
+}
+
+/// How margin is allocated to a position
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum MarginMode {
+    /// Only the position's initial margin backs it
+    Isolated,
+
+    /// Free account balance backs the position as well
+    Cross { available_balance: f64 },
+}
+
+/// One maintenance margin tier (risk limit)
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct MaintenanceTier {
+    /// Upper bound of position notional for this tier
+    pub max_notional: f64,
+
+    /// Maintenance margin rate
+    pub rate: f64,
+
+    /// Maintenance amount deducted so margin is continuous across tiers
+    pub deduction: f64,
+}
+
+/// Tiered maintenance margin schedule
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MaintenanceMarginTiers {
+    /// Tiers ordered by `max_notional`
+    tiers: Vec<MaintenanceTier>,
+}
+
+impl MaintenanceMarginTiers {
+    /// Build a schedule from (max notional, rate) pairs; deductions are derived
+    pub fn new(mut limits: Vec<(f64, f64)>) -> Self {
+        limits.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Equal));
+
+        let mut tiers: Vec<MaintenanceTier> = Vec::with_capacity(limits.len());
+        for (max_notional, rate) in limits {
+            let deduction = match tiers.last() {
+                Some(previous) => previous.deduction + previous.max_notional * (rate - previous.rate),
+                None => 0.0,
+            };
+            tiers.push(MaintenanceTier { max_notional, rate, deduction });
+        }
+        Self { tiers }
+    }
+
+    /// Single flat maintenance margin rate
+    pub fn flat(rate: f64) -> Self {
+        Self::new(vec![(f64::MAX, rate)])
+    }
+
+    /// Tier applying to a position notional (the last tier beyond the table)
+    pub fn tier_for(&self, notional: f64) -> MaintenanceTier {
+        self.tiers.iter()
+            .find(|tier| notional <= tier.max_notional)
+            .or_else(|| self.tiers.last())
+            .copied()
+            .unwrap_or(MaintenanceTier { max_notional: f64::MAX, rate: 0.0, deduction: 0.0 })
+    }
+
+    /// Maintenance margin of a position notional
+    pub fn maintenance_margin(&self, notional: f64) -> f64 {
+        let tier = self.tier_for(notional);
+        (notional * tier.rate - tier.deduction).max(0.0)
+    }
+}
+
+impl Default for MaintenanceMarginTiers {
+    /// Bybit BTCUSDT linear perpetual risk limits
+    fn default() -> Self {
+        Self::new(vec![
+            (2_000_000.0, 0.005),
+            (4_000_000.0, 0.01),
+            (6_000_000.0, 0.015),
+            (8_000_000.0, 0.02),
+            (10_000_000.0, 0.025),
+        ])
+    }
+}
+
+/// Price at which a position's margin falls to its maintenance margin
+///
+/// Returns `None` when the position cannot be liquidated (a long backed by more
+/// margin than its notional).
+pub fn liquidation_price(
+    is_long: bool,
+    entry_price: f64,
+    size: f64,
+    leverage: f64,
+    margin_mode: MarginMode,
+    tiers: &MaintenanceMarginTiers,
+) -> Option<f64> {
+    if size <= 0.0 || entry_price <= 0.0 || leverage <= 0.0 {
+        return None;
+    }
+
+    let notional = entry_price * size;
+    let initial_margin = notional / leverage;
+    let extra_margin = match margin_mode {
+        MarginMode::Isolated => 0.0,
+        MarginMode::Cross { available_balance } => available_balance.max(0.0),
+    };
+    let buffer = initial_margin + extra_margin - tiers.maintenance_margin(notional);
+
+    if is_long {
+        let price = entry_price - buffer / size;
+        (price > 0.0).then_some(price)
+    } else {
+        Some(entry_price + buffer / size)
+    }
+}
+
+/// Distance from `mark_price` to `liquidation_price` in % of mark (adverse direction)
+pub fn liquidation_distance_percent(is_long: bool, mark_price: f64, liquidation_price: f64) -> f64 {
+    if mark_price <= 0.0 {
+        return 0.0;
+    }
+    let distance = if is_long { mark_price - liquidation_price } else { liquidation_price - mark_price };
+    distance / mark_price * 100.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_net_pnl_includes_fees_and_funding() {
+        let mut position = Position::new("BTCUSDT".to_string(), PositionDirection::Short, 2.0, 100.0);
+        position.fees = 0.1;
+        position.funding = funding_payment(false, 2.0, 100.0, 0.0001);
+
+        let pnl = realized_pnl(&position, 95.0, 0.1);
+        assert!((pnl.gross_pnl - 10.0).abs() < 1e-9);
+        assert!((pnl.fees - 0.2).abs() < 1e-9);
+        assert!((pnl.funding + 0.02).abs() < 1e-9);
+        assert!((pnl.net_pnl - 9.82).abs() < 1e-9);
+        assert!((pnl.return_percent - 4.91).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_liquidation_price_with_tiers() {
+        let tiers = MaintenanceMarginTiers::new(vec![(1_000.0, 0.01), (10_000.0, 0.02)]);
+        assert!((tiers.tier_for(5_000.0).deduction - 10.0).abs() < 1e-9);
+        assert!((tiers.maintenance_margin(5_000.0) - 90.0).abs() < 1e-9);
+
+        // 10x isolated long: 100 - (10 - 1) / 1
+        let long = liquidation_price(true, 100.0, 1.0, 10.0, MarginMode::Isolated, &tiers).unwrap();
+        assert!((long - 91.0).abs() < 1e-9);
+        assert!((liquidation_distance_percent(true, 100.0, long) - 9.0).abs() < 1e-9);
+
+        // Cross margin pushes liquidation further away
+        let short = liquidation_price(false, 100.0, 1.0, 10.0, MarginMode::Isolated, &tiers).unwrap();
+        let cross = liquidation_price(false, 100.0, 1.0, 10.0, MarginMode::Cross { available_balance: 20.0 }, &tiers).unwrap();
+        assert!((short - 109.0).abs() < 1e-9);
+        assert!((cross - 129.0).abs() < 1e-9);
+        assert!(liquidation_price(true, 100.0, 1.0, 1.0, MarginMode::Cross { available_balance: 10.0 }, &tiers).is_none());
+    }
+}
@@ -3,9 +3,15 @@
 //! This module provides position management, tracking, and P&L calculation
 //! capabilities.
 
+pub mod position_manager;
 pub mod manager;
 pub mod tracker;
 pub mod calculator;
+pub mod trailing_stop;
+pub mod reconciliation;
+pub mod portfolio;
+pub mod persistence;
+pub mod sizing;
 
 pub use manager::*;
 pub use tracker::*;
@@ -0,0 +1,293 @@
+//! Book Persistence
+//!
+//! This module persists the live book — open positions, the trading system's
+//! active trades, working orders and trailing-stop state — so a restarted process reconstructs it and resumes
+//! managing positions that are still open on the exchange instead of orphaning
+//! them. Snapshots go to an atomically replaced JSON file by default, or to SQLite
+//! with the `sqlite` feature. After restoring, run the position reconciler once to
+//! pick up fills and closes that happened while the process was down.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tracing::{debug, info, warn};
+
+use crate::engine::json_store::{load_json, save_json};
+use crate::execution::order_manager::{ManagedOrder, OrderManager};
+use crate::position::position_manager::{Position, PositionManager};
+use crate::position::trailing_stop::TrailingStopManager;
+use crate::trading_system::Trade;
+
+/// Snapshot format version
+const BOOK_VERSION: u32 = 1;
+
+/// Persisted state of the live book
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BookSnapshot {
+    /// Format version
+    pub version: u32,
+
+    /// Time the snapshot was taken
+    pub saved_at: DateTime<Utc>,
+
+    /// Open positions
+    pub positions: Vec<Position>,
+
+    /// Active trades of the trading system
+    #[serde(default)]
+    pub trades: Vec<Trade>,
+
+    /// Orders that have not reached a terminal state
+    pub orders: Vec<ManagedOrder>,
+
+    /// Trailing stops of open positions
+    pub trailing_stops: TrailingStopManager,
+}
+
+impl BookSnapshot {
+    /// Capture the current book
+    pub fn capture(
+        positions: &PositionManager,
+        trades: &HashMap<String, Trade>,
+        orders: &OrderManager,
+        trailing_stops: &TrailingStopManager,
+    ) -> Self {
+        Self {
+            version: BOOK_VERSION,
+            saved_at: Utc::now(),
+            positions: positions.get_all_positions().into_iter().cloned().collect(),
+            trades: trades.values().cloned().collect(),
+            orders: orders.get_open_orders().into_iter().cloned().collect(),
+            trailing_stops: trailing_stops.clone(),
+        }
+    }
+
+    /// Load the snapshot into empty managers
+    ///
+    /// Trailing stops of positions that are not part of the snapshot are dropped.
+    pub fn restore_into(
+        self,
+        positions: &mut PositionManager,
+        trades: &mut HashMap<String, Trade>,
+        orders: &mut OrderManager,
+        trailing_stops: &mut TrailingStopManager,
+    ) -> Result<()> {
+        let mut position_ids = Vec::with_capacity(self.positions.len());
+        for position in self.positions {
+            position_ids.push(position.id.clone());
+            positions.restore_position(position)?;
+        }
+        for trade in self.trades {
+            trades.insert(trade.id.clone(), trade);
+        }
+        for order in self.orders {
+            orders.restore_order(order);
+        }
+
+        *trailing_stops = self.trailing_stops;
+        for id in trailing_stops.get_tracked_ids() {
+            if !position_ids.contains(&id) {
+                warn!("Dropping trailing stop for unknown position {}", id);
+                trailing_stops.untrack(&id);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Durable storage for book snapshots
+pub trait BookStore: Send {
+    /// Replace the stored snapshot
+    fn save(&mut self, snapshot: &BookSnapshot) -> Result<()>;
+
+    /// Load the stored snapshot, if any
+    fn load(&self) -> Result<Option<BookSnapshot>>;
+}
+
+fn check_version(snapshot: BookSnapshot) -> Result<BookSnapshot> {
+    if snapshot.version != BOOK_VERSION {
+        return Err(anyhow::anyhow!("Unsupported book snapshot version {}", snapshot.version));
+    }
+    Ok(snapshot)
+}
+
+/// JSON file store; writes go to a temp file that is then renamed over the snapshot
+pub struct JsonFileBookStore {
+    /// Snapshot file
+    path: PathBuf,
+}
+
+impl JsonFileBookStore {
+    /// Create a store at `path`
+    pub fn new(path: impl AsRef<Path>) -> Self {
+        Self { path: path.as_ref().to_path_buf() }
+    }
+}
+
+impl BookStore for JsonFileBookStore {
+    fn save(&mut self, snapshot: &BookSnapshot) -> Result<()> {
+        save_json(&self.path, snapshot)
+    }
+
+    fn load(&self) -> Result<Option<BookSnapshot>> {
+        load_json(&self.path)?.map(check_version).transpose()
+    }
+}
+
+/// SQLite store keeping the latest snapshot in a single row
+#[cfg(feature = "sqlite")]
+pub struct SqliteBookStore {
+    /// Connection
+    connection: rusqlite::Connection,
+}
+
+#[cfg(feature = "sqlite")]
+impl SqliteBookStore {
+    /// Open (or create) a store at `path`
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let connection = rusqlite::Connection::open(path)?;
+        connection.execute_batch(
+            "PRAGMA journal_mode = WAL;
+             CREATE TABLE IF NOT EXISTS book_snapshot (
+                id INTEGER PRIMARY KEY CHECK (id = 1),
+                saved_at TEXT NOT NULL,
+                snapshot TEXT NOT NULL
+            );",
+        )?;
+        Ok(Self { connection })
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl BookStore for SqliteBookStore {
+    fn save(&mut self, snapshot: &BookSnapshot) -> Result<()> {
+        self.connection.execute(
+            "INSERT OR REPLACE INTO book_snapshot VALUES (1, ?1, ?2)",
+            rusqlite::params![snapshot.saved_at.to_rfc3339(), serde_json::to_string(snapshot)?],
+        )?;
+        Ok(())
+    }
+
+    fn load(&self) -> Result<Option<BookSnapshot>> {
+        use rusqlite::OptionalExtension;
+
+        let json: Option<String> = self.connection
+            .query_row("SELECT snapshot FROM book_snapshot WHERE id = 1", [], |row| row.get(0))
+            .optional()?;
+        match json {
+            Some(json) => Ok(Some(check_version(serde_json::from_str(&json)?)?)),
+            None => Ok(None),
+        }
+    }
+}
+
+/// Restores the book on startup and persists it periodically
+pub struct BookPersister {
+    /// Storage backend
+    store: Box<dyn BookStore>,
+
+    /// Minimum time between snapshots
+    interval: Duration,
+
+    /// Time of the last snapshot
+    last_saved: Option<Instant>,
+}
+
+impl BookPersister {
+    /// Create a new persister
+    pub fn new(store: Box<dyn BookStore>, interval: Duration) -> Self {
+        Self { store, interval, last_saved: None }
+    }
+
+    /// Restore the stored book, if any; returns whether one was restored
+    pub fn restore(
+        &self,
+        positions: &mut PositionManager,
+        trades: &mut HashMap<String, Trade>,
+        orders: &mut OrderManager,
+        trailing_stops: &mut TrailingStopManager,
+    ) -> Result<bool> {
+        let snapshot = match self.store.load()? {
+            Some(snapshot) => snapshot,
+            None => return Ok(false),
+        };
+
+        info!("Restoring book saved at {}: {} positions, {} trades, {} orders, {} trailing stops",
+              snapshot.saved_at, snapshot.positions.len(), snapshot.trades.len(), snapshot.orders.len(),
+              snapshot.trailing_stops.len());
+        snapshot.restore_into(positions, trades, orders, trailing_stops)?;
+        Ok(true)
+    }
+
+    /// Persist the book now
+    pub fn persist(
+        &mut self,
+        positions: &PositionManager,
+        trades: &HashMap<String, Trade>,
+        orders: &OrderManager,
+        trailing_stops: &TrailingStopManager,
+    ) -> Result<()> {
+        self.store.save(&BookSnapshot::capture(positions, trades, orders, trailing_stops))?;
+        self.last_saved = Some(Instant::now());
+        debug!("Book persisted");
+        Ok(())
+    }
+
+    /// Persist the book if the interval elapsed; returns whether it did
+    pub fn maybe_persist(
+        &mut self,
+        positions: &PositionManager,
+        trades: &HashMap<String, Trade>,
+        orders: &OrderManager,
+        trailing_stops: &TrailingStopManager,
+    ) -> Result<bool> {
+        if self.last_saved.is_some_and(|last| last.elapsed() < self.interval) {
+            return Ok(false);
+        }
+        self.persist(positions, trades, orders, trailing_stops)?;
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::exchange::bybit::types::{OrderSide, OrderType};
+    use crate::execution::order_manager::ExecutionReport;
+    use crate::position::position_manager::PositionDirection;
+    use crate::position::trailing_stop::TrailingStopConfig;
+
+    #[test]
+    fn test_book_survives_restart() {
+        let mut positions = PositionManager::new();
+        let mut orders = OrderManager::new();
+        let mut trailing_stops = TrailingStopManager::new();
+
+        let position_id = positions.open_position("BTCUSDT".to_string(), PositionDirection::Long, 1.0, 100.0).unwrap();
+        trailing_stops.track(positions.get_position(&position_id).unwrap(), TrailingStopConfig::default());
+        trailing_stops.on_price("BTCUSDT", 110.0);
+        let order_id = orders.create_order("BTCUSDT", OrderSide::Sell, OrderType::Limit, 1.0, Some(120.0)).unwrap();
+        orders.apply_report(&order_id, ExecutionReport::Submitted { exchange_order_id: "ex-1".to_string() }).unwrap();
+
+        let path = std::env::temp_dir().join(format!("omni_book_{}.json", std::process::id()));
+        let mut persister = BookPersister::new(Box::new(JsonFileBookStore::new(&path)), Duration::from_secs(60));
+        let trades = HashMap::new();
+        assert!(persister.maybe_persist(&positions, &trades, &orders, &trailing_stops).unwrap());
+        assert!(!persister.maybe_persist(&positions, &trades, &orders, &trailing_stops).unwrap());
+
+        let (mut restored_positions, mut restored_trades, mut restored_orders, mut restored_stops) =
+            (PositionManager::new(), HashMap::new(), OrderManager::new(), TrailingStopManager::new());
+        assert!(persister.restore(&mut restored_positions, &mut restored_trades, &mut restored_orders, &mut restored_stops).unwrap());
+        assert!(restored_trades.is_empty());
+
+        assert!(restored_positions.get_position(&position_id).is_some());
+        assert_eq!(restored_stops.get_stop(&position_id), trailing_stops.get_stop(&position_id));
+        assert!(restored_orders.apply_exchange_report("ex-1", ExecutionReport::Cancelled).is_ok());
+        let next = restored_orders.create_order("BTCUSDT", OrderSide::Buy, OrderType::Market, 1.0, None).unwrap();
+        assert_ne!(next, order_id);
+        let _ = std::fs::remove_file(&path);
+    }
+}
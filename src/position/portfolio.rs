@@ -0,0 +1,317 @@
+//! Portfolio Exposure
+//!
+//! This module aggregates open positions into a single exposure snapshot: net and
+//! gross notional, per-symbol and per-sector notional, leverage utilization and
+//! margin usage, plus the liquidation price and distance of every position. The
+//! snapshot is serializable so the risk manager and the dashboard read the same
+//! figures.
+
+use std::collections::{BTreeMap, HashMap};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::position::calculator::{liquidation_distance_percent, liquidation_price, MaintenanceMarginTiers, MarginMode};
+use crate::position::position_manager::{Position, PositionDirection, PositionManager, PositionStatus};
+
+/// Sector used for symbols without a mapping
+pub const UNCLASSIFIED_SECTOR: &str = "Unclassified";
+
+/// Exposure of one symbol
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SymbolExposure {
+    /// Long notional at mark
+    pub long_notional: f64,
+
+    /// Short notional at mark
+    pub short_notional: f64,
+
+    /// Long minus short notional
+    pub net_notional: f64,
+
+    /// Margin posted at the symbol's leverage
+    pub margin: f64,
+
+    /// Maintenance margin required to keep the positions open
+    #[serde(default)]
+    pub maintenance_margin: f64,
+
+    /// Sector the symbol belongs to
+    pub sector: String,
+}
+
+/// Liquidation risk of one position
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LiquidationRisk {
+    /// Position ID
+    pub position_id: String,
+
+    /// Symbol
+    pub symbol: String,
+
+    /// Liquidation price (`None` if the position cannot be liquidated)
+    pub liquidation_price: Option<f64>,
+
+    /// Adverse move from mark to liquidation in %
+    pub distance_percent: Option<f64>,
+}
+
+/// Exposure of the whole book at one point in time
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PortfolioExposure {
+    /// Snapshot time
+    pub timestamp: DateTime<Utc>,
+
+    /// Account equity the ratios refer to
+    pub equity: f64,
+
+    /// Total long notional
+    pub long_notional: f64,
+
+    /// Total short notional
+    pub short_notional: f64,
+
+    /// Long minus short notional
+    pub net_exposure: f64,
+
+    /// Long plus short notional
+    pub gross_exposure: f64,
+
+    /// Exposure by symbol
+    pub symbols: BTreeMap<String, SymbolExposure>,
+
+    /// Net notional by sector
+    pub sectors: BTreeMap<String, f64>,
+
+    /// Gross exposure divided by equity
+    pub leverage: f64,
+
+    /// Leverage as a fraction of the maximum allowed leverage
+    pub leverage_utilization: f64,
+
+    /// Total margin posted
+    pub margin_used: f64,
+
+    /// Margin used as a fraction of equity
+    pub margin_usage: f64,
+
+    /// Total maintenance margin
+    #[serde(default)]
+    pub maintenance_margin: f64,
+
+    /// Liquidation risk by position
+    #[serde(default)]
+    pub liquidation: Vec<LiquidationRisk>,
+}
+
+impl PortfolioExposure {
+    /// Exposure of a symbol, if it has open positions
+    pub fn get_symbol(&self, symbol: &str) -> Option<&SymbolExposure> {
+        self.symbols.get(symbol)
+    }
+
+    /// Net notional of a sector
+    pub fn get_sector(&self, sector: &str) -> f64 {
+        self.sectors.get(sector).copied().unwrap_or(0.0)
+    }
+
+    /// Position closest to liquidation
+    pub fn closest_to_liquidation(&self) -> Option<&LiquidationRisk> {
+        self.liquidation.iter()
+            .filter(|risk| risk.distance_percent.is_some())
+            .min_by(|a, b| a.distance_percent.partial_cmp(&b.distance_percent).unwrap_or(std::cmp::Ordering::Equal))
+    }
+}
+
+/// Builds exposure snapshots from open positions
+#[derive(Debug, Clone)]
+pub struct Portfolio {
+    /// Sector by symbol
+    sectors: HashMap<String, String>,
+
+    /// Leverage by symbol (exchange leverage is set per symbol)
+    leverage: HashMap<String, f64>,
+
+    /// Leverage for symbols without an explicit setting
+    default_leverage: f64,
+
+    /// Maximum allowed account leverage
+    max_leverage: f64,
+
+    /// Whether positions share the free balance (cross margin)
+    cross_margin: bool,
+
+    /// Maintenance margin tiers by symbol
+    maintenance_tiers: HashMap<String, MaintenanceMarginTiers>,
+
+    /// Maintenance margin tiers for symbols without a schedule
+    default_tiers: MaintenanceMarginTiers,
+}
+
+impl Portfolio {
+    /// Create a new portfolio view
+    pub fn new(max_leverage: f64) -> Self {
+        Self {
+            sectors: HashMap::new(),
+            leverage: HashMap::new(),
+            default_leverage: 1.0,
+            max_leverage: max_leverage.max(f64::EPSILON),
+            cross_margin: false,
+            maintenance_tiers: HashMap::new(),
+            default_tiers: MaintenanceMarginTiers::default(),
+        }
+    }
+
+    /// Assign a symbol to a sector
+    pub fn set_sector(&mut self, symbol: &str, sector: &str) {
+        self.sectors.insert(symbol.to_string(), sector.to_string());
+    }
+
+    /// Record the leverage a symbol trades at
+    pub fn set_leverage(&mut self, symbol: &str, leverage: f64) {
+        self.leverage.insert(symbol.to_string(), leverage.max(1.0));
+    }
+
+    /// Use cross (shared free balance) or isolated margin for liquidation prices
+    pub fn set_cross_margin(&mut self, cross_margin: bool) {
+        self.cross_margin = cross_margin;
+    }
+
+    /// Set the maintenance margin tiers of a symbol
+    pub fn set_maintenance_tiers(&mut self, symbol: &str, tiers: MaintenanceMarginTiers) {
+        self.maintenance_tiers.insert(symbol.to_string(), tiers);
+    }
+
+    /// Sector of a symbol
+    pub fn get_sector(&self, symbol: &str) -> &str {
+        self.sectors.get(symbol).map(String::as_str).unwrap_or(UNCLASSIFIED_SECTOR)
+    }
+
+    /// Leverage of a symbol
+    pub fn get_leverage(&self, symbol: &str) -> f64 {
+        self.leverage.get(symbol).copied().unwrap_or(self.default_leverage)
+    }
+
+    /// Maintenance margin tiers of a symbol
+    pub fn get_maintenance_tiers(&self, symbol: &str) -> &MaintenanceMarginTiers {
+        self.maintenance_tiers.get(symbol).unwrap_or(&self.default_tiers)
+    }
+
+    /// Snapshot the exposure of the open positions in `positions`
+    pub fn exposure(&self, positions: &PositionManager, equity: f64) -> PortfolioExposure {
+        self.exposure_of(positions.get_all_positions(), equity)
+    }
+
+    /// Snapshot the exposure of `positions`; closed positions are ignored
+    pub fn exposure_of<'a>(&self, positions: impl IntoIterator<Item = &'a Position>, equity: f64) -> PortfolioExposure {
+        let positions: Vec<&Position> = positions.into_iter()
+            .filter(|p| !matches!(p.status, PositionStatus::Closed))
+            .collect();
+
+        let mut symbols: BTreeMap<String, SymbolExposure> = BTreeMap::new();
+        for position in &positions {
+            let notional = position.size * position.current_price;
+            let exposure = symbols.entry(position.symbol.clone()).or_insert_with(|| SymbolExposure {
+                sector: self.get_sector(&position.symbol).to_string(),
+                ..SymbolExposure::default()
+            });
+            match position.direction {
+                PositionDirection::Long => exposure.long_notional += notional,
+                PositionDirection::Short => exposure.short_notional += notional,
+            }
+            exposure.net_notional = exposure.long_notional - exposure.short_notional;
+            exposure.margin += notional / self.get_leverage(&position.symbol);
+            exposure.maintenance_margin += self.get_maintenance_tiers(&position.symbol).maintenance_margin(notional);
+        }
+
+        let mut sectors: BTreeMap<String, f64> = BTreeMap::new();
+        for exposure in symbols.values() {
+            *sectors.entry(exposure.sector.clone()).or_insert(0.0) += exposure.net_notional;
+        }
+
+        let long_notional: f64 = symbols.values().map(|s| s.long_notional).sum();
+        let short_notional: f64 = symbols.values().map(|s| s.short_notional).sum();
+        let margin_used: f64 = symbols.values().map(|s| s.margin).sum();
+        let maintenance_margin: f64 = symbols.values().map(|s| s.maintenance_margin).sum();
+        let gross_exposure = long_notional + short_notional;
+        let leverage = if equity > 0.0 { gross_exposure / equity } else { 0.0 };
+
+        let margin_mode = if self.cross_margin {
+            MarginMode::Cross { available_balance: (equity - margin_used).max(0.0) }
+        } else {
+            MarginMode::Isolated
+        };
+        let liquidation = positions.iter().map(|position| {
+            let is_long = matches!(position.direction, PositionDirection::Long);
+            let tiers = self.get_maintenance_tiers(&position.symbol);
+            let price = liquidation_price(
+                is_long,
+                position.entry_price,
+                position.size,
+                self.get_leverage(&position.symbol),
+                margin_mode,
+                tiers,
+            );
+            LiquidationRisk {
+                position_id: position.id.clone(),
+                symbol: position.symbol.clone(),
+                liquidation_price: price,
+                distance_percent: price.map(|p| liquidation_distance_percent(is_long, position.current_price, p)),
+            }
+        }).collect();
+
+        PortfolioExposure {
+            timestamp: Utc::now(),
+            equity,
+            long_notional,
+            short_notional,
+            net_exposure: long_notional - short_notional,
+            gross_exposure,
+            symbols,
+            sectors,
+            leverage,
+            leverage_utilization: leverage / self.max_leverage,
+            margin_used,
+            margin_usage: if equity > 0.0 { margin_used / equity } else { 0.0 },
+            maintenance_margin,
+            liquidation,
+        }
+    }
+}
+
+impl Default for Portfolio {
+    fn default() -> Self {
+        Self::new(10.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_exposure_aggregation() {
+        let mut portfolio = Portfolio::new(5.0);
+        portfolio.set_sector("BTCUSDT", "Layer1");
+        portfolio.set_sector("ETHUSDT", "Layer1");
+        portfolio.set_leverage("BTCUSDT", 10.0);
+
+        let positions = vec![
+            Position::new("BTCUSDT".to_string(), PositionDirection::Long, 0.1, 50_000.0),
+            Position::new("ETHUSDT".to_string(), PositionDirection::Short, 1.0, 3_000.0),
+            Position::new("DOGEUSDT".to_string(), PositionDirection::Long, 10_000.0, 0.1),
+        ];
+        let exposure = portfolio.exposure_of(&positions, 4_000.0);
+
+        assert!((exposure.gross_exposure - 9_000.0).abs() < 1e-9);
+        assert!((exposure.net_exposure - 3_000.0).abs() < 1e-9);
+        assert!((exposure.get_sector("Layer1") - 2_000.0).abs() < 1e-9);
+        assert!((exposure.get_sector(UNCLASSIFIED_SECTOR) - 1_000.0).abs() < 1e-9);
+        assert!((exposure.margin_used - 4_500.0).abs() < 1e-9);
+        assert!((exposure.leverage_utilization - 0.45).abs() < 1e-9);
+
+        // The 10x BTC position sits closest to liquidation
+        let closest = exposure.closest_to_liquidation().unwrap();
+        assert_eq!(closest.symbol, "BTCUSDT");
+        assert!((closest.distance_percent.unwrap() - 9.5).abs() < 1e-9);
+    }
+}
@@ -4,7 +4,16 @@
 
 use std::collections::HashMap;
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 use anyhow::Result;
+use tracing::{info, warn};
+
+use crate::agents::risk_manager::MAX_CONCURRENT_POSITIONS;
+use crate::engine::message_bus::Message;
+use crate::position::calculator::{self, PnlBreakdown};
+
+/// Topic used for break-even stop events (`Message::Custom`)
+pub const BREAK_EVEN_TOPIC: &str = "break_even_stop";
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum PositionDirection {
@@ -35,6 +44,23 @@ pub struct Position {
     pub open_time: u64,
     pub close_time: Option<u64>,
     pub fees: f64,
+    #[serde(default)]
+    pub strategy: Option<String>,
+    /// First stop loss set on the position; defines 1R
+    #[serde(default)]
+    pub initial_stop: Option<f64>,
+    /// Whether the stop was already moved to break-even
+    #[serde(default)]
+    pub break_even_applied: bool,
+    /// Net funding paid while open (negative when received)
+    #[serde(default)]
+    pub funding: f64,
+    /// Realized P&L before fees and funding
+    #[serde(default)]
+    pub realized_gross_pnl: f64,
+    /// Whether the position outlived its strategy's max holding period
+    #[serde(default)]
+    pub stale: bool,
 }
 
 impl Position {
@@ -72,6 +98,12 @@ impl Position {
             open_time,
             close_time: None,
             fees: 0.0,
+            strategy: None,
+            initial_stop: None,
+            break_even_applied: false,
+            funding: 0.0,
+            realized_gross_pnl: 0.0,
+            stale: false,
         }
     }
 
@@ -80,21 +112,42 @@ impl Position {
         self.calculate_unrealized_pnl();
     }
 
+    /// Unrealized P&L net of fees paid and funding accrued so far
     pub fn calculate_unrealized_pnl(&mut self) {
-        match self.direction {
-            PositionDirection::Long => {
-                self.unrealized_pnl = (self.current_price - self.entry_price) * self.size;
-            }
-            PositionDirection::Short => {
-                self.unrealized_pnl = (self.entry_price - self.current_price) * self.size;
-            }
+        self.unrealized_pnl = self.pnl_breakdown().net_pnl;
+    }
+
+    /// Gross and net P&L: realized once closed, otherwise at the current price
+    pub fn pnl_breakdown(&self) -> PnlBreakdown {
+        match self.status {
+            PositionStatus::Closed => PnlBreakdown::new(
+                self.realized_gross_pnl,
+                self.fees,
+                self.funding,
+                self.entry_price * self.size,
+            ),
+            _ => calculator::unrealized_pnl(self, self.current_price, 0.0),
         }
     }
 
+    /// Accrue one funding settlement at `mark_price`
+    pub fn accrue_funding(&mut self, funding_rate: f64, mark_price: f64) {
+        let is_long = matches!(self.direction, PositionDirection::Long);
+        self.funding += calculator::funding_payment(is_long, self.size, mark_price, funding_rate);
+        self.calculate_unrealized_pnl();
+    }
+
     pub fn close_position(&mut self, exit_price: f64) -> f64 {
+        self.close_position_with_fee(exit_price, 0.0)
+    }
+
+    /// Close paying `exit_fee`; returns the net realized P&L
+    pub fn close_position_with_fee(&mut self, exit_price: f64, exit_fee: f64) -> f64 {
+        let pnl = calculator::realized_pnl(self, exit_price, exit_fee);
         self.current_price = exit_price;
-        self.calculate_unrealized_pnl();
-        self.realized_pnl = self.unrealized_pnl - self.fees;
+        self.fees = pnl.fees;
+        self.realized_gross_pnl = pnl.gross_pnl;
+        self.realized_pnl = pnl.net_pnl;
         self.unrealized_pnl = 0.0;
         self.status = PositionStatus::Closed;
         self.close_time = Some(
@@ -108,6 +161,20 @@ impl Position {
 
     pub fn set_stop_loss(&mut self, stop_loss: f64) {
         self.stop_loss = Some(stop_loss);
+        self.initial_stop.get_or_insert(stop_loss);
+    }
+
+    /// Open profit in multiples of the initial risk (entry to initial stop)
+    pub fn r_multiple(&self) -> Option<f64> {
+        let risk = (self.entry_price - self.initial_stop?).abs();
+        if risk <= 0.0 {
+            return None;
+        }
+        let gain = match self.direction {
+            PositionDirection::Long => self.current_price - self.entry_price,
+            PositionDirection::Short => self.entry_price - self.current_price,
+        };
+        Some(gain / risk)
     }
 
     pub fn set_take_profit(&mut self, take_profit: f64) {
@@ -151,6 +218,90 @@ impl Position {
     }
 }
 
+/// Moves the stop to entry plus round-trip fees once price advances far enough
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BreakEvenRule {
+    /// Open profit, in multiples of the initial risk, that triggers the move
+    pub trigger_r_multiple: f64,
+
+    /// Fee rate per side (0.00055 = 0.055% taker)
+    pub fee_rate: f64,
+
+    /// Extra distance beyond break-even in bps
+    pub buffer_bps: f64,
+}
+
+impl Default for BreakEvenRule {
+    fn default() -> Self {
+        Self {
+            trigger_r_multiple: 1.0,
+            fee_rate: 0.00055,
+            buffer_bps: 0.0,
+        }
+    }
+}
+
+impl BreakEvenRule {
+    /// Stop price that covers entry and exit fees
+    pub fn break_even_price(&self, position: &Position) -> f64 {
+        let offset = 2.0 * self.fee_rate + self.buffer_bps / 10_000.0;
+        match position.direction {
+            PositionDirection::Long => position.entry_price * (1.0 + offset),
+            PositionDirection::Short => position.entry_price * (1.0 - offset),
+        }
+    }
+}
+
+/// What happens to a position that outlives its max holding period
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ExpiryAction {
+    /// Close the position at the current price
+    Close,
+
+    /// Only mark the position as stale
+    Flag,
+}
+
+/// Maximum holding period declared by a strategy
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HoldingPolicy {
+    /// Maximum time a position may stay open, in seconds
+    pub max_holding_secs: u64,
+
+    /// Action once the period is exceeded
+    pub action: ExpiryAction,
+}
+
+/// A position that exceeded its max holding period
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HoldingExpiry {
+    pub position_id: String,
+    pub symbol: String,
+    pub strategy: String,
+    pub held_secs: u64,
+    pub action: ExpiryAction,
+}
+
+/// Emitted when a stop was moved to break-even
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BreakEvenEvent {
+    pub position_id: String,
+    pub symbol: String,
+    pub previous_stop: Option<f64>,
+    pub new_stop: f64,
+    pub r_multiple: f64,
+}
+
+impl BreakEvenEvent {
+    /// Build the event message to publish on the message bus
+    pub fn to_message(&self) -> Message {
+        Message::Custom(
+            BREAK_EVEN_TOPIC.to_string(),
+            serde_json::to_value(self).unwrap_or(Value::Null),
+        )
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct PositionManager {
     positions: HashMap<String, Position>,
@@ -158,6 +309,8 @@ pub struct PositionManager {
     total_realized_pnl: f64,
     total_unrealized_pnl: f64,
     max_positions: usize,
+    break_even_rule: Option<BreakEvenRule>,
+    holding_policies: HashMap<String, HoldingPolicy>,
 }
 
 impl PositionManager {
@@ -167,7 +320,9 @@ impl PositionManager {
             closed_positions: Vec::new(),
             total_realized_pnl: 0.0,
             total_unrealized_pnl: 0.0,
-            max_positions: 100,
+            max_positions: MAX_CONCURRENT_POSITIONS,
+            break_even_rule: None,
+            holding_policies: HashMap::new(),
         }
     }
 
@@ -189,9 +344,40 @@ impl PositionManager {
         Ok(position_id)
     }
 
+    /// Change the most positions open at once
+    pub fn set_max_positions(&mut self, max_positions: usize) {
+        self.max_positions = max_positions;
+    }
+
+    /// Most positions open at once
+    pub fn get_max_positions(&self) -> usize {
+        self.max_positions
+    }
+
+    pub fn open_position_for_strategy(
+        &mut self,
+        symbol: String,
+        direction: PositionDirection,
+        size: f64,
+        entry_price: f64,
+        strategy: String,
+    ) -> Result<String> {
+        let position_id = self.open_position(symbol, direction, size, entry_price)?;
+        if let Some(position) = self.positions.get_mut(&position_id) {
+            position.strategy = Some(strategy);
+        }
+
+        Ok(position_id)
+    }
+
     pub fn close_position(&mut self, position_id: &str, exit_price: f64) -> Result<f64> {
+        self.close_position_with_fee(position_id, exit_price, 0.0)
+    }
+
+    /// Close a position paying `exit_fee`; returns the net realized P&L
+    pub fn close_position_with_fee(&mut self, position_id: &str, exit_price: f64, exit_fee: f64) -> Result<f64> {
         if let Some(mut position) = self.positions.remove(position_id) {
-            let realized_pnl = position.close_position(exit_price);
+            let realized_pnl = position.close_position_with_fee(exit_price, exit_fee);
             self.total_realized_pnl += realized_pnl;
             self.closed_positions.push(position);
             Ok(realized_pnl)
@@ -218,6 +404,184 @@ impl PositionManager {
         self.calculate_total_unrealized_pnl();
     }
 
+    /// Accrue a funding settlement on every open position in `symbol`
+    pub fn apply_funding(&mut self, symbol: &str, funding_rate: f64, mark_price: f64) {
+        for position in self.positions.values_mut().filter(|p| p.symbol == symbol) {
+            position.accrue_funding(funding_rate, mark_price);
+        }
+        self.calculate_total_unrealized_pnl();
+    }
+
+    /// Book a fee paid on an open position, e.g. the entry taker fee
+    pub fn charge_fee(&mut self, position_id: &str, fee: f64) -> Result<()> {
+        if let Some(position) = self.positions.get_mut(position_id) {
+            position.fees += fee;
+            position.calculate_unrealized_pnl();
+            self.calculate_total_unrealized_pnl();
+            Ok(())
+        } else {
+            Err(anyhow::anyhow!("Position not found: {}", position_id))
+        }
+    }
+
+    /// Gross and net realized P&L of all closed positions
+    pub fn get_realized_pnl_breakdown(&self) -> PnlBreakdown {
+        self.closed_positions.iter()
+            .map(|p| p.pnl_breakdown())
+            .fold(PnlBreakdown::default(), |total, pnl| total.combine(&pnl))
+    }
+
+    /// Gross and net unrealized P&L of all open positions
+    pub fn get_unrealized_pnl_breakdown(&self) -> PnlBreakdown {
+        self.positions.values()
+            .map(|p| p.pnl_breakdown())
+            .fold(PnlBreakdown::default(), |total, pnl| total.combine(&pnl))
+    }
+
+    pub fn set_position_stop_loss(&mut self, position_id: &str, stop_loss: f64) -> Result<()> {
+        if let Some(position) = self.positions.get_mut(position_id) {
+            position.set_stop_loss(stop_loss);
+            Ok(())
+        } else {
+            Err(anyhow::anyhow!("Position not found: {}", position_id))
+        }
+    }
+
+    pub fn set_position_size(&mut self, position_id: &str, size: f64) -> Result<()> {
+        if let Some(position) = self.positions.get_mut(position_id) {
+            position.size = size;
+            position.calculate_unrealized_pnl();
+            Ok(())
+        } else {
+            Err(anyhow::anyhow!("Position not found: {}", position_id))
+        }
+    }
+
+    pub fn set_break_even_rule(&mut self, rule: Option<BreakEvenRule>) {
+        self.break_even_rule = rule;
+    }
+
+    /// Move stops to break-even on positions that reached the rule's R multiple
+    ///
+    /// Positions without an initial stop have no defined risk and are skipped. A stop
+    /// is only moved if break-even tightens it, and each position is moved once.
+    pub fn apply_break_even_rule(&mut self) -> Vec<BreakEvenEvent> {
+        let rule = match &self.break_even_rule {
+            Some(rule) => rule,
+            None => return Vec::new(),
+        };
+
+        let mut events = Vec::new();
+        for position in self.positions.values_mut().filter(|p| !p.break_even_applied) {
+            let r_multiple = match position.r_multiple() {
+                Some(r) if r >= rule.trigger_r_multiple => r,
+                _ => continue,
+            };
+
+            let new_stop = rule.break_even_price(position);
+            let tightens = match (position.stop_loss, &position.direction) {
+                (Some(stop), PositionDirection::Long) => new_stop > stop,
+                (Some(stop), PositionDirection::Short) => new_stop < stop,
+                (None, _) => true,
+            };
+            position.break_even_applied = true;
+            if !tightens {
+                continue;
+            }
+
+            info!("Moving stop of {} ({}) to break-even {:.6} at {:.2}R", position.id, position.symbol, new_stop, r_multiple);
+            events.push(BreakEvenEvent {
+                position_id: position.id.clone(),
+                symbol: position.symbol.clone(),
+                previous_stop: position.stop_loss,
+                new_stop,
+                r_multiple,
+            });
+            position.set_stop_loss(new_stop);
+        }
+
+        events.sort_by(|a, b| a.position_id.cmp(&b.position_id));
+        events
+    }
+
+    /// Set or clear the max holding period of a strategy's positions
+    pub fn set_holding_policy(&mut self, strategy: &str, policy: Option<HoldingPolicy>) {
+        match policy {
+            Some(policy) => self.holding_policies.insert(strategy.to_string(), policy),
+            None => self.holding_policies.remove(strategy),
+        };
+    }
+
+    pub fn get_holding_policy(&self, strategy: &str) -> Option<&HoldingPolicy> {
+        self.holding_policies.get(strategy)
+    }
+
+    /// Positions that outlived their strategy's max holding period at `now` (unix seconds)
+    ///
+    /// Expired positions are marked stale; closing `Close` ones is left to the caller,
+    /// which may need to unwind related state (trades, exchange orders) first.
+    pub fn check_holding_periods(&mut self, now: u64) -> Vec<HoldingExpiry> {
+        let mut expired = Vec::new();
+        for position in self.positions.values_mut() {
+            let strategy = match &position.strategy {
+                Some(strategy) => strategy,
+                None => continue,
+            };
+            let policy = match self.holding_policies.get(strategy) {
+                Some(policy) => policy,
+                None => continue,
+            };
+
+            let held_secs = now.saturating_sub(position.open_time);
+            if held_secs < policy.max_holding_secs {
+                continue;
+            }
+            if !position.stale {
+                warn!("Position {} ({}) held {}s, over the {}s limit of {}",
+                      position.id, position.symbol, held_secs, policy.max_holding_secs, strategy);
+                position.stale = true;
+            }
+            expired.push(HoldingExpiry {
+                position_id: position.id.clone(),
+                symbol: position.symbol.clone(),
+                strategy: strategy.clone(),
+                held_secs,
+                action: policy.action,
+            });
+        }
+
+        expired.sort_by(|a, b| a.position_id.cmp(&b.position_id));
+        expired
+    }
+
+    /// Close expired positions whose policy says so at their current price; flags the rest
+    pub fn enforce_holding_periods(&mut self, now: u64) -> Vec<HoldingExpiry> {
+        let expired = self.check_holding_periods(now);
+        for expiry in expired.iter().filter(|e| e.action == ExpiryAction::Close) {
+            let price = match self.positions.get(&expiry.position_id) {
+                Some(position) => position.current_price,
+                None => continue,
+            };
+            if let Err(e) = self.close_position(&expiry.position_id, price) {
+                warn!("Failed to close expired position {}: {}", expiry.position_id, e);
+            }
+        }
+        expired
+    }
+
+    /// Re-insert an open position restored from persisted state
+    pub fn restore_position(&mut self, position: Position) -> Result<()> {
+        if matches!(position.status, PositionStatus::Closed) {
+            return Err(anyhow::anyhow!("Position {} is closed", position.id));
+        }
+        if !self.positions.contains_key(&position.id) && self.positions.len() >= self.max_positions {
+            return Err(anyhow::anyhow!("Maximum number of positions reached"));
+        }
+        self.positions.insert(position.id.clone(), position);
+        self.calculate_total_unrealized_pnl();
+        Ok(())
+    }
+
     pub fn get_position(&self, position_id: &str) -> Option<&Position> {
         self.positions.get(position_id)
     }
@@ -228,6 +592,12 @@ impl PositionManager {
             .collect()
     }
 
+    pub fn get_positions_by_strategy(&self, strategy: &str) -> Vec<&Position> {
+        self.positions.values()
+            .filter(|pos| pos.strategy.as_deref() == Some(strategy))
+            .collect()
+    }
+
     pub fn get_all_positions(&self) -> Vec<&Position> {
         self.positions.values().collect()
     }
@@ -302,3 +672,60 @@ impl Default for PositionManager {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_break_even_rule_moves_stop_once() {
+        let mut manager = PositionManager::new();
+        manager.set_break_even_rule(Some(BreakEvenRule { trigger_r_multiple: 1.0, fee_rate: 0.001, buffer_bps: 0.0 }));
+        let id = manager.open_position("BTCUSDT".to_string(), PositionDirection::Long, 1.0, 100.0).unwrap();
+        manager.set_position_stop_loss(&id, 95.0).unwrap();
+
+        manager.update_position_price(&id, 104.0).unwrap();
+        assert!(manager.apply_break_even_rule().is_empty());
+
+        manager.update_position_price(&id, 105.0).unwrap();
+        let events = manager.apply_break_even_rule();
+        assert_eq!(events.len(), 1);
+        assert!((events[0].new_stop - 100.2).abs() < 1e-9);
+        assert_eq!(events[0].previous_stop, Some(95.0));
+        assert_eq!(manager.get_position(&id).unwrap().initial_stop, Some(95.0));
+
+        manager.update_position_price(&id, 110.0).unwrap();
+        assert!(manager.apply_break_even_rule().is_empty());
+    }
+
+    #[test]
+    fn test_fees_and_funding_come_out_of_net_pnl() {
+        let mut manager = PositionManager::new();
+        let id = manager.open_position("BTCUSDT".to_string(), PositionDirection::Long, 2.0, 100.0).unwrap();
+        manager.charge_fee(&id, 0.11).unwrap();
+        manager.apply_funding("BTCUSDT", 0.0001, 100.0);
+
+        let net = manager.close_position_with_fee(&id, 110.0, 0.121).unwrap();
+        let pnl = manager.get_realized_pnl_breakdown();
+        assert!((pnl.gross_pnl - 20.0).abs() < 1e-9);
+        assert!((pnl.fees - 0.231).abs() < 1e-9);
+        assert!((pnl.funding - 0.02).abs() < 1e-9);
+        assert!((net - (20.0 - 0.231 - 0.02)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_holding_period_expiry() {
+        let mut manager = PositionManager::new();
+        manager.set_holding_policy("scalper", Some(HoldingPolicy { max_holding_secs: 60, action: ExpiryAction::Close }));
+        manager.set_holding_policy("swing", Some(HoldingPolicy { max_holding_secs: 60, action: ExpiryAction::Flag }));
+        let scalp = manager.open_position_for_strategy("BTCUSDT".to_string(), PositionDirection::Long, 1.0, 100.0, "scalper".to_string()).unwrap();
+        let swing = manager.open_position_for_strategy("ETHUSDT".to_string(), PositionDirection::Long, 1.0, 100.0, "swing".to_string()).unwrap();
+        let opened = manager.get_position(&scalp).unwrap().open_time;
+
+        assert!(manager.enforce_holding_periods(opened + 59).is_empty());
+        let expired = manager.enforce_holding_periods(opened + 120);
+        assert_eq!(expired.len(), 2);
+        assert!(manager.get_position(&scalp).is_none());
+        assert!(manager.get_position(&swing).unwrap().stale);
+    }
+}
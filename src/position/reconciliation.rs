@@ -0,0 +1,422 @@
+//! Position Reconciliation
+//!
+//! This module periodically compares `PositionManager` state with the exchange's
+//! open positions and recent executions. Drift such as positions closed manually,
+//! liquidated or auto-deleveraged on the exchange, size differences and positions
+//! the system does not know about is reported to the `UnifiedErrorManager` and,
+//! when enabled, corrected locally so the two never silently diverge.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::sync::Arc;
+use std::time::Duration;
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use tokio::sync::Mutex;
+use tokio::task::JoinHandle;
+use tracing::{debug, info, warn};
+
+use crate::exchange::bybit::adapter::BybitAdapter;
+use crate::exchange::bybit::types::{BybitExecution, BybitPosition, PositionSide};
+use crate::monitoring::unified_error_manager::{ErrorCategory, ErrorSeverity, UnifiedErrorManager};
+use crate::position::position_manager::{PositionDirection, PositionManager};
+
+/// Strategy tag of positions adopted from the exchange
+pub const RECONCILED_STRATEGY: &str = "exchange_reconciliation";
+
+/// Exchange state needed for reconciliation
+#[async_trait]
+pub trait ExchangePositionSource: Send + Sync {
+    /// Open positions
+    async fn get_open_positions(&self) -> Result<Vec<BybitPosition>>;
+
+    /// Executions on `symbol` since `start_time_ms`
+    async fn get_recent_executions(&self, symbol: &str, start_time_ms: i64) -> Result<Vec<BybitExecution>>;
+}
+
+#[async_trait]
+impl ExchangePositionSource for BybitAdapter {
+    async fn get_open_positions(&self) -> Result<Vec<BybitPosition>> {
+        Ok(self.get_positions(None).await?.into_iter().filter(|p| p.size > 0.0).collect())
+    }
+
+    async fn get_recent_executions(&self, symbol: &str, start_time_ms: i64) -> Result<Vec<BybitExecution>> {
+        self.get_executions(Some(symbol), Some(start_time_ms)).await
+    }
+}
+
+/// Reconciliation configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReconciliationConfig {
+    /// Seconds between reconciliation runs
+    pub interval_secs: u64,
+
+    /// Relative size difference tolerated before reporting drift
+    pub size_tolerance: f64,
+
+    /// Correct local state to match the exchange
+    pub auto_correct: bool,
+
+    /// Track exchange positions the system did not open
+    pub adopt_untracked: bool,
+}
+
+impl Default for ReconciliationConfig {
+    fn default() -> Self {
+        Self {
+            interval_secs: 30,
+            size_tolerance: 0.001,
+            auto_correct: true,
+            adopt_untracked: true,
+        }
+    }
+}
+
+/// Why a locally open position is gone on the exchange
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CloseCause {
+    /// Closed by a regular order not placed by the system
+    ManualClose,
+
+    /// Liquidated
+    Liquidation,
+
+    /// Auto-deleveraged
+    Adl,
+
+    /// No execution explains the close
+    Unknown,
+}
+
+/// Kind of divergence
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum DriftKind {
+    /// Open locally, flat on the exchange
+    ClosedOnExchange { cause: CloseCause, exit_price: Option<f64> },
+
+    /// Same side, different size
+    SizeMismatch { local: f64, exchange: f64 },
+
+    /// Opposite sides
+    SideMismatch,
+
+    /// Open on the exchange, unknown locally
+    Untracked,
+}
+
+/// One divergence between local and exchange state
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PositionDrift {
+    /// Symbol
+    pub symbol: String,
+
+    /// Local positions involved
+    pub position_ids: Vec<String>,
+
+    /// Kind
+    pub kind: DriftKind,
+
+    /// Whether local state was corrected
+    pub corrected: bool,
+}
+
+/// Result of one reconciliation run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReconciliationReport {
+    /// Symbols compared
+    pub symbols_checked: usize,
+
+    /// Divergences found
+    pub drifts: Vec<PositionDrift>,
+}
+
+impl ReconciliationReport {
+    /// Whether local state matched the exchange
+    pub fn is_consistent(&self) -> bool {
+        self.drifts.is_empty()
+    }
+}
+
+/// Signed size: positive long, negative short
+fn signed(direction: &PositionDirection, size: f64) -> f64 {
+    match direction {
+        PositionDirection::Long => size,
+        PositionDirection::Short => -size,
+    }
+}
+
+/// Classify a close from executions after the position opened
+fn close_cause(executions: &[BybitExecution]) -> (CloseCause, Option<f64>) {
+    let latest = executions.iter().filter(|e| e.exec_qty > 0.0).max_by_key(|e| e.exec_time);
+    match latest {
+        Some(e) if e.exec_type == "BustTrade" => (CloseCause::Liquidation, Some(e.exec_price)),
+        Some(e) if e.exec_type == "AdlTrade" => (CloseCause::Adl, Some(e.exec_price)),
+        Some(e) if e.exec_type == "Trade" => (CloseCause::ManualClose, Some(e.exec_price)),
+        _ => (CloseCause::Unknown, None),
+    }
+}
+
+/// Compares local positions with the exchange and corrects drift
+pub struct PositionReconciler {
+    /// Configuration
+    config: ReconciliationConfig,
+}
+
+impl PositionReconciler {
+    /// Create a new reconciler
+    pub fn new(config: ReconciliationConfig) -> Self {
+        Self { config }
+    }
+
+    /// Run one reconciliation pass
+    pub async fn reconcile(
+        &self,
+        source: &dyn ExchangePositionSource,
+        positions: &mut PositionManager,
+        errors: &mut UnifiedErrorManager,
+    ) -> Result<ReconciliationReport> {
+        let exchange: HashMap<String, BybitPosition> = source.get_open_positions().await?
+            .into_iter()
+            .filter(|p| p.size > 0.0 && p.side != PositionSide::None)
+            .map(|p| (p.symbol.clone(), p))
+            .collect();
+
+        // Local positions grouped by symbol
+        let mut local: BTreeMap<String, Vec<(String, f64, f64, u64)>> = BTreeMap::new();
+        for position in positions.get_all_positions() {
+            local.entry(position.symbol.clone()).or_default().push((
+                position.id.clone(),
+                signed(&position.direction, position.size),
+                position.current_price,
+                position.open_time,
+            ));
+        }
+
+        let symbols: BTreeSet<String> = local.keys().chain(exchange.keys()).cloned().collect();
+        let mut drifts = Vec::new();
+
+        for symbol in &symbols {
+            let held = local.get(symbol).cloned().unwrap_or_default();
+            let ids: Vec<String> = held.iter().map(|(id, ..)| id.clone()).collect();
+            let local_size: f64 = held.iter().map(|(_, size, ..)| size).sum();
+            let remote = exchange.get(symbol);
+            let remote_size = remote.map(|p| match p.side {
+                PositionSide::Sell => -p.size,
+                _ => p.size,
+            }).unwrap_or(0.0);
+
+            let kind = match remote {
+                None if held.is_empty() => continue,
+                None => {
+                    let opened_ms = held.iter().map(|(.., open_time)| *open_time).min().unwrap_or(0) as i64 * 1000;
+                    let executions = match source.get_recent_executions(symbol, opened_ms).await {
+                        Ok(executions) => executions,
+                        Err(e) => {
+                            warn!("Could not load executions for {}: {}", symbol, e);
+                            Vec::new()
+                        }
+                    };
+                    let (cause, exit_price) = close_cause(&executions);
+                    DriftKind::ClosedOnExchange { cause, exit_price }
+                }
+                Some(_) if held.is_empty() => DriftKind::Untracked,
+                Some(_) if local_size.signum() != remote_size.signum() => DriftKind::SideMismatch,
+                Some(_) => {
+                    let difference = (local_size - remote_size).abs() / remote_size.abs().max(f64::EPSILON);
+                    if difference <= self.config.size_tolerance {
+                        continue;
+                    }
+                    DriftKind::SizeMismatch { local: local_size.abs(), exchange: remote_size.abs() }
+                }
+            };
+
+            let corrected = self.config.auto_correct && self.correct(positions, &held, remote, &kind);
+            let severity = match kind {
+                DriftKind::ClosedOnExchange { cause: CloseCause::Liquidation, .. }
+                | DriftKind::ClosedOnExchange { cause: CloseCause::Adl, .. }
+                | DriftKind::SideMismatch => ErrorSeverity::Critical,
+                _ => ErrorSeverity::Warning,
+            };
+            errors.report_with_context(
+                ErrorCategory::Reconciliation,
+                severity,
+                "position_reconciler",
+                &format!("{} drift on {}: {:?}", if corrected { "Corrected" } else { "Detected" }, symbol, kind),
+                HashMap::from([
+                    ("symbol".to_string(), symbol.clone()),
+                    ("local_size".to_string(), local_size.to_string()),
+                    ("exchange_size".to_string(), remote_size.to_string()),
+                    ("position_ids".to_string(), ids.join(",")),
+                ]),
+            );
+
+            drifts.push(PositionDrift {
+                symbol: symbol.clone(),
+                position_ids: ids,
+                kind,
+                corrected,
+            });
+        }
+
+        if drifts.is_empty() {
+            debug!("Reconciliation of {} symbols found no drift", symbols.len());
+        } else {
+            info!("Reconciliation found {} drifts across {} symbols", drifts.len(), symbols.len());
+        }
+
+        Ok(ReconciliationReport {
+            symbols_checked: symbols.len(),
+            drifts,
+        })
+    }
+
+    /// Bring local state in line with the exchange; returns whether it did
+    fn correct(
+        &self,
+        positions: &mut PositionManager,
+        held: &[(String, f64, f64, u64)],
+        remote: Option<&BybitPosition>,
+        kind: &DriftKind,
+    ) -> bool {
+        let close_all = |positions: &mut PositionManager, price: Option<f64>| {
+            held.iter().all(|(id, _, current_price, _)| {
+                positions.close_position(id, price.unwrap_or(*current_price)).is_ok()
+            })
+        };
+        let adopt = |positions: &mut PositionManager, remote: &BybitPosition| {
+            let direction = match remote.side {
+                PositionSide::Sell => PositionDirection::Short,
+                _ => PositionDirection::Long,
+            };
+            positions.open_position_for_strategy(
+                remote.symbol.clone(),
+                direction,
+                remote.size,
+                remote.entry_price,
+                RECONCILED_STRATEGY.to_string(),
+            )
+            .and_then(|id| match remote.stop_loss {
+                Some(stop) => positions.set_position_stop_loss(&id, stop),
+                None => Ok(()),
+            })
+            .is_ok()
+        };
+
+        match (kind, remote) {
+            (DriftKind::ClosedOnExchange { exit_price, .. }, _) => close_all(positions, *exit_price),
+            (DriftKind::Untracked, Some(remote)) => self.config.adopt_untracked && adopt(positions, remote),
+            (DriftKind::SideMismatch, Some(remote)) => {
+                close_all(positions, Some(remote.mark_price)) && self.config.adopt_untracked && adopt(positions, remote)
+            }
+            (DriftKind::SizeMismatch { local, exchange }, _) => {
+                // Scale every local position so the total matches the exchange
+                let ratio = exchange / local;
+                held.iter().all(|(id, size, ..)| positions.set_position_size(id, size.abs() * ratio).is_ok())
+            }
+            _ => false,
+        }
+    }
+
+    /// Reconcile every `interval_secs` in a background task
+    pub fn spawn(
+        self,
+        source: Arc<dyn ExchangePositionSource>,
+        positions: Arc<Mutex<PositionManager>>,
+        errors: Arc<Mutex<UnifiedErrorManager>>,
+    ) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(Duration::from_secs(self.config.interval_secs.max(1)));
+            loop {
+                interval.tick().await;
+                let mut positions = positions.lock().await;
+                let mut errors = errors.lock().await;
+                if let Err(e) = self.reconcile(source.as_ref(), &mut positions, &mut errors).await {
+                    errors.report(
+                        ErrorCategory::Exchange,
+                        ErrorSeverity::Error,
+                        "position_reconciler",
+                        &format!("Reconciliation failed at {}: {}", Utc::now(), e),
+                    );
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct MockExchange {
+        positions: Vec<BybitPosition>,
+        executions: Vec<BybitExecution>,
+    }
+
+    #[async_trait]
+    impl ExchangePositionSource for MockExchange {
+        async fn get_open_positions(&self) -> Result<Vec<BybitPosition>> {
+            Ok(self.positions.clone())
+        }
+
+        async fn get_recent_executions(&self, symbol: &str, _start_time_ms: i64) -> Result<Vec<BybitExecution>> {
+            Ok(self.executions.iter().filter(|e| e.symbol == symbol).cloned().collect())
+        }
+    }
+
+    fn exchange_position(symbol: &str, side: PositionSide, size: f64) -> BybitPosition {
+        BybitPosition {
+            position_idx: 0,
+            symbol: symbol.to_string(),
+            side,
+            size,
+            entry_price: 100.0,
+            leverage: 10.0,
+            mark_price: 100.0,
+            position_value: size * 100.0,
+            unrealised_pnl: 0.0,
+            take_profit: None,
+            stop_loss: None,
+            created_time: String::new(),
+            updated_time: String::new(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_reconcile_detects_and_corrects_drift() {
+        let mut positions = PositionManager::new();
+        positions.open_position("BTCUSDT".to_string(), PositionDirection::Long, 1.0, 100.0).unwrap();
+        positions.open_position("ETHUSDT".to_string(), PositionDirection::Long, 2.0, 100.0).unwrap();
+
+        let exchange = MockExchange {
+            positions: vec![
+                exchange_position("ETHUSDT", PositionSide::Buy, 1.5),
+                exchange_position("SOLUSDT", PositionSide::Sell, 3.0),
+            ],
+            executions: vec![BybitExecution {
+                symbol: "BTCUSDT".to_string(),
+                order_id: "1".to_string(),
+                side: "Sell".to_string(),
+                exec_price: 90.0,
+                exec_qty: 1.0,
+                exec_type: "BustTrade".to_string(),
+                exec_time: i64::MAX,
+            }],
+        };
+
+        let mut errors = UnifiedErrorManager::default();
+        let reconciler = PositionReconciler::new(ReconciliationConfig::default());
+        let report = reconciler.reconcile(&exchange, &mut positions, &mut errors).await.unwrap();
+
+        let kinds: Vec<&DriftKind> = report.drifts.iter().map(|d| &d.kind).collect();
+        assert_eq!(kinds, vec![
+            &DriftKind::ClosedOnExchange { cause: CloseCause::Liquidation, exit_price: Some(90.0) },
+            &DriftKind::SizeMismatch { local: 2.0, exchange: 1.5 },
+            &DriftKind::Untracked,
+        ]);
+        assert_eq!(errors.get_count(ErrorCategory::Reconciliation), 3);
+
+        let second = reconciler.reconcile(&exchange, &mut positions, &mut errors).await.unwrap();
+        assert!(second.is_consistent());
+    }
+}
@@ -0,0 +1,420 @@
+//! Position Sizing
+//!
+//! This module turns a trade setup into a position size through the `Sizer` trait,
+//! so the sizing rule is a choice rather than a formula buried in the caller. It
+//! provides fixed risk per trade, fractional Kelly, volatility targeting and risk
+//! parity across concurrent positions, and a registry that selects a sizer per
+//! strategy. Every sizer is capped by the notional the caller derives from its
+//! capital config and current equity.
+
+use std::collections::HashMap;
+use std::fmt::Debug;
+
+/// Everything a sizer may base its decision on
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SizingContext {
+    /// Account equity
+    pub equity: f64,
+
+    /// Planned entry price
+    pub entry_price: f64,
+
+    /// Planned stop loss price
+    pub stop_loss_price: f64,
+
+    /// Maximum notional the caller allows for this position
+    pub max_notional: f64,
+
+    /// Recent per-bar volatility of returns, if known
+    pub volatility: Option<f64>,
+
+    /// Observed win rate of the strategy (0.0-1.0), if known
+    pub win_rate: Option<f64>,
+
+    /// Observed average win divided by average loss of the strategy, if known
+    pub payoff_ratio: Option<f64>,
+
+    /// Most positions that may be open at once, if known
+    pub max_positions: Option<usize>,
+}
+
+impl SizingContext {
+    /// Create a context without volatility or edge statistics
+    pub fn new(equity: f64, entry_price: f64, stop_loss_price: f64, max_notional: f64) -> Self {
+        Self {
+            equity,
+            entry_price,
+            stop_loss_price,
+            max_notional,
+            volatility: None,
+            win_rate: None,
+            payoff_ratio: None,
+            max_positions: None,
+        }
+    }
+
+    /// Price distance from entry to stop
+    pub fn risk_per_unit(&self) -> f64 {
+        (self.entry_price - self.stop_loss_price).abs()
+    }
+
+    /// Quantity limited to the maximum notional; invalid inputs size to zero
+    pub fn cap(&self, quantity: f64) -> f64 {
+        if self.entry_price <= 0.0 || !quantity.is_finite() || quantity <= 0.0 {
+            return 0.0;
+        }
+        quantity.min(self.max_notional.max(0.0) / self.entry_price)
+    }
+}
+
+/// Position sizing rule
+pub trait Sizer: Debug + Send + Sync {
+    /// Name of the rule
+    fn get_name(&self) -> &str;
+
+    /// Position size in base units
+    fn size(&self, context: &SizingContext) -> f64;
+}
+
+/// Risks a fixed fraction of equity between entry and stop
+#[derive(Debug, Clone)]
+pub struct FixedRiskSizer {
+    /// Fraction of equity lost if the stop is hit
+    risk_fraction: f64,
+}
+
+impl FixedRiskSizer {
+    /// Create a new fixed-risk sizer
+    pub fn new(risk_fraction: f64) -> Self {
+        Self { risk_fraction: risk_fraction.max(0.0) }
+    }
+}
+
+impl Default for FixedRiskSizer {
+    fn default() -> Self {
+        Self::new(0.01)
+    }
+}
+
+impl Sizer for FixedRiskSizer {
+    fn get_name(&self) -> &str {
+        "fixed_risk"
+    }
+
+    fn size(&self, context: &SizingContext) -> f64 {
+        let risk_per_unit = context.risk_per_unit();
+        if risk_per_unit <= 0.0 {
+            return 0.0;
+        }
+        context.cap(context.equity * self.risk_fraction / risk_per_unit)
+    }
+}
+
+/// Risks a fraction of the Kelly-optimal share of equity
+#[derive(Debug, Clone)]
+pub struct FractionalKellySizer {
+    /// Share of the full Kelly bet actually taken
+    fraction: f64,
+
+    /// Win rate assumed until the strategy has its own
+    default_win_rate: f64,
+
+    /// Payoff ratio assumed until the strategy has its own
+    default_payoff_ratio: f64,
+
+    /// Notional cap on top of the caller's, if any
+    max_notional: Option<f64>,
+}
+
+impl FractionalKellySizer {
+    /// Create a new fractional Kelly sizer
+    pub fn new(fraction: f64, default_win_rate: f64, default_payoff_ratio: f64) -> Self {
+        Self {
+            fraction: fraction.clamp(0.0, 1.0),
+            default_win_rate,
+            default_payoff_ratio,
+            max_notional: None,
+        }
+    }
+
+    /// Override the notional cap
+    pub fn with_max_notional(mut self, max_notional: f64) -> Self {
+        self.max_notional = Some(max_notional);
+        self
+    }
+
+    /// Full Kelly fraction for a win rate and payoff ratio (never negative)
+    pub fn kelly_fraction(win_rate: f64, payoff_ratio: f64) -> f64 {
+        if payoff_ratio <= 0.0 {
+            return 0.0;
+        }
+        (win_rate - (1.0 - win_rate) / payoff_ratio).max(0.0)
+    }
+}
+
+impl Default for FractionalKellySizer {
+    fn default() -> Self {
+        Self::new(0.25, 0.5, 1.5)
+    }
+}
+
+impl Sizer for FractionalKellySizer {
+    fn get_name(&self) -> &str {
+        "fractional_kelly"
+    }
+
+    fn size(&self, context: &SizingContext) -> f64 {
+        let risk_per_unit = context.risk_per_unit();
+        if risk_per_unit <= 0.0 {
+            return 0.0;
+        }
+
+        let kelly = Self::kelly_fraction(
+            context.win_rate.unwrap_or(self.default_win_rate),
+            context.payoff_ratio.unwrap_or(self.default_payoff_ratio),
+        );
+        let quantity = context.equity * kelly * self.fraction / risk_per_unit;
+        let quantity = context.cap(quantity);
+        self.max_notional.map_or(quantity, |cap| quantity.min(cap.max(0.0) / context.entry_price))
+    }
+}
+
+/// Sizes so the position's expected per-bar move is a fixed share of equity
+#[derive(Debug, Clone)]
+pub struct VolatilityTargetSizer {
+    /// Target per-bar volatility of the position as a fraction of equity
+    target_volatility: f64,
+}
+
+impl VolatilityTargetSizer {
+    /// Create a new volatility targeting sizer
+    pub fn new(target_volatility: f64) -> Self {
+        Self { target_volatility: target_volatility.max(0.0) }
+    }
+}
+
+impl Default for VolatilityTargetSizer {
+    fn default() -> Self {
+        Self::new(0.005)
+    }
+}
+
+impl Sizer for VolatilityTargetSizer {
+    fn get_name(&self) -> &str {
+        "volatility_target"
+    }
+
+    fn size(&self, context: &SizingContext) -> f64 {
+        match context.volatility {
+            Some(volatility) if volatility > 0.0 && context.entry_price > 0.0 => {
+                let notional = context.equity * self.target_volatility / volatility;
+                context.cap(notional / context.entry_price)
+            }
+            _ => 0.0,
+        }
+    }
+}
+
+/// Sizes every concurrent position to the same share of a portfolio volatility target
+///
+/// Each of the `max_positions` slots gets a volatility budget of
+/// `target / sqrt(max_positions)`, so positions contribute equal volatility and,
+/// treating them as uncorrelated, add up to the target when every slot is used.
+/// Calm symbols get a larger notional than volatile ones instead of all getting
+/// the same notional.
+#[derive(Debug, Clone)]
+pub struct RiskParitySizer {
+    /// Target per-bar volatility of the whole book as a fraction of equity
+    target_volatility: f64,
+
+    /// Slots assumed when the context does not say
+    default_positions: usize,
+}
+
+impl RiskParitySizer {
+    /// Create a new risk parity sizer
+    pub fn new(target_volatility: f64, default_positions: usize) -> Self {
+        Self {
+            target_volatility: target_volatility.max(0.0),
+            default_positions: default_positions.max(1),
+        }
+    }
+
+    /// Volatility budget of one position
+    pub fn position_budget(&self, positions: usize) -> f64 {
+        self.target_volatility / (positions.max(1) as f64).sqrt()
+    }
+}
+
+impl Default for RiskParitySizer {
+    fn default() -> Self {
+        Self::new(0.01, 2)
+    }
+}
+
+impl Sizer for RiskParitySizer {
+    fn get_name(&self) -> &str {
+        "risk_parity"
+    }
+
+    fn size(&self, context: &SizingContext) -> f64 {
+        match context.volatility {
+            Some(volatility) if volatility > 0.0 && context.entry_price > 0.0 => {
+                let positions = context.max_positions.unwrap_or(self.default_positions);
+                let notional = context.equity * self.position_budget(positions) / volatility;
+                context.cap(notional / context.entry_price)
+            }
+            _ => 0.0,
+        }
+    }
+}
+
+/// Share of capital per position so each contributes equal volatility (inverse volatility weights)
+pub fn risk_parity_weights(volatilities: &[f64]) -> Vec<f64> {
+    let inverse: Vec<f64> = volatilities.iter()
+        .map(|v| if *v > 0.0 && v.is_finite() { 1.0 / v } else { 0.0 })
+        .collect();
+    let total: f64 = inverse.iter().sum();
+    if total <= 0.0 {
+        return vec![0.0; volatilities.len()];
+    }
+    inverse.iter().map(|w| w / total).collect()
+}
+
+/// Standard deviation of bar-to-bar returns of `closes`
+pub fn realized_volatility(closes: &[f64]) -> Option<f64> {
+    let returns: Vec<f64> = closes.windows(2)
+        .filter(|w| w[0] > 0.0)
+        .map(|w| w[1] / w[0] - 1.0)
+        .collect();
+    if returns.len() < 2 {
+        return None;
+    }
+
+    let mean = returns.iter().sum::<f64>() / returns.len() as f64;
+    let variance = returns.iter().map(|r| (r - mean).powi(2)).sum::<f64>() / (returns.len() - 1) as f64;
+    Some(variance.sqrt())
+}
+
+/// Selects the sizer of each strategy
+#[derive(Debug)]
+pub struct SizerRegistry {
+    /// Sizer for strategies without their own
+    default: Box<dyn Sizer>,
+
+    /// Sizers by strategy name
+    by_strategy: HashMap<String, Box<dyn Sizer>>,
+}
+
+impl SizerRegistry {
+    /// Create a registry with a default sizer
+    pub fn new(default: Box<dyn Sizer>) -> Self {
+        Self { default, by_strategy: HashMap::new() }
+    }
+
+    /// Replace the default sizer
+    pub fn set_default(&mut self, sizer: Box<dyn Sizer>) {
+        self.default = sizer;
+    }
+
+    /// Use `sizer` for `strategy`
+    pub fn set_sizer(&mut self, strategy: &str, sizer: Box<dyn Sizer>) {
+        self.by_strategy.insert(strategy.to_string(), sizer);
+    }
+
+    /// Go back to the default sizer for `strategy`
+    pub fn remove_sizer(&mut self, strategy: &str) {
+        self.by_strategy.remove(strategy);
+    }
+
+    /// Sizer used for `strategy`
+    pub fn get_sizer(&self, strategy: &str) -> &dyn Sizer {
+        self.by_strategy.get(strategy).map(|s| s.as_ref()).unwrap_or(self.default.as_ref())
+    }
+
+    /// Size a position for `strategy`
+    pub fn size(&self, strategy: &str, context: &SizingContext) -> f64 {
+        self.get_sizer(strategy).size(context)
+    }
+}
+
+impl Default for SizerRegistry {
+    fn default() -> Self {
+        Self::new(Box::new(FixedRiskSizer::default()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sizers_and_per_strategy_selection() {
+        let context = SizingContext {
+            volatility: Some(0.01),
+            win_rate: Some(0.6),
+            payoff_ratio: Some(2.0),
+            ..SizingContext::new(1_000.0, 100.0, 95.0, 500.0)
+        };
+
+        // 1% of 1000 over a 5 unit stop
+        assert!((FixedRiskSizer::default().size(&context) - 2.0).abs() < 1e-9);
+
+        // Full Kelly 0.4, quarter Kelly risks 10% of equity, but the 500 notional caps it
+        assert!((FractionalKellySizer::kelly_fraction(0.6, 2.0) - 0.4).abs() < 1e-9);
+        assert!((FractionalKellySizer::default().size(&context) - 5.0).abs() < 1e-9);
+        assert!((FractionalKellySizer::default().with_max_notional(12.0).size(&context) - 0.12).abs() < 1e-9);
+
+        // 0.5% of equity per 1% move is 500 notional
+        assert!((VolatilityTargetSizer::default().size(&context) - 5.0).abs() < 1e-9);
+
+        let mut registry = SizerRegistry::default();
+        registry.set_sizer("momentum", Box::new(VolatilityTargetSizer::default()));
+        assert_eq!(registry.get_sizer("momentum").get_name(), "volatility_target");
+        assert_eq!(registry.get_sizer("other").get_name(), "fixed_risk");
+    }
+
+    #[test]
+    fn test_risk_parity_gives_positions_equal_volatility() {
+        let context = SizingContext {
+            volatility: Some(0.01),
+            ..SizingContext::new(1_000.0, 100.0, 95.0, 1_000.0)
+        };
+
+        // Two slots split a 1% book target into 0.707% each: 707 notional at 1% volatility
+        let risk_parity = RiskParitySizer::default();
+        assert!((risk_parity.size(&context) - 7.0710678).abs() < 1e-6);
+
+        // Calm symbols get more notional, volatile ones less, for the same volatility contribution
+        let contribution = |volatility: f64| {
+            let sized = SizingContext { volatility: Some(volatility), max_notional: 10_000.0, ..context };
+            risk_parity.size(&sized) * sized.entry_price * volatility
+        };
+        assert!((contribution(0.005) - contribution(0.02)).abs() < 1e-9);
+        assert!(risk_parity.size(&SizingContext { volatility: Some(0.005), max_notional: 10_000.0, ..context })
+            > risk_parity.size(&SizingContext { volatility: Some(0.02), max_notional: 10_000.0, ..context }));
+
+        // Uncorrelated positions filling every slot add up to the book target
+        for positions in [1, 4, 9] {
+            let budget = risk_parity.position_budget(positions);
+            assert!((budget * (positions as f64).sqrt() - 0.01).abs() < 1e-12);
+        }
+        let four_slots = SizingContext { max_positions: Some(4), ..context };
+        assert!((risk_parity.size(&four_slots) - 5.0).abs() < 1e-9);
+
+        // The notional cap still applies, and nothing is sized without a volatility estimate
+        assert!((risk_parity.size(&SizingContext { max_notional: 300.0, ..context }) - 3.0).abs() < 1e-9);
+        assert_eq!(risk_parity.size(&SizingContext { volatility: None, ..context }), 0.0);
+        assert_eq!(risk_parity.size(&SizingContext { volatility: Some(0.0), ..context }), 0.0);
+
+        let weights = risk_parity_weights(&[0.01, 0.02, 0.0]);
+        assert!((weights[0] - 2.0 / 3.0).abs() < 1e-9);
+        assert!((weights[1] - 1.0 / 3.0).abs() < 1e-9);
+        assert_eq!(weights[2], 0.0);
+        assert_eq!(risk_parity_weights(&[0.0, f64::NAN]), vec![0.0, 0.0]);
+
+        let mut registry = SizerRegistry::default();
+        registry.set_sizer("basket", Box::new(risk_parity));
+        assert_eq!(registry.get_sizer("basket").get_name(), "risk_parity");
+    }
+}
@@ -0,0 +1,375 @@
+//! Trailing Stop Manager
+//!
+//! This module trails stop losses behind open positions using a fixed percentage,
+//! an ATR multiple or a chandelier exit (highest high / lowest low over a lookback
+//! minus an ATR multiple). Stops are driven by tick and candle events, only ever
+//! ratchet in the position's favor, and can be pushed to the exchange as stop
+//! amendments.
+
+use std::collections::{HashMap, VecDeque};
+use anyhow::Result;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use tracing::{debug, warn};
+
+use crate::exchange::bybit::adapter::BybitAdapter;
+use crate::position::position_manager::{Position, PositionDirection, PositionManager};
+use crate::strategy::simple_strategy::Candle;
+
+/// How the stop distance is computed
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum TrailingMode {
+    /// Fixed percentage behind the best price since entry
+    Percentage { percent: f64 },
+
+    /// ATR multiple behind the best price since entry
+    Atr { period: usize, multiplier: f64 },
+
+    /// ATR multiple behind the highest high (long) or lowest low (short) of the last `period` candles
+    Chandelier { period: usize, multiplier: f64 },
+}
+
+/// Trailing stop configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrailingStopConfig {
+    /// Trailing mode
+    pub mode: TrailingMode,
+
+    /// Favorable move from entry in % before the stop starts trailing
+    pub activation_percent: f64,
+
+    /// Minimum stop move in bps before an update is emitted
+    pub min_step_bps: f64,
+}
+
+impl Default for TrailingStopConfig {
+    fn default() -> Self {
+        Self {
+            mode: TrailingMode::Percentage { percent: 1.0 },
+            activation_percent: 0.0,
+            min_step_bps: 1.0,
+        }
+    }
+}
+
+/// A stop that moved
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StopUpdate {
+    /// Position ID
+    pub position_id: String,
+
+    /// Symbol
+    pub symbol: String,
+
+    /// Stop before the update
+    pub previous_stop: Option<f64>,
+
+    /// New stop
+    pub stop: f64,
+}
+
+/// Pushes stop amendments to an exchange
+#[async_trait]
+pub trait StopAmender: Send + Sync {
+    /// Set the exchange-side stop loss for `symbol`
+    async fn amend_stop(&self, symbol: &str, stop: f64) -> Result<()>;
+}
+
+#[async_trait]
+impl StopAmender for BybitAdapter {
+    async fn amend_stop(&self, symbol: &str, stop: f64) -> Result<()> {
+        self.set_trading_stop(symbol, Some(stop), None).await
+    }
+}
+
+/// Trailing state of one position
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct TrailingState {
+    /// Symbol
+    symbol: String,
+
+    /// Whether the position is long
+    is_long: bool,
+
+    /// Entry price
+    entry_price: f64,
+
+    /// Configuration
+    config: TrailingStopConfig,
+
+    /// Current stop
+    stop: Option<f64>,
+
+    /// Best price since entry (highest for longs, lowest for shorts)
+    extreme: f64,
+
+    /// Wilder-smoothed ATR
+    atr: Option<f64>,
+
+    /// True ranges collected while seeding the ATR
+    seed_true_ranges: Vec<f64>,
+
+    /// Previous candle close
+    prev_close: Option<f64>,
+
+    /// Highs (long) or lows (short) of the chandelier lookback
+    window: VecDeque<f64>,
+}
+
+impl TrailingState {
+    fn period(&self) -> usize {
+        match self.config.mode {
+            TrailingMode::Percentage { .. } => 1,
+            TrailingMode::Atr { period, .. } | TrailingMode::Chandelier { period, .. } => period.max(1),
+        }
+    }
+
+    fn on_candle(&mut self, candle: &Candle) {
+        let true_range = match self.prev_close {
+            Some(prev_close) => (candle.high - candle.low)
+                .max((candle.high - prev_close).abs())
+                .max((candle.low - prev_close).abs()),
+            None => candle.high - candle.low,
+        };
+        self.prev_close = Some(candle.close);
+
+        let period = self.period();
+        self.atr = match self.atr {
+            Some(atr) => Some((atr * (period as f64 - 1.0) + true_range) / period as f64),
+            None => {
+                self.seed_true_ranges.push(true_range);
+                (self.seed_true_ranges.len() >= period)
+                    .then(|| self.seed_true_ranges.iter().sum::<f64>() / period as f64)
+            }
+        };
+
+        self.window.push_back(if self.is_long { candle.high } else { candle.low });
+        while self.window.len() > period {
+            self.window.pop_front();
+        }
+        self.on_price(if self.is_long { candle.high } else { candle.low });
+    }
+
+    fn on_price(&mut self, price: f64) {
+        self.extreme = if self.is_long { self.extreme.max(price) } else { self.extreme.min(price) };
+    }
+
+    /// Stop level implied by the current state, if trailing is active
+    fn candidate(&self) -> Option<f64> {
+        let favorable = if self.is_long {
+            (self.extreme - self.entry_price) / self.entry_price * 100.0
+        } else {
+            (self.entry_price - self.extreme) / self.entry_price * 100.0
+        };
+        if favorable < self.config.activation_percent {
+            return None;
+        }
+
+        let sign = if self.is_long { 1.0 } else { -1.0 };
+        match self.config.mode {
+            TrailingMode::Percentage { percent } => Some(self.extreme * (1.0 - sign * percent / 100.0)),
+            TrailingMode::Atr { multiplier, .. } => self.atr.map(|atr| self.extreme - sign * multiplier * atr),
+            TrailingMode::Chandelier { multiplier, .. } => {
+                if self.window.len() < self.period() {
+                    return None;
+                }
+                let anchor = if self.is_long {
+                    self.window.iter().cloned().fold(f64::MIN, f64::max)
+                } else {
+                    self.window.iter().cloned().fold(f64::MAX, f64::min)
+                };
+                self.atr.map(|atr| anchor - sign * multiplier * atr)
+            }
+        }
+    }
+
+    /// Ratchet the stop towards the candidate; returns the previous stop if it moved
+    fn ratchet(&mut self) -> Option<Option<f64>> {
+        let candidate = self.candidate()?;
+        let improves = match self.stop {
+            Some(stop) => {
+                let step_bps = (candidate - stop).abs() / stop.abs().max(f64::EPSILON) * 10_000.0;
+                let better = if self.is_long { candidate > stop } else { candidate < stop };
+                better && step_bps >= self.config.min_step_bps
+            }
+            None => true,
+        };
+        if !improves {
+            return None;
+        }
+
+        let previous = self.stop;
+        self.stop = Some(candidate);
+        Some(previous)
+    }
+
+    fn is_hit(&self, price: f64) -> bool {
+        match self.stop {
+            Some(stop) if self.is_long => price <= stop,
+            Some(stop) => price >= stop,
+            None => false,
+        }
+    }
+}
+
+/// Trails stops for tracked positions
+///
+/// Serializable so trailing progress (best price, ATR, lookback) survives a restart.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TrailingStopManager {
+    /// Trailing state by position ID
+    stops: HashMap<String, TrailingState>,
+}
+
+impl TrailingStopManager {
+    /// Create a new trailing stop manager
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start trailing a position; its current stop loss is kept as the floor
+    pub fn track(&mut self, position: &Position, config: TrailingStopConfig) {
+        let is_long = matches!(position.direction, PositionDirection::Long);
+        let extreme = if is_long {
+            position.entry_price.max(position.current_price)
+        } else {
+            position.entry_price.min(position.current_price)
+        };
+
+        debug!("Trailing stop tracking {} on {} ({:?})", position.id, position.symbol, config.mode);
+        self.stops.insert(position.id.clone(), TrailingState {
+            symbol: position.symbol.clone(),
+            is_long,
+            entry_price: position.entry_price,
+            config,
+            stop: position.stop_loss,
+            extreme,
+            atr: None,
+            seed_true_ranges: Vec::new(),
+            prev_close: None,
+            window: VecDeque::new(),
+        });
+    }
+
+    /// IDs of tracked positions
+    pub fn get_tracked_ids(&self) -> Vec<String> {
+        self.stops.keys().cloned().collect()
+    }
+
+    /// Stop trailing a position
+    pub fn untrack(&mut self, position_id: &str) {
+        self.stops.remove(position_id);
+    }
+
+    /// Current stop of a position
+    pub fn get_stop(&self, position_id: &str) -> Option<f64> {
+        self.stops.get(position_id).and_then(|s| s.stop)
+    }
+
+    /// Number of tracked positions
+    pub fn len(&self) -> usize {
+        self.stops.len()
+    }
+
+    /// Whether no position is tracked
+    pub fn is_empty(&self) -> bool {
+        self.stops.is_empty()
+    }
+
+    /// Feed a trade or ticker price; returns stops that moved
+    pub fn on_price(&mut self, symbol: &str, price: f64) -> Vec<StopUpdate> {
+        self.update(symbol, |state| state.on_price(price))
+    }
+
+    /// Feed a closed candle (updates ATR and the chandelier lookback); returns stops that moved
+    pub fn on_candle(&mut self, symbol: &str, candle: &Candle) -> Vec<StopUpdate> {
+        self.update(symbol, |state| state.on_candle(candle))
+    }
+
+    fn update<F: Fn(&mut TrailingState)>(&mut self, symbol: &str, apply: F) -> Vec<StopUpdate> {
+        let mut updates = Vec::new();
+        for (position_id, state) in self.stops.iter_mut().filter(|(_, s)| s.symbol == symbol) {
+            apply(state);
+            if let (Some(previous_stop), Some(stop)) = (state.ratchet(), state.stop) {
+                updates.push(StopUpdate {
+                    position_id: position_id.clone(),
+                    symbol: symbol.to_string(),
+                    previous_stop,
+                    stop,
+                });
+            }
+        }
+        updates.sort_by(|a, b| a.position_id.cmp(&b.position_id));
+        updates
+    }
+
+    /// Positions on `symbol` whose stop is hit at `price`
+    pub fn triggered(&self, symbol: &str, price: f64) -> Vec<String> {
+        self.stops.iter()
+            .filter(|(_, s)| s.symbol == symbol && s.is_hit(price))
+            .map(|(id, _)| id.clone())
+            .collect()
+    }
+
+    /// Write updated stops into the position manager; untracks positions it no longer has
+    pub fn apply(&mut self, positions: &mut PositionManager, updates: &[StopUpdate]) {
+        for update in updates {
+            if let Err(e) = positions.set_position_stop_loss(&update.position_id, update.stop) {
+                warn!("Dropping trailing stop for {}: {}", update.position_id, e);
+                self.untrack(&update.position_id);
+            }
+        }
+    }
+
+    /// Push the latest stop per symbol to the exchange; returns the number amended
+    pub async fn push_amendments(&self, amender: &dyn StopAmender, updates: &[StopUpdate]) -> usize {
+        let mut latest: HashMap<&str, f64> = HashMap::new();
+        for update in updates {
+            latest.insert(update.symbol.as_str(), update.stop);
+        }
+
+        let mut amended = 0;
+        for (symbol, stop) in latest {
+            match amender.amend_stop(symbol, stop).await {
+                Ok(()) => amended += 1,
+                Err(e) => warn!("Failed to amend exchange stop for {} to {:.6}: {}", symbol, stop, e),
+            }
+        }
+        amended
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn candle(high: f64, low: f64, close: f64) -> Candle {
+        Candle { open_time: 0, open: close, high, low, close, volume: 1.0 }
+    }
+
+    #[test]
+    fn test_stops_ratchet_and_trigger() {
+        let long = Position::new("BTCUSDT".to_string(), PositionDirection::Long, 1.0, 100.0);
+        let short = Position::new("BTCUSDT".to_string(), PositionDirection::Short, 1.0, 100.0);
+        let mut manager = TrailingStopManager::new();
+        manager.track(&long, TrailingStopConfig::default());
+        manager.track(&short, TrailingStopConfig {
+            mode: TrailingMode::Chandelier { period: 2, multiplier: 1.0 },
+            ..TrailingStopConfig::default()
+        });
+
+        manager.on_price("BTCUSDT", 110.0);
+        assert!((manager.get_stop(&long.id).unwrap() - 108.9).abs() < 1e-9);
+
+        // Stops never loosen
+        assert!(manager.on_price("BTCUSDT", 105.0).is_empty());
+        assert!((manager.get_stop(&long.id).unwrap() - 108.9).abs() < 1e-9);
+        assert_eq!(manager.triggered("BTCUSDT", 105.0), vec![long.id.clone()]);
+
+        // Chandelier needs a full lookback and ATR before it trails
+        manager.on_candle("BTCUSDT", &candle(100.0, 98.0, 99.0));
+        assert!(manager.get_stop(&short.id).is_none());
+        manager.on_candle("BTCUSDT", &candle(99.0, 96.0, 97.0));
+        assert!((manager.get_stop(&short.id).unwrap() - 98.5).abs() < 1e-9);
+    }
+}
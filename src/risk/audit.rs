@@ -0,0 +1,112 @@
+//! Risk Audit Trail
+//!
+//! This module keeps a record of risk decisions that deserve a second look:
+//! limit changes, overrides and the trades they let through. Records are kept in
+//! memory for the dashboard and, when a file is configured, appended as JSON
+//! lines so the trail survives restarts.
+
+use std::collections::VecDeque;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tracing::{info, warn};
+
+/// One audited risk decision
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AuditRecord {
+    /// Time of the decision
+    pub timestamp: DateTime<Utc>,
+
+    /// Area the decision belongs to (e.g. "exposure_limit")
+    pub category: String,
+
+    /// What happened (e.g. "override_set")
+    pub action: String,
+
+    /// Decision details
+    pub details: Value,
+}
+
+/// Audit trail of risk decisions
+#[derive(Debug, Clone)]
+pub struct AuditTrail {
+    /// Most recent records, oldest first
+    records: VecDeque<AuditRecord>,
+
+    /// Records kept in memory
+    max_records: usize,
+
+    /// File records are appended to
+    path: Option<PathBuf>,
+}
+
+impl AuditTrail {
+    /// Create an in-memory trail
+    pub fn new(max_records: usize) -> Self {
+        Self {
+            records: VecDeque::new(),
+            max_records,
+            path: None,
+        }
+    }
+
+    /// Also append every record to `path`
+    pub fn with_file(mut self, path: impl AsRef<Path>) -> Self {
+        self.path = Some(path.as_ref().to_path_buf());
+        self
+    }
+
+    /// Record a decision
+    pub fn record(&mut self, category: &str, action: &str, details: Value) {
+        let record = AuditRecord {
+            timestamp: Utc::now(),
+            category: category.to_string(),
+            action: action.to_string(),
+            details,
+        };
+        info!("Audit [{}] {}: {}", record.category, record.action, record.details);
+
+        if let Some(path) = &self.path {
+            if let Err(e) = Self::append(path, &record) {
+                warn!("Failed to write audit record to {}: {}", path.display(), e);
+            }
+        }
+
+        self.records.push_back(record);
+        while self.records.len() > self.max_records {
+            self.records.pop_front();
+        }
+    }
+
+    /// Append a record to the audit file
+    fn append(path: &Path, record: &AuditRecord) -> anyhow::Result<()> {
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            fs::create_dir_all(parent)?;
+        }
+
+        let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+        let mut line = serde_json::to_vec(record)?;
+        line.push(b'\n');
+        file.write_all(&line)?;
+        Ok(())
+    }
+
+    /// Most recent records, oldest first
+    pub fn get_records(&self) -> Vec<&AuditRecord> {
+        self.records.iter().collect()
+    }
+
+    /// Most recent records of one category, oldest first
+    pub fn get_records_by_category(&self, category: &str) -> Vec<&AuditRecord> {
+        self.records.iter().filter(|r| r.category == category).collect()
+    }
+}
+
+impl Default for AuditTrail {
+    fn default() -> Self {
+        Self::new(1000)
+    }
+}
@@ -0,0 +1,174 @@
+//! Daily Loss Kill Switch
+//!
+//! This module limits how much equity, realized plus unrealized, may be lost in
+//! one UTC day. The day's loss is measured from the equity at the first update
+//! of the day. Once the loss reaches the limit the switch trips: the caller
+//! flattens the book and no new trade is taken until the next UTC day, when the
+//! switch re-arms by itself.
+
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use anyhow::Result;
+use tracing::{info, warn};
+
+/// Safety limits of the trading system
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SafetyConfig {
+    /// Whether the kill switch may stop trading
+    pub emergency_stop_enabled: bool,
+
+    /// Largest loss per UTC day in quote currency
+    pub max_daily_loss: Option<f64>,
+
+    /// Largest loss per UTC day as a percentage of the day's starting equity
+    pub max_daily_loss_pct: Option<f64>,
+}
+
+impl Default for SafetyConfig {
+    fn default() -> Self {
+        Self {
+            emergency_stop_enabled: true,
+            max_daily_loss: None,
+            max_daily_loss_pct: Some(5.0),
+        }
+    }
+}
+
+impl SafetyConfig {
+    /// Check the limits
+    pub fn validate(&self) -> Result<()> {
+        if let Some(loss) = self.max_daily_loss.filter(|l| *l <= 0.0) {
+            return Err(anyhow::anyhow!("Max daily loss must be positive, got {}", loss));
+        }
+        if let Some(pct) = self.max_daily_loss_pct.filter(|p| *p <= 0.0 || *p > 100.0) {
+            return Err(anyhow::anyhow!("Max daily loss percentage must be in (0, 100], got {}", pct));
+        }
+        Ok(())
+    }
+}
+
+/// Per-day loss limit
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DailyLossKillSwitch {
+    /// Limits
+    config: SafetyConfig,
+
+    /// UTC day being measured
+    day: Option<NaiveDate>,
+
+    /// Equity at the first update of the day
+    start_equity: f64,
+
+    /// Latest equity
+    equity: f64,
+
+    /// UTC day the switch tripped on
+    tripped_on: Option<NaiveDate>,
+}
+
+impl DailyLossKillSwitch {
+    /// Create a new kill switch
+    pub fn new(config: SafetyConfig) -> Self {
+        Self {
+            config,
+            day: None,
+            start_equity: 0.0,
+            equity: 0.0,
+            tripped_on: None,
+        }
+    }
+
+    /// Replace the limits
+    pub fn set_config(&mut self, config: SafetyConfig) {
+        self.config = config;
+    }
+
+    /// Get the limits
+    pub fn get_config(&self) -> &SafetyConfig {
+        &self.config
+    }
+
+    /// Record the latest equity (realized plus unrealized); returns whether the switch tripped
+    pub fn update(&mut self, now: DateTime<Utc>, equity: f64) -> bool {
+        let today = now.date_naive();
+        if self.day != Some(today) {
+            if self.tripped_on.take().is_some() {
+                info!("Daily loss kill switch re-armed for {}", today);
+            }
+            self.day = Some(today);
+            self.start_equity = equity;
+        }
+        self.equity = equity;
+
+        if !self.config.emergency_stop_enabled || self.tripped_on.is_some() {
+            return false;
+        }
+        let Some(limit) = self.get_limit() else {
+            return false;
+        };
+        if self.get_daily_loss() < limit {
+            return false;
+        }
+
+        warn!("Daily loss {:.4} reached the {:.4} limit: trading disabled until the next UTC day",
+              self.get_daily_loss(), limit);
+        self.tripped_on = Some(today);
+        true
+    }
+
+    /// Whether trading is disabled at `now`
+    pub fn is_tripped(&self, now: DateTime<Utc>) -> bool {
+        self.tripped_on == Some(now.date_naive())
+    }
+
+    /// Loss since the start of the day (positive number)
+    pub fn get_daily_loss(&self) -> f64 {
+        (self.start_equity - self.equity).max(0.0)
+    }
+
+    /// Tightest loss limit for the current day
+    pub fn get_limit(&self) -> Option<f64> {
+        let pct_limit = self.config.max_daily_loss_pct.map(|pct| self.start_equity * pct / 100.0);
+        match (self.config.max_daily_loss, pct_limit) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b) => a.or(b),
+        }
+    }
+}
+
+impl Default for DailyLossKillSwitch {
+    fn default() -> Self {
+        Self::new(SafetyConfig::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_trips_and_rearms_next_day() {
+        let mut switch = DailyLossKillSwitch::new(SafetyConfig {
+            max_daily_loss: Some(8.0),
+            ..SafetyConfig::default()
+        });
+        let morning = Utc.with_ymd_and_hms(2024, 3, 1, 1, 0, 0).unwrap();
+
+        assert!(!switch.update(morning, 100.0));
+        assert_eq!(switch.get_limit(), Some(5.0));
+        assert!(!switch.update(morning + chrono::Duration::hours(2), 96.0));
+        assert!(switch.update(morning + chrono::Duration::hours(3), 94.0));
+        assert!(switch.is_tripped(morning + chrono::Duration::hours(4)));
+
+        // Recovering the same day does not re-enable trading
+        assert!(!switch.update(morning + chrono::Duration::hours(5), 101.0));
+        assert!(switch.is_tripped(morning + chrono::Duration::hours(5)));
+
+        let next_day = morning + chrono::Duration::days(1);
+        assert!(!switch.is_tripped(next_day));
+        assert!(!switch.update(next_day, 94.0));
+        assert_eq!(switch.get_daily_loss(), 0.0);
+    }
+}
@@ -0,0 +1,118 @@
+//! Leverage Governor
+//!
+//! This module caps leverage by the current risk state instead of by signal
+//! confidence alone. The volatility cap targets a per-bar volatility of the
+//! leveraged position: the calmer the market, the more leverage is allowed. The
+//! cap then shrinks linearly with the account drawdown, down to the minimum
+//! leverage at the configured drawdown. Without a volatility estimate only the
+//! minimum leverage is allowed.
+
+use serde::{Deserialize, Serialize};
+
+/// Leverage governor settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LeverageGovernorConfig {
+    /// Target per-bar volatility of the leveraged position (fraction)
+    pub target_volatility: f64,
+
+    /// Leverage never exceeds this
+    pub max_leverage: f64,
+
+    /// Leverage always allowed
+    pub min_leverage: f64,
+
+    /// Drawdown (%) at which leverage is cut to the minimum
+    pub drawdown_floor_pct: f64,
+}
+
+impl Default for LeverageGovernorConfig {
+    fn default() -> Self {
+        Self {
+            target_volatility: 0.01,
+            max_leverage: 25.0,
+            min_leverage: 1.0,
+            drawdown_floor_pct: 15.0,
+        }
+    }
+}
+
+/// Leverage cap with the inputs it came from
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct LeverageDecision {
+    /// Realized per-bar volatility (fraction), if known
+    pub volatility: Option<f64>,
+
+    /// Account drawdown from peak (%)
+    pub drawdown_pct: f64,
+
+    /// Cap from volatility alone
+    pub volatility_cap: f64,
+
+    /// Share of the volatility cap left at this drawdown (0.0-1.0)
+    pub drawdown_factor: f64,
+
+    /// Highest leverage permitted
+    pub max_leverage: f64,
+}
+
+/// Maps realized volatility and drawdown to a leverage cap
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LeverageGovernor {
+    /// Settings
+    config: LeverageGovernorConfig,
+}
+
+impl LeverageGovernor {
+    /// Create a new governor
+    pub fn new(config: LeverageGovernorConfig) -> Self {
+        Self { config }
+    }
+
+    /// Get the settings
+    pub fn get_config(&self) -> &LeverageGovernorConfig {
+        &self.config
+    }
+
+    /// Highest leverage permitted at `volatility` (per bar) and `drawdown_pct`
+    pub fn decide(&self, volatility: Option<f64>, drawdown_pct: f64) -> LeverageDecision {
+        let min = self.config.min_leverage.max(1.0);
+        let max = self.config.max_leverage.max(min);
+
+        let volatility_cap = match volatility.filter(|v| *v > 0.0 && v.is_finite()) {
+            Some(volatility) => (self.config.target_volatility / volatility).clamp(min, max),
+            None => min,
+        };
+        let drawdown_factor = if self.config.drawdown_floor_pct > 0.0 {
+            (1.0 - drawdown_pct.max(0.0) / self.config.drawdown_floor_pct).clamp(0.0, 1.0)
+        } else {
+            1.0
+        };
+
+        LeverageDecision {
+            volatility,
+            drawdown_pct,
+            volatility_cap,
+            drawdown_factor,
+            max_leverage: (min + (volatility_cap - min) * drawdown_factor).max(min),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_leverage_shrinks_with_volatility_and_drawdown() {
+        let governor = LeverageGovernor::default();
+
+        assert_eq!(governor.decide(Some(0.0001), 0.0).max_leverage, 25.0);
+        assert!((governor.decide(Some(0.001), 0.0).max_leverage - 10.0).abs() < 1e-9);
+        assert_eq!(governor.decide(Some(0.05), 0.0).max_leverage, 1.0);
+        assert_eq!(governor.decide(None, 0.0).max_leverage, 1.0);
+
+        // Halfway to the drawdown floor leaves half of the room above the minimum
+        assert!((governor.decide(Some(0.001), 7.5).max_leverage - 5.5).abs() < 1e-9);
+        assert_eq!(governor.decide(Some(0.001), 20.0).max_leverage, 1.0);
+    }
+}
@@ -0,0 +1,284 @@
+//! Exposure Limits
+//!
+//! This module holds the table of per-symbol and per-sector exposure caps that
+//! every new trade is checked against before it is placed. A cap is a maximum
+//! net notional, a maximum share of equity, or both (the tighter one applies).
+//! Operators can temporarily override a cap; setting and clearing an override,
+//! and every accepted trade that only passed because of one, is written to the
+//! audit trail.
+
+use std::collections::BTreeMap;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use anyhow::Result;
+
+use crate::position::portfolio::PortfolioExposure;
+use crate::risk::audit::AuditTrail;
+
+/// Audit category of exposure limit decisions
+pub const EXPOSURE_LIMIT_AUDIT: &str = "exposure_limit";
+
+/// What an exposure limit applies to
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum LimitScope {
+    /// One symbol
+    Symbol(String),
+
+    /// All symbols of a sector
+    Sector(String),
+}
+
+/// Cap on the net notional of a symbol or sector
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExposureLimit {
+    /// Symbol or sector
+    pub scope: LimitScope,
+
+    /// Largest absolute net notional
+    pub max_notional: Option<f64>,
+
+    /// Largest absolute net notional as a percentage of equity
+    pub max_equity_pct: Option<f64>,
+}
+
+impl ExposureLimit {
+    /// Tightest cap at `equity`
+    pub fn cap(&self, equity: f64) -> Option<f64> {
+        let pct_cap = self.max_equity_pct.map(|pct| equity * pct / 100.0);
+        match (self.max_notional, pct_cap) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b) => a.or(b),
+        }
+    }
+}
+
+/// Temporary replacement of an exposure limit
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LimitOverride {
+    /// Limit in force while the override is active
+    pub limit: ExposureLimit,
+
+    /// Why the override was set
+    pub reason: String,
+
+    /// When the override lapses; never when unset
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+impl LimitOverride {
+    /// Whether the override applies at `now`
+    pub fn is_active(&self, now: DateTime<Utc>) -> bool {
+        self.expires_at.map_or(true, |expiry| now < expiry)
+    }
+}
+
+/// Trade that only fits within a cap because of an override
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OverrideUse {
+    /// Symbol traded
+    pub symbol: String,
+
+    /// Scope whose override the trade relies on
+    pub scope: LimitScope,
+
+    /// Exposure of the scope after the trade
+    pub exposure: f64,
+
+    /// Cap without the override
+    pub base_limit: f64,
+
+    /// Why the override was set
+    pub reason: String,
+}
+
+impl OverrideUse {
+    /// Write the use to the audit trail
+    pub fn record(&self, audit: &mut AuditTrail) {
+        audit.record(EXPOSURE_LIMIT_AUDIT, "override_used", json!(self));
+    }
+}
+
+/// Per-symbol and per-sector exposure caps
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ExposureLimitTable {
+    /// Configured limits
+    #[serde(default)]
+    pub limits: Vec<ExposureLimit>,
+
+    /// Active overrides
+    #[serde(default)]
+    pub overrides: Vec<LimitOverride>,
+}
+
+impl ExposureLimitTable {
+    /// Create a table from configured limits
+    pub fn new(limits: Vec<ExposureLimit>) -> Self {
+        Self {
+            limits,
+            overrides: Vec::new(),
+        }
+    }
+
+    /// Add or replace the limit of a scope
+    pub fn set_limit(&mut self, limit: ExposureLimit) {
+        self.limits.retain(|l| l.scope != limit.scope);
+        self.limits.push(limit);
+    }
+
+    /// Configured limit of a scope
+    pub fn get_limit(&self, scope: &LimitScope) -> Option<&ExposureLimit> {
+        self.limits.iter().find(|l| &l.scope == scope)
+    }
+
+    /// Override the limit of a scope, replacing any earlier override
+    pub fn set_override(&mut self, limit_override: LimitOverride, audit: &mut AuditTrail) {
+        self.overrides.retain(|o| o.limit.scope != limit_override.limit.scope);
+        audit.record(EXPOSURE_LIMIT_AUDIT, "override_set", json!({
+            "scope": limit_override.limit.scope,
+            "base": self.get_limit(&limit_override.limit.scope),
+            "override": limit_override,
+        }));
+        self.overrides.push(limit_override);
+    }
+
+    /// Remove the override of a scope
+    pub fn clear_override(&mut self, scope: &LimitScope, audit: &mut AuditTrail) -> Option<LimitOverride> {
+        let index = self.overrides.iter().position(|o| &o.limit.scope == scope)?;
+        let removed = self.overrides.remove(index);
+        audit.record(EXPOSURE_LIMIT_AUDIT, "override_cleared", json!({ "scope": scope, "override": removed }));
+        Some(removed)
+    }
+
+    /// Active override of a scope
+    pub fn get_override(&self, scope: &LimitScope, now: DateTime<Utc>) -> Option<&LimitOverride> {
+        self.overrides.iter().find(|o| &o.limit.scope == scope && o.is_active(now))
+    }
+
+    /// Current exposure of each limited scope as a fraction of its cap, keyed `symbol:X` / `sector:X`
+    pub fn utilization(&self, exposure: &PortfolioExposure, now: DateTime<Utc>) -> BTreeMap<String, f64> {
+        self.limits.iter()
+            .filter_map(|limit| {
+                let cap = match self.get_override(&limit.scope, now) {
+                    Some(o) => o.limit.cap(exposure.equity),
+                    None => limit.cap(exposure.equity),
+                }?;
+                let (key, net) = match &limit.scope {
+                    LimitScope::Symbol(symbol) => (
+                        format!("symbol:{}", symbol),
+                        exposure.get_symbol(symbol).map_or(0.0, |s| s.net_notional),
+                    ),
+                    LimitScope::Sector(sector) => (format!("sector:{}", sector), exposure.get_sector(sector)),
+                };
+                let utilization = if cap > 0.0 { net.abs() / cap } else { 0.0 };
+                Some((key, utilization))
+            })
+            .collect()
+    }
+
+    /// Check that adding `notional` (positive long) on `symbol` keeps it and its sector within their caps.
+    /// Returns the overrides the trade relies on, to be audited once the trade is accepted.
+    pub fn check(
+        &self,
+        symbol: &str,
+        sector: &str,
+        notional: f64,
+        exposure: &PortfolioExposure,
+        now: DateTime<Utc>,
+    ) -> Result<Vec<OverrideUse>> {
+        let symbol_net = exposure.get_symbol(symbol).map_or(0.0, |s| s.net_notional);
+        let scopes = [
+            (LimitScope::Symbol(symbol.to_string()), symbol_net),
+            (LimitScope::Sector(sector.to_string()), exposure.get_sector(sector)),
+        ];
+
+        let mut uses = Vec::new();
+        for (scope, net) in scopes {
+            let before = net.abs();
+            let after = (net + notional).abs();
+            // Trades that shrink the exposure are always allowed
+            if after <= before {
+                continue;
+            }
+
+            let base = self.get_limit(&scope).and_then(|l| l.cap(exposure.equity));
+            let limit_override = self.get_override(&scope, now);
+            let cap = match limit_override {
+                Some(o) => o.limit.cap(exposure.equity),
+                None => base,
+            };
+
+            if cap.is_some_and(|cap| after > cap) {
+                return Err(anyhow::anyhow!(
+                    "{:?} exposure would reach {:.2}, limit {:.2}",
+                    scope, after, cap.unwrap_or_default()
+                ));
+            }
+            if let (Some(base), Some(limit_override)) = (base, limit_override) {
+                if after > base {
+                    uses.push(OverrideUse {
+                        symbol: symbol.to_string(),
+                        scope,
+                        exposure: after,
+                        base_limit: base,
+                        reason: limit_override.reason.clone(),
+                    });
+                }
+            }
+        }
+
+        Ok(uses)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::position::portfolio::{Portfolio, SymbolExposure};
+    use crate::position::position_manager::Position;
+
+    #[test]
+    fn test_limits_and_audited_override() {
+        let mut exposure = Portfolio::new(10.0).exposure_of(std::iter::empty::<&Position>(), 1000.0);
+        exposure.symbols.insert("SOLUSDT".to_string(), SymbolExposure {
+            long_notional: 300.0,
+            net_notional: 300.0,
+            sector: "L1".to_string(),
+            ..SymbolExposure::default()
+        });
+        exposure.sectors.insert("L1".to_string(), 300.0);
+        let mut table = ExposureLimitTable::new(vec![
+            ExposureLimit { scope: LimitScope::Symbol("SOLUSDT".to_string()), max_notional: Some(500.0), max_equity_pct: Some(40.0) },
+            ExposureLimit { scope: LimitScope::Sector("L1".to_string()), max_notional: None, max_equity_pct: Some(60.0) },
+        ]);
+        let mut audit = AuditTrail::default();
+        let now = Utc::now();
+
+        assert!(table.check("SOLUSDT", "L1", 150.0, &exposure, now).is_err());
+        assert!(table.check("SOLUSDT", "L1", 100.0, &exposure, now).is_ok_and(|uses| uses.is_empty()));
+        assert!(table.check("AVAXUSDT", "L1", 350.0, &exposure, now).is_err());
+        assert!(table.check("SOLUSDT", "L1", -500.0, &exposure, now).is_ok());
+
+        table.set_override(LimitOverride {
+            limit: ExposureLimit { scope: LimitScope::Symbol("SOLUSDT".to_string()), max_notional: Some(500.0), max_equity_pct: None },
+            reason: "event trade".to_string(),
+            expires_at: Some(now + chrono::Duration::hours(1)),
+        }, &mut audit);
+        let uses = table.check("SOLUSDT", "L1", 150.0, &exposure, now).unwrap();
+        assert_eq!(uses.len(), 1);
+        assert_eq!(uses[0].base_limit, 400.0);
+        uses[0].record(&mut audit);
+        assert!(table.check("SOLUSDT", "L1", 150.0, &exposure, now + chrono::Duration::hours(2)).is_err());
+
+        // A symbol override does not lift the sector cap
+        table.set_override(LimitOverride {
+            limit: ExposureLimit { scope: LimitScope::Symbol("SOLUSDT".to_string()), max_notional: Some(1000.0), max_equity_pct: None },
+            reason: "event trade".to_string(),
+            expires_at: None,
+        }, &mut audit);
+        assert!(table.check("SOLUSDT", "L1", 350.0, &exposure, now).is_err());
+
+        let actions: Vec<&str> = audit.get_records().iter().map(|r| r.action.as_str()).collect();
+        assert_eq!(actions, vec!["override_set", "override_used", "override_set"]);
+    }
+}
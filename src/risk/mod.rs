@@ -0,0 +1,25 @@
+//! Risk Module for OMNI Trading System
+//!
+//! This module provides portfolio-level risk measures computed from the
+//! candle history the trading system keeps, the daily loss kill switch, the
+//! leverage governor, the exposure limits table, the pre-trade check pipeline,
+//! gap and black-swan protection, stress testing and the audit trail of risk
+//! decisions.
+
+pub mod audit;
+pub mod kill_switch;
+pub mod leverage;
+pub mod limits;
+pub mod pre_trade;
+pub mod protection;
+pub mod stress;
+pub mod var;
+
+pub use audit::*;
+pub use kill_switch::*;
+pub use leverage::*;
+pub use limits::*;
+pub use pre_trade::*;
+pub use protection::*;
+pub use stress::*;
+pub use var::*;
@@ -0,0 +1,511 @@
+//! Pre-Trade Risk Checks
+//!
+//! This module defines the ordered chain of checks every trade signal must pass
+//! before an order is placed: trading state, market conditions, position
+//! capacity, capital, exposure, leverage, correlation, cooldown and the
+//! zero-loss assessment. The pipeline only owns the order of the checks, the
+//! per-symbol cooldowns and the record of what was rejected and why; the trading
+//! system evaluates each check against its own components, and traders running
+//! without one evaluate them against their risk manager. Evaluation stops at the
+//! first failing check.
+
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use anyhow::Result;
+use tracing::info;
+
+use crate::agents::risk_manager::RiskManager;
+use crate::engine::message_bus::TradeDirection;
+use crate::position::portfolio::UNCLASSIFIED_SECTOR;
+
+/// One step of the pre-trade pipeline
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum PreTradeCheck {
+    /// Circuit breaker and daily loss kill switch
+    TradingState,
+
+    /// Scheduled event windows, spread and volatility spikes, severe news
+    MarketConditions,
+
+    /// Free slot under the concurrent position limit; failing signals are queued
+    Capacity,
+
+    /// Strategy and agent budgets, collateral available for the margin
+    Capital,
+
+    /// Open position count and per-symbol/per-sector caps
+    Exposure,
+
+    /// Leverage within the governor's cap
+    Leverage,
+
+    /// Correlation with open positions and cluster caps
+    Correlation,
+
+    /// Minimum time between entries on the same symbol
+    Cooldown,
+
+    /// Confidence, risk-reward and expected value thresholds
+    ZeroLoss,
+}
+
+impl PreTradeCheck {
+    /// All checks in their default order
+    pub fn all() -> Vec<Self> {
+        vec![
+            Self::TradingState,
+            Self::MarketConditions,
+            Self::Capacity,
+            Self::Capital,
+            Self::Exposure,
+            Self::Leverage,
+            Self::Correlation,
+            Self::Cooldown,
+            Self::ZeroLoss,
+        ]
+    }
+}
+
+/// Pre-trade pipeline settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PreTradeConfig {
+    /// Checks to run, in order
+    pub checks: Vec<PreTradeCheck>,
+
+    /// Minimum signal confidence (0.0-1.0)
+    pub min_confidence: f64,
+
+    /// Minimum win probability (0-100%) for the zero-loss assessment
+    pub min_win_probability: f64,
+
+    /// Minimum reward-to-risk ratio for the zero-loss assessment
+    pub min_risk_reward_ratio: f64,
+
+    /// Minimum expected value for the zero-loss assessment (quote currency)
+    pub min_expected_value: f64,
+
+    /// Seconds between entries on the same symbol
+    pub symbol_cooldown_secs: i64,
+
+    /// Sources that run their own per-symbol cooldown (seconds) instead of the shared one
+    pub source_cooldown_secs: HashMap<String, i64>,
+
+    /// Rejections kept in memory
+    pub max_rejections: usize,
+}
+
+impl Default for PreTradeConfig {
+    fn default() -> Self {
+        Self {
+            checks: PreTradeCheck::all(),
+            min_confidence: 0.7,
+            min_win_probability: 70.0,
+            min_risk_reward_ratio: 1.5,
+            min_expected_value: 0.0,
+            symbol_cooldown_secs: 900,
+            source_cooldown_secs: HashMap::new(),
+            max_rejections: 1000,
+        }
+    }
+}
+
+/// Trade a signal would open, as seen by the checks
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TradeProposal {
+    /// Symbol
+    pub symbol: String,
+
+    /// Trade direction
+    pub direction: TradeDirection,
+
+    /// Strategy or agent that produced the signal
+    pub source: String,
+
+    /// Signal confidence (0.0-1.0)
+    pub confidence: f64,
+
+    /// Entry price
+    pub entry_price: f64,
+
+    /// Stop loss price
+    pub stop_loss_price: f64,
+
+    /// Take profit price
+    pub take_profit_price: f64,
+
+    /// Position size in base units
+    pub position_size: f64,
+
+    /// Leverage
+    pub leverage: f64,
+
+    /// Signal time
+    pub timestamp: DateTime<Utc>,
+}
+
+impl TradeProposal {
+    /// Position notional
+    pub fn position_value(&self) -> f64 {
+        self.position_size * self.entry_price
+    }
+
+    /// Reward-to-risk ratio of the take profit against the stop loss
+    pub fn risk_reward_ratio(&self) -> f64 {
+        let risk = (self.entry_price - self.stop_loss_price).abs();
+        if risk <= 0.0 {
+            return 0.0;
+        }
+        (self.take_profit_price - self.entry_price).abs() / risk
+    }
+
+    /// Position notional, negative for shorts
+    pub fn signed_value(&self) -> f64 {
+        match self.direction {
+            TradeDirection::Short => -self.position_value(),
+            _ => self.position_value(),
+        }
+    }
+}
+
+/// Signal rejected by a check
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PreTradeRejection {
+    /// Rejection time
+    pub timestamp: DateTime<Utc>,
+
+    /// Symbol
+    pub symbol: String,
+
+    /// Strategy or agent that produced the signal
+    pub source: String,
+
+    /// Check that failed
+    pub check: PreTradeCheck,
+
+    /// Why the check failed
+    pub reason: String,
+}
+
+/// Outcome of running the checks on one proposal
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PreTradeDecision {
+    /// Checks passed, in order
+    pub passed: Vec<PreTradeCheck>,
+
+    /// First failing check, if any
+    pub rejection: Option<PreTradeRejection>,
+}
+
+impl PreTradeDecision {
+    /// Whether every check passed
+    pub fn is_approved(&self) -> bool {
+        self.rejection.is_none()
+    }
+}
+
+/// Ordered pre-trade checks with a record of rejections
+#[derive(Debug, Clone, Default)]
+pub struct PreTradePipeline {
+    /// Settings
+    config: PreTradeConfig,
+
+    /// Most recent rejections, oldest first
+    rejections: VecDeque<PreTradeRejection>,
+
+    /// Rejections per check since start
+    rejection_counts: BTreeMap<PreTradeCheck, u64>,
+
+    /// Proposals approved since start
+    approved_count: u64,
+
+    /// Last entry per symbol, keyed by source for sources with their own cooldown
+    last_entries: HashMap<(Option<String>, String), DateTime<Utc>>,
+}
+
+impl PreTradePipeline {
+    /// Create a new pipeline
+    pub fn new(config: PreTradeConfig) -> Self {
+        Self {
+            config,
+            ..Self::default()
+        }
+    }
+
+    /// Get the settings
+    pub fn get_config(&self) -> &PreTradeConfig {
+        &self.config
+    }
+
+    /// Checks to run, in order
+    pub fn get_checks(&self) -> &[PreTradeCheck] {
+        &self.config.checks
+    }
+
+    /// Run `evaluate` on each of `checks` in order, stopping at the first failure
+    pub fn evaluate<F>(checks: &[PreTradeCheck], proposal: &TradeProposal, mut evaluate: F) -> PreTradeDecision
+    where
+        F: FnMut(PreTradeCheck, &TradeProposal) -> Result<()>,
+    {
+        let mut passed = Vec::with_capacity(checks.len());
+        for &check in checks {
+            if let Err(e) = evaluate(check, proposal) {
+                return PreTradeDecision {
+                    passed,
+                    rejection: Some(PreTradeRejection {
+                        timestamp: proposal.timestamp,
+                        symbol: proposal.symbol.clone(),
+                        source: proposal.source.clone(),
+                        check,
+                        reason: e.to_string(),
+                    }),
+                };
+            }
+            passed.push(check);
+        }
+
+        PreTradeDecision { passed, rejection: None }
+    }
+
+    /// Record the outcome of a proposal
+    pub fn record(&mut self, decision: &PreTradeDecision) {
+        let Some(rejection) = &decision.rejection else {
+            self.approved_count += 1;
+            return;
+        };
+
+        info!("Pre-trade {:?} check rejected {} signal on {}: {}",
+              rejection.check, rejection.source, rejection.symbol, rejection.reason);
+        *self.rejection_counts.entry(rejection.check).or_insert(0) += 1;
+        self.rejections.push_back(rejection.clone());
+        while self.rejections.len() > self.config.max_rejections {
+            self.rejections.pop_front();
+        }
+    }
+
+    /// Run a proposal through the checks against `risk_manager`, record the outcome and
+    /// start the cooldown of an approved one. For traders placing orders without a
+    /// trading system; `open_symbols` are the symbols with open positions. An approved
+    /// entry is booked with the risk manager until `RiskManager::record_external_exit`.
+    pub fn check_with_risk_manager(
+        &mut self,
+        proposal: &TradeProposal,
+        risk_manager: &mut RiskManager,
+        open_symbols: &[String],
+    ) -> PreTradeDecision {
+        let checks = self.config.checks.clone();
+        let decision = Self::evaluate(&checks, proposal, |check, proposal| {
+            let symbol = proposal.symbol.as_str();
+            match check {
+                PreTradeCheck::TradingState => {
+                    if !risk_manager.is_trading_allowed(proposal.timestamp) {
+                        return Err(anyhow::anyhow!("trading stopped"));
+                    }
+                },
+                PreTradeCheck::MarketConditions => {
+                    risk_manager.check_news_block(symbol, proposal.timestamp)?;
+                    risk_manager.check_whale_activity(symbol, proposal.timestamp)?;
+                    risk_manager.check_market_regime(symbol)?;
+                },
+                PreTradeCheck::Capacity => {
+                    risk_manager.check_position_capacity(open_symbols.len())?;
+                },
+                PreTradeCheck::Capital => {
+                    if proposal.position_value() <= 0.0 {
+                        return Err(anyhow::anyhow!("no position size"));
+                    }
+                    risk_manager.check_strategy_budget(&proposal.source, proposal.position_value(), proposal.timestamp)?;
+                },
+                PreTradeCheck::Exposure => {
+                    if open_symbols.iter().any(|open| open == symbol) {
+                        return Err(anyhow::anyhow!("trade already open on {}", symbol));
+                    }
+                    risk_manager.check_exposure_limits(symbol, UNCLASSIFIED_SECTOR, proposal.signed_value(), proposal.timestamp)?;
+                },
+                PreTradeCheck::Leverage => {
+                    let max_leverage = risk_manager.get_max_leverage(symbol);
+                    if proposal.leverage <= 0.0 || proposal.leverage > max_leverage {
+                        return Err(anyhow::anyhow!(
+                            "leverage {:.2} outside (0, {:.2}]", proposal.leverage, max_leverage
+                        ));
+                    }
+                },
+                PreTradeCheck::Correlation => {
+                    risk_manager.check_cluster_exposure(symbol, proposal.signed_value())?;
+                },
+                PreTradeCheck::Cooldown => {
+                    self.check_cooldown(symbol, &proposal.source, proposal.timestamp)?;
+                },
+                PreTradeCheck::ZeroLoss => {
+                    if proposal.confidence < self.config.min_confidence {
+                        return Err(anyhow::anyhow!(
+                            "confidence {:.2} below {:.2}", proposal.confidence, self.config.min_confidence
+                        ));
+                    }
+                    if proposal.risk_reward_ratio() < self.config.min_risk_reward_ratio {
+                        return Err(anyhow::anyhow!(
+                            "reward-to-risk {:.2} below {:.2}", proposal.risk_reward_ratio(), self.config.min_risk_reward_ratio
+                        ));
+                    }
+                },
+            }
+            Ok(())
+        });
+
+        self.record(&decision);
+        if decision.is_approved() {
+            risk_manager.record_override_uses(&proposal.symbol);
+            risk_manager.record_external_entry(proposal);
+            self.record_entry(&proposal.symbol, &proposal.source, proposal.timestamp);
+        }
+        decision
+    }
+
+    /// Give `source` its own per-symbol cooldown, apart from the shared one
+    pub fn set_source_cooldown(&mut self, source: &str, cooldown_secs: i64) {
+        self.config.source_cooldown_secs.insert(source.to_string(), cooldown_secs);
+    }
+
+    /// Cooldown clock and length for entries from `source` on `symbol`
+    fn cooldown_of(&self, symbol: &str, source: &str) -> ((Option<String>, String), i64) {
+        match self.config.source_cooldown_secs.get(source) {
+            Some(secs) => ((Some(source.to_string()), symbol.to_string()), *secs),
+            None => ((None, symbol.to_string()), self.config.symbol_cooldown_secs),
+        }
+    }
+
+    /// Check that `symbol` is out of `source`'s cooldown at `now`
+    pub fn check_cooldown(&self, symbol: &str, source: &str, now: DateTime<Utc>) -> Result<()> {
+        let (key, cooldown_secs) = self.cooldown_of(symbol, source);
+        let Some(last_entry) = self.last_entries.get(&key) else {
+            return Ok(());
+        };
+        let ready_at = *last_entry + Duration::seconds(cooldown_secs);
+        if now < ready_at {
+            return Err(anyhow::anyhow!(
+                "{} in cooldown for another {}s", symbol, (ready_at - now).num_seconds()
+            ));
+        }
+        Ok(())
+    }
+
+    /// Start `source`'s cooldown of `symbol`
+    pub fn record_entry(&mut self, symbol: &str, source: &str, now: DateTime<Utc>) {
+        let (key, _) = self.cooldown_of(symbol, source);
+        self.last_entries.insert(key, now);
+    }
+
+    /// Most recent rejections, oldest first
+    pub fn get_rejections(&self) -> Vec<&PreTradeRejection> {
+        self.rejections.iter().collect()
+    }
+
+    /// Rejections per check since start
+    pub fn get_rejection_counts(&self) -> &BTreeMap<PreTradeCheck, u64> {
+        &self.rejection_counts
+    }
+
+    /// Proposals approved since start
+    pub fn get_approved_count(&self) -> u64 {
+        self.approved_count
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agents::risk_manager::StrategyRiskBudget;
+
+    #[test]
+    fn test_stops_at_first_failure_and_records_it() {
+        let mut pipeline = PreTradePipeline::new(PreTradeConfig::default());
+        let now = Utc::now();
+        let proposal = TradeProposal {
+            symbol: "BTCUSDT".to_string(),
+            direction: TradeDirection::Short,
+            source: "trend".to_string(),
+            confidence: 0.8,
+            entry_price: 100.0,
+            stop_loss_price: 102.0,
+            take_profit_price: 95.0,
+            position_size: 2.0,
+            leverage: 5.0,
+            timestamp: now,
+        };
+        assert_eq!(proposal.signed_value(), -200.0);
+
+        let mut evaluated = Vec::new();
+        let decision = PreTradePipeline::evaluate(pipeline.get_checks(), &proposal, |check, _| {
+            evaluated.push(check);
+            match check {
+                PreTradeCheck::Leverage => Err(anyhow::anyhow!("leverage 5.00 above cap 3.00")),
+                _ => Ok(()),
+            }
+        });
+        assert_eq!(evaluated.last(), Some(&PreTradeCheck::Leverage));
+        assert_eq!(decision.passed.len(), 5);
+        pipeline.record(&decision);
+
+        let rejections = pipeline.get_rejections();
+        assert_eq!(rejections.len(), 1);
+        assert_eq!(rejections[0].check, PreTradeCheck::Leverage);
+        assert_eq!(rejections[0].reason, "leverage 5.00 above cap 3.00");
+        assert_eq!(pipeline.get_rejection_counts().get(&PreTradeCheck::Leverage), Some(&1));
+
+        pipeline.record_entry("BTCUSDT", "trend", now);
+        assert!(pipeline.check_cooldown("BTCUSDT", "trend", now + Duration::seconds(60)).is_err());
+        assert!(pipeline.check_cooldown("BTCUSDT", "trend", now + Duration::seconds(900)).is_ok());
+        assert!(pipeline.check_cooldown("ETHUSDT", "trend", now).is_ok());
+
+        // A source with its own cooldown runs on its own clock
+        pipeline.set_source_cooldown("scalper", 10);
+        assert!(pipeline.check_cooldown("BTCUSDT", "scalper", now).is_ok());
+        pipeline.record_entry("BTCUSDT", "scalper", now);
+        assert!(pipeline.check_cooldown("BTCUSDT", "scalper", now + Duration::seconds(5)).is_err());
+        assert!(pipeline.check_cooldown("BTCUSDT", "scalper", now + Duration::seconds(10)).is_ok());
+    }
+
+    #[test]
+    fn test_checks_against_a_risk_manager() {
+        let mut pipeline = PreTradePipeline::new(PreTradeConfig::default());
+        let mut risk_manager = RiskManager::new(1_000.0);
+        let proposal = TradeProposal {
+            symbol: "BTCUSDT".to_string(),
+            direction: TradeDirection::Long,
+            source: "trend".to_string(),
+            confidence: 0.8,
+            entry_price: 100.0,
+            stop_loss_price: 98.0,
+            take_profit_price: 104.0,
+            position_size: 0.5,
+            leverage: 1.0,
+            timestamp: Utc::now(),
+        };
+        assert_eq!(proposal.risk_reward_ratio(), 2.0);
+
+        // An open position on the symbol is rejected by the exposure check
+        let decision = pipeline.check_with_risk_manager(&proposal, &mut risk_manager, &["BTCUSDT".to_string()]);
+        assert_eq!(decision.rejection.map(|r| r.check), Some(PreTradeCheck::Exposure));
+
+        // An approved entry starts the symbol's cooldown
+        assert!(pipeline.check_with_risk_manager(&proposal, &mut risk_manager, &[]).is_approved());
+        let decision = pipeline.check_with_risk_manager(&proposal, &mut risk_manager, &[]);
+        assert_eq!(decision.rejection.map(|r| r.check), Some(PreTradeCheck::Cooldown));
+        assert_eq!(pipeline.get_approved_count(), 1);
+
+        // The approved entry counts against the strategy budget until it is closed
+        risk_manager.set_strategy_budget("trend", StrategyRiskBudget {
+            max_capital: 1_000.0,
+            max_daily_loss: 100.0,
+            max_concurrent_positions: 1,
+        });
+        let eth = TradeProposal { symbol: "ETHUSDT".to_string(), ..proposal.clone() };
+        let decision = pipeline.check_with_risk_manager(&eth, &mut risk_manager, &["BTCUSDT".to_string()]);
+        assert_eq!(decision.rejection.map(|r| r.check), Some(PreTradeCheck::Capital));
+        assert!(risk_manager.get_portfolio_exposure().unwrap().get_symbol("BTCUSDT").is_some());
+
+        risk_manager.record_external_exit("BTCUSDT", 1.0, Utc::now());
+        assert!(pipeline.check_with_risk_manager(&eth, &mut risk_manager, &[]).is_approved());
+    }
+}
@@ -0,0 +1,297 @@
+//! Gap and Black-Swan Protection
+//!
+//! This module holds the rules that protect the book around moments when prices
+//! can jump past stops. Ahead of a scheduled high-impact event (CPI, FOMC, ...)
+//! leveraged positions are closed and new entries are blocked until the event
+//! has passed. When a symbol's spread or volatility explodes, new entries on it
+//! are vetoed and the stops of open positions are widened once, so they are not
+//! taken out by the noise; the stops are restored once the spike has passed.
+
+use std::collections::HashMap;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use anyhow::Result;
+
+/// Expected market impact of a scheduled event
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum EventImpact {
+    /// Rarely moves crypto
+    Low,
+
+    /// May move crypto
+    Medium,
+
+    /// Routinely moves crypto (CPI, FOMC, NFP)
+    High,
+}
+
+/// Scheduled macro event
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ScheduledEvent {
+    /// Event name
+    pub name: String,
+
+    /// Release time
+    pub scheduled_at: DateTime<Utc>,
+
+    /// Expected impact
+    pub impact: EventImpact,
+}
+
+/// What to do with open positions ahead of an event
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum EventAction {
+    /// Close positions above the event leverage
+    CloseLeveraged,
+
+    /// Close every position
+    CloseAll,
+}
+
+/// Protection settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ProtectionConfig {
+    /// Lowest impact that triggers the event window
+    pub min_impact: EventImpact,
+
+    /// Minutes before an event the window opens
+    pub pre_event_minutes: i64,
+
+    /// Minutes after an event the window closes
+    pub post_event_minutes: i64,
+
+    /// What happens to open positions when the window opens
+    pub event_action: EventAction,
+
+    /// Highest leverage kept through an event with `CloseLeveraged`
+    pub event_max_leverage: f64,
+
+    /// Spread (basis points) above which entries are vetoed
+    pub max_spread_bps: f64,
+
+    /// Volatility as a multiple of its baseline above which entries are vetoed
+    pub volatility_spike_multiple: f64,
+
+    /// Factor applied to the stop distance of open positions during a spike
+    pub stop_widen_factor: f64,
+
+    /// Weight of the latest volatility reading in the baseline
+    pub baseline_alpha: f64,
+}
+
+impl Default for ProtectionConfig {
+    fn default() -> Self {
+        Self {
+            min_impact: EventImpact::High,
+            pre_event_minutes: 30,
+            post_event_minutes: 15,
+            event_action: EventAction::CloseLeveraged,
+            event_max_leverage: 1.0,
+            max_spread_bps: 25.0,
+            volatility_spike_multiple: 3.0,
+            stop_widen_factor: 1.5,
+            baseline_alpha: 0.05,
+        }
+    }
+}
+
+/// Latest market readings of one symbol
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MarketStress {
+    /// Latest spread in basis points
+    pub spread_bps: Option<f64>,
+
+    /// Latest realized volatility
+    pub volatility: Option<f64>,
+
+    /// Slow-moving volatility baseline
+    pub baseline_volatility: Option<f64>,
+
+    /// Close time of the candle the baseline last moved on
+    #[serde(default)]
+    pub baseline_candle: Option<DateTime<Utc>>,
+}
+
+impl MarketStress {
+    /// Latest volatility as a multiple of the baseline
+    pub fn volatility_ratio(&self) -> Option<f64> {
+        match (self.volatility, self.baseline_volatility) {
+            (Some(volatility), Some(baseline)) if baseline > 0.0 => Some(volatility / baseline),
+            _ => None,
+        }
+    }
+}
+
+/// Gap and black-swan protection rules
+#[derive(Debug, Clone, Default)]
+pub struct GapProtection {
+    /// Settings
+    config: ProtectionConfig,
+
+    /// Upcoming scheduled events
+    events: Vec<ScheduledEvent>,
+
+    /// Latest readings per symbol
+    stress: HashMap<String, MarketStress>,
+}
+
+impl GapProtection {
+    /// Create new protection rules
+    pub fn new(config: ProtectionConfig) -> Self {
+        Self {
+            config,
+            ..Self::default()
+        }
+    }
+
+    /// Get the settings
+    pub fn get_config(&self) -> &ProtectionConfig {
+        &self.config
+    }
+
+    /// Replace the event schedule
+    pub fn set_events(&mut self, events: Vec<ScheduledEvent>) {
+        self.events = events;
+    }
+
+    /// Get the event schedule
+    pub fn get_events(&self) -> &[ScheduledEvent] {
+        &self.events
+    }
+
+    /// Event whose window covers `now`, if any
+    pub fn active_event(&self, now: DateTime<Utc>) -> Option<&ScheduledEvent> {
+        let before = Duration::minutes(self.config.pre_event_minutes);
+        let after = Duration::minutes(self.config.post_event_minutes);
+        self.events.iter()
+            .filter(|event| event.impact >= self.config.min_impact)
+            .find(|event| now >= event.scheduled_at - before && now <= event.scheduled_at + after)
+    }
+
+    /// Whether a position at `leverage` must be closed at `now`
+    pub fn should_close(&self, leverage: f64, now: DateTime<Utc>) -> bool {
+        if self.active_event(now).is_none() {
+            return false;
+        }
+        match self.config.event_action {
+            EventAction::CloseAll => true,
+            EventAction::CloseLeveraged => leverage > self.config.event_max_leverage,
+        }
+    }
+
+    /// Record the latest spread of a symbol
+    pub fn update_spread(&mut self, symbol: &str, bid: f64, ask: f64) {
+        let mid = (bid + ask) / 2.0;
+        if bid <= 0.0 || ask < bid || mid <= 0.0 {
+            return;
+        }
+        self.stress.entry(symbol.to_string()).or_default().spread_bps = Some((ask - bid) / mid * 10_000.0);
+    }
+
+    /// Record the realized volatility of a symbol as of the candle closed at `candle_time`
+    ///
+    /// The baseline moves once per closed candle, however often the reading is repeated.
+    pub fn update_volatility(&mut self, symbol: &str, volatility: f64, candle_time: DateTime<Utc>) {
+        if !volatility.is_finite() || volatility <= 0.0 {
+            return;
+        }
+        let alpha = self.config.baseline_alpha;
+        let stress = self.stress.entry(symbol.to_string()).or_default();
+        stress.volatility = Some(volatility);
+        if stress.baseline_candle.is_some_and(|last| candle_time <= last) {
+            return;
+        }
+        stress.baseline_candle = Some(candle_time);
+        stress.baseline_volatility = Some(match stress.baseline_volatility {
+            Some(baseline) => baseline * (1.0 - alpha) + volatility * alpha,
+            None => volatility,
+        });
+    }
+
+    /// Latest readings of a symbol
+    pub fn get_stress(&self, symbol: &str) -> Option<&MarketStress> {
+        self.stress.get(symbol)
+    }
+
+    /// Whether the symbol's volatility is spiking
+    pub fn is_volatility_spike(&self, symbol: &str) -> bool {
+        self.stress.get(symbol)
+            .and_then(MarketStress::volatility_ratio)
+            .is_some_and(|ratio| ratio >= self.config.volatility_spike_multiple)
+    }
+
+    /// Check that a new entry on `symbol` is allowed at `now`
+    pub fn check_entry(&self, symbol: &str, now: DateTime<Utc>) -> Result<()> {
+        if let Some(event) = self.active_event(now) {
+            return Err(anyhow::anyhow!("{} scheduled at {}", event.name, event.scheduled_at));
+        }
+
+        let Some(stress) = self.stress.get(symbol) else {
+            return Ok(());
+        };
+        if let Some(spread) = stress.spread_bps.filter(|s| *s > self.config.max_spread_bps) {
+            return Err(anyhow::anyhow!(
+                "{} spread {:.1}bps above {:.1}bps", symbol, spread, self.config.max_spread_bps
+            ));
+        }
+        if self.is_volatility_spike(symbol) {
+            return Err(anyhow::anyhow!(
+                "{} volatility at {:.1}x its baseline", symbol, stress.volatility_ratio().unwrap_or_default()
+            ));
+        }
+        Ok(())
+    }
+
+    /// Stop moved away from the entry by the widen factor
+    pub fn widened_stop(&self, entry_price: f64, stop_loss_price: f64) -> f64 {
+        entry_price + (stop_loss_price - entry_price) * self.config.stop_widen_factor.max(1.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_event_window_and_market_vetoes() {
+        let mut protection = GapProtection::new(ProtectionConfig::default());
+        let fomc = Utc::now() + Duration::hours(2);
+        protection.set_events(vec![
+            ScheduledEvent { name: "FOMC".to_string(), scheduled_at: fomc, impact: EventImpact::High },
+            ScheduledEvent { name: "Jobless claims".to_string(), scheduled_at: fomc - Duration::hours(1), impact: EventImpact::Medium },
+        ]);
+
+        let before = fomc - Duration::minutes(45);
+        assert!(protection.check_entry("BTCUSDT", before).is_ok());
+        assert!(!protection.should_close(3.0, before));
+
+        let during = fomc - Duration::minutes(10);
+        assert!(protection.check_entry("BTCUSDT", during).is_err());
+        assert!(protection.should_close(3.0, during));
+        assert!(!protection.should_close(1.0, during));
+        assert!(protection.check_entry("BTCUSDT", fomc + Duration::minutes(20)).is_ok());
+
+        protection.update_spread("ETHUSDT", 99.9, 100.1);
+        assert!(protection.check_entry("ETHUSDT", before).is_ok());
+        protection.update_spread("ETHUSDT", 99.0, 101.0);
+        assert!(protection.check_entry("ETHUSDT", before).is_err());
+
+        let candle = Utc::now();
+        protection.update_volatility("SOLUSDT", 0.01, candle);
+        protection.update_volatility("SOLUSDT", 0.05, candle + Duration::minutes(1));
+        assert!(protection.is_volatility_spike("SOLUSDT"));
+        assert!(protection.check_entry("SOLUSDT", before).is_err());
+
+        // Repeating the reading within a candle does not drag the baseline up
+        let baseline = protection.get_stress("SOLUSDT").unwrap().baseline_volatility;
+        for _ in 0..100 {
+            protection.update_volatility("SOLUSDT", 0.05, candle + Duration::minutes(1));
+        }
+        assert_eq!(protection.get_stress("SOLUSDT").unwrap().baseline_volatility, baseline);
+        assert!(protection.is_volatility_spike("SOLUSDT"));
+
+        assert!((protection.widened_stop(100.0, 98.0) - 97.0).abs() < 1e-9);
+        assert!((protection.widened_stop(100.0, 102.0) - 103.0).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,260 @@
+//! Stress Testing
+//!
+//! This module replays hypothetical shocks against the current portfolio and
+//! reports the equity left after each one. Price shocks move one symbol and drag
+//! the rest of the book along by a contagion factor; funding spikes charge the
+//! net position a given rate for a number of funding intervals; an exchange
+//! outage leaves the book unmanaged for N minutes, during which every position
+//! is assumed to move against it by a multiple of its realized volatility. If the
+//! worst case loses more than the configured share of equity the result is a
+//! no-go, which the zero-loss enforcer uses to refuse new trades.
+
+use std::collections::HashMap;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::position::portfolio::PortfolioExposure;
+
+/// Hypothetical shock
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum StressScenario {
+    /// Price move of one symbol, spread to the others by `contagion`
+    PriceShock {
+        /// Scenario name
+        name: String,
+
+        /// Symbol that moves
+        symbol: String,
+
+        /// Move in % (negative for a drop)
+        move_pct: f64,
+
+        /// Share of the move the other symbols follow (0.0-1.0)
+        contagion: f64,
+    },
+
+    /// Funding rate charged to the net position of every symbol
+    FundingSpike {
+        /// Scenario name
+        name: String,
+
+        /// Rate per funding interval (fraction, positive when longs pay)
+        rate: f64,
+
+        /// Funding intervals the spike lasts
+        intervals: u32,
+    },
+
+    /// Exchange unreachable for a number of minutes
+    ExchangeOutage {
+        /// Scenario name
+        name: String,
+
+        /// Outage length in minutes
+        minutes: u32,
+    },
+}
+
+impl StressScenario {
+    /// Scenario name
+    pub fn name(&self) -> &str {
+        match self {
+            StressScenario::PriceShock { name, .. }
+            | StressScenario::FundingSpike { name, .. }
+            | StressScenario::ExchangeOutage { name, .. } => name,
+        }
+    }
+}
+
+/// Stress test settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StressConfig {
+    /// Scenarios to replay
+    pub scenarios: Vec<StressScenario>,
+
+    /// Largest worst-case loss (% of equity) that is still a go
+    pub max_loss_pct: f64,
+
+    /// Adverse move during an outage, in standard deviations
+    pub outage_sigma: f64,
+
+    /// Volatility assumed for symbols without an estimate (fraction per minute)
+    pub default_volatility: f64,
+}
+
+impl Default for StressConfig {
+    fn default() -> Self {
+        Self {
+            scenarios: vec![
+                StressScenario::PriceShock {
+                    name: "BTC -20% in 1h".to_string(),
+                    symbol: "BTCUSDT".to_string(),
+                    move_pct: -20.0,
+                    contagion: 0.8,
+                },
+                StressScenario::FundingSpike {
+                    name: "Funding spike 0.3% x3".to_string(),
+                    rate: 0.003,
+                    intervals: 3,
+                },
+                StressScenario::ExchangeOutage {
+                    name: "Exchange outage 30m".to_string(),
+                    minutes: 30,
+                },
+            ],
+            max_loss_pct: 25.0,
+            outage_sigma: 3.0,
+            default_volatility: 0.005,
+        }
+    }
+}
+
+/// Outcome of one scenario
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScenarioResult {
+    /// Scenario name
+    pub scenario: String,
+
+    /// P&L of the scenario
+    pub pnl: f64,
+
+    /// Equity after the scenario
+    pub equity_after: f64,
+
+    /// Loss as a percentage of equity
+    pub loss_pct: f64,
+}
+
+/// Outcome of a stress test
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StressTestResult {
+    /// Test time
+    pub timestamp: DateTime<Utc>,
+
+    /// Equity before the shocks
+    pub equity: f64,
+
+    /// Result per scenario
+    pub scenarios: Vec<ScenarioResult>,
+
+    /// Lowest equity across the scenarios
+    pub worst_case_equity: f64,
+
+    /// Scenario producing the lowest equity
+    pub worst_scenario: Option<String>,
+
+    /// Whether the worst case stays within the loss limit
+    pub go: bool,
+}
+
+/// Replays stress scenarios against a portfolio
+#[derive(Debug, Clone, Default)]
+pub struct StressTester {
+    /// Settings
+    config: StressConfig,
+}
+
+impl StressTester {
+    /// Create a new stress tester
+    pub fn new(config: StressConfig) -> Self {
+        Self { config }
+    }
+
+    /// Get the settings
+    pub fn get_config(&self) -> &StressConfig {
+        &self.config
+    }
+
+    /// P&L of one scenario; `volatility` is per minute by symbol
+    pub fn scenario_pnl(&self, scenario: &StressScenario, exposure: &PortfolioExposure, volatility: &HashMap<String, f64>) -> f64 {
+        match scenario {
+            StressScenario::PriceShock { symbol, move_pct, contagion, .. } => {
+                exposure.symbols.iter()
+                    .map(|(s, e)| {
+                        let factor = if s == symbol { 1.0 } else { *contagion };
+                        e.net_notional * move_pct / 100.0 * factor
+                    })
+                    .sum()
+            },
+            StressScenario::FundingSpike { rate, intervals, .. } => {
+                -exposure.symbols.values()
+                    .map(|e| e.net_notional * rate * *intervals as f64)
+                    .sum::<f64>()
+            },
+            StressScenario::ExchangeOutage { minutes, .. } => {
+                -exposure.symbols.iter()
+                    .map(|(s, e)| {
+                        let vol = volatility.get(s).copied().unwrap_or(self.config.default_volatility);
+                        e.net_notional.abs() * vol * (*minutes as f64).sqrt() * self.config.outage_sigma
+                    })
+                    .sum::<f64>()
+            },
+        }
+    }
+
+    /// Replay every scenario against `exposure`
+    pub fn run(&self, exposure: &PortfolioExposure, volatility: &HashMap<String, f64>) -> StressTestResult {
+        let equity = exposure.equity;
+        let scenarios: Vec<ScenarioResult> = self.config.scenarios.iter()
+            .map(|scenario| {
+                let pnl = self.scenario_pnl(scenario, exposure, volatility);
+                ScenarioResult {
+                    scenario: scenario.name().to_string(),
+                    pnl,
+                    equity_after: equity + pnl,
+                    loss_pct: if equity > 0.0 { (-pnl).max(0.0) / equity * 100.0 } else { 0.0 },
+                }
+            })
+            .collect();
+
+        let worst = scenarios.iter().min_by(|a, b| a.equity_after.total_cmp(&b.equity_after));
+        let worst_case_equity = worst.map_or(equity, |w| w.equity_after);
+        let worst_loss_pct = worst.map_or(0.0, |w| w.loss_pct);
+
+        StressTestResult {
+            timestamp: Utc::now(),
+            equity,
+            worst_scenario: worst.map(|w| w.scenario.clone()),
+            worst_case_equity,
+            go: worst_case_equity > 0.0 && worst_loss_pct <= self.config.max_loss_pct,
+            scenarios,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::position::portfolio::{Portfolio, SymbolExposure};
+    use crate::position::position_manager::Position;
+
+    #[test]
+    fn test_worst_case_and_go_flag() {
+        let mut exposure = Portfolio::new(10.0).exposure_of(std::iter::empty::<&Position>(), 1000.0);
+        exposure.symbols.insert("BTCUSDT".to_string(), SymbolExposure {
+            long_notional: 1000.0,
+            net_notional: 1000.0,
+            ..SymbolExposure::default()
+        });
+        exposure.symbols.insert("ETHUSDT".to_string(), SymbolExposure {
+            short_notional: 500.0,
+            net_notional: -500.0,
+            ..SymbolExposure::default()
+        });
+        let tester = StressTester::default();
+        let volatility = HashMap::from([("BTCUSDT".to_string(), 0.002)]);
+
+        let result = tester.run(&exposure, &volatility);
+        assert_eq!(result.scenarios.len(), 3);
+        // -20% on 1000 long, +16% on 500 short: -200 + 80
+        assert!((result.scenarios[0].pnl + 120.0).abs() < 1e-9);
+        assert!((result.scenarios[1].pnl + 4.5).abs() < 1e-9);
+        assert_eq!(result.worst_scenario.as_deref(), Some("BTC -20% in 1h"));
+        assert!((result.worst_case_equity - 880.0).abs() < 1e-9);
+        assert!(result.go);
+
+        let strict = StressTester::new(StressConfig { max_loss_pct: 10.0, ..StressConfig::default() });
+        assert!(!strict.run(&exposure, &volatility).go);
+    }
+}
@@ -0,0 +1,309 @@
+//! Value-at-Risk
+//!
+//! This module estimates the Value-at-Risk and Conditional VaR (expected
+//! shortfall) of the open book from the stored candle history. Every position is
+//! revalued over the close-to-close returns its symbol saw in the past, on the
+//! timestamps all symbols have in common, which gives a P&L series for the
+//! current book. The historical estimate reads the loss quantile straight from
+//! that series; the parametric estimate fits a normal distribution to it. Both
+//! are scaled from one bar to the horizon with the square root of time and
+//! reported as positive losses in quote currency.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::strategy::simple_strategy::Candle;
+
+/// VaR settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VarConfig {
+    /// Confidence level (e.g. 0.95)
+    pub confidence: f64,
+
+    /// Horizon in bars of the candle timeframe
+    pub horizon_bars: usize,
+
+    /// Most recent returns used
+    pub lookback: usize,
+
+    /// Fewest returns an estimate is made from
+    pub min_observations: usize,
+}
+
+impl Default for VarConfig {
+    fn default() -> Self {
+        Self {
+            confidence: 0.95,
+            horizon_bars: 1,
+            lookback: 500,
+            min_observations: 30,
+        }
+    }
+}
+
+/// VaR and CVaR from one method
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct VarEstimate {
+    /// Loss not exceeded at the confidence level
+    pub var: f64,
+
+    /// Average loss beyond the VaR
+    pub cvar: f64,
+}
+
+/// VaR of the open book
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PortfolioVar {
+    /// Estimate time
+    pub timestamp: DateTime<Utc>,
+
+    /// Confidence level
+    pub confidence: f64,
+
+    /// Horizon in bars
+    pub horizon_bars: usize,
+
+    /// Returns the estimate is based on
+    pub observations: usize,
+
+    /// Long plus short notional of the book
+    pub gross_exposure: f64,
+
+    /// Estimate from the empirical P&L distribution
+    pub historical: VarEstimate,
+
+    /// Estimate from a normal fit of the P&L distribution
+    pub parametric: VarEstimate,
+
+    /// Historical VaR of each position on its own
+    pub symbol_var: BTreeMap<String, f64>,
+}
+
+impl PortfolioVar {
+    /// VaR saved by holding the positions together rather than apart
+    pub fn diversification_benefit(&self) -> f64 {
+        self.symbol_var.values().sum::<f64>() - self.historical.var
+    }
+}
+
+/// Historical VaR and CVaR of a P&L series
+pub fn historical_var(pnl: &[f64], confidence: f64) -> VarEstimate {
+    if pnl.is_empty() {
+        return VarEstimate { var: 0.0, cvar: 0.0 };
+    }
+
+    let mut losses: Vec<f64> = pnl.iter().map(|p| -p).collect();
+    losses.sort_by(|a, b| b.partial_cmp(a).unwrap_or(std::cmp::Ordering::Equal));
+
+    // Losses in the tail beyond the confidence level, worst first
+    let tail = (((1.0 - confidence) * losses.len() as f64).ceil() as usize).clamp(1, losses.len());
+    let var = losses[tail - 1];
+    let cvar = losses[..tail].iter().sum::<f64>() / tail as f64;
+
+    VarEstimate {
+        var: var.max(0.0),
+        cvar: cvar.max(0.0),
+    }
+}
+
+/// Parametric (normal) VaR and CVaR of a P&L series
+pub fn parametric_var(pnl: &[f64], confidence: f64) -> VarEstimate {
+    if pnl.len() < 2 {
+        return VarEstimate { var: 0.0, cvar: 0.0 };
+    }
+
+    let n = pnl.len() as f64;
+    let mean = pnl.iter().sum::<f64>() / n;
+    let std_dev = (pnl.iter().map(|p| (p - mean).powi(2)).sum::<f64>() / (n - 1.0)).sqrt();
+    let z = normal_quantile(confidence);
+
+    VarEstimate {
+        var: (z * std_dev - mean).max(0.0),
+        cvar: (std_dev * normal_pdf(z) / (1.0 - confidence) - mean).max(0.0),
+    }
+}
+
+/// P&L per bar of a book of signed notionals (positive long) over the candle history
+pub fn portfolio_pnl_series(notionals: &BTreeMap<String, f64>, candles: &HashMap<String, Vec<Candle>>) -> Vec<f64> {
+    let closes: Vec<(f64, BTreeMap<i64, f64>)> = notionals.iter()
+        .filter(|(_, notional)| **notional != 0.0)
+        .filter_map(|(symbol, notional)| {
+            let series = candles.get(symbol)?;
+            Some((*notional, series.iter()
+                .filter(|c| c.close > 0.0 && c.close.is_finite())
+                .map(|c| (c.open_time, c.close))
+                .collect()))
+        })
+        .collect();
+    if closes.is_empty() {
+        return Vec::new();
+    }
+
+    // Only bars every symbol has, so each P&L reflects the same moment
+    let mut times: BTreeSet<i64> = closes[0].1.keys().copied().collect();
+    for (_, series) in &closes[1..] {
+        times.retain(|t| series.contains_key(t));
+    }
+    let times: Vec<i64> = times.into_iter().collect();
+
+    times.windows(2)
+        .map(|w| {
+            closes.iter()
+                .map(|(notional, series)| notional * (series[&w[1]] / series[&w[0]] - 1.0))
+                .sum()
+        })
+        .collect()
+}
+
+/// VaR of a book of signed notionals (positive long) from the candle history
+pub fn calculate_var(
+    notionals: &BTreeMap<String, f64>,
+    candles: &HashMap<String, Vec<Candle>>,
+    config: &VarConfig,
+) -> Result<PortfolioVar> {
+    if !(0.5..1.0).contains(&config.confidence) {
+        return Err(anyhow::anyhow!("VaR confidence must be in [0.5, 1), got {}", config.confidence));
+    }
+    if config.horizon_bars == 0 {
+        return Err(anyhow::anyhow!("VaR horizon must be at least one bar"));
+    }
+
+    // A flat book risks nothing, whatever the history
+    if notionals.values().all(|n| *n == 0.0) {
+        return Ok(PortfolioVar {
+            timestamp: Utc::now(),
+            confidence: config.confidence,
+            horizon_bars: config.horizon_bars,
+            observations: 0,
+            gross_exposure: 0.0,
+            historical: VarEstimate { var: 0.0, cvar: 0.0 },
+            parametric: VarEstimate { var: 0.0, cvar: 0.0 },
+            symbol_var: BTreeMap::new(),
+        });
+    }
+
+    if let Some(symbol) = notionals.iter().find(|(s, n)| **n != 0.0 && !candles.contains_key(*s)).map(|(s, _)| s) {
+        return Err(anyhow::anyhow!("No candle history for {}", symbol));
+    }
+
+    let recent = |mut pnl: Vec<f64>| {
+        if pnl.len() > config.lookback {
+            pnl.drain(..pnl.len() - config.lookback);
+        }
+        pnl
+    };
+
+    let pnl = recent(portfolio_pnl_series(notionals, candles));
+    if pnl.len() < config.min_observations {
+        return Err(anyhow::anyhow!(
+            "Not enough candle history for VaR: {} returns, {} required",
+            pnl.len(), config.min_observations
+        ));
+    }
+
+    let scale = (config.horizon_bars as f64).sqrt();
+    let scaled = |estimate: VarEstimate| VarEstimate {
+        var: estimate.var * scale,
+        cvar: estimate.cvar * scale,
+    };
+
+    let symbol_var = notionals.iter()
+        .filter(|(_, notional)| **notional != 0.0)
+        .map(|(symbol, notional)| {
+            let single = BTreeMap::from([(symbol.clone(), *notional)]);
+            let pnl = recent(portfolio_pnl_series(&single, candles));
+            (symbol.clone(), historical_var(&pnl, config.confidence).var * scale)
+        })
+        .collect();
+
+    Ok(PortfolioVar {
+        timestamp: Utc::now(),
+        confidence: config.confidence,
+        horizon_bars: config.horizon_bars,
+        observations: pnl.len(),
+        gross_exposure: notionals.values().map(|n| n.abs()).sum(),
+        historical: scaled(historical_var(&pnl, config.confidence)),
+        parametric: scaled(parametric_var(&pnl, config.confidence)),
+        symbol_var,
+    })
+}
+
+/// Standard normal density
+fn normal_pdf(x: f64) -> f64 {
+    (-0.5 * x * x).exp() / (2.0 * std::f64::consts::PI).sqrt()
+}
+
+/// Standard normal quantile (Acklam's rational approximation)
+fn normal_quantile(p: f64) -> f64 {
+    const A: [f64; 6] = [-3.969683028665376e1, 2.209460984245205e2, -2.759285104469687e2,
+                         1.383577518672690e2, -3.066479806614716e1, 2.506628277459239];
+    const B: [f64; 5] = [-5.447609879822406e1, 1.615858368580409e2, -1.556989798598866e2,
+                         6.680131188771972e1, -1.328068155288572e1];
+    const C: [f64; 6] = [-7.784894002430293e-3, -3.223964580411365e-1, -2.400758277161838,
+                         -2.549732539343734, 4.374664141464968, 2.938163982698783];
+    const D: [f64; 4] = [7.784695709041462e-3, 3.224671290700398e-1, 2.445134137142996,
+                         3.754408661907416];
+    const P_LOW: f64 = 0.02425;
+
+    if p <= P_LOW {
+        let q = (-2.0 * p.ln()).sqrt();
+        (((((C[0] * q + C[1]) * q + C[2]) * q + C[3]) * q + C[4]) * q + C[5])
+            / ((((D[0] * q + D[1]) * q + D[2]) * q + D[3]) * q + 1.0)
+    } else if p < 1.0 - P_LOW {
+        let q = p - 0.5;
+        let r = q * q;
+        (((((A[0] * r + A[1]) * r + A[2]) * r + A[3]) * r + A[4]) * r + A[5]) * q
+            / (((((B[0] * r + B[1]) * r + B[2]) * r + B[3]) * r + B[4]) * r + 1.0)
+    } else {
+        -normal_quantile(1.0 - p)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn candles(closes: &[f64]) -> Vec<Candle> {
+        closes.iter().enumerate()
+            .map(|(i, close)| Candle {
+                open_time: i as i64 * 60_000,
+                open: *close,
+                high: *close,
+                low: *close,
+                close: *close,
+                volume: 1.0,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_var_of_hedged_and_unhedged_book() {
+        assert!((normal_quantile(0.95) - 1.644854).abs() < 1e-5);
+
+        // Alternating +1% / -1% returns
+        let closes: Vec<f64> = (0..41).map(|i| if i % 2 == 0 { 100.0 } else { 101.0 }).collect();
+        let history = HashMap::from([
+            ("BTCUSDT".to_string(), candles(&closes)),
+            ("ETHUSDT".to_string(), candles(&closes)),
+        ]);
+        let config = VarConfig { min_observations: 20, ..VarConfig::default() };
+
+        let long = BTreeMap::from([("BTCUSDT".to_string(), 1000.0)]);
+        let var = calculate_var(&long, &history, &config).unwrap();
+        assert_eq!(var.observations, 40);
+        assert!((var.historical.var - 1000.0 / 101.0).abs() < 1e-9);
+        assert!(var.historical.cvar >= var.historical.var);
+        assert!(var.parametric.var > 0.0);
+
+        // Long and short the same returns cancel out
+        let hedged = BTreeMap::from([("BTCUSDT".to_string(), 1000.0), ("ETHUSDT".to_string(), -1000.0)]);
+        let var = calculate_var(&hedged, &history, &config).unwrap();
+        assert!(var.historical.var.abs() < 1e-9);
+        assert!(var.diversification_benefit() > 0.0);
+
+        let short_history = HashMap::from([("BTCUSDT".to_string(), candles(&closes[..10]))]);
+        assert!(calculate_var(&long, &short_history, &config).is_err());
+    }
+}
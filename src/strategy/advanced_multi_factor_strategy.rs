@@ -9,7 +9,9 @@ use anyhow::Result;
 use tracing::{info, debug, warn, error};
 use chrono::{DateTime, Utc};
 
+use crate::agents::order_flow_agent::OrderFlowFeatures;
 use crate::strategy::simple_strategy::Candle;
+use crate::strategy::indicators::calculate_adx_from_series;
 use crate::agents::quantum_predictor::{QuantumPredictor, QuantumPrediction};
 use crate::agents::hyperdimensional_pattern_recognizer::{HyperdimensionalPatternRecognizer, PatternRecognition};
 use crate::quantum::spectral_tree_engine::SpectralTreeEngine;
@@ -137,6 +139,9 @@ pub struct AdvancedMultiFactorStrategy {
     
     /// Analysis cache
     analysis_cache: HashMap<String, MultiFactorAnalysis>,
+    
+    /// Latest order flow by symbol
+    order_flow: HashMap<String, OrderFlowFeatures>,
 }
 
 /// Strategy configuration
@@ -252,6 +257,7 @@ impl AdvancedMultiFactorStrategy {
             hyperdimensional_engine: HyperdimensionalComputing::new(),
             config,
             analysis_cache: HashMap::new(),
+            order_flow: HashMap::new(),
         })
     }
     
@@ -282,8 +288,8 @@ impl AdvancedMultiFactorStrategy {
         let spectral_components = self.calculate_spectral_components(candles)?;
         
         // 5. Market Microstructure Analysis
-        let microstructure_score = self.calculate_microstructure_score(candles)?;
-        let microstructure_metrics = self.calculate_microstructure_metrics(candles)?;
+        let microstructure_score = self.calculate_microstructure_score(symbol, candles)?;
+        let microstructure_metrics = self.calculate_microstructure_metrics(symbol, candles)?;
         
         // 6. Volume Profile Analysis
         let volume_score = self.calculate_volume_score(candles)?;
@@ -476,7 +482,7 @@ impl AdvancedMultiFactorStrategy {
         let prices: Vec<f64> = candles.iter().map(|c| c.close).collect();
 
         // Calculate dominant frequencies (simplified)
-        let trend_strength = self.calculate_trend_strength(&prices);
+        let trend_strength = self.calculate_trend_strength(candles);
         let cycle_strength = self.calculate_cycle_strength(&prices);
         let noise_level = self.calculate_noise_level(&prices);
 
@@ -489,7 +495,7 @@ impl AdvancedMultiFactorStrategy {
     }
 
     /// Calculate market microstructure score
-    fn calculate_microstructure_score(&self, candles: &[Candle]) -> Result<f64> {
+    fn calculate_microstructure_score(&self, symbol: &str, candles: &[Candle]) -> Result<f64> {
         let mut score: f64 = 50.0;
 
         // Volume-price relationship
@@ -516,11 +522,16 @@ impl AdvancedMultiFactorStrategy {
             score -= 5.0; // Wide spreads
         }
 
+        // Aggressive buying or selling on the tape
+        if let Some(flow) = self.order_flow.get(symbol) {
+            score += flow.imbalance.clamp(-1.0, 1.0) * 10.0;
+        }
+
         Ok(score.max(0.0).min(100.0))
     }
 
     /// Calculate microstructure metrics
-    fn calculate_microstructure_metrics(&self, candles: &[Candle]) -> Result<HashMap<String, f64>> {
+    fn calculate_microstructure_metrics(&self, symbol: &str, candles: &[Candle]) -> Result<HashMap<String, f64>> {
         let mut metrics = HashMap::new();
 
         metrics.insert("volume_price_correlation".to_string(),
@@ -531,6 +542,11 @@ impl AdvancedMultiFactorStrategy {
                       self.calculate_average_spread_proxy(candles)?);
         metrics.insert("volume_weighted_price".to_string(),
                       self.calculate_vwap(candles)?);
+        if let Some(flow) = self.order_flow.get(symbol) {
+            metrics.insert("order_flow_delta".to_string(), flow.delta);
+            metrics.insert("cumulative_volume_delta".to_string(), flow.cumulative_delta);
+            metrics.insert("order_flow_imbalance".to_string(), flow.imbalance);
+        }
 
         Ok(metrics)
     }
@@ -842,23 +858,15 @@ impl AdvancedMultiFactorStrategy {
 
     // Additional helper methods for advanced analysis
 
-    /// Calculate trend strength
-    fn calculate_trend_strength(&self, prices: &[f64]) -> f64 {
-        if prices.len() < 2 {
-            return 0.0;
-        }
+    /// Calculate trend strength as the ADX (0-100) over the ATR period
+    fn calculate_trend_strength(&self, candles: &[Candle]) -> f64 {
+        let highs: Vec<f64> = candles.iter().map(|c| c.high).collect();
+        let lows: Vec<f64> = candles.iter().map(|c| c.low).collect();
+        let closes: Vec<f64> = candles.iter().map(|c| c.close).collect();
 
-        let mut directional_moves = 0;
-        let total_moves = prices.len() - 1;
-
-        for i in 1..prices.len() {
-            if (prices[i] > prices[i - 1] && prices[0] < prices[prices.len() - 1]) ||
-               (prices[i] < prices[i - 1] && prices[0] > prices[prices.len() - 1]) {
-                directional_moves += 1;
-            }
-        }
-
-        (directional_moves as f64 / total_moves as f64) * 100.0
+        calculate_adx_from_series(&highs, &lows, &closes, self.config.technical_settings.atr_period)
+            .map(|result| result.adx)
+            .unwrap_or(0.0)
     }
 
     /// Calculate cycle strength
@@ -1033,6 +1041,11 @@ impl AdvancedMultiFactorStrategy {
         self.analysis_cache.get(symbol)
     }
 
+    /// Record the latest order flow of a symbol for the microstructure score
+    pub fn update_order_flow(&mut self, features: OrderFlowFeatures) {
+        self.order_flow.insert(features.symbol.clone(), features);
+    }
+
     /// Clear analysis cache
     pub fn clear_cache(&mut self) {
         self.analysis_cache.clear();
@@ -0,0 +1,359 @@
+//! DCA Strategy
+//!
+//! This module provides a dollar-cost averaging (accumulation) strategy for the
+//! OMNI-ALPHA VΩ∞∞ platform. It schedules periodic buys of a configurable basket
+//! within a capital budget and is aimed at long-horizon accumulation rather than HFT.
+
+use std::collections::{HashMap, VecDeque};
+use anyhow::Result;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use tracing::{debug, info};
+
+/// Basket allocation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DcaAllocation {
+    /// Symbol
+    pub symbol: String,
+
+    /// Relative weight within the basket
+    pub weight: f64,
+}
+
+/// Dip multiplier configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DipMultiplierConfig {
+    /// Number of observed prices used for the reference average
+    pub lookback: usize,
+
+    /// Dip thresholds as (drawdown from reference in %, buy multiplier), e.g. (10.0, 1.5)
+    pub tiers: Vec<(f64, f64)>,
+
+    /// Upper bound on the multiplier applied to a single buy
+    pub max_multiplier: f64,
+}
+
+impl Default for DipMultiplierConfig {
+    fn default() -> Self {
+        Self {
+            lookback: 30,
+            tiers: vec![(5.0, 1.25), (10.0, 1.5), (20.0, 2.0)],
+            max_multiplier: 2.0,
+        }
+    }
+}
+
+/// DCA configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DcaConfig {
+    /// Basket of symbols to accumulate
+    pub basket: Vec<DcaAllocation>,
+
+    /// Interval between scheduled buys in seconds
+    pub interval_seconds: i64,
+
+    /// Quote amount (USDT) spent per scheduled buy across the whole basket
+    pub amount_per_period: f64,
+
+    /// Total quote capital the strategy is allowed to deploy
+    pub max_capital: f64,
+
+    /// Minimum order value per symbol; smaller slices are skipped
+    pub min_order_value: f64,
+
+    /// Optional dip-multiplier logic
+    pub dip_multiplier: Option<DipMultiplierConfig>,
+}
+
+impl Default for DcaConfig {
+    fn default() -> Self {
+        Self {
+            basket: vec![
+                DcaAllocation { symbol: "BTCUSDT".to_string(), weight: 0.6 },
+                DcaAllocation { symbol: "ETHUSDT".to_string(), weight: 0.4 },
+            ],
+            interval_seconds: 7 * 24 * 3600, // Weekly
+            amount_per_period: 5.0,
+            max_capital: 12.0,
+            min_order_value: 1.0,
+            dip_multiplier: Some(DipMultiplierConfig::default()),
+        }
+    }
+}
+
+/// Scheduled DCA buy
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DcaOrder {
+    /// Symbol
+    pub symbol: String,
+
+    /// Quote amount to spend
+    pub quote_amount: f64,
+
+    /// Base quantity at the reference price
+    pub quantity: f64,
+
+    /// Price used for sizing
+    pub price: f64,
+
+    /// Dip multiplier that was applied
+    pub multiplier: f64,
+
+    /// Scheduled time
+    pub timestamp: DateTime<Utc>,
+}
+
+/// Accumulated holding for one symbol
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DcaHolding {
+    /// Total base quantity accumulated
+    pub quantity: f64,
+
+    /// Total quote capital spent
+    pub cost: f64,
+
+    /// Number of buys
+    pub buys: usize,
+}
+
+impl DcaHolding {
+    /// Average entry price
+    pub fn average_price(&self) -> f64 {
+        if self.quantity > 0.0 {
+            self.cost / self.quantity
+        } else {
+            0.0
+        }
+    }
+}
+
+/// DCA strategy
+pub struct DcaStrategy {
+    /// Configuration
+    config: DcaConfig,
+
+    /// Time of the last executed schedule
+    last_run: Option<DateTime<Utc>>,
+
+    /// Capital deployed so far
+    deployed_capital: f64,
+
+    /// Recent prices per symbol for dip detection
+    price_history: HashMap<String, VecDeque<f64>>,
+
+    /// Accumulated holdings
+    holdings: HashMap<String, DcaHolding>,
+}
+
+impl DcaStrategy {
+    /// Create a new DCA strategy
+    pub fn new(config: DcaConfig) -> Result<Self> {
+        if config.basket.is_empty() {
+            return Err(anyhow::anyhow!("DCA basket must contain at least one symbol"));
+        }
+
+        if config.basket.iter().any(|a| a.weight <= 0.0) {
+            return Err(anyhow::anyhow!("DCA basket weights must be positive"));
+        }
+
+        if config.interval_seconds <= 0 {
+            return Err(anyhow::anyhow!("DCA interval must be positive"));
+        }
+
+        Ok(Self {
+            config,
+            last_run: None,
+            deployed_capital: 0.0,
+            price_history: HashMap::new(),
+            holdings: HashMap::new(),
+        })
+    }
+
+    /// Record an observed price for dip detection
+    pub fn observe_price(&mut self, symbol: &str, price: f64) {
+        let lookback = self.config.dip_multiplier.as_ref().map(|d| d.lookback).unwrap_or(0);
+        if lookback == 0 || price <= 0.0 {
+            return;
+        }
+
+        let history = self.price_history.entry(symbol.to_string()).or_insert_with(VecDeque::new);
+        history.push_back(price);
+        while history.len() > lookback {
+            history.pop_front();
+        }
+    }
+
+    /// Check whether a scheduled buy is due
+    pub fn is_due(&self, now: DateTime<Utc>) -> bool {
+        match self.last_run {
+            Some(last) => now - last >= Duration::seconds(self.config.interval_seconds),
+            None => true,
+        }
+    }
+
+    /// Remaining capital the strategy may deploy
+    pub fn remaining_capital(&self) -> f64 {
+        (self.config.max_capital - self.deployed_capital).max(0.0)
+    }
+
+    /// Generate the scheduled buys for `now`
+    ///
+    /// Returns an empty list when no buy is due or the capital budget is exhausted.
+    /// `available_capital` is the free balance on the account and further caps spending.
+    pub fn generate_orders(
+        &mut self,
+        now: DateTime<Utc>,
+        prices: &HashMap<String, f64>,
+        available_capital: f64,
+    ) -> Vec<DcaOrder> {
+        if !self.is_due(now) {
+            return Vec::new();
+        }
+
+        let mut budget = self.remaining_capital().min(available_capital.max(0.0));
+        if budget < self.config.min_order_value {
+            debug!("DCA budget exhausted: {:.2} remaining", budget);
+            return Vec::new();
+        }
+
+        let total_weight: f64 = self.config.basket.iter().map(|a| a.weight).sum();
+        let mut orders = Vec::new();
+
+        for allocation in &self.config.basket {
+            let price = match prices.get(&allocation.symbol) {
+                Some(p) if *p > 0.0 => *p,
+                _ => {
+                    debug!("No price for {}, skipping DCA slice", allocation.symbol);
+                    continue;
+                }
+            };
+
+            let multiplier = self.dip_multiplier(&allocation.symbol, price);
+            let slice = self.config.amount_per_period * allocation.weight / total_weight * multiplier;
+            let quote_amount = slice.min(budget);
+
+            if quote_amount < self.config.min_order_value {
+                continue;
+            }
+
+            budget -= quote_amount;
+            orders.push(DcaOrder {
+                symbol: allocation.symbol.clone(),
+                quote_amount,
+                quantity: quote_amount / price,
+                price,
+                multiplier,
+                timestamp: now,
+            });
+        }
+
+        // A period where every slice was skipped is retried on the next call
+        if !orders.is_empty() {
+            self.last_run = Some(now);
+            info!("DCA schedule generated {} buys totalling ${:.2}",
+                orders.len(), orders.iter().map(|o| o.quote_amount).sum::<f64>());
+        }
+
+        orders
+    }
+
+    /// Record a filled DCA buy
+    pub fn record_fill(&mut self, symbol: &str, quantity: f64, price: f64) {
+        let cost = quantity * price;
+        self.deployed_capital += cost;
+
+        let holding = self.holdings.entry(symbol.to_string()).or_default();
+        holding.quantity += quantity;
+        holding.cost += cost;
+        holding.buys += 1;
+    }
+
+    /// Dip multiplier for the current price relative to the recent average
+    fn dip_multiplier(&self, symbol: &str, price: f64) -> f64 {
+        let dip_config = match &self.config.dip_multiplier {
+            Some(config) => config,
+            None => return 1.0,
+        };
+
+        let history = match self.price_history.get(symbol) {
+            Some(h) if !h.is_empty() => h,
+            _ => return 1.0,
+        };
+
+        let reference = history.iter().sum::<f64>() / history.len() as f64;
+        if reference <= 0.0 {
+            return 1.0;
+        }
+
+        let drawdown = (reference - price) / reference * 100.0;
+
+        dip_config.tiers.iter()
+            .filter(|(threshold, _)| drawdown >= *threshold)
+            .map(|(_, multiplier)| *multiplier)
+            .fold(1.0, f64::max)
+            .min(dip_config.max_multiplier)
+    }
+
+    /// Get accumulated holdings
+    pub fn get_holdings(&self) -> &HashMap<String, DcaHolding> {
+        &self.holdings
+    }
+
+    /// Get capital deployed so far
+    pub fn get_deployed_capital(&self) -> f64 {
+        self.deployed_capital
+    }
+
+    /// Get configuration
+    pub fn get_config(&self) -> &DcaConfig {
+        &self.config
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn prices(btc: f64, eth: f64) -> HashMap<String, f64> {
+        let mut map = HashMap::new();
+        map.insert("BTCUSDT".to_string(), btc);
+        map.insert("ETHUSDT".to_string(), eth);
+        map
+    }
+
+    #[test]
+    fn test_schedule_and_budget() {
+        let config = DcaConfig { dip_multiplier: None, ..DcaConfig::default() };
+        let mut strategy = DcaStrategy::new(config).unwrap();
+        let now = Utc::now();
+
+        // Without prices nothing is bought and the period stays due
+        assert!(strategy.generate_orders(now, &HashMap::new(), 100.0).is_empty());
+        assert!(strategy.is_due(now));
+
+        let orders = strategy.generate_orders(now, &prices(50000.0, 3000.0), 100.0);
+        assert_eq!(orders.len(), 2);
+        assert!((orders[0].quote_amount - 3.0).abs() < 1e-9);
+        assert!((orders[1].quote_amount - 2.0).abs() < 1e-9);
+
+        // Not due again until the interval elapses
+        assert!(strategy.generate_orders(now + Duration::hours(1), &prices(50000.0, 3000.0), 100.0).is_empty());
+
+        for order in &orders {
+            strategy.record_fill(&order.symbol, order.quantity, order.price);
+        }
+        assert!((strategy.remaining_capital() - 7.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_dip_multiplier() {
+        let mut strategy = DcaStrategy::new(DcaConfig::default()).unwrap();
+        for _ in 0..10 {
+            strategy.observe_price("BTCUSDT", 50000.0);
+        }
+
+        assert_eq!(strategy.dip_multiplier("BTCUSDT", 50000.0), 1.0);
+        assert_eq!(strategy.dip_multiplier("BTCUSDT", 44000.0), 1.5);
+        assert_eq!(strategy.dip_multiplier("BTCUSDT", 30000.0), 2.0);
+    }
+}
@@ -0,0 +1,290 @@
+//! Strategy Ensemble
+//!
+//! This module runs several strategies on the same data and combines their signals
+//! into a single `TradingDecision` using performance-weighted voting. Weights are
+//! derived from the per-strategy performance scores maintained by the `FeedbackLoop`.
+
+use anyhow::Result;
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use tracing::{debug, info, warn};
+
+use crate::agents::agent_coordinator::{DecisionType, TradingDecision};
+use crate::agents::feedback_loop::FeedbackLoop;
+use crate::strategy::simple_strategy::Candle;
+use crate::strategy::strategy_trait::{Strategy, StrategySignal};
+
+/// Ensemble configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EnsembleConfig {
+    /// Weight given to strategies without a performance record
+    pub default_weight: f64,
+
+    /// Minimum weight any strategy can have
+    pub min_weight: f64,
+
+    /// Net weighted vote (-1.0 to 1.0) required to act
+    pub consensus_threshold: f64,
+
+    /// Minimum consolidated confidence (0-100) required to act
+    pub min_confidence: f64,
+}
+
+impl Default for EnsembleConfig {
+    fn default() -> Self {
+        Self {
+            default_weight: 1.0,
+            min_weight: 0.05,
+            consensus_threshold: 0.3,
+            min_confidence: 60.0,
+        }
+    }
+}
+
+/// Individual vote in an ensemble decision
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EnsembleVote {
+    /// Strategy signal
+    pub signal: StrategySignal,
+
+    /// Weight applied to the signal
+    pub weight: f64,
+}
+
+/// Strategy ensemble with weighted voting
+pub struct StrategyEnsemble {
+    /// Configuration
+    config: EnsembleConfig,
+
+    /// Member strategies
+    strategies: Vec<Box<dyn Strategy>>,
+
+    /// Votes from the last decision
+    last_votes: Vec<EnsembleVote>,
+}
+
+impl StrategyEnsemble {
+    /// Create a new strategy ensemble
+    pub fn new(config: EnsembleConfig) -> Self {
+        Self {
+            config,
+            strategies: Vec::new(),
+            last_votes: Vec::new(),
+        }
+    }
+
+    /// Add a strategy to the ensemble
+    pub fn add_strategy(&mut self, strategy: Box<dyn Strategy>) {
+        info!("Adding strategy {} to ensemble", strategy.get_name());
+        self.strategies.push(strategy);
+    }
+
+    /// Remove a strategy by name
+    pub fn remove_strategy(&mut self, name: &str) -> Option<Box<dyn Strategy>> {
+        let index = self.strategies.iter().position(|s| s.get_name() == name)?;
+        Some(self.strategies.remove(index))
+    }
+
+    /// Names of the member strategies
+    pub fn strategy_names(&self) -> Vec<String> {
+        self.strategies.iter().map(|s| s.get_name()).collect()
+    }
+
+    /// Weight for a strategy based on its `FeedbackLoop` performance record
+    ///
+    /// The performance score (-1.0 to 1.0) is mapped to (0.0 to 2.0) and scaled by
+    /// the record's confidence.
+    pub fn strategy_weight(&self, name: &str, feedback_loop: &FeedbackLoop) -> f64 {
+        match feedback_loop.get_agent_performance(name) {
+            Some(performance) => {
+                let weight = (1.0 + performance.score) * performance.confidence.max(0.1);
+                weight.max(self.config.min_weight)
+            }
+            None => self.config.default_weight,
+        }
+    }
+
+    /// Run all strategies and combine their signals into one decision
+    pub fn decide(&mut self, symbol: &str, candles: &[Candle], feedback_loop: &FeedbackLoop) -> Result<TradingDecision> {
+        let mut votes = Vec::new();
+
+        for strategy in self.strategies.iter_mut() {
+            let name = strategy.get_name();
+            if candles.len() < strategy.min_candles() {
+                debug!("{} skipped: {} candles < {}", name, candles.len(), strategy.min_candles());
+                continue;
+            }
+
+            match strategy.analyze(symbol, candles) {
+                Ok(signal) => votes.push(signal),
+                Err(e) => warn!("Strategy {} failed for {}: {}", name, symbol, e),
+            }
+        }
+
+        let weighted: Vec<EnsembleVote> = votes.into_iter()
+            .map(|signal| {
+                let weight = self.strategy_weight(&signal.strategy, feedback_loop);
+                EnsembleVote { signal, weight }
+            })
+            .collect();
+
+        let decision = self.consolidate(symbol, &weighted);
+        self.last_votes = weighted;
+
+        Ok(decision)
+    }
+
+    /// Combine weighted votes into a single decision
+    fn consolidate(&self, symbol: &str, votes: &[EnsembleVote]) -> TradingDecision {
+        let total_weight: f64 = votes.iter().map(|v| v.weight).sum();
+
+        let (decision_type, confidence, net_vote) = if votes.is_empty() || total_weight <= 0.0 {
+            (DecisionType::InsufficientData, 0.0, 0.0)
+        } else {
+            // Net vote in [-1, 1], each vote scaled by its own confidence
+            let net_vote = votes.iter()
+                .map(|v| v.weight * v.signal.direction() * v.signal.confidence / 100.0)
+                .sum::<f64>() / total_weight;
+
+            // Confidence is the weighted confidence of the voters agreeing with the outcome
+            let direction = net_vote.signum();
+            let agreeing_weight: f64 = votes.iter()
+                .filter(|v| v.signal.direction() == direction)
+                .map(|v| v.weight)
+                .sum();
+            let agreeing_confidence = if agreeing_weight > 0.0 {
+                votes.iter()
+                    .filter(|v| v.signal.direction() == direction)
+                    .map(|v| v.weight * v.signal.confidence)
+                    .sum::<f64>() / agreeing_weight
+            } else {
+                0.0
+            };
+            let confidence = agreeing_confidence * (agreeing_weight / total_weight);
+
+            let decision_type = if net_vote.abs() < self.config.consensus_threshold
+                || confidence < self.config.min_confidence
+            {
+                DecisionType::Hold
+            } else if net_vote > 0.0 {
+                DecisionType::Buy
+            } else {
+                DecisionType::Sell
+            };
+
+            (decision_type, confidence, net_vote)
+        };
+
+        let breakdown: Vec<String> = votes.iter()
+            .map(|v| format!("{}={:?}@{:.0}(w{:.2})", v.signal.strategy, v.signal.decision_type, v.signal.confidence, v.weight))
+            .collect();
+
+        TradingDecision {
+            symbol: symbol.to_string(),
+            timestamp: Utc::now(),
+            decision_type,
+            confidence,
+            market_analysis: None,
+            sentiment_analysis: None,
+            risk_assessment: None,
+            zero_loss_assessment: None,
+            quantum_prediction: None,
+            pattern_recognition: None,
+            multi_factor_analysis: None,
+            spectral_prediction: None,
+            trade_execution: None,
+            reasoning: format!("Ensemble net vote {:.2}: {}", net_vote, breakdown.join(", ")),
+            superintelligence_score: net_vote.abs() * 100.0,
+            agent: None,
+            consensus: None,
+        }
+    }
+
+    /// Votes from the last decision
+    pub fn get_last_votes(&self) -> &[EnsembleVote] {
+        &self.last_votes
+    }
+
+    /// Get configuration
+    pub fn get_config(&self) -> &EnsembleConfig {
+        &self.config
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use crate::agents::feedback_loop::FeedbackLoopConfig;
+    use crate::engine::message_bus::MessageBus;
+
+    fn vote(strategy: &str, decision_type: DecisionType, confidence: f64, weight: f64) -> EnsembleVote {
+        let mut signal = StrategySignal::hold(strategy, "BTCUSDT", 100.0, "");
+        signal.decision_type = decision_type;
+        signal.confidence = confidence;
+        EnsembleVote { signal, weight }
+    }
+
+    #[test]
+    fn test_weighted_majority_outvotes_headcount() {
+        let ensemble = StrategyEnsemble::new(EnsembleConfig::default());
+        let votes = |buy_weight: f64| vec![
+            vote("trend", DecisionType::Buy, 90.0, buy_weight),
+            vote("mean_reversion", DecisionType::Sell, 80.0, 0.5),
+            vote("breakout", DecisionType::Sell, 80.0, 0.5),
+        ];
+
+        // The best-performing strategy carries the vote against two weaker ones
+        let decision = ensemble.consolidate("BTCUSDT", &votes(3.0));
+        assert!(matches!(decision.decision_type, DecisionType::Buy));
+        assert!((decision.confidence - 67.5).abs() < 1e-9);
+
+        // With less weight behind it, the split vote falls short of consensus
+        let decision = ensemble.consolidate("BTCUSDT", &votes(0.5));
+        assert!(matches!(decision.decision_type, DecisionType::Hold));
+    }
+
+    #[test]
+    fn test_tied_vote_holds() {
+        let ensemble = StrategyEnsemble::new(EnsembleConfig::default());
+        let decision = ensemble.consolidate("BTCUSDT", &[
+            vote("trend", DecisionType::Buy, 80.0, 1.0),
+            vote("mean_reversion", DecisionType::Sell, 80.0, 1.0),
+        ]);
+
+        assert!(matches!(decision.decision_type, DecisionType::Hold));
+        assert_eq!(decision.superintelligence_score, 0.0);
+    }
+
+    #[test]
+    fn test_all_strategies_abstaining_holds() {
+        struct Abstain(&'static str);
+
+        impl Strategy for Abstain {
+            fn get_name(&self) -> String {
+                self.0.to_string()
+            }
+
+            fn analyze(&mut self, symbol: &str, candles: &[Candle]) -> Result<StrategySignal> {
+                Ok(StrategySignal::hold(self.0, symbol, candles.last().map_or(0.0, |c| c.close), "no setup"))
+            }
+
+            fn min_candles(&self) -> usize {
+                0
+            }
+        }
+
+        let feedback_loop = FeedbackLoop::new(FeedbackLoopConfig::default(), Arc::new(MessageBus::new()));
+        let mut ensemble = StrategyEnsemble::new(EnsembleConfig::default());
+        assert!(matches!(ensemble.decide("BTCUSDT", &[], &feedback_loop).unwrap().decision_type, DecisionType::InsufficientData));
+
+        ensemble.add_strategy(Box::new(Abstain("trend")));
+        ensemble.add_strategy(Box::new(Abstain("mean_reversion")));
+        let decision = ensemble.decide("BTCUSDT", &[], &feedback_loop).unwrap();
+
+        assert!(matches!(decision.decision_type, DecisionType::Hold));
+        assert_eq!(decision.confidence, 0.0);
+        assert_eq!(ensemble.get_last_votes().len(), 2);
+        assert!(ensemble.get_last_votes().iter().all(|v| v.weight == ensemble.get_config().default_weight));
+    }
+}
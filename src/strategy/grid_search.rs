@@ -0,0 +1,360 @@
+//! Grid-Search Optimizer
+//!
+//! This module exhaustively evaluates every combination of stepped parameter ranges.
+//! Results are appended to a `ResultStore` (JSON Lines by default, SQLite with the
+//! `sqlite` feature) after every batch, so an interrupted run resumes where it
+//! stopped and the full result set can be analyzed afterwards.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use anyhow::Result;
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use tracing::{info, warn};
+
+use crate::backtest::BacktestResult;
+use crate::strategy::optimizer::{pareto_front, Candidate, FitnessScore, ParameterRange, ParameterSet};
+
+/// Result of one grid point
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GridResult {
+    /// Index of the combination in the grid
+    pub index: usize,
+
+    /// Parameters
+    pub parameters: BTreeMap<String, f64>,
+
+    /// Fitness, if the backtest succeeded
+    pub fitness: Option<FitnessScore>,
+
+    /// Error message, if it failed
+    pub error: Option<String>,
+}
+
+/// Persistent storage for grid results
+pub trait ResultStore: Send {
+    /// Indices already evaluated
+    fn completed(&self) -> Result<HashSet<usize>>;
+
+    /// Append a batch of results durably
+    fn append(&mut self, results: &[GridResult]) -> Result<()>;
+
+    /// Load every stored result
+    fn load_all(&self) -> Result<Vec<GridResult>>;
+}
+
+/// Append-only JSON Lines result store
+pub struct JsonLinesStore {
+    /// File path
+    path: PathBuf,
+}
+
+impl JsonLinesStore {
+    /// Open (or create) a store at `path`
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        OpenOptions::new().create(true).append(true).open(&path)?;
+        Ok(Self { path })
+    }
+}
+
+impl ResultStore for JsonLinesStore {
+    fn completed(&self) -> Result<HashSet<usize>> {
+        Ok(self.load_all()?.into_iter().map(|r| r.index).collect())
+    }
+
+    fn append(&mut self, results: &[GridResult]) -> Result<()> {
+        let mut file = OpenOptions::new().append(true).open(&self.path)?;
+        for result in results {
+            writeln!(file, "{}", serde_json::to_string(result)?)?;
+        }
+        file.sync_data()?;
+        Ok(())
+    }
+
+    fn load_all(&self) -> Result<Vec<GridResult>> {
+        let reader = BufReader::new(File::open(&self.path)?);
+        let mut results = Vec::new();
+        for line in reader.lines() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            // A line cut off by a crash is re-evaluated on resume
+            match serde_json::from_str(&line) {
+                Ok(result) => results.push(result),
+                Err(e) => warn!("Skipping unreadable grid result in {}: {}", self.path.display(), e),
+            }
+        }
+        Ok(results)
+    }
+}
+
+/// SQLite result store
+#[cfg(feature = "sqlite")]
+pub struct SqliteStore {
+    /// Connection
+    connection: rusqlite::Connection,
+}
+
+#[cfg(feature = "sqlite")]
+impl SqliteStore {
+    /// Open (or create) a store at `path`
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let connection = rusqlite::Connection::open(path)?;
+        connection.execute_batch(
+            "CREATE TABLE IF NOT EXISTS grid_results (
+                idx INTEGER PRIMARY KEY,
+                parameters TEXT NOT NULL,
+                total_return REAL,
+                max_drawdown REAL,
+                sharpe_ratio REAL,
+                win_rate REAL,
+                total_trades INTEGER,
+                error TEXT
+            );",
+        )?;
+        Ok(Self { connection })
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl ResultStore for SqliteStore {
+    fn completed(&self) -> Result<HashSet<usize>> {
+        let mut statement = self.connection.prepare("SELECT idx FROM grid_results")?;
+        let indices = statement.query_map([], |row| row.get::<_, i64>(0))?
+            .map(|index| Ok(index? as usize))
+            .collect::<Result<HashSet<usize>>>()?;
+        Ok(indices)
+    }
+
+    fn append(&mut self, results: &[GridResult]) -> Result<()> {
+        let transaction = self.connection.transaction()?;
+        for result in results {
+            let fitness = result.fitness.as_ref();
+            transaction.execute(
+                "INSERT OR REPLACE INTO grid_results VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+                rusqlite::params![
+                    result.index as i64,
+                    serde_json::to_string(&result.parameters)?,
+                    fitness.map(|f| f.total_return),
+                    fitness.map(|f| f.max_drawdown),
+                    fitness.map(|f| f.sharpe_ratio),
+                    fitness.map(|f| f.win_rate),
+                    fitness.map(|f| f.total_trades as i64),
+                    result.error,
+                ],
+            )?;
+        }
+        transaction.commit()?;
+        Ok(())
+    }
+
+    fn load_all(&self) -> Result<Vec<GridResult>> {
+        let mut statement = self.connection.prepare(
+            "SELECT idx, parameters, total_return, max_drawdown, sharpe_ratio, win_rate, total_trades, error
+             FROM grid_results ORDER BY idx",
+        )?;
+        let rows = statement.query_map([], |row| {
+            Ok((
+                row.get::<_, i64>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, Option<f64>>(2)?,
+                row.get::<_, Option<f64>>(3)?,
+                row.get::<_, Option<f64>>(4)?,
+                row.get::<_, Option<f64>>(5)?,
+                row.get::<_, Option<i64>>(6)?,
+                row.get::<_, Option<String>>(7)?,
+            ))
+        })?;
+
+        let mut results = Vec::new();
+        for row in rows {
+            let (index, parameters, total_return, max_drawdown, sharpe_ratio, win_rate, total_trades, error) = row?;
+            let fitness = match (total_return, max_drawdown, sharpe_ratio, win_rate, total_trades) {
+                (Some(total_return), Some(max_drawdown), Some(sharpe_ratio), Some(win_rate), Some(total_trades)) => {
+                    Some(FitnessScore { total_return, max_drawdown, sharpe_ratio, win_rate, total_trades: total_trades as u32 })
+                }
+                _ => None,
+            };
+            results.push(GridResult {
+                index: index as usize,
+                parameters: serde_json::from_str(&parameters)?,
+                fitness,
+                error,
+            });
+        }
+        Ok(results)
+    }
+}
+
+/// Grid-search report
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GridSearchReport {
+    /// All results (including earlier runs), by grid index
+    pub results: Vec<GridResult>,
+
+    /// Pareto-optimal parameter sets
+    pub pareto_front: Vec<Candidate>,
+
+    /// Best result by scalar fitness
+    pub best: Option<GridResult>,
+
+    /// Combinations evaluated in this run
+    pub evaluated_this_run: usize,
+
+    /// Combinations skipped because they were already stored
+    pub resumed: usize,
+}
+
+/// Exhaustive grid-search optimizer
+pub struct GridSearch {
+    /// Ordered parameter space
+    space: BTreeMap<String, Vec<f64>>,
+
+    /// Results persisted per batch
+    batch_size: usize,
+}
+
+impl GridSearch {
+    /// Create a grid search; every range must have a positive step
+    pub fn new(space: HashMap<String, ParameterRange>) -> Result<Self> {
+        if space.is_empty() {
+            return Err(anyhow::anyhow!("Parameter space must not be empty"));
+        }
+
+        let mut grid = BTreeMap::new();
+        for (name, range) in space {
+            let step = match range.step {
+                Some(step) if step > 0.0 => step,
+                _ => return Err(anyhow::anyhow!("Parameter {} needs a positive step for grid search", name)),
+            };
+            let count = ((range.max - range.min) / step + 1e-9).floor() as usize + 1;
+            let values = (0..count).map(|i| range.normalize(range.min + i as f64 * step)).collect();
+            grid.insert(name, values);
+        }
+
+        Ok(Self { space: grid, batch_size: 64 })
+    }
+
+    /// Number of results evaluated in parallel before they are persisted
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size.max(1);
+        self
+    }
+
+    /// Number of combinations
+    pub fn total_combinations(&self) -> usize {
+        self.space.values().map(|v| v.len()).product()
+    }
+
+    /// Parameters of the combination at `index` (mixed-radix, last parameter fastest)
+    pub fn combination(&self, index: usize) -> BTreeMap<String, f64> {
+        let mut remainder = index;
+        let mut params = BTreeMap::new();
+        for (name, values) in self.space.iter().rev() {
+            params.insert(name.clone(), values[remainder % values.len()]);
+            remainder /= values.len();
+        }
+        params
+    }
+
+    /// Evaluate every combination not yet in `store`
+    pub fn run<F>(&self, store: &mut dyn ResultStore, evaluate: F) -> Result<GridSearchReport>
+    where
+        F: Fn(&ParameterSet) -> Result<BacktestResult> + Sync,
+    {
+        let total = self.total_combinations();
+        let completed = store.completed()?;
+        let pending: Vec<usize> = (0..total).filter(|i| !completed.contains(i)).collect();
+        info!("Grid search: {} combinations, {} already stored, {} to evaluate",
+              total, total - pending.len(), pending.len());
+
+        for batch in pending.chunks(self.batch_size) {
+            let results: Vec<GridResult> = batch.par_iter()
+                .map(|&index| {
+                    let parameters = self.combination(index);
+                    let set: ParameterSet = parameters.iter().map(|(k, v)| (k.clone(), *v)).collect();
+                    match evaluate(&set) {
+                        Ok(result) => GridResult { index, parameters, fitness: Some(FitnessScore::from_result(&result)), error: None },
+                        Err(e) => GridResult { index, parameters, fitness: None, error: Some(e.to_string()) },
+                    }
+                })
+                .collect();
+            store.append(&results)?;
+        }
+
+        let mut results = store.load_all()?;
+        results.sort_by_key(|r| r.index);
+        results.dedup_by_key(|r| r.index);
+
+        let candidates: Vec<Candidate> = results.iter()
+            .filter_map(|r| Some(Candidate {
+                parameters: r.parameters.iter().map(|(k, v)| (k.clone(), *v)).collect(),
+                fitness: r.fitness.clone()?,
+                generation: 0,
+            }))
+            .collect();
+        let best = results.iter()
+            .filter(|r| r.fitness.is_some())
+            .max_by(|a, b| {
+                let (a, b) = (a.fitness.as_ref().map(|f| f.scalar()), b.fitness.as_ref().map(|f| f.scalar()));
+                a.partial_cmp(&b).unwrap_or(std::cmp::Ordering::Equal)
+            })
+            .cloned();
+
+        Ok(GridSearchReport {
+            pareto_front: pareto_front(&candidates),
+            best,
+            evaluated_this_run: pending.len(),
+            resumed: total - pending.len(),
+            results,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backtest::{BacktestConfig, BacktestTrade};
+
+    #[test]
+    fn test_grid_search_resumes_from_store() {
+        let path = std::env::temp_dir().join(format!("omni_grid_{}.jsonl", std::process::id()));
+        let _ = fs::remove_file(&path);
+
+        let space = HashMap::from([
+            ("fast".to_string(), ParameterRange::stepped(5.0, 15.0, 5.0)),
+            ("slow".to_string(), ParameterRange::stepped(20.0, 30.0, 10.0)),
+        ]);
+        let grid = GridSearch::new(space).unwrap().with_batch_size(2);
+        assert_eq!(grid.total_combinations(), 6);
+
+        let evaluate = |params: &ParameterSet| {
+            let mut trade = BacktestTrade::new("BTCUSDT".to_string(), 0, 100.0, 1.0, "long".to_string());
+            trade.exit_time = 3600;
+            trade.profit_loss = params["slow"] - params["fast"];
+            Ok(BacktestResult::new(BacktestConfig::new(0, 86_400, 1_000.0, vec!["BTCUSDT".to_string()]), vec![trade]))
+        };
+
+        // Simulate an interrupted run by storing the first two results only
+        let mut store = JsonLinesStore::open(&path).unwrap();
+        let partial: Vec<GridResult> = (0..2)
+            .map(|index| GridResult { index, parameters: grid.combination(index), fitness: None, error: Some("interrupted".to_string()) })
+            .collect();
+        store.append(&partial).unwrap();
+
+        let report = grid.run(&mut store, evaluate).unwrap();
+        assert_eq!(report.resumed, 2);
+        assert_eq!(report.evaluated_this_run, 4);
+        assert_eq!(report.results.len(), 6);
+        assert_eq!(report.best.unwrap().parameters["slow"], 30.0);
+
+        let _ = fs::remove_file(&path);
+    }
+}
@@ -1,4 +1,5 @@
 use crate::exchange::types::Candle;
+use chrono::{DateTime, Duration, Utc};
 
 /// Calculate Simple Moving Average (SMA)
 pub fn calculate_sma(candles: &[Candle], period: usize) -> f64 {
@@ -253,3 +254,529 @@ pub fn calculate_position_size(capital: f64, risk_percentage: f64, current_price
 
     units
 }
+
+/// Ichimoku Cloud values
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct IchimokuCloud {
+    /// Tenkan-sen (conversion line) at the current bar
+    pub tenkan_sen: f64,
+
+    /// Kijun-sen (base line) at the current bar
+    pub kijun_sen: f64,
+
+    /// Senkou Span A plotted at the current bar (computed `displacement` bars ago)
+    pub senkou_span_a: f64,
+
+    /// Senkou Span B plotted at the current bar (computed `displacement` bars ago)
+    pub senkou_span_b: f64,
+
+    /// Senkou Span A computed now, plotted `displacement` bars ahead
+    pub leading_span_a: f64,
+
+    /// Senkou Span B computed now, plotted `displacement` bars ahead
+    pub leading_span_b: f64,
+
+    /// Chikou Span (current close, plotted `displacement` bars back)
+    pub chikou_span: f64,
+
+    /// Close `displacement` bars ago, which the Chikou Span is compared against
+    pub chikou_reference: f64,
+}
+
+impl IchimokuCloud {
+    /// Upper edge of the cloud at the current bar
+    pub fn cloud_top(&self) -> f64 {
+        self.senkou_span_a.max(self.senkou_span_b)
+    }
+
+    /// Lower edge of the cloud at the current bar
+    pub fn cloud_bottom(&self) -> f64 {
+        self.senkou_span_a.min(self.senkou_span_b)
+    }
+
+    /// Whether the projected cloud is bullish (Span A above Span B)
+    pub fn is_bullish_cloud(&self) -> bool {
+        self.leading_span_a > self.leading_span_b
+    }
+}
+
+/// Midpoint of the highest high and lowest low over `period` candles ending at `end` (exclusive)
+fn period_midpoint(candles: &[Candle], end: usize, period: usize) -> f64 {
+    let window = &candles[end - period..end];
+    let highest_high = window.iter().map(|c| c.high).fold(f64::MIN, f64::max);
+    let lowest_low = window.iter().map(|c| c.low).fold(f64::MAX, f64::min);
+    (highest_high + lowest_low) / 2.0
+}
+
+/// Calculate Ichimoku Cloud with the given periods (standard: 9, 26, 52, 26)
+///
+/// Returns `None` when there are fewer than `senkou_b_period + displacement` candles,
+/// since the cloud plotted at the current bar is computed `displacement` bars ago.
+pub fn calculate_ichimoku(
+    candles: &[Candle],
+    tenkan_period: usize,
+    kijun_period: usize,
+    senkou_b_period: usize,
+    displacement: usize,
+) -> Option<IchimokuCloud> {
+    let longest = tenkan_period.max(kijun_period).max(senkou_b_period);
+    if tenkan_period == 0 || candles.len() < longest + displacement {
+        return None;
+    }
+
+    let len = candles.len();
+    let displaced_end = len - displacement;
+
+    let tenkan_sen = period_midpoint(candles, len, tenkan_period);
+    let kijun_sen = period_midpoint(candles, len, kijun_period);
+
+    let displaced_tenkan = period_midpoint(candles, displaced_end, tenkan_period);
+    let displaced_kijun = period_midpoint(candles, displaced_end, kijun_period);
+
+    Some(IchimokuCloud {
+        tenkan_sen,
+        kijun_sen,
+        senkou_span_a: (displaced_tenkan + displaced_kijun) / 2.0,
+        senkou_span_b: period_midpoint(candles, displaced_end, senkou_b_period),
+        leading_span_a: (tenkan_sen + kijun_sen) / 2.0,
+        leading_span_b: period_midpoint(candles, len, senkou_b_period),
+        chikou_span: candles[len - 1].close,
+        chikou_reference: candles[displaced_end - 1].close,
+    })
+}
+
+/// Calculate Ichimoku Cloud with the standard 9/26/52/26 settings
+pub fn calculate_ichimoku_default(candles: &[Candle]) -> Option<IchimokuCloud> {
+    calculate_ichimoku(candles, 9, 26, 52, 26)
+}
+
+/// Average Directional Index with Directional Movement indicators
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AdxResult {
+    /// Average Directional Index (0-100), trend strength regardless of direction
+    pub adx: f64,
+
+    /// Positive Directional Indicator (+DI)
+    pub plus_di: f64,
+
+    /// Negative Directional Indicator (-DI)
+    pub minus_di: f64,
+}
+
+impl AdxResult {
+    /// Whether the trend is strong enough to trade (ADX above `threshold`, typically 25)
+    pub fn is_trending(&self, threshold: f64) -> bool {
+        self.adx >= threshold
+    }
+
+    /// Whether directional movement is bullish (+DI above -DI)
+    pub fn is_bullish(&self) -> bool {
+        self.plus_di > self.minus_di
+    }
+}
+
+/// Calculate ADX / DMI from raw high, low and close series using Wilder smoothing
+///
+/// Requires at least `2 * period` values; returns `None` otherwise.
+pub fn calculate_adx_from_series(highs: &[f64], lows: &[f64], closes: &[f64], period: usize) -> Option<AdxResult> {
+    let len = highs.len().min(lows.len()).min(closes.len());
+    if period == 0 || len < 2 * period {
+        return None;
+    }
+
+    let period_f = period as f64;
+    let mut smoothed_tr = 0.0;
+    let mut smoothed_plus_dm = 0.0;
+    let mut smoothed_minus_dm = 0.0;
+    let mut dx_values = Vec::with_capacity(len);
+    let mut plus_di = 0.0;
+    let mut minus_di = 0.0;
+
+    for i in 1..len {
+        let up_move = highs[i] - highs[i - 1];
+        let down_move = lows[i - 1] - lows[i];
+
+        let plus_dm = if up_move > down_move && up_move > 0.0 { up_move } else { 0.0 };
+        let minus_dm = if down_move > up_move && down_move > 0.0 { down_move } else { 0.0 };
+        let true_range = (highs[i] - lows[i])
+            .max((highs[i] - closes[i - 1]).abs())
+            .max((lows[i] - closes[i - 1]).abs());
+
+        if i <= period {
+            // Seed the smoothed values with plain sums over the first period
+            smoothed_tr += true_range;
+            smoothed_plus_dm += plus_dm;
+            smoothed_minus_dm += minus_dm;
+            if i < period {
+                continue;
+            }
+        } else {
+            smoothed_tr = smoothed_tr - smoothed_tr / period_f + true_range;
+            smoothed_plus_dm = smoothed_plus_dm - smoothed_plus_dm / period_f + plus_dm;
+            smoothed_minus_dm = smoothed_minus_dm - smoothed_minus_dm / period_f + minus_dm;
+        }
+
+        if smoothed_tr > 0.0 {
+            plus_di = 100.0 * smoothed_plus_dm / smoothed_tr;
+            minus_di = 100.0 * smoothed_minus_dm / smoothed_tr;
+        } else {
+            plus_di = 0.0;
+            minus_di = 0.0;
+        }
+
+        let di_sum = plus_di + minus_di;
+        let dx = if di_sum > 0.0 { 100.0 * (plus_di - minus_di).abs() / di_sum } else { 0.0 };
+        dx_values.push(dx);
+    }
+
+    // First ADX is the mean of the first `period` DX values, then Wilder-smoothed
+    let mut adx = dx_values[..period].iter().sum::<f64>() / period_f;
+    for dx in &dx_values[period..] {
+        adx = (adx * (period_f - 1.0) + dx) / period_f;
+    }
+
+    Some(AdxResult { adx, plus_di, minus_di })
+}
+
+/// Calculate Average Directional Index (ADX) with +DI/-DI
+pub fn calculate_adx(candles: &[Candle], period: usize) -> Option<AdxResult> {
+    let highs: Vec<f64> = candles.iter().map(|c| c.high).collect();
+    let lows: Vec<f64> = candles.iter().map(|c| c.low).collect();
+    let closes: Vec<f64> = candles.iter().map(|c| c.close).collect();
+
+    calculate_adx_from_series(&highs, &lows, &closes, period)
+}
+
+/// Anchor point for VWAP accumulation
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum VwapAnchor {
+    /// Reset at the most recent session open (UTC hour of day, 0 = midnight)
+    Session { start_hour_utc: u32 },
+
+    /// Accumulate from the first candle at or after a timestamp
+    Timestamp(DateTime<Utc>),
+
+    /// Accumulate from a candle index
+    Index(usize),
+}
+
+/// Anchored VWAP value with standard-deviation bands
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AnchoredVwap {
+    /// Volume-weighted average price since the anchor
+    pub vwap: f64,
+
+    /// Volume-weighted standard deviation of typical price around the VWAP
+    pub std_dev: f64,
+
+    /// Total volume since the anchor
+    pub cumulative_volume: f64,
+
+    /// Index of the anchor candle
+    pub anchor_index: usize,
+}
+
+impl AnchoredVwap {
+    /// Upper band at `multiplier` standard deviations
+    pub fn upper_band(&self, multiplier: f64) -> f64 {
+        self.vwap + multiplier * self.std_dev
+    }
+
+    /// Lower band at `multiplier` standard deviations
+    pub fn lower_band(&self, multiplier: f64) -> f64 {
+        self.vwap - multiplier * self.std_dev
+    }
+}
+
+/// Resolve the candle index an anchor refers to
+fn resolve_vwap_anchor(candles: &[Candle], anchor: VwapAnchor) -> Option<usize> {
+    match anchor {
+        VwapAnchor::Index(index) => (index < candles.len()).then_some(index),
+        VwapAnchor::Timestamp(timestamp) => candles.iter().position(|c| c.timestamp >= timestamp),
+        VwapAnchor::Session { start_hour_utc } => {
+            let last = candles.last()?.timestamp;
+            let mut session_start = last.date_naive().and_hms_opt(start_hour_utc % 24, 0, 0)?.and_utc();
+            if session_start > last {
+                session_start -= Duration::days(1);
+            }
+            candles.iter().position(|c| c.timestamp >= session_start)
+        }
+    }
+}
+
+/// Calculate anchored VWAP using typical price ((high + low + close) / 3) and candle volume
+///
+/// Returns `None` when the anchor cannot be resolved or there is no volume since it.
+pub fn calculate_anchored_vwap(candles: &[Candle], anchor: VwapAnchor) -> Option<AnchoredVwap> {
+    let anchor_index = resolve_vwap_anchor(candles, anchor)?;
+
+    let mut cumulative_volume = 0.0;
+    let mut cumulative_pv = 0.0;
+    let mut cumulative_p2v = 0.0;
+
+    for candle in &candles[anchor_index..] {
+        let typical_price = (candle.high + candle.low + candle.close) / 3.0;
+        cumulative_volume += candle.volume;
+        cumulative_pv += typical_price * candle.volume;
+        cumulative_p2v += typical_price * typical_price * candle.volume;
+    }
+
+    if cumulative_volume <= 0.0 {
+        return None;
+    }
+
+    let vwap = cumulative_pv / cumulative_volume;
+    let variance = (cumulative_p2v / cumulative_volume - vwap * vwap).max(0.0);
+
+    Some(AnchoredVwap {
+        vwap,
+        std_dev: variance.sqrt(),
+        cumulative_volume,
+        anchor_index,
+    })
+}
+
+/// Calculate VWAP for the current UTC-day session
+pub fn calculate_session_vwap(candles: &[Candle]) -> Option<AnchoredVwap> {
+    calculate_anchored_vwap(candles, VwapAnchor::Session { start_hour_utc: 0 })
+}
+
+/// SuperTrend value for a single candle
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SuperTrendValue {
+    /// SuperTrend line (trailing stop level)
+    pub value: f64,
+
+    /// Whether the trend is up (price above the SuperTrend line)
+    pub is_uptrend: bool,
+
+    /// Whether the trend flipped on this candle
+    pub flipped: bool,
+
+    /// ATR used for the bands
+    pub atr: f64,
+}
+
+/// Streaming SuperTrend indicator
+///
+/// Maintains Wilder-smoothed ATR and the final bands incrementally, so each live
+/// candle is an O(1) update instead of a recomputation of the whole series.
+#[derive(Debug, Clone)]
+pub struct SuperTrend {
+    /// ATR period
+    period: usize,
+
+    /// ATR multiplier
+    multiplier: f64,
+
+    /// True ranges collected while seeding the ATR
+    seed_true_ranges: Vec<f64>,
+
+    /// Current ATR
+    atr: Option<f64>,
+
+    /// Previous close
+    prev_close: Option<f64>,
+
+    /// Final upper band
+    final_upper: f64,
+
+    /// Final lower band
+    final_lower: f64,
+
+    /// Current trend direction
+    is_uptrend: bool,
+}
+
+impl SuperTrend {
+    /// Create a new SuperTrend (standard: period 10, multiplier 3.0)
+    pub fn new(period: usize, multiplier: f64) -> Self {
+        Self {
+            period: period.max(1),
+            multiplier,
+            seed_true_ranges: Vec::with_capacity(period.max(1)),
+            atr: None,
+            prev_close: None,
+            final_upper: f64::MAX,
+            final_lower: f64::MIN,
+            is_uptrend: true,
+        }
+    }
+
+    /// Feed the next closed candle; returns `None` until the ATR is seeded
+    pub fn update(&mut self, candle: &Candle) -> Option<SuperTrendValue> {
+        let true_range = match self.prev_close {
+            Some(prev_close) => (candle.high - candle.low)
+                .max((candle.high - prev_close).abs())
+                .max((candle.low - prev_close).abs()),
+            None => candle.high - candle.low,
+        };
+
+        let atr = match self.atr {
+            Some(atr) => (atr * (self.period as f64 - 1.0) + true_range) / self.period as f64,
+            None => {
+                self.seed_true_ranges.push(true_range);
+                if self.seed_true_ranges.len() < self.period {
+                    self.prev_close = Some(candle.close);
+                    return None;
+                }
+                self.seed_true_ranges.iter().sum::<f64>() / self.period as f64
+            }
+        };
+        let first_value = self.atr.is_none();
+        self.atr = Some(atr);
+
+        let hl2 = (candle.high + candle.low) / 2.0;
+        let basic_upper = hl2 + self.multiplier * atr;
+        let basic_lower = hl2 - self.multiplier * atr;
+        let prev_close = self.prev_close.unwrap_or(candle.close);
+
+        let final_upper = if first_value || basic_upper < self.final_upper || prev_close > self.final_upper {
+            basic_upper
+        } else {
+            self.final_upper
+        };
+        let final_lower = if first_value || basic_lower > self.final_lower || prev_close < self.final_lower {
+            basic_lower
+        } else {
+            self.final_lower
+        };
+
+        let was_uptrend = self.is_uptrend;
+        if first_value {
+            self.is_uptrend = candle.close >= hl2;
+        } else if self.is_uptrend && candle.close < final_lower {
+            self.is_uptrend = false;
+        } else if !self.is_uptrend && candle.close > final_upper {
+            self.is_uptrend = true;
+        }
+
+        self.final_upper = final_upper;
+        self.final_lower = final_lower;
+        self.prev_close = Some(candle.close);
+
+        Some(SuperTrendValue {
+            value: if self.is_uptrend { final_lower } else { final_upper },
+            is_uptrend: self.is_uptrend,
+            flipped: !first_value && was_uptrend != self.is_uptrend,
+            atr,
+        })
+    }
+
+    /// Whether the indicator has enough data to produce values
+    pub fn is_ready(&self) -> bool {
+        self.atr.is_some()
+    }
+}
+
+/// Calculate the SuperTrend series for a batch of candles
+pub fn calculate_supertrend(candles: &[Candle], period: usize, multiplier: f64) -> Vec<Option<SuperTrendValue>> {
+    let mut supertrend = SuperTrend::new(period, multiplier);
+    candles.iter().map(|c| supertrend.update(c)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Rising fixture: candle `i` has low `i`, high `i + 1` and close `i + 0.5`
+    fn rising_candles(count: usize) -> Vec<Candle> {
+        (0..count)
+            .map(|i| {
+                let base = i as f64;
+                Candle {
+                    timestamp: Utc::now(),
+                    open: base + 0.25,
+                    high: base + 1.0,
+                    low: base,
+                    close: base + 0.5,
+                    volume: 1000.0,
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_ichimoku_rising_fixture() {
+        let n = 100.0;
+        let candles = rising_candles(100);
+        let cloud = calculate_ichimoku_default(&candles).unwrap();
+
+        assert_eq!(cloud.tenkan_sen, n - 4.5);
+        assert_eq!(cloud.kijun_sen, n - 13.0);
+        assert_eq!(cloud.leading_span_a, n - 8.75);
+        assert_eq!(cloud.leading_span_b, n - 26.0);
+        assert_eq!(cloud.senkou_span_a, n - 34.75);
+        assert_eq!(cloud.senkou_span_b, n - 52.0);
+        assert_eq!(cloud.chikou_span, n - 0.5);
+        assert_eq!(cloud.chikou_reference, n - 26.5);
+        assert!(cloud.is_bullish_cloud());
+        assert!(cloud.chikou_span > cloud.cloud_top());
+    }
+
+    #[test]
+    fn test_ichimoku_requires_displaced_history() {
+        assert!(calculate_ichimoku_default(&rising_candles(77)).is_none());
+        assert!(calculate_ichimoku_default(&rising_candles(78)).is_some());
+    }
+
+    #[test]
+    fn test_adx_strong_uptrend() {
+        let result = calculate_adx(&rising_candles(60), 14).unwrap();
+
+        // Every bar makes a higher high and higher low, so there is no negative movement
+        assert_eq!(result.minus_di, 0.0);
+        assert!(result.plus_di > 0.0);
+        assert!((result.adx - 100.0).abs() < 1e-9);
+        assert!(result.is_trending(25.0));
+        assert!(result.is_bullish());
+    }
+
+    #[test]
+    fn test_adx_requires_two_periods() {
+        assert!(calculate_adx(&rising_candles(27), 14).is_none());
+        assert!(calculate_adx(&rising_candles(28), 14).is_some());
+    }
+
+    #[test]
+    fn test_anchored_vwap_index_anchor() {
+        let mut candles = rising_candles(10);
+        for candle in candles.iter_mut() {
+            candle.volume = 0.0;
+        }
+        candles[8].volume = 1.0;
+        candles[9].volume = 3.0;
+
+        // Typical prices: candle 8 = 8.5, candle 9 = 9.5
+        let vwap = calculate_anchored_vwap(&candles, VwapAnchor::Index(5)).unwrap();
+        assert!((vwap.vwap - 9.25).abs() < 1e-9);
+        assert_eq!(vwap.cumulative_volume, 4.0);
+        assert_eq!(vwap.anchor_index, 5);
+
+        assert!(calculate_anchored_vwap(&candles, VwapAnchor::Index(2)).is_some());
+        assert!(calculate_anchored_vwap(&candles, VwapAnchor::Index(10)).is_none());
+    }
+
+    #[test]
+    fn test_supertrend_streaming_matches_batch() {
+        let mut candles = rising_candles(40);
+        // Sharp reversal at the end should flip the trend down
+        for (i, candle) in candles.iter_mut().enumerate().skip(30) {
+            let base = 29.0 - 2.0 * (i - 29) as f64;
+            candle.high = base + 1.0;
+            candle.low = base;
+            candle.close = base + 0.1;
+        }
+
+        let batch = calculate_supertrend(&candles, 10, 3.0);
+        assert!(batch[8].is_none());
+        assert!(batch[9].unwrap().is_uptrend);
+        assert!(!batch.last().unwrap().unwrap().is_uptrend);
+        assert_eq!(batch.iter().flatten().filter(|v| v.flipped).count(), 1);
+
+        let mut streaming = SuperTrend::new(10, 3.0);
+        for (candle, expected) in candles.iter().zip(batch.iter()) {
+            assert_eq!(streaming.update(candle), *expected);
+        }
+    }
+}
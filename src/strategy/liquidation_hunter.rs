@@ -0,0 +1,568 @@
+//! Liquidation Hunter Strategy
+//!
+//! This module trades liquidation cascades. Liquidation events and open-interest
+//! snapshots are fed in as they arrive; when forced liquidations cluster around a
+//! price level while open interest drops, the strategy either fades the cascade
+//! (betting on exhaustion) or rides it (betting on continuation). Stops are tight
+//! ATR multiples and every setup is pre-approved by the `ZeroLossEnforcer`.
+
+use std::collections::{HashMap, VecDeque};
+use anyhow::Result;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use tracing::{debug, info};
+
+use crate::agents::agent_coordinator::DecisionType;
+use crate::agents::zero_loss_enforcer::ZeroLossEnforcer;
+use crate::engine::message_bus::TradeDirection;
+use crate::strategy::simple_strategy::Candle;
+use crate::strategy::strategy_trait::{Strategy, StrategySignal};
+
+/// Maximum number of liquidation events kept per symbol
+const MAX_EVENTS_PER_SYMBOL: usize = 5000;
+
+/// Maximum number of open-interest snapshots kept per symbol
+const MAX_OI_SNAPSHOTS_PER_SYMBOL: usize = 1000;
+
+/// Side of the positions that were liquidated
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LiquidationSide {
+    /// Long positions liquidated (forced selling)
+    Long,
+
+    /// Short positions liquidated (forced buying)
+    Short,
+}
+
+/// Single forced liquidation from the exchange feed
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LiquidationEvent {
+    /// Symbol
+    pub symbol: String,
+
+    /// Side of the liquidated position
+    pub side: LiquidationSide,
+
+    /// Liquidation price
+    pub price: f64,
+
+    /// Liquidated value in quote currency
+    pub value: f64,
+
+    /// Event time
+    pub timestamp: DateTime<Utc>,
+}
+
+/// Open-interest snapshot
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OpenInterestSnapshot {
+    /// Symbol
+    pub symbol: String,
+
+    /// Open interest in quote currency
+    pub open_interest: f64,
+
+    /// Snapshot time
+    pub timestamp: DateTime<Utc>,
+}
+
+/// How to trade a detected cascade
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CascadeMode {
+    /// Trade against the cascade once it is exhausted
+    Fade,
+
+    /// Trade in the direction of the cascade
+    Ride,
+
+    /// Fade exhausted cascades, ride accelerating ones
+    Adaptive,
+}
+
+/// Liquidation hunter configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LiquidationHunterConfig {
+    /// Fade, ride or choose adaptively
+    pub mode: CascadeMode,
+
+    /// Window over which liquidations are aggregated (seconds)
+    pub window_seconds: i64,
+
+    /// Minimum liquidated value in the window to count as a cascade
+    pub min_cascade_value: f64,
+
+    /// Minimum share (0.0-1.0) of the liquidated value on the dominant side
+    pub min_side_imbalance: f64,
+
+    /// Width of a price cluster in % of price
+    pub cluster_width_percent: f64,
+
+    /// Minimum open-interest drop over the window in %
+    pub min_oi_drop_percent: f64,
+
+    /// Open-interest drop in % above which an adaptive cascade is considered exhausted
+    pub exhaustion_oi_drop_percent: f64,
+
+    /// ATR period
+    pub atr_period: usize,
+
+    /// Stop distance in ATRs
+    pub stop_atr_multiplier: f64,
+
+    /// Take profit distance in ATRs
+    pub take_profit_atr_multiplier: f64,
+
+    /// Position value (quote currency) used for the zero-loss pre-approval
+    pub position_value: f64,
+}
+
+impl Default for LiquidationHunterConfig {
+    fn default() -> Self {
+        Self {
+            mode: CascadeMode::Adaptive,
+            window_seconds: 300,
+            min_cascade_value: 1_000_000.0,
+            min_side_imbalance: 0.7,
+            cluster_width_percent: 0.25,
+            min_oi_drop_percent: 1.0,
+            exhaustion_oi_drop_percent: 3.0,
+            atr_period: 14,
+            stop_atr_multiplier: 0.5,
+            take_profit_atr_multiplier: 5.0,
+            position_value: 12.0,
+        }
+    }
+}
+
+/// Liquidations grouped around a price level
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LiquidationCluster {
+    /// Value-weighted price level
+    pub price_level: f64,
+
+    /// Liquidated value in the cluster
+    pub value: f64,
+
+    /// Number of events in the cluster
+    pub event_count: usize,
+}
+
+/// Detected liquidation cascade
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LiquidationCascade {
+    /// Symbol
+    pub symbol: String,
+
+    /// Side that dominated the liquidations
+    pub dominant_side: LiquidationSide,
+
+    /// Total liquidated value in the window
+    pub total_value: f64,
+
+    /// Share (0.0-1.0) of the value on the dominant side
+    pub imbalance: f64,
+
+    /// Price clusters, largest first
+    pub clusters: Vec<LiquidationCluster>,
+
+    /// Open-interest change over the window in %
+    pub oi_change_percent: f64,
+
+    /// Whether liquidations slowed down in the second half of the window
+    pub decelerating: bool,
+
+    /// First event in the window
+    pub start: DateTime<Utc>,
+
+    /// Last event in the window
+    pub end: DateTime<Utc>,
+}
+
+/// Liquidation-cluster hunting strategy
+pub struct LiquidationHunter {
+    /// Configuration
+    config: LiquidationHunterConfig,
+
+    /// Recent liquidations by symbol
+    liquidations: HashMap<String, VecDeque<LiquidationEvent>>,
+
+    /// Recent open-interest snapshots by symbol
+    open_interest: HashMap<String, VecDeque<OpenInterestSnapshot>>,
+
+    /// Zero-loss enforcer used to pre-approve setups
+    zero_loss_enforcer: ZeroLossEnforcer,
+
+    /// End of the last cascade a signal was produced for, by symbol
+    last_signaled: HashMap<String, DateTime<Utc>>,
+}
+
+impl LiquidationHunter {
+    /// Create a new liquidation hunter with a default zero-loss enforcer
+    pub fn new(config: LiquidationHunterConfig) -> Self {
+        Self::with_enforcer(config, ZeroLossEnforcer::new())
+    }
+
+    /// Create a new liquidation hunter with a configured zero-loss enforcer
+    pub fn with_enforcer(config: LiquidationHunterConfig, zero_loss_enforcer: ZeroLossEnforcer) -> Self {
+        Self {
+            config,
+            liquidations: HashMap::new(),
+            open_interest: HashMap::new(),
+            zero_loss_enforcer,
+            last_signaled: HashMap::new(),
+        }
+    }
+
+    /// Record a liquidation event from the feed
+    pub fn record_liquidation(&mut self, event: LiquidationEvent) {
+        let events = self.liquidations.entry(event.symbol.clone()).or_insert_with(VecDeque::new);
+        events.push_back(event);
+        while events.len() > MAX_EVENTS_PER_SYMBOL {
+            events.pop_front();
+        }
+    }
+
+    /// Record an open-interest snapshot
+    pub fn record_open_interest(&mut self, snapshot: OpenInterestSnapshot) {
+        let snapshots = self.open_interest.entry(snapshot.symbol.clone()).or_insert_with(VecDeque::new);
+        snapshots.push_back(snapshot);
+        while snapshots.len() > MAX_OI_SNAPSHOTS_PER_SYMBOL {
+            snapshots.pop_front();
+        }
+    }
+
+    /// Detect a cascade in the window ending at the latest liquidation for a symbol
+    pub fn detect_cascade(&self, symbol: &str) -> Option<LiquidationCascade> {
+        let events = self.liquidations.get(symbol)?;
+        let end = events.back()?.timestamp;
+        let start = end - Duration::seconds(self.config.window_seconds);
+
+        let window: Vec<&LiquidationEvent> = events.iter()
+            .filter(|e| e.timestamp >= start)
+            .collect();
+
+        let total_value: f64 = window.iter().map(|e| e.value).sum();
+        if total_value < self.config.min_cascade_value {
+            return None;
+        }
+
+        let long_value: f64 = window.iter()
+            .filter(|e| e.side == LiquidationSide::Long)
+            .map(|e| e.value)
+            .sum();
+        let (dominant_side, imbalance) = if long_value >= total_value - long_value {
+            (LiquidationSide::Long, long_value / total_value)
+        } else {
+            (LiquidationSide::Short, 1.0 - long_value / total_value)
+        };
+
+        if imbalance < self.config.min_side_imbalance {
+            debug!("{}: liquidations too balanced ({:.2})", symbol, imbalance);
+            return None;
+        }
+
+        let oi_change_percent = self.open_interest_change(symbol, start)?;
+        if -oi_change_percent < self.config.min_oi_drop_percent {
+            debug!("{}: open interest change {:.2}% does not confirm cascade", symbol, oi_change_percent);
+            return None;
+        }
+
+        let first = window.first().map(|e| e.timestamp).unwrap_or(start);
+        let midpoint = first + (end - first) / 2;
+        let first_half: f64 = window.iter().filter(|e| e.timestamp < midpoint).map(|e| e.value).sum();
+        let second_half = total_value - first_half;
+
+        Some(LiquidationCascade {
+            symbol: symbol.to_string(),
+            dominant_side,
+            total_value,
+            imbalance,
+            clusters: self.cluster_events(&window),
+            oi_change_percent,
+            decelerating: second_half < first_half,
+            start: first,
+            end,
+        })
+    }
+
+    /// Open-interest change in % from the snapshot preceding `start` to the latest one
+    fn open_interest_change(&self, symbol: &str, start: DateTime<Utc>) -> Option<f64> {
+        let snapshots = self.open_interest.get(symbol)?;
+
+        let before = snapshots.iter().rev().find(|s| s.timestamp <= start)
+            .or_else(|| snapshots.front())?;
+        let after = snapshots.back()?;
+
+        if before.open_interest <= 0.0 || before.timestamp >= after.timestamp {
+            return None;
+        }
+
+        Some((after.open_interest - before.open_interest) / before.open_interest * 100.0)
+    }
+
+    /// Group events into price clusters, largest first
+    fn cluster_events(&self, events: &[&LiquidationEvent]) -> Vec<LiquidationCluster> {
+        let mut sorted: Vec<&LiquidationEvent> = events.to_vec();
+        sorted.sort_by(|a, b| a.price.partial_cmp(&b.price).unwrap_or(std::cmp::Ordering::Equal));
+
+        let mut clusters: Vec<LiquidationCluster> = Vec::new();
+        let mut weighted_sum = 0.0;
+
+        for event in sorted {
+            let joins_last = clusters.last().map_or(false, |c| {
+                (event.price - c.price_level).abs() / c.price_level * 100.0 <= self.config.cluster_width_percent
+            });
+
+            if joins_last {
+                let cluster = clusters.last_mut().expect("cluster exists");
+                weighted_sum += event.price * event.value;
+                cluster.value += event.value;
+                cluster.event_count += 1;
+                cluster.price_level = weighted_sum / cluster.value;
+            } else {
+                weighted_sum = event.price * event.value;
+                clusters.push(LiquidationCluster {
+                    price_level: event.price,
+                    value: event.value,
+                    event_count: 1,
+                });
+            }
+        }
+
+        clusters.sort_by(|a, b| b.value.partial_cmp(&a.value).unwrap_or(std::cmp::Ordering::Equal));
+        clusters
+    }
+
+    /// Whether a cascade should be faded rather than ridden
+    fn should_fade(&self, cascade: &LiquidationCascade) -> bool {
+        match self.config.mode {
+            CascadeMode::Fade => true,
+            CascadeMode::Ride => false,
+            CascadeMode::Adaptive => {
+                cascade.decelerating && -cascade.oi_change_percent >= self.config.exhaustion_oi_drop_percent
+            }
+        }
+    }
+
+    /// Estimated win probability (0-100) for a cascade trade
+    fn win_probability(&self, cascade: &LiquidationCascade, fade: bool) -> f64 {
+        let size_factor = (cascade.total_value / self.config.min_cascade_value).ln().max(0.0) * 5.0;
+        let imbalance_factor = (cascade.imbalance - 0.5) * 40.0;
+        let oi_factor = (-cascade.oi_change_percent).min(10.0) * 2.0;
+        let timing_factor = if fade == cascade.decelerating { 5.0 } else { -5.0 };
+
+        (50.0 + size_factor + imbalance_factor + oi_factor + timing_factor).clamp(0.0, 95.0)
+    }
+
+    /// Get the zero-loss enforcer used for pre-approval
+    pub fn get_zero_loss_enforcer(&self) -> &ZeroLossEnforcer {
+        &self.zero_loss_enforcer
+    }
+
+    /// Get configuration
+    pub fn get_config(&self) -> &LiquidationHunterConfig {
+        &self.config
+    }
+}
+
+impl Strategy for LiquidationHunter {
+    fn get_name(&self) -> String {
+        "liquidation_hunter".to_string()
+    }
+
+    fn analyze(&mut self, symbol: &str, candles: &[Candle]) -> Result<StrategySignal> {
+        let name = self.get_name();
+        let price = candles.last().map(|c| c.close)
+            .ok_or_else(|| anyhow::anyhow!("No candles for {}", symbol))?;
+
+        let cascade = match self.detect_cascade(symbol) {
+            Some(cascade) => cascade,
+            None => return Ok(StrategySignal::hold(&name, symbol, price, "No liquidation cascade")),
+        };
+
+        if self.last_signaled.get(symbol).map_or(false, |last| *last >= cascade.end) {
+            return Ok(StrategySignal::hold(&name, symbol, price, "Cascade already traded"));
+        }
+
+        let atr = average_true_range(candles, self.config.atr_period);
+        if atr <= 0.0 {
+            return Ok(StrategySignal::hold(&name, symbol, price, "Insufficient data for ATR"));
+        }
+
+        // Long liquidations are forced selling: ride short, fade long
+        let fade = self.should_fade(&cascade);
+        let go_long = match cascade.dominant_side {
+            LiquidationSide::Long => fade,
+            LiquidationSide::Short => !fade,
+        };
+
+        let (direction, decision_type, stop_loss, take_profit) = if go_long {
+            (TradeDirection::Long, DecisionType::EnterLong,
+             price - atr * self.config.stop_atr_multiplier,
+             price + atr * self.config.take_profit_atr_multiplier)
+        } else {
+            (TradeDirection::Short, DecisionType::EnterShort,
+             price + atr * self.config.stop_atr_multiplier,
+             price - atr * self.config.take_profit_atr_multiplier)
+        };
+
+        let win_probability = self.win_probability(&cascade, fade);
+        let assessment = self.zero_loss_enforcer.pre_approve_setup(
+            symbol,
+            direction,
+            price,
+            stop_loss,
+            take_profit,
+            self.config.position_value,
+            win_probability,
+        )?;
+
+        let description = format!(
+            "{} {:?} liquidation cascade of {:.0} ({:.0}% one-sided, OI {:+.2}%, top cluster {:.4})",
+            if fade { "Fading" } else { "Riding" },
+            cascade.dominant_side,
+            cascade.total_value,
+            cascade.imbalance * 100.0,
+            cascade.oi_change_percent,
+            cascade.clusters.first().map_or(price, |c| c.price_level),
+        );
+
+        if !assessment.approved {
+            debug!("{}: {} rejected by zero-loss enforcer: {}", symbol, description, assessment.reasoning);
+            return Ok(StrategySignal::hold(
+                &name,
+                symbol,
+                price,
+                &format!("{}; {}", description, assessment.reasoning),
+            ));
+        }
+
+        info!("{}: {}", symbol, description);
+        self.last_signaled.insert(symbol.to_string(), cascade.end);
+
+        Ok(StrategySignal {
+            strategy: name,
+            symbol: symbol.to_string(),
+            timestamp: Utc::now(),
+            decision_type,
+            confidence: win_probability,
+            entry_price: price,
+            stop_loss: Some(stop_loss),
+            take_profit: Some(take_profit),
+            reasoning: format!("{}; {}", description, assessment.reasoning),
+        })
+    }
+
+    fn min_candles(&self) -> usize {
+        self.config.atr_period + 1
+    }
+}
+
+/// Average true range over the last `period` candles
+fn average_true_range(candles: &[Candle], period: usize) -> f64 {
+    if period == 0 || candles.len() < period + 1 {
+        return 0.0;
+    }
+
+    let start = candles.len() - period;
+    let sum: f64 = (start..candles.len())
+        .map(|i| {
+            let prev_close = candles[i - 1].close;
+            (candles[i].high - candles[i].low)
+                .max((candles[i].high - prev_close).abs())
+                .max((candles[i].low - prev_close).abs())
+        })
+        .sum();
+
+    sum / period as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn flat_candles(count: usize, price: f64) -> Vec<Candle> {
+        (0..count)
+            .map(|i| Candle {
+                open_time: i as i64 * 60,
+                open: price,
+                high: price + 1.0,
+                low: price - 1.0,
+                close: price,
+                volume: 100.0,
+            })
+            .collect()
+    }
+
+    fn feed_long_cascade(hunter: &mut LiquidationHunter, decelerating: bool) {
+        let start = Utc::now() - Duration::seconds(600);
+
+        hunter.record_open_interest(OpenInterestSnapshot {
+            symbol: "BTCUSDT".to_string(),
+            open_interest: 100_000_000.0,
+            timestamp: start,
+        });
+
+        for i in 0..10 {
+            let value = if (i < 5) == decelerating { 300_000.0 } else { 50_000.0 };
+            hunter.record_liquidation(LiquidationEvent {
+                symbol: "BTCUSDT".to_string(),
+                side: LiquidationSide::Long,
+                price: 100.0 - i as f64 * 0.01,
+                value,
+                timestamp: start + Duration::seconds(320 + i * 25),
+            });
+        }
+
+        hunter.record_open_interest(OpenInterestSnapshot {
+            symbol: "BTCUSDT".to_string(),
+            open_interest: 95_000_000.0,
+            timestamp: start + Duration::seconds(600),
+        });
+    }
+
+    fn permissive_enforcer() -> ZeroLossEnforcer {
+        let mut enforcer = ZeroLossEnforcer::new();
+        enforcer.set_min_win_probability(50.0);
+        enforcer.set_min_risk_reward_ratio(2.0);
+        enforcer.set_min_expected_value(0.0);
+        enforcer
+    }
+
+    #[test]
+    fn test_detects_and_fades_exhausted_cascade() {
+        let mut hunter = LiquidationHunter::with_enforcer(LiquidationHunterConfig::default(), permissive_enforcer());
+        feed_long_cascade(&mut hunter, true);
+
+        let cascade = hunter.detect_cascade("BTCUSDT").unwrap();
+        assert_eq!(cascade.dominant_side, LiquidationSide::Long);
+        assert!(cascade.decelerating);
+        assert_eq!(cascade.clusters.len(), 1);
+
+        let signal = hunter.analyze("BTCUSDT", &flat_candles(30, 100.0)).unwrap();
+        assert!(matches!(signal.decision_type, DecisionType::EnterLong));
+        assert!((signal.stop_loss.unwrap() - 99.0).abs() < 1e-9);
+
+        // The same cascade is not traded twice
+        let again = hunter.analyze("BTCUSDT", &flat_candles(30, 100.0)).unwrap();
+        assert!(matches!(again.decision_type, DecisionType::Hold));
+    }
+
+    #[test]
+    fn test_rides_accelerating_cascade_and_respects_enforcer() {
+        let mut hunter = LiquidationHunter::with_enforcer(LiquidationHunterConfig::default(), permissive_enforcer());
+        feed_long_cascade(&mut hunter, false);
+
+        let signal = hunter.analyze("BTCUSDT", &flat_candles(30, 100.0)).unwrap();
+        assert!(matches!(signal.decision_type, DecisionType::EnterShort));
+
+        let config = LiquidationHunterConfig {
+            position_value: 5.0,
+            ..LiquidationHunterConfig::default()
+        };
+        let mut strict = LiquidationHunter::new(config);
+        feed_long_cascade(&mut strict, false);
+        let rejected = strict.analyze("BTCUSDT", &flat_candles(30, 100.0)).unwrap();
+        assert!(matches!(rejected.decision_type, DecisionType::Hold));
+    }
+}
@@ -6,3 +6,13 @@ pub mod simple_strategy;
 pub mod indicators;
 pub mod advanced_strategy;
 pub mod advanced_multi_factor_strategy;
+pub mod dca;
+pub mod optimizer;
+pub mod grid_search;
+pub mod strategy_trait;
+pub mod ensemble;
+pub mod patterns;
+pub mod registry;
+pub mod liquidation_hunter;
+pub mod regime;
+pub mod session_filter;
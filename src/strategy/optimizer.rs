@@ -0,0 +1,472 @@
+//! Strategy Parameter Optimizer
+//!
+//! This module provides a genetic-algorithm optimizer that evolves strategy parameter
+//! sets, evaluates them through the `BacktestEngine` in parallel, and reports the
+//! Pareto-optimal configurations across return, drawdown and risk-adjusted return.
+
+use std::collections::{BTreeMap, HashMap};
+use anyhow::Result;
+use chrono::Utc;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use tracing::{debug, info, warn};
+
+use crate::agents::feedback_loop::MutationRecord;
+use crate::backtest::BacktestResult;
+
+/// Strategy parameter set
+pub type ParameterSet = HashMap<String, f64>;
+
+/// Search range for a single parameter
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ParameterRange {
+    /// Minimum value
+    pub min: f64,
+
+    /// Maximum value
+    pub max: f64,
+
+    /// Optional step; values are snapped to `min + k * step`
+    pub step: Option<f64>,
+}
+
+impl ParameterRange {
+    /// Create a continuous range
+    pub fn new(min: f64, max: f64) -> Self {
+        Self { min, max, step: None }
+    }
+
+    /// Create a stepped range
+    pub fn stepped(min: f64, max: f64, step: f64) -> Self {
+        Self { min, max, step: Some(step) }
+    }
+
+    /// Clamp and snap a value into the range
+    pub fn normalize(&self, value: f64) -> f64 {
+        let clamped = value.max(self.min).min(self.max);
+        match self.step {
+            Some(step) if step > 0.0 => {
+                let snapped = self.min + ((clamped - self.min) / step).round() * step;
+                snapped.min(self.max)
+            }
+            _ => clamped,
+        }
+    }
+
+    /// Sample a random value from the range
+    fn sample<R: Rng>(&self, rng: &mut R) -> f64 {
+        if self.max <= self.min {
+            return self.min;
+        }
+        self.normalize(rng.gen_range(self.min..=self.max))
+    }
+}
+
+/// Genetic algorithm configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OptimizerConfig {
+    /// Population size per generation
+    pub population_size: usize,
+
+    /// Number of generations
+    pub generations: usize,
+
+    /// Probability that a parameter is mutated (0.0 to 1.0)
+    pub mutation_rate: f64,
+
+    /// Mutation strength as a fraction of the parameter range
+    pub mutation_strength: f64,
+
+    /// Probability that two parents are crossed over (0.0 to 1.0)
+    pub crossover_rate: f64,
+
+    /// Number of best individuals carried over unchanged
+    pub elitism: usize,
+
+    /// Tournament size for parent selection
+    pub tournament_size: usize,
+
+    /// Optional RNG seed for reproducible runs
+    pub seed: Option<u64>,
+}
+
+impl Default for OptimizerConfig {
+    fn default() -> Self {
+        Self {
+            population_size: 40,
+            generations: 20,
+            mutation_rate: 0.2,
+            mutation_strength: 0.1,
+            crossover_rate: 0.7,
+            elitism: 2,
+            tournament_size: 3,
+            seed: None,
+        }
+    }
+}
+
+/// Objective values for a single evaluated parameter set
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FitnessScore {
+    /// Total return in % (maximize)
+    pub total_return: f64,
+
+    /// Maximum drawdown in % (minimize)
+    pub max_drawdown: f64,
+
+    /// Sharpe ratio (maximize)
+    pub sharpe_ratio: f64,
+
+    /// Win rate in %
+    pub win_rate: f64,
+
+    /// Number of trades
+    pub total_trades: u32,
+}
+
+impl FitnessScore {
+    /// Build a score from a backtest result
+    pub fn from_result(result: &BacktestResult) -> Self {
+        Self {
+            total_return: result.total_return,
+            max_drawdown: result.max_drawdown,
+            sharpe_ratio: result.sharpe_ratio,
+            win_rate: result.win_rate,
+            total_trades: result.total_trades,
+        }
+    }
+
+    /// Check whether this score Pareto-dominates another
+    pub fn dominates(&self, other: &FitnessScore) -> bool {
+        let no_worse = self.total_return >= other.total_return
+            && self.max_drawdown <= other.max_drawdown
+            && self.sharpe_ratio >= other.sharpe_ratio;
+        let better = self.total_return > other.total_return
+            || self.max_drawdown < other.max_drawdown
+            || self.sharpe_ratio > other.sharpe_ratio;
+
+        no_worse && better
+    }
+
+    /// Scalar fitness used for tournament selection
+    pub(crate) fn scalar(&self) -> f64 {
+        self.total_return - 0.5 * self.max_drawdown + 10.0 * self.sharpe_ratio
+    }
+}
+
+/// Evaluated individual
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Candidate {
+    /// Parameter set
+    pub parameters: ParameterSet,
+
+    /// Fitness
+    pub fitness: FitnessScore,
+
+    /// Generation the candidate was produced in
+    pub generation: usize,
+}
+
+impl Candidate {
+    /// Convert the candidate at `index` in the Pareto front into a `MutationRecord` for the `FeedbackLoop`
+    pub fn to_mutation_record(&self, parent_agent: &str, index: usize, performance_before: f64) -> MutationRecord {
+        let timestamp = Utc::now();
+
+        MutationRecord {
+            id: format!("ga-{}-{}-{}-{}", parent_agent, self.generation, index, timestamp.timestamp_millis()),
+            parent_agent: parent_agent.to_string(),
+            mutated_agent: format!("{}-ga-g{}-{}", parent_agent, self.generation, index),
+            timestamp,
+            parameters: self.parameters.clone(),
+            description: format!(
+                "GA Pareto candidate: return {:.2}%, drawdown {:.2}%, sharpe {:.2}",
+                self.fitness.total_return, self.fitness.max_drawdown, self.fitness.sharpe_ratio
+            ),
+            performance_before,
+            performance_after: Some(self.fitness.total_return),
+            success: Some(self.fitness.total_return > performance_before),
+        }
+    }
+}
+
+/// Optimization report
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OptimizationReport {
+    /// Pareto-optimal candidates across all generations
+    pub pareto_front: Vec<Candidate>,
+
+    /// Best scalar fitness per generation
+    pub generation_best: Vec<f64>,
+
+    /// Total number of evaluations performed
+    pub evaluations: usize,
+
+    /// Number of evaluations that failed
+    pub failed_evaluations: usize,
+}
+
+impl OptimizationReport {
+    /// Convert the Pareto front into mutation records
+    pub fn to_mutation_records(&self, parent_agent: &str, performance_before: f64) -> Vec<MutationRecord> {
+        self.pareto_front.iter()
+            .enumerate()
+            .map(|(index, c)| c.to_mutation_record(parent_agent, index, performance_before))
+            .collect()
+    }
+}
+
+/// Genetic-algorithm strategy parameter optimizer
+pub struct GeneticOptimizer {
+    /// Configuration
+    config: OptimizerConfig,
+
+    /// Parameter search space, ordered so seeded runs draw in a stable order
+    space: BTreeMap<String, ParameterRange>,
+
+    /// Random number generator
+    rng: StdRng,
+}
+
+impl GeneticOptimizer {
+    /// Create a new optimizer
+    pub fn new(config: OptimizerConfig, space: HashMap<String, ParameterRange>) -> Result<Self> {
+        if space.is_empty() {
+            return Err(anyhow::anyhow!("Parameter space must not be empty"));
+        }
+
+        if config.population_size < 2 {
+            return Err(anyhow::anyhow!("Population size must be at least 2"));
+        }
+
+        let rng = match config.seed {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => StdRng::from_entropy(),
+        };
+
+        Ok(Self { config, space: space.into_iter().collect(), rng })
+    }
+
+    /// Run the optimizer
+    ///
+    /// `evaluate` runs a backtest for a parameter set, typically by driving a
+    /// `BacktestEngine` with the strategy configured from the parameters. It is
+    /// invoked in parallel across the population.
+    pub fn optimize<F>(&mut self, evaluate: F) -> Result<OptimizationReport>
+    where
+        F: Fn(&ParameterSet) -> Result<BacktestResult> + Sync,
+    {
+        let mut population: Vec<ParameterSet> = (0..self.config.population_size)
+            .map(|_| self.random_parameters())
+            .collect();
+
+        let mut archive: Vec<Candidate> = Vec::new();
+        let mut generation_best = Vec::new();
+        let mut evaluations = 0;
+        let mut failed_evaluations = 0;
+
+        for generation in 0..self.config.generations {
+            let results: Vec<(ParameterSet, Result<BacktestResult>)> = population
+                .par_iter()
+                .map(|params| (params.clone(), evaluate(params)))
+                .collect();
+
+            let mut evaluated = Vec::with_capacity(results.len());
+            for (parameters, result) in results {
+                evaluations += 1;
+                match result {
+                    Ok(result) => evaluated.push(Candidate {
+                        parameters,
+                        fitness: FitnessScore::from_result(&result),
+                        generation,
+                    }),
+                    Err(e) => {
+                        failed_evaluations += 1;
+                        warn!("Backtest evaluation failed: {}", e);
+                    }
+                }
+            }
+
+            if evaluated.is_empty() {
+                return Err(anyhow::anyhow!("All evaluations failed in generation {}", generation));
+            }
+
+            evaluated.sort_by(|a, b| b.fitness.scalar().partial_cmp(&a.fitness.scalar()).unwrap_or(std::cmp::Ordering::Equal));
+            generation_best.push(evaluated[0].fitness.scalar());
+            debug!("Generation {} best fitness {:.4}", generation, evaluated[0].fitness.scalar());
+
+            archive.extend(evaluated.iter().cloned());
+            archive = pareto_front(&archive);
+
+            population = self.next_generation(&evaluated);
+        }
+
+        info!("GA optimization complete: {} evaluations, {} Pareto-optimal configurations",
+            evaluations, archive.len());
+
+        Ok(OptimizationReport {
+            pareto_front: archive,
+            generation_best,
+            evaluations,
+            failed_evaluations,
+        })
+    }
+
+    /// Breed the next generation from evaluated candidates sorted by fitness
+    fn next_generation(&mut self, evaluated: &[Candidate]) -> Vec<ParameterSet> {
+        let mut next: Vec<ParameterSet> = evaluated.iter()
+            .take(self.config.elitism.min(evaluated.len()))
+            .map(|c| c.parameters.clone())
+            .collect();
+
+        while next.len() < self.config.population_size {
+            let parent_a = self.tournament_select(evaluated);
+            let parent_b = self.tournament_select(evaluated);
+
+            let mut child = if self.rng.gen::<f64>() < self.config.crossover_rate {
+                self.crossover(&parent_a, &parent_b)
+            } else {
+                parent_a
+            };
+
+            self.mutate(&mut child);
+            next.push(child);
+        }
+
+        next
+    }
+
+    /// Select a parent by tournament
+    fn tournament_select(&mut self, evaluated: &[Candidate]) -> ParameterSet {
+        let mut best: Option<&Candidate> = None;
+
+        for _ in 0..self.config.tournament_size.max(1) {
+            let candidate = &evaluated[self.rng.gen_range(0..evaluated.len())];
+            if best.map_or(true, |b| candidate.fitness.scalar() > b.fitness.scalar()) {
+                best = Some(candidate);
+            }
+        }
+
+        best.map(|c| c.parameters.clone()).unwrap_or_default()
+    }
+
+    /// Uniform crossover
+    fn crossover(&mut self, a: &ParameterSet, b: &ParameterSet) -> ParameterSet {
+        self.space.keys()
+            .map(|name| {
+                let source = if self.rng.gen::<bool>() { a } else { b };
+                let value = source.get(name).or_else(|| a.get(name)).copied().unwrap_or(0.0);
+                (name.clone(), value)
+            })
+            .collect()
+    }
+
+    /// Gaussian-like mutation scaled to each parameter range
+    fn mutate(&mut self, params: &mut ParameterSet) {
+        for (name, range) in &self.space {
+            if self.rng.gen::<f64>() >= self.config.mutation_rate {
+                continue;
+            }
+
+            let span = range.max - range.min;
+            let delta = self.rng.gen_range(-1.0..=1.0) * span * self.config.mutation_strength;
+            let value = params.get(name).copied().unwrap_or(range.min) + delta;
+            params.insert(name.clone(), range.normalize(value));
+        }
+    }
+
+    /// Sample a random parameter set from the space
+    fn random_parameters(&mut self) -> ParameterSet {
+        let mut params = ParameterSet::new();
+        for (name, range) in &self.space {
+            params.insert(name.clone(), range.sample(&mut self.rng));
+        }
+        params
+    }
+}
+
+/// Extract the non-dominated candidates, dropping duplicate parameter sets
+pub fn pareto_front(candidates: &[Candidate]) -> Vec<Candidate> {
+    let mut front: Vec<Candidate> = Vec::new();
+
+    for candidate in candidates {
+        if candidates.iter().any(|other| other.fitness.dominates(&candidate.fitness)) {
+            continue;
+        }
+        if front.iter().any(|c| c.parameters == candidate.parameters) {
+            continue;
+        }
+        front.push(candidate.clone());
+    }
+
+    front
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backtest::BacktestConfig;
+
+    fn space() -> HashMap<String, ParameterRange> {
+        ["fast", "slow", "stop", "target", "leverage"].iter()
+            .enumerate()
+            .map(|(i, name)| (name.to_string(), ParameterRange::stepped(0.0, 10.0 + i as f64, 0.5)))
+            .collect()
+    }
+
+    fn evaluate(params: &ParameterSet) -> Result<BacktestResult> {
+        let config = BacktestConfig::new(0, 30 * 86_400, 1_000.0, vec!["BTCUSDT".to_string()]);
+        let mut result = BacktestResult::new(config, Vec::new());
+        result.total_return = -params.values().map(|v| (v - 4.0).powi(2)).sum::<f64>();
+        Ok(result)
+    }
+
+    #[test]
+    fn test_same_seed_yields_same_best_parameters() {
+        let config = OptimizerConfig {
+            population_size: 12,
+            generations: 5,
+            seed: Some(42),
+            ..OptimizerConfig::default()
+        };
+
+        let first = GeneticOptimizer::new(config.clone(), space()).unwrap().optimize(evaluate).unwrap();
+        let second = GeneticOptimizer::new(config, space()).unwrap().optimize(evaluate).unwrap();
+
+        assert_eq!(first.generation_best, second.generation_best);
+        let best = |report: &OptimizationReport| report.pareto_front.iter()
+            .max_by(|a, b| a.fitness.scalar().partial_cmp(&b.fitness.scalar()).unwrap())
+            .map(|c| c.parameters.clone());
+        assert_eq!(best(&first), best(&second));
+
+        // Every Pareto candidate gets its own mutation record
+        let records = first.to_mutation_records("trend", 0.0);
+        let ids: std::collections::HashSet<&str> = records.iter().map(|r| r.id.as_str()).collect();
+        let agents: std::collections::HashSet<&str> = records.iter().map(|r| r.mutated_agent.as_str()).collect();
+        assert_eq!(ids.len(), records.len());
+        assert_eq!(agents.len(), records.len());
+    }
+
+    #[test]
+    fn test_mutation_respects_parameter_ranges() {
+        let config = OptimizerConfig {
+            mutation_rate: 1.0,
+            mutation_strength: 5.0,
+            seed: Some(7),
+            ..OptimizerConfig::default()
+        };
+        let space = space();
+        let mut optimizer = GeneticOptimizer::new(config, space.clone()).unwrap();
+
+        let mut params = optimizer.random_parameters();
+        for _ in 0..200 {
+            optimizer.mutate(&mut params);
+            for (name, range) in &space {
+                let value = params[name];
+                assert!(value >= range.min && value <= range.max, "{} = {} out of range", name, value);
+                assert_eq!((value / 0.5).fract(), 0.0, "{} = {} off the step", name, value);
+            }
+        }
+    }
+}
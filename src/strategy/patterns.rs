@@ -0,0 +1,590 @@
+//! Candlestick Pattern Recognition
+//!
+//! This module detects classic single, double, triple and five-candle candlestick
+//! patterns and returns typed `PatternType` values with a strength score, so
+//! strategies can weight pattern evidence instead of matching on strings.
+
+use serde::{Deserialize, Serialize};
+
+use crate::exchange::types::Candle;
+
+/// Number of candles used to determine the prior trend
+const TREND_LOOKBACK: usize = 5;
+
+/// Body size (as a fraction of range) at or below which a candle is a doji
+const DOJI_BODY_RATIO: f64 = 0.1;
+
+/// Candlestick pattern type
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum PatternType {
+    // Single-candle patterns
+    Doji,
+    DragonflyDoji,
+    GravestoneDoji,
+    LongLeggedDoji,
+    SpinningTop,
+    Hammer,
+    InvertedHammer,
+    HangingMan,
+    ShootingStar,
+    BullishMarubozu,
+    BearishMarubozu,
+
+    // Two-candle patterns
+    BullishEngulfing,
+    BearishEngulfing,
+    BullishHarami,
+    BearishHarami,
+    BullishHaramiCross,
+    BearishHaramiCross,
+    PiercingLine,
+    DarkCloudCover,
+    TweezerTop,
+    TweezerBottom,
+    BullishKicker,
+    BearishKicker,
+    BullishBeltHold,
+    BearishBeltHold,
+
+    // Three-candle patterns
+    MorningStar,
+    EveningStar,
+    MorningDojiStar,
+    EveningDojiStar,
+    ThreeWhiteSoldiers,
+    ThreeBlackCrows,
+    ThreeInsideUp,
+    ThreeInsideDown,
+    ThreeOutsideUp,
+    ThreeOutsideDown,
+    BullishAbandonedBaby,
+    BearishAbandonedBaby,
+
+    // Five-candle patterns
+    RisingThreeMethods,
+    FallingThreeMethods,
+}
+
+/// Directional bias of a pattern
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PatternBias {
+    Bullish,
+    Bearish,
+    Neutral,
+}
+
+impl PatternType {
+    /// Directional bias of the pattern
+    pub fn bias(&self) -> PatternBias {
+        use PatternType::*;
+        match self {
+            Doji | LongLeggedDoji | SpinningTop => PatternBias::Neutral,
+            DragonflyDoji | Hammer | InvertedHammer | BullishMarubozu | BullishEngulfing
+            | BullishHarami | BullishHaramiCross | PiercingLine | TweezerBottom | BullishKicker
+            | BullishBeltHold | MorningStar | MorningDojiStar | ThreeWhiteSoldiers | ThreeInsideUp
+            | ThreeOutsideUp | BullishAbandonedBaby | RisingThreeMethods => PatternBias::Bullish,
+            GravestoneDoji | HangingMan | ShootingStar | BearishMarubozu | BearishEngulfing
+            | BearishHarami | BearishHaramiCross | DarkCloudCover | TweezerTop | BearishKicker
+            | BearishBeltHold | EveningStar | EveningDojiStar | ThreeBlackCrows | ThreeInsideDown
+            | ThreeOutsideDown | BearishAbandonedBaby | FallingThreeMethods => PatternBias::Bearish,
+        }
+    }
+
+    /// Number of candles that make up the pattern
+    pub fn candle_count(&self) -> usize {
+        use PatternType::*;
+        match self {
+            Doji | DragonflyDoji | GravestoneDoji | LongLeggedDoji | SpinningTop | Hammer
+            | InvertedHammer | HangingMan | ShootingStar | BullishMarubozu | BearishMarubozu => 1,
+            BullishEngulfing | BearishEngulfing | BullishHarami | BearishHarami | BullishHaramiCross
+            | BearishHaramiCross | PiercingLine | DarkCloudCover | TweezerTop | TweezerBottom
+            | BullishKicker | BearishKicker | BullishBeltHold | BearishBeltHold => 2,
+            RisingThreeMethods | FallingThreeMethods => 5,
+            _ => 3,
+        }
+    }
+
+    /// Base reliability of the pattern (0.0 to 1.0)
+    pub fn reliability(&self) -> f64 {
+        use PatternType::*;
+        match self {
+            Doji | SpinningTop | LongLeggedDoji => 0.3,
+            DragonflyDoji | GravestoneDoji | BullishHarami | BearishHarami | TweezerTop
+            | TweezerBottom | BullishBeltHold | BearishBeltHold => 0.45,
+            Hammer | InvertedHammer | HangingMan | ShootingStar | BullishHaramiCross
+            | BearishHaramiCross | BullishMarubozu | BearishMarubozu => 0.5,
+            PiercingLine | DarkCloudCover | ThreeInsideUp | ThreeInsideDown => 0.6,
+            BullishEngulfing | BearishEngulfing | ThreeOutsideUp | ThreeOutsideDown
+            | RisingThreeMethods | FallingThreeMethods => 0.65,
+            MorningStar | EveningStar | MorningDojiStar | EveningDojiStar | ThreeWhiteSoldiers
+            | ThreeBlackCrows => 0.7,
+            BullishKicker | BearishKicker | BullishAbandonedBaby | BearishAbandonedBaby => 0.75,
+        }
+    }
+}
+
+/// Detected pattern occurrence
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DetectedPattern {
+    /// Pattern type
+    pub pattern: PatternType,
+
+    /// Index of the last candle in the pattern
+    pub index: usize,
+
+    /// Strength score (0.0 to 1.0)
+    pub strength: f64,
+
+    /// Directional bias
+    pub bias: PatternBias,
+}
+
+/// Candle geometry helpers
+struct Shape {
+    open: f64,
+    high: f64,
+    low: f64,
+    close: f64,
+    body: f64,
+    range: f64,
+    upper_shadow: f64,
+    lower_shadow: f64,
+}
+
+impl Shape {
+    fn of(candle: &Candle) -> Self {
+        let range = (candle.high - candle.low).max(f64::EPSILON);
+        let body_top = candle.open.max(candle.close);
+        let body_bottom = candle.open.min(candle.close);
+
+        Self {
+            open: candle.open,
+            high: candle.high,
+            low: candle.low,
+            close: candle.close,
+            body: (candle.close - candle.open).abs(),
+            range,
+            upper_shadow: candle.high - body_top,
+            lower_shadow: body_bottom - candle.low,
+        }
+    }
+
+    fn is_bullish(&self) -> bool {
+        self.close > self.open
+    }
+
+    fn is_bearish(&self) -> bool {
+        self.close < self.open
+    }
+
+    fn body_ratio(&self) -> f64 {
+        self.body / self.range
+    }
+
+    fn body_top(&self) -> f64 {
+        self.open.max(self.close)
+    }
+
+    fn body_bottom(&self) -> f64 {
+        self.open.min(self.close)
+    }
+
+    fn midpoint(&self) -> f64 {
+        (self.open + self.close) / 2.0
+    }
+
+    fn is_doji(&self) -> bool {
+        self.body_ratio() <= DOJI_BODY_RATIO
+    }
+
+    fn is_long(&self, average_body: f64) -> bool {
+        self.body > average_body * 1.2 && self.body_ratio() > 0.5
+    }
+}
+
+/// Prior trend direction before `index`: 1 up, -1 down, 0 flat/unknown
+fn prior_trend(candles: &[Candle], index: usize) -> i8 {
+    if index < TREND_LOOKBACK + 1 {
+        return 0;
+    }
+
+    let start = candles[index - TREND_LOOKBACK - 1].close;
+    let end = candles[index - 1].close;
+    let change = (end - start) / start.abs().max(f64::EPSILON);
+
+    if change > 0.01 {
+        1
+    } else if change < -0.01 {
+        -1
+    } else {
+        0
+    }
+}
+
+/// Average body size over the candles preceding `index`
+fn average_body(candles: &[Candle], index: usize) -> f64 {
+    let start = index.saturating_sub(10);
+    let window = &candles[start..index.max(start + 1).min(candles.len())];
+    window.iter().map(|c| (c.close - c.open).abs()).sum::<f64>() / window.len() as f64
+}
+
+/// Volume confirmation factor (0.0 to 1.0) for the candle at `index`
+fn volume_factor(candles: &[Candle], index: usize) -> f64 {
+    let start = index.saturating_sub(10);
+    if start == index {
+        return 0.5;
+    }
+
+    let average = candles[start..index].iter().map(|c| c.volume).sum::<f64>() / (index - start) as f64;
+    if average <= 0.0 {
+        return 0.5;
+    }
+
+    (candles[index].volume / average / 2.0).min(1.0)
+}
+
+/// Combine base reliability, geometric quality and volume confirmation into a score
+fn score(pattern: PatternType, quality: f64, candles: &[Candle], index: usize) -> f64 {
+    let strength = pattern.reliability() * 0.5 + quality.clamp(0.0, 1.0) * 0.3 + volume_factor(candles, index) * 0.2;
+    strength.clamp(0.0, 1.0)
+}
+
+/// Detect single-candle patterns at `index`
+fn detect_single(candles: &[Candle], index: usize, found: &mut Vec<(PatternType, f64)>) {
+    let c = Shape::of(&candles[index]);
+    let trend = prior_trend(candles, index);
+    let avg_body = average_body(candles, index);
+
+    if c.is_doji() {
+        let shadow_balance = 1.0 - (c.upper_shadow - c.lower_shadow).abs() / c.range;
+        if c.upper_shadow <= c.range * 0.1 && c.lower_shadow >= c.range * 0.6 {
+            found.push((PatternType::DragonflyDoji, c.lower_shadow / c.range));
+        } else if c.lower_shadow <= c.range * 0.1 && c.upper_shadow >= c.range * 0.6 {
+            found.push((PatternType::GravestoneDoji, c.upper_shadow / c.range));
+        } else if c.upper_shadow >= c.range * 0.3 && c.lower_shadow >= c.range * 0.3 && c.range > avg_body * 2.0 {
+            found.push((PatternType::LongLeggedDoji, shadow_balance));
+        } else {
+            found.push((PatternType::Doji, 1.0 - c.body_ratio() / DOJI_BODY_RATIO));
+        }
+        return;
+    }
+
+    let small_body = c.body_ratio() < 0.35;
+
+    // Long lower shadow, small upper shadow
+    if small_body && c.lower_shadow >= 2.0 * c.body && c.upper_shadow <= c.body {
+        let quality = (c.lower_shadow / c.range).min(1.0);
+        match trend {
+            -1 => found.push((PatternType::Hammer, quality)),
+            1 => found.push((PatternType::HangingMan, quality)),
+            _ => {}
+        }
+    }
+
+    // Long upper shadow, small lower shadow
+    if small_body && c.upper_shadow >= 2.0 * c.body && c.lower_shadow <= c.body {
+        let quality = (c.upper_shadow / c.range).min(1.0);
+        match trend {
+            -1 => found.push((PatternType::InvertedHammer, quality)),
+            1 => found.push((PatternType::ShootingStar, quality)),
+            _ => {}
+        }
+    }
+
+    if small_body && c.upper_shadow > c.body && c.lower_shadow > c.body
+        && !found.iter().any(|(p, _)| p.candle_count() == 1)
+    {
+        found.push((PatternType::SpinningTop, 1.0 - c.body_ratio()));
+    }
+
+    if c.body_ratio() >= 0.95 && c.is_long(avg_body) {
+        let pattern = if c.is_bullish() { PatternType::BullishMarubozu } else { PatternType::BearishMarubozu };
+        found.push((pattern, c.body_ratio()));
+    }
+}
+
+/// Detect two-candle patterns ending at `index`
+fn detect_double(candles: &[Candle], index: usize, found: &mut Vec<(PatternType, f64)>) {
+    if index < 1 {
+        return;
+    }
+
+    let prev = Shape::of(&candles[index - 1]);
+    let c = Shape::of(&candles[index]);
+    let trend = prior_trend(candles, index - 1);
+    let avg_body = average_body(candles, index - 1).max(f64::EPSILON);
+    let tolerance = c.range.max(prev.range) * 0.05;
+
+    // Engulfing
+    if prev.is_bearish() && c.is_bullish() && c.open <= prev.close && c.close >= prev.open && c.body > prev.body {
+        found.push((PatternType::BullishEngulfing, (c.body / prev.body.max(f64::EPSILON) - 1.0).min(1.0)));
+    }
+    if prev.is_bullish() && c.is_bearish() && c.open >= prev.close && c.close <= prev.open && c.body > prev.body {
+        found.push((PatternType::BearishEngulfing, (c.body / prev.body.max(f64::EPSILON) - 1.0).min(1.0)));
+    }
+
+    // Harami and harami cross
+    let inside = c.body_top() < prev.body_top() && c.body_bottom() > prev.body_bottom();
+    if prev.is_long(avg_body) && inside {
+        let quality = 1.0 - c.body / prev.body;
+        match (prev.is_bearish(), c.is_doji()) {
+            (true, true) => found.push((PatternType::BullishHaramiCross, quality)),
+            (false, true) => found.push((PatternType::BearishHaramiCross, quality)),
+            (true, false) if c.is_bullish() => found.push((PatternType::BullishHarami, quality)),
+            (false, false) if c.is_bearish() => found.push((PatternType::BearishHarami, quality)),
+            _ => {}
+        }
+    }
+
+    // Piercing line and dark cloud cover
+    if prev.is_bearish() && prev.is_long(avg_body) && c.is_bullish()
+        && c.open < prev.close && c.close > prev.midpoint() && c.close < prev.open
+    {
+        found.push((PatternType::PiercingLine, (c.close - prev.midpoint()) / (prev.body / 2.0)));
+    }
+    if prev.is_bullish() && prev.is_long(avg_body) && c.is_bearish()
+        && c.open > prev.close && c.close < prev.midpoint() && c.close > prev.open
+    {
+        found.push((PatternType::DarkCloudCover, (prev.midpoint() - c.close) / (prev.body / 2.0)));
+    }
+
+    // Tweezers
+    if trend == 1 && prev.is_bullish() && c.is_bearish() && (prev.high - c.high).abs() <= tolerance {
+        found.push((PatternType::TweezerTop, 1.0 - (prev.high - c.high).abs() / tolerance.max(f64::EPSILON)));
+    }
+    if trend == -1 && prev.is_bearish() && c.is_bullish() && (prev.low - c.low).abs() <= tolerance {
+        found.push((PatternType::TweezerBottom, 1.0 - (prev.low - c.low).abs() / tolerance.max(f64::EPSILON)));
+    }
+
+    // Kickers: opposite-coloured long candles separated by a gap through the prior open
+    if prev.is_bearish() && c.is_bullish() && c.open > prev.open && c.is_long(avg_body) {
+        found.push((PatternType::BullishKicker, ((c.open - prev.open) / avg_body).min(1.0)));
+    }
+    if prev.is_bullish() && c.is_bearish() && c.open < prev.open && c.is_long(avg_body) {
+        found.push((PatternType::BearishKicker, ((prev.open - c.open) / avg_body).min(1.0)));
+    }
+
+    // Belt holds: long candle opening at its extreme against the prior trend
+    if trend == -1 && c.is_bullish() && c.is_long(avg_body) && c.lower_shadow <= c.range * 0.02 && c.open < prev.close {
+        found.push((PatternType::BullishBeltHold, c.body_ratio()));
+    }
+    if trend == 1 && c.is_bearish() && c.is_long(avg_body) && c.upper_shadow <= c.range * 0.02 && c.open > prev.close {
+        found.push((PatternType::BearishBeltHold, c.body_ratio()));
+    }
+}
+
+/// Detect three-candle patterns ending at `index`
+fn detect_triple(candles: &[Candle], index: usize, found: &mut Vec<(PatternType, f64)>) {
+    if index < 2 {
+        return;
+    }
+
+    let first = Shape::of(&candles[index - 2]);
+    let second = Shape::of(&candles[index - 1]);
+    let third = Shape::of(&candles[index]);
+    let avg_body = average_body(candles, index - 2).max(f64::EPSILON);
+
+    // Morning / evening stars (and doji stars, abandoned babies)
+    let small_middle = second.body < first.body * 0.3;
+    if first.is_bearish() && first.is_long(avg_body) && small_middle
+        && second.body_top() < first.close && third.is_bullish() && third.close > first.midpoint()
+    {
+        let quality = (third.close - first.midpoint()) / (first.body / 2.0);
+        if second.is_doji() && second.high < first.low && second.high < third.low {
+            found.push((PatternType::BullishAbandonedBaby, quality));
+        } else if second.is_doji() {
+            found.push((PatternType::MorningDojiStar, quality));
+        } else {
+            found.push((PatternType::MorningStar, quality));
+        }
+    }
+    if first.is_bullish() && first.is_long(avg_body) && small_middle
+        && second.body_bottom() > first.close && third.is_bearish() && third.close < first.midpoint()
+    {
+        let quality = (first.midpoint() - third.close) / (first.body / 2.0);
+        if second.is_doji() && second.low > first.high && second.low > third.high {
+            found.push((PatternType::BearishAbandonedBaby, quality));
+        } else if second.is_doji() {
+            found.push((PatternType::EveningDojiStar, quality));
+        } else {
+            found.push((PatternType::EveningStar, quality));
+        }
+    }
+
+    // Three white soldiers / three black crows
+    let shapes = [&first, &second, &third];
+    if shapes.iter().all(|s| s.is_bullish() && s.body_ratio() > 0.6)
+        && second.close > first.close && third.close > second.close
+        && second.open > first.open && second.open < first.close
+        && third.open > second.open && third.open < second.close
+    {
+        let quality = shapes.iter().map(|s| s.body_ratio()).sum::<f64>() / 3.0;
+        found.push((PatternType::ThreeWhiteSoldiers, quality));
+    }
+    if shapes.iter().all(|s| s.is_bearish() && s.body_ratio() > 0.6)
+        && second.close < first.close && third.close < second.close
+        && second.open < first.open && second.open > first.close
+        && third.open < second.open && third.open > second.close
+    {
+        let quality = shapes.iter().map(|s| s.body_ratio()).sum::<f64>() / 3.0;
+        found.push((PatternType::ThreeBlackCrows, quality));
+    }
+
+    // Three inside up / down (harami confirmed)
+    let inside = second.body_top() < first.body_top() && second.body_bottom() > first.body_bottom();
+    if first.is_bearish() && second.is_bullish() && inside && third.is_bullish() && third.close > first.open {
+        found.push((PatternType::ThreeInsideUp, (third.close - first.open) / first.body.max(f64::EPSILON)));
+    }
+    if first.is_bullish() && second.is_bearish() && inside && third.is_bearish() && third.close < first.open {
+        found.push((PatternType::ThreeInsideDown, (first.open - third.close) / first.body.max(f64::EPSILON)));
+    }
+
+    // Three outside up / down (engulfing confirmed)
+    if first.is_bearish() && second.is_bullish() && second.open <= first.close && second.close >= first.open
+        && third.is_bullish() && third.close > second.close
+    {
+        found.push((PatternType::ThreeOutsideUp, (third.close - second.close) / second.body.max(f64::EPSILON)));
+    }
+    if first.is_bullish() && second.is_bearish() && second.open >= first.close && second.close <= first.open
+        && third.is_bearish() && third.close < second.close
+    {
+        found.push((PatternType::ThreeOutsideDown, (second.close - third.close) / second.body.max(f64::EPSILON)));
+    }
+}
+
+/// Detect five-candle continuation patterns ending at `index`
+fn detect_five(candles: &[Candle], index: usize, found: &mut Vec<(PatternType, f64)>) {
+    if index < 4 {
+        return;
+    }
+
+    let first = Shape::of(&candles[index - 4]);
+    let middle: Vec<Shape> = candles[index - 3..index].iter().map(Shape::of).collect();
+    let last = Shape::of(&candles[index]);
+    let avg_body = average_body(candles, index - 4).max(f64::EPSILON);
+
+    let contained = middle.iter().all(|s| s.high <= first.high && s.low >= first.low && s.body < first.body * 0.5);
+
+    if first.is_bullish() && first.is_long(avg_body) && contained
+        && middle.iter().all(|s| s.is_bearish()) && last.is_bullish() && last.close > first.close
+    {
+        found.push((PatternType::RisingThreeMethods, (last.close - first.close) / first.body));
+    }
+    if first.is_bearish() && first.is_long(avg_body) && contained
+        && middle.iter().all(|s| s.is_bullish()) && last.is_bearish() && last.close < first.close
+    {
+        found.push((PatternType::FallingThreeMethods, (first.close - last.close) / first.body));
+    }
+}
+
+/// Detect all patterns completed by the candle at `index`
+pub fn detect_patterns_at(candles: &[Candle], index: usize) -> Vec<DetectedPattern> {
+    if index >= candles.len() {
+        return Vec::new();
+    }
+
+    let mut found = Vec::new();
+    detect_single(candles, index, &mut found);
+    detect_double(candles, index, &mut found);
+    detect_triple(candles, index, &mut found);
+    detect_five(candles, index, &mut found);
+
+    let mut detected: Vec<DetectedPattern> = found.into_iter()
+        .map(|(pattern, quality)| DetectedPattern {
+            pattern,
+            index,
+            strength: score(pattern, quality, candles, index),
+            bias: pattern.bias(),
+        })
+        .collect();
+
+    detected.sort_by(|a, b| b.strength.partial_cmp(&a.strength).unwrap_or(std::cmp::Ordering::Equal));
+    detected
+}
+
+/// Detect patterns completed by the most recent candle
+pub fn detect_patterns(candles: &[Candle]) -> Vec<DetectedPattern> {
+    match candles.len() {
+        0 => Vec::new(),
+        len => detect_patterns_at(candles, len - 1),
+    }
+}
+
+/// Scan the whole series for patterns
+pub fn scan_patterns(candles: &[Candle]) -> Vec<DetectedPattern> {
+    (0..candles.len()).flat_map(|i| detect_patterns_at(candles, i)).collect()
+}
+
+/// Net directional pattern score (-1.0 to 1.0) for the most recent candle
+pub fn pattern_bias_score(candles: &[Candle]) -> f64 {
+    let patterns = detect_patterns(candles);
+    if patterns.is_empty() {
+        return 0.0;
+    }
+
+    let net: f64 = patterns.iter()
+        .map(|p| match p.bias {
+            PatternBias::Bullish => p.strength,
+            PatternBias::Bearish => -p.strength,
+            PatternBias::Neutral => 0.0,
+        })
+        .sum();
+
+    (net / patterns.len() as f64).clamp(-1.0, 1.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn candle(open: f64, high: f64, low: f64, close: f64) -> Candle {
+        Candle {
+            timestamp: Utc::now(),
+            open,
+            high,
+            low,
+            close,
+            volume: 1000.0,
+        }
+    }
+
+    fn downtrend() -> Vec<Candle> {
+        (0..8).map(|i| {
+            let base = 110.0 - i as f64 * 2.0;
+            candle(base, base + 0.5, base - 1.5, base - 1.0)
+        }).collect()
+    }
+
+    #[test]
+    fn test_bullish_engulfing() {
+        let mut candles = downtrend();
+        candles.push(candle(95.0, 95.5, 93.5, 94.0));
+        candles.push(candle(93.8, 96.5, 93.5, 96.0));
+
+        let patterns = detect_patterns(&candles);
+        assert!(patterns.iter().any(|p| p.pattern == PatternType::BullishEngulfing));
+        assert!(pattern_bias_score(&candles) > 0.0);
+    }
+
+    #[test]
+    fn test_hammer_after_downtrend() {
+        let mut candles = downtrend();
+        candles.push(candle(94.0, 94.6, 90.0, 94.5));
+
+        let patterns = detect_patterns(&candles);
+        assert!(patterns.iter().any(|p| p.pattern == PatternType::Hammer));
+        assert!(patterns.iter().all(|p| p.strength > 0.0 && p.strength <= 1.0));
+    }
+
+    #[test]
+    fn test_morning_star() {
+        let mut candles = downtrend();
+        candles.push(candle(96.0, 96.2, 90.8, 91.0));
+        candles.push(candle(90.0, 90.4, 89.4, 89.8));
+        candles.push(candle(90.5, 95.2, 90.3, 95.0));
+
+        let patterns = detect_patterns(&candles);
+        assert!(patterns.iter().any(|p| p.pattern == PatternType::MorningStar));
+    }
+}
@@ -0,0 +1,241 @@
+//! Volatility Regime Switching
+//!
+//! This module classifies the market into low, normal or high volatility regimes
+//! from realized volatility and return entropy (`EntropyCalculator`), and derives
+//! which strategies should be active in the current regime. A regime change only
+//! takes effect after it has been observed for several consecutive updates so the
+//! system does not flip strategies on a single noisy bar.
+
+use std::collections::HashMap;
+use serde::{Deserialize, Serialize};
+use tracing::info;
+
+use crate::engine::entropy_calc::{EntropyCalculator, EntropyLevel, VolatilityProfile};
+use crate::strategy::registry::{StrategyControl, StrategyRegistry, StrategyStatus};
+
+/// Volatility regime
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum VolatilityRegime {
+    /// Quiet market
+    Low,
+
+    /// Typical market
+    Normal,
+
+    /// Volatile or disordered market
+    High,
+}
+
+/// Regime classifier configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RegimeConfig {
+    /// Number of returns used for the volatility profile
+    pub window: usize,
+
+    /// Bars per year used for annualization
+    pub bars_per_year: f64,
+
+    /// Annualized volatility (%) below which the regime is `Low`
+    pub low_volatility_threshold: f64,
+
+    /// Annualized volatility (%) above which the regime is `High`
+    pub high_volatility_threshold: f64,
+
+    /// Consecutive observations required before switching regime
+    pub confirmation_updates: usize,
+
+    /// Strategies to run in each regime
+    ///
+    /// Strategies that are not listed for any regime are left untouched.
+    pub strategies: HashMap<VolatilityRegime, Vec<String>>,
+}
+
+impl Default for RegimeConfig {
+    fn default() -> Self {
+        Self {
+            window: 100,
+            bars_per_year: 365.0 * 24.0 * 60.0,
+            low_volatility_threshold: 30.0,
+            high_volatility_threshold: 90.0,
+            confirmation_updates: 3,
+            strategies: HashMap::new(),
+        }
+    }
+}
+
+/// Regime change event
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RegimeChange {
+    /// Previous regime
+    pub from: VolatilityRegime,
+
+    /// New regime
+    pub to: VolatilityRegime,
+
+    /// Profile that confirmed the change
+    pub profile: VolatilityProfile,
+}
+
+/// Volatility regime classifier
+#[derive(Debug, Clone)]
+pub struct RegimeClassifier {
+    /// Configuration
+    config: RegimeConfig,
+
+    /// Entropy calculator
+    calculator: EntropyCalculator,
+
+    /// Confirmed regime
+    current: VolatilityRegime,
+
+    /// Candidate regime and how many consecutive times it was observed
+    pending: Option<(VolatilityRegime, usize)>,
+
+    /// Last computed profile
+    last_profile: Option<VolatilityProfile>,
+}
+
+impl RegimeClassifier {
+    /// Create a new regime classifier
+    pub fn new(config: RegimeConfig) -> Self {
+        let calculator = EntropyCalculator::new(config.window, config.bars_per_year);
+
+        Self {
+            config,
+            calculator,
+            current: VolatilityRegime::Normal,
+            pending: None,
+            last_profile: None,
+        }
+    }
+
+    /// Classify a single profile without hysteresis
+    ///
+    /// High entropy pushes a borderline market one regime up, low entropy one down.
+    pub fn classify(&self, profile: &VolatilityProfile) -> VolatilityRegime {
+        let volatility = profile.annualized_volatility;
+        let span = self.config.high_volatility_threshold - self.config.low_volatility_threshold;
+        let adjustment = match profile.entropy_level {
+            EntropyLevel::High => span * 0.1,
+            EntropyLevel::Low => -span * 0.1,
+            EntropyLevel::Medium => 0.0,
+        };
+        let adjusted = volatility + adjustment;
+
+        if adjusted < self.config.low_volatility_threshold {
+            VolatilityRegime::Low
+        } else if adjusted > self.config.high_volatility_threshold {
+            VolatilityRegime::High
+        } else {
+            VolatilityRegime::Normal
+        }
+    }
+
+    /// Update with the latest close prices; returns a change once it is confirmed
+    pub fn update(&mut self, closes: &[f64]) -> Option<RegimeChange> {
+        let profile = self.calculator.profile(closes)?;
+        let observed = self.classify(&profile);
+        self.last_profile = Some(profile.clone());
+
+        if observed == self.current {
+            self.pending = None;
+            return None;
+        }
+
+        let count = match self.pending {
+            Some((regime, count)) if regime == observed => count + 1,
+            _ => 1,
+        };
+
+        if count < self.config.confirmation_updates.max(1) {
+            self.pending = Some((observed, count));
+            return None;
+        }
+
+        let change = RegimeChange {
+            from: self.current,
+            to: observed,
+            profile,
+        };
+
+        info!("Volatility regime changed from {:?} to {:?} (vol {:.1}%, entropy {:.2})",
+              change.from, change.to, change.profile.annualized_volatility, change.profile.entropy);
+
+        self.current = observed;
+        self.pending = None;
+
+        Some(change)
+    }
+
+    /// Control commands that bring the registry in line with a regime
+    pub fn strategy_controls(&self, regime: VolatilityRegime, registry: &StrategyRegistry) -> Vec<StrategyControl> {
+        let wanted = self.config.strategies.get(&regime).cloned().unwrap_or_default();
+        let mut managed: Vec<&String> = self.config.strategies.values().flatten().collect();
+        managed.sort();
+        managed.dedup();
+
+        managed.into_iter()
+            .filter_map(|name| {
+                let status = registry.status(name)?;
+                let should_run = wanted.contains(name);
+
+                match (should_run, status) {
+                    (true, StrategyStatus::Disabled) => Some(StrategyControl::Enable { strategy: name.clone() }),
+                    (false, StrategyStatus::Active) => Some(StrategyControl::Disable { strategy: name.clone() }),
+                    _ => None,
+                }
+            })
+            .collect()
+    }
+
+    /// Get the confirmed regime
+    pub fn get_regime(&self) -> VolatilityRegime {
+        self.current
+    }
+
+    /// Get the last computed volatility profile
+    pub fn get_last_profile(&self) -> Option<&VolatilityProfile> {
+        self.last_profile.as_ref()
+    }
+
+    /// Get configuration
+    pub fn get_config(&self) -> &RegimeConfig {
+        &self.config
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn oscillating_closes(count: usize, amplitude: f64) -> Vec<f64> {
+        (0..count)
+            .map(|i| 100.0 * (1.0 + amplitude * ((i as f64) * 0.7).sin()))
+            .collect()
+    }
+
+    #[test]
+    fn test_regime_switch_requires_confirmation() {
+        let config = RegimeConfig {
+            window: 50,
+            bars_per_year: 1.0,
+            low_volatility_threshold: 0.5,
+            high_volatility_threshold: 5.0,
+            confirmation_updates: 2,
+            strategies: HashMap::new(),
+        };
+        let mut classifier = RegimeClassifier::new(config);
+
+        let volatile = oscillating_closes(60, 0.2);
+        assert!(classifier.update(&volatile).is_none());
+        assert_eq!(classifier.get_regime(), VolatilityRegime::Normal);
+
+        let change = classifier.update(&volatile).unwrap();
+        assert_eq!(change.to, VolatilityRegime::High);
+
+        let quiet = oscillating_closes(60, 0.0001);
+        classifier.update(&quiet);
+        let change = classifier.update(&quiet).unwrap();
+        assert_eq!(change.to, VolatilityRegime::Low);
+    }
+}
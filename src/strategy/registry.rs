@@ -0,0 +1,305 @@
+//! Strategy Registry
+//!
+//! This module keeps track of the strategies a `TradingSystem` runs and their
+//! lifecycle, so strategies can be enabled, disabled or replaced at runtime via
+//! control messages on the `MessageBus`. A strategy being disabled or replaced is
+//! first put into a draining state until its open positions are closed.
+
+use std::collections::HashMap;
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tracing::{info, warn};
+
+use crate::engine::message_bus::Message;
+use crate::strategy::strategy_trait::Strategy;
+
+/// Topic used for strategy control messages (`Message::Custom`)
+pub const STRATEGY_CONTROL_TOPIC: &str = "strategy_control";
+
+/// Strategy lifecycle status
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum StrategyStatus {
+    /// Generating signals
+    Active,
+
+    /// No new signals; open positions are being closed
+    Draining,
+
+    /// Not generating signals and holding no positions
+    Disabled,
+}
+
+/// Strategy control command
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum StrategyControl {
+    /// Re-enable a disabled strategy
+    Enable { strategy: String },
+
+    /// Drain and disable a strategy
+    Disable { strategy: String },
+
+    /// Drain a strategy, then swap in a previously staged replacement
+    Replace { strategy: String, replacement: String },
+}
+
+impl StrategyControl {
+    /// Build the control message to publish on the message bus
+    pub fn to_message(&self) -> Message {
+        Message::Custom(
+            STRATEGY_CONTROL_TOPIC.to_string(),
+            serde_json::to_value(self).unwrap_or(Value::Null),
+        )
+    }
+
+    /// Parse a control command from a message, if it is one
+    pub fn from_message(message: &Message) -> Option<Self> {
+        match message {
+            Message::Custom(topic, payload) if topic == STRATEGY_CONTROL_TOPIC => {
+                match serde_json::from_value(payload.clone()) {
+                    Ok(control) => Some(control),
+                    Err(e) => {
+                        warn!("Ignoring malformed strategy control message: {}", e);
+                        None
+                    }
+                }
+            }
+            _ => None,
+        }
+    }
+
+    /// Name of the targeted strategy
+    pub fn strategy(&self) -> &str {
+        match self {
+            StrategyControl::Enable { strategy }
+            | StrategyControl::Disable { strategy }
+            | StrategyControl::Replace { strategy, .. } => strategy,
+        }
+    }
+}
+
+/// Registered strategy slot
+struct StrategySlot {
+    /// Strategy instance
+    strategy: Box<dyn Strategy>,
+
+    /// Lifecycle status
+    status: StrategyStatus,
+
+    /// Replacement to activate once draining completes
+    pending_replacement: Option<Box<dyn Strategy>>,
+}
+
+/// Strategy registry
+pub struct StrategyRegistry {
+    /// Registered strategies by name
+    slots: HashMap<String, StrategySlot>,
+
+    /// Strategies staged as replacements, by name
+    staged: HashMap<String, Box<dyn Strategy>>,
+}
+
+impl StrategyRegistry {
+    /// Create an empty registry
+    pub fn new() -> Self {
+        Self {
+            slots: HashMap::new(),
+            staged: HashMap::new(),
+        }
+    }
+
+    /// Register an active strategy
+    pub fn register(&mut self, strategy: Box<dyn Strategy>) -> Result<()> {
+        let name = strategy.get_name();
+        if self.slots.contains_key(&name) {
+            return Err(anyhow::anyhow!("Strategy already registered: {}", name));
+        }
+
+        info!("Registering strategy {}", name);
+        self.slots.insert(name, StrategySlot {
+            strategy,
+            status: StrategyStatus::Active,
+            pending_replacement: None,
+        });
+
+        Ok(())
+    }
+
+    /// Stage a strategy so a later `Replace` command can reference it by name
+    pub fn stage(&mut self, strategy: Box<dyn Strategy>) {
+        let name = strategy.get_name();
+        info!("Staging replacement strategy {}", name);
+        self.staged.insert(name, strategy);
+    }
+
+    /// Apply a control command
+    pub fn apply(&mut self, control: &StrategyControl) -> Result<()> {
+        let slot = self.slots.get_mut(control.strategy())
+            .ok_or_else(|| anyhow::anyhow!("Unknown strategy: {}", control.strategy()))?;
+
+        match control {
+            StrategyControl::Enable { strategy } => {
+                if slot.status == StrategyStatus::Draining {
+                    return Err(anyhow::anyhow!("Strategy {} is still draining", strategy));
+                }
+                slot.status = StrategyStatus::Active;
+                info!("Strategy {} enabled", strategy);
+            }
+            StrategyControl::Disable { strategy } => {
+                if slot.status == StrategyStatus::Active {
+                    slot.status = StrategyStatus::Draining;
+                    info!("Strategy {} draining before disable", strategy);
+                }
+            }
+            StrategyControl::Replace { strategy, replacement } => {
+                let staged = self.staged.remove(replacement)
+                    .ok_or_else(|| anyhow::anyhow!("Replacement strategy not staged: {}", replacement))?;
+                slot.pending_replacement = Some(staged);
+                slot.status = StrategyStatus::Draining;
+                info!("Strategy {} draining before replacement by {}", strategy, replacement);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Names of strategies currently draining
+    pub fn draining(&self) -> Vec<String> {
+        self.slots.iter()
+            .filter(|(_, slot)| slot.status == StrategyStatus::Draining)
+            .map(|(name, _)| name.clone())
+            .collect()
+    }
+
+    /// Mark a draining strategy as drained (no open positions left)
+    ///
+    /// The strategy becomes disabled, or its staged replacement is activated under
+    /// the replacement's own name.
+    pub fn complete_drain(&mut self, name: &str) {
+        let replacement = match self.slots.get_mut(name) {
+            Some(slot) if slot.status == StrategyStatus::Draining => {
+                slot.status = StrategyStatus::Disabled;
+                slot.pending_replacement.take()
+            }
+            _ => return,
+        };
+
+        match replacement {
+            Some(strategy) => {
+                let new_name = strategy.get_name();
+                self.slots.remove(name);
+                self.slots.insert(new_name.clone(), StrategySlot {
+                    strategy,
+                    status: StrategyStatus::Active,
+                    pending_replacement: None,
+                });
+                info!("Strategy {} replaced by {}", name, new_name);
+            }
+            None => info!("Strategy {} drained and disabled", name),
+        }
+    }
+
+    /// Mutable access to the active strategies
+    pub fn active_mut(&mut self) -> impl Iterator<Item = &mut Box<dyn Strategy>> {
+        self.slots.values_mut()
+            .filter(|slot| slot.status == StrategyStatus::Active)
+            .map(|slot| &mut slot.strategy)
+    }
+
+    /// Status of a strategy
+    pub fn status(&self, name: &str) -> Option<StrategyStatus> {
+        self.slots.get(name).map(|slot| slot.status)
+    }
+
+    /// Whether a strategy may open new positions
+    pub fn is_active(&self, name: &str) -> bool {
+        self.status(name) == Some(StrategyStatus::Active)
+    }
+
+    /// All registered strategies with their status
+    pub fn statuses(&self) -> HashMap<String, StrategyStatus> {
+        self.slots.iter().map(|(name, slot)| (name.clone(), slot.status)).collect()
+    }
+}
+
+impl Default for StrategyRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::strategy::simple_strategy::Candle;
+    use crate::strategy::strategy_trait::StrategySignal;
+
+    struct Named(&'static str);
+
+    impl Strategy for Named {
+        fn get_name(&self) -> String {
+            self.0.to_string()
+        }
+
+        fn analyze(&mut self, symbol: &str, candles: &[Candle]) -> Result<StrategySignal> {
+            Ok(StrategySignal::hold(self.0, symbol, candles.last().map_or(0.0, |c| c.close), ""))
+        }
+    }
+
+    fn active_names(registry: &mut StrategyRegistry) -> Vec<String> {
+        let mut names: Vec<String> = registry.active_mut().map(|s| s.get_name()).collect();
+        names.sort();
+        names
+    }
+
+    #[test]
+    fn test_enable_and_disable_drain_first() {
+        let mut registry = StrategyRegistry::new();
+        registry.register(Box::new(Named("trend"))).unwrap();
+        registry.register(Box::new(Named("scalper"))).unwrap();
+        assert!(registry.register(Box::new(Named("trend"))).is_err());
+
+        let disable = StrategyControl::Disable { strategy: "trend".to_string() };
+        assert_eq!(StrategyControl::from_message(&disable.to_message()), Some(disable.clone()));
+        registry.apply(&disable).unwrap();
+        assert_eq!(registry.status("trend"), Some(StrategyStatus::Draining));
+        assert!(!registry.is_active("trend"));
+        assert_eq!(active_names(&mut registry), vec!["scalper".to_string()]);
+
+        let enable = StrategyControl::Enable { strategy: "trend".to_string() };
+        assert!(registry.apply(&enable).is_err());
+
+        registry.complete_drain("trend");
+        assert_eq!(registry.status("trend"), Some(StrategyStatus::Disabled));
+        registry.apply(&enable).unwrap();
+        assert!(registry.is_active("trend"));
+        assert!(registry.apply(&StrategyControl::Disable { strategy: "unknown".to_string() }).is_err());
+    }
+
+    #[test]
+    fn test_draining_strategy_closes_its_positions_before_replacement() {
+        let mut registry = StrategyRegistry::new();
+        registry.register(Box::new(Named("trend"))).unwrap();
+
+        let replace = StrategyControl::Replace { strategy: "trend".to_string(), replacement: "trend_v2".to_string() };
+        assert!(registry.apply(&replace).is_err());
+        registry.stage(Box::new(Named("trend_v2")));
+        registry.apply(&replace).unwrap();
+
+        // Until the trading system reports it flat, the strategy opens nothing new
+        // but stays listed as draining so its open positions keep being managed
+        assert_eq!(registry.draining(), vec!["trend".to_string()]);
+        assert!(active_names(&mut registry).is_empty());
+        assert!(!registry.is_active("trend"));
+        registry.apply(&StrategyControl::Disable { strategy: "trend".to_string() }).unwrap();
+        assert_eq!(registry.status("trend"), Some(StrategyStatus::Draining));
+
+        registry.complete_drain("trend");
+
+        assert!(registry.draining().is_empty());
+        assert_eq!(registry.status("trend"), None);
+        assert!(registry.is_active("trend_v2"));
+        assert_eq!(active_names(&mut registry), vec!["trend_v2".to_string()]);
+    }
+}
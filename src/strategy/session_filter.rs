@@ -0,0 +1,280 @@
+//! Session Filter
+//!
+//! This module provides a reusable time-of-day filter. Strategies can be wrapped
+//! in a `SessionFilteredStrategy` so their entry signals are suppressed during
+//! chosen trading sessions (Asia/EU/US), specific UTC hours, weekends, the weekend
+//! re-open gap, and the minutes around funding timestamps.
+
+use anyhow::Result;
+use chrono::{DateTime, Datelike, Duration, TimeZone, Timelike, Utc, Weekday};
+use serde::{Deserialize, Serialize};
+use tracing::debug;
+
+use crate::agents::market_regime_agent::{MarketRegime, MarketRegimeChange};
+use crate::position::position_manager::HoldingPolicy;
+use crate::strategy::simple_strategy::Candle;
+use crate::strategy::strategy_trait::{Strategy, StrategySignal};
+
+/// Major trading session (UTC hours, sessions overlap)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum TradingSession {
+    /// 00:00-09:00 UTC
+    Asia,
+
+    /// 07:00-16:00 UTC
+    Europe,
+
+    /// 13:00-22:00 UTC
+    UnitedStates,
+}
+
+impl TradingSession {
+    /// All sessions
+    pub const ALL: [TradingSession; 3] = [TradingSession::Asia, TradingSession::Europe, TradingSession::UnitedStates];
+
+    /// Session hours as [start, end) in UTC
+    pub fn hours_utc(&self) -> (u32, u32) {
+        match self {
+            TradingSession::Asia => (0, 9),
+            TradingSession::Europe => (7, 16),
+            TradingSession::UnitedStates => (13, 22),
+        }
+    }
+
+    /// Whether the session is open at a time
+    pub fn contains(&self, time: DateTime<Utc>) -> bool {
+        let (start, end) = self.hours_utc();
+        let hour = time.hour();
+        hour >= start && hour < end
+    }
+
+    /// Sessions open at a time
+    pub fn active_at(time: DateTime<Utc>) -> Vec<TradingSession> {
+        Self::ALL.iter().copied().filter(|s| s.contains(time)).collect()
+    }
+}
+
+/// Session filter configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionFilterConfig {
+    /// Sessions during which signals are suppressed
+    pub blocked_sessions: Vec<TradingSession>,
+
+    /// Individual UTC hours during which signals are suppressed
+    pub blocked_hours_utc: Vec<u32>,
+
+    /// Suppress signals on Saturday and Sunday
+    pub block_weekends: bool,
+
+    /// Minutes after Monday 00:00 UTC during which signals are suppressed (weekend gap)
+    pub weekend_gap_minutes: i64,
+
+    /// Funding timestamps as UTC hours
+    pub funding_hours_utc: Vec<u32>,
+
+    /// Minutes before and after each funding timestamp during which signals are suppressed
+    pub funding_buffer_minutes: i64,
+}
+
+impl Default for SessionFilterConfig {
+    fn default() -> Self {
+        Self {
+            blocked_sessions: Vec::new(),
+            blocked_hours_utc: Vec::new(),
+            block_weekends: false,
+            weekend_gap_minutes: 0,
+            funding_hours_utc: vec![0, 8, 16],
+            funding_buffer_minutes: 0,
+        }
+    }
+}
+
+/// Time-of-day signal filter
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionFilter {
+    /// Configuration
+    config: SessionFilterConfig,
+}
+
+impl SessionFilter {
+    /// Create a new session filter
+    pub fn new(config: SessionFilterConfig) -> Self {
+        Self { config }
+    }
+
+    /// Reason signals are suppressed at a time, if any
+    pub fn block_reason(&self, time: DateTime<Utc>) -> Option<String> {
+        let weekday = time.weekday();
+        if self.config.block_weekends && matches!(weekday, Weekday::Sat | Weekday::Sun) {
+            return Some(format!("weekend ({:?})", weekday));
+        }
+
+        if self.config.weekend_gap_minutes > 0 && weekday == Weekday::Mon {
+            let minutes = (time.hour() * 60 + time.minute()) as i64;
+            if minutes < self.config.weekend_gap_minutes {
+                return Some("weekend gap".to_string());
+            }
+        }
+
+        if let Some(session) = self.config.blocked_sessions.iter().find(|s| s.contains(time)) {
+            return Some(format!("{:?} session", session));
+        }
+
+        if self.config.blocked_hours_utc.contains(&time.hour()) {
+            return Some(format!("hour {:02}:00 UTC", time.hour()));
+        }
+
+        if self.config.funding_buffer_minutes > 0 {
+            if let Some(funding) = self.nearest_funding(time) {
+                if (time - funding).num_minutes().abs() <= self.config.funding_buffer_minutes {
+                    return Some(format!("funding at {}", funding.format("%H:%M")));
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Whether signals are allowed at a time
+    pub fn is_allowed(&self, time: DateTime<Utc>) -> bool {
+        self.block_reason(time).is_none()
+    }
+
+    /// Funding timestamp closest to a time (checking the previous, same and next day)
+    fn nearest_funding(&self, time: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let midnight = Utc.with_ymd_and_hms(time.year(), time.month(), time.day(), 0, 0, 0).single()?;
+
+        [-1i64, 0, 1].iter()
+            .flat_map(|day| {
+                self.config.funding_hours_utc.iter()
+                    .map(move |hour| midnight + Duration::days(*day) + Duration::hours(*hour as i64))
+            })
+            .min_by_key(|funding| (time - *funding).num_seconds().abs())
+    }
+
+    /// Get configuration
+    pub fn get_config(&self) -> &SessionFilterConfig {
+        &self.config
+    }
+}
+
+/// Strategy wrapper that suppresses entry signals outside allowed sessions
+pub struct SessionFilteredStrategy {
+    /// Wrapped strategy
+    inner: Box<dyn Strategy>,
+
+    /// Session filter
+    filter: SessionFilter,
+}
+
+impl SessionFilteredStrategy {
+    /// Attach a session filter to a strategy
+    pub fn new(inner: Box<dyn Strategy>, filter: SessionFilter) -> Self {
+        Self { inner, filter }
+    }
+
+    /// Get the session filter
+    pub fn get_filter(&self) -> &SessionFilter {
+        &self.filter
+    }
+}
+
+impl Strategy for SessionFilteredStrategy {
+    fn get_name(&self) -> String {
+        self.inner.get_name()
+    }
+
+    fn analyze(&mut self, symbol: &str, candles: &[Candle]) -> Result<StrategySignal> {
+        let signal = self.inner.analyze(symbol, candles)?;
+        if signal.direction() == 0.0 {
+            return Ok(signal);
+        }
+
+        let time = candles.last().and_then(candle_time).unwrap_or(signal.timestamp);
+        match self.filter.block_reason(time) {
+            Some(reason) => {
+                debug!("{} signal for {} suppressed: {}", signal.strategy, symbol, reason);
+                Ok(StrategySignal::hold(
+                    &signal.strategy,
+                    symbol,
+                    signal.entry_price,
+                    &format!("Suppressed by session filter ({}): {}", reason, signal.reasoning),
+                ))
+            }
+            None => Ok(signal),
+        }
+    }
+
+    fn min_candles(&self) -> usize {
+        self.inner.min_candles()
+    }
+
+    fn holding_policy(&self) -> Option<HoldingPolicy> {
+        self.inner.holding_policy()
+    }
+
+    fn market_regimes(&self) -> Option<Vec<MarketRegime>> {
+        self.inner.market_regimes()
+    }
+
+    fn on_regime_change(&mut self, change: &MarketRegimeChange) {
+        self.inner.on_regime_change(change)
+    }
+}
+
+/// Candle open time, accepting both second and millisecond timestamps
+fn candle_time(candle: &Candle) -> Option<DateTime<Utc>> {
+    if candle.open_time.abs() >= 100_000_000_000 {
+        Utc.timestamp_millis_opt(candle.open_time).single()
+    } else {
+        Utc.timestamp_opt(candle.open_time, 0).single()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_session_filter_blocks_configured_windows() {
+        let filter = SessionFilter::new(SessionFilterConfig {
+            blocked_sessions: vec![TradingSession::Asia],
+            block_weekends: true,
+            funding_buffer_minutes: 10,
+            ..SessionFilterConfig::default()
+        });
+
+        // Wednesday 2024-01-03
+        let asia = Utc.with_ymd_and_hms(2024, 1, 3, 3, 0, 0).unwrap();
+        let europe = Utc.with_ymd_and_hms(2024, 1, 3, 11, 0, 0).unwrap();
+        let before_funding = Utc.with_ymd_and_hms(2024, 1, 3, 15, 55, 0).unwrap();
+        let saturday = Utc.with_ymd_and_hms(2024, 1, 6, 11, 0, 0).unwrap();
+
+        assert!(!filter.is_allowed(asia));
+        assert!(filter.is_allowed(europe));
+        assert!(!filter.is_allowed(before_funding));
+        assert!(!filter.is_allowed(saturday));
+    }
+
+    #[test]
+    fn test_filtered_strategy_keeps_inner_regimes() {
+        struct RangeOnly;
+
+        impl Strategy for RangeOnly {
+            fn get_name(&self) -> String {
+                "range_only".to_string()
+            }
+
+            fn analyze(&mut self, symbol: &str, _candles: &[Candle]) -> Result<StrategySignal> {
+                Ok(StrategySignal::hold("range_only", symbol, 0.0, ""))
+            }
+
+            fn market_regimes(&self) -> Option<Vec<MarketRegime>> {
+                Some(vec![MarketRegime::Ranging])
+            }
+        }
+
+        let strategy = SessionFilteredStrategy::new(Box::new(RangeOnly), SessionFilter::new(SessionFilterConfig::default()));
+        assert_eq!(strategy.market_regimes(), Some(vec![MarketRegime::Ranging]));
+    }
+}
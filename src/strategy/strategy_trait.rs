@@ -0,0 +1,99 @@
+//! Strategy Trait
+//!
+//! This module defines the common `Strategy` trait implemented by trading strategies
+//! so they can be composed, swapped and evaluated uniformly.
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::agents::agent_coordinator::DecisionType;
+use crate::agents::market_regime_agent::{MarketRegime, MarketRegimeChange};
+use crate::position::position_manager::HoldingPolicy;
+use crate::strategy::simple_strategy::Candle;
+
+/// Signal produced by a strategy for a single symbol
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StrategySignal {
+    /// Strategy that produced the signal
+    pub strategy: String,
+
+    /// Symbol
+    pub symbol: String,
+
+    /// Signal timestamp
+    pub timestamp: DateTime<Utc>,
+
+    /// Recommended action
+    pub decision_type: DecisionType,
+
+    /// Confidence level (0-100)
+    pub confidence: f64,
+
+    /// Reference entry price
+    pub entry_price: f64,
+
+    /// Suggested stop loss price
+    pub stop_loss: Option<f64>,
+
+    /// Suggested take profit price
+    pub take_profit: Option<f64>,
+
+    /// Reasoning
+    pub reasoning: String,
+}
+
+impl StrategySignal {
+    /// Create a hold signal
+    pub fn hold(strategy: &str, symbol: &str, price: f64, reasoning: &str) -> Self {
+        Self {
+            strategy: strategy.to_string(),
+            symbol: symbol.to_string(),
+            timestamp: Utc::now(),
+            decision_type: DecisionType::Hold,
+            confidence: 0.0,
+            entry_price: price,
+            stop_loss: None,
+            take_profit: None,
+            reasoning: reasoning.to_string(),
+        }
+    }
+
+    /// Directional vote: +1 for long/buy, -1 for short/sell, 0 otherwise
+    pub fn direction(&self) -> f64 {
+        match self.decision_type {
+            DecisionType::EnterLong | DecisionType::Buy => 1.0,
+            DecisionType::EnterShort | DecisionType::Sell => -1.0,
+            _ => 0.0,
+        }
+    }
+}
+
+/// Core trait that all composable trading strategies implement
+pub trait Strategy: Send + Sync {
+    /// Get the strategy's unique name
+    fn get_name(&self) -> String;
+
+    /// Analyze candles for a symbol and produce a signal
+    fn analyze(&mut self, symbol: &str, candles: &[Candle]) -> Result<StrategySignal>;
+
+    /// Minimum number of candles required for a meaningful signal
+    fn min_candles(&self) -> usize {
+        50
+    }
+
+    /// Maximum holding period of the strategy's positions, if it has one
+    fn holding_policy(&self) -> Option<HoldingPolicy> {
+        None
+    }
+
+    /// Market regimes the strategy trades in; symbols in any other regime are skipped
+    ///
+    /// `None` trades in every regime, as do symbols whose regime is not known yet.
+    fn market_regimes(&self) -> Option<Vec<MarketRegime>> {
+        None
+    }
+
+    /// Called when the market regime of a symbol changes
+    fn on_regime_change(&mut self, _change: &MarketRegimeChange) {}
+}
@@ -3,27 +3,94 @@
 //! This module provides the core trading system for the OMNI-ALPHA VΩ∞∞ platform,
 //! integrating all components into a cohesive trading engine.
 
+use std::path::Path;
 use std::sync::Arc;
-use std::collections::{HashMap, VecDeque};
-use chrono::{DateTime, Utc};
+use std::collections::{HashMap, HashSet, VecDeque};
+use chrono::{DateTime, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
 use anyhow::Result;
 use tokio::sync::{Mutex, RwLock};
 use tracing::{info, debug, warn, error};
 use async_trait::async_trait;
 
-use crate::engine::message_bus::{Message, MessageBus, TradeDirection};
+use crate::engine::message_bus::{BusMessage, Message, MessageBus, MessageType, TradeDirection};
 use crate::engine::agent_trait::{Agent, AgentContext};
+use crate::engine::message_log::{JsonLinesMessageStore, MessageRecorder};
+use crate::engine::agent_runtime::AgentHeartbeat;
+use crate::engine::shadow_mode::{live_factory, ShadowAgentFactory};
+use crate::engine::supervisor::{AgentFactory, EscalationPolicy, RestartPolicy, SupervisorAction};
+use crate::engine::state_machine::{Event, State};
 use crate::agents::agent_coordinator::AgentCoordinator;
-use crate::agents::zero_loss_enforcer::ZeroLossEnforcer;
-use crate::agents::memory_node::{MemoryNode, MemoryNodeConfig};
-use crate::agents::feedback_loop::{FeedbackLoop, FeedbackLoopConfig};
+use crate::agents::zero_loss_enforcer::{ProtectiveAction, ZeroLossEnforcer, ZeroLossEnforcerConfig};
+use crate::agents::memory_node::{AgentContribution, AgentTrust, MarketConditions, MemoryNode, MemoryNodeConfig, SetupOutcomes};
+use crate::agents::consensus::VoteDirection;
+use crate::agents::feedback_loop::{AbOutcome, FeedbackLoop, FeedbackLoopConfig};
 use crate::agents::compound_controller::{CompoundController, CompoundControllerConfig, CapitalTier};
-use crate::agents::ghost_trader::{GhostTrader, GhostTraderConfig};
+use crate::agents::ghost_trader::{GhostTrader, GhostTraderConfig, TradeSimulationParams};
 use crate::agents::anti_loss_hedger::{AntiLossHedger, AntiLossHedgerConfig};
-use crate::agents::god_kernel::{GodKernel, GodKernelConfig};
+use crate::agents::god_kernel::{GodKernel, GodKernelConfig, StrategyBuilder};
+use crate::agents::news_agent::{NewsAgent, NewsAgentConfig, NewsItem};
+use crate::agents::onchain_agent::{OnChainAgent, OnChainAgentConfig, OnChainFeatures, OnChainProvider, RestOnChainProvider, RestOnChainProviderConfig};
+use crate::agents::funding_agent::{FundingAgent, FundingAgentConfig, FundingSnapshot};
+use crate::agents::order_flow_agent::{OrderFlowAgent, OrderFlowAgentConfig, OrderFlowFeatures};
+use crate::agents::market_regime_agent::{MarketRegime, MarketRegimeAgent, MarketRegimeAgentConfig, MarketRegimeChange};
+use crate::agents::whale_agent::{WhaleAgent, WhaleAgentConfig, WhaleAlert};
+use crate::agents::macro_calendar_agent::{MacroCalendar, MacroCalendarAgent, MacroCalendarAgentConfig};
+use crate::agents::scalper_agent::{ScalperAgent, ScalperAgentConfig, SCALPER_SOURCE};
+use crate::agents::risk_manager::{RiskManager, StrategyRiskBudget, CIRCUIT_BREAKER_TOPIC, KILL_SWITCH_TOPIC};
+use crate::capital::drawdown_throttle::{DrawdownThrottle, DrawdownThrottleConfig};
+use crate::capital::genesis::CapitalGenesisConfig;
+use crate::capital::collateral::{settle_coin_for_symbol, to_settle_units, CollateralManager, CollateralReport};
+use crate::capital::ledger::LedgerStore;
+use crate::capital::manager::CapitalManager;
 use crate::market_simulator::MarketSimulator;
-use crate::exchange::BybitAdapter;
+use crate::exchange::{BybitAdapter, OrderSide, OrderStatus};
+use crate::execution::order_manager::lock_order_manager;
+use crate::agents::agent_coordinator::DecisionType;
+use crate::position::calculator::{price_pnl, PnlBreakdown};
+use crate::position::portfolio::{Portfolio, PortfolioExposure};
+use crate::position::sizing::{realized_volatility, Sizer, SizerRegistry, SizingContext};
+use crate::position::position_manager::{BreakEvenRule, ExpiryAction, HoldingPolicy, PositionManager, PositionDirection};
+use crate::position::persistence::{BookPersister, JsonFileBookStore};
+use crate::position::trailing_stop::{StopAmender, TrailingStopManager};
+use crate::risk::audit::AuditTrail;
+use crate::risk::kill_switch::SafetyConfig;
+use crate::risk::limits::{ExposureLimit, ExposureLimitTable};
+use crate::risk::pre_trade::{PreTradeCheck, PreTradeConfig, PreTradeDecision, PreTradePipeline, PreTradeRejection, TradeProposal};
+use crate::risk::protection::{GapProtection, ProtectionConfig, ScheduledEvent};
+use crate::risk::stress::{StressConfig, StressTestResult, StressTester};
+use crate::risk::var::PortfolioVar;
+use crate::strategy::registry::{StrategyRegistry, StrategyControl};
+use crate::strategy::optimizer::{ParameterRange, ParameterSet};
+use crate::backtest::BacktestResult;
+use crate::deployment::config_manager::AgentRosterConfig;
+use crate::backtest::parallel::{BacktestJob, ParallelBacktester};
+use crate::strategy::regime::{RegimeClassifier, RegimeConfig, VolatilityRegime};
+use crate::strategy::simple_strategy::Candle;
+use crate::strategy::strategy_trait::Strategy;
+use crate::monitoring::performance_monitor::{PerformanceMonitor, TradeAttribution};
+use crate::monitoring::liquidation_monitor::{LiquidationAlert, LiquidationAlertConfig, LiquidationMonitor, LIQUIDATION_ALERT_TOPIC};
+use crate::monitoring::surveillance::{SurveillanceAlert, SurveillanceConfig, TradeSurveillance};
+use crate::monitoring::risk_report::{DailyRiskReport, RiskHistory, RiskSnapshot};
+use crate::monitoring::health_checker::{HealthChecker, HealthCheckerConfig, SystemHealth};
+use crate::monitoring::unified_error_manager::UnifiedErrorManager;
+use crate::market_data::analyzer::{CorrelationAnalyzer, CorrelationFilterConfig};
+use crate::backtest::trade_record::{excursion_percent, export_trades, TradeRecord};
+
+/// Backtests a strategy with a mutation job's parameters, for the A/B evaluations
+pub type MutationEvaluator = Arc<dyn Fn(&str, &BacktestJob) -> Result<BacktestResult> + Send + Sync>;
+
+/// Times a reduce-only order is polled before it is given up on
+const REDUCE_FILL_POLLS: usize = 5;
+
+/// Delay between polls of a reduce-only order (milliseconds)
+const REDUCE_FILL_POLL_MS: u64 = 200;
+
+/// Interval between perpetual funding settlements (seconds)
+const FUNDING_INTERVAL_SECS: i64 = 8 * 60 * 60;
+
+/// Interval between refreshes of the USDT rates of non-USDT collateral (seconds)
+const COLLATERAL_RATE_REFRESH_SECS: i64 = 300;
 
 /// Trading mode
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
@@ -92,6 +159,86 @@ pub struct TradingSystemConfig {
 
     /// Exchange configuration
     pub exchange: ExchangeConfig,
+
+    /// Settle coin, reserve and position cap of the initial capital
+    #[serde(default)]
+    pub capital: CapitalGenesisConfig,
+
+    /// Daily loss limit and emergency stop
+    #[serde(default)]
+    pub safety: SafetyConfig,
+
+    /// Per-symbol and per-sector exposure caps
+    #[serde(default)]
+    pub exposure_limits: Vec<ExposureLimit>,
+
+    /// Checks every trade signal must pass
+    #[serde(default)]
+    pub pre_trade: PreTradeConfig,
+
+    /// Scheduled event, spread and volatility protection
+    #[serde(default)]
+    pub protection: ProtectionConfig,
+
+    /// Shocks the portfolio is stress tested against
+    #[serde(default)]
+    pub stress: StressConfig,
+
+    /// Directory the daily risk report is written to at the end of each UTC day
+    #[serde(default)]
+    pub risk_report_dir: Option<String>,
+
+    /// Liquidation proximity buffers and auto-deleveraging
+    #[serde(default)]
+    pub liquidation_alerts: LiquidationAlertConfig,
+
+    /// File the feedback loop's performance and mutation records persist to
+    #[serde(default)]
+    pub feedback_state_path: Option<String>,
+
+    /// File the memory node's trade contributions and trust scores persist to
+    #[serde(default)]
+    pub memory_state_path: Option<String>,
+
+    /// File every message routed to the agents is recorded to for replay
+    #[serde(default)]
+    pub message_log_path: Option<String>,
+
+    /// File the live book is persisted to and restored from on startup
+    #[serde(default)]
+    pub book_path: Option<String>,
+
+    /// Seconds between book snapshots
+    #[serde(default = "default_book_persist_interval_secs")]
+    pub book_persist_interval_secs: u64,
+
+    /// When the zero-loss enforcer tightens stops, reduces and hedges open trades
+    #[serde(default)]
+    pub zero_loss: ZeroLossEnforcerConfig,
+
+    /// How the anti-loss hedger picks, sizes and unwinds hedges
+    #[serde(default)]
+    pub hedging: AntiLossHedgerConfig,
+
+    /// When agents count as degraded or unhealthy from their heartbeats
+    #[serde(default)]
+    pub health: HealthCheckerConfig,
+
+    /// Agent roster naming the data and signal agents that run and their settings
+    #[serde(default = "default_agent_roster_path")]
+    pub agent_roster_path: Option<String>,
+
+    /// On-chain data provider the on-chain agent polls
+    #[serde(default)]
+    pub onchain_provider: RestOnChainProviderConfig,
+}
+
+fn default_agent_roster_path() -> Option<String> {
+    Some("config/agents.toml".to_string())
+}
+
+fn default_book_persist_interval_secs() -> u64 {
+    30
 }
 
 impl Default for TradingSystemConfig {
@@ -104,6 +251,24 @@ impl Default for TradingSystemConfig {
             max_concurrent_trades: 1,
             heartbeat_interval: 1,
             exchange: ExchangeConfig::default(),
+            capital: CapitalGenesisConfig::default(),
+            safety: SafetyConfig::default(),
+            exposure_limits: Vec::new(),
+            pre_trade: PreTradeConfig::default(),
+            protection: ProtectionConfig::default(),
+            stress: StressConfig::default(),
+            risk_report_dir: None,
+            liquidation_alerts: LiquidationAlertConfig::default(),
+            feedback_state_path: None,
+            memory_state_path: None,
+            message_log_path: None,
+            book_path: None,
+            book_persist_interval_secs: default_book_persist_interval_secs(),
+            zero_loss: ZeroLossEnforcerConfig::default(),
+            hedging: AntiLossHedgerConfig::default(),
+            health: HealthCheckerConfig::default(),
+            agent_roster_path: default_agent_roster_path(),
+            onchain_provider: RestOnChainProviderConfig::default(),
         }
     }
 }
@@ -185,6 +350,38 @@ pub struct Trade {
 
     /// Trade metadata
     pub metadata: HashMap<String, String>,
+
+    /// Largest move against the trade in % of entry price
+    #[serde(default)]
+    pub max_adverse_excursion: f64,
+
+    /// Largest move in favor of the trade in % of entry price
+    #[serde(default)]
+    pub max_favorable_excursion: f64,
+
+    /// Loss at the initial stop (1R), in the same units as `realized_pnl`
+    #[serde(default)]
+    pub initial_risk: Option<f64>,
+
+    /// Realized P&L in multiples of the initial risk
+    #[serde(default)]
+    pub r_multiple: Option<f64>,
+
+    /// Taker fees paid so far, entry and exit
+    #[serde(default)]
+    pub fees: f64,
+
+    /// Funding paid so far; negative when received
+    #[serde(default)]
+    pub funding: f64,
+
+    /// What the originating strategy and the voting agents contributed to the entry
+    #[serde(default)]
+    pub agent_contributions: Vec<AgentContribution>,
+
+    /// Market conditions at entry
+    #[serde(default)]
+    pub market_conditions: MarketConditions,
 }
 
 /// Trading system state
@@ -262,12 +459,24 @@ pub struct TradingSystem {
     /// God kernel
     god_kernel: GodKernel,
 
+    /// Backtests the feedback loop's parameter mutations, when set
+    mutation_evaluator: Option<MutationEvaluator>,
+
     /// Market simulator (for simulation and backtesting modes)
     market_simulator: Option<MarketSimulator>,
 
     /// Active trades
     active_trades: HashMap<String, Trade>,
 
+    /// Trailing stops of open positions, persisted with the book
+    trailing_stops: TrailingStopManager,
+
+    /// Persists the book and restores it on startup, when a book path is set
+    book_persister: Option<BookPersister>,
+
+    /// Last funding settlement accrued on open positions (Unix seconds)
+    last_funding_settlement: i64,
+
     /// Trade history
     trade_history: VecDeque<Trade>,
 
@@ -276,6 +485,63 @@ pub struct TradingSystem {
 
     /// Market data cache
     market_data_cache: HashMap<String, HashMap<u64, VecDeque<MarketData>>>,
+
+    /// Position manager
+    position_manager: PositionManager,
+
+    /// Portfolio exposure view over open positions
+    portfolio: Portfolio,
+
+    /// Position sizing rule per strategy
+    sizers: SizerRegistry,
+
+    /// Capital tranches per strategy, by settle coin
+    collateral_manager: CollateralManager,
+
+    /// Cuts sizing during drawdowns
+    drawdown_throttle: DrawdownThrottle,
+
+    /// Registered strategies
+    strategy_registry: StrategyRegistry,
+
+    /// Per-strategy performance attribution
+    performance_monitor: PerformanceMonitor,
+
+    /// Volatility regime classifier
+    regime_classifier: RegimeClassifier,
+
+    /// Latest market regime by symbol, from the market regime agent
+    market_regimes: HashMap<String, MarketRegime>,
+
+    /// Agent health from their heartbeats
+    health_checker: HealthChecker,
+
+    /// Errors reported by the agent supervisor
+    error_manager: UnifiedErrorManager,
+
+    /// Cross-asset correlation analyzer
+    correlation_analyzer: CorrelationAnalyzer,
+
+    /// Pre-trade risk checks
+    pre_trade: PreTradePipeline,
+
+    /// Post-trade surveillance
+    surveillance: TradeSurveillance,
+
+    /// Gap and black-swan protection
+    gap_protection: GapProtection,
+
+    /// Portfolio stress tester
+    stress_tester: StressTester,
+
+    /// Risk snapshots for the daily risk report
+    risk_history: RiskHistory,
+
+    /// Liquidation proximity alerts
+    liquidation_monitor: LiquidationMonitor,
+
+    /// Exchange adapter (stop amendments in live mode)
+    exchange: Arc<BybitAdapter>,
 }
 
 /// Market data
@@ -340,12 +606,21 @@ impl TradingSystem {
         let feedback_loop_config = FeedbackLoopConfig::default();
         let compound_controller_config = CompoundControllerConfig::default();
         let ghost_trader_config = GhostTraderConfig::default();
-        let anti_loss_hedger_config = AntiLossHedgerConfig::default();
+        let anti_loss_hedger_config = config.hedging.clone();
         let god_kernel_config = GodKernelConfig::default();
 
         // Create agents with configs and message bus
-        let agent_coordinator = AgentCoordinator::new(config.initial_capital);
-        let zero_loss_enforcer = ZeroLossEnforcer::new();
+        let mut agent_coordinator = AgentCoordinator::new(config.initial_capital);
+        agent_coordinator.get_risk_manager_mut().set_safety_config(config.safety.clone());
+        agent_coordinator.get_risk_manager_mut().set_exposure_limits(ExposureLimitTable::new(config.exposure_limits.clone()));
+        agent_coordinator.get_risk_manager_mut().set_max_concurrent_positions(config.max_concurrent_trades);
+        let mut position_manager = PositionManager::new();
+        position_manager.set_max_positions(config.max_concurrent_trades);
+        let mut zero_loss_enforcer = ZeroLossEnforcer::new();
+        zero_loss_enforcer.set_min_win_probability(config.pre_trade.min_win_probability);
+        zero_loss_enforcer.set_min_risk_reward_ratio(config.pre_trade.min_risk_reward_ratio);
+        zero_loss_enforcer.set_min_expected_value(config.pre_trade.min_expected_value);
+        zero_loss_enforcer.set_config(config.zero_loss.clone());
         let memory_node = MemoryNode::new(memory_node_config, Arc::clone(&message_bus));
         let feedback_loop = FeedbackLoop::new(feedback_loop_config, Arc::clone(&message_bus));
         let compound_controller = CompoundController::new(compound_controller_config, Arc::clone(&message_bus), initial_capital);
@@ -371,41 +646,152 @@ impl TradingSystem {
             ghost_trader,
             anti_loss_hedger,
             god_kernel,
+            mutation_evaluator: None,
             market_simulator,
             active_trades: HashMap::new(),
+            trailing_stops: TrailingStopManager::new(),
+            book_persister: None,
+            last_funding_settlement: Utc::now().timestamp() / FUNDING_INTERVAL_SECS * FUNDING_INTERVAL_SECS,
             trade_history: VecDeque::new(),
             next_trade_id: 1,
             market_data_cache: HashMap::new(),
+            position_manager,
+            portfolio: Portfolio::default(),
+            sizers: SizerRegistry::default(),
+            collateral_manager: CollateralManager::new(config.capital.capital_manager(initial_capital)),
+            drawdown_throttle: DrawdownThrottle::new(DrawdownThrottleConfig::default(), initial_capital),
+            strategy_registry: StrategyRegistry::new(),
+            performance_monitor: PerformanceMonitor::new(),
+            regime_classifier: RegimeClassifier::new(RegimeConfig::default()),
+            market_regimes: HashMap::new(),
+            health_checker: HealthChecker::new(config.health.clone()),
+            error_manager: UnifiedErrorManager::default(),
+            correlation_analyzer: CorrelationAnalyzer::new(CorrelationFilterConfig::default()),
+            pre_trade: PreTradePipeline::new(config.pre_trade.clone()),
+            surveillance: TradeSurveillance::new(SurveillanceConfig::default()),
+            gap_protection: GapProtection::new(config.protection.clone()),
+            stress_tester: StressTester::new(config.stress.clone()),
+            risk_history: RiskHistory::default(),
+            liquidation_monitor: LiquidationMonitor::new(config.liquidation_alerts.clone()),
+            exchange: adapter,
         }
     }
 
     /// Start the trading system
     pub async fn start(&mut self) -> Result<()> {
         info!("Starting OMNI-ALPHA VΩ∞∞ trading system");
-        info!("Initial capital: ${:.2}", self.state.initial_capital);
+        info!("Initial capital: {:.2} {}", self.state.initial_capital, self.config.capital.settle_coin);
         info!("Trading mode: {:?}", self.state.mode);
         info!("Assets: {:?}", self.config.assets);
 
+        // Refuse to trade capital the wallet does not hold
+        self.config.capital.validate(self.state.initial_capital)?;
+        self.config.safety.validate()?;
+        if self.state.mode == TradingMode::Live {
+            let coin = self.config.capital.settle_coin.clone();
+            let balances = self.exchange.get_wallet_balance(Some(&coin)).await?;
+            let equity = balances.get(&coin).map(|balance| balance.equity).unwrap_or(0.0);
+            self.config.capital.check_wallet_balance(self.state.initial_capital, equity)?;
+        }
+
         // Initialize components
         self.initialize_components().await?;
 
         // Set system as running
+        self.handle_state_event(Event::Start)?;
         self.state.running = true;
         self.state.start_time = Utc::now();
 
         Ok(())
     }
 
+    /// Accrue funding on open positions once per settlement, at each symbol's latest rate
+    fn settle_funding(&mut self, now: DateTime<Utc>) {
+        let settlement = now.timestamp() / FUNDING_INTERVAL_SECS * FUNDING_INTERVAL_SECS;
+        if settlement <= self.last_funding_settlement {
+            return;
+        }
+        self.last_funding_settlement = settlement;
+
+        let symbols: HashSet<String> = self.active_trades.values().map(|trade| trade.symbol.clone()).collect();
+        for symbol in symbols {
+            let Some(snapshot) = self.agent_coordinator.get_funding(&symbol) else {
+                warn!("No funding rate for {}: skipping its settlement", symbol);
+                continue;
+            };
+            self.position_manager.apply_funding(&symbol, snapshot.funding_rate, snapshot.mark_price);
+        }
+
+        for trade in self.active_trades.values_mut() {
+            if let Some(position) = trade.metadata.get("position_id").and_then(|id| self.position_manager.get_position(id)) {
+                trade.funding = position.funding;
+            }
+        }
+    }
+
+    /// Persist the book now, or only once the interval elapsed
+    fn persist_book(&mut self, force: bool) {
+        let Some(persister) = self.book_persister.as_mut() else {
+            return;
+        };
+        let order_manager = self.agent_coordinator.get_trade_executor().get_order_manager();
+        let result = lock_order_manager(&order_manager).and_then(|orders| {
+            if force {
+                persister.persist(&self.position_manager, &self.active_trades, &orders, &self.trailing_stops)
+            } else {
+                persister.maybe_persist(&self.position_manager, &self.active_trades, &orders, &self.trailing_stops).map(|_| ())
+            }
+        });
+        if let Err(e) = result {
+            warn!("Failed to persist the book: {}", e);
+        }
+    }
+
     /// Initialize components
     async fn initialize_components(&mut self) -> Result<()> {
         // Initialize zero loss enforcer
         self.zero_loss_enforcer.initialize(Arc::clone(&self.message_bus));
 
-        // Initialize memory node
-        // (No initialization needed)
+        // Restore the memory node's contributions and trust scores
+        if let Some(path) = &self.config.memory_state_path {
+            self.memory_node.load(path)?;
+        }
 
-        // Initialize feedback loop
-        // (No initialization needed)
+        // Restore the feedback loop's records
+        if let Some(path) = &self.config.feedback_state_path {
+            self.feedback_loop.load(path)?;
+        }
+
+        // Pick up the book a previous run left open
+        if let Some(path) = &self.config.book_path {
+            let persister = BookPersister::new(
+                Box::new(JsonFileBookStore::new(path)),
+                std::time::Duration::from_secs(self.config.book_persist_interval_secs),
+            );
+            let order_manager = self.agent_coordinator.get_trade_executor().get_order_manager();
+            let mut orders = lock_order_manager(&order_manager)?;
+            if persister.restore(&mut self.position_manager, &mut self.active_trades, &mut orders, &mut self.trailing_stops)? {
+                self.state.active_trades_count = self.active_trades.len();
+            }
+            drop(orders);
+            self.book_persister = Some(persister);
+        }
+
+        // Record agent traffic so decisions can be replayed
+        if let Some(path) = &self.config.message_log_path {
+            let recorder = MessageRecorder::new(Box::new(JsonLinesMessageStore::new(path)))?;
+            info!("Recording agent messages to {} from sequence {}", path, recorder.get_next_sequence());
+            self.agent_coordinator.set_message_recorder(recorder);
+        }
+
+        // Agents publish their heartbeats on the system bus
+        self.agent_coordinator.get_agent_runtime_mut().set_message_bus(Arc::clone(&self.message_bus));
+
+        // Start the agents the roster enables
+        self.spawn_roster_agents().await?;
+
+        // Run the core agents' message handling in the runtime too
+        self.spawn_core_agents().await?;
 
         // Initialize compound controller
         // (No initialization needed)
@@ -417,7 +803,7 @@ impl TradingSystem {
         // (No initialization needed)
 
         // Initialize god kernel
-        let context = Arc::new(RwLock::new(AgentContext::new()));
+        let context = Arc::new(RwLock::new(AgentContext::new(self.god_kernel.get_name().to_string())));
         self.god_kernel.initialize(context).await?;
 
         // Initialize market simulator if needed
@@ -431,6 +817,149 @@ impl TradingSystem {
         Ok(())
     }
 
+    /// Start the core agents in the runtime, under supervision
+    ///
+    /// The runtime instances get the agents' bus subscriptions and heartbeats; the
+    /// trading loop keeps its own instances for the decisions it makes inline.
+    /// Nothing is started when backtesting.
+    async fn spawn_core_agents(&mut self) -> Result<()> {
+        if self.config.mode == TradingMode::Backtesting {
+            return Ok(());
+        }
+
+        let bus = Arc::clone(&self.message_bus);
+        let exchange = Arc::clone(&self.exchange);
+        let initial_capital = self.state.initial_capital;
+        let hedging = self.config.hedging.clone();
+        let factories: Vec<(&str, AgentFactory)> = vec![
+            ("GodKernel", Box::new({
+                let bus = Arc::clone(&bus);
+                move || Box::new(GodKernel::new(GodKernelConfig::default(), Arc::clone(&bus))) as Box<dyn Agent>
+            })),
+            ("GhostTrader", Box::new({
+                let bus = Arc::clone(&bus);
+                move || Box::new(GhostTrader::new(GhostTraderConfig::default(), Arc::clone(&bus))) as Box<dyn Agent>
+            })),
+            ("CompoundController", Box::new({
+                let bus = Arc::clone(&bus);
+                move || Box::new(CompoundController::new(CompoundControllerConfig::default(), Arc::clone(&bus), initial_capital)) as Box<dyn Agent>
+            })),
+            ("FeedbackLoop", Box::new({
+                let bus = Arc::clone(&bus);
+                move || Box::new(FeedbackLoop::new(FeedbackLoopConfig::default(), Arc::clone(&bus))) as Box<dyn Agent>
+            })),
+            ("MemoryNode", Box::new({
+                let bus = Arc::clone(&bus);
+                move || Box::new(MemoryNode::new(MemoryNodeConfig::default(), Arc::clone(&bus))) as Box<dyn Agent>
+            })),
+            ("AntiLossHedger", Box::new(move || {
+                Box::new(AntiLossHedger::new(hedging.clone(), Arc::clone(&exchange), Arc::clone(&bus))) as Box<dyn Agent>
+            })),
+        ];
+
+        for (name, factory) in factories {
+            let context = Arc::new(RwLock::new(AgentContext::new(name.to_string())));
+            self.agent_coordinator.supervise_agent(factory, context, RestartPolicy::Always, EscalationPolicy::Abandon).await?;
+            info!("Started core agent {}", name);
+        }
+        Ok(())
+    }
+
+    /// Start the data and signal agents the roster enables, under supervision
+    ///
+    /// Agents not listed in the roster run with their defaults. Nothing is started
+    /// when backtesting, since the agents poll live sources.
+    async fn spawn_roster_agents(&mut self) -> Result<()> {
+        if self.config.mode == TradingMode::Backtesting {
+            return Ok(());
+        }
+        let roster = match self.config.agent_roster_path.as_deref().map(Path::new) {
+            Some(path) if path.exists() => AgentRosterConfig::from_file(path)?,
+            _ => AgentRosterConfig::default(),
+        };
+
+        let mut factories: Vec<(&str, AgentFactory)> = Vec::new();
+        if roster.is_enabled("news") {
+            let config: NewsAgentConfig = roster.agent_config("news")?;
+            let bus = Arc::clone(&self.message_bus);
+            factories.push(("news", Box::new(move || {
+                Box::new(NewsAgent::new(config.clone(), Arc::clone(&bus))) as Box<dyn Agent>
+            })));
+        }
+
+        if roster.is_enabled("onchain") {
+            let config: OnChainAgentConfig = roster.agent_config("onchain")?;
+            let provider: Arc<dyn OnChainProvider> = Arc::new(RestOnChainProvider::new(self.config.onchain_provider.clone()));
+            let bus = Arc::clone(&self.message_bus);
+            factories.push(("onchain", Box::new(move || {
+                Box::new(OnChainAgent::new(config.clone(), Arc::clone(&provider), Arc::clone(&bus))) as Box<dyn Agent>
+            })));
+        }
+
+        if roster.is_enabled("funding") {
+            let config: FundingAgentConfig = roster.agent_config("funding")?;
+            let exchange = Arc::clone(&self.exchange);
+            let bus = Arc::clone(&self.message_bus);
+            factories.push(("funding", Box::new(move || {
+                Box::new(FundingAgent::new(config.clone(), Arc::clone(&exchange), Arc::clone(&bus))) as Box<dyn Agent>
+            })));
+        }
+
+        if roster.is_enabled("order_flow") {
+            let config: OrderFlowAgentConfig = roster.agent_config("order_flow")?;
+            let exchange = Arc::clone(&self.exchange);
+            let bus = Arc::clone(&self.message_bus);
+            factories.push(("order_flow", Box::new(move || {
+                Box::new(OrderFlowAgent::new(config.clone(), Arc::clone(&exchange), Arc::clone(&bus))) as Box<dyn Agent>
+            })));
+        }
+
+        if roster.is_enabled("whale") {
+            let config: WhaleAgentConfig = roster.agent_config("whale")?;
+            let exchange = Arc::clone(&self.exchange);
+            let bus = Arc::clone(&self.message_bus);
+            factories.push(("whale", Box::new(move || {
+                Box::new(WhaleAgent::new(config.clone(), Arc::clone(&exchange), Arc::clone(&bus))) as Box<dyn Agent>
+            })));
+        }
+
+        if roster.is_enabled("macro_calendar") {
+            let config: MacroCalendarAgentConfig = roster.agent_config("macro_calendar")?;
+            let bus = Arc::clone(&self.message_bus);
+            factories.push(("macro_calendar", Box::new(move || {
+                Box::new(MacroCalendarAgent::new(config.clone(), Arc::clone(&bus))) as Box<dyn Agent>
+            })));
+        }
+
+        if roster.is_enabled("market_regime") {
+            let config: MarketRegimeAgentConfig = roster.agent_config("market_regime")?;
+            let exchange = Arc::clone(&self.exchange);
+            let bus = Arc::clone(&self.message_bus);
+            factories.push(("market_regime", Box::new(move || {
+                Box::new(MarketRegimeAgent::new(config.clone(), Arc::clone(&exchange), Arc::clone(&bus))) as Box<dyn Agent>
+            })));
+        }
+
+        if roster.is_enabled("scalper") {
+            let config: ScalperAgentConfig = roster.agent_config("scalper")?;
+            let exchange = Arc::clone(&self.exchange);
+            let bus = Arc::clone(&self.message_bus);
+            let scalper = ScalperAgent::new(config.clone(), Arc::clone(&exchange), Arc::clone(&bus));
+            self.set_holding_policy(SCALPER_SOURCE, Some(scalper.holding_policy()));
+            self.pre_trade.set_source_cooldown(SCALPER_SOURCE, config.cooldown_secs);
+            factories.push(("scalper", Box::new(move || {
+                Box::new(ScalperAgent::new(config.clone(), Arc::clone(&exchange), Arc::clone(&bus))) as Box<dyn Agent>
+            })));
+        }
+
+        for (name, factory) in factories {
+            let context = Arc::new(RwLock::new(AgentContext::new(name.to_string())));
+            self.agent_coordinator.supervise_agent(factory, context, RestartPolicy::Always, EscalationPolicy::Abandon).await?;
+            info!("Started agent {} from the roster", name);
+        }
+        Ok(())
+    }
+
     /// Register agents with god kernel
     fn register_agents(&mut self) -> Result<()> {
         // Register zero loss enforcer
@@ -474,7 +1003,9 @@ impl TradingSystem {
         self.close_all_trades().await?;
 
         // Set system as not running
+        self.handle_state_event(Event::Stop)?;
         self.state.running = false;
+        self.persist_book(true);
 
         // Calculate final performance
         self.calculate_performance();
@@ -500,26 +1031,190 @@ impl TradingSystem {
         // Update market data
         self.update_market_data().await?;
 
+        // Keep the USDT rates of non-USDT collateral current
+        self.refresh_collateral_rates_if_due(Utc::now()).await;
+
+        // Share the rolling correlations the cluster exposure caps are built from
+        let matrix = self.correlation_analyzer.matrix(&self.config.assets);
+        self.agent_coordinator.get_risk_manager_mut().update_correlations(matrix);
+
+        // Share realized volatility with the leverage governor, the order router and the gap protection
+        for symbol in self.config.assets.clone() {
+            if let Some(volatility) = self.get_realized_volatility(&symbol) {
+                self.agent_coordinator.update_volatility(&symbol, volatility);
+                if let Some(candle_time) = self.get_latest_candle_time(&symbol) {
+                    self.gap_protection.update_volatility(&symbol, volatility, candle_time);
+                }
+            }
+        }
+
+        // De-risk ahead of scheduled events and through volatility spikes
+        self.apply_gap_protection(Utc::now()).await?;
+
         // Process agents
         self.process_agents().await?;
 
+        // Restart dead or stalled agents; flatten the book if the supervisor halts the system
+        let events = self.agent_coordinator.check_agents(&mut self.error_manager).await;
+        if events.iter().any(|event| event.action == SupervisorAction::Halted) && !self.active_trades.is_empty() {
+            warn!("Agent supervisor halted the system: closing {} open trade(s)", self.active_trades.len());
+            self.close_all_trades().await?;
+        }
+
+        // Switch strategies and sizing on volatility regime changes
+        self.update_regime();
+
+        // Generate signals from active strategies
+        self.process_strategies()?;
+
+        // Paper-trade the challenger strategies and the candidate agents
+        let candles = self.cached_candles();
+        self.god_kernel.run_shadow(&candles, &mut self.ghost_trader);
+        let prices: HashMap<String, f64> = candles.iter()
+            .filter_map(|(symbol, candles)| Some((symbol.clone(), candles.last()?.close)))
+            .collect();
+        self.god_kernel.run_shadow_agents(&prices, Utc::now()).await;
+
         // Process messages
         self.process_messages().await?;
 
+        // Book funding on open positions at each settlement
+        self.settle_funding(Utc::now());
+
+        // Loosen the stops tightened for whales that have gone quiet
+        self.restore_stops_after_whale(Utc::now()).await;
+
+        // Close positions of draining strategies
+        self.drain_strategies().await?;
+
         // Update active trades
         self.update_trades().await?;
 
+        // Fill position slots freed this cycle with queued signals
+        self.process_queued_signals().await?;
+
+        // Snapshot the book so a restart resumes managing it
+        self.persist_book(false);
+
+        // Stop for the day once realized plus unrealized losses reach the daily limit
+        let now = Utc::now();
+        let equity = self.current_equity();
+        self.update_circuit_breaker(equity);
+        if self.agent_coordinator.get_risk_manager_mut().update_daily_equity(now, equity) {
+            let kill_switch = self.agent_coordinator.get_risk_manager().get_kill_switch();
+            self.message_bus.send(Message::Custom(
+                KILL_SWITCH_TOPIC.to_string(),
+                serde_json::json!({
+                    "state": "tripped",
+                    "daily_loss": kill_switch.get_daily_loss(),
+                    "limit": kill_switch.get_limit(),
+                    "equity": equity,
+                }),
+            ));
+        }
+
+        // Flatten the book while the circuit breaker or the daily loss limit stops trading
+        if !self.agent_coordinator.get_risk_manager().is_trading_allowed(now) && !self.active_trades.is_empty() {
+            warn!("Trading stopped: closing {} open trade(s)", self.active_trades.len());
+            self.close_all_trades().await?;
+        }
+
         // Update performance metrics
         self.calculate_performance();
+        self.agent_coordinator.update_vote_weights(&self.feedback_loop);
 
         // Evolve system occasionally
         if self.should_evolve_system() {
+            let mut messages = self.god_kernel.apply_strategy_attribution(&self.performance_monitor, 10)?;
+            messages.extend(self.god_kernel.apply_trust_scores(&self.memory_node.get_trust_scores(), 10)?);
+            for message in messages {
+                self.message_bus.send(message);
+            }
+            for budget in self.god_kernel.adjust_agent_budgets(&mut self.agent_coordinator)? {
+                info!("Capital budget of {} now ${:.2}", budget.agent, budget.budget);
+            }
+
+            // Promote proven challengers to live trading and breed new ones
+            let (promoted, messages) = self.god_kernel.promote_and_retire(&mut self.agent_coordinator)?;
+            for strategy in promoted {
+                self.strategy_registry.register(strategy)?;
+            }
+            for message in messages.into_iter().chain(self.god_kernel.spawn_challengers()?) {
+                self.message_bus.send(message);
+            }
+
+            // Candidate agents that proved themselves in shadow mode join the live runtime
+            let (promoted, messages) = self.god_kernel.promote_shadow_agents(&mut self.agent_coordinator).await?;
+            for (name, factory) in promoted {
+                let context = Arc::new(RwLock::new(AgentContext::new(name.clone())));
+                let factory = live_factory(factory, Arc::clone(&self.message_bus));
+                if let Err(e) = self.agent_coordinator
+                    .supervise_agent(factory, context, RestartPolicy::Always, EscalationPolicy::Abandon).await
+                {
+                    warn!("Failed to start promoted agent {}: {}", name, e);
+                }
+            }
+            for message in messages {
+                self.message_bus.send(message);
+            }
             self.god_kernel.evolve_system().await?;
+
+            // Backtest the scheduled parameter mutations; adopted ones are swapped in from the bus
+            if let Some(evaluate) = self.mutation_evaluator.clone() {
+                if let Err(e) = self.run_mutation_evaluations_blocking(evaluate).await {
+                    warn!("Mutation evaluations failed: {}", e);
+                }
+            }
+
+            if let Some(path) = &self.config.feedback_state_path {
+                if let Err(e) = self.feedback_loop.save(path) {
+                    warn!("Failed to persist feedback loop state: {}", e);
+                }
+            }
+            if let Some(path) = &self.config.memory_state_path {
+                if let Err(e) = self.memory_node.save(path) {
+                    warn!("Failed to persist memory node state: {}", e);
+                }
+            }
         }
 
         Ok(())
     }
 
+    /// Replay one bar per symbol through a full decision cycle in paper mode
+    ///
+    /// The bars bypass the market simulator, so recorded history can be fed through
+    /// exactly the strategy, risk and sizing path used live. Returns the trades opened
+    /// during the cycle.
+    pub async fn replay_bars(&mut self, bars: Vec<MarketData>) -> Result<Vec<Trade>> {
+        if self.state.mode == TradingMode::Live {
+            return Err(anyhow::anyhow!("Bar replay is only available in simulation and backtesting modes"));
+        }
+
+        let first_new_id = self.next_trade_id;
+        for bar in bars {
+            let symbol = bar.symbol.clone();
+            self.cache_market_data(&symbol, bar.timeframe, bar);
+        }
+
+        self.update_regime();
+        self.process_strategies()?;
+        self.process_messages().await?;
+        self.update_trades().await?;
+        self.calculate_performance();
+
+        let is_new = |trade: &&Trade| {
+            trade.id.trim_start_matches("trade-").parse::<usize>().map_or(false, |n| n >= first_new_id)
+        };
+        let mut opened: Vec<Trade> = self.active_trades.values()
+            .chain(self.trade_history.iter())
+            .filter(is_new)
+            .cloned()
+            .collect();
+        opened.sort_by_key(|t| t.id.trim_start_matches("trade-").parse::<usize>().unwrap_or(0));
+        Ok(opened)
+    }
+
     /// Update market data
     async fn update_market_data(&mut self) -> Result<()> {
         // Create a vector to store market data for caching after the loop
@@ -567,6 +1262,18 @@ impl TradingSystem {
                 debug!("Fetching live market data from exchange");
 
                 // TODO: Implement exchange API integration
+
+                // Top of book for the spread veto
+                for symbol in self.config.assets.clone() {
+                    match self.exchange.get_orderbook(&symbol, 1).await {
+                        Ok(book) => {
+                            if let (Some(bid), Some(ask)) = (book.bids.first(), book.asks.first()) {
+                                self.update_quote(&symbol, bid.0, ask.0);
+                            }
+                        },
+                        Err(e) => debug!("Failed to fetch the {} order book: {}", symbol, e),
+                    }
+                }
             },
         }
 
@@ -575,6 +1282,12 @@ impl TradingSystem {
 
     /// Cache market data
     fn cache_market_data(&mut self, symbol: &str, timeframe: u64, data: MarketData) {
+        // Track correlations on the fastest timeframe
+        if Some(&timeframe) == self.config.timeframes.iter().min() {
+            self.correlation_analyzer.update_price(symbol, data.close);
+            self.anti_loss_hedger.update_price(symbol, data.close);
+        }
+
         // Get or create symbol cache
         let symbol_cache = self.market_data_cache
             .entry(symbol.to_string())
@@ -613,16 +1326,81 @@ impl TradingSystem {
 
     /// Process messages
     async fn process_messages(&mut self) -> Result<()> {
+        // The position limit follows the capital tier
+        self.sync_position_capacity();
+
         // Process trade signals
         let messages = self.message_bus.get_messages_for_agent("trading_system");
 
         for message in messages {
+            // Agents running in the coordinator's runtime, and the shadow candidates, see the same messages
+            let bus_message = to_bus_message(&message);
+            self.agent_coordinator.dispatch_to_agents(&bus_message);
+            self.god_kernel.dispatch_to_shadow_agents(&bus_message).await;
+
             match message {
                 Message::TradeSignal { symbol, direction, confidence, entry_price, stop_loss_price, take_profit_price, source, timestamp } => {
-                    // Check if we should execute the trade
-                    if self.should_execute_trade(&symbol, direction, confidence) {
-                        // Execute trade
-                        self.execute_trade(&symbol, direction, entry_price, stop_loss_price, take_profit_price, &source).await?;
+                    // Ignore signals from strategies that are draining or disabled
+                    if self.strategy_registry.status(&source).is_some() && !self.strategy_registry.is_active(&source) {
+                        debug!("Ignoring signal from inactive strategy {}", source);
+                        continue;
+                    }
+
+                    // Size the trade and run it through the pre-trade checks
+                    let position_size = self.calculate_position_size(&symbol, entry_price, stop_loss_price, &source);
+                    let proposal = TradeProposal {
+                        leverage: self.calculate_leverage(&symbol),
+                        symbol,
+                        direction,
+                        source,
+                        confidence,
+                        entry_price,
+                        stop_loss_price,
+                        take_profit_price,
+                        position_size,
+                        timestamp,
+                    };
+                    let decision = self.run_pre_trade_checks(&proposal);
+                    if decision.is_approved() {
+                        self.execute_trade(&proposal).await?;
+                    } else if decision.rejection.is_some_and(|r| r.check == PreTradeCheck::Capacity) {
+                        // Retry once a position slot frees up
+                        self.agent_coordinator.get_risk_manager_mut().queue_signal(proposal);
+                    }
+                },
+                Message::Custom(topic, payload) if topic == "mutation_adopted" => {
+                    let strategy = payload.get("agent_name").and_then(|v| v.as_str()).unwrap_or_default().to_string();
+                    match serde_json::from_value::<ParameterSet>(payload.get("parameters").cloned().unwrap_or_default()) {
+                        Ok(parameters) => {
+                            if let Err(e) = self.adopt_mutation(&strategy, &parameters) {
+                                warn!("Failed to adopt mutation of {}: {}", strategy, e);
+                            }
+                        },
+                        Err(e) => warn!("Ignoring mutation of {} without parameters: {}", strategy, e),
+                    }
+                },
+                message @ Message::Custom(..) => {
+                    if let Some(control) = StrategyControl::from_message(&message) {
+                        if let Err(e) = self.strategy_registry.apply(&control) {
+                            warn!("Strategy control {:?} rejected: {}", control, e);
+                        }
+                    } else if let Some(item) = NewsItem::from_message(&message) {
+                        self.agent_coordinator.ingest_news(&item);
+                    } else if let Some(features) = OnChainFeatures::from_message(&message) {
+                        self.agent_coordinator.update_onchain_features(features);
+                    } else if let Some(snapshot) = FundingSnapshot::from_message(&message) {
+                        self.agent_coordinator.update_funding(snapshot);
+                    } else if let Some(features) = OrderFlowFeatures::from_message(&message) {
+                        self.agent_coordinator.update_order_flow(features);
+                    } else if let Some(alert) = WhaleAlert::from_message(&message) {
+                        self.agent_coordinator.get_risk_manager_mut().apply_whale_alert(&alert);
+                        self.tighten_stops_for_whale(&alert.symbol).await;
+                    } else if let Some(calendar) = MacroCalendar::from_message(&message) {
+                        self.set_scheduled_events(calendar.events);
+                    } else if let Some(change) = MarketRegimeChange::from_message(&message) {
+                        self.apply_market_regime(&change);
+                    } else if let Some(heartbeat) = AgentHeartbeat::from_message(&message) {
+                        self.health_checker.record(heartbeat);
                     }
                 },
                 _ => {},
@@ -632,42 +1410,302 @@ impl TradingSystem {
         Ok(())
     }
 
-    /// Should execute trade
-    fn should_execute_trade(&self, symbol: &str, direction: TradeDirection, confidence: f64) -> bool {
-        // Check if we're at max concurrent trades
-        if self.active_trades.len() >= self.config.max_concurrent_trades {
-            return false;
+    /// Run a proposal through the pre-trade checks and record the outcome
+    fn run_pre_trade_checks(&mut self, proposal: &TradeProposal) -> PreTradeDecision {
+        let checks = self.pre_trade.get_checks().to_vec();
+        let decision = PreTradePipeline::evaluate(&checks, proposal, |check, proposal| {
+            self.evaluate_pre_trade_check(check, proposal)
+        });
+        self.pre_trade.record(&decision);
+        decision
+    }
+
+    /// Run an order placed outside the system through the pre-trade checks against the risk manager;
+    /// `open_symbols` are the symbols the caller holds positions on
+    pub fn check_external_trade(&mut self, proposal: &TradeProposal, open_symbols: &[String]) -> PreTradeDecision {
+        let decision = self.pre_trade.check_with_risk_manager(proposal, self.agent_coordinator.get_risk_manager_mut(), open_symbols);
+        if decision.is_approved() {
+            // Fold the booked entry in with the system's own positions
+            let exposure = self.get_portfolio_exposure();
+            self.agent_coordinator.get_risk_manager_mut().update_portfolio_exposure(exposure);
+        }
+        decision
+    }
+
+    /// Release an entry approved by `check_external_trade` once its position is closed
+    pub fn record_external_exit(&mut self, symbol: &str, pnl: f64) {
+        self.agent_coordinator.get_risk_manager_mut().record_external_exit(symbol, pnl, Utc::now());
+        let exposure = self.get_portfolio_exposure();
+        self.agent_coordinator.get_risk_manager_mut().update_portfolio_exposure(exposure);
+    }
+
+    /// Apply the concurrent position limit of the current capital tier everywhere positions are opened
+    fn sync_position_capacity(&mut self) {
+        let max_positions = self.config.max_concurrent_trades
+            .min(self.compound_controller.get_max_concurrent_positions());
+        self.agent_coordinator.get_risk_manager_mut().set_max_concurrent_positions(max_positions);
+        self.position_manager.set_max_positions(max_positions);
+    }
+
+    /// Execute queued signals while position slots are free
+    async fn process_queued_signals(&mut self) -> Result<()> {
+        let now = Utc::now();
+        while self.agent_coordinator.get_risk_manager().check_position_capacity(self.active_trades.len()).is_ok() {
+            let Some(proposal) = self.agent_coordinator.get_risk_manager_mut().next_queued_signal(now) else {
+                break;
+            };
+            if self.run_pre_trade_checks(&proposal).is_approved() {
+                self.execute_trade(&proposal).await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Evaluate one pre-trade check
+    fn evaluate_pre_trade_check(&mut self, check: PreTradeCheck, proposal: &TradeProposal) -> Result<()> {
+        let symbol = proposal.symbol.as_str();
+        let source = proposal.source.as_str();
+        let position_value = proposal.position_value();
+
+        match check {
+            PreTradeCheck::TradingState => {
+                // No new trades while the circuit breaker or the daily loss limit stops trading
+                if !self.agent_coordinator.get_risk_manager().is_trading_allowed(proposal.timestamp) {
+                    return Err(anyhow::anyhow!("trading stopped"));
+                }
+            },
+            PreTradeCheck::MarketConditions => {
+                // No entries around scheduled events, while spreads or volatility explode, after severe news or while a whale is active
+                self.gap_protection.check_entry(symbol, proposal.timestamp)?;
+                self.agent_coordinator.get_risk_manager().check_news_block(symbol, proposal.timestamp)?;
+                self.agent_coordinator.get_risk_manager().check_whale_activity(symbol, proposal.timestamp)?;
+                self.agent_coordinator.get_risk_manager().check_market_regime(symbol)?;
+            },
+            PreTradeCheck::Capital => {
+                if position_value <= 0.0 {
+                    return Err(anyhow::anyhow!("no position size"));
+                }
+
+                // Enforce the originating strategy's risk budget and agent's capital budget
+                self.agent_coordinator.get_risk_manager().check_strategy_budget(source, position_value, proposal.timestamp)?;
+                self.agent_coordinator.check_agent_budget(source, position_value)?;
+
+                // The strategy's tranche must hold the margin in the symbol's settle coin
+                let capital_manager = self.collateral_manager.get_manager_for_symbol(symbol)
+                    .ok_or_else(|| anyhow::anyhow!("no {} collateral", settle_coin_for_symbol(symbol)))?;
+                let tranche = capital_manager.tranche_for(source);
+                let margin = to_settle_units(symbol, position_value / proposal.leverage, proposal.entry_price);
+                let available = capital_manager.get_tranche(tranche).map_or(0.0, |t| t.available());
+                if margin > available {
+                    return Err(anyhow::anyhow!(
+                        "margin {:.6} exceeds {:.6} available in tranche {}", margin, available, tranche
+                    ));
+                }
+            },
+            PreTradeCheck::Capacity => {
+                self.agent_coordinator.get_risk_manager().check_position_capacity(self.active_trades.len())?;
+            },
+            PreTradeCheck::Exposure => {
+                if self.active_trades.values().any(|trade| trade.symbol == symbol) {
+                    return Err(anyhow::anyhow!("trade already open on {}", symbol));
+                }
+
+                // Cap the net notional of the symbol and its sector
+                let sector = self.portfolio.get_sector(symbol).to_string();
+                self.agent_coordinator.get_risk_manager_mut()
+                    .check_exposure_limits(symbol, &sector, proposal.signed_value(), proposal.timestamp)?;
+            },
+            PreTradeCheck::Leverage => {
+                let max_leverage = self.agent_coordinator.get_risk_manager().get_max_leverage(symbol);
+                if proposal.leverage <= 0.0 || proposal.leverage > max_leverage {
+                    return Err(anyhow::anyhow!(
+                        "leverage {:.2} outside (0, {:.2}]", proposal.leverage, max_leverage
+                    ));
+                }
+            },
+            PreTradeCheck::Correlation => {
+                // Correlation with open positions
+                let open_positions: Vec<(String, bool)> = self.active_trades.values()
+                    .map(|trade| (trade.symbol.clone(), matches!(trade.direction, TradeDirection::Long)))
+                    .collect();
+                let correlation_check = self.correlation_analyzer.check_exposure(
+                    symbol,
+                    matches!(proposal.direction, TradeDirection::Long),
+                    &open_positions,
+                );
+                if !correlation_check.approved {
+                    return Err(anyhow::anyhow!("{}", correlation_check.reasoning));
+                }
+
+                // Cap the directional exposure of the symbol's correlation cluster
+                self.agent_coordinator.get_risk_manager().check_cluster_exposure(symbol, proposal.signed_value())?;
+            },
+            PreTradeCheck::Cooldown => {
+                self.pre_trade.check_cooldown(symbol, &proposal.source, proposal.timestamp)?;
+            },
+            PreTradeCheck::ZeroLoss => {
+                let min_confidence = self.pre_trade.get_config().min_confidence;
+                if proposal.confidence < min_confidence {
+                    return Err(anyhow::anyhow!(
+                        "confidence {:.2} below {:.2}", proposal.confidence, min_confidence
+                    ));
+                }
+
+                let assessment = self.zero_loss_enforcer.pre_approve_setup(
+                    symbol,
+                    proposal.direction.clone(),
+                    proposal.entry_price,
+                    proposal.stop_loss_price,
+                    proposal.take_profit_price,
+                    position_value,
+                    proposal.confidence * 100.0,
+                )?;
+                if !assessment.approved {
+                    return Err(anyhow::anyhow!("{}", assessment.reasoning.trim()));
+                }
+            },
         }
 
-        // Check if we already have a trade for this symbol
-        for trade in self.active_trades.values() {
-            if trade.symbol == symbol {
-                return false;
+        Ok(())
+    }
+
+    /// Pre-trade ghost simulation of a proposal
+    ///
+    /// In live mode the book and trade tape are refreshed first. Proposals on symbols
+    /// without recorded market data pass unchecked.
+    async fn ghost_check(&mut self, proposal: &TradeProposal) -> Result<bool> {
+        if self.state.mode == TradingMode::Live {
+            if let Err(e) = self.ghost_trader.refresh_market_data(&self.exchange, &proposal.symbol, &self.config.exchange.category).await {
+                warn!("Failed to refresh market data for ghost simulation of {}: {}", proposal.symbol, e);
             }
         }
+        if !self.ghost_trader.has_market_data(&proposal.symbol) {
+            return Ok(true);
+        }
+
+        let ghost_config = self.ghost_trader.get_simulation_config();
+        let params = TradeSimulationParams {
+            symbol: proposal.symbol.clone(),
+            current_price: proposal.entry_price,
+            direction: proposal.direction.clone(),
+            entry_price: proposal.entry_price,
+            stop_loss_price: proposal.stop_loss_price,
+            take_profit_price: proposal.take_profit_price,
+            position_size: proposal.position_size,
+            leverage: proposal.leverage,
+            timeframe: 60,
+            duration: 3600,
+            volatility: 0.02,
+            trend: 0.0,
+            num_simulations: ghost_config.simulation_depth,
+            min_success_rate: ghost_config.min_success_rate,
+            min_roi: ghost_config.min_expected_roi,
+        };
+
+        let result = self.ghost_trader.simulate_trade(params)?;
+        if let Some(reason) = &result.rejection_reason {
+            info!("Skipping {} trade on {}: ghost simulation rejected it: {}", proposal.source, proposal.symbol, reason);
+        }
+        Ok(result.approved)
+    }
+
+    /// Execute a trade that passed the pre-trade checks
+    async fn execute_trade(&mut self, proposal: &TradeProposal) -> Result<()> {
+        let symbol = proposal.symbol.as_str();
+        let source = proposal.source.as_str();
+        let entry_price = proposal.entry_price;
+        let position_value = proposal.position_value();
+        let leverage = proposal.leverage;
+
+        // Fill the order against the live book and replay the tape before committing capital
+        if !self.ghost_check(proposal).await? {
+            return Ok(());
+        }
+
+        // Round-trip fees and expected slippage, held back until the trade closes
+        let slippage_bps = self.agent_coordinator.get_risk_manager().get_expected_slippage_bps(symbol);
+        let cost_reserve = to_settle_units(
+            symbol,
+            position_value * 2.0 * (self.config.capital.taker_fee_rate + slippage_bps / 10_000.0),
+            entry_price,
+        );
 
-        // Check confidence threshold
-        if confidence < 0.7 {
-            return false;
+        // Fund the margin from the strategy's capital tranche in the symbol's settle coin
+        let Some(capital_manager) = self.collateral_manager.get_manager_for_symbol_mut(symbol) else {
+            info!("Skipping {} trade on {}: no {} collateral", source, symbol, settle_coin_for_symbol(symbol));
+            return Ok(());
+        };
+        let tranche = capital_manager.tranche_for(source).to_string();
+        let margin = to_settle_units(symbol, position_value / leverage, entry_price);
+        if let Err(e) = capital_manager.reserve_with_costs(&tranche, margin, cost_reserve) {
+            info!("Skipping {} trade on {}: {}", source, symbol, e);
+            return Ok(());
         }
 
-        // Check with ghost trader
-        // (In a real implementation, we would simulate the trade first)
+        // Hand the reservation back if the trade cannot be opened
+        if let Err(e) = self.open_trade(proposal, &tranche, cost_reserve) {
+            if let Some(capital_manager) = self.collateral_manager.get_manager_for_symbol_mut(symbol) {
+                if let Err(cancel_error) = capital_manager.cancel_reservation(&tranche, margin, cost_reserve) {
+                    warn!("Failed to hand back the {} reservation on {}: {}", source, symbol, cancel_error);
+                }
+            }
+            return Err(e);
+        }
 
-        true
+        Ok(())
     }
 
-    /// Execute trade
-    async fn execute_trade(&mut self, symbol: &str, direction: TradeDirection, entry_price: f64, stop_loss_price: f64, take_profit_price: f64, source: &str) -> Result<()> {
+    /// Open the position and record the trade of a proposal whose capital is reserved
+    fn open_trade(&mut self, proposal: &TradeProposal, tranche: &str, cost_reserve: f64) -> Result<()> {
+        let symbol = proposal.symbol.as_str();
+        let source = proposal.source.as_str();
+        let direction = proposal.direction.clone();
+        let entry_price = proposal.entry_price;
+        let stop_loss_price = proposal.stop_loss_price;
+        let take_profit_price = proposal.take_profit_price;
+        let position_size = proposal.position_size;
+        let position_value = proposal.position_value();
+        let leverage = proposal.leverage;
+
+        // Check the agent budget up front; it is committed once nothing else can fail
+        self.agent_coordinator.check_agent_budget(source, position_value)?;
+
         // Generate trade ID
         let trade_id = format!("trade-{}", self.next_trade_id);
         self.next_trade_id += 1;
 
-        // Calculate position size
-        let position_size = self.calculate_position_size(symbol, entry_price, stop_loss_price);
-
-        // Calculate leverage
-        let leverage = self.calculate_leverage(symbol);
+        // Track the position under the originating strategy
+        let position_direction = match direction {
+            TradeDirection::Short => PositionDirection::Short,
+            _ => PositionDirection::Long,
+        };
+        let position_id = self.position_manager.open_position_for_strategy(
+            symbol.to_string(),
+            position_direction,
+            position_size,
+            entry_price,
+            source.to_string(),
+        )?;
+        self.position_manager.set_position_stop_loss(&position_id, stop_loss_price)?;
+        let entry_fee = entry_price * position_size * self.config.capital.taker_fee_rate;
+        self.position_manager.charge_fee(&position_id, entry_fee)?;
+        self.portfolio.set_leverage(symbol, leverage);
+
+        // Credit the strategy and whichever agents voted on the symbol
+        let mut agent_contributions = vec![AgentContribution {
+            agent: source.to_string(),
+            agreed: true,
+            confidence: proposal.confidence * 100.0,
+            weight: 1.0,
+        }];
+        if let Some(consensus) = self.agent_coordinator.get_cached_decision(symbol).and_then(|d| d.consensus.as_ref()) {
+            let vote_direction = match direction {
+                TradeDirection::Short => VoteDirection::Short,
+                _ => VoteDirection::Long,
+            };
+            agent_contributions.extend(consensus.contributions(vote_direction).into_iter().filter(|c| c.agent != source));
+        }
 
         // Create trade
         let trade = Trade {
@@ -688,7 +1726,20 @@ impl TradingSystem {
             roi: None,
             source: source.to_string(),
             tags: vec![],
-            metadata: HashMap::new(),
+            metadata: HashMap::from([
+                ("position_id".to_string(), position_id),
+                ("tranche".to_string(), tranche.to_string()),
+                ("cost_reserve".to_string(), cost_reserve.to_string()),
+            ]),
+            max_adverse_excursion: 0.0,
+            max_favorable_excursion: 0.0,
+            initial_risk: Some((entry_price - stop_loss_price).abs() * position_size)
+                .filter(|risk| *risk > 0.0),
+            r_multiple: None,
+            fees: entry_fee,
+            funding: 0.0,
+            agent_contributions,
+            market_conditions: self.market_conditions(symbol),
         };
 
         // Log trade
@@ -698,6 +1749,14 @@ impl TradingSystem {
         // Register trade with zero loss enforcer
         self.zero_loss_enforcer.register_trade(trade.clone())?;
 
+        // Charge the strategy's risk budget and audit the limit overrides the trade relies on
+        self.agent_coordinator.get_risk_manager_mut().record_strategy_open(source, position_value);
+        self.agent_coordinator.get_risk_manager_mut().record_override_uses(symbol);
+        self.agent_coordinator.commit_agent_capital(source, position_value)?;
+
+        // Start the symbol's cooldown
+        self.pre_trade.record_entry(symbol, source, proposal.timestamp);
+
         // Add to active trades
         self.active_trades.insert(trade_id, trade);
 
@@ -707,47 +1766,102 @@ impl TradingSystem {
         Ok(())
     }
 
-    /// Calculate position size
-    fn calculate_position_size(&self, symbol: &str, entry_price: f64, stop_loss_price: f64) -> f64 {
-        // Get risk per trade (1% of capital)
-        let risk_amount = self.state.current_capital * 0.01;
-
-        // Calculate risk per unit
-        let risk_per_unit = (entry_price - stop_loss_price).abs();
+    /// Realized volatility of a symbol on the fastest timeframe
+    fn get_realized_volatility(&self, symbol: &str) -> Option<f64> {
+        let timeframe = self.config.timeframes.iter().min().copied().unwrap_or(1);
+        self.market_data_cache.get(symbol)
+            .and_then(|cache| cache.get(&timeframe))
+            .and_then(|cache| realized_volatility(&cache.iter().map(|data| data.close).collect::<Vec<f64>>()))
+    }
 
-        if risk_per_unit > 0.0 {
-            // Calculate position size based on risk
-            let position_size = risk_amount / risk_per_unit;
+    /// Time of the latest cached candle of a symbol on the fastest timeframe
+    fn get_latest_candle_time(&self, symbol: &str) -> Option<DateTime<Utc>> {
+        let timeframe = self.config.timeframes.iter().min().copied().unwrap_or(1);
+        self.market_data_cache.get(symbol)
+            .and_then(|cache| cache.get(&timeframe))
+            .and_then(|cache| cache.back())
+            .map(|data| data.timestamp)
+    }
 
-            // Limit position size based on capital tier
-            let max_position_size = match self.state.capital_tier {
-                CapitalTier::Tier1 => self.state.current_capital * 0.1,
-                CapitalTier::Tier2 => self.state.current_capital * 0.15,
-                CapitalTier::Tier3 => self.state.current_capital * 0.2,
-                CapitalTier::Tier4 => self.state.current_capital * 0.25,
-            };
+    /// Current market conditions of a symbol, as far as they are known
+    fn market_conditions(&self, symbol: &str) -> MarketConditions {
+        let timeframe = self.config.timeframes.iter().min().copied().unwrap_or(1);
+        MarketConditions {
+            volatility: self.get_realized_volatility(symbol),
+            volume: self.market_data_cache.get(symbol)
+                .and_then(|cache| cache.get(&timeframe))
+                .and_then(|cache| cache.back())
+                .map(|data| data.volume),
+            funding_rate: self.agent_coordinator.get_funding(symbol).map(|snapshot| snapshot.funding_rate),
+            ..MarketConditions::default()
+        }
+    }
 
-            position_size.min(max_position_size / entry_price)
+    /// Calculate position size with the strategy's sizer
+    fn calculate_position_size(&self, symbol: &str, entry_price: f64, stop_loss_price: f64, source: &str) -> f64 {
+        // Limit position size to the notional of the current capital tier
+        let tier_size = self.compound_controller.get_position_size_usdt();
+        let max_position_size = self.config.capital.max_position_notional.map_or(tier_size, |cap| tier_size.min(cap));
+
+        // Volatility of the fastest timeframe
+        let volatility = self.get_realized_volatility(symbol);
+
+        // Edge of the strategy from its closed trades
+        let pnls: Vec<f64> = self.trade_history.iter()
+            .filter(|trade| trade.source == source)
+            .filter_map(|trade| trade.realized_pnl)
+            .collect();
+        let wins: Vec<f64> = pnls.iter().copied().filter(|pnl| *pnl > 0.0).collect();
+        let losses: Vec<f64> = pnls.iter().copied().filter(|pnl| *pnl < 0.0).collect();
+        let (win_rate, payoff_ratio) = if !wins.is_empty() && !losses.is_empty() {
+            let avg_win = wins.iter().sum::<f64>() / wins.len() as f64;
+            let avg_loss = -losses.iter().sum::<f64>() / losses.len() as f64;
+            (Some(wins.len() as f64 / pnls.len() as f64), Some(avg_win / avg_loss))
         } else {
-            0.0
-        }
+            (None, None)
+        };
+
+        // Size against the capital of the strategy's tranche
+        let equity = self.collateral_manager.get_manager_for_symbol(symbol)
+            .and_then(|manager| {
+                let equity = manager.get_tranche(manager.tranche_for(source))?.equity();
+                self.collateral_manager.to_usdt(manager.get_settle_coin(), equity)
+            })
+            .unwrap_or(self.state.current_capital);
+        let context = SizingContext {
+            volatility,
+            win_rate,
+            payoff_ratio,
+            max_positions: Some(self.agent_coordinator.get_risk_manager().get_position_capacity().max_concurrent_positions),
+            ..SizingContext::new(equity, entry_price, stop_loss_price, max_position_size)
+        };
+        let sizer = self.sizers.get_sizer(source);
+        let position_size = sizer.size(&context)
+            * self.drawdown_throttle.get_multiplier()
+            * self.agent_coordinator.get_risk_manager().get_market_regime_multiplier(symbol);
+        debug!("Sized {} {} signal with {}: {:.6}", source, symbol, sizer.get_name(), position_size);
+        position_size
     }
 
     /// Calculate leverage
     fn calculate_leverage(&self, symbol: &str) -> f64 {
         // Base leverage on capital tier
-        match self.state.capital_tier {
+        let tier_leverage: f64 = match self.state.capital_tier {
             CapitalTier::Tier1 => 1.0,
             CapitalTier::Tier2 => 2.0,
             CapitalTier::Tier3 => 3.0,
             CapitalTier::Tier4 => 5.0,
-        }
+        };
+
+        // Never above what the current volatility and drawdown permit
+        tier_leverage.min(self.agent_coordinator.get_risk_manager().get_max_leverage(symbol))
     }
 
     /// Update trades
     async fn update_trades(&mut self) -> Result<()> {
         let mut trades_to_close = Vec::new();
         let mut trades_to_update = Vec::new();
+        let mut protective_actions = Vec::new();
 
         // First collect all the trades and prices we need to process
         for (trade_id, trade) in &self.active_trades {
@@ -760,19 +1874,27 @@ impl TradingSystem {
         // Now update each trade
         for (trade_id, mut trade, current_price) in trades_to_update {
             // Update unrealized PnL
-            let unrealized_pnl = match trade.direction {
-                TradeDirection::Long => (current_price - trade.entry_price) * trade.size * trade.leverage,
-                TradeDirection::Short => (trade.entry_price - current_price) * trade.size * trade.leverage,
-                _ => 0.0,
-            };
+            let unrealized_pnl = price_pnl(matches!(trade.direction, TradeDirection::Long), trade.entry_price, current_price, trade.size);
 
             // Update the trade in the active_trades map
             if let Some(active_trade) = self.active_trades.get_mut(&trade_id) {
                 active_trade.unrealized_pnl = unrealized_pnl;
+
+                let excursion = excursion_percent(
+                    trade.entry_price,
+                    current_price,
+                    !matches!(trade.direction, TradeDirection::Short),
+                );
+                active_trade.max_favorable_excursion = active_trade.max_favorable_excursion.max(excursion);
+                active_trade.max_adverse_excursion = active_trade.max_adverse_excursion.max(-excursion);
+            }
+
+            if let Some(position_id) = trade.metadata.get("position_id") {
+                let _ = self.position_manager.update_position_price(position_id, current_price);
             }
 
             // Update zero loss enforcer
-            self.zero_loss_enforcer.update_trade(&trade_id, current_price)?;
+            protective_actions.extend(self.zero_loss_enforcer.update_trade(&trade_id, current_price)?);
 
             // Check if trade should be closed
             if self.should_close_trade(&trade, current_price) {
@@ -780,6 +1902,81 @@ impl TradingSystem {
             }
         }
 
+        // Tighten, reduce and hedge the trades the zero-loss enforcer is worried about
+        for action in protective_actions {
+            if !trades_to_close.iter().any(|id| id == action.trade_id()) {
+                self.apply_protective_action(action).await?;
+            }
+        }
+
+        // Activate or expire hedges still waiting on their threshold
+        let pending_hedges: Vec<(String, f64)> = self.anti_loss_hedger.get_active_hedges().iter()
+            .filter_map(|hedge| self.get_current_price(&hedge.original_symbol).map(|price| (hedge.id.clone(), price)))
+            .collect();
+        for (hedge_id, price) in pending_hedges {
+            match self.anti_loss_hedger.update_hedge(&hedge_id, price) {
+                Ok(Some(message)) => {
+                    self.message_bus.send(message);
+                    if self.state.mode == TradingMode::Live {
+                        if let Err(e) = self.anti_loss_hedger.execute_hedge(&hedge_id).await {
+                            warn!("Failed to execute hedge {}: {}", hedge_id, e);
+                        }
+                    }
+                },
+                Ok(None) => {},
+                Err(e) => warn!("Failed to update hedge {}: {}", hedge_id, e),
+            }
+        }
+
+        // Unwind hedges whose trade recovered or that stopped paying for themselves
+        let trade_prices: Vec<(String, f64)> = self.active_trades.values()
+            .filter_map(|trade| self.get_current_price(&trade.symbol).map(|price| (trade.id.clone(), price)))
+            .collect();
+        for (trade_id, price) in trade_prices {
+            for (hedge_id, reason) in self.anti_loss_hedger.check_unwinds(&trade_id, price) {
+                info!("Unwinding hedge {} of trade {}: {}", hedge_id, trade_id, reason);
+                self.unwind_hedge(&hedge_id, price).await?;
+            }
+        }
+
+        // Move stops to break-even where the rule triggered
+        for event in self.position_manager.apply_break_even_rule() {
+            let trade_id = self.active_trades.values()
+                .find(|t| t.metadata.get("position_id") == Some(&event.position_id))
+                .map(|t| t.id.clone());
+            if let Some(trade) = trade_id.and_then(|id| self.active_trades.get_mut(&id)) {
+                trade.stop_loss_price = event.new_stop;
+                self.zero_loss_enforcer.sync_stop(&trade.id, event.new_stop);
+            }
+
+            if self.state.mode == TradingMode::Live {
+                if let Err(e) = self.exchange.amend_stop(&event.symbol, event.new_stop).await {
+                    warn!("Failed to amend exchange stop for {}: {}", event.symbol, e);
+                }
+            }
+            self.message_bus.send(event.to_message());
+        }
+
+        // Close trades that outlived their strategy's max holding period
+        for strategy in self.strategy_registry.active_mut() {
+            if let Some(policy) = strategy.holding_policy() {
+                self.position_manager.set_holding_policy(&strategy.get_name(), Some(policy));
+            }
+        }
+        let now = Utc::now().timestamp().max(0) as u64;
+        for expiry in self.position_manager.check_holding_periods(now) {
+            if expiry.action != ExpiryAction::Close {
+                continue;
+            }
+            let trade_id = self.active_trades.values()
+                .find(|t| t.metadata.get("position_id") == Some(&expiry.position_id))
+                .map(|t| t.id.clone());
+            if let Some(trade_id) = trade_id.filter(|id| !trades_to_close.contains(id)) {
+                info!("Closing trade {} on {}: held {}s", trade_id, expiry.symbol, expiry.held_secs);
+                trades_to_close.push(trade_id);
+            }
+        }
+
         // Close trades
         for trade_id in trades_to_close {
             if let Some(trade) = self.active_trades.get(&trade_id) {
@@ -788,6 +1985,317 @@ impl TradingSystem {
             }
         }
 
+        // Share the current exposure with the risk manager
+        let exposure = self.get_portfolio_exposure();
+
+        // Stress test the book; a no-go stops the zero-loss enforcer approving new trades
+        let minutes = self.config.timeframes.iter().min().copied().unwrap_or(1).max(1) as f64;
+        let volatility: HashMap<String, f64> = exposure.symbols.keys()
+            .filter_map(|symbol| Some((symbol.clone(), self.get_realized_volatility(symbol)? / minutes.sqrt())))
+            .collect();
+        let stress = self.stress_tester.run(&exposure, &volatility);
+        self.zero_loss_enforcer.set_stress_result(stress);
+
+        self.agent_coordinator.get_risk_manager_mut().update_portfolio_exposure(exposure);
+
+        // Re-estimate the VaR of the book from the cached candles
+        let candles: HashMap<String, Vec<Candle>> = self.cached_candles().into_iter().collect();
+        if let Err(e) = self.agent_coordinator.get_risk_manager_mut().update_var(&candles) {
+            debug!("VaR not updated: {}", e);
+        }
+
+        // Keep the risk history once a minute; write the previous day's report once the UTC day rolls over
+        let now = Utc::now();
+        let previous_day = self.risk_history.get_latest().map(|s| s.timestamp.date_naive());
+        let risk_manager = self.agent_coordinator.get_risk_manager();
+        if let Some(exposure) = risk_manager.get_portfolio_exposure().filter(|_| self.risk_history.is_due(now)) {
+            let snapshot = RiskSnapshot::capture(exposure, risk_manager.get_portfolio_var(), risk_manager.get_exposure_limits(), now);
+            self.risk_history.record(snapshot);
+        }
+        if let (Some(day), Some(dir)) = (previous_day, &self.config.risk_report_dir) {
+            if day < now.date_naive() {
+                if let Err(e) = self.generate_risk_report(day).write(dir) {
+                    warn!("Failed to write risk report for {}: {}", day, e);
+                }
+            }
+        }
+
+        // Alert as positions approach liquidation; close those in the emergency buffer if configured
+        self.check_liquidation_proximity().await?;
+
+        // Trim positions before margin usage gets near liquidation
+        self.deleverage().await?;
+
+        // Shift capital towards the tranches that are performing
+        let now = Utc::now().timestamp().max(0) as u64;
+        for capital_manager in self.collateral_manager.get_managers_mut() {
+            if let Some(transfers) = capital_manager.maybe_rebalance(now) {
+                for transfer in transfers {
+                    info!("{} tranche {} rebalanced by {:.4} to weight {:.2}",
+                          capital_manager.get_settle_coin(), transfer.tranche, transfer.amount, transfer.weight);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Publish liquidation proximity alerts and close the positions flagged for deleveraging
+    async fn check_liquidation_proximity(&mut self) -> Result<()> {
+        let Some(exposure) = self.agent_coordinator.get_risk_manager().get_portfolio_exposure() else {
+            return Ok(());
+        };
+        for alert in self.liquidation_monitor.check(exposure) {
+            self.message_bus.send(Message::Custom(
+                LIQUIDATION_ALERT_TOPIC.to_string(),
+                serde_json::to_value(&alert).unwrap_or(serde_json::Value::Null),
+            ));
+        }
+
+        // Close every emergency-level position on each pass, so a failed close is retried
+        let mut closed = false;
+        for position_id in self.liquidation_monitor.get_deleverage_positions() {
+            let trade = self.active_trades.values()
+                .find(|t| t.metadata.get("position_id") == Some(&position_id))
+                .map(|t| (t.id.clone(), t.symbol.clone(), self.get_current_price(&t.symbol).unwrap_or(t.entry_price)));
+            let Some((trade_id, symbol, exit_price)) = trade else {
+                continue;
+            };
+            warn!("Closing trade {} on {}: position {} is at the emergency liquidation buffer", trade_id, symbol, position_id);
+            match self.close_trade(&trade_id, exit_price).await {
+                Ok(()) => closed = true,
+                Err(e) => warn!("Failed to close trade {} near liquidation, retrying next pass: {}", trade_id, e),
+            }
+        }
+
+        if closed {
+            let exposure = self.get_portfolio_exposure();
+            self.agent_coordinator.get_risk_manager_mut().update_portfolio_exposure(exposure);
+        }
+        Ok(())
+    }
+
+    /// Close the largest trades of each symbol the risk manager wants to trim
+    async fn deleverage(&mut self) -> Result<()> {
+        let actions = self.agent_coordinator.get_risk_manager().plan_deleverage();
+        if actions.is_empty() {
+            return Ok(());
+        }
+
+        for action in actions {
+            let mut trades: Vec<(String, f64, f64)> = self.active_trades.iter()
+                .filter(|(_, trade)| trade.symbol == action.symbol)
+                .map(|(id, trade)| {
+                    let price = self.get_current_price(&trade.symbol).unwrap_or(trade.entry_price);
+                    (id.clone(), trade.size * price, price)
+                })
+                .collect();
+            trades.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+
+            let mut reduced = 0.0;
+            for (trade_id, notional, exit_price) in trades {
+                if reduced >= action.reduce_notional {
+                    break;
+                }
+                warn!("Deleveraging: closing trade {} on {} ({})", trade_id, action.symbol, action.reason);
+                self.close_trade(&trade_id, exit_price).await?;
+                reduced += notional;
+            }
+        }
+
+        let exposure = self.get_portfolio_exposure();
+        self.agent_coordinator.get_risk_manager_mut().update_portfolio_exposure(exposure);
+        Ok(())
+    }
+
+    /// Carry out a protective action of the zero-loss enforcer
+    async fn apply_protective_action(&mut self, action: ProtectiveAction) -> Result<()> {
+        self.message_bus.send(action.to_message());
+
+        match action {
+            ProtectiveAction::TightenStop { trade_id, symbol, new_stop } => {
+                let Some(trade) = self.active_trades.get_mut(&trade_id) else {
+                    return Ok(());
+                };
+                // The break-even rule or a whale may already have moved the stop closer
+                let tighter = match trade.direction {
+                    TradeDirection::Short => new_stop < trade.stop_loss_price,
+                    _ => new_stop > trade.stop_loss_price,
+                };
+                if !tighter {
+                    return Ok(());
+                }
+                trade.stop_loss_price = new_stop;
+                if let Some(position_id) = trade.metadata.get("position_id").cloned() {
+                    if let Err(e) = self.position_manager.set_position_stop_loss(&position_id, new_stop) {
+                        warn!("Failed to tighten stop of position {}: {}", position_id, e);
+                    }
+                }
+                if self.state.mode == TradingMode::Live {
+                    if let Err(e) = self.exchange.amend_stop(&symbol, new_stop).await {
+                        warn!("Failed to amend exchange stop for {}: {}", symbol, e);
+                    }
+                }
+            },
+            ProtectiveAction::ReduceSize { trade_id, symbol, fraction } => {
+                let Some(price) = self.get_current_price(&symbol) else {
+                    return Ok(());
+                };
+                if self.state.mode != TradingMode::Live {
+                    self.reduce_trade(&trade_id, fraction, price)?;
+                    return Ok(());
+                }
+
+                // Book only what the reduce-only order actually filled
+                let Some(trade) = self.active_trades.get(&trade_id) else {
+                    return Ok(());
+                };
+                let size = trade.size;
+                let side = match trade.direction {
+                    TradeDirection::Long => OrderSide::Sell,
+                    TradeDirection::Short => OrderSide::Buy,
+                    _ => return Ok(()),
+                };
+                let qty = size * fraction.clamp(0.0, 1.0);
+                if qty <= 0.0 {
+                    return Ok(());
+                }
+                match self.reduce_on_exchange(&symbol, side, qty).await {
+                    Ok(Some((filled, fill_price))) => self.reduce_trade(&trade_id, filled / size, fill_price)?,
+                    Ok(None) => {
+                        warn!("Reduce-only order for trade {} on {} did not fill", trade_id, symbol);
+                        self.zero_loss_enforcer.sync_size(&trade_id, size);
+                    },
+                    Err(e) => {
+                        warn!("Failed to reduce trade {} on the exchange: {}", trade_id, e);
+                        self.zero_loss_enforcer.sync_size(&trade_id, size);
+                    },
+                }
+            },
+            ProtectiveAction::Hedge { trade_id, symbol, direction, position_size, entry_price } => {
+                // Give the hedger current correlations and betas to pick a hedge asset from
+                for asset in &self.config.assets {
+                    if let Some(correlation) = self.correlation_analyzer.correlation(&symbol, asset) {
+                        self.anti_loss_hedger.update_correlation(&symbol, asset, correlation);
+                    }
+                    if let Some(beta) = self.correlation_analyzer.beta(&symbol, asset) {
+                        self.anti_loss_hedger.update_beta(&symbol, asset, beta);
+                    }
+                }
+
+                let hedge = match self.anti_loss_hedger.create_hedge(&trade_id, &symbol, direction, position_size, entry_price) {
+                    Ok(hedge) => hedge,
+                    Err(e) => {
+                        warn!("Failed to hedge trade {}: {}", trade_id, e);
+                        return Ok(());
+                    },
+                };
+                let price = self.get_current_price(&symbol).unwrap_or(entry_price);
+                if let Some(message) = self.anti_loss_hedger.update_hedge(&hedge.id, price)? {
+                    self.message_bus.send(message);
+                    if self.state.mode == TradingMode::Live {
+                        if let Err(e) = self.anti_loss_hedger.execute_hedge(&hedge.id).await {
+                            warn!("Failed to execute hedge {}: {}", hedge.id, e);
+                        }
+                    }
+                }
+            },
+        }
+
+        Ok(())
+    }
+
+    /// Flatten a hedge on the exchange and close it at the hedged asset's price
+    async fn unwind_hedge(&mut self, hedge_id: &str, price: f64) -> Result<()> {
+        if self.state.mode == TradingMode::Live {
+            if let Err(e) = self.anti_loss_hedger.unwind_hedge_order(hedge_id).await {
+                warn!("Failed to unwind hedge {} on the exchange: {}", hedge_id, e);
+            }
+        }
+        if let Some(message) = self.anti_loss_hedger.close_hedge(hedge_id, price)? {
+            self.message_bus.send(message);
+        }
+
+        Ok(())
+    }
+
+    /// Send a reduce-only market order and wait for it to settle
+    ///
+    /// Returns the filled quantity and average fill price, or `None` if nothing filled.
+    async fn reduce_on_exchange(&self, symbol: &str, side: OrderSide, qty: f64) -> Result<Option<(f64, f64)>> {
+        let order = self.exchange.place_position_order(symbol, side, qty, 0, true).await?;
+        for _ in 0..REDUCE_FILL_POLLS {
+            tokio::time::sleep(std::time::Duration::from_millis(REDUCE_FILL_POLL_MS)).await;
+            let order = self.exchange.get_order(symbol, &order.order_id).await?;
+            match order.order_status {
+                // An IOC order that filled only partly ends cancelled
+                OrderStatus::Filled | OrderStatus::Cancelled | OrderStatus::Rejected => {
+                    if order.cum_exec_qty <= 0.0 {
+                        return Ok(None);
+                    }
+                    return Ok(Some((order.cum_exec_qty, order.cum_exec_value / order.cum_exec_qty)));
+                },
+                _ => {},
+            }
+        }
+        Err(anyhow::anyhow!("reduce-only order {} on {} still open", order.order_id, symbol))
+    }
+
+    /// Close part of a trade, returning its share of margin and result to the capital tranche
+    fn reduce_trade(&mut self, trade_id: &str, fraction: f64, price: f64) -> Result<()> {
+        let Some(trade) = self.active_trades.get_mut(trade_id) else {
+            return Ok(());
+        };
+        let fraction = fraction.clamp(0.0, 1.0);
+        let reduced_size = trade.size * fraction;
+        if reduced_size <= 0.0 {
+            return Ok(());
+        }
+
+        let gross_pnl = price_pnl(matches!(trade.direction, TradeDirection::Long), trade.entry_price, price, reduced_size);
+        let fees = price * reduced_size * self.config.capital.taker_fee_rate;
+
+        trade.size -= reduced_size;
+        trade.fees += fees;
+        let cost_reserve = trade.metadata.get("cost_reserve").and_then(|c| c.parse::<f64>().ok()).unwrap_or(0.0);
+        trade.metadata.insert("cost_reserve".to_string(), (cost_reserve * (1.0 - fraction)).to_string());
+        let reduced_pnl = trade.metadata.get("reduced_pnl").and_then(|p| p.parse::<f64>().ok()).unwrap_or(0.0);
+        trade.metadata.insert("reduced_pnl".to_string(), (reduced_pnl + gross_pnl - fees).to_string());
+        let reduced_total = trade.metadata.get("reduced_size").and_then(|s| s.parse::<f64>().ok()).unwrap_or(0.0);
+        trade.metadata.insert("reduced_size".to_string(), (reduced_total + reduced_size).to_string());
+        let trade = trade.clone();
+        self.zero_loss_enforcer.sync_size(trade_id, trade.size);
+        info!("Reduced trade {} on {} by {:.0}% at ${:.2}: ${:.2} realized",
+              trade_id, trade.symbol, fraction * 100.0, price, gross_pnl - fees);
+
+        if let Some(position_id) = trade.metadata.get("position_id") {
+            if let Err(e) = self.position_manager.set_position_size(position_id, trade.size) {
+                warn!("Failed to reduce position {} for trade {}: {}", position_id, trade_id, e);
+            }
+        }
+
+        let notional = trade.entry_price * reduced_size;
+        self.agent_coordinator.get_risk_manager_mut().record_strategy_reduce(&trade.source, notional, gross_pnl - fees, Utc::now());
+        self.agent_coordinator.release_agent_capital(&trade.source, notional);
+
+        if let Some(tranche) = trade.metadata.get("tranche") {
+            let symbol = trade.symbol.as_str();
+            let released = match self.collateral_manager.get_manager_for_symbol_mut(symbol) {
+                Some(capital_manager) => capital_manager.reduce_with_costs(
+                    tranche,
+                    to_settle_units(symbol, notional / trade.leverage, trade.entry_price),
+                    cost_reserve * fraction,
+                    to_settle_units(symbol, gross_pnl, price),
+                    to_settle_units(symbol, fees, price),
+                    0.0,
+                ),
+                None => Err(anyhow::anyhow!("no {} collateral", settle_coin_for_symbol(symbol))),
+            };
+            if let Err(e) = released {
+                warn!("Failed to release capital for reduced trade {}: {}", trade_id, e);
+            }
+        }
+
         Ok(())
     }
 
@@ -830,25 +2338,79 @@ impl TradingSystem {
             trade.exit_price = Some(exit_price);
             trade.exit_time = Some(Utc::now());
 
-            // Calculate realized PnL
-            let realized_pnl = match trade.direction {
-                TradeDirection::Long => (exit_price - trade.entry_price) * trade.size * trade.leverage,
-                TradeDirection::Short => (trade.entry_price - exit_price) * trade.size * trade.leverage,
-                _ => 0.0,
-            };
+            // Close the tracked position paying the exit fee; its breakdown is the trade's realized P&L
+            let exit_fee = exit_price * trade.size * self.config.capital.taker_fee_rate;
+            let mut pnl = PnlBreakdown::new(
+                price_pnl(matches!(trade.direction, TradeDirection::Long), trade.entry_price, exit_price, trade.size),
+                exit_fee,
+                0.0,
+                trade.entry_price * trade.size,
+            );
+            if let Some(position_id) = trade.metadata.get("position_id") {
+                if let Err(e) = self.position_manager.close_position_with_fee(position_id, exit_price, exit_fee) {
+                    warn!("Failed to close position {} for trade {}: {}", position_id, trade_id, e);
+                }
+                if let Some(position) = self.position_manager.get_closed_positions().iter().rev().find(|p| &p.id == position_id) {
+                    pnl = position.pnl_breakdown();
+                }
+            }
+            trade.fees += exit_fee;
+            trade.funding = pnl.funding;
 
-            trade.realized_pnl = Some(realized_pnl);
+            // Fold in what protective reductions already realized
+            let reduced_pnl = trade.metadata.get("reduced_pnl").and_then(|p| p.parse::<f64>().ok()).unwrap_or(0.0);
+            let reduced_size = trade.metadata.get("reduced_size").and_then(|s| s.parse::<f64>().ok()).unwrap_or(0.0);
+            let total_pnl = pnl.net_pnl + reduced_pnl;
+
+            trade.realized_pnl = Some(total_pnl);
+            trade.r_multiple = trade.initial_risk.map(|risk| total_pnl / risk);
 
             // Calculate ROI
-            let roi = realized_pnl / (trade.entry_price * trade.size) * 100.0;
+            let roi = total_pnl / (trade.entry_price * (trade.size + reduced_size)) * 100.0;
             trade.roi = Some(roi);
 
             // Log trade
             info!("Closing trade: {} - {} at ${:.2}, PnL: ${:.2}, ROI: {:.2}%",
-                trade_id, trade.symbol, exit_price, realized_pnl, roi);
+                trade_id, trade.symbol, exit_price, total_pnl, roi);
 
             // Close hedge with zero loss enforcer
             self.zero_loss_enforcer.close_trade(trade_id, exit_price, Utc::now())?;
+            let hedge_ids: Vec<String> = self.anti_loss_hedger.get_hedges_for_trade(trade_id).iter()
+                .map(|hedge| hedge.id.clone())
+                .collect();
+            for hedge_id in hedge_ids {
+                self.unwind_hedge(&hedge_id, exit_price).await?;
+            }
+
+            // Release the strategy's risk budget
+            self.agent_coordinator.get_risk_manager_mut().record_strategy_close(
+                &trade.source,
+                trade.entry_price * trade.size,
+                pnl.net_pnl,
+                Utc::now(),
+            );
+            self.agent_coordinator.release_agent_capital(&trade.source, trade.entry_price * trade.size);
+
+            // Return the margin and the result to the strategy's capital tranche
+            if let Some(tranche) = trade.metadata.get("tranche") {
+                let symbol = trade.symbol.clone();
+                let margin = to_settle_units(&symbol, trade.entry_price * trade.size / trade.leverage, trade.entry_price);
+                let cost_reserve = trade.metadata.get("cost_reserve").and_then(|c| c.parse::<f64>().ok()).unwrap_or(0.0);
+                let released = match self.collateral_manager.get_manager_for_symbol_mut(&symbol) {
+                    Some(capital_manager) => capital_manager.release_with_costs(
+                        tranche,
+                        margin,
+                        cost_reserve,
+                        to_settle_units(&symbol, pnl.gross_pnl, exit_price),
+                        to_settle_units(&symbol, pnl.fees, exit_price),
+                        to_settle_units(&symbol, pnl.funding, exit_price),
+                    ),
+                    None => Err(anyhow::anyhow!("no {} collateral", settle_coin_for_symbol(&symbol))),
+                };
+                if let Err(e) = released {
+                    warn!("Failed to release capital for trade {}: {}", trade_id, e);
+                }
+            }
 
             // Store in memory node
             let memory_trade = crate::agents::memory_node::Trade {
@@ -862,14 +2424,52 @@ impl TradingSystem {
                 position_size: trade.size,
                 pnl: trade.realized_pnl,
                 roi: trade.roi,
-                contributing_agents: Vec::new(),
-                agent_confidence: HashMap::new(),
-                strategy: String::from("default"),
+                r_multiple: trade.r_multiple,
+                contributing_agents: trade.agent_contributions.iter().map(|c| c.agent.clone()).collect(),
+                agent_confidence: trade.agent_contributions.iter().map(|c| (c.agent.clone(), c.confidence / 100.0)).collect(),
+                agent_contributions: trade.agent_contributions.clone(),
+                strategy: trade.source.clone(),
                 tags: Vec::new(),
                 metadata: HashMap::new(),
+                market_conditions: trade.market_conditions.clone(),
             };
             self.memory_node.store_trade(memory_trade)?;
 
+            // Reinforce the contributing agents; eligible ones are mutated
+            if let Some(memory) = self.memory_node.get_trade_memory(&trade.id) {
+                let feedback = self.memory_node.generate_reinforcement(memory);
+                for message in self.feedback_loop.process_trade(memory, &feedback)? {
+                    self.message_bus.send(message);
+                }
+            }
+
+            // Attribute the result to the originating strategy
+            let attribution = TradeAttribution {
+                trade_id: trade.id.clone(),
+                strategy: trade.source.clone(),
+                symbol: trade.symbol.clone(),
+                pnl: pnl.net_pnl,
+                roi: pnl.return_percent,
+                gross_pnl: pnl.gross_pnl,
+                fees: pnl.fees,
+                funding: pnl.funding,
+                entry_time: trade.entry_time,
+                exit_time: trade.exit_time.unwrap_or_else(Utc::now),
+            };
+            self.performance_monitor.record_trade(attribution.clone());
+
+            // Watch for churn, repeated stop-outs and fee burn; the feedback loop disables offenders
+            let stopped_out = match trade.direction {
+                TradeDirection::Long => exit_price <= trade.stop_loss_price,
+                TradeDirection::Short => exit_price >= trade.stop_loss_price,
+                _ => false,
+            };
+            for alert in self.surveillance.record_trade(&attribution, stopped_out) {
+                for message in self.feedback_loop.process_surveillance_alert(&alert) {
+                    self.message_bus.send(message);
+                }
+            }
+
             // Add to trade history
             self.trade_history.push_back(trade);
 
@@ -881,7 +2481,17 @@ impl TradingSystem {
             // Update state
             self.state.active_trades_count = self.active_trades.len();
             self.state.completed_trades_count += 1;
-            self.state.current_capital += realized_pnl;
+            self.state.current_capital += pnl.net_pnl;
+
+            // Cut or restore sizing on every sizing path as the equity curve moves
+            if self.drawdown_throttle.update(self.state.current_capital) {
+                let multiplier = self.drawdown_throttle.get_multiplier();
+                self.agent_coordinator.get_risk_manager_mut().set_drawdown_multiplier(multiplier);
+                self.compound_controller.set_drawdown_multiplier(multiplier);
+            }
+
+            // Halt the whole system once the drawdown reaches the circuit breaker
+            self.update_circuit_breaker(self.current_equity());
 
             // Update compound controller
             self.compound_controller.update_capital(self.state.current_capital);
@@ -891,6 +2501,186 @@ impl TradingSystem {
         Ok(())
     }
 
+    /// Realized capital plus the unrealized P&L of the open trades
+    fn current_equity(&self) -> f64 {
+        self.state.current_capital
+            + self.active_trades.values().map(|trade| trade.unrealized_pnl).sum::<f64>()
+    }
+
+    /// Feed the circuit breaker the latest equity and publish a halt
+    fn update_circuit_breaker(&mut self, equity: f64) {
+        match self.agent_coordinator.get_risk_manager_mut().update_equity(equity) {
+            Ok(true) => {
+                let state_machine = self.agent_coordinator.get_risk_manager().get_state_machine();
+                let reason = state_machine.read().ok().and_then(|m| m.get_halt_reason().map(str::to_string));
+                self.message_bus.send(Message::Custom(
+                    CIRCUIT_BREAKER_TOPIC.to_string(),
+                    serde_json::json!({
+                        "state": "halted",
+                        "reason": reason,
+                        "equity": equity,
+                    }),
+                ));
+            }
+            Ok(false) => {}
+            Err(e) => warn!("Circuit breaker could not halt the system: {}", e),
+        }
+    }
+
+    /// Close leveraged trades ahead of scheduled events and widen stops through volatility spikes
+    async fn apply_gap_protection(&mut self, now: DateTime<Utc>) -> Result<()> {
+        let to_close: Vec<String> = self.active_trades.values()
+            .filter(|trade| self.gap_protection.should_close(trade.leverage, now))
+            .map(|trade| trade.id.clone())
+            .collect();
+        if !to_close.is_empty() {
+            let event = self.gap_protection.active_event(now).map(|e| e.name.clone()).unwrap_or_default();
+            warn!("Closing {} trade(s) ahead of {}", to_close.len(), event);
+        }
+        for trade_id in to_close {
+            if let Some(trade) = self.active_trades.get(&trade_id) {
+                let current_price = self.get_current_price(&trade.symbol).unwrap_or(trade.entry_price);
+                self.close_trade(&trade_id, current_price).await?;
+            }
+        }
+
+        // Widen each trade's stop once per spike so noise does not take it out, and restore it after
+        let trade_ids: Vec<String> = self.active_trades.keys().cloned().collect();
+        for trade_id in trade_ids {
+            let Some(trade) = self.active_trades.get_mut(&trade_id) else {
+                continue;
+            };
+            let spiking = self.gap_protection.is_volatility_spike(&trade.symbol);
+            let widened_from = trade.metadata.get("stop_widened").and_then(|s| s.parse::<f64>().ok());
+            let new_stop = match (spiking, widened_from) {
+                (true, None) => {
+                    let new_stop = self.gap_protection.widened_stop(trade.entry_price, trade.stop_loss_price);
+                    info!("Widening stop of {} on {} from ${:.2} to ${:.2} during volatility spike",
+                          trade.id, trade.symbol, trade.stop_loss_price, new_stop);
+                    trade.metadata.insert("stop_widened".to_string(), trade.stop_loss_price.to_string());
+                    new_stop
+                },
+                (false, Some(original_stop)) => {
+                    trade.metadata.remove("stop_widened");
+                    // Keep any tightening made since, e.g. by the trailing stop
+                    let restored = match trade.direction {
+                        TradeDirection::Short => original_stop.min(trade.stop_loss_price),
+                        _ => original_stop.max(trade.stop_loss_price),
+                    };
+                    if restored == trade.stop_loss_price {
+                        continue;
+                    }
+                    info!("Restoring stop of {} on {} from ${:.2} to ${:.2} after volatility spike",
+                          trade.id, trade.symbol, trade.stop_loss_price, restored);
+                    restored
+                },
+                _ => continue,
+            };
+
+            trade.stop_loss_price = new_stop;
+            let symbol = trade.symbol.clone();
+            if let Some(position_id) = trade.metadata.get("position_id").cloned() {
+                if let Err(e) = self.position_manager.set_position_stop_loss(&position_id, new_stop) {
+                    warn!("Failed to move stop of position {}: {}", position_id, e);
+                }
+            }
+            if self.state.mode == TradingMode::Live {
+                if let Err(e) = self.exchange.amend_stop(&symbol, new_stop).await {
+                    warn!("Failed to amend exchange stop for {}: {}", symbol, e);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Tighten the stops of the open trades on a symbol a whale is active on, once per active window
+    async fn tighten_stops_for_whale(&mut self, symbol: &str) {
+        let Some(current_price) = self.get_current_price(symbol) else {
+            return;
+        };
+        let trade_ids: Vec<String> = self.active_trades.values()
+            .filter(|trade| trade.symbol == symbol)
+            .map(|trade| trade.id.clone())
+            .collect();
+
+        for trade_id in trade_ids {
+            let new_stop = {
+                let risk_manager = self.agent_coordinator.get_risk_manager();
+                let Some(trade) = self.active_trades.get_mut(&trade_id) else {
+                    continue;
+                };
+                if trade.metadata.contains_key("whale_tightened") {
+                    continue;
+                }
+                // Only move stops that are still on the losing side of the price, and only closer
+                let new_stop = risk_manager.tightened_stop(current_price, trade.stop_loss_price);
+                let tighter = match trade.direction {
+                    TradeDirection::Long => trade.stop_loss_price < current_price && new_stop > trade.stop_loss_price,
+                    TradeDirection::Short => trade.stop_loss_price > current_price && new_stop < trade.stop_loss_price,
+                };
+                if !tighter {
+                    continue;
+                }
+                info!("Tightening stop of {} on {} from ${:.2} to ${:.2} while a whale is active",
+                      trade.id, trade.symbol, trade.stop_loss_price, new_stop);
+                trade.metadata.insert("whale_tightened".to_string(), trade.stop_loss_price.to_string());
+                trade.metadata.insert("whale_stop".to_string(), new_stop.to_string());
+                trade.stop_loss_price = new_stop;
+                self.zero_loss_enforcer.sync_stop(&trade.id, new_stop);
+                if let Some(position_id) = trade.metadata.get("position_id").cloned() {
+                    if let Err(e) = self.position_manager.set_position_stop_loss(&position_id, new_stop) {
+                        warn!("Failed to tighten stop of position {}: {}", position_id, e);
+                    }
+                }
+                new_stop
+            };
+            if self.state.mode == TradingMode::Live {
+                if let Err(e) = self.exchange.amend_stop(symbol, new_stop).await {
+                    warn!("Failed to amend exchange stop for {}: {}", symbol, e);
+                }
+            }
+        }
+    }
+
+    /// Restore the stops tightened for a whale once its activity has ended
+    ///
+    /// A stop moved again since the whale tightened it (e.g. by the trailing stop) is kept.
+    async fn restore_stops_after_whale(&mut self, now: DateTime<Utc>) {
+        let trade_ids: Vec<String> = self.active_trades.values()
+            .filter(|trade| trade.metadata.contains_key("whale_tightened"))
+            .filter(|trade| !self.agent_coordinator.get_risk_manager().is_whale_active(&trade.symbol, now))
+            .map(|trade| trade.id.clone())
+            .collect();
+
+        for trade_id in trade_ids {
+            let Some(trade) = self.active_trades.get_mut(&trade_id) else {
+                continue;
+            };
+            let original_stop = trade.metadata.remove("whale_tightened").and_then(|s| s.parse::<f64>().ok());
+            let whale_stop = trade.metadata.remove("whale_stop").and_then(|s| s.parse::<f64>().ok());
+            let Some(original_stop) = original_stop.filter(|_| whale_stop == Some(trade.stop_loss_price)) else {
+                continue;
+            };
+
+            info!("Restoring stop of {} on {} from ${:.2} to ${:.2} now the whale is gone",
+                  trade.id, trade.symbol, trade.stop_loss_price, original_stop);
+            trade.stop_loss_price = original_stop;
+            let symbol = trade.symbol.clone();
+            self.zero_loss_enforcer.sync_stop(&trade_id, original_stop);
+            if let Some(position_id) = trade.metadata.get("position_id").cloned() {
+                if let Err(e) = self.position_manager.set_position_stop_loss(&position_id, original_stop) {
+                    warn!("Failed to restore stop of position {}: {}", position_id, e);
+                }
+            }
+            if self.state.mode == TradingMode::Live {
+                if let Err(e) = self.exchange.amend_stop(&symbol, original_stop).await {
+                    warn!("Failed to amend exchange stop for {}: {}", symbol, e);
+                }
+            }
+        }
+    }
+
     /// Close all trades
     async fn close_all_trades(&mut self) -> Result<()> {
         let trade_ids: Vec<String> = self.active_trades.keys().cloned().collect();
@@ -948,16 +2738,507 @@ impl TradingSystem {
         self.state.completed_trades_count > 0 && self.state.completed_trades_count % 100 == 0
     }
 
+    /// Cached candles of every asset on the fastest timeframe
+    fn cached_candles(&self) -> Vec<(String, Vec<Candle>)> {
+        let timeframe = self.config.timeframes.iter().min().copied().unwrap_or(1);
+
+        self.config.assets.iter()
+            .filter_map(|symbol| {
+                let cache = self.market_data_cache.get(symbol)?.get(&timeframe)?;
+                let candles = cache.iter()
+                    .map(|data| Candle {
+                        open_time: data.timestamp.timestamp_millis(),
+                        open: data.open,
+                        high: data.high,
+                        low: data.low,
+                        close: data.close,
+                        volume: data.volume,
+                    })
+                    .collect();
+                Some((symbol.clone(), candles))
+            })
+            .collect()
+    }
+
+    /// Generate trade signals from active strategies using cached candles
+    fn process_strategies(&mut self) -> Result<()> {
+        let candles_by_symbol = self.cached_candles();
+        let market_regimes = self.market_regimes.clone();
+
+        let mut signals = Vec::new();
+        for strategy in self.strategy_registry.active_mut() {
+            for (symbol, candles) in &candles_by_symbol {
+                if candles.len() < strategy.min_candles() {
+                    continue;
+                }
+                if let (Some(regimes), Some(regime)) = (strategy.market_regimes(), market_regimes.get(symbol)) {
+                    if !regimes.contains(regime) {
+                        continue;
+                    }
+                }
+
+                match strategy.analyze(symbol, candles) {
+                    Ok(signal) => signals.push(signal),
+                    Err(e) => warn!("Strategy {} failed for {}: {}", strategy.get_name(), symbol, e),
+                }
+            }
+        }
+
+        for signal in signals {
+            let direction = match signal.decision_type {
+                DecisionType::EnterLong | DecisionType::Buy => TradeDirection::Long,
+                DecisionType::EnterShort | DecisionType::Sell => TradeDirection::Short,
+                _ => continue,
+            };
+            let is_long = matches!(direction, TradeDirection::Long);
+            let price = signal.entry_price;
+
+            self.message_bus.send(Message::TradeSignal {
+                symbol: signal.symbol,
+                direction,
+                confidence: signal.confidence / 100.0,
+                entry_price: price,
+                stop_loss_price: signal.stop_loss.unwrap_or(if is_long { price * 0.99 } else { price * 1.01 }),
+                take_profit_price: signal.take_profit.unwrap_or(if is_long { price * 1.02 } else { price * 0.98 }),
+                source: signal.strategy,
+                timestamp: signal.timestamp,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Hand a symbol's new market regime to the risk manager and the strategies
+    fn apply_market_regime(&mut self, change: &MarketRegimeChange) {
+        self.market_regimes.insert(change.symbol.clone(), change.to);
+        self.agent_coordinator.get_risk_manager_mut().apply_market_regime(change);
+        for strategy in self.strategy_registry.active_mut() {
+            strategy.on_regime_change(change);
+        }
+    }
+
+    /// Get the latest market regime of a symbol
+    pub fn get_market_regime(&self, symbol: &str) -> Option<MarketRegime> {
+        self.market_regimes.get(symbol).copied()
+    }
+
+    /// Classify the volatility regime of the primary asset and apply confirmed changes
+    fn update_regime(&mut self) {
+        let timeframe = self.config.timeframes.iter().min().copied().unwrap_or(1);
+        let closes: Vec<f64> = match self.config.assets.first()
+            .and_then(|symbol| self.market_data_cache.get(symbol))
+            .and_then(|cache| cache.get(&timeframe))
+        {
+            Some(cache) => cache.iter().map(|data| data.close).collect(),
+            None => return,
+        };
+
+        let change = match self.regime_classifier.update(&closes) {
+            Some(change) => change,
+            None => return,
+        };
+
+        self.agent_coordinator.get_risk_manager_mut().set_regime(change.to);
+
+        for control in self.regime_classifier.strategy_controls(change.to, &self.strategy_registry) {
+            if let Err(e) = self.strategy_registry.apply(&control) {
+                warn!("Failed to apply regime control {:?}: {}", control, e);
+            }
+        }
+    }
+
+    /// Set the volatility regime configuration
+    pub fn set_regime_config(&mut self, config: RegimeConfig) {
+        self.regime_classifier = RegimeClassifier::new(config);
+    }
+
+    /// Get the current volatility regime
+    pub fn get_regime(&self) -> VolatilityRegime {
+        self.regime_classifier.get_regime()
+    }
+
+    /// Set the correlation filter configuration (clears price history)
+    pub fn set_correlation_config(&mut self, config: CorrelationFilterConfig) {
+        self.correlation_analyzer = CorrelationAnalyzer::new(config);
+    }
+
+    /// Set the risk budget of a registered strategy
+    pub fn set_strategy_budget(&mut self, strategy: &str, budget: StrategyRiskBudget) {
+        self.agent_coordinator.get_risk_manager_mut().set_strategy_budget(strategy, budget);
+    }
+
+    /// Get the correlation analyzer
+    pub fn get_correlation_analyzer(&self) -> &CorrelationAnalyzer {
+        &self.correlation_analyzer
+    }
+
+    /// Close open trades of draining strategies and complete the drain once flat
+    async fn drain_strategies(&mut self) -> Result<()> {
+        for strategy in self.strategy_registry.draining() {
+            let trade_ids: Vec<String> = self.active_trades.values()
+                .filter(|trade| trade.source == strategy)
+                .map(|trade| trade.id.clone())
+                .collect();
+
+            for trade_id in trade_ids {
+                if let Some(trade) = self.active_trades.get(&trade_id) {
+                    let current_price = self.get_current_price(&trade.symbol).unwrap_or(trade.entry_price);
+                    info!("Draining trade {} of strategy {}", trade_id, strategy);
+                    self.close_trade(&trade_id, current_price).await?;
+                }
+            }
+
+            if self.position_manager.get_positions_by_strategy(&strategy).is_empty() {
+                self.strategy_registry.complete_drain(&strategy);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Register a strategy to run inside the trading system
+    pub fn register_strategy(&mut self, strategy: Box<dyn Strategy>) -> Result<()> {
+        self.strategy_registry.register(strategy)
+    }
+
+    /// Run a candidate agent in shadow mode: it sees live data, but its decisions are only scored
+    ///
+    /// The factory is given the bus the agent publishes on; once the god kernel
+    /// promotes the candidate it is built again on the live bus.
+    pub async fn add_shadow_agent(&mut self, factory: ShadowAgentFactory) -> Result<String> {
+        // The candidate's name is only known once it is built
+        let name = factory(Arc::new(MessageBus::new())).get_name().to_string();
+        let context = Arc::new(RwLock::new(AgentContext::new(name)));
+        self.god_kernel.add_shadow_agent(factory, context).await
+    }
+
+    /// Register a strategy the god kernel may breed shadow challengers from
+    ///
+    /// The feedback loop may also mutate its parameters within `bounds`; an adopted
+    /// mutation rebuilds the strategy with the builder and replaces it once drained.
+    pub fn register_evolvable_strategy(
+        &mut self,
+        name: &str,
+        parameters: HashMap<String, f64>,
+        bounds: HashMap<String, ParameterRange>,
+        builder: StrategyBuilder,
+    ) -> Result<()> {
+        self.strategy_registry.register(builder(name, &parameters)?)?;
+        if !bounds.is_empty() {
+            self.declare_mutation_bounds(name, bounds, parameters.clone());
+        }
+        self.god_kernel.register_strategy_builder(name, parameters, builder)
+    }
+
+    /// Set the backtest the scheduled mutation evaluations run with
+    pub fn set_mutation_evaluator(&mut self, evaluator: MutationEvaluator) {
+        self.mutation_evaluator = Some(evaluator);
+    }
+
+    /// Rebuild a strategy with adopted parameters and replace the running one once it drains
+    fn adopt_mutation(&mut self, strategy: &str, parameters: &ParameterSet) -> Result<()> {
+        let replacement = self.god_kernel.rebuild_strategy(strategy, parameters)?;
+        let replacement_name = replacement.get_name();
+        self.strategy_registry.stage(replacement);
+        self.strategy_registry.apply(&StrategyControl::Replace {
+            strategy: strategy.to_string(),
+            replacement: replacement_name,
+        })?;
+        info!("Adopted mutated parameters for {}: {:?}", strategy, parameters);
+        Ok(())
+    }
+
+    /// Declare the parameter bounds the feedback loop may mutate a strategy within
+    pub fn declare_mutation_bounds(&mut self, strategy: &str, bounds: HashMap<String, ParameterRange>, current: ParameterSet) {
+        self.feedback_loop.declare_parameters(strategy, bounds, current);
+    }
+
+    /// Backtest the scheduled mutations against their parents on every asset
+    ///
+    /// `evaluate` backtests the named strategy with the job's parameters. Adopted
+    /// mutations are announced on the message bus.
+    pub fn run_mutation_evaluations<F>(&mut self, evaluate: F) -> Result<Vec<AbOutcome>>
+    where
+        F: Fn(&str, &BacktestJob) -> Result<BacktestResult> + Send + Sync,
+    {
+        let outcomes = self.feedback_loop.run_ab_evaluations(&ParallelBacktester::new(), &self.config.assets, evaluate)?;
+        self.announce_adopted_mutations(&outcomes);
+        Ok(outcomes)
+    }
+
+    /// Run the scheduled A/B evaluations on the blocking thread pool so the backtests
+    /// do not stall the update loop; failed evaluations stay scheduled
+    async fn run_mutation_evaluations_blocking(&mut self, evaluate: MutationEvaluator) -> Result<Vec<AbOutcome>> {
+        let evaluations = self.feedback_loop.take_pending_evaluations();
+        if evaluations.is_empty() || self.config.assets.is_empty() {
+            self.feedback_loop.restore_pending_evaluations(evaluations);
+            return Ok(Vec::new());
+        }
+
+        let jobs = evaluations.clone();
+        let symbols = self.config.assets.clone();
+        let backtested = tokio::task::spawn_blocking(move || {
+            FeedbackLoop::backtest_evaluations(&jobs, &ParallelBacktester::new(), &symbols, |name, job| evaluate(name, job))
+        }).await;
+        let fitness = match backtested {
+            Ok(Ok(fitness)) => fitness,
+            Ok(Err(e)) => {
+                self.feedback_loop.restore_pending_evaluations(evaluations);
+                return Err(e);
+            }
+            Err(e) => {
+                self.feedback_loop.restore_pending_evaluations(evaluations);
+                return Err(anyhow::anyhow!("Mutation backtests panicked: {}", e));
+            }
+        };
+
+        let outcomes = self.feedback_loop.apply_ab_results(evaluations, fitness)?;
+        self.announce_adopted_mutations(&outcomes);
+        Ok(outcomes)
+    }
+
+    /// Announce adopted mutations on the message bus
+    fn announce_adopted_mutations(&self, outcomes: &[AbOutcome]) {
+        for outcome in outcomes.iter().filter(|o| o.adopted) {
+            self.message_bus.send(Message::Custom(
+                "mutation_adopted".to_string(),
+                serde_json::json!({
+                    "agent_name": outcome.agent,
+                    "mutation_id": outcome.mutation_id,
+                    "parameters": self.feedback_loop.get_parameters(&outcome.agent),
+                    "fitness": outcome.candidate_fitness,
+                }),
+            ));
+        }
+    }
+
+    /// Stage a strategy that a later `StrategyControl::Replace` message can swap in
+    pub fn stage_strategy(&mut self, strategy: Box<dyn Strategy>) {
+        self.strategy_registry.stage(strategy);
+    }
+
+    /// Get the strategy registry
+    pub fn get_strategy_registry(&self) -> &StrategyRegistry {
+        &self.strategy_registry
+    }
+
+    /// Get the per-strategy performance monitor
+    pub fn get_performance_monitor(&self) -> &PerformanceMonitor {
+        &self.performance_monitor
+    }
+
+    /// How the closed trades entered in conditions most like the current ones on `symbol` resolved
+    pub fn get_similar_setup_outcomes(&self, symbol: &str, k: usize) -> SetupOutcomes {
+        self.memory_node.similar_setup_outcomes(&self.market_conditions(symbol), k)
+    }
+
+    /// Get the audit trail of the zero-loss enforcer's protective actions and vetoes
+    pub fn get_zero_loss_audit_trail(&self) -> &AuditTrail {
+        self.zero_loss_enforcer.get_audit_trail()
+    }
+
+    /// Get the rolling trust score of every agent that contributed to a closed trade
+    pub fn get_agent_trust_scores(&self) -> HashMap<String, AgentTrust> {
+        self.memory_node.get_trust_scores()
+    }
+
+    /// Get the risk manager, whose limits entries placed outside the system must respect too
+    pub fn get_risk_manager(&self) -> &RiskManager {
+        self.agent_coordinator.get_risk_manager()
+    }
+
+    /// Get the position manager
+    pub fn get_position_manager(&self) -> &PositionManager {
+        &self.position_manager
+    }
+
+    /// Get the portfolio exposure view
+    pub fn get_portfolio(&self) -> &Portfolio {
+        &self.portfolio
+    }
+
+    /// Get the portfolio exposure view for configuration (sectors, leverage)
+    pub fn get_portfolio_mut(&mut self) -> &mut Portfolio {
+        &mut self.portfolio
+    }
+
+    /// Get the capital managers by settle coin
+    pub fn get_collateral_manager(&self) -> &CollateralManager {
+        &self.collateral_manager
+    }
+
+    /// Get the capital managers for configuration (collateral, tranches, rebalancing)
+    pub fn get_collateral_manager_mut(&mut self) -> &mut CollateralManager {
+        &mut self.collateral_manager
+    }
+
+    /// Record a settle coin's capital changes in a ledger, restoring the tranches it already holds
+    pub fn open_capital_ledger(&mut self, settle_coin: &str, initial_capital: f64, store: Box<dyn LedgerStore>) -> Result<()> {
+        let manager = CapitalManager::for_settle_coin_with_ledger(settle_coin, initial_capital, store)?;
+        self.collateral_manager.replace_collateral(manager);
+        Ok(())
+    }
+
+    /// Refresh the USDT rates of the non-USDT collateral from the exchange
+    pub async fn refresh_collateral_rates(&mut self) -> Result<()> {
+        self.collateral_manager.refresh_rates(&self.exchange).await
+    }
+
+    /// Refresh the collateral rates once they are older than the refresh interval
+    async fn refresh_collateral_rates_if_due(&mut self, now: DateTime<Utc>) {
+        if self.state.mode == TradingMode::Backtesting {
+            return;
+        }
+        let due = self.collateral_manager.get_rates_updated()
+            .map_or(true, |updated| (now - updated).num_seconds() >= COLLATERAL_RATE_REFRESH_SECS);
+        if due {
+            if let Err(e) = self.refresh_collateral_rates().await {
+                warn!("Collateral rate refresh incomplete: {}", e);
+            }
+        }
+    }
+
+    /// Set the drawdown throttle thresholds
+    pub fn set_drawdown_throttle_config(&mut self, config: DrawdownThrottleConfig) {
+        self.drawdown_throttle = DrawdownThrottle::new(config, self.state.current_capital);
+        self.agent_coordinator.get_risk_manager_mut().set_drawdown_multiplier(1.0);
+        self.compound_controller.set_drawdown_multiplier(1.0);
+    }
+
+    /// Get the drawdown throttle
+    pub fn get_drawdown_throttle(&self) -> &DrawdownThrottle {
+        &self.drawdown_throttle
+    }
+
+    /// Get the capital allocation across settle coins and strategy tranches
+    pub fn get_capital_report(&self) -> CollateralReport {
+        self.collateral_manager.report()
+    }
+
+    /// Set the position sizer of a strategy
+    pub fn set_sizer(&mut self, strategy: &str, sizer: Box<dyn Sizer>) {
+        self.sizers.set_sizer(strategy, sizer);
+    }
+
+    /// Set the position sizer of strategies without their own
+    pub fn set_default_sizer(&mut self, sizer: Box<dyn Sizer>) {
+        self.sizers.set_default(sizer);
+    }
+
+    /// Apply an event to the system state machine
+    fn handle_state_event(&self, event: Event) -> Result<State> {
+        self.agent_coordinator.get_risk_manager().get_state_machine().write()
+            .map_err(|_| anyhow::anyhow!("System state machine is poisoned"))?
+            .handle(event)
+    }
+
+    /// Current system state
+    pub fn get_system_state(&self) -> State {
+        self.agent_coordinator.get_risk_manager().get_state_machine().read()
+            .map(|m| m.get_state())
+            .unwrap_or(State::Halted)
+    }
+
+    /// Resume trading after the circuit breaker halted it
+    pub fn reset_circuit_breaker(&mut self) -> Result<()> {
+        let equity = self.state.current_capital;
+        self.agent_coordinator.get_risk_manager_mut().reset_circuit_breaker(equity)?;
+        self.message_bus.send(Message::Custom(
+            CIRCUIT_BREAKER_TOPIC.to_string(),
+            serde_json::json!({ "state": "reset", "equity": equity }),
+        ));
+        Ok(())
+    }
+
+    /// Latest VaR and CVaR of the open book, for the dashboard
+    pub fn get_portfolio_var(&self) -> Option<PortfolioVar> {
+        self.agent_coordinator.get_risk_manager().get_portfolio_var().cloned()
+    }
+
+    /// Health of the agents from their latest heartbeats, for the dashboard
+    pub fn get_system_health(&self) -> SystemHealth {
+        self.health_checker.check(Utc::now())
+    }
+
+    /// Risk report of one UTC day from the recorded snapshots
+    pub fn generate_risk_report(&self, date: NaiveDate) -> DailyRiskReport {
+        DailyRiskReport::new(date, &self.risk_history)
+    }
+
+    /// Latest stress test of the portfolio
+    pub fn get_stress_result(&self) -> Option<StressTestResult> {
+        self.zero_loss_enforcer.get_stress_result().cloned()
+    }
+
+    /// Replace the schedule of high-impact events the gap protection trades around
+    pub fn set_scheduled_events(&mut self, events: Vec<ScheduledEvent>) {
+        self.gap_protection.set_events(events);
+    }
+
+    /// Record the top of book of a symbol for the spread veto
+    pub fn update_quote(&mut self, symbol: &str, bid: f64, ask: f64) {
+        self.gap_protection.update_spread(symbol, bid, ask);
+    }
+
+    /// Liquidation proximity alerts raised since start
+    pub fn get_liquidation_alerts(&self) -> Vec<LiquidationAlert> {
+        self.liquidation_monitor.get_alerts().to_vec()
+    }
+
+    /// Surveillance alerts raised since start
+    pub fn get_surveillance_alerts(&self) -> Vec<SurveillanceAlert> {
+        self.surveillance.get_alerts().to_vec()
+    }
+
+    /// Most recent pre-trade rejections, oldest first
+    pub fn get_pre_trade_rejections(&self) -> Vec<PreTradeRejection> {
+        self.pre_trade.get_rejections().into_iter().cloned().collect()
+    }
+
+    /// Get the errors reported by the agent supervisor
+    pub fn get_error_manager(&self) -> &UnifiedErrorManager {
+        &self.error_manager
+    }
+
+    /// Snapshot net/gross, per-symbol and per-sector exposure and margin usage
+    pub fn get_portfolio_exposure(&self) -> PortfolioExposure {
+        let external = self.agent_coordinator.get_risk_manager().get_external_positions();
+        self.portfolio.exposure_of(self.position_manager.get_all_positions().into_iter().chain(&external), self.state.current_capital)
+    }
+
     /// Get active trades
     pub fn get_active_trades(&self) -> Vec<Trade> {
         self.active_trades.values().cloned().collect()
     }
 
+    /// Move stops to entry plus fees once trades advance the rule's R multiple
+    pub fn set_break_even_rule(&mut self, rule: Option<BreakEvenRule>) {
+        self.position_manager.set_break_even_rule(rule);
+    }
+
+    /// Cap how long trades from a signal source (e.g. an agent) may stay open
+    pub fn set_holding_policy(&mut self, source: &str, policy: Option<HoldingPolicy>) {
+        self.position_manager.set_holding_policy(source, policy);
+    }
+
     /// Get trade history
     pub fn get_trade_history(&self) -> Vec<Trade> {
         self.trade_history.iter().cloned().collect()
     }
 
+    /// Get the trade journal (closed trades, then active ones) in the common export schema
+    pub fn get_trade_records(&self) -> Vec<TradeRecord> {
+        self.trade_history.iter()
+            .chain(self.active_trades.values())
+            .map(TradeRecord::from)
+            .collect()
+    }
+
+    /// Export the trade journal as CSV or JSON depending on the file extension
+    pub fn export_trades(&self, path: impl AsRef<std::path::Path>) -> Result<()> {
+        export_trades(path, &self.get_trade_records())
+    }
+
     /// Get capital
     pub fn get_capital(&self) -> f64 {
         self.state.current_capital
@@ -991,3 +3272,132 @@ impl TradingSystem {
         &self.config
     }
 }
+
+/// Bus message the agents receive for a message read by the trading loop
+///
+/// Trade signals go out on `signal.<symbol>`, custom events on `agent.<event>`.
+fn to_bus_message(message: &Message) -> BusMessage {
+    let (message_type, topic) = match message {
+        Message::TradeSignal { symbol, .. } => (MessageType::TradeSignal, format!("signal.{}", symbol)),
+        Message::Custom(event, _) => (MessageType::AgentCommunication, format!("agent.{}", event)),
+        _ => (MessageType::AgentCommunication, "agent".to_string()),
+    };
+    BusMessage {
+        message_type,
+        topic,
+        content: serde_json::to_string(message).unwrap_or_default(),
+        timestamp: Utc::now(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use async_trait::async_trait;
+    use crate::engine::agent_trait::AgentConfig;
+
+    struct ListeningAgent {
+        received: Arc<AtomicUsize>,
+    }
+
+    #[async_trait]
+    impl Agent for ListeningAgent {
+        fn get_name(&self) -> &str {
+            "listener"
+        }
+
+        fn get_config(&self) -> Box<dyn AgentConfig> {
+            Box::new(())
+        }
+
+        fn topics(&self) -> Vec<String> {
+            vec!["agent.#".to_string()]
+        }
+
+        async fn initialize(&mut self, _context: Arc<RwLock<AgentContext>>) -> Result<()> {
+            Ok(())
+        }
+
+        async fn start(&mut self) -> Result<()> {
+            Ok(())
+        }
+
+        async fn stop(&mut self) -> Result<()> {
+            Ok(())
+        }
+
+        async fn tick(&mut self) -> Result<()> {
+            Ok(())
+        }
+
+        async fn handle_message(&mut self, message: BusMessage) -> Result<()> {
+            assert!(matches!(message.message_type, MessageType::AgentCommunication));
+            self.received.fetch_add(1, Ordering::SeqCst);
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_bus_messages_reach_registered_agents() {
+        let mut system = TradingSystem::new(TradingSystemConfig {
+            mode: TradingMode::Simulation,
+            ..TradingSystemConfig::default()
+        });
+        let received = Arc::new(AtomicUsize::new(0));
+        let agent = ListeningAgent { received: Arc::clone(&received) };
+        let context = Arc::new(RwLock::new(AgentContext::new("listener".to_string())));
+        system.agent_coordinator.spawn_agent(Box::new(agent), context).await.unwrap();
+
+        system.message_bus.send(Message::Custom("test_event".to_string(), serde_json::Value::Null));
+        system.process_messages().await.unwrap();
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+
+        assert_eq!(received.load(Ordering::SeqCst), 1);
+        system.agent_coordinator.stop_agents().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_draining_strategy_opens_nothing_and_disables_once_flat() {
+        use crate::strategy::registry::{StrategyControl, StrategyStatus};
+        use crate::strategy::simple_strategy::Candle;
+        use crate::strategy::strategy_trait::{Strategy, StrategySignal};
+
+        struct Trend;
+
+        impl Strategy for Trend {
+            fn get_name(&self) -> String {
+                "trend".to_string()
+            }
+
+            fn analyze(&mut self, symbol: &str, candles: &[Candle]) -> Result<StrategySignal> {
+                Ok(StrategySignal::hold("trend", symbol, candles.last().map_or(0.0, |c| c.close), ""))
+            }
+        }
+
+        let mut system = TradingSystem::new(TradingSystemConfig {
+            mode: TradingMode::Simulation,
+            ..TradingSystemConfig::default()
+        });
+        system.register_strategy(Box::new(Trend)).unwrap();
+        system.strategy_registry.apply(&StrategyControl::Disable { strategy: "trend".to_string() }).unwrap();
+
+        system.message_bus.send(Message::TradeSignal {
+            symbol: "BTCUSDT".to_string(),
+            direction: TradeDirection::Long,
+            confidence: 90.0,
+            entry_price: 100.0,
+            stop_loss_price: 98.0,
+            take_profit_price: 104.0,
+            source: "trend".to_string(),
+            timestamp: Utc::now(),
+        });
+        system.process_messages().await.unwrap();
+        assert!(system.active_trades.is_empty());
+        assert!(system.agent_coordinator.get_risk_manager_mut().next_queued_signal(Utc::now()).is_none());
+        assert_eq!(system.strategy_registry.status("trend"), Some(StrategyStatus::Draining));
+
+        system.drain_strategies().await.unwrap();
+        assert_eq!(system.strategy_registry.status("trend"), Some(StrategyStatus::Disabled));
+    }
+}